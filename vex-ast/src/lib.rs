@@ -280,8 +280,10 @@ pub struct Function {
     pub is_exported: bool, // ⭐ NEW: Export visibility
     #[serde(skip)]
     pub span_id: Option<String>, // ⭐ NEW: Source location ID
+    pub attributes: Vec<Attribute>, // #[inline(always)], #[deprecated("...")]
     pub is_async: bool,
     pub is_gpu: bool,
+    pub is_const: bool,    // const fn: callable from constant initializers
     pub is_mutable: bool,  // ⭐ NEW: Method-level mutability (fn method()!)
     pub is_operator: bool, // ⭐ NEW: Operator overload method (fn op+(...))
     pub is_static: bool,   // ⭐ NEW: Static method flag (fn Type.method())
@@ -298,6 +300,24 @@ pub struct Function {
     pub variadic_type: Option<Type>, // Type of variadic params: ...any, ...string
 }
 
+/// Attribute on an item, field or statement: #[inline(always)], #[deprecated("use parse")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    #[serde(skip)]
+    pub span_id: Option<String>,
+    pub name: String,
+    pub args: Vec<AttributeArg>, // Empty for #[cold]
+}
+
+/// Attribute argument: always, "reason", 16, note = "reason"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AttributeArg {
+    Ident(String),
+    Str(String),
+    Int(String),
+    KeyValue(String, String), // Value is always a string literal
+}
+
 /// Where clause predicate: T: Display, U: Clone + Debug, T.Item: Display
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WhereClausePredicate {
@@ -345,6 +365,7 @@ pub struct Struct {
     pub is_exported: bool, // ⭐ NEW: Export visibility
     #[serde(skip)]
     pub span_id: Option<String>, // ⭐ NEW: Source location ID
+    pub attributes: Vec<Attribute>, // #[must_use], #[deprecated("...")]
     pub name: String,
     pub type_params: Vec<TypeParam>, // Generic type parameters with bounds: <T: Display>
     pub const_params: Vec<(String, Type)>, // ⭐ NEW: Const params: (N, usize), (SIZE, i32)
@@ -361,8 +382,9 @@ pub struct Struct {
 pub struct Field {
     pub name: String,
     pub ty: Type,
-    pub tag: Option<String>,      // Go-style tags: `json:"id" db:"pk"`
-    pub metadata: Option<String>, // ⭐ NEW: Raw metadata string (parsed in compiler)
+    pub tag: Option<String>,        // Go-style tags: `json:"id" db:"pk"`
+    pub metadata: Option<String>,   // ⭐ NEW: Raw metadata string (parsed in compiler)
+    pub attributes: Vec<Attribute>, // #[deprecated("...")]
 }
 
 /// Type alias definition: type UserID = u64;
@@ -382,16 +404,18 @@ pub struct Enum {
     pub is_exported: bool, // ⭐ NEW: Export visibility
     #[serde(skip)]
     pub span_id: Option<String>, // ⭐ NEW: Source location ID
+    pub attributes: Vec<Attribute>, // #[must_use], #[deprecated("...")]
     pub name: String,
     pub type_params: Vec<TypeParam>, // Generic type parameters with bounds
     pub variants: Vec<EnumVariant>,
 }
 
-/// Enum variant - can be unit, single-value tuple, or multi-value tuple
+/// Enum variant - can be unit, single-value tuple, multi-value tuple, or struct-like
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: String,
     pub data: Vec<Type>, // Empty for unit variants, 1+ for tuple variants
+    pub field_names: Vec<String>, // Struct-like variants: names parallel to data, else empty
 }
 
 /// Constant declaration
@@ -524,12 +548,15 @@ pub enum Type {
     /// Array: [T; N]
     Array(Box<Type>, usize),
 
-    /// Array with const generic size: [T; N] where N is a const param
+    /// Array with const generic size: [T; N] or [T; R * C] over const params
     ConstArray {
         elem_type: Box<Type>,
-        size_param: String, // Name of const parameter: "N"
+        size: Box<Expression>, // Size expression: N, R * C
     },
 
+    /// Const generic argument: the 2 and 3 in Matrix<f32, 2, 3>
+    ConstValue(i64),
+
     /// Slice: &[T] or &mut [T]
     Slice(Box<Type>, bool), // bool = is_mutable
 
@@ -583,6 +610,10 @@ pub enum Type {
         is_const: bool,
     },
 
+    /// Contract object: dyn Shape - any value implementing the contract,
+    /// methods dispatched through a vtable
+    Dyn(String),
+
     /// ⭐ NEW: Associated type reference: Self::Item, Self::Output
     /// Used in trait definitions and implementations
     AssociatedType {
@@ -740,11 +771,12 @@ pub enum Statement {
         default_case: Option<Block>,
     },
 
-    /// Select statement (async)
+    /// Select statement (Go-style): wait on several channel operations
     Select {
         #[serde(skip)]
         span_id: Option<String>,
         cases: Vec<SelectCase>,
+        default_case: Option<Block>, // Non-blocking poll when present
     },
 
     /// Go statement (async)
@@ -761,6 +793,12 @@ pub enum Statement {
         block: Block,
     },
 
+    /// Statement with attributes: #[name(args)] stmt
+    Attributed {
+        attributes: Vec<Attribute>,
+        stmt: Box<Statement>,
+    },
+
     /// Expression statement
     Expression(Expression),
 }
@@ -772,17 +810,19 @@ pub struct SwitchCase {
     pub body: Block,
 }
 
-/// Select case (async)
+/// Select case: case v = <-ch: ... or case ch <- value: ...
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SelectCase {
-    pub var: Option<String>, // result variable
-    pub expr: Expression,    // await expression
+    pub var: Option<String>, // Receive binding (None for sends and bare receives)
+    pub expr: Expression,    // ChannelReceive(ch) or MethodCall { ch.send(value) }
     pub body: Block,
 }
 
 /// Match arm: pattern => expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchArm {
+    #[serde(skip)]
+    pub span_id: Option<String>, // Source location of the pattern
     pub pattern: Pattern,
     pub guard: Option<Expression>, // if guard
     pub body: Expression,
@@ -804,11 +844,12 @@ pub enum Pattern {
         name: String,
         fields: Vec<(String, Pattern)>,
     },
-    /// Enum variant: Some(x), None, V4(a, b, c, d)
+    /// Enum variant: Some(x), None, V4(a, b, c, d), Shape.Rect { w, h: height }
     Enum {
         name: String,
         variant: String,
-        data: Vec<Pattern>, // Empty for unit, 1+ for tuple variants
+        data: Vec<Pattern>,       // Empty for unit, 1+ for tuple variants
+        field_names: Vec<String>, // Struct-like variants: names parallel to data, else empty
     },
     /// Array/Slice pattern: [a, b, ..rest] or [x, y, z]
     Array {
@@ -817,6 +858,8 @@ pub enum Pattern {
     },
     /// Or pattern: 1 | 2 | 3 (for SIMD-optimized matching)
     Or(Vec<Pattern>),
+    /// Union member pattern: n: i32 or _: string
+    Type { binding: Option<String>, ty: Type },
 }
 
 /// Compound assignment operators
@@ -927,10 +970,12 @@ pub enum Expression {
     },
 
     /// Enum constructor: Result.Ok(42) or Option.None or IpAddr.V4(127, 0, 0, 1)
+    /// or Shape.Rect { w: 2.0, h: 3.0 }
     EnumLiteral {
         enum_name: String,
         variant: String,
-        data: Vec<Expression>, // Empty for unit, 1+ for tuple variants
+        data: Vec<Expression>,    // Empty for unit, 1+ for tuple variants
+        field_names: Vec<String>, // Struct-like variants: names parallel to data, else empty
     },
 
     /// Range: 0..10 (exclusive end), ..10 (from start), 5.. (to end), .. (full)
@@ -957,8 +1002,13 @@ pub enum Expression {
     /// Await: await expr
     Await(Box<Expression>),
 
+    /// Yield: yield expr (only in functions returning Generator<T>)
+    Yield(Box<Expression>),
+
     /// Match expression: match value { pattern => expr, ... }
     Match {
+        #[serde(skip)]
+        span_id: Option<String>, // `match value`, where exhaustiveness errors point
         value: Box<Expression>,
         arms: Vec<MatchArm>,
     },
@@ -998,6 +1048,12 @@ pub enum Expression {
         target_type: Type,
     },
 
+    /// Union member test: expr is Type
+    Is {
+        expr: Box<Expression>,
+        target_type: Type,
+    },
+
     /// Try operator: expr? (unwrap Result or propagate error)
     /// Desugars to: match expr { Ok(v) => v, Err(e) => return Err(e) }
     TryOp {
@@ -1099,6 +1155,36 @@ pub enum PostfixOp {
     PostDec, // i--
 }
 
+impl Attribute {
+    /// Reason given as `"text"`, `note = "text"` or `reason = "text"`
+    pub fn message(&self) -> Option<&str> {
+        self.args.iter().find_map(|arg| match arg {
+            AttributeArg::Str(text) => Some(text.as_str()),
+            AttributeArg::KeyValue(key, text) if key == "note" || key == "reason" => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+    }
+}
+
+/// First attribute with this name: find_attribute(&func.attributes, "inline")
+pub fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes.iter().find(|attr| attr.name == name)
+}
+
+impl EnumVariant {
+    /// Check if the variant declares named fields: Rect { w: f64, h: f64 }
+    pub fn is_struct_like(&self) -> bool {
+        !self.field_names.is_empty()
+    }
+
+    /// Position of a named field within the variant's payload
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.field_names.iter().position(|f| f == name)
+    }
+}
+
 impl Type {
     /// Check if type is a reference
     pub fn is_reference(&self) -> bool {
@@ -1120,6 +1206,16 @@ impl Type {
             _ => None,
         }
     }
+
+    /// Get the item type if this is Generator<T>
+    pub fn generator_item(&self) -> Option<&Type> {
+        match self {
+            Type::Generic { name, type_args } if name == "Generator" && type_args.len() == 1 => {
+                type_args.first()
+            }
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
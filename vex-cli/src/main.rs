@@ -767,6 +767,29 @@ fn main() -> Result<()> {
                 // return Err(anyhow::anyhow!("Contract enforcement failed"));
             }
 
+            // 🔍 Semantic type check: report E0308/E0599/E0609 before codegen
+            let mut type_checker = vex_compiler::TypeChecker::new(&span_map);
+            if type_checker.check_program(&ast).is_err() {
+                if json {
+                    println!("{}", type_checker.diagnostics().to_json());
+                } else {
+                    type_checker.diagnostics().print_all(&source);
+                    type_checker.diagnostics().print_summary();
+                }
+                return Err(anyhow::anyhow!("Type check failed"));
+            }
+            println!("   ✅ Type check passed");
+
+            // Attribute warnings (#[deprecated], #[must_use], unknown attributes)
+            let type_warning_count = type_checker.diagnostics().warning_count();
+            if type_warning_count > 0 {
+                if json {
+                    println!("{}", type_checker.diagnostics().to_json());
+                } else {
+                    type_checker.diagnostics().print_all(&source);
+                }
+            }
+
             let mut borrow_checker = vex_compiler::BorrowChecker::new();
             if let Err(borrow_error) = borrow_checker.check_program(&mut ast) {
                 // Convert borrow error to diagnostic
@@ -794,7 +817,7 @@ fn main() -> Result<()> {
 
             // Run linter for warnings
             let mut linter = vex_compiler::Linter::new();
-            let lint_warnings = linter.lint(&ast, &span_map);
+            let lint_warnings = linter.lint_typed(&ast, &span_map, type_checker.type_table());
 
             if !lint_warnings.is_empty() {
                 if json {
@@ -830,6 +853,8 @@ fn main() -> Result<()> {
             let mut codegen = vex_compiler::ASTCodeGen::new_with_source_file(
                 &context, filename, span_map, input_str,
             );
+            codegen.set_type_table(type_checker.into_type_table());
+            codegen.set_overflow_checks(opt_level == 0);
 
             // Compile the program
             let compile_result = codegen.compile_program(&ast);
@@ -862,7 +887,7 @@ fn main() -> Result<()> {
                 .iter()
                 .filter(|d| d.level == vex_compiler::ErrorLevel::Error)
                 .count();
-            let warning_count = lint_warnings.len();
+            let warning_count = lint_warnings.len() + type_warning_count;
 
             if !json && (error_count > 0 || warning_count > 0) {
                 eprintln!();
@@ -1459,6 +1484,28 @@ fn main() -> Result<()> {
                 // return Err(anyhow::anyhow!("Contract enforcement failed"));
             }
 
+            // 🔍 Semantic type check: report E0308/E0599/E0609 before codegen
+            let mut type_checker = vex_compiler::TypeChecker::new(&span_map);
+            if type_checker.check_program(&ast).is_err() {
+                if json {
+                    println!("{}", type_checker.diagnostics().to_json());
+                } else {
+                    type_checker.diagnostics().print_all(&source);
+                    type_checker.diagnostics().print_summary();
+                }
+                return Err(anyhow::anyhow!("Type check failed"));
+            }
+            if !json {
+                println!("   ✅ Type check passed");
+            }
+            if type_checker.diagnostics().warning_count() > 0 {
+                if json {
+                    println!("{}", type_checker.diagnostics().to_json());
+                } else {
+                    type_checker.diagnostics().print_all(&source);
+                }
+            }
+
             let mut borrow_checker = vex_compiler::BorrowChecker::new();
             if let Err(borrow_error) = borrow_checker.check_program(&mut ast) {
                 // Convert borrow error to diagnostic
@@ -1494,6 +1541,8 @@ fn main() -> Result<()> {
                 span_map,
                 &parser_file,
             );
+            codegen.set_type_table(type_checker.into_type_table());
+            codegen.set_overflow_checks(opt_level == 0);
 
             // Register module namespaces with codegen
             for (module_name, imported_funcs) in module_namespaces {
@@ -94,13 +94,14 @@ impl BorrowRulesChecker {
             Expression::Deref(expr) => self.check_deref_expression(expr, parent_span),
 
             Expression::Await(expr)
+            | Expression::Yield(expr)
             | Expression::TryOp { expr }
             | Expression::ChannelReceive(expr) => {
                 self.check_expression_for_borrows(expr, parent_span)?;
                 Ok(())
             }
 
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.check_match_expression(value, arms, parent_span)
             }
 
@@ -217,4 +217,50 @@ mod tests {
             assert!(diag.related[0].1.contains("borrow occurs") || diag.related[0].1.contains("borrow"));
         }
     }
+
+    #[test]
+    fn test_mutable_closure_holds_exclusive_borrow() {
+        let mut checker = BorrowRulesChecker::new();
+
+        // let tick = || { count = count + 1; };  (CallableMut)
+        let tick = Statement::Let {
+            is_mutable: false,
+            name: "tick".to_string(),
+            ty: None,
+            value: Expression::Closure {
+                params: vec![],
+                return_type: None,
+                body: Box::new(Expression::Block {
+                    statements: vec![Statement::Assign {
+                        span_id: None,
+                        target: Expression::Ident("count".to_string()),
+                        value: Expression::IntLiteral(1),
+                    }],
+                    return_expr: None,
+                }),
+                capture_mode: vex_ast::CaptureMode::Mutable,
+            },
+        };
+        assert!(checker.check_statement(&tick, None).is_ok());
+
+        // let r = &count;  (should fail while tick is alive)
+        let read = Statement::Let {
+            is_mutable: false,
+            name: "r".to_string(),
+            ty: None,
+            value: Expression::Reference {
+                is_mutable: false,
+                expr: Box::new(Expression::Ident("count".to_string())),
+            },
+        };
+        assert!(checker.check_statement(&read, None).is_err());
+
+        // count = 5;  (should fail as well)
+        let assign = Statement::Assign {
+            span_id: None,
+            target: Expression::Ident("count".to_string()),
+            value: Expression::IntLiteral(5),
+        };
+        assert!(checker.check_statement(&assign, None).is_err());
+    }
 }
@@ -2,8 +2,9 @@
 
 use super::checker::BorrowRulesChecker;
 use super::tracking::BorrowKind;
+use crate::borrow_checker::closure_traits::mutated_captures;
 use crate::borrow_checker::errors::{BorrowError, BorrowResult};
-use vex_ast::{Expression, Statement};
+use vex_ast::{CaptureMode, Expression, Statement};
 
 impl BorrowRulesChecker {
     /// Check a statement for borrow rule violations
@@ -103,6 +104,8 @@ impl BorrowRulesChecker {
                 self.check_unsafe_block(block, span_id.as_ref().or(parent_span))
             }
 
+            Statement::Attributed { stmt, .. } => self.check_statement(stmt, parent_span),
+
             _ => Ok(()),
         }
     }
@@ -132,6 +135,24 @@ impl BorrowRulesChecker {
             }
         }
 
+        // A CallableMut closure keeps exclusive access to the variables it assigns to
+        if let Expression::Closure {
+            params,
+            body,
+            capture_mode: CaptureMode::Mutable,
+            ..
+        } = value
+        {
+            for var in mutated_captures(params, body) {
+                self.create_borrow(
+                    name.to_string(),
+                    var,
+                    BorrowKind::Mutable,
+                    parent_span.cloned(),
+                )?;
+            }
+        }
+
         Ok(())
     }
 
@@ -73,6 +73,11 @@ pub fn get_builtin_functions() -> &'static [&'static str] {
         "vec_free",
         "box_new",
         "box_free",
+        // Option/Result constructors called with type arguments: Some<i32>(v)
+        "Some",
+        "None",
+        "Ok",
+        "Err",
         // Phase 0.7: Primitive to string conversions
         "vex_i32_to_string",
         "vex_i64_to_string",
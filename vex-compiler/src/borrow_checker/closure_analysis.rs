@@ -1,12 +1,13 @@
 // Closure Trait Analysis
-// Phase 5: Analyze closure capture modes and determine Callable traits
+// Phase 1.5: Analyze closure capture modes and determine Callable traits
 
 use crate::borrow_checker::closure_traits::analyze_closure_body;
 use crate::borrow_checker::errors::BorrowResult;
-use vex_ast::{Expression, Item, Program, Statement};
+use crate::borrow_checker::moves::type_classification::literal_type;
+use vex_ast::{Expression, Function, Item, Program, Statement};
 
 impl super::BorrowChecker {
-    /// Phase 5: Analyze all closures and set their capture modes
+    /// Phase 1.5: Analyze all closures and set their capture modes
     /// This determines which trait each closure implements:
     /// - Callable: Immutable capture (can call multiple times)
     /// - CallableMut: Mutable capture (can call multiple times, mutates environment)
@@ -21,17 +22,10 @@ impl super::BorrowChecker {
     /// Recursively analyze closures in an item
     fn analyze_item_closures(&mut self, item: &mut Item) -> BorrowResult<()> {
         match item {
-            Item::Function(func) => {
-                for stmt in &mut func.body.statements {
-                    self.analyze_statement_closures(stmt)?;
-                }
-                Ok(())
-            }
+            Item::Function(func) => self.analyze_function_closures(func),
             Item::TraitImpl(trait_impl) => {
                 for func in &mut trait_impl.methods {
-                    for stmt in &mut func.body.statements {
-                        self.analyze_statement_closures(stmt)?;
-                    }
+                    self.analyze_function_closures(func)?;
                 }
                 Ok(())
             }
@@ -39,11 +33,44 @@ impl super::BorrowChecker {
         }
     }
 
+    fn analyze_function_closures(&mut self, func: &mut Function) -> BorrowResult<()> {
+        self.closure_var_types.clear();
+        if let Some(receiver) = &func.receiver {
+            self.closure_var_types
+                .insert(receiver.name.clone(), receiver.ty.clone());
+        }
+        for param in &func.params {
+            self.closure_var_types
+                .insert(param.name.clone(), param.ty.clone());
+        }
+        for stmt in &mut func.body.statements {
+            self.analyze_statement_closures(stmt)?;
+        }
+        Ok(())
+    }
+
     /// Recursively analyze closures in a statement
     fn analyze_statement_closures(&mut self, stmt: &mut Statement) -> BorrowResult<()> {
         match stmt {
-            Statement::Let { value, .. } => {
+            Statement::Let {
+                name, ty, value, ..
+            } => {
                 self.analyze_expression_closures(value)?;
+
+                // Remember the binding's type so later closures know whether capturing it moves it
+                let binding_type = match (ty, &*value) {
+                    (Some(ty), _) => Some(ty.clone()),
+                    (None, Expression::Ident(var)) => self.closure_var_types.get(var).cloned(),
+                    (None, value) => literal_type(value),
+                };
+                match binding_type {
+                    Some(ty) => {
+                        self.closure_var_types.insert(name.clone(), ty);
+                    }
+                    None => {
+                        self.closure_var_types.remove(name);
+                    }
+                }
             }
             Statement::LetPattern { value, .. } => {
                 self.analyze_expression_closures(value)?;
@@ -156,7 +183,7 @@ impl super::BorrowChecker {
             } => {
                 // These don't typically contain user closures
             }
-            Statement::Defer(stmt) => {
+            Statement::Defer(stmt) | Statement::Attributed { stmt, .. } => {
                 self.analyze_statement_closures(stmt)?;
             }
             Statement::Go { span_id: _, expr } => {
@@ -196,7 +223,12 @@ impl super::BorrowChecker {
                 self.analyze_expression_closures(body)?;
 
                 // Then analyze this closure and determine its capture mode
-                let analyzed_mode = analyze_closure_body(params, body);
+                let is_move_type = |name: &str| {
+                    self.closure_var_types
+                        .get(name)
+                        .is_some_and(|ty| self.moves.is_move_type(ty))
+                };
+                let analyzed_mode = analyze_closure_body(params, body, &is_move_type);
 
                 // Update the capture mode from Infer to the analyzed result
                 *capture_mode = analyzed_mode;
@@ -302,7 +334,7 @@ impl super::BorrowChecker {
                 }
                 Ok(())
             }
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.analyze_expression_closures(value)?;
                 for arm in arms {
                     if let Some(guard) = &mut arm.guard {
@@ -326,6 +358,7 @@ impl super::BorrowChecker {
                 Ok(())
             }
             Expression::Await(expr)
+            | Expression::Yield(expr)
             | Expression::TryOp { expr }
             | Expression::New(expr)
             | Expression::Deref(expr)
@@ -334,7 +367,7 @@ impl super::BorrowChecker {
                 self.analyze_expression_closures(expr)?;
                 Ok(())
             }
-            Expression::Cast { expr, .. } => {
+            Expression::Cast { expr, .. } | Expression::Is { expr, .. } => {
                 self.analyze_expression_closures(expr)?;
                 Ok(())
             }
@@ -5,27 +5,61 @@ use std::collections::{HashMap, HashSet};
 use vex_ast::*;
 
 /// Analyze closure to determine which trait it should implement
-pub fn analyze_closure_trait(closure: &Expression, params: &[Param]) -> CaptureMode {
+pub fn analyze_closure_trait(
+    closure: &Expression,
+    params: &[Param],
+    is_move_type: &dyn Fn(&str) -> bool,
+) -> CaptureMode {
     if let Expression::Closure { body, .. } = closure {
-        let analysis = CaptureAnalyzer::new(params);
-        analysis.analyze_body(body)
+        analyze_closure_body(params, body, is_move_type)
     } else {
         CaptureMode::Infer
     }
 }
 
 /// Analyze closure body directly to determine capture mode
-/// Used when we already have the body and params separately
-pub fn analyze_closure_body(params: &[Param], body: &Expression) -> CaptureMode {
-    let analysis = CaptureAnalyzer::new(params);
-    analysis.analyze_body(body)
+/// Used when we already have the body and params separately.
+/// `is_move_type` tells whether a captured variable's type is moved rather than copied.
+pub fn analyze_closure_body(
+    params: &[Param],
+    body: &Expression,
+    is_move_type: &dyn Fn(&str) -> bool,
+) -> CaptureMode {
+    let mut analyzer = CaptureAnalyzer::new(params, is_move_type);
+    analyzer.visit_body(body);
+    analyzer.infer_capture_mode()
+}
+
+/// Captured variables the closure assigns to, in name order.
+/// A closure holds these exclusively for as long as it is alive.
+pub fn mutated_captures(params: &[Param], body: &Expression) -> Vec<String> {
+    let mut analyzer = CaptureAnalyzer::new(params, &|_| false);
+    analyzer.visit_body(body);
+    let mut names: Vec<String> = analyzer
+        .captured_vars
+        .into_iter()
+        .filter(|(_, info)| info.is_mutated)
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    names
+}
+
+/// Variables from the enclosing scope that the body mentions, in name order.
+/// Used for `go` statements, whose expression captures like a closure body.
+pub fn captured_variables(params: &[Param], body: &Expression) -> Vec<String> {
+    let mut analyzer = CaptureAnalyzer::new(params, &|_| false);
+    analyzer.visit_expression(body);
+    let mut names: Vec<String> = analyzer.captured_vars.into_keys().collect();
+    names.sort();
+    names
 }
 
 struct CaptureAnalyzer<'a> {
     param_names: HashSet<String>,
     captured_vars: HashMap<String, CaptureInfo>,
     local_vars: HashSet<String>,
-    _phantom: std::marker::PhantomData<&'a ()>,
+    is_move_type: &'a dyn Fn(&str) -> bool,
 }
 
 #[derive(Debug, Clone)]
@@ -35,30 +69,47 @@ struct CaptureInfo {
 }
 
 impl<'a> CaptureAnalyzer<'a> {
-    fn new(params: &[Param]) -> Self {
+    fn new(params: &[Param], is_move_type: &'a dyn Fn(&str) -> bool) -> Self {
         let param_names: HashSet<String> = params.iter().map(|p| p.name.clone()).collect();
 
         Self {
             param_names,
             captured_vars: HashMap::new(),
             local_vars: HashSet::new(),
-            _phantom: std::marker::PhantomData,
+            is_move_type,
         }
     }
 
-    fn analyze_body(&self, body: &Expression) -> CaptureMode {
-        // Clone self to make mutable
-        let mut analyzer = CaptureAnalyzer {
-            param_names: self.param_names.clone(),
-            captured_vars: self.captured_vars.clone(),
-            local_vars: self.local_vars.clone(),
-            _phantom: std::marker::PhantomData,
-        };
+    /// The body's value is returned to the caller, so a captured value there is moved out
+    fn visit_body(&mut self, body: &Expression) {
+        self.visit_expression(body);
+        match body {
+            Expression::Block {
+                return_expr: Some(ret),
+                ..
+            } => self.consume(ret),
+            other => self.consume(other),
+        }
+    }
 
-        analyzer.visit_expression(body);
+    fn is_captured(&self, name: &str) -> bool {
+        !self.param_names.contains(name) && !self.local_vars.contains(name)
+    }
 
-        // Determine capture mode based on how variables are used
-        analyzer.infer_capture_mode()
+    /// A captured value used by value: passed to a call, bound, stored or returned.
+    /// Copy types are copied out and stay usable on the next call.
+    fn consume(&mut self, expr: &Expression) {
+        if let Expression::Ident(name) = expr {
+            if self.is_captured(name) && (self.is_move_type)(name) {
+                self.captured_vars
+                    .entry(name.clone())
+                    .or_insert(CaptureInfo {
+                        is_mutated: false,
+                        is_moved: false,
+                    })
+                    .is_moved = true;
+            }
+        }
     }
 
     fn visit_expression(&mut self, expr: &Expression) {
@@ -97,13 +148,23 @@ impl<'a> CaptureAnalyzer<'a> {
                 self.visit_expression(func);
                 for arg in args {
                     self.visit_expression(arg);
+                    self.consume(arg);
                 }
             }
 
-            Expression::MethodCall { receiver, args, .. } => {
+            Expression::MethodCall {
+                receiver,
+                args,
+                is_mutable_call,
+                ..
+            } => {
                 self.visit_expression(receiver);
                 for arg in args {
                     self.visit_expression(arg);
+                    // method()! borrows its arguments mutably instead of taking them
+                    if !is_mutable_call {
+                        self.consume(arg);
+                    }
                 }
             }
 
@@ -119,7 +180,7 @@ impl<'a> CaptureAnalyzer<'a> {
                 }
             }
 
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.visit_expression(value);
                 for arm in arms {
                     if let Some(guard) = &arm.guard {
@@ -132,18 +193,21 @@ impl<'a> CaptureAnalyzer<'a> {
             Expression::Array(elements) => {
                 for elem in elements {
                     self.visit_expression(elem);
+                    self.consume(elem);
                 }
             }
 
             Expression::TupleLiteral(elements) => {
                 for elem in elements {
                     self.visit_expression(elem);
+                    self.consume(elem);
                 }
             }
 
             Expression::StructLiteral { fields, .. } => {
                 for (_, expr) in fields {
                     self.visit_expression(expr);
+                    self.consume(expr);
                 }
             }
 
@@ -190,7 +254,11 @@ impl<'a> CaptureAnalyzer<'a> {
 
     fn declare_pattern_locals(&mut self, pattern: &Pattern) {
         match pattern {
-            Pattern::Ident(name) => {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => {
                 self.local_vars.insert(name.clone());
             }
             Pattern::Tuple(patterns) => {
@@ -213,22 +281,24 @@ impl<'a> CaptureAnalyzer<'a> {
                     self.declare_pattern_locals(p);
                 }
             }
-            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) => {}
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) | Pattern::Type { .. } => {}
         }
     }
 
     fn visit_statement(&mut self, stmt: &Statement) {
         match stmt {
             Statement::Let { name, value, .. } => {
-                // New local variable in closure
-                self.local_vars.insert(name.clone());
+                // New local variable in closure, in scope after its initializer
                 self.visit_expression(value);
+                self.consume(value);
+                self.local_vars.insert(name.clone());
             }
 
             Statement::LetPattern { pattern, value, .. } => {
                 // New local variables from pattern
-                self.declare_pattern_locals(pattern);
                 self.visit_expression(value);
+                self.consume(value);
+                self.declare_pattern_locals(pattern);
             }
 
             Statement::Assign { span_id: _, target, value } => {
@@ -250,6 +320,7 @@ impl<'a> CaptureAnalyzer<'a> {
                     }
                 }
                 self.visit_expression(value);
+                self.consume(value);
             }
 
             Statement::CompoundAssign { target, value, .. } => {
@@ -275,6 +346,7 @@ impl<'a> CaptureAnalyzer<'a> {
             Statement::Return { span_id: _, value: expr } => {
                 if let Some(e) = expr {
                     self.visit_expression(e);
+                    self.consume(e);
                 }
             }
 
@@ -336,7 +408,7 @@ impl<'a> CaptureAnalyzer<'a> {
             }
 
             // Match is an expression, not a statement in Vex
-            Statement::Defer(stmt) => {
+            Statement::Defer(stmt) | Statement::Attributed { stmt, .. } => {
                 self.visit_statement(stmt);
             }
             Statement::Go { span_id: _, expr } => {
@@ -389,21 +461,114 @@ impl<'a> CaptureAnalyzer<'a> {
 mod tests {
     use super::*;
 
+    fn ident(name: &str) -> Expression {
+        Expression::Ident(name.to_string())
+    }
+
+    fn call(func: &str, args: Vec<Expression>) -> Expression {
+        Expression::Call {
+            span_id: None,
+            func: Box::new(ident(func)),
+            type_args: vec![],
+            args,
+        }
+    }
+
+    fn block(statements: Vec<Statement>) -> Expression {
+        Expression::Block {
+            statements,
+            return_expr: None,
+        }
+    }
+
+    fn param(name: &str) -> Param {
+        Param {
+            name: name.to_string(),
+            ty: Type::I32,
+            default_value: None,
+        }
+    }
+
+    // Only `data` (a string) is moved rather than copied
+    fn strings_move(name: &str) -> bool {
+        name == "data"
+    }
+
     #[test]
     fn test_immutable_capture() {
         // let x = 5; let f = |y| x + y;
-        // Should infer Callable (Immutable)
+        let body = Expression::Binary {
+            span_id: None,
+            left: Box::new(ident("x")),
+            op: BinaryOp::Add,
+            right: Box::new(ident("y")),
+        };
+        let mode = analyze_closure_body(&[param("y")], &body, &strings_move);
+        assert_eq!(mode, CaptureMode::Immutable);
+        assert!(mutated_captures(&[param("y")], &body).is_empty());
     }
 
     #[test]
     fn test_mutable_capture() {
         // let! counter = 0; let f = || { counter = counter + 1; };
-        // Should infer CallableMut (Mutable)
+        let body = block(vec![Statement::Assign {
+            span_id: None,
+            target: ident("counter"),
+            value: Expression::Binary {
+                span_id: None,
+                left: Box::new(ident("counter")),
+                op: BinaryOp::Add,
+                right: Box::new(Expression::IntLiteral(1)),
+            },
+        }]);
+        assert_eq!(
+            analyze_closure_body(&[], &body, &strings_move),
+            CaptureMode::Mutable
+        );
+        assert_eq!(mutated_captures(&[], &body), vec!["counter".to_string()]);
     }
 
     #[test]
     fn test_move_capture() {
-        // let data = vec![1,2,3]; let f = || process(data);
-        // Should infer CallableOnce (Once)
+        // let data = "payload"; let f = || process(data);
+        let body = call("process", vec![ident("data")]);
+        assert_eq!(
+            analyze_closure_body(&[], &body, &strings_move),
+            CaptureMode::Once
+        );
+
+        // Returning the captured value moves it out as well
+        assert_eq!(
+            analyze_closure_body(&[], &ident("data"), &strings_move),
+            CaptureMode::Once
+        );
+    }
+
+    #[test]
+    fn test_copy_capture_passed_by_value_is_not_moved() {
+        // let n = 5; let f = || process(n);
+        let body = call("process", vec![ident("n")]);
+        assert_eq!(
+            analyze_closure_body(&[], &body, &strings_move),
+            CaptureMode::Immutable
+        );
+    }
+
+    #[test]
+    fn test_locals_and_params_are_not_captures() {
+        // |data| { let copy = data; process(copy) }
+        let body = Expression::Block {
+            statements: vec![Statement::Let {
+                is_mutable: false,
+                name: "copy".to_string(),
+                ty: None,
+                value: ident("data"),
+            }],
+            return_expr: Some(Box::new(call("process", vec![ident("copy")]))),
+        };
+        assert_eq!(
+            analyze_closure_body(&[param("data")], &body, &|_| true),
+            CaptureMode::Immutable
+        );
     }
 }
@@ -22,6 +22,12 @@ pub enum BorrowError {
         location: Option<String>,
     },
 
+    /// Element assignment through a read-only slice view (`&[T]`)
+    AssignThroughImmutableSlice {
+        variable: String,
+        location: Option<String>,
+    },
+
     /// Use of moved value (Phase 2)
     UseAfterMove {
         variable: String,
@@ -75,6 +81,14 @@ pub enum BorrowError {
         operation: String,
         location: Option<String>,
     },
+
+    /// CallableOnce closure called again, or called inside a loop (Phase 2)
+    OnceClosureCalledTwice {
+        closure: String,
+        first_call: Option<String>,
+        called_at: Option<String>,
+        in_loop: bool,
+    },
 }
 
 impl fmt::Display for BorrowError {
@@ -116,6 +130,21 @@ impl fmt::Display for BorrowError {
                 )
             }
 
+            BorrowError::AssignThroughImmutableSlice { variable, location } => {
+                write!(
+                    f,
+                    "cannot assign to element of `{}`, which is a read-only slice",
+                    variable
+                )?;
+                if let Some(loc) = location {
+                    write!(f, " at {}", loc)?;
+                }
+                write!(
+                    f,
+                    "\nhelp: take a mutable view instead: `&[T]!` or `&array!`"
+                )
+            }
+
             BorrowError::UseAfterMove {
                 variable,
                 moved_at,
@@ -253,6 +282,29 @@ impl fmt::Display for BorrowError {
                 }
                 write!(f, "\nhelp: wrap this operation in an `unsafe {{ }}` block")
             }
+
+            BorrowError::OnceClosureCalledTwice {
+                closure,
+                first_call,
+                called_at,
+                in_loop,
+            } => {
+                write!(
+                    f,
+                    "closure `{}` cannot be called more than once: it moves a captured value out of its environment",
+                    closure
+                )?;
+                if *in_loop {
+                    write!(f, "\nnote: this call is inside a loop and would run again")?;
+                }
+                if let Some(first) = first_call {
+                    write!(f, "\nnote: first called here: {}", first)?;
+                }
+                if let Some(called) = called_at {
+                    write!(f, "\nnote: called again here: {}", called)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -353,6 +405,34 @@ impl BorrowError {
                 }
             }
 
+            BorrowError::AssignThroughImmutableSlice { variable, location } => {
+                let mut notes = vec![format!("`{}` is a read-only slice view", variable)];
+                if let Some(loc) = location {
+                    notes.push(format!("assignment at {}", loc));
+                }
+
+                let related: Vec<(Span, String)> = location
+                    .as_ref()
+                    .and_then(|loc_id| span_map.get(loc_id))
+                    .map(|span| vec![(span.clone(), "assignment occurs here".to_string())])
+                    .unwrap_or_default();
+
+                Diagnostic {
+                    level: ErrorLevel::Error,
+                    code: error_codes::IMMUTABLE_ASSIGN.to_string(),
+                    message: format!(
+                        "cannot assign to element of `{}`, which is a read-only slice",
+                        variable
+                    ),
+                    span: resolve_span(location),
+                    primary_label: Some("assignment through `&[T]`".to_string()),
+                    notes,
+                    help: Some("take a mutable view instead: `&[T]!` or `&array!`".to_string()),
+                    suggestion: None,
+                    related,
+                }
+            }
+
             BorrowError::UseAfterMove {
                 variable,
                 moved_at,
@@ -662,6 +742,43 @@ impl BorrowError {
                     related: Vec::new(),
                 }
             }
+
+            BorrowError::OnceClosureCalledTwice {
+                closure,
+                first_call,
+                called_at,
+                in_loop,
+            } => {
+                let mut notes = vec![
+                    "it moves a captured value out of its environment, so it implements only CallableOnce"
+                        .to_string(),
+                ];
+                let mut related: Vec<(Span, String)> = Vec::new();
+                if *in_loop {
+                    notes.push("this call is inside a loop and would run again".to_string());
+                }
+                if let Some(first) = first_call {
+                    notes.push(format!("first called here: {}", first));
+                    if let Some(span) = span_map.get(first) {
+                        related.push((span.clone(), "first call moves the closure".to_string()));
+                    }
+                }
+
+                Diagnostic {
+                    level: ErrorLevel::Error,
+                    code: error_codes::USE_AFTER_MOVE.to_string(),
+                    message: format!("closure `{}` cannot be called more than once", closure),
+                    span: resolve_span(called_at),
+                    primary_label: Some("closure called again".to_string()),
+                    notes,
+                    help: Some(
+                        "clone the captured value inside the closure so each call has its own copy"
+                            .to_string(),
+                    ),
+                    suggestion: None,
+                    related,
+                }
+            }
         }
     }
 }
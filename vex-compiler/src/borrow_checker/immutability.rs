@@ -1,7 +1,7 @@
 // Phase 1: Immutability Checker
 // Enforces let vs let! semantics
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use vex_ast::{Expression, Pattern, Program, Statement, Type};
 
 use super::errors::{BorrowError, BorrowResult};
@@ -14,6 +14,9 @@ pub struct ImmutabilityChecker {
     /// Variables declared with `let!` (mutable)
     mutable_vars: HashSet<String>,
 
+    /// Variables holding slice views, and whether elements may be written through them
+    slice_views: HashMap<String, bool>,
+
     /// Builtin function registry for identifying builtin functions
     builtin_registry: super::builtin_metadata::BuiltinBorrowRegistry,
 
@@ -29,6 +32,7 @@ impl ImmutabilityChecker {
         Self {
             immutable_vars: HashSet::new(),
             mutable_vars: HashSet::new(),
+            slice_views: HashMap::new(),
             builtin_registry: super::builtin_metadata::BuiltinBorrowRegistry::new(),
             current_function: None,
             in_mutable_method: false,
@@ -58,10 +62,12 @@ impl ImmutabilityChecker {
                 // Create new scope for function
                 let saved_immutable = self.immutable_vars.clone();
                 let saved_mutable = self.mutable_vars.clone();
+                let saved_views = self.slice_views.clone();
 
                 // Function parameters are always mutable (local bindings)
                 for param in &func.params {
                     self.mutable_vars.insert(param.name.clone());
+                    self.register_slice_param(&param.name, &param.ty);
                 }
 
                 // ⭐ NEW: Handle method receiver (self, p, this, etc.)
@@ -91,6 +97,7 @@ impl ImmutabilityChecker {
                 // Restore scope
                 self.immutable_vars = saved_immutable;
                 self.mutable_vars = saved_mutable;
+                self.slice_views = saved_views;
                 self.current_function = None;
                 self.in_mutable_method = false; // ⭐ NEW: Reset flag
 
@@ -106,10 +113,12 @@ impl ImmutabilityChecker {
                     // Create new scope for method
                     let saved_immutable = self.immutable_vars.clone();
                     let saved_mutable = self.mutable_vars.clone();
+                    let saved_views = self.slice_views.clone();
 
                     // Method parameters are always mutable (local bindings)
                     for param in &method.params {
                         self.mutable_vars.insert(param.name.clone());
+                        self.register_slice_param(&param.name, &param.ty);
                     }
 
                     // Handle method receiver (self)
@@ -140,6 +149,7 @@ impl ImmutabilityChecker {
                     // Restore scope
                     self.immutable_vars = saved_immutable;
                     self.mutable_vars = saved_mutable;
+                    self.slice_views = saved_views;
                     self.current_function = None;
                     self.in_mutable_method = false; // ⭐ NEW: Reset flag
                 }
@@ -166,8 +176,8 @@ impl ImmutabilityChecker {
             Statement::Let {
                 name,
                 is_mutable,
+                ty,
                 value,
-                ..
             } => {
                 // Register the variable
                 if *is_mutable {
@@ -176,6 +186,17 @@ impl ImmutabilityChecker {
                     self.immutable_vars.insert(name.clone());
                 }
 
+                // Slice views are tracked by whether they allow writes, not by the binding
+                let view = match ty {
+                    Some(Type::Slice(_, is_writable)) => Some(*is_writable),
+                    Some(_) => None,
+                    None => self.view_mutability(value),
+                };
+                match view {
+                    Some(is_writable) => self.slice_views.insert(name.clone(), is_writable),
+                    None => self.slice_views.remove(name),
+                };
+
                 // Check the initializer expression
                 self.check_expression(value)?;
 
@@ -239,7 +260,19 @@ impl ImmutabilityChecker {
                     Expression::Index { object, .. } => {
                         // Index assignment: x[i] = ...
                         if let Expression::Ident(base_name) = &**object {
-                            if self.immutable_vars.contains(base_name) {
+                            // Writes through a view depend on the view, not on `let` vs `let!`
+                            if let Some(&is_writable) = self.slice_views.get(base_name) {
+                                if !is_writable {
+                                    let location = self
+                                        .current_function
+                                        .as_ref()
+                                        .map(|f| format!("in function `{}`", f));
+                                    return Err(BorrowError::AssignThroughImmutableSlice {
+                                        variable: base_name.clone(),
+                                        location,
+                                    });
+                                }
+                            } else if self.immutable_vars.contains(base_name) {
                                 let location = self
                                     .current_function
                                     .as_ref()
@@ -379,10 +412,45 @@ impl ImmutabilityChecker {
                 Ok(())
             }
 
+            Statement::Attributed { stmt, .. } => self.check_statement(stmt),
+
             _ => Ok(()),
         }
     }
 
+    /// `&[T]` / `&[T]!` parameters are views whose element mutability comes from the type
+    fn register_slice_param(&mut self, name: &str, ty: &Type) {
+        if let Type::Slice(_, is_writable) = ty {
+            self.slice_views.insert(name.to_string(), *is_writable);
+        }
+    }
+
+    /// Element mutability of a view created by `&arr`, `&arr!` or `xs[a..b]`
+    fn view_mutability(&self, value: &Expression) -> Option<bool> {
+        match value {
+            Expression::Reference { is_mutable, expr } => match expr.as_ref() {
+                Expression::Ident(_) => Some(*is_mutable),
+                Expression::Index { .. } => self.view_mutability(expr).map(|_| *is_mutable),
+                _ => None,
+            },
+            Expression::Index { object, index }
+                if matches!(
+                    index.as_ref(),
+                    Expression::Range { .. } | Expression::RangeInclusive { .. }
+                ) =>
+            {
+                // A subslice inherits its source view; subslicing owned storage reads only
+                match object.as_ref() {
+                    Expression::Ident(name) => {
+                        Some(self.slice_views.get(name).copied().unwrap_or(false))
+                    }
+                    _ => Some(false),
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Check an expression (may contain nested assignments)
     fn check_expression(&mut self, expr: &Expression) -> BorrowResult<()> {
         match expr {
@@ -568,12 +636,66 @@ mod tests {
         assert!(checker.mutable_vars.contains("y"));
         assert!(!checker.immutable_vars.contains("y"));
     }
+
+    #[test]
+    fn test_slice_view_mutability() {
+        let mut checker = ImmutabilityChecker::new();
+        let assign = |name: &str| Statement::Assign {
+            span_id: None,
+            target: Expression::Index {
+                object: Box::new(Expression::Ident(name.to_string())),
+                index: Box::new(Expression::IntLiteral(0)),
+            },
+            value: Expression::IntLiteral(1),
+        };
+        let subslice = |name: &str| Expression::Index {
+            object: Box::new(Expression::Ident(name.to_string())),
+            index: Box::new(Expression::Range {
+                start: Some(Box::new(Expression::IntLiteral(1))),
+                end: None,
+            }),
+        };
+
+        // let! data = ...; let view = &data!; let tail = view[1..]; let head = data[1..];
+        checker.mutable_vars.insert("data".to_string());
+        for (name, value) in [
+            (
+                "view",
+                Expression::Reference {
+                    is_mutable: true,
+                    expr: Box::new(Expression::Ident("data".to_string())),
+                },
+            ),
+            ("tail", subslice("view")),
+            ("head", subslice("data")),
+        ] {
+            let let_stmt = Statement::Let {
+                name: name.to_string(),
+                ty: None,
+                value,
+                is_mutable: false,
+            };
+            checker.check_statement(&let_stmt).unwrap();
+        }
+
+        // Writable views allow element writes even through a `let` binding
+        assert!(checker.check_statement(&assign("view")).is_ok());
+        assert!(checker.check_statement(&assign("tail")).is_ok());
+        assert!(matches!(
+            checker.check_statement(&assign("head")),
+            Err(BorrowError::AssignThroughImmutableSlice { .. })
+        ));
+    }
 }
 
 impl ImmutabilityChecker {
     pub fn mark_pattern_mutable(&mut self, pattern: &Pattern) {
         match pattern {
-            Pattern::Ident(name) => {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => {
                 self.mutable_vars.insert(name.clone());
             }
             Pattern::Tuple(patterns) => {
@@ -596,13 +718,17 @@ impl ImmutabilityChecker {
                     self.mark_pattern_mutable(p);
                 }
             }
-            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) => {}
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) | Pattern::Type { .. } => {}
         }
     }
 
     pub fn mark_pattern_immutable(&mut self, pattern: &Pattern) {
         match pattern {
-            Pattern::Ident(name) => {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => {
                 self.immutable_vars.insert(name.clone());
             }
             Pattern::Tuple(patterns) => {
@@ -625,7 +751,7 @@ impl ImmutabilityChecker {
                     self.mark_pattern_immutable(p);
                 }
             }
-            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) => {}
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) | Pattern::Type { .. } => {}
         }
     }
 }
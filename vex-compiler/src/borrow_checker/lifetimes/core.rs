@@ -33,6 +33,10 @@ pub struct LifetimeChecker {
 
     /// Track if we're inside an unsafe block
     pub(super) in_unsafe_block: bool,
+
+    /// Const parameters of each struct, in scope in its external methods:
+    /// `fn (self: &Matrix<T, R, C>) size(): i32 { return R * C; }`
+    pub(super) struct_const_params: HashMap<String, Vec<String>>,
 }
 
 impl LifetimeChecker {
@@ -46,6 +50,7 @@ impl LifetimeChecker {
             builtin_registry: BuiltinBorrowRegistry::new(),
             current_function: None,
             in_unsafe_block: false,
+            struct_const_params: HashMap::new(),
         };
 
         // Register built-in functions as always in scope (scope 0 = global)
@@ -74,6 +79,14 @@ impl LifetimeChecker {
                     // Register struct type name
                     self.variable_scopes.insert(struct_def.name.clone(), 0);
                     self.in_scope.insert(struct_def.name.clone());
+                    self.struct_const_params.insert(
+                        struct_def.name.clone(),
+                        struct_def
+                            .const_params
+                            .iter()
+                            .map(|(name, _)| name.clone())
+                            .collect(),
+                    );
                 }
                 Item::Enum(enum_def) => {
                     // Register enum type name
@@ -112,6 +125,25 @@ impl LifetimeChecker {
         // ⭐ CRITICAL FIX: Method receiver uses custom name (p, self, this, etc.)
         if let Some(ref receiver) = func.receiver {
             self.declare_variable(&receiver.name);
+            let owner = match &receiver.ty {
+                Type::Reference(inner, _) => inner.as_ref(),
+                ty => ty,
+            };
+            if let Type::Named(name) | Type::Generic { name, .. } = owner {
+                for param in self
+                    .struct_const_params
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_default()
+                {
+                    self.declare_variable(&param);
+                }
+            }
+        }
+
+        // Const parameters are values: fn zeros<const N: usize>(): [i32; N]
+        for (name, _) in &func.const_params {
+            self.declare_variable(name);
         }
 
         // Parameters are in function scope (scope 1)
@@ -171,7 +203,11 @@ impl LifetimeChecker {
     /// Extract and declare variables from a pattern (for match arms)
     pub(super) fn declare_pattern_bindings(&mut self, pattern: &Pattern) {
         match pattern {
-            Pattern::Ident(name) => {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => {
                 self.declare_variable(name);
             }
             Pattern::Enum { data, .. } => {
@@ -192,8 +228,8 @@ impl LifetimeChecker {
                     self.declare_pattern_bindings(p);
                 }
             }
-            Pattern::Wildcard => {
-                // _ doesn't bind anything
+            Pattern::Wildcard | Pattern::Type { binding: None, .. } => {
+                // _ and `_: T` don't bind anything
             }
             Pattern::Literal(_) => {
                 // Literals don't bind variables
@@ -232,4 +268,17 @@ mod tests {
         // The builtin `println` should be present at scope 0
         assert_eq!(checker.variable_scopes.get("println"), Some(&0usize));
     }
+
+    #[test]
+    fn test_const_params_are_in_scope() {
+        let source = r#"
+            struct Matrix<T, const R: usize, const C: usize> { data: [T; R * C] }
+            fn (self: &Matrix<T, R, C>) size(): i32 { return R * C; }
+            fn width<const W: usize>(): i32 { return W; }
+            fn main(): i32 { return 0; }
+        "#;
+        let mut parser = vex_parser::Parser::new(source).expect("lexer failed");
+        let program = parser.parse().expect("parse failed");
+        assert!(LifetimeChecker::new().check_program(&program).is_ok());
+    }
 }
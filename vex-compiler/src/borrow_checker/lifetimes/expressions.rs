@@ -158,7 +158,7 @@ impl super::LifetimeChecker {
                 Ok(())
             }
 
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.check_expression(value)?;
 
                 for arm in arms {
@@ -246,6 +246,7 @@ impl super::LifetimeChecker {
             Expression::PostfixOp { expr, .. } => self.check_expression(expr),
 
             Expression::Await(expr)
+            | Expression::Yield(expr)
             | Expression::TryOp { expr }
             | Expression::ChannelReceive(expr) => self.check_expression(expr),
 
@@ -260,7 +261,9 @@ impl super::LifetimeChecker {
 
             Expression::Make { size, .. } => self.check_expression(size),
 
-            Expression::Cast { expr, .. } => self.check_expression(expr),
+            Expression::Cast { expr, .. } | Expression::Is { expr, .. } => {
+                self.check_expression(expr)
+            }
 
             Expression::ErrorNew(expr) => self.check_expression(expr),
 
@@ -226,13 +226,36 @@ impl super::LifetimeChecker {
 
             Statement::Expression(expr) => self.check_expression(expr),
 
+            Statement::Attributed { stmt, .. } => self.check_statement(stmt),
+
             Statement::CompoundAssign { target, value, .. } => {
                 self.check_expression(target)?;
                 self.check_expression(value)
             }
 
-            Statement::Select { .. } => {
-                // TODO: Implement select case checking when async is ready
+            Statement::Select {
+                span_id: _,
+                cases,
+                default_case,
+            } => {
+                for case in cases {
+                    self.check_expression(&case.expr)?;
+
+                    // Receive binding is scoped to its case body
+                    self.enter_scope();
+                    if let Some(var) = &case.var {
+                        self.declare_variable(var);
+                    }
+                    self.check_block(&case.body)?;
+                    self.exit_scope();
+                }
+
+                if let Some(default) = default_case {
+                    self.enter_scope();
+                    self.check_block(default)?;
+                    self.exit_scope();
+                }
+
                 Ok(())
             }
 
@@ -249,7 +272,10 @@ impl super::LifetimeChecker {
                 Ok(())
             }
 
-            Statement::Defer(_) | Statement::Go { span_id: _, expr: _ } | Statement::Break { span_id: _ } | Statement::Continue { span_id: _ } => {
+            // Captures are copied into the task, so they only need to be live here
+            Statement::Go { span_id: _, expr } => self.check_expression(expr),
+
+            Statement::Defer(_) | Statement::Break { span_id: _ } | Statement::Continue { span_id: _ } => {
                 Ok(())
             }
         }
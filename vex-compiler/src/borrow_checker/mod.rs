@@ -17,7 +17,7 @@ pub mod orchestrator;
 
 pub use borrows::BorrowRulesChecker;
 pub use builtin_metadata::{BuiltinBorrowRegistry, BuiltinMetadata, ParamEffect};
-pub use closure_traits::{analyze_closure_body, analyze_closure_trait};
+pub use closure_traits::{analyze_closure_body, analyze_closure_trait, mutated_captures};
 pub use errors::{BorrowError, BorrowResult};
 pub use immutability::ImmutabilityChecker;
 pub use lifetimes::LifetimeChecker;
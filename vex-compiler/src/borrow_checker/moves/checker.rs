@@ -26,6 +26,12 @@ pub struct MoveChecker {
     pub(super) current_function: Option<String>,
     /// Map of variable -> span_id of the move location (if available)
     pub(super) move_locations: std::collections::HashMap<String, Option<String>>,
+
+    /// CallableOnce closures bound with `let` -> loop depth at the binding
+    pub(super) once_closures: HashMap<String, usize>,
+
+    /// Number of loops around the statement being checked
+    pub(super) loop_depth: usize,
 }
 
 impl MoveChecker {
@@ -39,6 +45,7 @@ impl MoveChecker {
                 // Create new scope for function (save only non-global vars)
                 let saved_moved = self.moved_vars.clone();
                 let saved_types = self.var_types.clone();
+                let saved_once_closures = std::mem::take(&mut self.once_closures);
 
                 // Save function-local vars only (filter out globals before saving)
                 let saved_local_valid: HashSet<String> = self
@@ -74,6 +81,7 @@ impl MoveChecker {
                     self.valid_vars.insert(global.clone());
                 }
                 self.var_types = saved_types;
+                self.once_closures = saved_once_closures;
                 self.current_function = None;
 
                 Ok(())
@@ -2,7 +2,7 @@
 
 use super::checker::MoveChecker;
 use crate::borrow_checker::errors::{BorrowError, BorrowResult};
-use vex_ast::{Expression, Param};
+use vex_ast::{Expression, Param, Type};
 
 impl MoveChecker {
     /// Check an expression for use of moved variables
@@ -106,14 +106,42 @@ impl MoveChecker {
                 Ok(())
             }
 
-            Expression::Await(expr)
-            | Expression::TryOp { expr }
-            | Expression::ChannelReceive(expr) => {
+            // Awaiting reads the result and frees the future, so it can't be awaited again
+            Expression::Await(expr) => {
+                self.check_expression(expr, this_span)?;
+                if let Expression::Ident(var) = expr.as_ref() {
+                    if matches!(self.var_types.get(var), Some(Type::Future(_))) {
+                        self.moved_vars.insert(var.clone());
+                        self.valid_vars.remove(var);
+                        self.move_locations.insert(var.clone(), this_span.cloned());
+                    }
+                }
+                Ok(())
+            }
+
+            Expression::TryOp { expr } | Expression::ChannelReceive(expr) => {
                 self.check_expression(expr, this_span)?;
                 Ok(())
             }
 
-            Expression::Match { value, arms } => {
+            // The yielded value is handed to the generator's consumer
+            Expression::Yield(expr) => {
+                self.check_expression(expr, this_span)?;
+                if let Expression::Ident(var) = expr.as_ref() {
+                    if self
+                        .var_types
+                        .get(var)
+                        .is_some_and(|ty| self.is_move_type(ty))
+                    {
+                        self.moved_vars.insert(var.clone());
+                        self.valid_vars.remove(var);
+                        self.move_locations.insert(var.clone(), this_span.cloned());
+                    }
+                }
+                Ok(())
+            }
+
+            Expression::Match { value, arms, .. } => {
                 self.check_match_expression(value, arms, this_span)
             }
 
@@ -177,6 +205,33 @@ impl MoveChecker {
         Ok(())
     }
 
+    /// Calling a CallableOnce closure (or handing it on) uses it up. A second call,
+    /// or a call inside a loop that encloses the binding, is rejected.
+    pub(super) fn consume_once_closure(
+        &mut self,
+        name: &str,
+        at: Option<&String>,
+    ) -> BorrowResult<()> {
+        let Some(&bound_depth) = self.once_closures.get(name) else {
+            return Ok(());
+        };
+
+        let in_loop = self.loop_depth > bound_depth;
+        if in_loop || self.moved_vars.contains(name) {
+            return Err(BorrowError::OnceClosureCalledTwice {
+                closure: name.to_string(),
+                first_call: self.move_locations.get(name).cloned().flatten(),
+                called_at: at.cloned(),
+                in_loop,
+            });
+        }
+
+        self.moved_vars.insert(name.to_string());
+        self.valid_vars.remove(name);
+        self.move_locations.insert(name.to_string(), at.cloned());
+        Ok(())
+    }
+
     fn check_call_expression(
         &mut self,
         func: &Expression,
@@ -185,7 +240,9 @@ impl MoveChecker {
     ) -> BorrowResult<()> {
         // Skip builtin function check if func is an identifier
         if let Expression::Ident(func_name) = func {
-            if !self.builtin_registry.is_builtin(func_name) {
+            if self.once_closures.contains_key(func_name) {
+                self.consume_once_closure(func_name, parent_span)?;
+            } else if !self.builtin_registry.is_builtin(func_name) {
                 self.check_expression(func, parent_span)?;
             }
             // Builtin functions are always valid, skip checking
@@ -200,6 +257,8 @@ impl MoveChecker {
 
             // If arg is a move type identifier, mark it as moved
             if let Expression::Ident(var) = arg {
+                // The callee may call a CallableOnce closure, so it is used up here
+                self.consume_once_closure(var, parent_span)?;
                 if let Some(ty) = self.var_types.get(var) {
                     if self.is_move_type(ty) {
                         self.moved_vars.insert(var.clone());
@@ -22,7 +22,7 @@ mod checker;
 mod expression_checking;
 mod pattern_helpers;
 mod statement_checking;
-mod type_classification;
+pub(super) mod type_classification;
 
 use crate::borrow_checker::errors::BorrowResult;
 use vex_ast::Program;
@@ -40,6 +40,8 @@ impl MoveChecker {
             builtin_registry: super::builtin_metadata::BuiltinBorrowRegistry::new(),
             current_function: None,
             move_locations: std::collections::HashMap::new(),
+            once_closures: std::collections::HashMap::new(),
+            loop_depth: 0,
         }
     }
 
@@ -278,4 +280,155 @@ mod tests {
         // s should still be valid
         assert!(!checker.moved_vars.contains("s"));
     }
+
+    fn once_closure_let(name: &str) -> Statement {
+        // let consume = || drop_it(data);
+        Statement::Let {
+            is_mutable: false,
+            name: name.to_string(),
+            ty: None,
+            value: Expression::Closure {
+                params: vec![],
+                return_type: None,
+                body: Box::new(Expression::Call {
+                    span_id: None,
+                    func: Box::new(Expression::Ident("drop_it".to_string())),
+                    type_args: vec![],
+                    args: vec![Expression::Ident("data".to_string())],
+                }),
+                capture_mode: vex_ast::CaptureMode::Once,
+            },
+        }
+    }
+
+    fn call_stmt(func: &str, span: &str) -> Statement {
+        Statement::Expression(Expression::Call {
+            span_id: Some(span.to_string()),
+            func: Box::new(Expression::Ident(func.to_string())),
+            type_args: vec![],
+            args: vec![],
+        })
+    }
+
+    #[test]
+    fn test_once_closure_called_twice() {
+        let mut checker = MoveChecker::new();
+        checker.valid_vars.insert("data".to_string());
+        checker.var_types.insert("data".to_string(), Type::String);
+
+        assert!(checker.check_statement(&once_closure_let("consume"), None).is_ok());
+        assert!(checker.check_statement(&call_stmt("consume", "span_first"), None).is_ok());
+
+        let result = checker.check_statement(&call_stmt("consume", "span_second"), None);
+        match result {
+            Err(BorrowError::OnceClosureCalledTwice {
+                closure,
+                first_call,
+                called_at,
+                in_loop,
+            }) => {
+                assert_eq!(closure, "consume");
+                assert_eq!(first_call, Some("span_first".to_string()));
+                assert_eq!(called_at, Some("span_second".to_string()));
+                assert!(!in_loop);
+            }
+            other => panic!("Expected OnceClosureCalledTwice, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_once_closure_called_in_loop() {
+        let mut checker = MoveChecker::new();
+        checker.valid_vars.insert("data".to_string());
+        checker.var_types.insert("data".to_string(), Type::String);
+
+        assert!(checker.check_statement(&once_closure_let("consume"), None).is_ok());
+
+        // while true { consume(); }
+        let loop_stmt = Statement::While {
+            span_id: None,
+            condition: Expression::BoolLiteral(true),
+            body: vex_ast::Block {
+                span_id: None,
+                statements: vec![call_stmt("consume", "span_loop")],
+            },
+        };
+        let result = checker.check_statement(&loop_stmt, None);
+        assert!(matches!(
+            result,
+            Err(BorrowError::OnceClosureCalledTwice { in_loop: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_once_closure_alias_shares_single_call() {
+        let mut checker = MoveChecker::new();
+        checker.valid_vars.insert("data".to_string());
+        checker.var_types.insert("data".to_string(), Type::String);
+
+        assert!(checker.check_statement(&once_closure_let("consume"), None).is_ok());
+
+        // let again = consume;  hands the single call on
+        let alias = Statement::Let {
+            is_mutable: false,
+            name: "again".to_string(),
+            ty: None,
+            value: Expression::Ident("consume".to_string()),
+        };
+        assert!(checker.check_statement(&alias, None).is_ok());
+        assert!(checker.check_statement(&call_stmt("again", "span_a"), None).is_ok());
+        assert!(checker.check_statement(&call_stmt("consume", "span_b"), None).is_err());
+    }
+
+    #[test]
+    fn test_go_moves_captured_values() {
+        let mut checker = MoveChecker::new();
+        checker.valid_vars.insert("s".to_string());
+        checker.var_types.insert("s".to_string(), Type::String);
+        checker.valid_vars.insert("n".to_string());
+        checker.var_types.insert("n".to_string(), Type::I32);
+
+        // go { s.push(n) };
+        let go_stmt = Statement::Go {
+            span_id: Some("span_go".to_string()),
+            expr: Expression::Block {
+                statements: vec![],
+                return_expr: Some(Box::new(Expression::MethodCall {
+                    receiver: Box::new(Expression::Ident("s".to_string())),
+                    method: "push".to_string(),
+                    type_args: vec![],
+                    args: vec![Expression::Ident("n".to_string())],
+                    is_mutable_call: false,
+                })),
+            },
+        };
+        assert!(checker.check_statement(&go_stmt, None).is_ok());
+
+        // The task owns `s` now; `n` is Copy
+        assert!(checker.moved_vars.contains("s"));
+        assert!(!checker.moved_vars.contains("n"));
+        assert_eq!(
+            checker.move_locations.get("s"),
+            Some(&Some("span_go".to_string()))
+        );
+        let result = checker.check_expression(&Expression::Ident("s".to_string()), None);
+        assert!(matches!(result, Err(BorrowError::UseAfterMove { .. })));
+    }
+
+    #[test]
+    fn test_await_consumes_future() {
+        let mut checker = MoveChecker::new();
+        checker.valid_vars.insert("f".to_string());
+        checker
+            .var_types
+            .insert("f".to_string(), Type::Future(Box::new(Type::I32)));
+
+        // await f;  frees the future once its value is read
+        let await_f = Expression::Await(Box::new(Expression::Ident("f".to_string())));
+        assert!(checker.check_expression(&await_f, None).is_ok());
+        assert!(checker.moved_vars.contains("f"));
+
+        let result = checker.check_expression(&await_f, None);
+        assert!(matches!(result, Err(BorrowError::UseAfterMove { .. })));
+    }
 }
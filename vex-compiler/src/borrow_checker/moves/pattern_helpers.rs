@@ -6,7 +6,11 @@ use vex_ast::Pattern;
 impl MoveChecker {
     pub(super) fn declare_pattern_variables(&mut self, pattern: &Pattern) {
         match pattern {
-            Pattern::Ident(name) => {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => {
                 self.moved_vars.remove(name);
                 self.valid_vars.insert(name.clone());
             }
@@ -30,7 +34,7 @@ impl MoveChecker {
                     self.declare_pattern_variables(p);
                 }
             }
-            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) => {}
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Or(_) | Pattern::Type { .. } => {}
         }
     }
 }
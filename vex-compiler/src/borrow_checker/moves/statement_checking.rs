@@ -1,8 +1,10 @@
 //! Statement-level move checking
 
 use super::checker::MoveChecker;
+use super::type_classification::literal_type;
+use crate::borrow_checker::closure_traits::captured_variables;
 use crate::borrow_checker::errors::BorrowResult;
-use vex_ast::{Expression, Statement, Type};
+use vex_ast::{CaptureMode, Expression, Statement, Type};
 
 impl MoveChecker {
     /// Check a statement for move violations
@@ -99,10 +101,40 @@ impl MoveChecker {
                 span_id.as_ref().or(parent_span),
             ),
 
+            Statement::Attributed { stmt, .. } => self.check_statement(stmt, parent_span),
+
+            Statement::Go { span_id, expr } => {
+                self.check_go_statement(expr, span_id.as_ref().or(parent_span))
+            }
+
             _ => Ok(()), // Other statement types don't affect moves
         }
     }
 
+    /// The spawned task copies its captures into its own environment and may
+    /// outlive this scope, so every captured move-type variable is moved
+    fn check_go_statement(
+        &mut self,
+        expr: &Expression,
+        parent_span: Option<&String>,
+    ) -> BorrowResult<()> {
+        self.check_expression(expr, parent_span)?;
+
+        for var in captured_variables(&[], expr) {
+            if self
+                .var_types
+                .get(&var)
+                .is_some_and(|ty| self.is_move_type(ty))
+            {
+                self.moved_vars.insert(var.clone());
+                self.valid_vars.remove(&var);
+                self.move_locations.insert(var, parent_span.cloned());
+            }
+        }
+
+        Ok(())
+    }
+
     fn check_let_statement(
         &mut self,
         name: &str,
@@ -138,35 +170,36 @@ impl MoveChecker {
             }
         }
 
+        // A CallableOnce closure can be called once; `let g = f;` hands that call on to `g`
+        let is_once_closure = match value {
+            Expression::Closure {
+                capture_mode: CaptureMode::Once,
+                ..
+            } => true,
+            Expression::Ident(var) if self.once_closures.contains_key(var) => {
+                self.consume_once_closure(var, parent_span)?;
+                true
+            }
+            _ => false,
+        };
+
         // Register the new variable (or re-declare existing one)
         // If this is a re-declaration (shadowing), it makes the name valid again
         self.moved_vars.remove(name);
         self.valid_vars.insert(name.to_string());
+        if is_once_closure {
+            self.once_closures.insert(name.to_string(), self.loop_depth);
+        } else {
+            self.once_closures.remove(name);
+        }
 
         if let Some(t) = ty {
             self.var_types.insert(name.to_string(), t.clone());
         } else {
             // Infer type from the initializer expression
             let inferred_ty = match value {
-                Expression::StringLiteral(_) | Expression::FStringLiteral(_) => Some(Type::String),
-                Expression::IntLiteral(_) => Some(Type::I32),
-                Expression::TypedIntLiteral { type_suffix, .. } => {
-                    Some(match type_suffix.as_str() {
-                        "i8" => Type::I8,
-                        "i16" => Type::I16,
-                        "i32" => Type::I32,
-                        "i64" => Type::I64,
-                        "u8" => Type::U8,
-                        "u16" => Type::U16,
-                        "u32" => Type::U32,
-                        "u64" => Type::U64,
-                        _ => Type::I32,
-                    })
-                }
-                Expression::FloatLiteral(_) => Some(Type::F64),
-                Expression::BoolLiteral(_) => Some(Type::Bool),
                 Expression::Ident(var) => self.var_types.get(var).cloned(),
-                _ => None,
+                _ => literal_type(value),
             };
 
             if let Some(ty_val) = inferred_ty {
@@ -289,11 +322,13 @@ impl MoveChecker {
         body: &vex_ast::Block,
         parent_span: Option<&String>,
     ) -> BorrowResult<()> {
+        self.loop_depth += 1;
         self.check_expression(condition, parent_span)?;
 
         for stmt in &body.statements {
             self.check_statement(stmt, parent_span)?;
         }
+        self.loop_depth -= 1;
 
         Ok(())
     }
@@ -310,6 +345,7 @@ impl MoveChecker {
             self.check_statement(init_stmt, parent_span)?;
         }
 
+        self.loop_depth += 1;
         if let Some(cond) = condition {
             self.check_expression(cond, parent_span)?;
         }
@@ -321,6 +357,7 @@ impl MoveChecker {
         for stmt in &body.statements {
             self.check_statement(stmt, parent_span)?;
         }
+        self.loop_depth -= 1;
 
         Ok(())
     }
@@ -337,9 +374,11 @@ impl MoveChecker {
         // Loop variable is valid in the loop body
         self.valid_vars.insert(variable.to_string());
 
+        self.loop_depth += 1;
         for stmt in &body.statements {
             self.check_statement(stmt, parent_span)?;
         }
+        self.loop_depth -= 1;
 
         Ok(())
     }
@@ -1,14 +1,14 @@
 //! Type classification - determining Copy vs Move types
 
 use super::checker::MoveChecker;
-use vex_ast::Type;
+use vex_ast::{Expression, Type};
 
 impl MoveChecker {
     /// Determine if a type is Copy or Move
     ///
-    /// Copy types: primitive integers, floats, bools, references
+    /// Copy types: primitive integers, floats, bools, references, slices
     /// Move types: String, structs, enums, arrays (for now)
-    pub(super) fn is_move_type(&self, ty: &Type) -> bool {
+    pub(in crate::borrow_checker) fn is_move_type(&self, ty: &Type) -> bool {
         match ty {
             Type::Unknown => false, // Unknown type - assume non-Copy for safety
 
@@ -48,8 +48,11 @@ impl MoveChecker {
             Type::Named(_) => true,   // Assume move for now, will be refined with Copy trait
             Type::Generic { .. } => true,
 
-            // Arrays and slices are Move
-            Type::Array(_, _) | Type::Slice(_, _) | Type::ConstArray { .. } => true,
+            // Arrays are Move
+            Type::Array(_, _) | Type::ConstArray { .. } => true,
+
+            // Slices are views (data, len, elem_size) into storage they don't own
+            Type::Slice(_, _) => false,
 
             // Tuples are Move if any element is Move
             Type::Tuple(types) => types.iter().any(|t| self.is_move_type(t)),
@@ -60,11 +63,17 @@ impl MoveChecker {
             // Complex types are Move
             Type::Union(_) | Type::Intersection(_) | Type::Conditional { .. } => true,
 
+            // error owns its heap-allocated message (error.new)
+            Type::Error => true,
+
             // Unit type is Copy
-            Type::Unit | Type::Nil | Type::Error => false,
+            Type::Unit | Type::Nil => false,
 
             Type::Infer(_) => false, // Infer is only for type checking
 
+            // Const generic arguments are compile-time integers
+            Type::ConstValue(_) => false,
+
             // Never type is Copy (never instantiated)
             Type::Never => false,
 
@@ -74,9 +83,34 @@ impl MoveChecker {
             // Typeof is compile-time only, treated as Copy
             Type::Typeof(_) => false,
 
+            // Contract objects are a (data, vtable) pointer pair
+            Type::Dyn(_) => false,
+
             // Self type and associated types - resolve at compile time
             Type::SelfType => true, // Conservative: treat as Move until resolved
             Type::AssociatedType { .. } => true, // Conservative: treat as Move until resolved
         }
     }
 }
+
+/// Type of a literal initializer (`let s = "hi";`), used when a binding has no annotation
+pub(in crate::borrow_checker) fn literal_type(value: &Expression) -> Option<Type> {
+    match value {
+        Expression::StringLiteral(_) | Expression::FStringLiteral(_) => Some(Type::String),
+        Expression::IntLiteral(_) => Some(Type::I32),
+        Expression::TypedIntLiteral { type_suffix, .. } => Some(match type_suffix.as_str() {
+            "i8" => Type::I8,
+            "i16" => Type::I16,
+            "i32" => Type::I32,
+            "i64" => Type::I64,
+            "u8" => Type::U8,
+            "u16" => Type::U16,
+            "u32" => Type::U32,
+            "u64" => Type::U64,
+            _ => Type::I32,
+        }),
+        Expression::FloatLiteral(_) => Some(Type::F64),
+        Expression::BoolLiteral(_) => Some(Type::Bool),
+        _ => None,
+    }
+}
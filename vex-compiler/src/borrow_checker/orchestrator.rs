@@ -7,14 +7,17 @@ use crate::borrow_checker::errors::BorrowResult;
 use crate::borrow_checker::immutability::ImmutabilityChecker;
 use crate::borrow_checker::lifetimes::LifetimeChecker;
 use crate::borrow_checker::moves::MoveChecker;
-use vex_ast::{ImportKind, Item, Program};
+use std::collections::HashMap;
+use vex_ast::{ImportKind, Item, Program, Type};
 
 /// Main borrow checker that orchestrates all phases
 pub struct BorrowChecker {
     pub(super) immutability: ImmutabilityChecker,
-    moves: MoveChecker,
+    pub(super) moves: MoveChecker,
     borrows: BorrowRulesChecker,
     lifetimes: LifetimeChecker,
+    /// Variable types of the function being scanned for closures (Copy vs Move captures)
+    pub(super) closure_var_types: HashMap<String, Type>,
 }
 
 impl BorrowChecker {
@@ -24,6 +27,7 @@ impl BorrowChecker {
             moves: MoveChecker::new(),
             borrows: BorrowRulesChecker::new(),
             lifetimes: LifetimeChecker::new(),
+            closure_var_types: HashMap::new(),
         }
     }
 
@@ -109,6 +113,10 @@ impl BorrowChecker {
         // Phase 1: Check immutability violations
         self.immutability.check_program(program)?;
 
+        // Phase 1.5: Analyze closure capture modes (determine Callable/CallableMut/CallableOnce)
+        // Runs before moves and borrows, which enforce the modes at call and capture sites
+        self.analyze_closure_traits(program)?;
+
         // Phase 2: Check move semantics (use-after-move)
         self.moves.check_program(program)?;
 
@@ -118,9 +126,6 @@ impl BorrowChecker {
         // Phase 4: Lifetime analysis (dangling references)
         self.lifetimes.check_program(program)?;
 
-        // Phase 5: Analyze closure capture modes (determine Callable/CallableMut/CallableOnce)
-        self.analyze_closure_traits(program)?;
-
         Ok(())
     }
 }
@@ -2,6 +2,7 @@
 // Associated types resolution and substitution
 
 use super::ASTCodeGen;
+use std::collections::HashMap;
 use vex_ast::Type;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -16,7 +17,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         assoc_name: &str,
     ) -> Result<Type, String> {
         // 1. Get the concrete type name from Self or explicit type
-        let type_name = match self_type {
+        let (type_name, type_args) = match self_type {
             Type::SelfType => {
                 // In impl context, Self refers to the current struct
                 // Try to infer from current context
@@ -26,8 +27,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     assoc_name
                 ));
             }
-            Type::Named(name) => name.clone(),
-            Type::Generic { name, .. } => name.clone(),
+            Type::Named(name) => (name.clone(), Vec::new()),
+            Type::Generic { name, type_args } => (name.clone(), type_args.clone()),
+            Type::Reference(inner, _) => return self.resolve_associated_type(inner, assoc_name),
             _ => {
                 return Err(format!(
                     "Invalid base type for associated type: {:?}",
@@ -39,14 +41,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // 2. Look up the associated type binding
         let key = (type_name.clone(), assoc_name.to_string());
         if let Some(bound_type) = self.associated_type_bindings.get(&key) {
-            return Ok(bound_type.clone());
+            return Ok(self.instantiate_associated_type(&type_name, &type_args, bound_type));
         }
 
         // 3. Check if it's defined in the struct's AST definition
         if let Some(struct_def) = self.struct_ast_defs.get(&type_name) {
             for (name, bound_type) in &struct_def.associated_type_bindings {
                 if name == assoc_name {
-                    return Ok(bound_type.clone());
+                    return Ok(self.instantiate_associated_type(&type_name, &type_args, bound_type));
                 }
             }
         }
@@ -57,6 +59,29 @@ impl<'ctx> ASTCodeGen<'ctx> {
         ))
     }
 
+    /// A generic struct's binding with its type parameters replaced by `type_args`:
+    /// `type Item = T;` in Wrap<T> is i32 for Wrap<i32>
+    fn instantiate_associated_type(
+        &self,
+        type_name: &str,
+        type_args: &[Type],
+        bound_type: &Type,
+    ) -> Type {
+        let Some(struct_def) = self.struct_ast_defs.get(type_name) else {
+            return bound_type.clone();
+        };
+        if type_args.is_empty() || struct_def.type_params.is_empty() {
+            return bound_type.clone();
+        }
+        let type_subst: HashMap<String, Type> = struct_def
+            .type_params
+            .iter()
+            .zip(type_args)
+            .map(|(param, ty)| (param.name.clone(), ty.clone()))
+            .collect();
+        self.substitute_type(bound_type, &type_subst)
+    }
+
     /// Register associated type bindings from a struct's trait implementation
     /// Called when registering a struct that implements traits with associated types
     pub(crate) fn register_associated_type_bindings(
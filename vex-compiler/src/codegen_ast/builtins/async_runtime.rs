@@ -191,9 +191,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Return YIELDED
         let yielded_status = self.context.i32_type().const_int(1, false);
-        self.builder
+        let yield_ret = self
+            .builder
             .build_return(Some(&yielded_status))
             .map_err(|e| format!("Failed to build yield return: {}", e))?;
+        self.record_async_yield(yield_ret, resume_block, &[]);
 
         // Position at resume block
         self.builder.position_at_end(resume_block);
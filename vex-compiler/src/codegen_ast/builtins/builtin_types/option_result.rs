@@ -1,10 +1,10 @@
 // Option and Result type constructors
 
-use crate::codegen_ast::ASTCodeGen;
+use crate::codegen_ast::{ASTCodeGen, OPTION_NONE_TAG, OPTION_SOME_TAG};
 use inkwell::values::BasicValueEnum;
 
 /// Builtin: Some(value: T) -> Option<T>
-/// Creates Option<T> with Some variant (tag=1, value)
+/// Creates Option<T> with Some variant (tag=0, value)
 /// Memory layout: { u8 tag, T value }
 pub fn builtin_option_some<'ctx>(
     codegen: &mut ASTCodeGen<'ctx>,
@@ -33,12 +33,12 @@ pub fn builtin_option_some<'ctx>(
         .build_alloca(option_type, "option_some")
         .map_err(|e| format!("Failed to allocate Option<T>: {}", e))?;
 
-    // Set tag = 1 (Some)
+    // Set tag = 0 (Some)
     let tag_ptr = codegen
         .builder
         .build_struct_gep(option_type, option_ptr, 0, "tag_ptr")
         .map_err(|e| format!("Failed to get tag pointer: {}", e))?;
-    let tag_val = codegen.context.i32_type().const_int(1, false);
+    let tag_val = codegen.context.i32_type().const_int(OPTION_SOME_TAG, false);
     codegen
         .builder
         .build_store(tag_ptr, tag_val)
@@ -64,7 +64,7 @@ pub fn builtin_option_some<'ctx>(
 }
 
 /// Builtin: None -> Option<T>
-/// Creates Option<T> with None variant (tag=0, no value)
+/// Creates Option<T> with None variant (tag=1, no value)
 /// Memory layout: { u8 tag, T padding }
 pub fn builtin_option_none<'ctx>(
     codegen: &mut ASTCodeGen<'ctx>,
@@ -100,12 +100,12 @@ pub fn builtin_option_none<'ctx>(
         .build_alloca(option_type, "option_none")
         .map_err(|e| format!("Failed to allocate Option<T>: {}", e))?;
 
-    // Set tag = 0 (None)
+    // Set tag = 1 (None)
     let tag_ptr = codegen
         .builder
         .build_struct_gep(option_type, option_ptr, 0, "tag_ptr")
         .map_err(|e| format!("Failed to get tag pointer: {}", e))?;
-    let tag_val = codegen.context.i32_type().const_int(0, false);
+    let tag_val = codegen.context.i32_type().const_int(OPTION_NONE_TAG, false);
     codegen
         .builder
         .build_store(tag_ptr, tag_val)
@@ -1,9 +1,10 @@
 //! Built-in functions for Vex channels (MPSC queue).
 
 use inkwell::module::Linkage;
-use inkwell::types::{PointerType, StructType};
-use inkwell::values::FunctionValue;
+use inkwell::types::{BasicType, BasicTypeEnum, PointerType, StructType};
+use inkwell::values::{BasicValueEnum, FunctionValue, PointerValue};
 use inkwell::AddressSpace;
+use vex_ast::{Expression, Type};
 
 use crate::codegen_ast::ASTCodeGen;
 
@@ -14,6 +15,7 @@ const CHANNEL_TYPE_NAME: &str = "struct.VexChannel";
 const CREATE_FN: &str = "vex_channel_create";
 const SEND_FN: &str = "vex_channel_send";
 const RECV_FN: &str = "vex_channel_recv";
+const SELECT_FN: &str = "vex_channel_select";
 
 /// Builtin: Channel.new<T>(capacity: i64) -> Channel<T>
 pub fn builtin_channel_new<'ctx>(
@@ -60,10 +62,21 @@ pub(super) fn builtin_channel_send<'ctx>(
         ));
     }
     let channel_ptr = args[0].into_pointer_value();
+
+    // Box the value on the heap: the receiver may run on another worker after
+    // this frame is gone, so a stack slot would dangle.
+    let value_size = args[1]
+        .get_type()
+        .size_of()
+        .ok_or_else(|| "Channel.send: cannot compute size of value".to_string())?;
+    let malloc_fn = codegen.get_or_declare_malloc();
     let value_ptr = codegen
         .builder
-        .build_alloca(args[1].get_type(), "send_val_alloca")
-        .map_err(|e| e.to_string())?;
+        .build_call(malloc_fn, &[value_size.into()], "send_val_box")
+        .map_err(|e| e.to_string())?
+        .try_as_basic_value()
+        .unwrap_basic()
+        .into_pointer_value();
     codegen
         .builder
         .build_store(value_ptr, args[1])
@@ -104,46 +117,120 @@ pub(super) fn builtin_channel_recv<'ctx>(
         ));
     }
     let channel_ptr = args[0].into_pointer_value();
-    let i8_ptr_ptr_type = codegen.context.ptr_type(AddressSpace::default());
-    let recv_val_alloca = codegen
-        .builder
-        .build_alloca(i8_ptr_ptr_type, "recv_val_alloca")
-        .map_err(|e| e.to_string())?;
 
-    let recv_fn = codegen.get_or_declare_vex_channel_recv();
-    let _status = codegen
-        .builder
-        .build_call(
-            recv_fn,
-            &[channel_ptr.into(), recv_val_alloca.into()],
-            "recv_status",
-        )
-        .map_err(|e| e.to_string())?
-        .try_as_basic_value()
-        .unwrap_basic()
-        .into_int_value();
+    // The static form carries no element type: values come back as i64,
+    // matching channel_element_type() for untyped channels
+    let i64_type = codegen.context.i64_type().into();
+    codegen.build_channel_recv(channel_ptr, i64_type)
+}
 
-    let received_ptr = codegen
-        .builder
-        .build_load(i8_ptr_ptr_type, recv_val_alloca, "loaded_recv_ptr")
-        .map_err(|e| e.to_string())?
-        .into_pointer_value();
+impl<'ctx> ASTCodeGen<'ctx> {
+    // --- Typed Send / Receive ---
 
-    // For now, let's assume we are dealing with simple types and just return the pointer.
-    // A full implementation would need to know the type `T` to load correctly.
-    // This is a placeholder.
-    let i64_val = codegen
-        .builder
-        .build_ptr_to_int(received_ptr, codegen.context.i64_type(), "ptr_to_int")
-        .map_err(|e| e.to_string())?;
+    /// Element type T of a Channel<T> expression (i64 when unknown, matching Channel.recv)
+    pub(crate) fn channel_element_type(&self, channel: &Expression) -> Type {
+        match self.infer_expression_type(channel) {
+            Ok(Type::Channel(inner)) => *inner,
+            Ok(Type::Generic { name, type_args }) if name == "Channel" && type_args.len() == 1 => {
+                type_args[0].clone()
+            }
+            Ok(Type::Reference(inner, _)) => match *inner {
+                Type::Channel(elem) => *elem,
+                _ => Type::I64,
+            },
+            _ => Type::I64,
+        }
+    }
 
-    Ok(i64_val.into())
-}
+    /// Compile a value to send on a Channel<T>. Struct literals compile to
+    /// pointers; the channel carries the value itself, so those are loaded.
+    pub(crate) fn compile_channel_send_value(
+        &mut self,
+        value: &Expression,
+        elem_ty: &Type,
+        elem_llvm: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let val = self.compile_expression_with_type(value, Some(elem_ty))?;
+        if val.is_pointer_value() && !elem_llvm.is_pointer_type() {
+            return self
+                .builder
+                .build_load(elem_llvm, val.into_pointer_value(), "channel_send_load")
+                .map_err(|e| format!("Failed to load send value: {}", e));
+        }
+        Ok(val)
+    }
+
+    /// Receive one T: load it out of the sender's heap box and free the box.
+    /// A closed, drained channel yields the zero value.
+    pub(crate) fn build_channel_recv(
+        &mut self,
+        channel_ptr: PointerValue<'ctx>,
+        elem_llvm: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let fn_val = self.current_function.ok_or("No current function")?;
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        // vex_channel_recv leaves the slot untouched when the channel is closed
+        let recv_slot = self.create_entry_block_alloca_for_type(ptr_type.into(), "recv_slot")?;
+        self.builder
+            .build_store(recv_slot, ptr_type.const_null())
+            .map_err(|e| e.to_string())?;
+
+        let recv_fn = self.get_or_declare_vex_channel_recv();
+        self.builder
+            .build_call(
+                recv_fn,
+                &[channel_ptr.into(), recv_slot.into()],
+                "recv_status",
+            )
+            .map_err(|e| e.to_string())?;
+        let received = self
+            .builder
+            .build_load(ptr_type, recv_slot, "recv_box")
+            .map_err(|e| e.to_string())?
+            .into_pointer_value();
+
+        let value_bb = self.context.append_basic_block(fn_val, "recv.value");
+        let done_bb = self.context.append_basic_block(fn_val, "recv.done");
+        let closed_bb = self
+            .builder
+            .get_insert_block()
+            .ok_or("No insertion block for channel receive")?;
+        let is_closed = self
+            .builder
+            .build_is_null(received, "recv_closed")
+            .map_err(|e| e.to_string())?;
+        self.builder
+            .build_conditional_branch(is_closed, done_bb, value_bb)
+            .map_err(|e| e.to_string())?;
+
+        self.builder.position_at_end(value_bb);
+        let value = self.build_load_aligned(elem_llvm, received, "recv_value")?;
+        let free_fn = self.get_or_declare_free();
+        self.builder
+            .build_call(free_fn, &[received.into()], "recv_free")
+            .map_err(|e| e.to_string())?;
+        let value_end_bb = self
+            .builder
+            .get_insert_block()
+            .ok_or("No insertion block for channel receive")?;
+        self.builder
+            .build_unconditional_branch(done_bb)
+            .map_err(|e| e.to_string())?;
+
+        self.builder.position_at_end(done_bb);
+        let phi = self
+            .builder
+            .build_phi(elem_llvm, "recv_result")
+            .map_err(|e| e.to_string())?;
+        let zero = elem_llvm.const_zero();
+        phi.add_incoming(&[(&zero, closed_bb), (&value, value_end_bb)]);
+        Ok(phi.as_basic_value())
+    }
 
-impl<'ctx> ASTCodeGen<'ctx> {
     // --- Function Declarations ---
 
-    pub(super) fn get_or_declare_vex_channel_create(&self) -> FunctionValue<'ctx> {
+    pub(crate) fn get_or_declare_vex_channel_create(&self) -> FunctionValue<'ctx> {
         if let Some(func) = self.module.get_function(CREATE_FN) {
             return func;
         }
@@ -179,6 +266,35 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .add_function(RECV_FN, fn_type, Some(Linkage::External))
     }
 
+    pub(crate) fn get_or_declare_vex_channel_select(&self) -> FunctionValue<'ctx> {
+        if let Some(func) = self.module.get_function(SELECT_FN) {
+            return func;
+        }
+        // C signature: int64_t vex_channel_select(vex_select_case_t* cases, size_t count, bool blocking)
+        let cases_ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let bool_type = self.context.bool_type();
+        let fn_type = i64_type.fn_type(
+            &[cases_ptr_type.into(), i64_type.into(), bool_type.into()],
+            false,
+        );
+        self.module
+            .add_function(SELECT_FN, fn_type, Some(Linkage::External))
+    }
+
+    /// LLVM layout of `vex_select_case_t`: { vex_channel_t* chan, i32 op, void* data }
+    pub(crate) fn get_select_case_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        self.context.struct_type(
+            &[
+                ptr_type.into(),
+                self.context.i32_type().into(),
+                ptr_type.into(),
+            ],
+            false,
+        )
+    }
+
     // --- Type Helpers ---
 
     pub(super) fn get_channel_ptr_type(&self) -> PointerType<'ctx> {
@@ -544,6 +544,37 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.module.add_function(name, fn_type, None)
     }
 
+    /// Continue only if `ok` holds; otherwise call vex_panic(message)
+    pub(crate) fn build_runtime_check(
+        &mut self,
+        ok: inkwell::values::IntValue<'ctx>,
+        message: &str,
+    ) -> Result<(), String> {
+        let function = self.current_function.ok_or("No current function")?;
+        let pass_block = self.context.append_basic_block(function, "check.pass");
+        let fail_block = self.context.append_basic_block(function, "check.fail");
+        self.builder
+            .build_conditional_branch(ok, pass_block, fail_block)
+            .map_err(|e| format!("Failed to build runtime check: {}", e))?;
+
+        self.builder.position_at_end(fail_block);
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let panic_fn = self.declare_runtime_fn_void("vex_panic", &[ptr_type.into()]);
+        let msg = self
+            .builder
+            .build_global_string_ptr(message, "check_msg")
+            .map_err(|e| format!("Failed to build string: {}", e))?;
+        self.builder
+            .build_call(panic_fn, &[msg.as_pointer_value().into()], "")
+            .map_err(|e| format!("Failed to call vex_panic: {}", e))?;
+        self.builder
+            .build_unreachable()
+            .map_err(|e| format!("Failed to build unreachable: {}", e))?;
+
+        self.builder.position_at_end(pass_block);
+        Ok(())
+    }
+
     // ===== FMT LIBRARY DECLARATIONS =====
 
     /// strlen(str: *u8) -> i64
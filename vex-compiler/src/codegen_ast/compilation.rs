@@ -4,7 +4,9 @@
 use inkwell::types::BasicTypeEnum;
 use inkwell::values::BasicValueEnum;
 use inkwell::{
-    targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine},
+    targets::{
+        CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetData, TargetMachine,
+    },
     OptimizationLevel,
 };
 use std::path::Path;
@@ -60,6 +62,28 @@ impl<'ctx> super::ASTCodeGen<'ctx> {
             .map_err(|e| format!("Failed to write object file: {}", e))
     }
 
+    /// Data layout of the native target, the same machine compile_to_object
+    /// emits for. Falls back to LLVM's default layout when it is unavailable.
+    pub(crate) fn native_target_data() -> TargetData {
+        Target::initialize_native(&InitializationConfig::default())
+            .ok()
+            .and_then(|_| {
+                let target_triple = TargetMachine::get_default_triple();
+                Target::from_triple(&target_triple)
+                    .ok()?
+                    .create_target_machine(
+                        &target_triple,
+                        "generic",
+                        "",
+                        OptimizationLevel::Default,
+                        RelocMode::Default,
+                        CodeModel::Default,
+                    )
+            })
+            .map(|target_machine| target_machine.get_target_data())
+            .unwrap_or_else(|| TargetData::create(""))
+    }
+
     /// Optimize the module using LLVM PassManager (New Pass Manager for LLVM 16+)
     /// This enables Dead Code Elimination (DCE) and other optimizations
     pub fn optimize_module(
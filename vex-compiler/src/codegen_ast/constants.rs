@@ -1,7 +1,41 @@
 // Constant compilation for Vex
 use super::*;
+use crate::type_system::const_eval::{ConstEvalError, ConstEvaluator, ConstValue};
+use inkwell::module::Linkage;
 
 impl<'ctx> ASTCodeGen<'ctx> {
+    /// Evaluate every constant initializer at compile time.
+    /// Overflow, division by zero and calls to non-const functions are errors;
+    /// initializers the evaluator does not model are compiled by compile_const.
+    pub(crate) fn evaluate_constants(&mut self, program: &Program) -> Result<(), String> {
+        let mut evaluator = ConstEvaluator::new(program);
+        for (decl, result) in evaluator.eval_all() {
+            match result {
+                Ok(value) => {
+                    eprintln!("🧮 Folded constant {} = {:?}", decl.name, value);
+                    if let Some(size) = value.as_int().and_then(|n| i64::try_from(n).ok()) {
+                        self.const_sizes
+                            .insert(decl.name.clone(), Type::ConstValue(size));
+                    }
+                    self.const_values.insert(decl.name.clone(), value);
+                }
+                Err(ConstEvalError::Failed(message)) => {
+                    return Err(format!(
+                        "evaluation of constant `{}` failed: {}",
+                        decl.name, message
+                    ));
+                }
+                Err(ConstEvalError::NotConst(message)) => {
+                    return Err(format!("{} (initializer of `{}`)", message, decl.name));
+                }
+                Err(ConstEvalError::Unsupported) => {
+                    eprintln!("   Constant {} compiled from its initializer", decl.name);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Compile a constant declaration
     /// Constants are global immutable values
     pub fn compile_const(&mut self, const_decl: &Const) -> Result<(), String> {
@@ -9,6 +43,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
             "🔨 compile_const: {} type={:?}",
             const_decl.name, const_decl.ty
         );
+
+        // Folded at compile time: the initializer is the computed value
+        if let Some(folded) = self.const_values.get(&const_decl.name).cloned() {
+            let value = self.const_value_to_llvm(&folded, &const_decl.name)?;
+            let ty = match &const_decl.ty {
+                Some(Type::ConstArray { .. }) | None => folded.ty(),
+                Some(ty) => ty.clone(),
+            };
+            self.define_global_constant(&const_decl.name, value, Some(ty));
+            return Ok(());
+        }
+
         // Evaluate the constant expression with expected type for target-typed inference
         let value = if let Some(ref ty) = const_decl.ty {
             self.compile_expression_with_type(&const_decl.value, Some(ty))?
@@ -29,32 +75,112 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
+        self.define_global_constant(&const_decl.name, value, const_decl.ty.clone());
+        Ok(())
+    }
+
+    /// Emit `value` as a read-only global and register it for lookups
+    fn define_global_constant(
+        &mut self,
+        name: &str,
+        value: BasicValueEnum<'ctx>,
+        ty: Option<Type>,
+    ) {
         // ⚠️ CRITICAL FIX: Use value's actual type, not ast_type_to_llvm
         // The value already has the correct LLVM type from compile_expression
         let llvm_type = value.get_type();
 
         // Create a global constant
-        let global = self.module.add_global(llvm_type, None, &const_decl.name);
+        let global = self.module.add_global(llvm_type, None, name);
         global.set_initializer(&value);
         global.set_constant(true); // Mark as constant (immutable)
-        global.set_linkage(inkwell::module::Linkage::Internal); // Internal linkage
+        global.set_linkage(Linkage::Internal); // Internal linkage
 
         // Store in GLOBAL constants map (never cleared during function compilation)
         self.global_constants
-            .insert(const_decl.name.clone(), global.as_pointer_value());
+            .insert(name.to_string(), global.as_pointer_value());
         self.global_constant_types
-            .insert(const_decl.name.clone(), llvm_type);
+            .insert(name.to_string(), llvm_type);
 
         // ⭐ NEW: Also store constant value for namespace access (math.PI)
         // This allows field_access to resolve math.PI without loading from global
-        self.module_constants.insert(const_decl.name.clone(), value);
+        self.module_constants.insert(name.to_string(), value);
 
         // ⭐ NEW: Store AST type for proper type inference in println() etc.
-        if let Some(ref ty) = const_decl.ty {
-            self.module_constant_types
-                .insert(const_decl.name.clone(), ty.clone());
+        if let Some(ty) = ty {
+            self.module_constant_types.insert(name.to_string(), ty);
         }
+    }
 
-        Ok(())
+    /// LLVM constant for a folded value; string contents become private read-only data
+    fn const_value_to_llvm(
+        &mut self,
+        value: &ConstValue,
+        name: &str,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        match value {
+            ConstValue::Int(n, ty) => {
+                let int_type = self.ast_type_to_llvm(ty).into_int_type();
+                // Two's complement words, least significant first
+                let words = [*n as u64, (*n >> 64) as u64];
+                Ok(int_type.const_int_arbitrary_precision(&words).into())
+            }
+            ConstValue::Float(f, ty) => Ok(self
+                .ast_type_to_llvm(ty)
+                .into_float_type()
+                .const_float(*f)
+                .into()),
+            ConstValue::Bool(b) => Ok(self.context.bool_type().const_int(*b as u64, false).into()),
+            ConstValue::Str(s) => {
+                let bytes = self.context.const_string(s.as_bytes(), true);
+                let data = self
+                    .module
+                    .add_global(bytes.get_type(), None, &format!("{}.str", name));
+                data.set_initializer(&bytes);
+                data.set_constant(true);
+                data.set_linkage(Linkage::Private);
+                data.set_unnamed_addr(true);
+                Ok(data.as_pointer_value().into())
+            }
+            ConstValue::Array(elem, items) => {
+                let values = items
+                    .iter()
+                    .map(|item| self.const_value_to_llvm(item, name))
+                    .collect::<Result<Vec<_>, String>>()?;
+                let array = match self.ast_type_to_llvm(elem) {
+                    BasicTypeEnum::IntType(t) => t.const_array(
+                        &values
+                            .iter()
+                            .map(|v| v.into_int_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::FloatType(t) => t.const_array(
+                        &values
+                            .iter()
+                            .map(|v| v.into_float_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::PointerType(t) => t.const_array(
+                        &values
+                            .iter()
+                            .map(|v| v.into_pointer_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::ArrayType(t) => t.const_array(
+                        &values
+                            .iter()
+                            .map(|v| v.into_array_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    other => {
+                        return Err(format!(
+                            "Constant {}: unsupported array element type {:?}",
+                            name, other
+                        ))
+                    }
+                };
+                Ok(array.into())
+            }
+        }
     }
 }
@@ -0,0 +1,326 @@
+// Contract object layout
+// `dyn C` values are `{ ptr data, ptr vtable }`; the vtable is a constant array of
+// function pointers in the slot order of type_system::object_safety, one per
+// (type, contract) pair. `&dyn C` and `Box<dyn C>` share the same layout.
+
+use super::ASTCodeGen;
+use crate::type_system::object_safety;
+use inkwell::module::Linkage;
+use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::AddressSpace;
+use vex_ast::{Expression, Type};
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Contract of `dyn C`, `&dyn C` or `Box<dyn C>`, None for any other type
+    pub(crate) fn dyn_contract(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Dyn(contract) => Some(contract.clone()),
+            Type::Reference(inner, _) | Type::Box(inner) => match &**inner {
+                Type::Dyn(contract) => Some(contract.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// LLVM layout of a contract object: `{ ptr data, ptr vtable }`
+    pub(crate) fn dyn_llvm_type(&self) -> BasicTypeEnum<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        self.context
+            .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+            .into()
+    }
+
+    /// Method names of `contract` in vtable slot order
+    fn contract_slots(&self, contract: &str) -> Result<Vec<String>, String> {
+        let trait_def = self
+            .trait_defs
+            .get(contract)
+            .ok_or_else(|| format!("Unknown contract `{}` in `dyn {}`", contract, contract))?;
+        let lookup = |name: &str| self.trait_defs.get(name);
+
+        let violations = object_safety::object_safety_violations(trait_def, &lookup);
+        if !violations.is_empty() {
+            return Err(format!(
+                "Contract `{}` cannot be made into an object: {}",
+                contract,
+                violations.join("; ")
+            ));
+        }
+        Ok(object_safety::vtable_methods(trait_def, &lookup)?
+            .iter()
+            .map(|m| m.name.clone())
+            .collect())
+    }
+
+    /// Whether `type_name` implements `contract` directly or through a subcontract
+    fn implements_contract(&self, type_name: &str, contract: &str) -> bool {
+        let mut pending: Vec<String> = self
+            .trait_impls
+            .keys()
+            .filter(|(_, ty)| ty == type_name)
+            .map(|(trait_name, _)| trait_name.clone())
+            .collect();
+        if let Some(struct_def) = self.struct_ast_defs.get(type_name) {
+            pending.extend(struct_def.impl_traits.iter().map(|t| t.name.clone()));
+        }
+
+        let mut seen: Vec<String> = Vec::new();
+        while let Some(name) = pending.pop() {
+            if name == contract {
+                return true;
+            }
+            if seen.contains(&name) {
+                continue;
+            }
+            if let Some(trait_def) = self.trait_defs.get(&name) {
+                pending.extend(trait_def.super_traits.iter().cloned());
+            }
+            seen.push(name);
+        }
+        false
+    }
+
+    /// Vtable of `type_name` for `contract`, emitted on first use
+    pub(crate) fn contract_vtable(
+        &mut self,
+        type_name: &str,
+        contract: &str,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let key = (type_name.to_string(), contract.to_string());
+        if let Some(vtable) = self.contract_vtables.get(&key) {
+            return Ok(*vtable);
+        }
+
+        let slots = self.contract_slots(contract)?;
+        if !self.implements_contract(type_name, contract) {
+            return Err(format!(
+                "`{}` does not implement contract `{}`",
+                type_name, contract
+            ));
+        }
+
+        let mut entries: Vec<PointerValue<'ctx>> = Vec::with_capacity(slots.len());
+        for method in &slots {
+            let fn_name = self.resolve_method_name(type_name, method, &[])?;
+            let fn_val = *self
+                .functions
+                .get(&fn_name)
+                .ok_or_else(|| format!("Method function {} not found", fn_name))?;
+            let slot = self.vtable_slot(&fn_name, fn_val)?;
+            entries.push(slot.as_global_value().as_pointer_value());
+        }
+
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let table_type = ptr_type.array_type(entries.len() as u32);
+        let global = self.module.add_global(
+            table_type,
+            None,
+            &format!("__vtable_{}_{}", type_name, contract),
+        );
+        global.set_initializer(&ptr_type.const_array(&entries));
+        global.set_constant(true);
+        global.set_linkage(Linkage::Private);
+
+        let vtable = global.as_pointer_value();
+        self.contract_vtables.insert(key, vtable);
+        Ok(vtable)
+    }
+
+    /// Vtable entries take the receiver by pointer; methods declared with a by-value
+    /// receiver get a thunk that loads it first
+    fn vtable_slot(
+        &mut self,
+        fn_name: &str,
+        fn_val: FunctionValue<'ctx>,
+    ) -> Result<FunctionValue<'ctx>, String> {
+        let by_value_receiver = self
+            .function_defs
+            .get(fn_name)
+            .and_then(|def| def.receiver.as_ref())
+            .filter(|receiver| !matches!(receiver.ty, Type::Reference(..)))
+            .map(|receiver| self.ast_type_to_llvm(&receiver.ty))
+            .filter(|receiver_type| receiver_type.is_struct_type());
+        let Some(receiver_type) = by_value_receiver else {
+            return Ok(fn_val);
+        };
+
+        let thunk_name = format!("__dyn_thunk_{}", fn_name);
+        if let Some(thunk) = self.module.get_function(&thunk_name) {
+            return Ok(thunk);
+        }
+
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let fn_type = fn_val.get_type();
+        let mut param_types: Vec<BasicMetadataTypeEnum<'ctx>> = vec![ptr_type.into()];
+        param_types.extend(fn_type.get_param_types().into_iter().skip(1));
+        let thunk_type = match fn_type.get_return_type() {
+            Some(ret) => ret.fn_type(&param_types, false),
+            None => self.context.void_type().fn_type(&param_types, false),
+        };
+        let thunk = self
+            .module
+            .add_function(&thunk_name, thunk_type, Some(Linkage::Private));
+
+        let saved_block = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(thunk, "entry");
+        self.builder.position_at_end(entry);
+
+        let self_ptr = thunk
+            .get_nth_param(0)
+            .ok_or("Thunk receiver parameter missing")?
+            .into_pointer_value();
+        let receiver = self
+            .builder
+            .build_load(receiver_type, self_ptr, "dyn_self")
+            .map_err(|e| format!("Failed to load receiver in thunk: {}", e))?;
+        let mut args: Vec<BasicMetadataValueEnum<'ctx>> = vec![receiver.into()];
+        args.extend(thunk.get_param_iter().skip(1).map(BasicMetadataValueEnum::from));
+
+        let call_site = self
+            .builder
+            .build_call(fn_val, &args, "dyn_forward")
+            .map_err(|e| format!("Failed to forward thunk call: {}", e))?;
+        match call_site.try_as_basic_value().basic() {
+            Some(val) => self.builder.build_return(Some(&val)),
+            None => self.builder.build_return(None),
+        }
+        .map_err(|e| format!("Failed to return from thunk: {}", e))?;
+
+        if let Some(block) = saved_block {
+            self.builder.position_at_end(block);
+        }
+        Ok(thunk)
+    }
+
+    /// Compile `expr` where `dyn contract` is expected, pairing a pointer to the
+    /// value with its vtable. References and boxes keep pointing at their value;
+    /// plain values are moved to the heap so the object can outlive the expression.
+    pub(crate) fn compile_into_dyn(
+        &mut self,
+        expr: &Expression,
+        contract: &str,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let dyn_type = self.dyn_llvm_type();
+        let found = self
+            .infer_expression_type(expr)
+            .map(|ty| self.resolve_type(&ty))?;
+
+        // Already a contract object: variables, parameters, calls
+        if let Some(found_contract) = self.dyn_contract(&found) {
+            if found_contract != contract {
+                return Err(format!(
+                    "cannot convert `dyn {}` to `dyn {}`",
+                    found_contract, contract
+                ));
+            }
+            return match self.compile_expression(expr)? {
+                BasicValueEnum::PointerValue(ptr) => self
+                    .builder
+                    .build_load(dyn_type, ptr, "dyn_value")
+                    .map_err(|e| format!("Failed to load contract object: {}", e)),
+                value => Ok(value),
+            };
+        }
+
+        let (type_name, data) = match &found {
+            Type::Reference(inner, _) => {
+                let Type::Named(name) = &**inner else {
+                    return Err(format!(
+                        "`{}` cannot be used as `dyn {}`",
+                        self.type_to_string(&found),
+                        contract
+                    ));
+                };
+                let data = self.compile_expression(expr)?;
+                (name.clone(), self.value_address(data, "dyn_ref")?)
+            }
+            Type::Box(inner) => {
+                let Type::Named(name) = &**inner else {
+                    return Err(format!(
+                        "`{}` cannot be used as `dyn {}`",
+                        self.type_to_string(&found),
+                        contract
+                    ));
+                };
+                let box_type = self.ast_type_to_llvm(&found);
+                let boxed = match self.compile_expression(expr)? {
+                    BasicValueEnum::PointerValue(ptr) => self
+                        .builder
+                        .build_load(box_type, ptr, "dyn_box")
+                        .map_err(|e| format!("Failed to load box: {}", e))?,
+                    value => value,
+                };
+                let data = self
+                    .builder
+                    .build_extract_value(boxed.into_struct_value(), 0, "dyn_box_data")
+                    .map_err(|e| format!("Failed to read box pointer: {}", e))?;
+                (name.clone(), data.into_pointer_value())
+            }
+            Type::Named(name) if self.struct_defs.contains_key(name) => {
+                let struct_type = self.ast_type_to_llvm(&found);
+                let value = match self.compile_expression(expr)? {
+                    BasicValueEnum::PointerValue(ptr) => self
+                        .builder
+                        .build_load(struct_type, ptr, "dyn_source")
+                        .map_err(|e| format!("Failed to load struct value: {}", e))?,
+                    value => value,
+                };
+                let size = struct_type
+                    .size_of()
+                    .ok_or("Cannot box an unsized value into a contract object")?;
+                let malloc_fn = self.get_or_declare_malloc();
+                let heap_ptr = self
+                    .builder
+                    .build_call(malloc_fn, &[size.into()], "dyn_alloc")
+                    .map_err(|e| format!("Failed to call malloc for contract object: {}", e))?
+                    .try_as_basic_value()
+                    .unwrap_basic()
+                    .into_pointer_value();
+                self.builder
+                    .build_store(heap_ptr, value)
+                    .map_err(|e| format!("Failed to store contract object value: {}", e))?;
+                (name.clone(), heap_ptr)
+            }
+            other => {
+                return Err(format!(
+                    "`{}` cannot be used as `dyn {}`",
+                    self.type_to_string(other),
+                    contract
+                ))
+            }
+        };
+
+        let vtable = self.contract_vtable(&type_name, contract)?;
+        let object = self
+            .builder
+            .build_insert_value(dyn_type.into_struct_type().get_undef(), data, 0, "dyn_data")
+            .map_err(|e| format!("Failed to build contract object: {}", e))?
+            .into_struct_value();
+        let object = self
+            .builder
+            .build_insert_value(object, vtable, 1, "dyn_object")
+            .map_err(|e| format!("Failed to build contract object: {}", e))?;
+        Ok(object.into_struct_value().into())
+    }
+
+    /// Pointer to a value, spilling non-pointer values to a stack slot
+    fn value_address(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        name: &str,
+    ) -> Result<PointerValue<'ctx>, String> {
+        if let BasicValueEnum::PointerValue(ptr) = value {
+            return Ok(ptr);
+        }
+        let slot = self
+            .builder
+            .build_alloca(value.get_type(), name)
+            .map_err(|e| format!("Failed to allocate {}: {}", name, e))?;
+        self.builder
+            .build_store(slot, value)
+            .map_err(|e| format!("Failed to store {}: {}", name, e))?;
+        Ok(slot)
+    }
+}
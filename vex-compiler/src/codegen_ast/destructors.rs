@@ -1,11 +1,323 @@
 // Automatic destructor compilation for RAII
 // Implements Drop trait and scope-based cleanup
 
+use super::functions::move_scanner::MoveState;
 use super::ASTCodeGen;
-use inkwell::values::BasicValueEnum;
-use vex_ast::Type;
+use inkwell::values::{BasicValueEnum, PointerValue};
+use vex_ast::{Expression, Type};
+
+// Scope-entry tags for memory owned by make/new/error.new bindings.
+// The '#' prefix keeps them apart from real type names (Point_drop etc.)
+const HEAP_OBJECT_DROP: &str = "#heap_object"; // variable IS the malloc'd struct
+const HEAP_CELL_DROP: &str = "#heap_cell"; // variable holds a pointer to a malloc'd cell
+const SLICE_BUFFER_DROP: &str = "#slice_buffer"; // variable holds { data, len, elem_size }
+const ERROR_DROP: &str = "#error"; // variable holds a VexError*
+const FUTURE_DROP: &str = "#future"; // variable holds a VexFuture* returned by an async call
+const ENUM_PAYLOAD_DROP: &str = "#enum:"; // followed by the enum name; releases the active payload
 
 impl<'ctx> ASTCodeGen<'ctx> {
+    /// Register a let binding that owns memory allocated by new(), make([T], n)
+    /// or error.new() so it is released at scope exit
+    ///
+    /// Must run before register_variable(): entries drop in LIFO order, so a
+    /// user Drop impl on the same binding still sees live memory.
+    /// make(Channel<T>, n) is not registered - channels are shared with tasks.
+    /// A freshly constructed enum whose variants own Vec/Box/Drop payloads
+    /// releases whichever payload is active. A future from an async call that
+    /// is never awaited is released too (awaiting it moves it to the awaiter).
+    /// A binding moved on some path (let q = p, f(p), S { f: p }, return p) gets
+    /// a drop flag that the moving statement clears, so scope exit releases it
+    /// only when it still owns the memory. A binding captured by a closure, async
+    /// block or go is not registered: the capture owns the memory now.
+    pub(crate) fn register_builtin_drop(
+        &mut self,
+        name: &str,
+        value: &Expression,
+        var_type: &Type,
+    ) -> Result<(), String> {
+        // A shadowed binding's flag must not guard the new one
+        self.drop_flags.remove(name);
+
+        let state = match self.moved_locals.state(name, value) {
+            // Async resume functions re-enter at every await: an entry-block flag
+            // would not survive between resumptions
+            MoveState::MaybeMoved if self.current_async_resume_fn.is_some() => MoveState::Escaped,
+            state => state,
+        };
+        if state == MoveState::Escaped {
+            eprintln!(
+                "📌 '{}' is moved out of its binding - not released at scope exit",
+                name
+            );
+            return Ok(());
+        }
+
+        let tag = match value {
+            Expression::New(_) if matches!(var_type, Type::Reference(_, _)) => {
+                HEAP_CELL_DROP.to_string()
+            }
+            Expression::New(_) => HEAP_OBJECT_DROP.to_string(),
+            Expression::Make { element_type, .. } if !matches!(element_type, Type::Channel(_)) => {
+                SLICE_BUFFER_DROP.to_string()
+            }
+            Expression::ErrorNew(_) => ERROR_DROP.to_string(),
+            Expression::Call { .. } | Expression::MethodCall { .. }
+                if matches!(var_type, Type::Future(_)) =>
+            {
+                FUTURE_DROP.to_string()
+            }
+            Expression::EnumLiteral { enum_name, .. }
+                if self.enum_needs_payload_drop(enum_name) =>
+            {
+                format!("{}{}", ENUM_PAYLOAD_DROP, enum_name)
+            }
+            _ => return Ok(()),
+        };
+
+        if state == MoveState::MaybeMoved {
+            let function = self
+                .current_function
+                .ok_or_else(|| format!("No function for drop flag of {}", name))?;
+            let flag =
+                self.create_entry_block_alloca(&format!("{}_drop_flag", name), &Type::Bool, true)?;
+            self.builder
+                .build_store(flag, self.context.bool_type().const_int(1, false))
+                .map_err(|e| format!("Failed to set drop flag of {}: {}", name, e))?;
+            self.drop_flags.insert(name.to_string(), (function, flag));
+        }
+
+        if let Some(scope) = self.scope_stack.last_mut() {
+            scope.push((name.to_string(), tag.clone()));
+            eprintln!("📌 Registered '{}' for {} release at scope exit", name, tag);
+        }
+        Ok(())
+    }
+
+    /// Drop flag of `name` in the function being compiled, if it was moved on some path
+    fn current_drop_flag(&self, name: &str) -> Option<PointerValue<'ctx>> {
+        let (function, flag) = self.drop_flags.get(name)?;
+        (Some(*function) == self.current_function).then_some(*flag)
+    }
+
+    /// Clear the drop flags of the bindings `stmt` moves out of, before it runs
+    pub(crate) fn clear_moved_drop_flags(
+        &mut self,
+        stmt: &vex_ast::Statement,
+    ) -> Result<(), String> {
+        let live = self
+            .builder
+            .get_insert_block()
+            .is_some_and(|block| block.get_terminator().is_none());
+        if !live {
+            return Ok(());
+        }
+        let moved: Vec<String> = self.moved_locals.moved_by(stmt).to_vec();
+        for name in moved {
+            if let Some(flag) = self.current_drop_flag(&name) {
+                self.builder
+                    .build_store(flag, self.context.bool_type().const_zero())
+                    .map_err(|e| format!("Failed to clear drop flag of {}: {}", name, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop builtin ownership of a binding whose value escapes via `return p;`
+    /// A heap struct returned by value is copied out first, so its cell is still freed;
+    /// a returned enum hands its payload to the caller
+    pub(crate) fn forget_builtin_drop(
+        &mut self,
+        name: &str,
+        copied_out: bool,
+    ) -> Result<(), String> {
+        let forgotten = |tag: &str| match tag {
+            HEAP_OBJECT_DROP => !copied_out,
+            HEAP_CELL_DROP | SLICE_BUFFER_DROP | ERROR_DROP | FUTURE_DROP => true,
+            tag => tag.starts_with(ENUM_PAYLOAD_DROP),
+        };
+
+        // A flagged binding is still released on the paths that do not return it
+        if let Some(flag) = self.current_drop_flag(name) {
+            let owned_elsewhere = self
+                .scope_stack
+                .iter()
+                .flatten()
+                .any(|(var, tag)| var == name && forgotten(tag));
+            if owned_elsewhere {
+                self.builder
+                    .build_store(flag, self.context.bool_type().const_zero())
+                    .map_err(|e| format!("Failed to clear drop flag of {}: {}", name, e))?;
+            }
+            return Ok(());
+        }
+
+        for scope in self.scope_stack.iter_mut() {
+            scope.retain(|(var, tag)| var != name || !forgotten(tag));
+        }
+        Ok(())
+    }
+
+    /// Drop every scope-exit entry of a binding whose value was moved elsewhere,
+    /// e.g. bit-copied into a `go` task's environment: the new owner releases it
+    pub(crate) fn forget_moved_binding(&mut self, name: &str) {
+        for scope in self.scope_stack.iter_mut() {
+            scope.retain(|(var, _)| var != name);
+        }
+    }
+
+    /// Release the future of an async call used as a statement, e.g. `fetch();`
+    /// The coroutine keeps running; it frees the future once it completes
+    pub(crate) fn release_discarded_future(
+        &mut self,
+        expr: &Expression,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<(), String> {
+        if !matches!(
+            expr,
+            Expression::Call { .. } | Expression::MethodCall { .. }
+        ) {
+            return Ok(());
+        }
+        let (Ok(Type::Future(_)), BasicValueEnum::PointerValue(future)) =
+            (self.infer_expression_type(expr), value)
+        else {
+            return Ok(());
+        };
+        let free_fn = self.get_or_declare_future_fn("vex_future_free")?;
+        self.builder
+            .build_call(free_fn, &[future.into()], "")
+            .map_err(|e| format!("Failed to call vex_future_free: {}", e))?;
+        Ok(())
+    }
+
+    /// Release memory for a scope entry registered by register_builtin_drop()
+    /// Returns false if `tag` is not a builtin drop tag
+    pub(crate) fn call_builtin_drop(
+        &mut self,
+        var_name: &str,
+        var_ptr: PointerValue<'ctx>,
+        tag: &str,
+    ) -> Result<bool, String> {
+        let builtin = matches!(
+            tag,
+            HEAP_OBJECT_DROP | HEAP_CELL_DROP | SLICE_BUFFER_DROP | ERROR_DROP | FUTURE_DROP
+        ) || tag.starts_with(ENUM_PAYLOAD_DROP);
+        if !builtin {
+            return Ok(false);
+        }
+
+        let Some(flag) = self.current_drop_flag(var_name) else {
+            self.build_builtin_release(var_name, var_ptr, tag)?;
+            return Ok(true);
+        };
+
+        // Moved on some path: release only while the binding still owns the memory
+        let function = self
+            .current_function
+            .ok_or_else(|| format!("No function for drop of {}", var_name))?;
+        let owned = self
+            .builder
+            .build_load(
+                self.context.bool_type(),
+                flag,
+                &format!("{}_owned", var_name),
+            )
+            .map_err(|e| format!("Failed to load drop flag of {}: {}", var_name, e))?;
+        let release_bb = self
+            .context
+            .append_basic_block(function, &format!("{}_release", var_name));
+        let done_bb = self
+            .context
+            .append_basic_block(function, &format!("{}_released", var_name));
+        self.builder
+            .build_conditional_branch(owned.into_int_value(), release_bb, done_bb)
+            .map_err(|e| format!("Failed to branch on drop flag of {}: {}", var_name, e))?;
+
+        self.builder.position_at_end(release_bb);
+        self.build_builtin_release(var_name, var_ptr, tag)?;
+        self.builder
+            .build_unconditional_branch(done_bb)
+            .map_err(|e| format!("Failed to branch after drop of {}: {}", var_name, e))?;
+
+        self.builder.position_at_end(done_bb);
+        Ok(true)
+    }
+
+    fn build_builtin_release(
+        &mut self,
+        var_name: &str,
+        var_ptr: PointerValue<'ctx>,
+        tag: &str,
+    ) -> Result<(), String> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+
+        match tag {
+            HEAP_OBJECT_DROP => {
+                let free_fn = self.get_or_declare_free();
+                self.builder
+                    .build_call(free_fn, &[var_ptr.into()], "")
+                    .map_err(|e| format!("Failed to free {}: {}", var_name, e))?;
+            }
+            HEAP_CELL_DROP => {
+                let cell = self
+                    .builder
+                    .build_load(ptr_type, var_ptr, &format!("{}_cell", var_name))
+                    .map_err(|e| format!("Failed to load {} for drop: {}", var_name, e))?;
+                let free_fn = self.get_or_declare_free();
+                self.builder
+                    .build_call(free_fn, &[cell.into()], "")
+                    .map_err(|e| format!("Failed to free {}: {}", var_name, e))?;
+            }
+            SLICE_BUFFER_DROP => {
+                let slice_type = *self
+                    .variable_types
+                    .get(var_name)
+                    .ok_or_else(|| format!("Type for slice {} not found", var_name))?;
+                let slice = self
+                    .builder
+                    .build_load(slice_type, var_ptr, &format!("{}_slice", var_name))
+                    .map_err(|e| format!("Failed to load {} for drop: {}", var_name, e))?;
+                let data = self
+                    .builder
+                    .build_extract_value(slice.into_struct_value(), 0, "slice_data")
+                    .map_err(|e| format!("Failed to extract slice data: {}", e))?;
+                let free_fn = self.get_or_declare_free();
+                self.builder
+                    .build_call(free_fn, &[data.into()], "")
+                    .map_err(|e| format!("Failed to free {}: {}", var_name, e))?;
+            }
+            ERROR_DROP => {
+                let error = self
+                    .builder
+                    .build_load(ptr_type, var_ptr, &format!("{}_error", var_name))
+                    .map_err(|e| format!("Failed to load {} for drop: {}", var_name, e))?;
+                let error_free_fn =
+                    self.declare_runtime_fn_void("vex_error_free", &[ptr_type.into()]);
+                self.builder
+                    .build_call(error_free_fn, &[error.into()], "")
+                    .map_err(|e| format!("Failed to call vex_error_free: {}", e))?;
+            }
+            FUTURE_DROP => {
+                let future = self
+                    .builder
+                    .build_load(ptr_type, var_ptr, &format!("{}_future", var_name))
+                    .map_err(|e| format!("Failed to load {} for drop: {}", var_name, e))?;
+                let free_fn = self.get_or_declare_future_fn("vex_future_free")?;
+                self.builder
+                    .build_call(free_fn, &[future.into()], "")
+                    .map_err(|e| format!("Failed to call vex_future_free: {}", e))?;
+            }
+            _ => {
+                let enum_name = tag
+                    .strip_prefix(ENUM_PAYLOAD_DROP)
+                    .ok_or_else(|| format!("Unknown drop tag {} for {}", tag, var_name))?;
+                self.build_enum_payload_drop(enum_name, var_ptr)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Register a variable for automatic Drop call at scope exit
     /// Called from register_variable() when variable implements Drop
     pub(crate) fn register_drop_variable(&mut self, name: &str, value_type: &Type) {
@@ -82,7 +394,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Check if a type implements Drop trait
-    fn type_has_drop(&self, ty: &Type) -> bool {
+    pub(crate) fn type_has_drop(&self, ty: &Type) -> bool {
         match ty {
             Type::Vec(_) => true,
             Type::Box(_) => true,
@@ -98,7 +410,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Call Vec drop
-    fn call_vec_drop(&mut self, value: BasicValueEnum<'ctx>, _inner: &Type) -> Result<(), String> {
+    pub(crate) fn call_vec_drop(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        _inner: &Type,
+    ) -> Result<(), String> {
         // Vec drop signature: fn drop(self: &Vec<T>)
         let drop_fn = self.get_or_declare_vec_drop()?;
 
@@ -110,7 +426,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Call Box drop
-    fn call_box_drop(&mut self, value: BasicValueEnum<'ctx>, _inner: &Type) -> Result<(), String> {
+    pub(crate) fn call_box_drop(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        _inner: &Type,
+    ) -> Result<(), String> {
         // Box drop signature: fn drop(self: &Box<T>)
         let drop_fn = self.get_or_declare_box_drop()?;
 
@@ -122,7 +442,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Call struct drop method
-    fn call_struct_drop(
+    pub(crate) fn call_struct_drop(
         &mut self,
         value: BasicValueEnum<'ctx>,
         struct_name: &str,
@@ -138,6 +458,29 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(())
     }
 
+    /// Heap `String` (a `vex_string_t*`), as opposed to the borrowed C `string`
+    pub(crate) fn is_heap_string(ty: &Type) -> bool {
+        matches!(ty, Type::Named(name) | Type::Generic { name, .. } if name == "String")
+    }
+
+    /// Release a heap `String` with vex_string_free (NULL is a no-op)
+    pub(crate) fn call_string_drop(&mut self, value: BasicValueEnum<'ctx>) -> Result<(), String> {
+        let free_fn = match self.module.get_function("vex_string_free") {
+            Some(func) => func,
+            None => {
+                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+                let fn_type = self.context.void_type().fn_type(&[ptr_type.into()], false);
+                self.module.add_function("vex_string_free", fn_type, None)
+            }
+        };
+
+        self.builder
+            .build_call(free_fn, &[value.into()], "")
+            .map_err(|e| format!("Failed to call vex_string_free: {}", e))?;
+
+        Ok(())
+    }
+
     /// Get or declare Vec drop function
     fn get_or_declare_vec_drop(&mut self) -> Result<inkwell::values::FunctionValue<'ctx>, String> {
         if let Some(func) = self.functions.get("Vec_drop") {
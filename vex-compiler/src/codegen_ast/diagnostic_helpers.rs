@@ -278,6 +278,7 @@ mod tests {
             vex_ast::Struct {
                 is_exported: false,
                 span_id: None,
+                attributes: vec![],
                 name: "Vec".to_string(),
                 type_params: vec![],
                 const_params: vec![],
@@ -27,6 +27,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     continue;
                 };
 
+                // make/new/error.new allocations are freed directly
+                if self.call_builtin_drop(var_name, var_ptr, type_name)? {
+                    continue;
+                }
+
                 // Get drop method name
                 let drop_method = format!("{}_drop", type_name);
 
@@ -0,0 +1,422 @@
+// Data-carrying enum layout
+// Tag + aligned union storage, positional and named payload access,
+// active-payload drop and the Option niche for references
+
+use super::ASTCodeGen;
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use vex_ast::{Enum, EnumVariant, Type};
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// LLVM layout of a user enum
+    ///
+    /// Unit-only enums are a bare i32 tag. Enums with data are `{ i32, U }`
+    /// where U is the payload type when every variant carries the same one,
+    /// and otherwise `[N x iA]`: an array of the widest payload alignment,
+    /// long enough for the largest payload.
+    pub(crate) fn enum_llvm_type(&self, enum_def: &Enum) -> BasicTypeEnum<'ctx> {
+        let tag_type = self.context.i32_type();
+        let payloads: Vec<BasicTypeEnum<'ctx>> = enum_def
+            .variants
+            .iter()
+            .filter_map(|variant| self.enum_variant_payload_type(variant))
+            .collect();
+
+        match self.shared_payload_storage(&payloads) {
+            Some(storage) => self
+                .context
+                .struct_type(&[tag_type.into(), storage], false)
+                .into(),
+            None => tag_type.into(),
+        }
+    }
+
+    /// Storage that can hold any of `payloads`: the payload itself when all
+    /// are the same type, otherwise an aligned byte array (None when empty)
+    pub(crate) fn shared_payload_storage(
+        &self,
+        payloads: &[BasicTypeEnum<'ctx>],
+    ) -> Option<BasicTypeEnum<'ctx>> {
+        let first = *payloads.first()?;
+        if payloads.iter().all(|payload| *payload == first) {
+            return Some(first);
+        }
+
+        let (size, align) = payloads
+            .iter()
+            .map(|payload| self.abi_size_align(*payload))
+            .fold((0, 1), |(size, align), (s, a)| (size.max(s), align.max(a)));
+        let unit = self.context.custom_width_int_type((align * 8) as u32);
+        Some(unit.array_type(size.div_ceil(align) as u32).into())
+    }
+
+    /// Payload of one variant: None for unit variants, T for `V(T)` and an
+    /// anonymous struct for `V(T1, T2, ...)`
+    pub(crate) fn enum_variant_payload_type(
+        &self,
+        variant: &EnumVariant,
+    ) -> Option<BasicTypeEnum<'ctx>> {
+        match variant.data.as_slice() {
+            [] => None,
+            [single] => Some(self.ast_type_to_llvm(single)),
+            fields => {
+                let field_types: Vec<BasicTypeEnum> =
+                    fields.iter().map(|ty| self.ast_type_to_llvm(ty)).collect();
+                Some(self.context.struct_type(&field_types, false).into())
+            }
+        }
+    }
+
+    /// ABI size and alignment in bytes on the native target
+    pub(crate) fn abi_size_align(&self, ty: BasicTypeEnum<'ctx>) -> (u64, u64) {
+        (
+            self.target_data.get_abi_size(&ty),
+            self.target_data.get_abi_alignment(&ty) as u64,
+        )
+    }
+
+    /// Build an enum value from its tag and (for data variants) its payload
+    ///
+    /// Bytes of the union not covered by the payload are zeroed, so two
+    /// values of the same variant never differ in their padding.
+    pub(crate) fn build_enum_value(
+        &self,
+        enum_type: BasicTypeEnum<'ctx>,
+        tag: u64,
+        payload: Option<BasicValueEnum<'ctx>>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let tag_value = self.context.i32_type().const_int(tag, false);
+        let struct_type = match enum_type {
+            BasicTypeEnum::StructType(struct_type) => struct_type,
+            _ => return Ok(tag_value.into()),
+        };
+
+        let slot = self
+            .builder
+            .build_alloca(struct_type, "enum_slot")
+            .map_err(|e| format!("Failed to allocate enum value: {}", e))?;
+        self.builder
+            .build_store(slot, struct_type.const_zero())
+            .map_err(|e| format!("Failed to zero enum value: {}", e))?;
+
+        let tag_ptr = self
+            .builder
+            .build_struct_gep(struct_type, slot, 0, "enum_tag_ptr")
+            .map_err(|e| format!("Failed to get tag pointer: {}", e))?;
+        self.builder
+            .build_store(tag_ptr, tag_value)
+            .map_err(|e| format!("Failed to store tag: {}", e))?;
+
+        if let Some(payload) = payload {
+            let payload_ptr = self
+                .builder
+                .build_struct_gep(struct_type, slot, 1, "enum_payload_ptr")
+                .map_err(|e| format!("Failed to get payload pointer: {}", e))?;
+            self.builder
+                .build_store(payload_ptr, payload)
+                .map_err(|e| format!("Failed to store payload: {}", e))?;
+        }
+
+        self.builder
+            .build_load(struct_type, slot, "enum_value")
+            .map_err(|e| format!("Failed to load enum value: {}", e))
+    }
+
+    /// Read the payload of `variant` out of an enum value
+    ///
+    /// The union is reinterpreted through memory when the variant's payload
+    /// type differs from the storage type. Niche-packed Options are their
+    /// own payload.
+    pub(crate) fn extract_enum_payload(
+        &self,
+        enum_name: &str,
+        variant: &str,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        if enum_name == "Option" && Self::is_niche_option_value(value) {
+            return Ok(value);
+        }
+
+        let enum_val = match value {
+            BasicValueEnum::StructValue(enum_val) => enum_val,
+            other => return Ok(other),
+        };
+        let storage_type = enum_val
+            .get_type()
+            .get_field_type_at_index(1)
+            .ok_or_else(|| format!("Enum {} value has no payload field", enum_name))?;
+
+        // Builtin and generic enums store the payload as written
+        let payload_type = self
+            .enum_ast_defs
+            .get(enum_name)
+            .filter(|def| {
+                def.type_params.is_empty() && enum_name != "Option" && enum_name != "Result"
+            })
+            .and_then(|def| def.variants.iter().find(|v| v.name == variant))
+            .and_then(|v| self.enum_variant_payload_type(v));
+
+        match payload_type {
+            Some(payload_type) if payload_type != storage_type => {
+                let slot = self
+                    .builder
+                    .build_alloca(enum_val.get_type(), "enum_payload_view")
+                    .map_err(|e| format!("Failed to allocate enum view: {}", e))?;
+                self.builder
+                    .build_store(slot, enum_val)
+                    .map_err(|e| format!("Failed to store enum view: {}", e))?;
+                let payload_ptr = self
+                    .builder
+                    .build_struct_gep(enum_val.get_type(), slot, 1, "enum_payload_ptr")
+                    .map_err(|e| format!("Failed to get payload pointer: {}", e))?;
+                self.builder
+                    .build_load(payload_type, payload_ptr, "enum_data")
+                    .map_err(|e| format!("Failed to load enum payload: {}", e))
+            }
+            _ => self
+                .builder
+                .build_extract_value(enum_val, 1, "enum_data")
+                .map_err(|e| format!("Failed to extract enum data: {}", e)),
+        }
+    }
+
+    /// Declared payload position of each written item of `variant`
+    ///
+    /// Tuple variants are written positionally. Struct-like variants are
+    /// written with field names in any order (`Shape.Rect { h: 1.0, w: 2.0 }`),
+    /// so item i goes to the position of `field_names[i]`.
+    pub(crate) fn enum_payload_positions(
+        &self,
+        enum_name: &str,
+        variant: &str,
+        field_names: &[String],
+        item_count: usize,
+    ) -> Result<Vec<usize>, String> {
+        let variant_def = self
+            .enum_ast_defs
+            .get(enum_name)
+            .and_then(|def| def.variants.iter().find(|v| v.name == variant));
+        let is_struct_like = variant_def.is_some_and(|v| v.is_struct_like());
+
+        match variant_def {
+            Some(variant_def) if !field_names.is_empty() => {
+                if !is_struct_like {
+                    return Err(format!(
+                        "{}.{} is a tuple variant and has no named fields",
+                        enum_name, variant
+                    ));
+                }
+                let mut positions = Vec::with_capacity(field_names.len());
+                for name in field_names {
+                    let index = variant_def.field_index(name).ok_or_else(|| {
+                        format!("{}.{} has no field named '{}'", enum_name, variant, name)
+                    })?;
+                    if positions.contains(&index) {
+                        return Err(format!(
+                            "Field '{}' of {}.{} is specified more than once",
+                            name, enum_name, variant
+                        ));
+                    }
+                    positions.push(index);
+                }
+                Ok(positions)
+            }
+            None if !field_names.is_empty() => {
+                Err(format!("{}.{} has no named fields", enum_name, variant))
+            }
+            _ if is_struct_like && item_count > 0 => Err(format!(
+                "{}.{} has named fields: write {}.{} {{ {}: ... }}",
+                enum_name,
+                variant,
+                enum_name,
+                variant,
+                variant_def
+                    .map(|v| v.field_names.join(": ..., "))
+                    .unwrap_or_default()
+            )),
+            _ => Ok((0..item_count).collect()),
+        }
+    }
+
+    /// Option<&T> reuses the never-null pointer as the tag. Only thin
+    /// references qualify: `&dyn T` and Box<T> are `{ ptr, .. }` structs,
+    /// which can't be told apart from a user struct starting with a pointer.
+    pub(crate) fn option_uses_niche(inner: &Type) -> bool {
+        matches!(inner, Type::Reference(target, _) if !matches!(**target, Type::Dyn(_)))
+    }
+
+    /// A niche-packed Option is a bare pointer - a tagged Option always
+    /// starts with its i32 tag
+    pub(crate) fn is_niche_option_value(value: BasicValueEnum<'ctx>) -> bool {
+        value.is_pointer_value()
+    }
+
+    /// Tag of a niche-packed Option: Some (0) when the pointer is set, None (1) when null
+    pub(crate) fn niche_option_tag(
+        &self,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let BasicValueEnum::PointerValue(pointer) = value else {
+            return Err(format!(
+                "{:?} is not a niche-packed Option",
+                value.get_type()
+            ));
+        };
+        let is_null = self
+            .builder
+            .build_is_null(pointer, "niche_is_none")
+            .map_err(|e| format!("Failed to test niche pointer: {}", e))?;
+        self.builder
+            .build_int_z_extend(is_null, self.context.i32_type(), "niche_tag")
+            .map_err(|e| format!("Failed to widen niche tag: {}", e))
+    }
+
+    /// Whether any variant of `enum_name` owns heap memory that must be released
+    pub(crate) fn enum_needs_payload_drop(&self, enum_name: &str) -> bool {
+        self.enum_ast_defs
+            .get(enum_name)
+            .filter(|def| def.type_params.is_empty())
+            .map(|def| {
+                def.variants
+                    .iter()
+                    .flat_map(|v| v.data.iter())
+                    .any(|ty| self.payload_field_needs_drop(ty, enum_name))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Payload fields are released with the same drops as bindings: Vec, Box,
+    /// heap `String` and structs with a drop method; enums with owning payloads
+    /// recursively. `string` payloads are borrowed C strings (literals live in
+    /// globals) and are left alone.
+    fn payload_field_needs_drop(&self, ty: &Type, outer_enum: &str) -> bool {
+        match ty {
+            Type::String => false,
+            _ if Self::is_heap_string(ty) => true,
+            Type::Named(name) if name == outer_enum => false,
+            Type::Named(name) if self.enum_needs_payload_drop(name) => true,
+            _ => self.type_has_drop(ty),
+        }
+    }
+
+    /// Release the payload of the active variant of the enum stored at `enum_ptr`
+    pub(crate) fn build_enum_payload_drop(
+        &mut self,
+        enum_name: &str,
+        enum_ptr: PointerValue<'ctx>,
+    ) -> Result<(), String> {
+        let enum_def = self
+            .enum_ast_defs
+            .get(enum_name)
+            .cloned()
+            .ok_or_else(|| format!("Enum {} not found for drop", enum_name))?;
+        let enum_type = match self.enum_llvm_type(&enum_def) {
+            BasicTypeEnum::StructType(struct_type) => struct_type,
+            _ => return Ok(()), // unit-only enums own nothing
+        };
+        let function = self.current_function.ok_or("Enum drop outside function")?;
+
+        let tag_ptr = self
+            .builder
+            .build_struct_gep(enum_type, enum_ptr, 0, "drop_tag_ptr")
+            .map_err(|e| format!("Failed to get tag pointer: {}", e))?;
+        let tag = self
+            .builder
+            .build_load(self.context.i32_type(), tag_ptr, "drop_tag")
+            .map_err(|e| format!("Failed to load tag for drop: {}", e))?
+            .into_int_value();
+        let payload_ptr = self
+            .builder
+            .build_struct_gep(enum_type, enum_ptr, 1, "drop_payload_ptr")
+            .map_err(|e| format!("Failed to get payload pointer: {}", e))?;
+
+        let done_block = self
+            .context
+            .append_basic_block(function, &format!("{}_drop_done", enum_name));
+        let mut cases = Vec::new();
+        let mut variant_blocks = Vec::new();
+        for (tag_index, variant) in enum_def.variants.iter().enumerate() {
+            if variant
+                .data
+                .iter()
+                .any(|ty| self.payload_field_needs_drop(ty, enum_name))
+            {
+                let block = self
+                    .context
+                    .append_basic_block(function, &format!("{}_drop_{}", enum_name, variant.name));
+                cases.push((
+                    self.context.i32_type().const_int(tag_index as u64, false),
+                    block,
+                ));
+                variant_blocks.push((variant, block));
+            }
+        }
+        self.builder
+            .build_switch(tag, done_block, &cases)
+            .map_err(|e| format!("Failed to build enum drop switch: {}", e))?;
+
+        for (variant, block) in variant_blocks {
+            self.builder.position_at_end(block);
+            let payload_type = self
+                .enum_variant_payload_type(variant)
+                .ok_or("Data variant without payload type")?;
+            for (i, field_ty) in variant.data.iter().enumerate() {
+                if !self.payload_field_needs_drop(field_ty, enum_name) {
+                    continue;
+                }
+                let field_ptr = if variant.data.len() == 1 {
+                    payload_ptr
+                } else {
+                    self.builder
+                        .build_struct_gep(
+                            payload_type.into_struct_type(),
+                            payload_ptr,
+                            i as u32,
+                            &format!("drop_field_{}", i),
+                        )
+                        .map_err(|e| format!("Failed to get payload field pointer: {}", e))?
+                };
+                self.build_payload_field_drop(field_ptr, field_ty)?;
+            }
+            self.builder
+                .build_unconditional_branch(done_block)
+                .map_err(|e| format!("Failed to branch after enum drop: {}", e))?;
+        }
+
+        self.builder.position_at_end(done_block);
+        Ok(())
+    }
+
+    /// Release one owning payload field stored at `field_ptr`
+    fn build_payload_field_drop(
+        &mut self,
+        field_ptr: PointerValue<'ctx>,
+        field_ty: &Type,
+    ) -> Result<(), String> {
+        if Self::is_heap_string(field_ty) {
+            let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+            let string = self
+                .builder
+                .build_load(ptr_type, field_ptr, "payload_string")
+                .map_err(|e| format!("Failed to load payload string: {}", e))?;
+            return self.call_string_drop(string);
+        }
+
+        // Drop functions take the owner by reference: `fn drop(self: &Vec<T>)`
+        match field_ty {
+            Type::Vec(inner) => self.call_vec_drop(field_ptr.into(), inner),
+            Type::Box(inner) => self.call_box_drop(field_ptr.into(), inner),
+            Type::Generic { name, type_args } if name == "Vec" && !type_args.is_empty() => {
+                self.call_vec_drop(field_ptr.into(), &type_args[0])
+            }
+            Type::Generic { name, type_args } if name == "Box" && !type_args.is_empty() => {
+                self.call_box_drop(field_ptr.into(), &type_args[0])
+            }
+            Type::Named(name) if self.type_has_drop(field_ty) => {
+                self.call_struct_drop(field_ptr.into(), name)
+            }
+            Type::Named(name) => self.build_enum_payload_drop(name, field_ptr),
+            _ => Ok(()),
+        }
+    }
+}
@@ -25,6 +25,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Fallback to builtin indexing (arrays, strings, Vec, Map)
         // Handle complex object expressions (e.g., self.field[index])
         let (base_ptr, var_type, struct_name_opt) = match object {
+            Expression::Ident(name) if !self.variables.contains_key(name) => {
+                // Constant array: index its read-only global in place
+                let global_ptr = *self
+                    .global_constants
+                    .get(name)
+                    .ok_or_else(|| format!("Variable {} not found", name))?;
+                let global_ty = *self
+                    .global_constant_types
+                    .get(name)
+                    .ok_or_else(|| format!("Type for {} not found", name))?;
+                (global_ptr, global_ty, None)
+            }
             Expression::Ident(name) => {
                 // Simple variable: arr[i]
                 let var_ptr = *self
@@ -66,22 +78,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                         let field_llvm_type = self.ast_type_to_llvm(field_ast_type);
 
-                        // Load the field value (it should be an array)
-                        let field_val = self
-                            .builder
-                            .build_load(field_llvm_type, field_ptr, "field_load")
-                            .map_err(|e| format!("Failed to load field: {}", e))?;
-
-                        // Store in a temporary for indexing
-                        let temp_alloca = self
-                            .builder
-                            .build_alloca(field_llvm_type, "temp_array")
-                            .map_err(|e| format!("Failed to allocate temp: {}", e))?;
-                        self.builder
-                            .build_store(temp_alloca, field_val)
-                            .map_err(|e| format!("Failed to store temp: {}", e))?;
-
-                        (temp_alloca, field_llvm_type, None)
+                        // Index the field in place so subslices share its storage
+                        (field_ptr, field_llvm_type, None)
                     } else {
                         return Err(format!("Struct definition {} not found", struct_name));
                     }
@@ -94,6 +92,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         };
 
+        // Subslicing: arr[a..b], arr[..n], view[r] -> bounds-checked view sharing storage
+        if matches!(var_type, BasicTypeEnum::ArrayType(_)) || self.is_slice_layout(var_type) {
+            if let Some(inclusive) = self.range_index_kind(index) {
+                let view = self.slice_from_storage(base_ptr, var_type)?;
+                return self.compile_subslice(view, index, inclusive);
+            }
+        }
+
         // Check if this is a Map or Vec
         if let Some(ref struct_name) = struct_name_opt {
             if struct_name == "Map" {
@@ -191,6 +197,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     return self.compile_string_slice(var_name, start.as_ref(), end.as_ref());
                 }
 
+                // Range values (text[r], text[a..=b]) slice by their bounds
+                let index_type = self.infer_expression_type(index).ok();
+                if let Some(inclusive) = index_type.and_then(|ty| self.range_kind(&ty)) {
+                    return self.compile_string_slice_by_range(var_name, index, inclusive);
+                }
+
                 // Single index - could be string byte access
                 // Try string indexing first, fall back to array if it fails
                 if let Ok(string_byte) = self.try_compile_string_index(var_name, index) {
@@ -199,67 +211,23 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
-        // Check if this is a slice type (reference to array: &[T])
-        // Slice layout: { i8* data, i64 len, i64 elem_size }
-        if let BasicTypeEnum::StructType(struct_ty) = var_type {
-            // Check if this looks like a slice struct (3 fields: ptr, len, elem_size)
-            if struct_ty.count_fields() == 3 {
-                // This is likely a slice - extract data pointer and index into it
-                // Load the slice struct
-                let slice_val = self
-                    .builder
-                    .build_load(var_type, base_ptr, "slice_load")
-                    .map_err(|e| format!("Failed to load slice: {}", e))?;
-
-                // Extract data pointer (field 0)
-                let data_ptr = self
-                    .builder
-                    .build_extract_value(slice_val.into_struct_value(), 0, "slice_data")
-                    .map_err(|e| format!("Failed to extract slice data: {}", e))?
-                    .into_pointer_value();
-
-                // Compile index expression
-                let index_val = self.compile_expression(index)?;
-                let index_int = if let BasicValueEnum::IntValue(iv) = index_val {
-                    iv
-                } else {
-                    return Err("Index must be integer".to_string());
-                };
-
-                // Element type (TODO: get from AST slice type)
-                let elem_type = self.context.i32_type();
+        // Slice indexing: { ptr data, i64 len, i64 elem_size }, element type from the AST
+        if self.is_slice_layout(var_type) {
+            let view = self.slice_from_storage(base_ptr, var_type)?;
+            let index_int = match self.compile_expression(index)? {
+                BasicValueEnum::IntValue(iv) => iv,
+                _ => return Err("Index must be integer".to_string()),
+            };
+            let elem_type = self.indexed_element_llvm_type(object);
+            let element_ptr = self.slice_element_ptr(view, index_int, elem_type)?;
+            return self
+                .builder
+                .build_load(elem_type, element_ptr, "slice_elem")
+                .map_err(|e| format!("Failed to load slice element: {}", e));
+        }
 
-                // Cast data pointer (i8*) to element type pointer (i32*)
-                let typed_data_ptr = self
-                    .builder
-                    .build_pointer_cast(
-                        data_ptr,
-                        self.context.ptr_type(inkwell::AddressSpace::default()),
-                        "typed_data_ptr",
-                    )
-                    .map_err(|e| format!("Failed to cast data pointer: {}", e))?;
-
-                // GEP with element type for correct offset calculation
-                unsafe {
-                    let element_ptr = self
-                        .builder
-                        .build_in_bounds_gep(
-                            elem_type,
-                            typed_data_ptr,
-                            &[index_int],
-                            "slice_elem_ptr",
-                        )
-                        .map_err(|e| format!("Failed to build slice GEP: {}", e))?;
-
-                    // Load element
-                    return self
-                        .builder
-                        .build_load(elem_type, element_ptr, "slice_elem")
-                        .map_err(|e| format!("Failed to load slice element: {}", e));
-                }
-            }
-        } // Array indexing
-          // Get element type from array type
+        // Array indexing
+        // Get element type from array type
         let elem_type = if let BasicTypeEnum::ArrayType(arr_ty) = var_type {
             arr_ty.get_element_type()
         } else {
@@ -405,6 +373,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return Err("Index must be integer".to_string());
         };
 
+        // Slices write through to the storage they view
+        if self.is_slice_layout(array_type) {
+            let view = self.slice_from_storage(array_ptr, array_type)?;
+            let elem_type = self.indexed_element_llvm_type(object);
+            return self.slice_element_ptr(view, index_int, elem_type);
+        }
+
         // GEP to get element pointer
         let zero = self.context.i32_type().const_int(0, false);
 
@@ -480,17 +455,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         start: Option<&Box<Expression>>,
         end: Option<&Box<Expression>>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
-        // Get string pointer
-        let string_ptr_var = *self
-            .variables
-            .get(var_name)
-            .ok_or_else(|| format!("Variable {} not found", var_name))?;
-
-        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
-        let string_ptr = self
-            .builder
-            .build_load(ptr_type, string_ptr_var, &format!("{}_ptr", var_name))
-            .map_err(|e| format!("Failed to load string pointer: {}", e))?;
+        let string_ptr = self.load_string_variable(var_name)?;
 
         // Compile start index (default: 0)
         let start_val = if let Some(start_expr) = start {
@@ -530,7 +495,43 @@ impl<'ctx> ASTCodeGen<'ctx> {
             self.context.i64_type().const_int((-1i64) as u64, true)
         };
 
-        // Call vex_string_substr(str, start, end) -> char*
+        self.build_string_substr(string_ptr, start_val, end_val)
+    }
+
+    /// Compile string slicing by a range value: text[r] -> vex_string_substr(text, r.start, r.end)
+    fn compile_string_slice_by_range(
+        &mut self,
+        var_name: &str,
+        range: &Expression,
+        inclusive: bool,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let string_ptr = self.load_string_variable(var_name)?;
+        let range_val = self.compile_expression(range)?;
+        let range_ptr = self.spill_range_value(range_val, "slice_range")?;
+        let (start_val, end_val) = self.range_slice_bounds(range_ptr, inclusive)?;
+        self.build_string_substr(string_ptr, start_val, end_val)
+    }
+
+    fn load_string_variable(&mut self, var_name: &str) -> Result<BasicValueEnum<'ctx>, String> {
+        let string_ptr_var = *self
+            .variables
+            .get(var_name)
+            .ok_or_else(|| format!("Variable {} not found", var_name))?;
+
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        self.builder
+            .build_load(ptr_type, string_ptr_var, &format!("{}_ptr", var_name))
+            .map_err(|e| format!("Failed to load string pointer: {}", e))
+    }
+
+    /// Call vex_string_substr(str, start, end) -> char*; end -1 means "to end"
+    fn build_string_substr(
+        &mut self,
+        string_ptr: BasicValueEnum<'ctx>,
+        start_val: inkwell::values::IntValue<'ctx>,
+        end_val: inkwell::values::IntValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
         let vex_string_substr = self.declare_runtime_fn(
             "vex_string_substr",
             &[
@@ -119,6 +119,37 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                         all_equal
                     }
+                    (BasicValueEnum::ArrayValue(la), BasicValueEnum::ArrayValue(ra)) => {
+                        // Union storage of a mixed-payload enum: constructors zero the
+                        // bytes a payload does not cover, so compare word by word
+                        let mut all_equal = self.context.bool_type().const_int(1, false);
+
+                        for i in 0..la.get_type().len() {
+                            let lw = self
+                                .builder
+                                .build_extract_value(la, i, &format!("la_w{}", i))
+                                .map_err(|e| format!("Failed to extract: {}", e))?;
+                            let rw = self
+                                .builder
+                                .build_extract_value(ra, i, &format!("ra_w{}", i))
+                                .map_err(|e| format!("Failed to extract: {}", e))?;
+
+                            if let (BasicValueEnum::IntValue(li), BasicValueEnum::IntValue(ri)) =
+                                (lw, rw)
+                            {
+                                let word_eq = self
+                                    .builder
+                                    .build_int_compare(IntPredicate::EQ, li, ri, "weq")
+                                    .map_err(|e| format!("Failed to compare: {}", e))?;
+                                all_equal = self
+                                    .builder
+                                    .build_and(all_equal, word_eq, "and")
+                                    .map_err(|e| format!("Failed to AND: {}", e))?;
+                            }
+                        }
+
+                        all_equal
+                    }
                     _ => {
                         // For other types, assume equal if tags are equal
                         // This handles None case where data is just zero/undef
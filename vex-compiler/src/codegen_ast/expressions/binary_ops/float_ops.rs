@@ -25,8 +25,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // Float power: call llvm.pow intrinsic
                 return self.compile_float_power(l, r);
             }
-            BinaryOp::Range | BinaryOp::RangeInclusive | BinaryOp::NullCoalesce => {
-                return Err("Range/NullCoalesce operators not implemented for floats".to_string());
+            BinaryOp::Range | BinaryOp::RangeInclusive => {
+                return Err("Range operators not implemented for floats".to_string());
+            }
+            BinaryOp::NullCoalesce => {
+                return Err("?? requires an Option or pointer on the left side".to_string());
             }
             BinaryOp::Eq => {
                 return Ok(self
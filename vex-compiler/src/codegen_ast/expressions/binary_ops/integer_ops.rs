@@ -15,6 +15,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         r: inkwell::values::IntValue<'ctx>,
         op: &BinaryOp,
         expected_type: Option<&Type>,
+        is_signed: bool,
+        location: Option<&str>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
         // Determine target bit width from expected type
         let target_bit_width = if let Some(ty) = expected_type {
@@ -31,7 +33,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         };
 
         // Call the original implementation
-        let result = self.compile_integer_binary_op_internal(l, r, op, target_bit_width)?;
+        let result =
+            self.compile_integer_binary_op_internal(l, r, op, target_bit_width, is_signed, location)?;
         
         // ⭐ CRITICAL: If expected_type is provided and result is wider, downcast to expected type
         // This ensures `0 - x` where x:i8 returns i8, not i32
@@ -67,25 +70,24 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(result)
     }
 
-    /// Compile binary operations for integer operands (backward compat)
-    pub(crate) fn compile_integer_binary_op(
-        &mut self,
-        l: inkwell::values::IntValue<'ctx>,
-        r: inkwell::values::IntValue<'ctx>,
-        op: &BinaryOp,
-    ) -> Result<BasicValueEnum<'ctx>, String> {
-        let target_bit_width = l.get_type().get_bit_width();
-        self.compile_integer_binary_op_internal(l, r, op, target_bit_width)
-    }
-
-    /// Internal implementation with explicit target bit width
+    /// Internal implementation with explicit target bit width.
+    /// `+ - *` check for overflow in debug builds; `location` names the operator's source position.
     fn compile_integer_binary_op_internal(
         &mut self,
         l: inkwell::values::IntValue<'ctx>,
         r: inkwell::values::IntValue<'ctx>,
         op: &BinaryOp,
         target_bit_width: u32,
+        is_signed: bool,
+        location: Option<&str>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
+        // a..b builds a Range value; both bounds are stored as i64
+        if matches!(op, BinaryOp::Range | BinaryOp::RangeInclusive) {
+            let start = self.range_bound_to_i64(l.into(), "start")?;
+            let end = self.range_bound_to_i64(r.into(), "end")?;
+            return self.build_range_value(start, end);
+        }
+
         // Check if we are in a constant context (both operands are constant)
         let is_const_context = l.is_const() && r.is_const();
 
@@ -305,9 +307,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
         }
 
         let result = match op {
-            BinaryOp::Add => self.builder.build_int_add(l, r, "add"),
-            BinaryOp::Sub => self.builder.build_int_sub(l, r, "sub"),
-            BinaryOp::Mul => self.builder.build_int_mul(l, r, "mul"),
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
+                return Ok(self.build_int_arith(op, l, r, is_signed, location)?.into())
+            }
             BinaryOp::Div => self.builder.build_int_signed_div(l, r, "div"),
             BinaryOp::Mod => self.builder.build_int_signed_rem(l, r, "mod"),
             BinaryOp::Pow => {
@@ -364,10 +366,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
             BinaryOp::Shl => self.builder.build_left_shift(l, r, "shl"),
             BinaryOp::Shr => self.builder.build_right_shift(l, r, true, "shr"),
             BinaryOp::Range | BinaryOp::RangeInclusive => {
-                return Err("Range operators not yet implemented".to_string());
+                return Err("Range operators are lowered before integer arithmetic".to_string());
             }
             BinaryOp::NullCoalesce => {
-                return Err("Null coalesce operator not yet implemented".to_string());
+                return Err("?? requires an Option or pointer on the left side".to_string());
             }
         }
         .map_err(|e| format!("Failed to build operation: {}", e))?;
@@ -3,7 +3,9 @@
 pub(crate) mod enum_ops;
 pub(crate) mod float_ops;
 pub(crate) mod integer_ops;
+pub(crate) mod null_coalesce;
 pub(crate) mod operator_overloading;
+pub(crate) mod overflow;
 pub(crate) mod pointer_loading;
 pub(crate) mod pointer_ops;
 pub(crate) mod power_ops;
@@ -0,0 +1,422 @@
+//! Null-coalescing operator (`??`)
+//!
+//! `value ?? fallback` unwraps an Option<T> or keeps a non-null pointer. The
+//! fallback is only evaluated on the None/null path.
+
+use super::super::super::{ASTCodeGen, OPTION_SOME_TAG};
+use crate::type_system::coercion_rules::{classify_coercion, CoercionKind};
+use inkwell::basic_block::BasicBlock;
+use inkwell::types::{BasicTypeEnum, StructType};
+use inkwell::values::{BasicValueEnum, PointerValue};
+use inkwell::IntPredicate;
+use vex_ast::*;
+
+/// How the left operand of `??` is tested
+enum CoalesceOperand {
+    /// Option<T>, with the payload type when it can be inferred
+    Option(Option<Type>),
+    /// Nil-able pointer; the result keeps the pointer type
+    Pointer(Type),
+}
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Compile `left ?? right`
+    ///
+    /// Returns Ok(None) when the left operand is neither an Option nor a pointer,
+    /// so user types can still provide `op??` through operator overloading.
+    pub(crate) fn compile_null_coalesce(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<Option<BasicValueEnum<'ctx>>, String> {
+        // `None ?? x` is always the fallback
+        if let Expression::EnumLiteral {
+            enum_name, variant, ..
+        } = left
+        {
+            if enum_name == "Option" && variant == "None" {
+                return self.compile_expression(right).map(Some);
+            }
+        }
+
+        let operand = match self.classify_coalesce_operand(left) {
+            Some(operand) => operand,
+            None => return Ok(None),
+        };
+
+        match operand {
+            CoalesceOperand::Option(payload_ast) => self
+                .compile_option_coalesce(left, right, payload_ast.as_ref())
+                .map(Some),
+            CoalesceOperand::Pointer(pointer_ast) => self
+                .compile_pointer_coalesce(left, right, &pointer_ast)
+                .map(Some),
+        }
+    }
+
+    fn classify_coalesce_operand(&self, left: &Expression) -> Option<CoalesceOperand> {
+        if let Expression::EnumLiteral {
+            enum_name, data, ..
+        } = left
+        {
+            if enum_name == "Option" {
+                let payload = data
+                    .first()
+                    .and_then(|value| self.infer_expression_type(value).ok());
+                return Some(CoalesceOperand::Option(payload));
+            }
+        }
+
+        // `Some<T>(v)` is a call to the Some builtin
+        if let Expression::Call {
+            func,
+            type_args,
+            args,
+            ..
+        } = left
+        {
+            if matches!(func.as_ref(), Expression::Ident(name) if name == "Some") {
+                let payload = type_args.first().cloned().or_else(|| {
+                    args.first()
+                        .and_then(|value| self.infer_expression_type(value).ok())
+                });
+                return Some(CoalesceOperand::Option(payload));
+            }
+        }
+
+        match self.infer_expression_type(left).ok() {
+            Some(Type::Option(inner)) => return Some(CoalesceOperand::Option(Some(*inner))),
+            Some(Type::Generic { name, type_args }) if name == "Option" && type_args.len() == 1 => {
+                return Some(CoalesceOperand::Option(type_args.into_iter().next()));
+            }
+            Some(Type::Named(name)) if name == "Option" => {
+                return Some(CoalesceOperand::Option(None));
+            }
+            Some(Type::Named(name)) if name == "ptr" => {
+                return Some(CoalesceOperand::Pointer(Type::Named(name)));
+            }
+            Some(ty @ (Type::Reference(..) | Type::RawPtr { .. } | Type::Error)) => {
+                return Some(CoalesceOperand::Pointer(ty));
+            }
+            _ => {}
+        }
+
+        // `let x = Some(5)` only records the builtin struct name
+        if let Expression::Ident(name) = left {
+            if let Some(struct_name) = self.variable_struct_names.get(name) {
+                if struct_name == "Option" || struct_name.starts_with("Option_") {
+                    return Some(CoalesceOperand::Option(None));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Option<T> ?? T: branch on the tag, load the payload only on the Some path
+    fn compile_option_coalesce(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        payload_ast: Option<&Type>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        if payload_ast.is_some_and(Self::option_uses_niche) {
+            return self.compile_niche_option_coalesce(left, right, payload_ast);
+        }
+
+        let function = self
+            .current_function
+            .ok_or("?? operator outside function")?;
+
+        let left_val = self.compile_expression(left)?;
+        let (option_ptr, option_type) = self.coalesce_option_slot(left, left_val, payload_ast)?;
+        let payload_type = option_type
+            .get_field_type_at_index(1)
+            .ok_or("Option value has no payload field")?;
+
+        let tag_ptr = self
+            .builder
+            .build_struct_gep(option_type, option_ptr, 0, "coalesce_tag_ptr")
+            .map_err(|e| format!("Failed to get Option tag pointer: {}", e))?;
+        let tag = self
+            .builder
+            .build_load(self.context.i32_type(), tag_ptr, "coalesce_tag")
+            .map_err(|e| format!("Failed to load Option tag: {}", e))?
+            .into_int_value();
+        let is_some = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                tag,
+                self.context.i32_type().const_int(OPTION_SOME_TAG, false),
+                "coalesce_is_some",
+            )
+            .map_err(|e| format!("Failed to compare Option tag: {}", e))?;
+
+        let some_block = self.context.append_basic_block(function, "coalesce_some");
+        let none_block = self.context.append_basic_block(function, "coalesce_none");
+        let merge_block = self.context.append_basic_block(function, "coalesce_merge");
+        self.builder
+            .build_conditional_branch(is_some, some_block, none_block)
+            .map_err(|e| format!("Failed to build conditional branch: {}", e))?;
+
+        // Some: unwrap the payload
+        self.builder.position_at_end(some_block);
+        let payload_ptr = self
+            .builder
+            .build_struct_gep(option_type, option_ptr, 1, "coalesce_payload_ptr")
+            .map_err(|e| format!("Failed to get Option payload pointer: {}", e))?;
+        let payload = self
+            .builder
+            .build_load(payload_type, payload_ptr, "coalesce_payload")
+            .map_err(|e| format!("Failed to load Option payload: {}", e))?;
+        self.builder
+            .build_unconditional_branch(merge_block)
+            .map_err(|e| format!("Failed to branch to merge: {}", e))?;
+
+        // None: only now evaluate the fallback
+        self.builder.position_at_end(none_block);
+        let fallback = self.compile_coalesce_fallback(right, payload_type, payload_ast)?;
+
+        self.build_coalesce_merge(payload_type, (payload, some_block), fallback, merge_block)
+    }
+
+    /// Option<&T> ?? T: the niche value is its own payload
+    fn compile_niche_option_coalesce(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        payload_ast: Option<&Type>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let function = self
+            .current_function
+            .ok_or("?? operator outside function")?;
+
+        let value = self.compile_expression(left)?;
+        let value_type = value.get_type();
+        let tag = self.niche_option_tag(value)?;
+        let is_some = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                tag,
+                self.context.i32_type().const_int(OPTION_SOME_TAG, false),
+                "coalesce_is_some",
+            )
+            .map_err(|e| format!("Failed to compare Option tag: {}", e))?;
+        let value_block = self
+            .builder
+            .get_insert_block()
+            .ok_or("?? operator outside basic block")?;
+        let none_block = self.context.append_basic_block(function, "coalesce_none");
+        let merge_block = self.context.append_basic_block(function, "coalesce_merge");
+        self.builder
+            .build_conditional_branch(is_some, merge_block, none_block)
+            .map_err(|e| format!("Failed to build conditional branch: {}", e))?;
+
+        self.builder.position_at_end(none_block);
+        let fallback = self.compile_coalesce_fallback(right, value_type, payload_ast)?;
+
+        self.build_coalesce_merge(value_type, (value, value_block), fallback, merge_block)
+    }
+
+    /// Pointer ?? pointer: keep the left pointer unless it is null
+    fn compile_pointer_coalesce(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        pointer_ast: &Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let function = self
+            .current_function
+            .ok_or("?? operator outside function")?;
+
+        let left_ptr = match self.compile_expression(left)? {
+            BasicValueEnum::PointerValue(ptr) => ptr,
+            other => {
+                return Err(format!(
+                    "?? expected a pointer on the left for {:?}, got {:?}",
+                    pointer_ast,
+                    other.get_type()
+                ))
+            }
+        };
+        let pointer_type: BasicTypeEnum<'ctx> = left_ptr.get_type().into();
+
+        let is_not_null = self
+            .builder
+            .build_is_not_null(left_ptr, "coalesce_not_null")
+            .map_err(|e| format!("Failed to compare pointer with null: {}", e))?;
+        let value_block = self
+            .builder
+            .get_insert_block()
+            .ok_or("?? operator outside basic block")?;
+        let none_block = self.context.append_basic_block(function, "coalesce_null");
+        let merge_block = self.context.append_basic_block(function, "coalesce_merge");
+        self.builder
+            .build_conditional_branch(is_not_null, merge_block, none_block)
+            .map_err(|e| format!("Failed to build conditional branch: {}", e))?;
+
+        self.builder.position_at_end(none_block);
+        let fallback = self.compile_coalesce_fallback(right, pointer_type, Some(pointer_ast))?;
+
+        self.build_coalesce_merge(
+            pointer_type,
+            (left_ptr.into(), value_block),
+            fallback,
+            merge_block,
+        )
+    }
+
+    /// Address and LLVM struct type of the Option being tested
+    fn coalesce_option_slot(
+        &mut self,
+        left: &Expression,
+        left_val: BasicValueEnum<'ctx>,
+        payload_ast: Option<&Type>,
+    ) -> Result<(PointerValue<'ctx>, StructType<'ctx>), String> {
+        match left_val {
+            BasicValueEnum::StructValue(option_val) => {
+                let option_type = option_val.get_type();
+                let slot = self
+                    .builder
+                    .build_alloca(option_type, "coalesce_option")
+                    .map_err(|e| format!("Failed to allocate Option temp: {}", e))?;
+                self.builder
+                    .build_store(slot, option_val)
+                    .map_err(|e| format!("Failed to store Option temp: {}", e))?;
+                Ok((slot, option_type))
+            }
+            BasicValueEnum::PointerValue(option_ptr) => {
+                // Option variables are passed around as a pointer to their slot
+                let var_type = match left {
+                    Expression::Ident(name) => self.variable_types.get(name).copied(),
+                    _ => None,
+                };
+                let option_type = match (var_type, payload_ast) {
+                    (Some(BasicTypeEnum::StructType(st)), _) => st,
+                    (_, Some(payload)) => match self
+                        .ast_type_to_llvm(&Type::Option(Box::new(payload.clone())))
+                    {
+                        BasicTypeEnum::StructType(st) => st,
+                        other => return Err(format!("Option lowered to non-struct {:?}", other)),
+                    },
+                    _ => {
+                        return Err("Cannot determine the Option type on the left of ??".to_string())
+                    }
+                };
+                Ok((option_ptr, option_type))
+            }
+            other => Err(format!(
+                "?? expected an Option on the left, got {:?}",
+                other.get_type()
+            )),
+        }
+    }
+
+    /// Compile the right-hand side of `??` and check it against the unwrapped type
+    fn compile_coalesce_fallback(
+        &mut self,
+        right: &Expression,
+        expected_llvm: BasicTypeEnum<'ctx>,
+        expected_ast: Option<&Type>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let mut fallback = self.compile_expression_with_type(right, expected_ast)?;
+
+        // Struct literals and struct variables evaluate to their storage address
+        if fallback.is_pointer_value() && expected_llvm.is_struct_type() {
+            fallback = self
+                .builder
+                .build_load(
+                    expected_llvm,
+                    fallback.into_pointer_value(),
+                    "coalesce_fallback",
+                )
+                .map_err(|e| format!("Failed to load ?? fallback: {}", e))?;
+        }
+
+        if fallback.get_type() == expected_llvm {
+            return Ok(fallback);
+        }
+
+        let fallback_ast = self.infer_expression_type(right).ok();
+        let is_literal = matches!(
+            right,
+            Expression::IntLiteral(_) | Expression::FloatLiteral(_)
+        );
+        let widening = match (&fallback_ast, expected_ast) {
+            (Some(from), Some(to)) => classify_coercion(from, to) == CoercionKind::Safe,
+            _ => false,
+        };
+
+        match (fallback, expected_llvm) {
+            (BasicValueEnum::IntValue(iv), BasicTypeEnum::IntType(target))
+                if is_literal || widening =>
+            {
+                let is_signed = !matches!(
+                    fallback_ast,
+                    Some(Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::U128 | Type::Bool)
+                );
+                return self
+                    .builder
+                    .build_int_cast_sign_flag(iv, target, is_signed, "coalesce_fallback_int")
+                    .map(Into::into)
+                    .map_err(|e| format!("Failed to cast ?? fallback: {}", e));
+            }
+            (BasicValueEnum::FloatValue(fv), BasicTypeEnum::FloatType(target))
+                if is_literal || widening =>
+            {
+                return self
+                    .builder
+                    .build_float_cast(fv, target, "coalesce_fallback_float")
+                    .map(Into::into)
+                    .map_err(|e| format!("Failed to cast ?? fallback: {}", e));
+            }
+            _ => {}
+        }
+
+        let found = fallback_ast
+            .map(|ty| format!("{:?}", ty))
+            .unwrap_or_else(|| format!("{:?}", fallback.get_type()));
+        let expected = expected_ast
+            .map(|ty| format!("{:?}", ty))
+            .unwrap_or_else(|| format!("{:?}", expected_llvm));
+        Err(format!(
+            "mismatched types in ??: fallback is {} but the left side unwraps to {}",
+            found, expected
+        ))
+    }
+
+    /// Join the unwrapped value and the fallback in `merge_block`
+    fn build_coalesce_merge(
+        &mut self,
+        result_type: BasicTypeEnum<'ctx>,
+        (value, value_block): (BasicValueEnum<'ctx>, BasicBlock<'ctx>),
+        fallback: BasicValueEnum<'ctx>,
+        merge_block: BasicBlock<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        // The fallback may have branched (nested ??) or diverged (e.g. panic)
+        let fallback_block = self
+            .builder
+            .get_insert_block()
+            .ok_or("?? fallback left no insertion block")?;
+        let fallback_reaches_merge = fallback_block.get_terminator().is_none();
+        if fallback_reaches_merge {
+            self.builder
+                .build_unconditional_branch(merge_block)
+                .map_err(|e| format!("Failed to branch to merge: {}", e))?;
+        }
+
+        self.builder.position_at_end(merge_block);
+        let phi = self
+            .builder
+            .build_phi(result_type, "coalesce_result")
+            .map_err(|e| format!("Failed to build phi: {}", e))?;
+        phi.add_incoming(&[(&value, value_block)]);
+        if fallback_reaches_merge {
+            phi.add_incoming(&[(&fallback, fallback_block)]);
+        }
+
+        Ok(phi.as_basic_value())
+    }
+}
@@ -186,6 +186,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             &[], // No generic type args for operator overloading
                             &vec![right.clone()],
                             false,
+                            None,
                         )?));
                     } else if !contract_name.is_empty() {
                         // ⚠️ Debug: Operator method not found despite contract name existing
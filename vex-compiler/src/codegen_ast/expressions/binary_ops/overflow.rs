@@ -0,0 +1,309 @@
+//! Integer overflow semantics
+//!
+//! `+ - *` trap on overflow when overflow checks are enabled (debug builds) and
+//! wrap otherwise. The `wrapping_*`, `checked_*` and `saturating_*` methods on
+//! integer values behave the same in every build.
+
+use super::super::super::ASTCodeGen;
+use inkwell::values::{BasicValueEnum, IntValue};
+use inkwell::IntPredicate;
+use vex_ast::*;
+
+/// How an integer method handles overflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverflowMode {
+    Wrapping,
+    Checked,
+    Saturating,
+}
+
+/// Split `checked_add` into its mode and arithmetic operator
+fn parse_overflow_method(method: &str) -> Option<(OverflowMode, BinaryOp)> {
+    let (mode, op) = method.split_once('_')?;
+    let mode = match mode {
+        "wrapping" => OverflowMode::Wrapping,
+        "checked" => OverflowMode::Checked,
+        "saturating" => OverflowMode::Saturating,
+        _ => return None,
+    };
+    let op = match op {
+        "add" => BinaryOp::Add,
+        "sub" => BinaryOp::Sub,
+        "mul" => BinaryOp::Mul,
+        _ => return None,
+    };
+    Some((mode, op))
+}
+
+fn op_name(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "add",
+        BinaryOp::Sub => "sub",
+        _ => "mul",
+    }
+}
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Source location of a span as `file:line:column`, for runtime messages
+    pub(crate) fn span_location(&self, span_id: Option<&String>) -> Option<String> {
+        span_id
+            .and_then(|id| self.span_map.get(id))
+            .map(|span| span.to_string())
+    }
+
+    /// `l op r` for Add/Sub/Mul: traps with a located message when overflow
+    /// checks are on, wraps in two's complement otherwise
+    pub(crate) fn build_int_arith(
+        &mut self,
+        op: &BinaryOp,
+        l: IntValue<'ctx>,
+        r: IntValue<'ctx>,
+        is_signed: bool,
+        location: Option<&str>,
+    ) -> Result<IntValue<'ctx>, String> {
+        if !self.overflow_checks {
+            return self.build_wrapping_arith(op, l, r);
+        }
+
+        let (result, overflow) = self.build_int_with_overflow(op, l, r, is_signed)?;
+        let ok = self
+            .builder
+            .build_not(overflow, "no_overflow")
+            .map_err(|e| format!("Failed to build overflow check: {}", e))?;
+        let verb = match op {
+            BinaryOp::Add => "add",
+            BinaryOp::Sub => "subtract",
+            _ => "multiply",
+        };
+        let message = match location {
+            Some(location) => format!("attempt to {} with overflow at {}", verb, location),
+            None => format!("attempt to {} with overflow", verb),
+        };
+        self.build_runtime_check(ok, &message)?;
+        Ok(result)
+    }
+
+    /// Plain two's complement add/sub/mul
+    fn build_wrapping_arith(
+        &mut self,
+        op: &BinaryOp,
+        l: IntValue<'ctx>,
+        r: IntValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        match op {
+            BinaryOp::Add => self.builder.build_int_add(l, r, "add"),
+            BinaryOp::Sub => self.builder.build_int_sub(l, r, "sub"),
+            BinaryOp::Mul => self.builder.build_int_mul(l, r, "mul"),
+            _ => return Err(format!("{:?} is not an overflowing operator", op)),
+        }
+        .map_err(|e| format!("Failed to build integer {}: {}", op_name(op), e))
+    }
+
+    /// `llvm.{s,u}{add,sub,mul}.with.overflow.iN`: the wrapped result and an i1
+    /// overflow flag
+    fn build_int_with_overflow(
+        &mut self,
+        op: &BinaryOp,
+        l: IntValue<'ctx>,
+        r: IntValue<'ctx>,
+        is_signed: bool,
+    ) -> Result<(IntValue<'ctx>, IntValue<'ctx>), String> {
+        let int_type = l.get_type();
+        let intrinsic_name = format!(
+            "llvm.{}{}.with.overflow.i{}",
+            if is_signed { "s" } else { "u" },
+            op_name(op),
+            int_type.get_bit_width()
+        );
+        let result_type = self
+            .context
+            .struct_type(&[int_type.into(), self.context.bool_type().into()], false);
+        let intrinsic = self.declare_llvm_intrinsic(
+            &intrinsic_name,
+            &[int_type.into(), int_type.into()],
+            result_type.into(),
+        );
+
+        let pair = self
+            .builder
+            .build_call(intrinsic, &[l.into(), r.into()], "with_overflow")
+            .map_err(|e| format!("Failed to call {}: {}", intrinsic_name, e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_struct_value();
+        let result = self
+            .builder
+            .build_extract_value(pair, 0, "ov_result")
+            .map_err(|e| format!("Failed to extract overflow result: {}", e))?
+            .into_int_value();
+        let overflow = self
+            .builder
+            .build_extract_value(pair, 1, "ov_flag")
+            .map_err(|e| format!("Failed to extract overflow flag: {}", e))?
+            .into_int_value();
+        Ok((result, overflow))
+    }
+
+    /// Clamp to the type's MIN/MAX instead of wrapping
+    fn build_saturating_arith(
+        &mut self,
+        op: &BinaryOp,
+        l: IntValue<'ctx>,
+        r: IntValue<'ctx>,
+        is_signed: bool,
+    ) -> Result<IntValue<'ctx>, String> {
+        let int_type = l.get_type();
+        let prefix = if is_signed { "s" } else { "u" };
+
+        // LLVM has saturating add/sub intrinsics but no saturating mul
+        if matches!(op, BinaryOp::Add | BinaryOp::Sub) {
+            let intrinsic_name = format!(
+                "llvm.{}{}.sat.i{}",
+                prefix,
+                op_name(op),
+                int_type.get_bit_width()
+            );
+            let intrinsic = self.declare_llvm_intrinsic(
+                &intrinsic_name,
+                &[int_type.into(), int_type.into()],
+                int_type.into(),
+            );
+            return Ok(self
+                .builder
+                .build_call(intrinsic, &[l.into(), r.into()], "saturating")
+                .map_err(|e| format!("Failed to call {}: {}", intrinsic_name, e))?
+                .try_as_basic_value()
+                .unwrap_basic()
+                .into_int_value());
+        }
+
+        let (product, overflow) = self.build_int_with_overflow(op, l, r, is_signed)?;
+        let bound = if is_signed {
+            // The product is negative exactly when the operand signs differ
+            let shift = int_type.const_int(int_type.get_bit_width() as u64 - 1, false);
+            let min = self
+                .builder
+                .build_left_shift(int_type.const_int(1, false), shift, "int_min")
+                .map_err(|e| format!("Failed to build shift: {}", e))?;
+            let max = self
+                .builder
+                .build_not(min, "int_max")
+                .map_err(|e| format!("Failed to build not: {}", e))?;
+            let signs = self
+                .builder
+                .build_xor(l, r, "sign_bits")
+                .map_err(|e| format!("Failed to build xor: {}", e))?;
+            let negative = self
+                .builder
+                .build_int_compare(IntPredicate::SLT, signs, int_type.const_zero(), "negative")
+                .map_err(|e| format!("Failed to compare: {}", e))?;
+            self.builder
+                .build_select(negative, min, max, "sat_bound")
+                .map_err(|e| format!("Failed to build select: {}", e))?
+                .into_int_value()
+        } else {
+            int_type.const_all_ones()
+        };
+        Ok(self
+            .builder
+            .build_select(overflow, bound, product, "saturating")
+            .map_err(|e| format!("Failed to build select: {}", e))?
+            .into_int_value())
+    }
+
+    /// `Some(l op r)`, or `None` when the operation overflows
+    fn build_checked_arith(
+        &mut self,
+        op: &BinaryOp,
+        l: IntValue<'ctx>,
+        r: IntValue<'ctx>,
+        is_signed: bool,
+        int_type: &Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let (result, overflow) = self.build_int_with_overflow(op, l, r, is_signed)?;
+        let option_type = self
+            .ast_type_to_llvm(&Type::Option(Box::new(int_type.clone())))
+            .into_struct_type();
+
+        // Some is tag 0 and None tag 1, so the overflow flag is the tag
+        let tag = self
+            .builder
+            .build_int_z_extend(overflow, self.context.i32_type(), "checked_tag")
+            .map_err(|e| format!("Failed to build tag: {}", e))?;
+
+        let with_tag = self
+            .builder
+            .build_insert_value(option_type.get_undef(), tag, 0, "checked_with_tag")
+            .map_err(|e| format!("Failed to build Option: {}", e))?;
+        let option = self
+            .builder
+            .build_insert_value(with_tag, result, 1, "checked")
+            .map_err(|e| format!("Failed to build Option: {}", e))?;
+        Ok(option.into_struct_value().into())
+    }
+
+    /// Integer type of a receiver that supports the overflow methods
+    fn overflow_receiver_type(&self, ty: &Type) -> Option<Type> {
+        (self.is_integer_type(ty) && *ty != Type::Bool).then(|| ty.clone())
+    }
+
+    /// Return type of `x.wrapping_add(y)`, `x.checked_mul(y)`, `x.saturating_sub(y)`, ...
+    pub(crate) fn int_overflow_method_type(
+        &self,
+        receiver_type: &Type,
+        method: &str,
+    ) -> Option<Type> {
+        let int_type = self.overflow_receiver_type(receiver_type)?;
+        match parse_overflow_method(method)?.0 {
+            OverflowMode::Checked => Some(Type::Option(Box::new(int_type))),
+            OverflowMode::Wrapping | OverflowMode::Saturating => Some(int_type),
+        }
+    }
+
+    /// Compile `x.wrapping_add(y)`, `x.checked_mul(y)`, `x.saturating_sub(y)`, ...
+    /// Returns Ok(None) for other methods or non-integer receivers.
+    pub(crate) fn compile_int_overflow_method(
+        &mut self,
+        receiver: &Expression,
+        method: &str,
+        args: &[Expression],
+    ) -> Result<Option<BasicValueEnum<'ctx>>, String> {
+        let Some((mode, op)) = parse_overflow_method(method) else {
+            return Ok(None);
+        };
+        let Some(int_type) = self
+            .infer_expression_type(receiver)
+            .ok()
+            .and_then(|ty| self.overflow_receiver_type(&ty))
+        else {
+            return Ok(None);
+        };
+        if args.len() != 1 {
+            return Err(format!("{}() takes exactly one argument", method));
+        }
+
+        let is_signed = !self.is_unsigned_integer_type(&int_type);
+        let l = match self.compile_expression_with_type(receiver, Some(&int_type))? {
+            BasicValueEnum::IntValue(v) => v,
+            _ => return Err(format!("{}() receiver must be an integer", method)),
+        };
+        let r = match self.compile_expression_with_type(&args[0], Some(&int_type))? {
+            BasicValueEnum::IntValue(v) => v,
+            _ => return Err(format!("{}() argument must be an integer", method)),
+        };
+        let r = if r.get_type() != l.get_type() {
+            self.builder
+                .build_int_cast_sign_flag(r, l.get_type(), is_signed, "overflow_arg")
+                .map_err(|e| format!("Failed to cast {}() argument: {}", method, e))?
+        } else {
+            r
+        };
+
+        let result = match mode {
+            OverflowMode::Wrapping => self.build_wrapping_arith(&op, l, r)?.into(),
+            OverflowMode::Saturating => self.build_saturating_arith(&op, l, r, is_signed)?.into(),
+            OverflowMode::Checked => self.build_checked_arith(&op, l, r, is_signed, &int_type)?,
+        };
+        Ok(Some(result))
+    }
+}
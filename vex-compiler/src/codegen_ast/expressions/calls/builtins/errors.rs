@@ -0,0 +1,50 @@
+// Builtin error value methods (error.new)
+
+use crate::codegen_ast::ASTCodeGen;
+use inkwell::values::BasicValueEnum;
+use vex_ast::*;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    pub(crate) fn compile_error_method(
+        &mut self,
+        var_name: &str,
+        method: &str,
+        args: &[Expression],
+    ) -> Result<Option<BasicValueEnum<'ctx>>, String> {
+        match method {
+            "message" => {
+                if !args.is_empty() {
+                    return Err("error.message() takes no arguments".to_string());
+                }
+
+                let error_alloca_ptr = *self
+                    .variables
+                    .get(var_name)
+                    .ok_or_else(|| format!("Error variable {} not found", var_name))?;
+
+                // Load the VexError* pointer
+                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+                let error_ptr = self
+                    .builder
+                    .build_load(ptr_type, error_alloca_ptr, "error_ptr_load")
+                    .map_err(|e| format!("Failed to load error pointer: {}", e))?;
+
+                // const char* vex_error_message(const VexError* err)
+                let message_fn = self.declare_runtime_fn(
+                    "vex_error_message",
+                    &[ptr_type.into()],
+                    ptr_type.into(),
+                );
+                let message = self
+                    .builder
+                    .build_call(message_fn, &[error_ptr.into()], "error_message")
+                    .map_err(|e| format!("Failed to call vex_error_message: {}", e))?
+                    .try_as_basic_value()
+                    .unwrap_basic();
+
+                Ok(Some(message))
+            }
+            _ => Ok(None),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 // Builtin type method compilation (Vec, Box, String, Map, etc.)
 
+mod errors;
 mod ranges_arrays;
 mod string_collections;
 mod vec_box;
@@ -94,9 +95,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     "String" => return self.compile_string_method(&var_name, method, args),
                     "Map" => return self.compile_map_method(&var_name, method, args),
                     "Set" => return self.compile_set_method(&var_name, method, args),
-                    "Range" => return self.compile_range_method(&var_name, method, args, false),
+                    "Range" => return self.compile_range_method(receiver, method, args, false),
                     "RangeInclusive" => {
-                        return self.compile_range_method(&var_name, method, args, true)
+                        return self.compile_range_method(receiver, method, args, true)
                     }
                     "Slice" => return self.compile_slice_method(&var_name, method, args),
                     "Channel" => return self.compile_channel_method(&var_name, method, args),
@@ -5,13 +5,45 @@ use inkwell::values::BasicValueEnum;
 use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
-    pub(super) fn compile_range_method(
+    /// Builtin methods on Range / RangeInclusive values. `next` and `len` keep
+    /// their runtime signatures; `rev` and `step_by` return a new range and
+    /// leave the receiver untouched.
+    pub(crate) fn compile_range_method(
         &mut self,
-        var_name: &str,
+        receiver: &Expression,
         method: &str,
         args: &[Expression],
         inclusive: bool,
     ) -> Result<Option<BasicValueEnum<'ctx>>, String> {
+        if !matches!(
+            method,
+            "next" | "len" | "is_empty" | "contains" | "rev" | "step_by"
+        ) {
+            return Ok(None);
+        }
+
+        // Variables are used in place (next() advances them); other receivers
+        // such as (0..10).rev() are materialized first
+        let range_ptr = match receiver {
+            Expression::Ident(var_name) if self.variables.contains_key(var_name) => {
+                self.variables[var_name]
+            }
+            _ => {
+                let value = self.compile_expression(receiver)?;
+                self.spill_range_value(value, "range_receiver")?
+            }
+        };
+
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let runtime_name = |name: &str| {
+            if inclusive {
+                format!("vex_range_inclusive_{}", name)
+            } else {
+                format!("vex_range_{}", name)
+            }
+        };
+
         match method {
             "next" => {
                 // r.next(&out) -> bool
@@ -20,77 +52,102 @@ impl<'ctx> ASTCodeGen<'ctx> {
                         "Range.next() requires exactly 1 argument (output pointer)".to_string()
                     );
                 }
-
-                // Compile output pointer argument first (before borrowing range_ptr)
                 let out_ptr = self.compile_expression(&args[0])?;
 
-                // Get range pointer
-                let range_ptr = self
-                    .variables
-                    .get(var_name)
-                    .ok_or_else(|| format!("Range variable {} not found", var_name))?
-                    .clone();
-
                 // vex_range_next expects: (ptr range, ptr out_value) -> i1
-                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
-                let fn_name = if inclusive {
-                    "vex_range_inclusive_next"
-                } else {
-                    "vex_range_next"
-                };
-
+                let fn_name = runtime_name("next");
                 let next_fn = self.declare_runtime_fn(
-                    fn_name,
+                    &fn_name,
                     &[ptr_type.into(), ptr_type.into()],
                     self.context.bool_type().into(),
                 );
-
                 let call_site = self
                     .builder
                     .build_call(next_fn, &[range_ptr.into(), out_ptr.into()], "range_next")
                     .map_err(|e| format!("Failed to call {}: {}", fn_name, e))?;
-
-                let result = call_site.try_as_basic_value().unwrap_basic();
-
-                Ok(Some(result))
+                Ok(Some(call_site.try_as_basic_value().unwrap_basic()))
             }
-            "len" => {
-                // r.len() -> i64
+            "len" | "is_empty" => {
+                // r.len() -> i64 (values left to yield), r.is_empty() -> bool
                 if !args.is_empty() {
-                    return Err("Range.len() takes no arguments".to_string());
+                    return Err(format!("Range.{}() takes no arguments", method));
                 }
-
-                // Declare function first
-                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
-                let fn_name = if inclusive {
-                    "vex_range_inclusive_len"
-                } else {
-                    "vex_range_len"
-                };
-
-                let len_fn = self.declare_runtime_fn(
-                    fn_name,
-                    &[ptr_type.into()],
-                    self.context.i64_type().into(),
+                let fn_name = runtime_name("len");
+                let len_fn = self.declare_runtime_fn(&fn_name, &[ptr_type.into()], i64_type.into());
+                let len = self
+                    .builder
+                    .build_call(len_fn, &[range_ptr.into()], "range_len")
+                    .map_err(|e| format!("Failed to call {}: {}", fn_name, e))?
+                    .try_as_basic_value()
+                    .unwrap_basic();
+                if method == "len" {
+                    return Ok(Some(len));
+                }
+                let is_empty = self
+                    .builder
+                    .build_int_compare(
+                        inkwell::IntPredicate::EQ,
+                        len.into_int_value(),
+                        i64_type.const_zero(),
+                        "range_is_empty",
+                    )
+                    .map_err(|e| format!("Failed to compare range length: {}", e))?;
+                Ok(Some(is_empty.into()))
+            }
+            "contains" => {
+                // r.contains(x) -> bool, a bounds check ignoring step and direction
+                if args.len() != 1 {
+                    return Err("Range.contains() requires exactly 1 argument".to_string());
+                }
+                let value = self.compile_expression(&args[0])?;
+                let value = self.range_bound_to_i64(value, "value")?;
+                let fn_name = runtime_name("contains");
+                let contains_fn = self.declare_runtime_fn(
+                    &fn_name,
+                    &[ptr_type.into(), i64_type.into()],
+                    self.context.bool_type().into(),
                 );
-
-                // Get range pointer after declaring function
-                let range_ptr = self
-                    .variables
-                    .get(var_name)
-                    .ok_or_else(|| format!("Range variable {} not found", var_name))?
-                    .clone();
-
                 let call_site = self
                     .builder
-                    .build_call(len_fn, &[range_ptr.into()], "range_len")
+                    .build_call(
+                        contains_fn,
+                        &[range_ptr.into(), value.into()],
+                        "range_contains",
+                    )
                     .map_err(|e| format!("Failed to call {}: {}", fn_name, e))?;
+                Ok(Some(call_site.try_as_basic_value().unwrap_basic()))
+            }
+            _ => {
+                // r.rev() / r.step_by(n) -> new range; the runtime edits a copy in place
+                let range_copy = self.spill_range_value(range_ptr.into(), "range_result")?;
+                let fn_name = runtime_name(method);
+                if method == "step_by" {
+                    if args.len() != 1 {
+                        return Err("Range.step_by() requires exactly 1 argument".to_string());
+                    }
+                    let step = self.compile_expression(&args[0])?;
+                    let step = self.range_bound_to_i64(step, "step")?;
+                    let step_fn =
+                        self.declare_runtime_fn_void(&fn_name, &[ptr_type.into(), i64_type.into()]);
+                    self.builder
+                        .build_call(step_fn, &[range_copy.into(), step.into()], "")
+                        .map_err(|e| format!("Failed to call {}: {}", fn_name, e))?;
+                } else {
+                    if !args.is_empty() {
+                        return Err("Range.rev() takes no arguments".to_string());
+                    }
+                    let rev_fn = self.declare_runtime_fn_void(&fn_name, &[ptr_type.into()]);
+                    self.builder
+                        .build_call(rev_fn, &[range_copy.into()], "")
+                        .map_err(|e| format!("Failed to call {}: {}", fn_name, e))?;
+                }
 
-                let result = call_site.try_as_basic_value().unwrap_basic();
-
+                let result = self
+                    .builder
+                    .build_load(self.range_struct_type(), range_copy, "range_value")
+                    .map_err(|e| format!("Failed to load range: {}", e))?;
                 Ok(Some(result))
             }
-            _ => Ok(None),
         }
     }
 
@@ -149,14 +206,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 let elem_ptr = call_site.try_as_basic_value().unwrap_basic();
 
                 // Load the element value (vex_slice_get returns void*)
-                // For now, assume i32 elements (TODO: type-specific loading)
+                let elem_type =
+                    self.indexed_element_llvm_type(&Expression::Ident(var_name.to_string()));
                 let elem_val = self
                     .builder
-                    .build_load(
-                        self.context.i32_type(),
-                        elem_ptr.into_pointer_value(),
-                        "slice_elem",
-                    )
+                    .build_load(elem_type, elem_ptr.into_pointer_value(), "slice_elem")
                     .map_err(|e| format!("Failed to load slice element: {}", e))?;
 
                 Ok(Some(elem_val))
@@ -353,8 +407,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .map_err(|e| format!("Failed to load channel pointer: {}", e))?
                     .into_pointer_value();
 
-                // Compile the value to send
-                let value = self.compile_expression(&args[0])?;
+                // Compile the value to send as the channel's element type
+                let channel_expr = Expression::Ident(var_name.to_string());
+                let elem_ty = self.channel_element_type(&channel_expr);
+                let elem_llvm = self.ast_type_to_llvm(&elem_ty);
+                let value = self.compile_channel_send_value(&args[0], &elem_ty, elem_llvm)?;
 
                 // Delegate to static builtin: Channel.send(ch, value)
                 let builtin_fn = self
@@ -384,13 +441,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .map_err(|e| format!("Failed to load channel pointer: {}", e))?
                     .into_pointer_value();
 
-                // Delegate to static builtin: Channel.recv(ch)
-                let builtin_fn = self
-                    .builtins
-                    .get("Channel.recv")
-                    .ok_or_else(|| "Channel.recv builtin not registered".to_string())?;
-
-                let result = builtin_fn(self, &[channel_ptr.into()])?;
+                // Load the received value as the channel's element type
+                let channel_expr = Expression::Ident(var_name.to_string());
+                let elem_ty = self.channel_element_type(&channel_expr);
+                let elem_llvm = self.ast_type_to_llvm(&elem_ty);
+                let result = self.build_channel_recv(channel_ptr, elem_llvm)?;
                 return Ok(Some(result));
             }
             _ => Ok(None),
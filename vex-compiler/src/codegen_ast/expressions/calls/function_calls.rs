@@ -747,7 +747,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             type_args.to_vec()
                         } else {
                             eprintln!("  ⚠️  Inferring type args for {} from arguments", func_name);
-                            self.infer_type_args_from_call(&func_def, args)?
+                            self.infer_type_args_from_call(&func_def, args, expected_type)?
                         };
 
                         eprintln!(
@@ -755,6 +755,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             func_name, final_type_args
                         );
 
+                        // Closures passed for Callable/CallableMut parameters
+                        if let Some(checker) = &self.trait_bounds_checker {
+                            let capture_modes: Vec<Option<CaptureMode>> = args
+                                .iter()
+                                .map(|arg| self.closure_capture_mode(arg))
+                                .collect();
+                            checker.check_closure_arguments(&func_def, &capture_modes)?;
+                        }
+
                         // Instantiate
                         let fn_val =
                             self.instantiate_generic_function(&func_def, &final_type_args)?;
@@ -12,6 +12,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         type_args: &[Type], // ⭐ NEW: Generic type arguments for static methods
         args: &[Expression],
         is_mutable_call: bool,
+        expected_type: Option<&Type>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
         // ⭐ NEW: Phase 0: Check for builtin contract methods (i32.to_string(), bool.clone(), etc.)
         // This must come BEFORE builtin type methods to support contract-based dispatch
@@ -19,6 +20,40 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return Ok(result);
         }
 
+        // error values (error.new) have builtin methods but no struct name
+        if let Expression::Ident(var_name) = receiver {
+            if matches!(self.variable_ast_types.get(var_name), Some(Type::Error)) {
+                if let Some(result) = self.compile_error_method(var_name, method, args)? {
+                    return Ok(result);
+                }
+            }
+        }
+
+        // Contract objects dispatch through their vtable
+        if let Some(contract) = self
+            .infer_expression_type(receiver)
+            .ok()
+            .and_then(|ty| self.dyn_contract(&ty))
+        {
+            return self.compile_dyn_method_call(receiver, &contract, method, args);
+        }
+
+        // Range values: r.contains(x), (0..10).rev(), r.step_by(2), ...
+        if let Some(inclusive) = self
+            .infer_expression_type(receiver)
+            .ok()
+            .and_then(|ty| self.range_kind(&ty))
+        {
+            if let Some(result) = self.compile_range_method(receiver, method, args, inclusive)? {
+                return Ok(result);
+            }
+        }
+
+        // Integer overflow methods: x.wrapping_add(y), x.checked_mul(y), x.saturating_sub(y)
+        if let Some(result) = self.compile_int_overflow_method(receiver, method, args)? {
+            return Ok(result);
+        }
+
         // Check if this is a module-level function call (io.print, log.info, etc.)
         if let Expression::Ident(module_name) = receiver {
             eprintln!(
@@ -376,6 +411,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Instance method call - get receiver info
         let (struct_name, receiver_val) = self.get_receiver_info(receiver)?;
+        let (struct_name, receiver_val) =
+            self.intersection_method_receiver(struct_name, receiver_val, method, args)?;
 
         // ⭐ Phase 2: Use variable_concrete_types for receiver type (more reliable)
         let receiver_type = if let Expression::Ident(var_name) = receiver {
@@ -464,9 +501,19 @@ impl<'ctx> ASTCodeGen<'ctx> {
             let parts: Vec<&str> = struct_name.split('_').collect();
             let base_struct_name = parts[0];
 
-            // Check if this is a generic struct with type arguments
+            // Check if this is a generic struct with type arguments, or a generic method
             if let Some(struct_def) = self.struct_ast_defs.get(base_struct_name) {
-                if !struct_def.type_params.is_empty() {
+                let struct_is_generic =
+                    !struct_def.type_params.is_empty() || !struct_def.const_params.is_empty();
+                let method_is_generic = struct_def
+                    .methods
+                    .iter()
+                    .any(|m| m.name == method && !m.type_params.is_empty())
+                    || self
+                        .function_defs
+                        .get(&format!("{}_{}", base_struct_name, method))
+                        .is_some_and(|m| !m.type_params.is_empty());
+                if struct_is_generic || method_is_generic {
                     eprintln!(
                         "🔍 Attempting generic method instantiation for {}.{}",
                         struct_name, method
@@ -478,7 +525,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                     // ⭐ Phase 2: Use type args from receiver type (already extracted above)
                     // This is more reliable than parsing mangled names
-                    if struct_type_args.is_empty() {
+                    if struct_is_generic && struct_type_args.is_empty() {
                         eprintln!(
                             "⚠️  No type args found for generic struct {}",
                             base_struct_name
@@ -491,11 +538,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                     // Try to find generic method definition
                     if let Ok(method_def) = self.find_generic_method(base_struct_name, method) {
-                        // Infer argument types for type parameter resolution
-                        let arg_types: Vec<Type> = args
-                            .iter()
-                            .map(|arg| self.infer_expression_type(arg))
-                            .collect::<Result<Vec<_>, _>>()?;
+                        // Method-level type params: explicit list.map<string>(f), else inferred
+                        let method_type_args = if method_def.type_params.is_empty() {
+                            Vec::new()
+                        } else if !type_args.is_empty() {
+                            type_args.to_vec()
+                        } else {
+                            self.infer_method_type_args(
+                                base_struct_name,
+                                &struct_type_args,
+                                &method_def,
+                                args,
+                                expected_type,
+                            )?
+                        };
 
                         // Instantiate the generic method!
                         match self.instantiate_generic_method(
@@ -503,7 +559,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             &struct_type_args,
                             method,
                             &method_def,
-                            &arg_types,
+                            &method_type_args,
                         ) {
                             Ok(fn_val) => {
                                 eprintln!("✅ Generic method instantiated successfully!");
@@ -513,7 +569,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                                     base_struct_name,
                                     &struct_type_args,
                                     method,
-                                    receiver,
+                                    &method_type_args,
                                     receiver_val.into(), // Convert PointerValue to BasicValueEnum
                                     args,
                                 )?;
@@ -546,7 +602,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
             // This is for stdlib types that have compiler builtin support
             let is_stdlib_with_builtin = matches!(
                 base_struct_name,
-                "Vec" | "Box" | "String" | "Map" | "Set" | "Channel" | "Array"
+                "Vec" | "Box" | "String" | "Map" | "Set" | "Channel" | "Array" | "Slice"
             );
 
             if is_stdlib_with_builtin {
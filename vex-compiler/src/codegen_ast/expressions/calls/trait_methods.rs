@@ -2,6 +2,9 @@
 
 use crate::codegen_ast::ASTCodeGen;
 use crate::debug_println;
+use crate::type_system::object_safety;
+use inkwell::types::{BasicMetadataTypeEnum, BasicType};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum};
 use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -417,8 +420,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let func = vex_ast::Function {
             is_exported: false,
             span_id: None, // Synthetic method, no source span
+            attributes: vec![],
             is_async: false,
             is_gpu: false,
+            is_const: false,
             is_mutable: trait_method.is_mutable, // ⭐ NEW: Copy mutability from trait
             is_operator: trait_method.is_operator, // ⭐ NEW: Copy operator flag from trait
             is_static: false,
@@ -500,4 +505,97 @@ impl<'ctx> ASTCodeGen<'ctx> {
             _ => ty.clone(),
         }
     }
+
+    /// Call a method on a contract object: load the function pointer from the
+    /// method's vtable slot and call it with the object's data pointer as receiver
+    pub(crate) fn compile_dyn_method_call(
+        &mut self,
+        receiver: &Expression,
+        contract: &str,
+        method: &str,
+        args: &[Expression],
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let trait_def = self
+            .trait_defs
+            .get(contract)
+            .ok_or_else(|| format!("Unknown contract `{}` in `dyn {}`", contract, contract))?;
+        let slots = object_safety::vtable_methods(trait_def, &|name| self.trait_defs.get(name))?;
+        let (slot, trait_method) = slots
+            .into_iter()
+            .enumerate()
+            .find(|(_, m)| m.name == method)
+            .map(|(slot, m)| (slot, m.clone()))
+            .ok_or_else(|| format!("Method '{}' not found for `dyn {}`", method, contract))?;
+        eprintln!(
+            "🎭 dyn {}.{}() through vtable slot {}",
+            contract, method, slot
+        );
+
+        let dyn_type = self.dyn_llvm_type();
+        let object = match self.compile_expression(receiver)? {
+            BasicValueEnum::PointerValue(ptr) => self
+                .builder
+                .build_load(dyn_type, ptr, "dyn_object")
+                .map_err(|e| format!("Failed to load contract object: {}", e))?,
+            value => value,
+        }
+        .into_struct_value();
+        let data = self
+            .builder
+            .build_extract_value(object, 0, "dyn_data")
+            .map_err(|e| format!("Failed to read contract object data: {}", e))?;
+        let vtable = self
+            .builder
+            .build_extract_value(object, 1, "dyn_vtable")
+            .map_err(|e| format!("Failed to read contract object vtable: {}", e))?
+            .into_pointer_value();
+
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let slot_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(
+                    ptr_type,
+                    vtable,
+                    &[self.context.i64_type().const_int(slot as u64, false)],
+                    "dyn_slot",
+                )
+                .map_err(|e| format!("Failed to index vtable: {}", e))?
+        };
+        let fn_ptr = self
+            .builder
+            .build_load(ptr_type, slot_ptr, &format!("dyn_{}", method))
+            .map_err(|e| format!("Failed to load vtable entry: {}", e))?
+            .into_pointer_value();
+
+        // Signature comes from the contract; every implementation shares it
+        let mut param_types: Vec<BasicMetadataTypeEnum<'ctx>> = vec![ptr_type.into()];
+        let mut arg_vals: Vec<BasicMetadataValueEnum<'ctx>> = vec![data.into()];
+        for (arg, param) in args.iter().zip(&trait_method.params) {
+            let param_type = self.ast_type_to_llvm(&param.ty);
+            let mut val = self.compile_expression_with_type(arg, Some(&param.ty))?;
+            if val.is_pointer_value() && param_type.is_struct_type() {
+                val = self
+                    .builder
+                    .build_load(param_type, val.into_pointer_value(), "dyn_arg")
+                    .map_err(|e| format!("Failed to load struct arg: {}", e))?;
+            }
+            param_types.push(param_type.into());
+            arg_vals.push(val.into());
+        }
+        let fn_type = match &trait_method.return_type {
+            Some(ty) => self.ast_type_to_llvm(ty).fn_type(&param_types, false),
+            None => self.context.void_type().fn_type(&param_types, false),
+        };
+
+        let call_site = self
+            .builder
+            .build_indirect_call(fn_type, fn_ptr, &arg_vals, "dyncall")
+            .map_err(|e| format!("Failed to build dyn method call: {}", e))?;
+        if let Some(val) = call_site.try_as_basic_value().basic() {
+            Ok(val)
+        } else {
+            // Void method - return a dummy i32 zero
+            Ok(self.context.i32_type().const_int(0, false).into())
+        }
+    }
 }
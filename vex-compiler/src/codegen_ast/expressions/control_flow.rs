@@ -1,4 +1,4 @@
-// Expression compilation - control flow (match, block, ?, await)
+// Expression compilation - control flow (match, block, ?, await, yield)
 use super::ASTCodeGen;
 use inkwell::values::BasicValueEnum;
 
@@ -6,11 +6,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
     /// Compile match expressions
     pub(crate) fn compile_match_dispatch(
         &mut self,
+        span_id: &Option<String>,
         value: &vex_ast::Expression,
         arms: &[vex_ast::MatchArm],
         expected_type: Option<&vex_ast::Type>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
-        self.compile_match_expression(value, arms, expected_type)
+        self.compile_match_expression(span_id, value, arms, expected_type)
     }
 
     /// Compile block expressions
@@ -143,16 +144,38 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Compile await expressions with full state machine support
+    ///
+    /// Inside an async function the coroutine yields until the awaited Future<T> is
+    /// ready, re-yielding from its resume block while it is still pending. Outside
+    /// async code the calling thread blocks on the future. Either way the result is
+    /// loaded from the future with T's LLVM type.
     pub(crate) fn compile_await_dispatch(
         &mut self,
         expr: &vex_ast::Expression,
     ) -> Result<BasicValueEnum<'ctx>, String> {
         eprintln!("⏸️ compile_await_dispatch: expr={:?}", expr);
 
+        // Only Future<T> values are awaited; anything else is already a value
+        let result_type = match self.infer_expression_type(expr) {
+            Ok(vex_ast::Type::Future(result_ty)) => Some(*result_ty),
+            _ => None,
+        };
+
         // Check if we're inside an async function
         if self.async_state_stack.is_empty() {
-            // Not in async context - just compile expression and return it
-            return self.compile_expression(expr);
+            let value = self.compile_expression(expr)?;
+            let (Some(result_type), BasicValueEnum::PointerValue(future_ptr)) =
+                (result_type, value)
+            else {
+                return Ok(value);
+            };
+
+            // Synchronous caller: block until a worker completes the future
+            let wait_fn = self.get_or_declare_future_fn("vex_future_wait")?;
+            self.builder
+                .build_call(wait_fn, &[future_ptr.into()], "")
+                .map_err(|e| format!("Failed to call vex_future_wait: {}", e))?;
+            return self.load_awaited_value(future_ptr, &result_type);
         }
 
         // ⭐ PHASE 2: Full state machine implementation
@@ -170,7 +193,22 @@ impl<'ctx> ASTCodeGen<'ctx> {
         );
 
         // Compile the future expression
-        let _future_val = self.compile_expression(expr)?;
+        let future_val = self.compile_expression(expr)?;
+
+        // The future handle must survive the yield: keep it in an entry-block slot
+        // that is spilled into the state struct like any other live local
+        let future_slot = match (&result_type, future_val) {
+            (Some(_), BasicValueEnum::PointerValue(future_ptr)) => {
+                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+                let slot =
+                    self.create_entry_block_alloca_for_type(ptr_type.into(), "awaited_future")?;
+                self.builder
+                    .build_store(slot, future_ptr)
+                    .map_err(|e| format!("Failed to store awaited future: {}", e))?;
+                Some(slot)
+            }
+            _ => None,
+        };
 
         // Generate next state ID
         let next_state_id = current_state_id + 1;
@@ -198,35 +236,139 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Return CORO_STATUS_YIELDED (1) to runtime
         let yielded_status = self.context.i32_type().const_int(1, false);
-        self.builder
+        let yield_ret = self
+            .builder
             .build_return(Some(&yielded_status))
             .map_err(|e| format!("Failed to build yield return: {}", e))?;
 
+        // Locals live across this await are spilled/reloaded around it later
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let extra_locals: Vec<_> = future_slot
+            .iter()
+            .map(|slot| (*slot, ptr_type.into()))
+            .collect();
+        self.record_async_yield(yield_ret, resume_block, &extra_locals);
+
         eprintln!("  Positioned at resume block");
 
         // ⭐ Position builder at resume block - this is where execution continues after yield
         // No need for separate continuation block - resume block IS the continuation
         self.builder.position_at_end(resume_block);
 
-        // Verify builder position
-        let current_block_after = self
-            .builder
-            .get_insert_block()
-            .and_then(|bb| bb.get_name().to_str().ok().map(|s| s.to_string()));
-        eprintln!("  🔍 Builder positioned at: {:?}", current_block_after);
-
         // Update state machine context with new state ID
         self.async_state_stack.pop();
         self.async_state_stack
             .push((state_ptr, state_field_ptr, next_state_id));
 
-        // Ensure resume block gets terminated - if we're here, it means there are more statements
-        // coming after this await that will compile into this block and add the terminator
+        let (Some(result_type), Some(future_slot)) = (result_type, future_slot) else {
+            // Nothing to wait for (e.g. awaiting a plain value): resume immediately
+            return Ok(future_val);
+        };
+
+        // Still pending: yield again. State id and spill slots are unchanged since
+        // the last yield, so the resume block reloads the same values next time.
+        let resume_fn = self
+            .current_async_resume_fn
+            .ok_or("Await outside async resume function")?;
+        let pending_block = self
+            .context
+            .append_basic_block(resume_fn, &format!("await_pending_{}", next_state_id));
+        let ready_block = self
+            .context
+            .append_basic_block(resume_fn, &format!("await_ready_{}", next_state_id));
+
+        let future_ptr = self
+            .builder
+            .build_load(ptr_type, future_slot, "awaited_future")
+            .map_err(|e| format!("Failed to load awaited future: {}", e))?
+            .into_pointer_value();
+        let is_ready_fn = self.get_or_declare_future_fn("vex_future_is_ready")?;
+        let is_ready = self
+            .builder
+            .build_call(is_ready_fn, &[future_ptr.into()], "future_ready")
+            .map_err(|e| format!("Failed to call vex_future_is_ready: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_int_value();
+        self.builder
+            .build_conditional_branch(is_ready, ready_block, pending_block)
+            .map_err(|e| format!("Failed to branch on future readiness: {}", e))?;
+
+        self.builder.position_at_end(pending_block);
+        self.builder
+            .build_return(Some(&yielded_status))
+            .map_err(|e| format!("Failed to build pending yield: {}", e))?;
+
+        self.builder.position_at_end(ready_block);
+        self.load_awaited_value(future_ptr, &result_type)
+    }
+
+    /// Load T from a ready future and free it; unit futures (`async fn` with no
+    /// return type) produce a dummy i32 like other void expressions.
+    /// The completed coroutine has let go of the future, so this frees it.
+    fn load_awaited_value(
+        &mut self,
+        future_ptr: inkwell::values::PointerValue<'ctx>,
+        result_type: &vex_ast::Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let value = if matches!(result_type, vex_ast::Type::Nil) {
+            self.context.i32_type().const_int(0, false).into()
+        } else {
+            self.load_future_result(future_ptr, result_type)?
+        };
+
+        let free_fn = self.get_or_declare_future_fn("vex_future_free")?;
+        self.builder
+            .build_call(free_fn, &[future_ptr.into()], "")
+            .map_err(|e| format!("Failed to call vex_future_free: {}", e))?;
+        Ok(value)
+    }
 
-        eprintln!("  Returning placeholder value");
+    /// Compile yield expressions inside a generator
+    ///
+    /// The item is stored in the generator handle and the resume function returns
+    /// YIELDED; the next call to resume continues from the following state.
+    pub(crate) fn compile_yield_dispatch(
+        &mut self,
+        expr: &vex_ast::Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let (state_ptr, state_field_ptr, current_state_id) = self
+            .async_state_stack
+            .last()
+            .copied()
+            .ok_or("`yield` outside a generator function")?;
+
+        let value = self.compile_expression(expr)?;
+        self.store_generator_item(value)?;
+
+        let next_state_id = current_state_id + 1;
+        let resume_block = self
+            .async_resume_blocks
+            .get((next_state_id - 1) as usize)
+            .copied()
+            .ok_or_else(|| format!("Resume block {} not pre-allocated", next_state_id))?;
+
+        self.builder
+            .build_store(
+                state_field_ptr,
+                self.context
+                    .i32_type()
+                    .const_int(next_state_id as u64, false),
+            )
+            .map_err(|e| format!("Failed to save state: {}", e))?;
+
+        let yielded_status = self.context.i32_type().const_int(1, false);
+        let yield_ret = self
+            .builder
+            .build_return(Some(&yielded_status))
+            .map_err(|e| format!("Failed to build yield return: {}", e))?;
+        self.record_async_yield(yield_ret, resume_block, &[]);
+
+        self.builder.position_at_end(resume_block);
+        self.async_state_stack.pop();
+        self.async_state_stack
+            .push((state_ptr, state_field_ptr, next_state_id));
 
-        // TODO: Load future result from runtime when available
-        // For now, return placeholder (0)
         Ok(self.context.i32_type().const_int(0, false).into())
     }
 }
@@ -1,4 +1,4 @@
-use super::super::super::ASTCodeGen;
+use super::super::super::{ASTCodeGen, OPTION_NONE_TAG, OPTION_SOME_TAG};
 use crate::type_system::coercion_rules::{
     classify_coercion, coercion_policy, format_coercion_error, format_coercion_warning,
     CoercionPolicy,
@@ -346,6 +346,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         enum_name: &str,
         variant: &str,
         data: &Vec<Expression>,
+        expected_type: Option<&Type>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
         eprintln!(
             "📗 compile_builtin_enum_literal: {}::{}, data.len={}",
@@ -357,17 +358,31 @@ impl<'ctx> ASTCodeGen<'ctx> {
             "Option" => {
                 // Option<T> = { i32 tag, T value }
                 // Some(x) = tag=0, None = tag=1
-                let tag_value = if variant == "Some" { 0 } else { 1 };
+                let tag_value = if variant == "Some" {
+                    OPTION_SOME_TAG
+                } else {
+                    OPTION_NONE_TAG
+                };
                 let tag = self.context.i32_type().const_int(tag_value, false);
+                let expected_payload = match expected_type {
+                    Some(Type::Option(inner)) => Some(inner.as_ref()),
+                    _ => None,
+                };
 
                 if variant == "None" {
-                    // None: Create Option<i32> with tag=0 and zero value
-                    // Default to i32 for now (proper type inference from context later)
-                    let default_value_type = self.context.i32_type();
-                    let zero_value = default_value_type.const_zero();
+                    // Option<&T>: None is the null niche
+                    if let Some(inner) = expected_payload.filter(|t| Self::option_uses_niche(t)) {
+                        return Ok(self.get_default_value(&self.ast_type_to_llvm(inner)));
+                    }
+
+                    // None: payload type from the expected Option<T>, i32 without context
+                    let default_value_type = expected_payload
+                        .map(|inner| self.ast_type_to_llvm(inner))
+                        .unwrap_or_else(|| self.context.i32_type().into());
+                    let zero_value = self.get_default_value(&default_value_type);
 
                     let option_struct_type = self.context.struct_type(
-                        &[self.context.i32_type().into(), default_value_type.into()],
+                        &[self.context.i32_type().into(), default_value_type],
                         false,
                     );
 
@@ -376,7 +391,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                         .build_alloca(option_struct_type, "option_none")
                         .map_err(|e| format!("Failed to allocate Option::None: {}", e))?;
 
-                    // Store tag=0
+                    // Store the None tag
                     let tag_ptr = self
                         .builder
                         .build_struct_gep(option_struct_type, option_ptr, 0, "tag_ptr")
@@ -406,6 +421,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     let value_expr = data
                         .first()
                         .ok_or_else(|| "Some() requires a value argument".to_string())?;
+                    let payload_ast = match expected_payload {
+                        Some(inner) => Some(inner.clone()),
+                        None => self.infer_expression_type(value_expr).ok(),
+                    };
+
+                    // Option<&T>: the non-null reference is the whole value
+                    if let Some(inner) = payload_ast.filter(Self::option_uses_niche) {
+                        return self.compile_expression_with_type(value_expr, Some(&inner));
+                    }
+
                     let value = self.compile_expression(value_expr)?;
                     let value_type = value.get_type();
 
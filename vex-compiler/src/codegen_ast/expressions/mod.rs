@@ -17,7 +17,9 @@ mod literals;
 mod literals_expressions;
 mod operators;
 pub(crate) mod pattern_matching;
+mod ranges;
 mod references;
+mod slices;
 mod special;
 mod special_expressions;
 mod structs_enums;
@@ -37,6 +39,22 @@ impl<'ctx> ASTCodeGen<'ctx> {
         expr: &vex_ast::Expression,
         expected_type: Option<&vex_ast::Type>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
+        // Values flowing into a union slot are wrapped with their member tag
+        if let Some(members) = expected_type.and_then(|ty| self.union_members(ty)) {
+            return self.compile_into_union(expr, &members);
+        }
+
+        // Values flowing into a contract object slot are paired with their vtable
+        if let Some(contract) = expected_type.and_then(|ty| self.dyn_contract(ty)) {
+            return self.compile_into_dyn(expr, &contract);
+        }
+
+        // Records convert to and from their member structs by field name
+        if let Some((from, to)) = expected_type.and_then(|ty| self.record_conversion(expr, ty)) {
+            let value = self.compile_expression(expr)?;
+            return self.project_record(value, &from, &to);
+        }
+
         match expr {
             Expression::IntLiteral(_)
             | Expression::TypedIntLiteral { .. }
@@ -51,11 +69,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Expression::Ident(name) => self.compile_identifier(name),
 
             Expression::Binary {
-                span_id: _,
+                span_id,
+                left,
+                op,
+                right,
+            } => self.compile_binary_op_with_expected(
                 left,
                 op,
                 right,
-            } => self.compile_binary_op_with_expected(left, op, right, expected_type),
+                expected_type,
+                span_id.as_ref(),
+            ),
 
             Expression::Unary {
                 span_id: _,
@@ -76,7 +100,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 type_args,
                 args,
                 is_mutable_call,
-            } => self.compile_method_call(receiver, method, type_args, args, *is_mutable_call),
+            } => self.compile_method_call(
+                receiver,
+                method,
+                type_args,
+                args,
+                *is_mutable_call,
+                expected_type,
+            ),
 
             Expression::Index { object, index } => self.compile_index(object, index),
 
@@ -101,8 +132,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Expression::PostfixOp { expr, op } => self.compile_postfix_op_dispatch(expr, op),
 
             Expression::Await(expr) => self.compile_await_dispatch(expr),
+            Expression::Yield(expr) => self.compile_yield_dispatch(expr),
 
-            Expression::Match { value, arms } => self.compile_match_dispatch(value, arms, expected_type),
+            Expression::Match {
+                span_id,
+                value,
+                arms,
+            } => self.compile_match_dispatch(span_id, value, arms, expected_type),
 
             Expression::Block {
                 statements,
@@ -130,7 +166,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 enum_name,
                 variant,
                 data,
-            } => self.compile_enum_dispatch(enum_name, variant, data),
+                field_names,
+            } => self.compile_enum_dispatch(enum_name, variant, data, field_names, expected_type),
 
             Expression::Closure {
                 params,
@@ -141,6 +178,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             Expression::Cast { expr, target_type } => self.compile_cast_dispatch(expr, target_type),
 
+            Expression::Is { expr, target_type } => self.compile_is_expression(expr, target_type),
+
             Expression::Range { start, end } => self.compile_range_dispatch(start, end, false),
 
             Expression::RangeInclusive { start, end } => {
@@ -155,6 +194,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             Expression::Typeof(expr) => self.compile_typeof_dispatch(expr),
 
+            Expression::New(expr) => self.compile_new_dispatch(expr),
+
+            Expression::Make { element_type, size } => {
+                self.compile_make_dispatch(element_type, size)
+            }
+
+            Expression::ErrorNew(message) => self.compile_error_new_dispatch(message),
+
             _ => {
                 let expr_str = format!("{:?}", expr);
                 self.diagnostics.emit(Diagnostic {
@@ -172,83 +219,4 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
     }
-    /// Compile Range or RangeInclusive expressions
-    fn compile_range(
-        &mut self,
-        start: &Option<Box<Expression>>,
-        end: &Option<Box<Expression>>,
-        _inclusive: bool,
-    ) -> Result<BasicValueEnum<'ctx>, String> {
-        // Default values: start=0, end=max_i64
-        let zero_i64 = self.context.i64_type().const_int(0, false);
-        let max_i64 = self.context.i64_type().const_int(i64::MAX as u64, false);
-
-        // Compile start expression or use 0
-        let start_i64 = if let Some(s) = start {
-            let start_val = self.compile_expression(s)?;
-            if start_val.is_int_value() {
-                let int_val = start_val.into_int_value();
-                if int_val.get_type().get_bit_width() < 64 {
-                    self.builder
-                        .build_int_s_extend(int_val, self.context.i64_type(), "start_ext")
-                        .map_err(|e| format!("Failed to extend start: {}", e))?
-                } else {
-                    int_val
-                }
-            } else {
-                return Err("Range start must be an integer".to_string());
-            }
-        } else {
-            zero_i64
-        };
-
-        // Compile end expression or use max_i64
-        let end_i64 = if let Some(e) = end {
-            let end_val = self.compile_expression(e)?;
-            if end_val.is_int_value() {
-                let int_val = end_val.into_int_value();
-                if int_val.get_type().get_bit_width() < 64 {
-                    self.builder
-                        .build_int_s_extend(int_val, self.context.i64_type(), "end_ext")
-                        .map_err(|e| format!("Failed to extend end: {}", e))?
-                } else {
-                    int_val
-                }
-            } else {
-                return Err("Range end must be an integer".to_string());
-            }
-        } else {
-            max_i64
-        };
-
-        // Create Range struct: { start: i64, end: i64, current: i64 }
-        let range_struct_type = self.context.struct_type(
-            &[
-                self.context.i64_type().into(),
-                self.context.i64_type().into(),
-                self.context.i64_type().into(),
-            ],
-            false,
-        );
-
-        // Build struct value
-        let mut range_val = range_struct_type.get_undef();
-        range_val = self
-            .builder
-            .build_insert_value(range_val, start_i64, 0, "range_start")
-            .map_err(|e| format!("Failed to insert start: {}", e))?
-            .into_struct_value();
-        range_val = self
-            .builder
-            .build_insert_value(range_val, end_i64, 1, "range_end")
-            .map_err(|e| format!("Failed to insert end: {}", e))?
-            .into_struct_value();
-        range_val = self
-            .builder
-            .build_insert_value(range_val, start_i64, 2, "range_current")
-            .map_err(|e| format!("Failed to insert current: {}", e))?
-            .into_struct_value();
-
-        Ok(range_val.into())
-    }
 }
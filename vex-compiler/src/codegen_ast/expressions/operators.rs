@@ -10,7 +10,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
         op: &vex_ast::BinaryOp,
         right: &vex_ast::Expression,
         expected_type: Option<&vex_ast::Type>,
+        span_id: Option<&String>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
+        // ?? short-circuits: the right operand must not be compiled eagerly
+        if matches!(op, vex_ast::BinaryOp::NullCoalesce) {
+            if let Some(result) = self.compile_null_coalesce(left, right)? {
+                return Ok(result);
+            }
+        }
+
         // Check for operator overloading BEFORE compiling (needs Expression AST for type inference)
         if let Some(result) = self.check_operator_overloading(left, op, right, expected_type)? {
             return Ok(result);
@@ -84,9 +92,19 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // Align integer widths with AST type awareness
                 let (l, r) =
                     self.align_integer_widths_with_ast(*l, *r, &left_ast_type, &right_ast_type)?;
+                let is_signed = !self.is_unsigned_integer_type(&left_ast_type)
+                    && !self.is_unsigned_integer_type(&right_ast_type);
+                let location = self.span_location(span_id);
                 match op {
                     vex_ast::BinaryOp::Pow => self.compile_int_power(l, r),
-                    _ => self.compile_integer_binary_op_with_expected(l, r, op, expected_type),
+                    _ => self.compile_integer_binary_op_with_expected(
+                        l,
+                        r,
+                        op,
+                        expected_type,
+                        is_signed,
+                        location.as_deref(),
+                    ),
                 }
             }
             (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => {
@@ -117,7 +135,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         op: &vex_ast::BinaryOp,
         right: &vex_ast::Expression,
     ) -> Result<BasicValueEnum<'ctx>, String> {
-        self.compile_binary_op_with_expected(left, op, right, None)
+        self.compile_binary_op_with_expected(left, op, right, None, None)
     }
 
     /// Compile binary operations dispatch
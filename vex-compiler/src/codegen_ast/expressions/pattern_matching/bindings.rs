@@ -12,7 +12,23 @@ impl<'ctx> ASTCodeGen<'ctx> {
         value: BasicValueEnum<'ctx>,
     ) -> Result<(), String> {
         match pattern {
-            Pattern::Wildcard | Pattern::Literal(_) => Ok(()), // No bindings
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Type { binding: None, .. } => Ok(()), // No bindings
+            Pattern::Type {
+                binding: Some(name),
+                ty,
+            } => {
+                let member = self.extract_union_member(value, ty)?;
+                self.bind_variable(name, member)?;
+                let member_type = self.resolve_type(ty);
+                if let vex_ast::Type::Named(struct_name) = &member_type {
+                    if self.struct_defs.contains_key(struct_name) {
+                        self.variable_struct_names
+                            .insert(name.clone(), struct_name.clone());
+                    }
+                }
+                self.variable_ast_types.insert(name.clone(), member_type);
+                Ok(())
+            }
             Pattern::Ident(name) => {
                 // Don't bind if it's a unit enum variant
                 if self.is_enum_variant(name) {
@@ -70,20 +86,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 }
                 Ok(())
             }
-            Pattern::Enum { variant, data, .. } => {
+            Pattern::Enum {
+                name,
+                variant,
+                data,
+                field_names,
+            } => {
                 if !data.is_empty() {
-                    // Value is the full enum struct: { i32 discriminant, T data }
-                    let data_val = if value.is_struct_value() {
-                        let enum_struct = value.into_struct_value();
-                        self.builder
-                            .build_extract_value(enum_struct, 1, "enum_data_bind")
-                            .map_err(|e| {
-                                format!("Failed to extract enum data for binding: {}", e)
-                            })?
-                    } else {
-                        // For pointers or other types, treat as the data value directly
-                        value
-                    };
+                    // Value is the full enum: { i32 discriminant, payload storage }
+                    let (enum_name, _) = self.find_enum_and_variant_index_by_name(name, variant)?;
+                    let data =
+                        self.positional_enum_patterns(&enum_name, variant, data, field_names)?;
+                    let data_val = self.extract_enum_payload(&enum_name, variant, value)?;
 
                     if data.len() == 1 {
                         self.compile_pattern_binding(&data[0], data_val)?;
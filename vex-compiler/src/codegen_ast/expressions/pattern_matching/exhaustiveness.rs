@@ -0,0 +1,1051 @@
+//! Pattern matching: exhaustiveness and reachability
+//!
+//! Arms are lowered into a pattern matrix of constructors applied to
+//! sub-patterns. A pattern is useful when some value matches it but no row
+//! above it; an arm that is not useful is unreachable, and a wildcard that is
+//! still useful after every arm yields a witness value the match misses.
+
+use crate::codegen_ast::ASTCodeGen;
+use crate::type_checker::display_type;
+use crate::type_system::conditional::substitute_params;
+use std::collections::HashMap;
+use vex_ast::{Enum, Expression, MatchArm, Pattern, Struct, Type};
+use vex_diagnostics::{error_codes, Diagnostic, ErrorLevel, Span};
+
+/// Missing values listed in a non-exhaustive error
+const MAX_WITNESSES: usize = 3;
+
+/// Head constructor of a lowered pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Ctor {
+    Variant {
+        enum_name: String,
+        variant: String,
+        arity: usize,
+    },
+    Tuple(usize),
+    /// Fields are the union of every field named for this struct in the match
+    Struct {
+        name: String,
+        fields: Vec<String>,
+    },
+    Bool(bool),
+    /// Integer, string and other literals: the domain is never complete
+    Literal(String),
+    /// Array of exactly `len` elements, or with `...rest` of at least `len`;
+    /// the first `len` elements are its fields
+    Array(usize, bool),
+    /// Union member test `_: T` by the member's position
+    Member(usize),
+}
+
+impl Ctor {
+    fn arity(&self) -> usize {
+        match self {
+            Ctor::Variant { arity, .. } => *arity,
+            Ctor::Tuple(len) | Ctor::Array(len, _) => *len,
+            Ctor::Struct { fields, .. } => fields.len(),
+            Ctor::Bool(_) | Ctor::Literal(_) | Ctor::Member(_) => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Pat {
+    Wild,
+    Ctor(Ctor, Vec<Pat>),
+    Or(Vec<Pat>),
+}
+
+/// Result of checking one `match`
+#[derive(Debug, Default)]
+pub(crate) struct MatchReport {
+    /// Rendered patterns for values no arm matches (at most MAX_WITNESSES + 1)
+    pub missing: Vec<String>,
+    /// Indices of arms already covered by earlier unguarded arms
+    pub unreachable_arms: Vec<usize>,
+}
+
+pub(crate) struct MatchChecker<'a> {
+    enums: &'a HashMap<String, Enum>,
+    /// Type of the matched value, when known; bare names are only variants of it
+    scrutinee: Option<&'a Type>,
+    /// Struct definitions, for the types of fields in struct patterns
+    structs: Option<&'a HashMap<String, Struct>>,
+    /// Members of the scrutinee when it is a union
+    union_members: &'a [Type],
+}
+
+impl<'a> MatchChecker<'a> {
+    pub(crate) fn new(enums: &'a HashMap<String, Enum>) -> Self {
+        Self {
+            enums,
+            scrutinee: None,
+            structs: None,
+            union_members: &[],
+        }
+    }
+
+    /// Check a match on a value of type `ty`
+    pub(crate) fn with_scrutinee(mut self, ty: Option<&'a Type>) -> Self {
+        self.scrutinee = ty;
+        self
+    }
+
+    /// Look up field types of struct patterns in `structs`
+    pub(crate) fn with_structs(mut self, structs: &'a HashMap<String, Struct>) -> Self {
+        self.structs = Some(structs);
+        self
+    }
+
+    /// Check a match on a value of the union `members`
+    pub(crate) fn with_union(mut self, members: &'a [Type]) -> Self {
+        self.union_members = members;
+        self
+    }
+
+    pub(crate) fn check(&self, arms: &[MatchArm]) -> MatchReport {
+        let mut struct_fields: HashMap<String, Vec<String>> = HashMap::new();
+        for arm in arms {
+            collect_struct_fields(&arm.pattern, &mut struct_fields);
+        }
+
+        let mut report = MatchReport::default();
+        let mut matrix: Vec<Vec<Pat>> = Vec::new();
+        for (index, arm) in arms.iter().enumerate() {
+            let pat = self.lower(&arm.pattern, self.scrutinee, &struct_fields);
+            if self
+                .find_witness(&matrix, std::slice::from_ref(&pat))
+                .is_none()
+            {
+                report.unreachable_arms.push(index);
+            }
+            // Guarded arms may fail at runtime, so they never cover anything
+            if arm.guard.is_none() {
+                matrix.push(vec![pat]);
+            }
+        }
+
+        // Each witness is added as a row so the next search finds a different value
+        while report.missing.len() <= MAX_WITNESSES {
+            let witness = match self.find_witness(&matrix, &[Pat::Wild]) {
+                Some(mut witness) => witness.remove(0),
+                None => break,
+            };
+            report.missing.push(self.render(&witness));
+            matrix.push(vec![witness]);
+        }
+
+        report
+    }
+
+    /// Lower a pattern matched against a value of type `ty` (None when unknown)
+    fn lower(
+        &self,
+        pattern: &Pattern,
+        ty: Option<&Type>,
+        struct_fields: &HashMap<String, Vec<String>>,
+    ) -> Pat {
+        match pattern {
+            Pattern::Wildcard => Pat::Wild,
+            // A bare name is a unit variant only of the matched enum, otherwise a binding
+            Pattern::Ident(name) => match ty.and_then(|ty| self.enum_of(ty)) {
+                Some(enum_name) if self.variant_arity(&enum_name, name) == Some(0) => Pat::Ctor(
+                    Ctor::Variant {
+                        enum_name,
+                        variant: name.clone(),
+                        arity: 0,
+                    },
+                    Vec::new(),
+                ),
+                _ => Pat::Wild,
+            },
+            Pattern::Literal(Expression::BoolLiteral(value)) => {
+                Pat::Ctor(Ctor::Bool(*value), Vec::new())
+            }
+            Pattern::Literal(expr) => Pat::Ctor(Ctor::Literal(format!("{:?}", expr)), Vec::new()),
+            Pattern::Tuple(elements) => {
+                let element_types = match ty.map(peel_references) {
+                    Some(Type::Tuple(types)) => types.as_slice(),
+                    _ => &[],
+                };
+                Pat::Ctor(
+                    Ctor::Tuple(elements.len()),
+                    elements
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| self.lower(p, element_types.get(i), struct_fields))
+                        .collect(),
+                )
+            }
+            Pattern::Struct { name, fields } => {
+                let all_fields = struct_fields.get(name).cloned().unwrap_or_default();
+                let def = self.structs.and_then(|structs| structs.get(name));
+                let args = all_fields
+                    .iter()
+                    .map(|field| {
+                        let field_type = def
+                            .and_then(|def| def.fields.iter().find(|f| &f.name == field))
+                            .map(|f| &f.ty);
+                        fields
+                            .iter()
+                            .find(|(f, _)| f == field)
+                            .map(|(_, p)| self.lower(p, field_type, struct_fields))
+                            .unwrap_or(Pat::Wild)
+                    })
+                    .collect();
+                Pat::Ctor(
+                    Ctor::Struct {
+                        name: name.clone(),
+                        fields: all_fields,
+                    },
+                    args,
+                )
+            }
+            Pattern::Enum {
+                name,
+                variant,
+                data,
+                field_names,
+            } => {
+                // `Some(x)` names no enum: prefer the matched one
+                let expected = ty
+                    .and_then(|ty| self.enum_of(ty))
+                    .filter(|expected| name.is_empty() || name == expected);
+                let (enum_name, arity) = expected
+                    .and_then(|enum_name| {
+                        self.variant_arity(&enum_name, variant)
+                            .map(|arity| (enum_name, arity))
+                    })
+                    .or_else(|| self.resolve_variant(name, variant))
+                    .unwrap_or_else(|| (name.clone(), data.len()));
+                let payload = ty
+                    .map(|ty| self.payload_types(ty, &enum_name, variant))
+                    .unwrap_or_default();
+                let mut args: Vec<Pat> = if field_names.is_empty() {
+                    data.iter()
+                        .enumerate()
+                        .map(|(i, p)| self.lower(p, payload.get(i), struct_fields))
+                        .collect()
+                } else {
+                    // Named fields may appear in any order, or not at all
+                    self.variant_fields(&enum_name, variant)
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field)| {
+                            field_names
+                                .iter()
+                                .position(|f| f == field)
+                                .map(|i| self.lower(&data[i], payload.get(index), struct_fields))
+                                .unwrap_or(Pat::Wild)
+                        })
+                        .collect()
+                };
+                args.resize(arity, Pat::Wild);
+                Pat::Ctor(
+                    Ctor::Variant {
+                        enum_name,
+                        variant: variant.clone(),
+                        arity,
+                    },
+                    args,
+                )
+            }
+            Pattern::Array { elements, rest } => {
+                let element_type = match ty.map(peel_references) {
+                    Some(
+                        Type::Array(elem, _)
+                        | Type::ConstArray {
+                            elem_type: elem, ..
+                        }
+                        | Type::Slice(elem, _)
+                        | Type::Vec(elem),
+                    ) => Some(elem.as_ref()),
+                    _ => None,
+                };
+                Pat::Ctor(
+                    Ctor::Array(elements.len(), rest.is_some()),
+                    elements
+                        .iter()
+                        .map(|p| self.lower(p, element_type, struct_fields))
+                        .collect(),
+                )
+            }
+            Pattern::Or(alternatives) => Pat::Or(
+                alternatives
+                    .iter()
+                    .map(|p| self.lower(p, ty, struct_fields))
+                    .collect(),
+            ),
+            Pattern::Type { ty, .. } => match self.union_members.iter().position(|m| m == ty) {
+                Some(index) => Pat::Ctor(Ctor::Member(index), Vec::new()),
+                None => Pat::Ctor(Ctor::Literal(display_type(ty)), Vec::new()),
+            },
+        }
+    }
+
+    /// Enum name and payload arity of a variant (enum name may be empty: `Some(x)`)
+    fn resolve_variant(&self, enum_name: &str, variant: &str) -> Option<(String, usize)> {
+        if enum_name.is_empty() {
+            for builtin in ["Option", "Result"] {
+                if let Some(arity) = self.variant_arity(builtin, variant) {
+                    return Some((builtin.to_string(), arity));
+                }
+            }
+            return self.enums.values().find_map(|def| {
+                def.variants
+                    .iter()
+                    .find(|v| v.name == variant)
+                    .map(|v| (def.name.clone(), v.data.len()))
+            });
+        }
+        self.variant_arity(enum_name, variant)
+            .map(|arity| (enum_name.to_string(), arity))
+    }
+
+    /// Enum a value of type `ty` belongs to
+    fn enum_of(&self, ty: &Type) -> Option<String> {
+        match peel_references(ty) {
+            Type::Option(_) => Some("Option".to_string()),
+            Type::Result(..) => Some("Result".to_string()),
+            Type::Named(name) | Type::Generic { name, .. } => {
+                self.variants_of(name).map(|_| name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Payload types of `variant` for a value of type `ty`, type arguments substituted
+    fn payload_types(&self, ty: &Type, enum_name: &str, variant: &str) -> Vec<Type> {
+        match (peel_references(ty), variant) {
+            (Type::Option(inner), "Some") | (Type::Result(inner, _), "Ok") => {
+                vec![inner.as_ref().clone()]
+            }
+            (Type::Result(_, err), "Err") => vec![err.as_ref().clone()],
+            (Type::Generic { name, type_args }, _) if name == enum_name => {
+                let Some(def) = self.enums.get(enum_name) else {
+                    return Vec::new();
+                };
+                let subst: HashMap<String, Type> = def
+                    .type_params
+                    .iter()
+                    .map(|param| param.name.clone())
+                    .zip(type_args.iter().cloned())
+                    .collect();
+                def.variants
+                    .iter()
+                    .find(|v| v.name == variant)
+                    .map(|v| {
+                        v.data
+                            .iter()
+                            .map(|t| substitute_params(t, &subst))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            (Type::Named(name), _) if name == enum_name => self
+                .enums
+                .get(enum_name)
+                .and_then(|def| def.variants.iter().find(|v| v.name == variant))
+                .map(|v| v.data.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn variant_arity(&self, enum_name: &str, variant: &str) -> Option<usize> {
+        self.variants_of(enum_name)?
+            .into_iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, arity)| arity)
+    }
+
+    /// Field names of a struct-like variant (empty for tuple and unit variants)
+    fn variant_fields(&self, enum_name: &str, variant: &str) -> Vec<String> {
+        self.enums
+            .get(enum_name)
+            .and_then(|def| def.variants.iter().find(|v| v.name == variant))
+            .map(|v| v.field_names.clone())
+            .unwrap_or_default()
+    }
+
+    /// All variants of an enum in declaration order, with payload arity
+    fn variants_of(&self, enum_name: &str) -> Option<Vec<(String, usize)>> {
+        match enum_name {
+            "Option" => Some(vec![("Some".to_string(), 1), ("None".to_string(), 0)]),
+            "Result" => Some(vec![("Ok".to_string(), 1), ("Err".to_string(), 1)]),
+            _ => self.enums.get(enum_name).map(|def| {
+                def.variants
+                    .iter()
+                    .map(|v| (v.name.clone(), v.data.len()))
+                    .collect()
+            }),
+        }
+    }
+
+    /// Every constructor of the type the `used` head constructors belong to,
+    /// when that set is finite
+    fn all_ctors(&self, used: &[Ctor]) -> Option<Vec<Ctor>> {
+        let ctor = used.first()?;
+        match ctor {
+            Ctor::Variant { enum_name, .. } => self.variants_of(enum_name).map(|variants| {
+                variants
+                    .into_iter()
+                    .map(|(variant, arity)| Ctor::Variant {
+                        enum_name: enum_name.clone(),
+                        variant,
+                        arity,
+                    })
+                    .collect()
+            }),
+            Ctor::Tuple(_) | Ctor::Struct { .. } => Some(vec![ctor.clone()]),
+            Ctor::Bool(_) => Some(vec![Ctor::Bool(true), Ctor::Bool(false)]),
+            Ctor::Member(_) => Some((0..self.union_members.len()).map(Ctor::Member).collect()),
+            Ctor::Array(..) => Some(split_array_lengths(used)),
+            Ctor::Literal(_) => None,
+        }
+    }
+
+    /// Usefulness of `row` against `matrix`, returning a value only `row` matches
+    fn find_witness(&self, matrix: &[Vec<Pat>], row: &[Pat]) -> Option<Vec<Pat>> {
+        let (head, rest) = match row.split_first() {
+            Some(split) => split,
+            None => return matrix.is_empty().then(Vec::new),
+        };
+
+        match head {
+            Pat::Or(alternatives) => alternatives
+                .iter()
+                .find_map(|alt| self.find_witness(matrix, &prepend(alt.clone(), rest))),
+            Pat::Ctor(ctor, _) => {
+                // `[a, ...rest]` stands for every array length it matches
+                let ctors = match ctor {
+                    Ctor::Array(_, true) => {
+                        let mut used = head_ctors(matrix);
+                        used.push(ctor.clone());
+                        split_array_lengths(&used)
+                            .into_iter()
+                            .filter(|len| covers(ctor, len))
+                            .collect()
+                    }
+                    _ => vec![ctor.clone()],
+                };
+                ctors.iter().find_map(|ctor| {
+                    let specialized = specialize(matrix, ctor);
+                    let expanded = specialize_row(row, ctor).into_iter().next()?;
+                    self.find_witness(&specialized, &expanded)
+                        .map(|witness| rebuild(ctor, witness))
+                })
+            }
+            Pat::Wild => {
+                let used = head_ctors(matrix);
+                let is_used = |ctor: &Ctor| used.iter().any(|head| covers(head, ctor));
+                let complete = self.all_ctors(&used).filter(|all| all.iter().all(is_used));
+
+                if let Some(all) = complete {
+                    return all.iter().find_map(|ctor| {
+                        let specialized = specialize(matrix, ctor);
+                        let mut expanded = vec![Pat::Wild; ctor.arity()];
+                        expanded.extend_from_slice(rest);
+                        self.find_witness(&specialized, &expanded)
+                            .map(|witness| rebuild(ctor, witness))
+                    });
+                }
+
+                // Some constructor is missing: only wildcard rows can match it
+                let witness = self.find_witness(&default_matrix(matrix), rest)?;
+                let missing = self
+                    .all_ctors(&used)
+                    .and_then(|all| all.into_iter().find(|ctor| !is_used(ctor)))
+                    .map(|ctor| {
+                        let arity = ctor.arity();
+                        Pat::Ctor(ctor, vec![Pat::Wild; arity])
+                    })
+                    .unwrap_or(Pat::Wild);
+                Some(prepend(missing, &witness))
+            }
+        }
+    }
+
+    /// Render a witness in Vex pattern syntax
+    fn render(&self, pat: &Pat) -> String {
+        match pat {
+            Pat::Wild => "_".to_string(),
+            Pat::Or(alternatives) => alternatives
+                .iter()
+                .map(|p| self.render(p))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Pat::Ctor(ctor, args) => {
+                let rendered: Vec<String> = args.iter().map(|p| self.render(p)).collect();
+                match ctor {
+                    Ctor::Variant {
+                        enum_name, variant, ..
+                    } => {
+                        let head = if enum_name == "Option" || enum_name == "Result" {
+                            variant.clone()
+                        } else {
+                            format!("{}.{}", enum_name, variant)
+                        };
+                        let fields = self.variant_fields(enum_name, variant);
+                        if rendered.is_empty() {
+                            head
+                        } else if fields.len() == rendered.len() {
+                            let fields: Vec<String> = fields
+                                .iter()
+                                .zip(&rendered)
+                                .map(|(field, p)| format!("{}: {}", field, p))
+                                .collect();
+                            format!("{} {{ {} }}", head, fields.join(", "))
+                        } else {
+                            format!("{}({})", head, rendered.join(", "))
+                        }
+                    }
+                    Ctor::Tuple(_) => format!("({})", rendered.join(", ")),
+                    Ctor::Struct { name, fields } => {
+                        let fields: Vec<String> = fields
+                            .iter()
+                            .zip(&rendered)
+                            .map(|(field, p)| format!("{}: {}", field, p))
+                            .collect();
+                        format!("{} {{ {} }}", name, fields.join(", "))
+                    }
+                    Ctor::Bool(value) => value.to_string(),
+                    Ctor::Literal(_) => "_".to_string(),
+                    Ctor::Member(index) => {
+                        format!("_: {}", display_type(&self.union_members[*index]))
+                    }
+                    Ctor::Array(_, has_rest) => {
+                        let mut elements = rendered;
+                        if *has_rest {
+                            elements.push("...".to_string());
+                        }
+                        format!("[{}]", elements.join(", "))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn peel_references(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(inner, _) => peel_references(inner),
+        _ => ty,
+    }
+}
+
+fn prepend(head: Pat, rest: &[Pat]) -> Vec<Pat> {
+    let mut row = Vec::with_capacity(rest.len() + 1);
+    row.push(head);
+    row.extend_from_slice(rest);
+    row
+}
+
+/// Fold the first `arity` columns of a witness back under `ctor`
+fn rebuild(ctor: &Ctor, mut witness: Vec<Pat>) -> Vec<Pat> {
+    let rest = witness.split_off(ctor.arity());
+    prepend(Pat::Ctor(ctor.clone(), witness), &rest)
+}
+
+/// Whether a pattern headed by `head` matches every value built with `ctor`
+fn covers(head: &Ctor, ctor: &Ctor) -> bool {
+    match (head, ctor) {
+        (Ctor::Array(prefix, true), Ctor::Array(len, _)) => len >= prefix,
+        _ => head == ctor,
+    }
+}
+
+/// Array lengths split into classes no array pattern in `used` tells apart:
+/// each length below the longest fixed length or prefix on its own, and every
+/// longer array together as a prefix constructor
+fn split_array_lengths(used: &[Ctor]) -> Vec<Ctor> {
+    let longest = used
+        .iter()
+        .filter_map(|ctor| match ctor {
+            Ctor::Array(len, false) => Some(len + 1),
+            Ctor::Array(len, true) => Some(*len),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    (0..longest)
+        .map(|len| Ctor::Array(len, false))
+        .chain(std::iter::once(Ctor::Array(longest, true)))
+        .collect()
+}
+
+/// Rows that can match a value built with `ctor`, with its fields spliced in
+fn specialize(matrix: &[Vec<Pat>], ctor: &Ctor) -> Vec<Vec<Pat>> {
+    matrix
+        .iter()
+        .flat_map(|row| specialize_row(row, ctor))
+        .collect()
+}
+
+fn specialize_row(row: &[Pat], ctor: &Ctor) -> Vec<Vec<Pat>> {
+    let (head, rest) = match row.split_first() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+    match head {
+        Pat::Wild => {
+            let mut expanded = vec![Pat::Wild; ctor.arity()];
+            expanded.extend_from_slice(rest);
+            vec![expanded]
+        }
+        Pat::Ctor(head_ctor, args) if covers(head_ctor, ctor) => {
+            // A shorter `...rest` prefix leaves the remaining elements open
+            let mut expanded = args.clone();
+            expanded.resize(ctor.arity(), Pat::Wild);
+            expanded.extend_from_slice(rest);
+            vec![expanded]
+        }
+        Pat::Ctor(..) => Vec::new(),
+        Pat::Or(alternatives) => alternatives
+            .iter()
+            .flat_map(|alt| specialize_row(&prepend(alt.clone(), rest), ctor))
+            .collect(),
+    }
+}
+
+/// Rows whose first column is a wildcard, with that column dropped
+fn default_matrix(matrix: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+    fn default_row(row: &[Pat], out: &mut Vec<Vec<Pat>>) {
+        match row.split_first() {
+            Some((Pat::Wild, rest)) => out.push(rest.to_vec()),
+            Some((Pat::Or(alternatives), rest)) => {
+                for alt in alternatives {
+                    default_row(&prepend(alt.clone(), rest), out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    for row in matrix {
+        default_row(row, &mut out);
+    }
+    out
+}
+
+/// Distinct constructors in the first column, looking through or-patterns
+fn head_ctors(matrix: &[Vec<Pat>]) -> Vec<Ctor> {
+    fn collect(pat: &Pat, out: &mut Vec<Ctor>) {
+        match pat {
+            Pat::Wild => {}
+            Pat::Ctor(ctor, _) => {
+                if !out.contains(ctor) {
+                    out.push(ctor.clone());
+                }
+            }
+            Pat::Or(alternatives) => alternatives.iter().for_each(|alt| collect(alt, out)),
+        }
+    }
+
+    let mut out = Vec::new();
+    for row in matrix {
+        if let Some(head) = row.first() {
+            collect(head, &mut out);
+        }
+    }
+    out
+}
+
+fn collect_struct_fields(pattern: &Pattern, out: &mut HashMap<String, Vec<String>>) {
+    match pattern {
+        Pattern::Struct { name, fields } => {
+            let known = out.entry(name.clone()).or_default();
+            for (field, _) in fields {
+                if !known.contains(field) {
+                    known.push(field.clone());
+                }
+            }
+            for (_, sub) in fields {
+                collect_struct_fields(sub, out);
+            }
+        }
+        Pattern::Tuple(elements)
+        | Pattern::Or(elements)
+        | Pattern::Enum { data: elements, .. }
+        | Pattern::Array { elements, .. } => {
+            for sub in elements {
+                collect_struct_fields(sub, out);
+            }
+        }
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::Ident(_) | Pattern::Type { .. } => {}
+    }
+}
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Report unreachable arms (warning) and missing values (error) for a match
+    pub(crate) fn check_match_arms(
+        &mut self,
+        arms: &[MatchArm],
+        scrutinee: Option<&Type>,
+        union_members: Option<&[Type]>,
+        span: Span,
+    ) -> Result<(), String> {
+        let report = MatchChecker::new(&self.enum_ast_defs)
+            .with_scrutinee(scrutinee)
+            .with_structs(&self.struct_ast_defs)
+            .with_union(union_members.unwrap_or_default())
+            .check(arms);
+
+        if !self.suppress_diagnostics {
+            for &index in &report.unreachable_arms {
+                let arm_span = arms[index]
+                    .span_id
+                    .as_ref()
+                    .and_then(|id| self.span_map.get(id))
+                    .cloned()
+                    .unwrap_or_else(|| span.clone());
+                self.diagnostics.emit(Diagnostic {
+                    level: ErrorLevel::Warning,
+                    code: error_codes::UNREACHABLE_PATTERN.to_string(),
+                    message: format!(
+                        "unreachable pattern: match arm {} is covered by earlier arms",
+                        index + 1
+                    ),
+                    span: arm_span,
+                    primary_label: Some("unreachable pattern".to_string()),
+                    notes: vec![],
+                    help: Some(
+                        "remove this arm or move it above the arms that cover it".to_string(),
+                    ),
+                    suggestion: None,
+                    related: Vec::new(),
+                });
+            }
+        }
+
+        if report.missing.is_empty() {
+            return Ok(());
+        }
+
+        let mut shown: Vec<String> = report
+            .missing
+            .iter()
+            .take(MAX_WITNESSES)
+            .map(|w| format!("`{}`", w))
+            .collect();
+        let covered = if report.missing.len() > MAX_WITNESSES {
+            format!("{} and more", shown.join(", "))
+        } else if shown.len() > 1 {
+            let last = shown.pop().unwrap_or_default();
+            format!("{} and {}", shown.join(", "), last)
+        } else {
+            shown.join("")
+        };
+        let message = format!("non-exhaustive patterns: {} not covered", covered);
+
+        if !self.suppress_diagnostics {
+            self.diagnostics.emit(Diagnostic {
+                level: ErrorLevel::Error,
+                code: error_codes::NON_EXHAUSTIVE.to_string(),
+                message: message.clone(),
+                span,
+                primary_label: Some("patterns not covered".to_string()),
+                notes: vec![],
+                help: Some(
+                    "add arms for the missing cases or a wildcard arm `_ => ...`".to_string(),
+                ),
+                suggestion: None,
+                related: Vec::new(),
+            });
+        }
+        Err(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vex_ast::EnumVariant;
+
+    fn shape_enum() -> HashMap<String, Enum> {
+        let mut enums = HashMap::new();
+        enums.insert(
+            "Shape".to_string(),
+            Enum {
+                is_exported: false,
+                span_id: None,
+                attributes: vec![],
+                name: "Shape".to_string(),
+                type_params: vec![],
+                variants: vec![
+                    EnumVariant {
+                        name: "Circle".to_string(),
+                        data: vec![vex_ast::Type::F64],
+                        field_names: vec![],
+                    },
+                    EnumVariant {
+                        name: "Rect".to_string(),
+                        data: vec![vex_ast::Type::F64, vex_ast::Type::F64],
+                        field_names: vec![],
+                    },
+                    EnumVariant {
+                        name: "Empty".to_string(),
+                        data: vec![],
+                        field_names: vec![],
+                    },
+                ],
+            },
+        );
+        enums
+    }
+
+    fn shape() -> Type {
+        Type::Named("Shape".to_string())
+    }
+
+    fn arm(pattern: Pattern) -> MatchArm {
+        MatchArm {
+            span_id: None,
+            pattern,
+            guard: None,
+            body: Expression::IntLiteral(0),
+        }
+    }
+
+    fn variant(name: &str, variant: &str, data: Vec<Pattern>) -> Pattern {
+        Pattern::Enum {
+            name: name.to_string(),
+            variant: variant.to_string(),
+            data,
+            field_names: vec![],
+        }
+    }
+
+    #[test]
+    fn test_missing_enum_variant_witness() {
+        let enums = shape_enum();
+        let arms = vec![
+            arm(variant("Shape", "Circle", vec![Pattern::Ident("r".into())])),
+            arm(Pattern::Ident("Empty".into())),
+        ];
+        let ty = shape();
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert_eq!(report.missing, vec!["Shape.Rect(_, _)".to_string()]);
+        assert!(report.unreachable_arms.is_empty());
+    }
+
+    #[test]
+    fn test_option_and_wildcard_are_exhaustive() {
+        let enums = HashMap::new();
+        let arms = vec![
+            arm(variant("", "Some", vec![Pattern::Wildcard])),
+            arm(Pattern::Ident("None".into())),
+        ];
+        let ty = Type::Option(Box::new(Type::I32));
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert!(report.missing.is_empty());
+        assert!(report.unreachable_arms.is_empty());
+
+        let arms = vec![arm(Pattern::Literal(Expression::IntLiteral(1)))];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert_eq!(report.missing, vec!["_".to_string()]);
+    }
+
+    #[test]
+    fn test_bare_names_are_variants_of_the_matched_enum_only() {
+        let enums = shape_enum();
+        // `Empty` binds an i32 here, even though Shape has a unit variant of that name
+        let arms = vec![arm(Pattern::Ident("Empty".into())), arm(Pattern::Wildcard)];
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&Type::I32))
+            .check(&arms);
+        assert!(report.missing.is_empty());
+        assert_eq!(report.unreachable_arms, vec![1]);
+
+        // Nested names follow the payload type: `Some(Empty)` is a variant of Shape
+        let ty = Type::Option(Box::new(shape()));
+        let arms = vec![
+            arm(variant("", "Some", vec![Pattern::Ident("Empty".into())])),
+            arm(Pattern::Ident("None".into())),
+        ];
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert_eq!(
+            report.missing,
+            vec!["Some(Shape.Circle(_))", "Some(Shape.Rect(_, _))"]
+        );
+
+        let ty = Type::Tuple(vec![Type::I32, shape()]);
+        let arms = vec![arm(Pattern::Tuple(vec![
+            Pattern::Ident("Empty".into()),
+            Pattern::Ident("Empty".into()),
+        ]))];
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert_eq!(
+            report.missing,
+            vec!["(_, Shape.Circle(_))", "(_, Shape.Rect(_, _))"]
+        );
+    }
+
+    #[test]
+    fn test_tuple_of_bools_witnesses() {
+        let enums = HashMap::new();
+        let t = || Pattern::Literal(Expression::BoolLiteral(true));
+        let f = || Pattern::Literal(Expression::BoolLiteral(false));
+        let arms = vec![
+            arm(Pattern::Tuple(vec![t(), Pattern::Wildcard])),
+            arm(Pattern::Tuple(vec![f(), t()])),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert_eq!(report.missing, vec!["(false, false)".to_string()]);
+    }
+
+    #[test]
+    fn test_unreachable_arms() {
+        let enums = shape_enum();
+        let arms = vec![
+            arm(Pattern::Or(vec![
+                variant("Shape", "Circle", vec![Pattern::Wildcard]),
+                Pattern::Ident("Empty".into()),
+            ])),
+            arm(Pattern::Ident("Empty".into())),
+            arm(Pattern::Wildcard),
+            arm(variant("Shape", "Rect", vec![])),
+        ];
+        let ty = shape();
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert_eq!(report.unreachable_arms, vec![1, 3]);
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn test_guarded_arm_does_not_cover() {
+        let enums = HashMap::new();
+        let mut guarded = arm(Pattern::Ident("x".into()));
+        guarded.guard = Some(Expression::BoolLiteral(true));
+        let arms = vec![
+            guarded,
+            arm(Pattern::Struct {
+                name: "Point".into(),
+                fields: vec![("x".into(), Pattern::Literal(Expression::IntLiteral(0)))],
+            }),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert!(report.unreachable_arms.is_empty());
+        assert_eq!(report.missing, vec!["Point { x: _ }".to_string()]);
+    }
+
+    #[test]
+    fn test_struct_like_variant_fields_by_name() {
+        let mut enums = shape_enum();
+        if let Some(shape) = enums.get_mut("Shape") {
+            shape.variants[1].field_names = vec!["w".to_string(), "h".to_string()];
+        }
+        let named = |fields: Vec<(&str, Pattern)>| Pattern::Enum {
+            name: "Shape".to_string(),
+            variant: "Rect".to_string(),
+            field_names: fields.iter().map(|(f, _)| f.to_string()).collect(),
+            data: fields.into_iter().map(|(_, p)| p).collect(),
+        };
+        let one = || Pattern::Literal(Expression::FloatLiteral(1.0));
+        let arms = vec![
+            arm(named(vec![("h", one()), ("w", Pattern::Wildcard)])),
+            arm(named(vec![("h", one())])),
+            arm(Pattern::Wildcard),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert_eq!(report.unreachable_arms, vec![1]);
+
+        let arms = vec![
+            arm(variant("Shape", "Circle", vec![Pattern::Wildcard])),
+            arm(Pattern::Ident("Empty".into())),
+        ];
+        let ty = shape();
+        let report = MatchChecker::new(&enums)
+            .with_scrutinee(Some(&ty))
+            .check(&arms);
+        assert_eq!(
+            report.missing,
+            vec!["Shape.Rect { w: _, h: _ }".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_union_member_patterns() {
+        let enums = HashMap::new();
+        let members = vec![
+            vex_ast::Type::I32,
+            vex_ast::Type::String,
+            vex_ast::Type::Bool,
+        ];
+        let member = |binding: Option<&str>, ty: vex_ast::Type| Pattern::Type {
+            binding: binding.map(str::to_string),
+            ty,
+        };
+        let arms = vec![
+            arm(member(Some("n"), vex_ast::Type::I32)),
+            arm(member(None, vex_ast::Type::Bool)),
+            arm(member(Some("m"), vex_ast::Type::I32)),
+        ];
+        let report = MatchChecker::new(&enums).with_union(&members).check(&arms);
+        assert_eq!(report.unreachable_arms, vec![2]);
+        assert_eq!(report.missing, vec!["_: string".to_string()]);
+
+        let arms = vec![
+            arm(member(None, vex_ast::Type::String)),
+            arm(Pattern::Or(vec![
+                member(None, vex_ast::Type::I32),
+                member(None, vex_ast::Type::Bool),
+            ])),
+        ];
+        let report = MatchChecker::new(&enums).with_union(&members).check(&arms);
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn test_array_lengths_and_rest_patterns() {
+        let enums = HashMap::new();
+        let array = |elements: Vec<Pattern>, rest: Option<&str>| Pattern::Array {
+            elements,
+            rest: rest.map(str::to_string),
+        };
+        let x = || Pattern::Ident("x".into());
+        let t = || Pattern::Literal(Expression::BoolLiteral(true));
+        let f = || Pattern::Literal(Expression::BoolLiteral(false));
+
+        let arms = vec![
+            arm(array(vec![], None)),
+            arm(array(vec![x()], Some("rest"))),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert!(report.missing.is_empty());
+        assert!(report.unreachable_arms.is_empty());
+
+        let arms = vec![
+            arm(array(vec![x()], None)),
+            arm(array(vec![x(), x()], Some("rest"))),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert_eq!(report.missing, vec!["[]".to_string()]);
+
+        let arms = vec![arm(array(vec![], None)), arm(array(vec![t()], Some("_")))];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert_eq!(report.missing, vec!["[false, ...]".to_string()]);
+
+        let arms = vec![
+            arm(array(vec![], None)),
+            arm(array(vec![t()], Some("_"))),
+            arm(array(vec![f()], Some("_"))),
+            arm(array(vec![x(), x()], None)),
+        ];
+        let report = MatchChecker::new(&enums).check(&arms);
+        assert!(report.missing.is_empty());
+        assert_eq!(report.unreachable_arms, vec![3]);
+    }
+}
@@ -49,6 +49,28 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
+        // Union member bindings are only read once the tag matches; the caller
+        // then branches on the guard alone from the guard block.
+        if let Pattern::Type {
+            binding: Some(_), ..
+        } = pattern
+        {
+            let function = self
+                .current_function
+                .ok_or_else(|| "Union guard outside function".to_string())?;
+            let guard_check_block = self
+                .context
+                .append_basic_block(function, "union_guard_check");
+            self.builder
+                .build_conditional_branch(pattern_matches, guard_check_block, else_block)
+                .map_err(|e| format!("Failed to build union guard branch: {}", e))?;
+
+            self.builder.position_at_end(guard_check_block);
+            self.compile_pattern_binding(pattern, match_value)?;
+            let guard_val = self.compile_expression(guard_expr)?;
+            return Ok(Some(guard_val.into_int_value()));
+        }
+
         // For all other pattern types, the guard is evaluated only if the pattern matches.
         // We can combine the conditions with an AND operation.
         let guard_val = self.compile_expression(guard_expr)?;
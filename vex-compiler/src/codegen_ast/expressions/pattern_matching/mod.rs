@@ -1,6 +1,7 @@
 // Match expression and pattern matching code generation
 
 mod bindings;
+mod exhaustiveness;
 mod guards;
 mod patterns;
 
@@ -13,6 +14,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
     /// Compile match expression as a series of if-else comparisons
     pub(crate) fn compile_match_expression(
         &mut self,
+        span_id: &Option<String>,
         value: &Expression,
         arms: &[MatchArm],
         expected_type: Option<&vex_ast::Type>,
@@ -21,6 +23,32 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return Err("Match expression must have at least one arm".to_string());
         }
 
+        // `n: T =>` arms test the tag of a union scrutinee
+        let scrutinee_type = self.infer_expression_type(value).ok();
+        let union_members = scrutinee_type
+            .as_ref()
+            .and_then(|ty| self.union_members(ty));
+
+        // Reject matches that could fall through before emitting any branches
+        let span = span_id
+            .as_ref()
+            .and_then(|id| self.span_map.get(id))
+            .cloned()
+            .unwrap_or_else(vex_diagnostics::Span::unknown);
+        self.check_match_arms(arms, scrutinee_type.as_ref(), union_members.as_deref(), span)?;
+
+        let outer_union_members = std::mem::replace(&mut self.match_union_members, union_members);
+        let result = self.compile_match_arms(value, arms, expected_type);
+        self.match_union_members = outer_union_members;
+        result
+    }
+
+    fn compile_match_arms(
+        &mut self,
+        value: &Expression,
+        arms: &[MatchArm],
+        expected_type: Option<&vex_ast::Type>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
         // Compile the value to match against
         let mut match_value = self.compile_expression(value)?;
 
@@ -156,7 +184,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         let ptr = value.into_pointer_value();
         let loaded_type = match expr {
-            Expression::Ident(name) => self.variable_types.get(name).cloned(),
+            // Only struct-like variables evaluate to their slot; others are already loaded
+            Expression::Ident(name) if self.variable_struct_names.contains_key(name) => {
+                self.variable_types.get(name).cloned()
+            }
             Expression::StructLiteral { name, .. } => {
                 let struct_def = self
                     .struct_defs
@@ -201,9 +232,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
     /// Checks if an identifier name corresponds to a known enum variant.
     pub(crate) fn is_enum_variant(&self, name: &str) -> bool {
-        self.enum_ast_defs
-            .values()
-            .any(|e| e.variants.iter().any(|v| v.name == name))
+        // `None` is the builtin Option's unit variant, not a binding
+        name == "None"
+            || self
+                .enum_ast_defs
+                .values()
+                .any(|e| e.variants.iter().any(|v| v.name == name))
     }
 
     fn build_alloca_and_store(
@@ -221,7 +255,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(ptr)
     }
 
-    fn create_entry_block_alloca_for_type(
+    pub(crate) fn create_entry_block_alloca_for_type(
         &mut self,
         ty: BasicTypeEnum<'ctx>,
         name: &str,
@@ -1,5 +1,5 @@
 //! Pattern matching: checking logic
-use crate::codegen_ast::ASTCodeGen;
+use crate::codegen_ast::{ASTCodeGen, OPTION_NONE_TAG, OPTION_SOME_TAG};
 use inkwell::values::BasicValueEnum;
 use inkwell::IntPredicate;
 use vex_ast::Pattern;
@@ -31,9 +31,24 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 name,
                 variant,
                 data,
-            } => self.check_enum_pattern(name, variant, data, value),
+                field_names,
+            } => self.check_enum_pattern(name, variant, data, field_names, value),
             Pattern::Or(patterns) => self.check_or_pattern(patterns, value),
             Pattern::Array { elements, rest } => self.check_array_pattern(elements, rest, value),
+            Pattern::Type { ty, .. } => {
+                let members = self
+                    .match_union_members
+                    .clone()
+                    .ok_or_else(|| "Type patterns need a union value to match on".to_string())?;
+                let index = self.union_member_index(&members, ty).ok_or_else(|| {
+                    format!(
+                        "`{}` is not a member of `{}`",
+                        self.type_to_string(ty),
+                        self.type_to_string(&vex_ast::Type::Union(members.clone()))
+                    )
+                })?;
+                self.build_union_tag_check(value, index)
+            }
         }
     }
 
@@ -163,10 +178,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
         name: &str,
         variant: &str,
         data: &[Pattern],
+        field_names: &[String],
         value: BasicValueEnum<'ctx>,
     ) -> Result<inkwell::values::IntValue<'ctx>, String> {
-        let (_enum_name, variant_index) =
-            self.find_enum_and_variant_index_by_name(name, variant)?;
+        let (enum_name, variant_index) = self.find_enum_and_variant_index_by_name(name, variant)?;
+        let data = self.positional_enum_patterns(&enum_name, variant, data, field_names)?;
         let enum_tag = self.extract_enum_tag(value)?;
         let expected_tag = self
             .context
@@ -181,20 +197,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return Ok(tag_matches);
         }
 
-        if !value.is_struct_value() {
+        if !value.is_struct_value() && !Self::is_niche_option_value(value) {
             return Ok(self.context.bool_type().const_int(0, false));
         }
 
-        let struct_val = value.into_struct_value();
-        let data_val = self
-            .builder
-            .build_extract_value(struct_val, 1, "enum_data")
-            .map_err(|e| format!("Failed to extract enum data: {}", e))?;
+        let data_val = self.extract_enum_payload(&enum_name, variant, value)?;
 
         let data_matches = if data.len() == 1 {
             self.compile_pattern_check(&data[0], data_val)?
         } else {
-            self.check_tuple_pattern(data, data_val)?
+            self.check_tuple_pattern(&data, data_val)?
         };
 
         self.builder
@@ -259,10 +271,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
     }
 
     /// Extracts the i32 tag from an enum value, which can be an int or a struct.
+    /// Niche-packed Options (references) derive the tag from their pointer.
     pub(crate) fn extract_enum_tag(
         &self,
         value: BasicValueEnum<'ctx>,
     ) -> Result<inkwell::values::IntValue<'ctx>, String> {
+        if Self::is_niche_option_value(value) {
+            return self.niche_option_tag(value);
+        }
+
         let tag_val = if value.is_struct_value() {
             self.builder
                 .build_extract_value(value.into_struct_value(), 0, "enum_tag")
@@ -325,8 +342,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let is_builtin = e_name == "Option" || e_name == "Result";
         let variant_index = if is_builtin {
             match (e_name.as_str(), variant_name) {
-                ("Option", "Some") => 0,
-                ("Option", "None") => 1,
+                ("Option", "Some") => OPTION_SOME_TAG as usize,
+                ("Option", "None") => OPTION_NONE_TAG as usize,
                 ("Result", "Ok") => 0,
                 ("Result", "Err") => 1,
                 _ => {
@@ -353,6 +370,32 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok((e_name, variant_index))
     }
 
+    /// Payload sub-patterns of an enum pattern in declaration order.
+    /// Named fields a pattern leaves out (`Shape.Rect { w }`) match anything.
+    pub(crate) fn positional_enum_patterns(
+        &self,
+        enum_name: &str,
+        variant: &str,
+        data: &[Pattern],
+        field_names: &[String],
+    ) -> Result<Vec<Pattern>, String> {
+        let positions = self.enum_payload_positions(enum_name, variant, field_names, data.len())?;
+        if field_names.is_empty() {
+            return Ok(data.to_vec());
+        }
+
+        let arity = self
+            .enum_ast_defs
+            .get(enum_name)
+            .and_then(|def| def.variants.iter().find(|v| v.name == variant))
+            .map_or(data.len(), |v| v.data.len());
+        let mut ordered = vec![Pattern::Wildcard; arity];
+        for (pattern, position) in data.iter().zip(positions) {
+            ordered[position] = pattern.clone();
+        }
+        Ok(ordered)
+    }
+
     /// Helper to compile equality comparison between two values
     pub(crate) fn compile_equality_comparison(
         &mut self,
@@ -0,0 +1,197 @@
+// Expression compilation - Range<T> values
+// Range and RangeInclusive share the runtime layout VexRange (vex.h):
+// { start: i64, end: i64, current: i64, step: i64 }
+
+use super::ASTCodeGen;
+use inkwell::types::StructType;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use vex_ast::*;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// LLVM layout of Range and RangeInclusive values
+    pub(crate) fn range_struct_type(&self) -> StructType<'ctx> {
+        let i64_type = self.context.i64_type();
+        self.context.struct_type(&[i64_type.into(); 4], false)
+    }
+
+    /// `Some(inclusive)` if `ty` is the builtin Range or RangeInclusive
+    /// (a user struct with the same name shadows it)
+    pub(crate) fn range_kind(&self, ty: &Type) -> Option<bool> {
+        let name = match ty {
+            Type::Named(name) | Type::Generic { name, .. } => name,
+            _ => return None,
+        };
+        if self.struct_ast_defs.contains_key(name) {
+            return None;
+        }
+        match name.as_str() {
+            "Range" => Some(false),
+            "RangeInclusive" => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Element type T of Range<T>; a bare `Range` iterates i64 values
+    pub(crate) fn range_element_type(ty: &Type) -> Type {
+        match ty {
+            Type::Generic { type_args, .. } => match type_args.first() {
+                Some(Type::Unknown) | None => Type::I64,
+                Some(elem) => elem.clone(),
+            },
+            _ => Type::I64,
+        }
+    }
+
+    /// Return type of the builtin Range methods
+    pub(crate) fn range_method_type(&self, range_type: &Type, method: &str) -> Option<Type> {
+        self.range_kind(range_type)?;
+        match method {
+            "contains" | "is_empty" | "next" => Some(Type::Bool),
+            "len" => Some(Type::I64),
+            "rev" | "step_by" => Some(range_type.clone()),
+            _ => None,
+        }
+    }
+
+    /// Compile Range or RangeInclusive expressions
+    pub(crate) fn compile_range(
+        &mut self,
+        start: &Option<Box<Expression>>,
+        end: &Option<Box<Expression>>,
+        _inclusive: bool,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        // Default values: start=0, end=max_i64
+        let start_i64 = match start {
+            Some(s) => {
+                let start_val = self.compile_expression(s)?;
+                self.range_bound_to_i64(start_val, "start")?
+            }
+            None => self.context.i64_type().const_int(0, false),
+        };
+        let end_i64 = match end {
+            Some(e) => {
+                let end_val = self.compile_expression(e)?;
+                self.range_bound_to_i64(end_val, "end")?
+            }
+            None => self.context.i64_type().const_int(i64::MAX as u64, false),
+        };
+
+        self.build_range_value(start_i64, end_i64)
+    }
+
+    /// Widen or narrow an integer bound to the i64 stored in the range
+    pub(crate) fn range_bound_to_i64(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        what: &str,
+    ) -> Result<IntValue<'ctx>, String> {
+        let BasicValueEnum::IntValue(int_val) = value else {
+            return Err(format!("Range {} must be an integer", what));
+        };
+        let i64_type = self.context.i64_type();
+        match int_val.get_type().get_bit_width() {
+            64 => Ok(int_val),
+            width if width < 64 => self
+                .builder
+                .build_int_s_extend(int_val, i64_type, &format!("{}_ext", what))
+                .map_err(|e| format!("Failed to extend {}: {}", what, e)),
+            _ => self
+                .builder
+                .build_int_truncate(int_val, i64_type, &format!("{}_trunc", what))
+                .map_err(|e| format!("Failed to truncate {}: {}", what, e)),
+        }
+    }
+
+    /// Build a fresh range value: cursor at `start`, step 1
+    pub(crate) fn build_range_value(
+        &mut self,
+        start: IntValue<'ctx>,
+        end: IntValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let one = self.context.i64_type().const_int(1, false);
+        let mut range_val = self.range_struct_type().get_undef();
+        for (index, value, name) in [
+            (0, start, "range_start"),
+            (1, end, "range_end"),
+            (2, start, "range_current"),
+            (3, one, "range_step"),
+        ] {
+            range_val = self
+                .builder
+                .build_insert_value(range_val, value, index, name)
+                .map_err(|e| format!("Failed to build range: {}", e))?
+                .into_struct_value();
+        }
+        Ok(range_val.into())
+    }
+
+    /// Copy a range value into a fresh entry-block slot, so runtime helpers can
+    /// take its address without touching the original
+    pub(crate) fn spill_range_value(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        name: &str,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let range_type = self.range_struct_type();
+        let value = match value {
+            BasicValueEnum::PointerValue(ptr) => self
+                .builder
+                .build_load(range_type, ptr, "range_copy")
+                .map_err(|e| format!("Failed to load range: {}", e))?,
+            other => other,
+        };
+        let slot = self.create_entry_block_alloca_for_type(range_type.into(), name)?;
+        self.builder
+            .build_store(slot, value)
+            .map_err(|e| format!("Failed to store range: {}", e))?;
+        Ok(slot)
+    }
+
+    /// Half-open `[start, end)` bounds of a range for slicing.
+    /// An open end (`start..`) becomes -1, which the runtime reads as "to the end".
+    pub(crate) fn range_slice_bounds(
+        &mut self,
+        range_ptr: PointerValue<'ctx>,
+        inclusive: bool,
+    ) -> Result<(IntValue<'ctx>, IntValue<'ctx>), String> {
+        let i64_type = self.context.i64_type();
+        let range_type = self.range_struct_type();
+        let mut bounds = Vec::with_capacity(2);
+        for (index, name) in [(0, "slice_start"), (1, "slice_end")] {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(range_type, range_ptr, index, name)
+                .map_err(|e| format!("Failed to get range bound: {}", e))?;
+            let bound = self
+                .builder
+                .build_load(i64_type, field_ptr, name)
+                .map_err(|e| format!("Failed to load range bound: {}", e))?
+                .into_int_value();
+            bounds.push(bound);
+        }
+        let (start, mut end) = (bounds[0], bounds[1]);
+
+        if inclusive {
+            end = self
+                .builder
+                .build_int_add(end, i64_type.const_int(1, false), "slice_end_incl")
+                .map_err(|e| format!("Failed to adjust inclusive end: {}", e))?;
+        } else {
+            let open_end = self
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    end,
+                    i64_type.const_int(i64::MAX as u64, false),
+                    "open_end",
+                )
+                .map_err(|e| format!("Failed to compare range end: {}", e))?;
+            end = self
+                .builder
+                .build_select(open_end, i64_type.const_all_ones(), end, "slice_end")
+                .map_err(|e| format!("Failed to select range end: {}", e))?
+                .into_int_value();
+        }
+        Ok((start, end))
+    }
+}
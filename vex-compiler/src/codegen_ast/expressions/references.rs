@@ -20,75 +20,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .get(name)
                     .ok_or_else(|| format!("Type for variable {} not found", name))?;
 
-                // If the variable is an array, create a slice struct
-                if let inkwell::types::BasicTypeEnum::ArrayType(arr_ty) = var_type {
-                    // Create slice struct: { i8* data, i64 len, i64 elem_size }
-                    let ptr_ty = self.context.ptr_type(inkwell::AddressSpace::default());
-                    let size_ty = self.context.i64_type();
-                    let slice_struct_ty = self.context.struct_type(
-                        &[
-                            ptr_ty.into(),  // void *data
-                            size_ty.into(), // size_t len
-                            size_ty.into(), // size_t elem_size
-                        ],
-                        false,
-                    );
-
-                    // Get array pointer
-                    let arr_ptr = self
+                // If the variable is an array, create a slice struct viewing it in place
+                if let inkwell::types::BasicTypeEnum::ArrayType(arr_ty) = *var_type {
+                    let arr_ptr = *self
                         .variables
                         .get(name)
                         .ok_or_else(|| format!("Variable {} not found", name))?;
-
-                    // Cast array to i8* for data field
-                    let data_ptr = self
-                        .builder
-                        .build_pointer_cast(*arr_ptr, ptr_ty, "slice_data")
-                        .map_err(|e| format!("Failed to cast array to slice data: {}", e))?;
-
-                    // Get array length
-                    let arr_len = arr_ty.len() as u64;
-                    let len_val = size_ty.const_int(arr_len, false);
-
-                    // Get element size from actual element type
-                    let elem_ty = arr_ty.get_element_type();
-                    // Calculate element size in bytes
-                    let elem_size = match elem_ty {
-                        inkwell::types::BasicTypeEnum::IntType(it) => {
-                            (it.get_bit_width() / 8) as u64
-                        }
-                        inkwell::types::BasicTypeEnum::FloatType(ft) => {
-                            if ft == self.context.f32_type() {
-                                4
-                            } else {
-                                8
-                            }
-                        }
-                        inkwell::types::BasicTypeEnum::PointerType(_) => 8, // 64-bit pointers
-                        inkwell::types::BasicTypeEnum::StructType(_) => 8,  // Default struct size
-                        _ => 4,                                             // Default
-                    };
-                    let elem_size_val = size_ty.const_int(elem_size, false);
-
-                    // Build slice struct
-                    let undef = slice_struct_ty.get_undef();
-                    let with_data = self
-                        .builder
-                        .build_insert_value(undef, data_ptr, 0, "slice_with_data")
-                        .map_err(|e| format!("Failed to insert data: {}", e))?;
-                    let with_len = self
-                        .builder
-                        .build_insert_value(with_data, len_val, 1, "slice_with_len")
-                        .map_err(|e| format!("Failed to insert len: {}", e))?;
-                    let slice_val = self
-                        .builder
-                        .build_insert_value(with_len, elem_size_val, 2, "slice_complete")
-                        .map_err(|e| format!("Failed to insert elem_size: {}", e))?;
-
-                    // Return slice struct value directly
-                    // Convert AggregateValueEnum to StructValue to BasicValueEnum
-                    let struct_val: inkwell::values::StructValue = slice_val.into_struct_value();
-                    return Ok(struct_val.into());
+                    return Ok(self.array_slice_value(arr_ptr, arr_ty)?.into());
                 }
 
                 // For non-arrays (identifiers), return the pointer directly (don't load)
@@ -98,6 +36,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .ok_or_else(|| format!("Variable {} not found", name))?;
                 Ok((*ptr).into())
             }
+            // `&arr[a..b]` is already a view; don't take the address of the slice value
+            vex_ast::Expression::Index { index, .. } if self.range_index_kind(index).is_some() => {
+                self.compile_expression(expr)
+            }
             _ => {
                 // For other expressions, compile them, store in temporary, return pointer
                 let value = self.compile_expression(expr)?;
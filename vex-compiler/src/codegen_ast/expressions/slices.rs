@@ -0,0 +1,234 @@
+// Expression compilation - Slice<T> views
+// Slices share the runtime layout VexSlice (vex.h): { data: ptr, len: i64, elem_size: i64 }
+// A subslice points into the storage of the array or slice it was taken from.
+
+use super::ASTCodeGen;
+use inkwell::types::{ArrayType, BasicType, BasicTypeEnum, StructType};
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue, StructValue};
+use vex_ast::*;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// LLVM layout of slice values
+    pub(crate) fn slice_struct_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        self.context
+            .struct_type(&[ptr_type.into(), i64_type.into(), i64_type.into()], false)
+    }
+
+    pub(crate) fn is_slice_layout(&self, ty: BasicTypeEnum<'ctx>) -> bool {
+        matches!(ty, BasicTypeEnum::StructType(st) if st == self.slice_struct_type())
+    }
+
+    /// Element type T of `&[T]`, `&[T]!` and `[T; N]`, looking through references
+    pub(crate) fn slice_element_type(ty: &Type) -> Option<&Type> {
+        match ty {
+            Type::Slice(elem, _) | Type::Array(elem, _) => Some(elem),
+            Type::Reference(inner, _) => Self::slice_element_type(inner),
+            _ => None,
+        }
+    }
+
+    /// Whether elements can be written through a view of this type: `&[T]!` and `&arr!`
+    pub(crate) fn is_mutable_view(ty: &Type) -> bool {
+        matches!(ty, Type::Slice(_, true) | Type::Reference(_, true))
+    }
+
+    /// LLVM element type of an indexed array or slice expression.
+    /// Falls back to i32 when the AST type is not known.
+    pub(crate) fn indexed_element_llvm_type(&mut self, object: &Expression) -> BasicTypeEnum<'ctx> {
+        let elem = self
+            .infer_expression_type(object)
+            .ok()
+            .and_then(|ty| Self::slice_element_type(&ty).cloned());
+        match elem {
+            Some(elem) => self.ast_type_to_llvm(&elem),
+            None => self.context.i32_type().into(),
+        }
+    }
+
+    /// `Some(inclusive)` if `index` selects a range of elements rather than one
+    pub(crate) fn range_index_kind(&self, index: &Expression) -> Option<bool> {
+        match index {
+            Expression::Range { .. } => Some(false),
+            Expression::RangeInclusive { .. } => Some(true),
+            _ => {
+                let index_type = self.infer_expression_type(index).ok()?;
+                self.range_kind(&index_type)
+            }
+        }
+    }
+
+    pub(crate) fn build_slice_value(
+        &mut self,
+        data: PointerValue<'ctx>,
+        len: IntValue<'ctx>,
+        elem_size: IntValue<'ctx>,
+    ) -> Result<StructValue<'ctx>, String> {
+        let mut slice = self.slice_struct_type().get_undef();
+        for (index, value, name) in [
+            (0, BasicValueEnum::from(data), "slice_data"),
+            (1, len.into(), "slice_len"),
+            (2, elem_size.into(), "slice_elem_size"),
+        ] {
+            slice = self
+                .builder
+                .build_insert_value(slice, value, index, name)
+                .map_err(|e| format!("Failed to build slice: {}", e))?
+                .into_struct_value();
+        }
+        Ok(slice)
+    }
+
+    /// Slice view over a whole array stored at `array_ptr`
+    pub(crate) fn array_slice_value(
+        &mut self,
+        array_ptr: PointerValue<'ctx>,
+        array_type: ArrayType<'ctx>,
+    ) -> Result<StructValue<'ctx>, String> {
+        let i64_type = self.context.i64_type();
+        let elem_size = array_type
+            .get_element_type()
+            .size_of()
+            .ok_or("Cannot slice an array of unsized elements")?;
+        let elem_size = self
+            .builder
+            .build_int_cast(elem_size, i64_type, "elem_size")
+            .map_err(|e| format!("Failed to cast element size: {}", e))?;
+        let len = i64_type.const_int(array_type.len() as u64, false);
+        self.build_slice_value(array_ptr, len, elem_size)
+    }
+
+    /// Slice view over an array or slice held in `storage`
+    pub(crate) fn slice_from_storage(
+        &mut self,
+        storage: PointerValue<'ctx>,
+        storage_type: BasicTypeEnum<'ctx>,
+    ) -> Result<StructValue<'ctx>, String> {
+        match storage_type {
+            BasicTypeEnum::ArrayType(array_type) => self.array_slice_value(storage, array_type),
+            ty if self.is_slice_layout(ty) => Ok(self
+                .builder
+                .build_load(ty, storage, "slice_load")
+                .map_err(|e| format!("Failed to load slice: {}", e))?
+                .into_struct_value()),
+            other => Err(format!("Cannot take a slice of {:?}", other)),
+        }
+    }
+
+    /// Slice view of any array or slice expression (variables are viewed in place)
+    pub(crate) fn compile_slice_source(
+        &mut self,
+        object: &Expression,
+    ) -> Result<StructValue<'ctx>, String> {
+        if let Expression::Ident(name) = object {
+            if let (Some(&storage), Some(&storage_type)) =
+                (self.variables.get(name), self.variable_types.get(name))
+            {
+                return self.slice_from_storage(storage, storage_type);
+            }
+        }
+        match self.compile_expression(object)? {
+            BasicValueEnum::StructValue(slice) if self.is_slice_layout(slice.get_type().into()) => {
+                Ok(slice)
+            }
+            BasicValueEnum::ArrayValue(array) => {
+                let array_type = array.get_type();
+                let storage =
+                    self.create_entry_block_alloca_for_type(array_type.into(), "slice_src")?;
+                self.builder
+                    .build_store(storage, array)
+                    .map_err(|e| format!("Failed to store array: {}", e))?;
+                self.array_slice_value(storage, array_type)
+            }
+            other => Err(format!("Cannot take a slice of {:?}", other.get_type())),
+        }
+    }
+
+    /// Subslice `view[range]`: bounds-checked, shares storage with `view`.
+    /// Open ends (`..n`, `a..`) default to 0 and the view's length.
+    pub(crate) fn compile_subslice(
+        &mut self,
+        view: StructValue<'ctx>,
+        range: &Expression,
+        inclusive: bool,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let i64_type = self.context.i64_type();
+        let extract = |codegen: &mut Self, index: u32, name: &str| {
+            codegen
+                .builder
+                .build_extract_value(view, index, name)
+                .map_err(|e| format!("Failed to read slice: {}", e))
+        };
+        let data = extract(self, 0, "view_data")?.into_pointer_value();
+        let len = extract(self, 1, "view_len")?.into_int_value();
+        let elem_size = extract(self, 2, "view_elem_size")?.into_int_value();
+
+        let range_val = self.compile_expression(range)?;
+        let range_ptr = self.spill_range_value(range_val, "subslice_range")?;
+        let (start, end) = self.range_slice_bounds(range_ptr, inclusive)?;
+        let open_end = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                end,
+                i64_type.const_all_ones(),
+                "open_end",
+            )
+            .map_err(|e| format!("Failed to compare slice end: {}", e))?;
+        let end = self
+            .builder
+            .build_select(open_end, len, end, "subslice_end")
+            .map_err(|e| format!("Failed to select slice end: {}", e))?
+            .into_int_value();
+
+        // start <= end <= len; unsigned compares also reject negative bounds
+        let start_ok = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULE, start, end, "start_ok")
+            .map_err(|e| format!("Failed to check slice start: {}", e))?;
+        let end_ok = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULE, end, len, "end_ok")
+            .map_err(|e| format!("Failed to check slice end: {}", e))?;
+        let in_bounds = self
+            .builder
+            .build_and(start_ok, end_ok, "subslice_in_bounds")
+            .map_err(|e| format!("Failed to check slice bounds: {}", e))?;
+        self.build_runtime_check(in_bounds, "slice range out of bounds")?;
+
+        let offset = self
+            .builder
+            .build_int_mul(start, elem_size, "subslice_offset")
+            .map_err(|e| format!("Failed to compute slice offset: {}", e))?;
+        let sub_data = unsafe {
+            self.builder
+                .build_in_bounds_gep(self.context.i8_type(), data, &[offset], "subslice_data")
+                .map_err(|e| format!("Failed to offset slice data: {}", e))?
+        };
+        let sub_len = self
+            .builder
+            .build_int_sub(end, start, "subslice_len")
+            .map_err(|e| format!("Failed to compute slice length: {}", e))?;
+        Ok(self.build_slice_value(sub_data, sub_len, elem_size)?.into())
+    }
+
+    /// Address of element `index` of a slice, typed by the AST element type
+    pub(crate) fn slice_element_ptr(
+        &mut self,
+        view: StructValue<'ctx>,
+        index: IntValue<'ctx>,
+        elem_type: BasicTypeEnum<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let data = self
+            .builder
+            .build_extract_value(view, 0, "slice_data")
+            .map_err(|e| format!("Failed to extract slice data: {}", e))?
+            .into_pointer_value();
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(elem_type, data, &[index], "slice_elem_ptr")
+                .map_err(|e| format!("Failed to build slice GEP: {}", e))
+        }
+    }
+}
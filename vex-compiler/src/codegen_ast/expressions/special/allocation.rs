@@ -0,0 +1,153 @@
+// Heap allocation expressions: new(expr), make([T], n), make(Channel<T>, n), error.new(msg)
+
+use crate::codegen_ast::ASTCodeGen;
+use inkwell::types::{BasicType, BasicTypeEnum};
+use inkwell::values::{BasicValueEnum, IntValue};
+use inkwell::AddressSpace;
+use vex_ast::*;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Compile new(expr): move the value into a fresh malloc'd cell
+    ///
+    /// Structs evaluate to the heap pointer itself so field access works the same
+    /// way it does on a stack struct; any other value yields a `&T` to the cell.
+    pub(crate) fn compile_new_expression(
+        &mut self,
+        expr: &Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let inner_type = self.infer_new_inner_type(expr)?;
+        let llvm_type = self.ast_type_to_llvm(&inner_type);
+
+        let mut value = self.compile_expression_with_type(expr, Some(&inner_type))?;
+
+        // Struct and array literals come back as a pointer to their stack slot
+        if value.is_pointer_value() && !matches!(llvm_type, BasicTypeEnum::PointerType(_)) {
+            value = self
+                .builder
+                .build_load(llvm_type, value.into_pointer_value(), "new_value")
+                .map_err(|e| format!("Failed to load value for new(): {}", e))?;
+        }
+        let value_type = value.get_type();
+
+        let size = value_type
+            .size_of()
+            .ok_or("Cannot allocate unsized value with new()")?;
+        let malloc_fn = self.get_or_declare_malloc();
+        let heap_ptr = self
+            .builder
+            .build_call(malloc_fn, &[size.into()], "new_alloc")
+            .map_err(|e| format!("Failed to call malloc for new(): {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value();
+
+        self.builder
+            .build_store(heap_ptr, value)
+            .map_err(|e| format!("Failed to store value into new() allocation: {}", e))?;
+
+        Ok(heap_ptr.into())
+    }
+
+    /// Compile make(Channel<T>, capacity) or make([T], len)
+    pub(crate) fn compile_make_expression(
+        &mut self,
+        element_type: &Type,
+        size: &Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let size_val = self.compile_expression(size)?;
+        let size_i64 = self.make_size_to_i64(size_val)?;
+
+        if let Type::Channel(_) = element_type {
+            let create_fn = self.get_or_declare_vex_channel_create();
+            return Ok(self
+                .builder
+                .build_call(create_fn, &[size_i64.into()], "make_channel")
+                .map_err(|e| format!("Failed to call vex_channel_create: {}", e))?
+                .try_as_basic_value()
+                .unwrap_basic());
+        }
+
+        // Slice: zeroed buffer of `len` elements wrapped in { data, len, elem_size }
+        let i64_type = self.context.i64_type();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let elem_llvm = self.ast_type_to_llvm(element_type);
+        let elem_size = elem_llvm
+            .size_of()
+            .ok_or_else(|| format!("Cannot make slice of unsized type {:?}", element_type))?;
+        let elem_size = self
+            .builder
+            .build_int_cast(elem_size, i64_type, "elem_size")
+            .map_err(|e| format!("Failed to cast element size: {}", e))?;
+
+        let calloc_fn = self.declare_runtime_fn(
+            "calloc",
+            &[i64_type.into(), i64_type.into()],
+            ptr_type.into(),
+        );
+        let data_ptr = self
+            .builder
+            .build_call(calloc_fn, &[size_i64.into(), elem_size.into()], "make_data")
+            .map_err(|e| format!("Failed to call calloc for make(): {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic();
+
+        let slice_type = self
+            .ast_type_to_llvm(&Type::Slice(Box::new(element_type.clone()), true))
+            .into_struct_type();
+        let slice = slice_type.get_undef();
+        let slice = self
+            .builder
+            .build_insert_value(slice, data_ptr, 0, "make_slice_data")
+            .map_err(|e| format!("Failed to build slice data field: {}", e))?;
+        let slice = self
+            .builder
+            .build_insert_value(slice, size_i64, 1, "make_slice_len")
+            .map_err(|e| format!("Failed to build slice len field: {}", e))?;
+        let slice = self
+            .builder
+            .build_insert_value(slice, elem_size, 2, "make_slice")
+            .map_err(|e| format!("Failed to build slice elem_size field: {}", e))?;
+
+        Ok(slice.into_struct_value().into())
+    }
+
+    /// Compile error.new(message): heap-allocated VexError owning a copy of the message
+    pub(crate) fn compile_error_new_expression(
+        &mut self,
+        message: &Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let message_val = self.compile_expression(message)?;
+        if !message_val.is_pointer_value() {
+            return Err("error.new() expects a string message".to_string());
+        }
+
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let error_new_fn =
+            self.declare_runtime_fn("vex_error_new", &[ptr_type.into()], ptr_type.into());
+        Ok(self
+            .builder
+            .build_call(error_new_fn, &[message_val.into()], "error_new")
+            .map_err(|e| format!("Failed to call vex_error_new: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic())
+    }
+
+    /// Sizes passed to make() are sign-extended to i64 (runtime takes size_t/int64_t)
+    fn make_size_to_i64(
+        &mut self,
+        size_val: BasicValueEnum<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let size_int = match size_val {
+            BasicValueEnum::IntValue(iv) => iv,
+            _ => return Err("make() size must be an integer".to_string()),
+        };
+        let i64_type = self.context.i64_type();
+        if size_int.get_type().get_bit_width() < 64 {
+            self.builder
+                .build_int_s_extend(size_int, i64_type, "make_size")
+                .map_err(|e| format!("Failed to extend make() size: {}", e))
+        } else {
+            Ok(size_int)
+        }
+    }
+}
@@ -1,7 +1,8 @@
 // Type casting operations
 
 use crate::codegen_ast::ASTCodeGen;
-use inkwell::values::BasicValueEnum;
+use inkwell::types::{FloatType, IntType};
+use inkwell::values::{BasicValueEnum, FloatValue, IntValue};
 use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -10,6 +11,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
     /// - Numeric casts: i32 -> i64, f64 -> i32, i32 -> f32, etc.
     /// - Pointer casts: *T -> *U, &T -> *T
     /// - Sign changes: i32 -> u32, u64 -> i64
+    ///
+    /// Integer widening zero-extends unsigned sources and sign-extends signed ones;
+    /// float -> int saturates like Rust's `as`.
     pub(crate) fn compile_cast_expression(
         &mut self,
         expr: &Expression,
@@ -35,14 +39,25 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let value = self.compile_expression(expr)?;
         let target_llvm = self.ast_type_to_llvm(target_type);
 
+        // Numeric casts take their extension and conversion from the AST source type
+        let source_unsigned = source_type.as_ref().is_some_and(is_unsigned_cast_source);
+        let target_unsigned = is_unsigned_cast_source(target_type);
+
         // Handle integer -> integer casts
         if let BasicValueEnum::IntValue(int_val) = value {
             if let inkwell::types::BasicTypeEnum::IntType(target_int) = target_llvm {
                 let source_width = int_val.get_type().get_bit_width();
                 let target_width = target_int.get_bit_width();
 
-                if source_width < target_width {
-                    // Widening cast: i32 -> i64 (safe, use sign extension)
+                if source_width < target_width && source_unsigned {
+                    // Widening from unsigned: u32 -> i64 (zero extension)
+                    return Ok(self
+                        .builder
+                        .build_int_z_extend(int_val, target_int, "cast_zext")
+                        .map_err(|e| format!("Failed to zero-extend: {}", e))?
+                        .into());
+                } else if source_width < target_width {
+                    // Widening from signed: i32 -> u64 (sign extension, -1 stays all ones)
                     return Ok(self
                         .builder
                         .build_int_s_extend(int_val, target_int, "cast_sext")
@@ -65,19 +80,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Handle float -> float casts
         if let BasicValueEnum::FloatValue(float_val) = value {
             if let inkwell::types::BasicTypeEnum::FloatType(target_float) = target_llvm {
-                // LLVM doesn't expose size for floats, infer from types
-                // f32 = 32bit, f64 = 64bit, f16 = 16bit, f128 = 128bit
-                let source_is_double = float_val.get_type() == self.context.f64_type();
-                let target_is_double = target_float == self.context.f64_type();
+                let source_width = self.float_bit_width(float_val.get_type());
+                let target_width = self.float_bit_width(target_float);
 
-                if !source_is_double && target_is_double {
+                if source_width < target_width {
                     // f32 -> f64 (safe, extend)
                     return Ok(self
                         .builder
                         .build_float_ext(float_val, target_float, "cast_fext")
                         .map_err(|e| format!("Failed to extend float: {}", e))?
                         .into());
-                } else if source_is_double && !target_is_double {
+                } else if source_width > target_width {
                     // f64 -> f32 (lossy, truncate)
                     return Ok(self
                         .builder
@@ -92,14 +105,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
         }
 
         // Handle int -> float
-        // Need to determine if source is signed or unsigned to choose correct LLVM instruction
         if let BasicValueEnum::IntValue(int_val) = value {
             if let inkwell::types::BasicTypeEnum::FloatType(target_float) = target_llvm {
-                // Check target_type to see if we're casting FROM unsigned
-                // For now, use signed (most common case)
-                // TODO: Track source type to distinguish signed vs unsigned
-                // - Use uitofp for u8/u16/u32/u64 -> float
-                // - Use sitofp for i8/i16/i32/i64 -> float
+                if source_unsigned {
+                    return Ok(self
+                        .builder
+                        .build_unsigned_int_to_float(int_val, target_float, "cast_utof")
+                        .map_err(|e| format!("Failed to convert uint to float: {}", e))?
+                        .into());
+                }
                 return Ok(self
                     .builder
                     .build_signed_int_to_float(int_val, target_float, "cast_itof")
@@ -108,31 +122,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
-        // Handle float -> int
-        // Similarly, need to know if target is signed or unsigned
+        // Handle float -> int: saturates at the target's bounds and maps NaN to 0
         if let BasicValueEnum::FloatValue(float_val) = value {
             if let inkwell::types::BasicTypeEnum::IntType(target_int) = target_llvm {
-                // Check target_type for unsigned types
-                let is_unsigned = matches!(
-                    target_type,
-                    Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::U128
-                );
-
-                if is_unsigned {
-                    // Float to unsigned int
-                    return Ok(self
-                        .builder
-                        .build_float_to_unsigned_int(float_val, target_int, "cast_ftou")
-                        .map_err(|e| format!("Failed to convert float to uint: {}", e))?
-                        .into());
-                } else {
-                    // Float to signed int
-                    return Ok(self
-                        .builder
-                        .build_float_to_signed_int(float_val, target_int, "cast_ftoi")
-                        .map_err(|e| format!("Failed to convert float to int: {}", e))?
-                        .into());
-                }
+                return Ok(self
+                    .build_float_to_int_saturating(float_val, target_int, !target_unsigned)?
+                    .into());
             }
         }
 
@@ -278,4 +273,75 @@ impl<'ctx> ASTCodeGen<'ctx> {
             target_llvm
         ))
     }
+
+    /// Bit width of an LLVM float type
+    fn float_bit_width(&self, float_type: FloatType<'ctx>) -> u32 {
+        if float_type == self.context.f16_type() {
+            16
+        } else if float_type == self.context.f32_type() {
+            32
+        } else if float_type == self.context.f64_type() {
+            64
+        } else {
+            128
+        }
+    }
+
+    /// `f as iN` / `f as uN` with Rust semantics: out-of-range values clamp to
+    /// MIN/MAX and NaN becomes 0. Plain fptosi/fptoui would produce poison.
+    fn build_float_to_int_saturating(
+        &mut self,
+        float_val: FloatValue<'ctx>,
+        target_int: IntType<'ctx>,
+        is_signed: bool,
+    ) -> Result<IntValue<'ctx>, String> {
+        let width = target_int.get_bit_width();
+
+        // Constants (e.g. in `const` initializers) are folded here, outside any function
+        if let Some((f, _)) = float_val.get_constant() {
+            let bits = if is_signed {
+                let min = i128::MIN >> (128 - width);
+                let max = i128::MAX >> (128 - width);
+                (f as i128).clamp(min, max) as u128
+            } else {
+                (f as u128).min(u128::MAX >> (128 - width))
+            };
+            return Ok(
+                target_int.const_int_arbitrary_precision(&[bits as u64, (bits >> 64) as u64])
+            );
+        }
+
+        let float_suffix = match self.float_bit_width(float_val.get_type()) {
+            16 => "f16",
+            32 => "f32",
+            64 => "f64",
+            _ => "f128",
+        };
+        let intrinsic_name = format!(
+            "llvm.{}.sat.i{}.{}",
+            if is_signed { "fptosi" } else { "fptoui" },
+            width,
+            float_suffix
+        );
+        let intrinsic = self.declare_llvm_intrinsic(
+            &intrinsic_name,
+            &[float_val.get_type().into()],
+            target_int.into(),
+        );
+        Ok(self
+            .builder
+            .build_call(intrinsic, &[float_val.into()], "cast_ftoi_sat")
+            .map_err(|e| format!("Failed to call {}: {}", intrinsic_name, e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_int_value())
+    }
+}
+
+/// Unsigned sources zero-extend and convert with uitofp
+fn is_unsigned_cast_source(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::U128 | Type::Bool | Type::Byte
+    )
 }
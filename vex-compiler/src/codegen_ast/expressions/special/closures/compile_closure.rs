@@ -326,6 +326,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             // Store mapping for later use during function calls
             self.closure_envs.insert(fn_ptr, env_ptr);
+            self.closure_env_types.insert(fn_ptr, env_type);
 
             eprintln!("✅ Created closure with environment binding");
             eprintln!("   Function: {:?}, Environment: {:?}", fn_ptr, env_ptr);
@@ -0,0 +1,232 @@
+// Escaping closures
+// A function declared to return `fn(..)` hands back a pointer to a heap closure
+// object `{ ptr call, ptr env }` rather than a bare function pointer: a capturing
+// closure's environment lives in the returning function's frame, which is gone by
+// the time the caller runs it. The environment is copied to the heap. Functions
+// without an environment get a shared adapter per signature whose env is the plain
+// function pointer, so the caller always calls `call(env, args...)`.
+
+use crate::codegen_ast::ASTCodeGen;
+use inkwell::module::Linkage;
+use inkwell::types::{BasicMetadataTypeEnum, BasicType, StructType};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::AddressSpace;
+use vex_ast::*;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// LLVM layout of a closure object: `{ ptr call, ptr env }`
+    fn closure_object_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        self.context
+            .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+    }
+
+    /// Whether `expr` calls a function that returns a closure object
+    fn returns_closure_object(&self, expr: &Expression) -> Option<(Vec<Type>, Type)> {
+        let Expression::Call { func, .. } = expr else {
+            return None;
+        };
+        let Expression::Ident(callee) = &**func else {
+            return None;
+        };
+        match self.function_defs.get(callee)?.return_type.as_ref()? {
+            Type::Function {
+                params,
+                return_type,
+            } => Some((params.clone(), (**return_type).clone())),
+            _ => None,
+        }
+    }
+
+    /// Turn a value returned from a `fn(..)`-returning function into a closure object
+    /// that outlives the current frame
+    pub(crate) fn escape_returned_closure(
+        &mut self,
+        expr: &Expression,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let Some(Type::Function {
+            params,
+            return_type,
+        }) = self.current_function_return_type.clone()
+        else {
+            return Ok(value);
+        };
+        // Already a closure object: `return make_adder(1);`
+        if self.returns_closure_object(expr).is_some() {
+            return Ok(value);
+        }
+        let BasicValueEnum::PointerValue(fn_ptr) = value else {
+            return Err("Returned closure did not compile to a function pointer".to_string());
+        };
+
+        let captured = match expr {
+            Expression::Ident(name) => self
+                .closure_variables
+                .get(name)
+                .copied()
+                .filter(|(_, env)| !env.is_null()),
+            _ => self.closure_envs.get(&fn_ptr).map(|env| (fn_ptr, *env)),
+        };
+
+        let (call, env) = match captured {
+            Some((call_fn, env_ptr)) => match self.closure_env_types.get(&call_fn).copied() {
+                Some(env_type) => {
+                    eprintln!("📦 Moving closure environment to the heap for return");
+                    (call_fn, self.copy_env_to_heap(env_ptr, env_type)?)
+                }
+                // Received from another call, so the environment is on the heap already
+                None => (call_fn, env_ptr),
+            },
+            None => {
+                let adapter = self.closure_adapter(&params, &return_type)?;
+                (adapter.as_global_value().as_pointer_value(), fn_ptr)
+            }
+        };
+
+        let object_type = self.closure_object_type();
+        let object = self.heap_alloc(object_type, "closure_object")?;
+        for (index, field) in [call, env].into_iter().enumerate() {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(object_type, object, index as u32, "closure_field")
+                .map_err(|e| format!("Failed to address closure object field: {}", e))?;
+            self.builder
+                .build_store(field_ptr, field)
+                .map_err(|e| format!("Failed to store closure object field: {}", e))?;
+        }
+        Ok(object.into())
+    }
+
+    /// `let f = make_adder(1);` binds the closure object's function and registers its
+    /// environment, so `f(x)` passes it like any other capturing closure
+    pub(crate) fn bind_closure_object(
+        &mut self,
+        name: &str,
+        value_expr: &Expression,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let Some((params, return_type)) = self.returns_closure_object(value_expr) else {
+            return Ok(value);
+        };
+        let BasicValueEnum::PointerValue(object) = value else {
+            return Ok(value);
+        };
+
+        let object_type = self.closure_object_type();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let mut fields = Vec::with_capacity(2);
+        for (index, field_name) in ["closure_call", "closure_env"].into_iter().enumerate() {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(object_type, object, index as u32, field_name)
+                .map_err(|e| format!("Failed to address closure object field: {}", e))?;
+            let field = self
+                .builder
+                .build_load(ptr_type, field_ptr, field_name)
+                .map_err(|e| format!("Failed to load closure object field: {}", e))?;
+            fields.push(field.into_pointer_value());
+        }
+
+        eprintln!(
+            "📝 Registering returned closure '{}' with heap environment",
+            name
+        );
+        self.closure_variables
+            .insert(name.to_string(), (fields[0], fields[1]));
+        self.closure_types
+            .insert(name.to_string(), (params, return_type));
+        Ok(fields[0].into())
+    }
+
+    /// Copy a closure environment out of the current frame
+    fn copy_env_to_heap(
+        &mut self,
+        env_ptr: PointerValue<'ctx>,
+        env_type: StructType<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let env_value = self
+            .builder
+            .build_load(env_type, env_ptr, "closure_env_value")
+            .map_err(|e| format!("Failed to load closure environment: {}", e))?;
+        let heap_env = self.heap_alloc(env_type, "closure_heap_env")?;
+        self.builder
+            .build_store(heap_env, env_value)
+            .map_err(|e| format!("Failed to store closure environment: {}", e))?;
+        Ok(heap_env)
+    }
+
+    fn heap_alloc(
+        &mut self,
+        ty: StructType<'ctx>,
+        name: &str,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let size = ty
+            .size_of()
+            .ok_or_else(|| format!("Cannot allocate unsized {}", name))?;
+        let malloc_fn = self.get_or_declare_malloc();
+        Ok(self
+            .builder
+            .build_call(malloc_fn, &[size.into()], name)
+            .map_err(|e| format!("Failed to call malloc for {}: {}", name, e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value())
+    }
+
+    /// `call(env, args...)` entry for functions without an environment: env is the
+    /// function pointer itself, called with the remaining arguments
+    fn closure_adapter(
+        &mut self,
+        params: &[Type],
+        return_type: &Type,
+    ) -> Result<FunctionValue<'ctx>, String> {
+        let signature: Vec<String> = params
+            .iter()
+            .chain(std::iter::once(return_type))
+            .map(|ty| self.type_to_string(ty))
+            .collect();
+        let adapter_name = format!("__closure_adapter_{}", signature.join("_"));
+        if let Some(adapter) = self.module.get_function(&adapter_name) {
+            return Ok(adapter);
+        }
+
+        let plain_type = self.ast_function_type_to_llvm(params, return_type)?;
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let mut adapter_params: Vec<BasicMetadataTypeEnum<'ctx>> = vec![ptr_type.into()];
+        adapter_params.extend(plain_type.get_param_types());
+        let ret_type = plain_type
+            .get_return_type()
+            .ok_or("Closure adapter needs a return type")?;
+        let adapter = self.module.add_function(
+            &adapter_name,
+            ret_type.fn_type(&adapter_params, false),
+            Some(Linkage::Private),
+        );
+
+        let saved_block = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(adapter, "entry");
+        self.builder.position_at_end(entry);
+
+        let target = adapter
+            .get_nth_param(0)
+            .ok_or("Closure adapter environment parameter missing")?
+            .into_pointer_value();
+        let args: Vec<BasicMetadataValueEnum<'ctx>> =
+            adapter.get_param_iter().skip(1).map(|p| p.into()).collect();
+        let result = self
+            .builder
+            .build_indirect_call(plain_type, target, &args, "adapted_call")
+            .map_err(|e| format!("Failed to build closure adapter call: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic();
+        self.builder
+            .build_return(Some(&result))
+            .map_err(|e| format!("Failed to return from closure adapter: {}", e))?;
+
+        if let Some(block) = saved_block {
+            self.builder.position_at_end(block);
+        }
+        Ok(adapter)
+    }
+}
@@ -1,5 +1,6 @@
 // Closure compilation and code generation
 
 mod compile_closure;
+mod escaping;
 mod utils;
 
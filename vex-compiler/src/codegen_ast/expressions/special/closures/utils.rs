@@ -6,7 +6,7 @@ use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
     /// Find free variables in an expression (variables used but not defined in params)
-    pub(super) fn find_free_variables(&self, expr: &Expression, params: &[Param]) -> Vec<String> {
+    pub(crate) fn find_free_variables(&self, expr: &Expression, params: &[Param]) -> Vec<String> {
         use std::collections::HashSet;
 
         let param_names: HashSet<String> = params.iter().map(|p| p.name.clone()).collect();
@@ -84,7 +84,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     self.collect_variables(expr, params, free_vars, visited);
                 }
             }
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.collect_variables(value, params, free_vars, visited);
                 for arm in arms {
                     if let Some(guard) = &arm.guard {
@@ -93,17 +93,157 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     self.collect_variables(&arm.body, params, free_vars, visited);
                 }
             }
-            Expression::Block { return_expr, .. } => {
-                // For blocks, we mainly care about the return expression
-                // TODO: Handle statement expressions more thoroughly
+            Expression::Block {
+                statements,
+                return_expr,
+            } => {
+                for stmt in statements {
+                    self.collect_statement_variables(stmt, params, free_vars, visited);
+                }
                 if let Some(ret) = return_expr {
                     self.collect_variables(ret, params, free_vars, visited);
                 }
             }
+            Expression::Deref(inner)
+            | Expression::Reference { expr: inner, .. }
+            | Expression::Cast { expr: inner, .. }
+            | Expression::Is { expr: inner, .. }
+            | Expression::TryOp { expr: inner }
+            | Expression::PostfixOp { expr: inner, .. }
+            | Expression::Await(inner)
+            | Expression::Yield(inner)
+            | Expression::ChannelReceive(inner) => {
+                self.collect_variables(inner, params, free_vars, visited);
+            }
             _ => {} // Literals, other expressions
         }
     }
 
+    /// Collect variable references from a statement nested in a block body
+    fn collect_statement_variables(
+        &self,
+        stmt: &Statement,
+        params: &std::collections::HashSet<String>,
+        free_vars: &mut Vec<String>,
+        visited: &mut std::collections::HashSet<String>,
+    ) {
+        match stmt {
+            Statement::Let { value, .. } | Statement::LetPattern { value, .. } => {
+                self.collect_variables(value, params, free_vars, visited);
+            }
+            Statement::Assign { target, value, .. }
+            | Statement::CompoundAssign { target, value, .. } => {
+                self.collect_variables(target, params, free_vars, visited);
+                self.collect_variables(value, params, free_vars, visited);
+            }
+            Statement::Return {
+                value: Some(expr), ..
+            }
+            | Statement::Expression(expr)
+            | Statement::Go { expr, .. } => {
+                self.collect_variables(expr, params, free_vars, visited);
+            }
+            Statement::Defer(inner) | Statement::Attributed { stmt: inner, .. } => {
+                self.collect_statement_variables(inner, params, free_vars, visited);
+            }
+            Statement::If {
+                condition,
+                then_block,
+                elif_branches,
+                else_block,
+                ..
+            } => {
+                self.collect_variables(condition, params, free_vars, visited);
+                self.collect_block_variables(then_block, params, free_vars, visited);
+                for (elif_cond, elif_block) in elif_branches {
+                    self.collect_variables(elif_cond, params, free_vars, visited);
+                    self.collect_block_variables(elif_block, params, free_vars, visited);
+                }
+                if let Some(else_b) = else_block {
+                    self.collect_block_variables(else_b, params, free_vars, visited);
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.collect_variables(condition, params, free_vars, visited);
+                self.collect_block_variables(body, params, free_vars, visited);
+            }
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                if let Some(init_stmt) = init {
+                    self.collect_statement_variables(init_stmt, params, free_vars, visited);
+                }
+                if let Some(cond) = condition {
+                    self.collect_variables(cond, params, free_vars, visited);
+                }
+                if let Some(post_stmt) = post {
+                    self.collect_statement_variables(post_stmt, params, free_vars, visited);
+                }
+                self.collect_block_variables(body, params, free_vars, visited);
+            }
+            Statement::ForIn { iterable, body, .. } => {
+                self.collect_variables(iterable, params, free_vars, visited);
+                self.collect_block_variables(body, params, free_vars, visited);
+            }
+            Statement::Loop { body, .. } | Statement::Unsafe { block: body, .. } => {
+                self.collect_block_variables(body, params, free_vars, visited);
+            }
+            Statement::Switch {
+                value,
+                cases,
+                default_case,
+                ..
+            } => {
+                if let Some(v) = value {
+                    self.collect_variables(v, params, free_vars, visited);
+                }
+                for case in cases {
+                    for pattern in &case.patterns {
+                        self.collect_variables(pattern, params, free_vars, visited);
+                    }
+                    self.collect_block_variables(&case.body, params, free_vars, visited);
+                }
+                if let Some(default) = default_case {
+                    self.collect_block_variables(default, params, free_vars, visited);
+                }
+            }
+            Statement::Select {
+                cases,
+                default_case,
+                ..
+            } => {
+                for case in cases {
+                    self.collect_variables(&case.expr, params, free_vars, visited);
+                    self.collect_block_variables(&case.body, params, free_vars, visited);
+                }
+                if let Some(default) = default_case {
+                    self.collect_block_variables(default, params, free_vars, visited);
+                }
+            }
+            Statement::Return { value: None, .. }
+            | Statement::Break { .. }
+            | Statement::Continue { .. } => {}
+        }
+    }
+
+    fn collect_block_variables(
+        &self,
+        block: &Block,
+        params: &std::collections::HashSet<String>,
+        free_vars: &mut Vec<String>,
+        visited: &mut std::collections::HashSet<String>,
+    ) {
+        for stmt in &block.statements {
+            self.collect_statement_variables(stmt, params, free_vars, visited);
+        }
+    }
+
     /// Generate closure struct with trait implementation
     /// Creates: struct __Closure_1 impl Callable(i32): i32 { fn_ptr, env_ptr }
     pub(super) fn generate_closure_struct(
@@ -153,8 +293,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let method = Function {
             is_exported: false, // Generated closure methods are internal
             span_id: None,      // Synthetic method, no source span
+            attributes: vec![],
             is_async: false,
             is_gpu: false,
+            is_const: false,
             is_mutable,         // ⭐ NEW: Method mutability matches closure capture mode
             is_operator: false, // Closures are not operators
             is_static: false,
@@ -181,6 +323,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let struct_def = Struct {
             is_exported: false, // Generated closure structs are internal
             span_id: None,      // Synthetic struct, no source span
+            attributes: vec![],
             name: struct_name.clone(),
             type_params: vec![],
             const_params: vec![],
@@ -323,4 +466,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             _ => None,
         }
     }
+
+    /// Capture mode of a closure literal or a variable bound to one
+    pub(crate) fn closure_capture_mode(&self, arg: &Expression) -> Option<CaptureMode> {
+        match arg {
+            Expression::Closure { capture_mode, .. } => Some(capture_mode.clone()),
+            Expression::Ident(name) => self.closure_capture_modes.get(name).cloned(),
+            _ => None,
+        }
+    }
 }
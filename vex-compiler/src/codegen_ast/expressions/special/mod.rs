@@ -1,5 +1,6 @@
-// Special operations coordinator (unary, postfix, closures, casts)
+// Special operations coordinator (unary, postfix, closures, casts, allocation)
 
+mod allocation;
 mod casts;
 mod closures;
 mod unary;
@@ -30,6 +30,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             &[], // No generic type args
                             &[], // No additional args for unary ops
                             false,
+                            None,
                         );
                     }
                 }
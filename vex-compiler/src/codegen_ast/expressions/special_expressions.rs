@@ -108,6 +108,31 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.compile_expression(&method_call)
     }
 
+    /// Compile new(expr) heap allocation
+    pub(crate) fn compile_new_dispatch(
+        &mut self,
+        expr: &vex_ast::Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        self.compile_new_expression(expr)
+    }
+
+    /// Compile make([T], len) and make(Channel<T>, capacity)
+    pub(crate) fn compile_make_dispatch(
+        &mut self,
+        element_type: &vex_ast::Type,
+        size: &vex_ast::Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        self.compile_make_expression(element_type, size)
+    }
+
+    /// Compile error.new(message)
+    pub(crate) fn compile_error_new_dispatch(
+        &mut self,
+        message: &vex_ast::Expression,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        self.compile_error_new_expression(message)
+    }
+
     /// Compile typeof expressions
     pub(crate) fn compile_typeof_dispatch(
         &mut self,
@@ -1,5 +1,6 @@
 // Expression compilation - structs and enums
 use super::ASTCodeGen;
+use inkwell::types::BasicTypeEnum;
 use inkwell::values::BasicValueEnum;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -19,156 +20,189 @@ impl<'ctx> ASTCodeGen<'ctx> {
         enum_name: &str,
         variant: &str,
         data: &[vex_ast::Expression],
+        field_names: &[String],
+        expected_type: Option<&vex_ast::Type>,
     ) -> Result<BasicValueEnum<'ctx>, String> {
+        // Named fields may be written in any order: position i of `data` holds
+        // the payload field at positions[i]
+        let positions = self.enum_payload_positions(enum_name, variant, field_names, data.len())?;
+
         // Phase 0.4: Handle builtin enums (Option, Result) specially
         if enum_name == "Option" || enum_name == "Result" {
-            return self.compile_builtin_enum_literal(enum_name, variant, &data.to_vec());
+            return self.compile_builtin_enum_literal(
+                enum_name,
+                variant,
+                &data.to_vec(),
+                expected_type,
+            );
         }
 
-        // For now, treat enums as tagged unions (C-style for unit variants)
-        // Unit variants (no data): Just return the tag as i32
-        // Data-carrying variants: Need struct with tag + data (TODO: full implementation)
-
-        // Look up enum definition
-        if let Some(enum_def) = self.enum_ast_defs.get(enum_name) {
-            // Find variant index
-            let variant_index = enum_def
-                .variants
+        // Unit-only enums are a bare i32 tag; data-carrying enums are
+        // { i32 tag, union storage } (see enum_llvm_type)
+        let enum_def = self
+            .enum_ast_defs
+            .get(enum_name)
+            .cloned()
+            .ok_or_else(|| format!("Enum {} not found in definitions", enum_name))?;
+
+        // Find variant index
+        let variant_index = enum_def
+            .variants
+            .iter()
+            .position(|v| v.name == variant)
+            .ok_or_else(|| format!("Variant {} not found in enum {}", variant, enum_name))?;
+        let variant_def = &enum_def.variants[variant_index];
+
+        if variant_def.is_struct_like() && data.len() < variant_def.data.len() {
+            let missing: Vec<&str> = variant_def
+                .field_names
                 .iter()
-                .position(|v| &v.name == variant)
-                .ok_or_else(|| format!("Variant {} not found in enum {}", variant, enum_name))?;
-
-            // Check if enum has ANY data-carrying variants
-            let enum_has_data = enum_def.variants.iter().any(|v| !v.data.is_empty());
-
-            if data.is_empty() && !enum_has_data {
-                // Pure unit enum (all variants are unit): return tag as i32 for compatibility
-                // (Variables expect i32, return statements expect i32)
-                let tag = self
-                    .context
-                    .i32_type()
-                    .const_int(variant_index as u64, false);
-                Ok(tag.into())
-            } else {
-                // Mixed enum (has data variants): create struct { tag: i32, data: T }
-                // For unit variants in mixed enums, use enum's data type from definition
-
-                let (data_value, actual_data_type) = if !data.is_empty() {
-                    // Variant has data: compile all expressions
-                    if data.len() == 1 {
-                        // Single-value tuple: compile directly
-                        let val = self.compile_expression(&data[0])?;
-                        let ty = val.get_type();
-                        (val, ty)
-                    } else {
-                        // Multi-value tuple: create struct with all values
-                        let mut field_values = Vec::new();
-                        let mut field_types = Vec::new();
-
-                        for expr in data {
-                            let val = self.compile_expression(expr)?;
-                            let ty = val.get_type();
-                            field_values.push(val);
-                            field_types.push(ty);
-                        }
-
-                        // Create tuple struct type
-                        let tuple_struct_type = self.context.struct_type(&field_types, false);
+                .enumerate()
+                .filter(|(i, _)| !positions.contains(i))
+                .map(|(_, name)| name.as_str())
+                .collect();
+            return Err(format!(
+                "Missing field(s) {} in {}.{}",
+                missing.join(", "),
+                enum_name,
+                variant
+            ));
+        }
 
-                        // Build tuple value
-                        let mut tuple_val = tuple_struct_type.get_undef();
-                        for (i, val) in field_values.iter().enumerate() {
-                            tuple_val = self
-                                .builder
-                                .build_insert_value(
-                                    tuple_val,
-                                    *val,
-                                    i as u32,
-                                    &format!("tuple_field_{}", i),
-                                )
-                                .map_err(|e| format!("Failed to insert tuple field: {}", e))?
-                                .into_struct_value();
-                        }
+        if data.len() != variant_def.data.len() {
+            return Err(format!(
+                "{}.{} takes {} value(s) but {} were supplied",
+                enum_name,
+                variant,
+                variant_def.data.len(),
+                data.len()
+            ));
+        }
 
-                        (tuple_val.into(), tuple_struct_type.into())
-                    }
-                } else {
-                    // Unit variant in mixed enum: use enum's largest data type with zero value
-                    let enum_llvm_type =
-                        self.ast_type_to_llvm(&vex_ast::Type::Named(enum_name.to_string()));
-                    if let inkwell::types::BasicTypeEnum::StructType(struct_ty) = enum_llvm_type {
-                        // Extract data field type (index 1)
-                        let data_field_type = struct_ty
-                            .get_field_type_at_index(1)
-                            .ok_or_else(|| "Enum struct missing data field".to_string())?;
-                        // Create zero/undef value for data field
-                        let zero_value = match data_field_type {
-                            inkwell::types::BasicTypeEnum::IntType(int_ty) => {
-                                int_ty.const_zero().into()
-                            }
-                            inkwell::types::BasicTypeEnum::FloatType(float_ty) => {
-                                float_ty.const_zero().into()
-                            }
-                            inkwell::types::BasicTypeEnum::PointerType(ptr_ty) => {
-                                ptr_ty.const_null().into()
-                            }
-                            _ => {
-                                return Err(format!(
-                                    "Unsupported enum data type: {:?}",
-                                    data_field_type
-                                ))
-                            }
-                        };
-                        (zero_value, data_field_type)
-                    } else {
-                        return Err(format!("Expected struct type for mixed enum {}", enum_name));
-                    }
-                };
+        // Generic enums keep the payload as compiled: their field types are
+        // only known per instantiation
+        if !enum_def.type_params.is_empty() {
+            let mut ordered = data.to_vec();
+            for (expr, &position) in data.iter().zip(&positions) {
+                ordered[position] = expr.clone();
+            }
+            return self.compile_generic_enum_literal(enum_name, variant_index, &ordered);
+        }
 
-                // Create struct type: { i32, T } (consistent with ast_type_to_llvm)
-                let tag_type = self.context.i32_type();
-                let struct_type = self
-                    .context
-                    .struct_type(&[tag_type.into(), actual_data_type], false);
+        // Fields are evaluated in the order they are written
+        let enum_type = self.enum_llvm_type(&enum_def);
+        let mut slots: Vec<Option<BasicValueEnum<'ctx>>> = vec![None; data.len()];
+        for (expr, &position) in data.iter().zip(&positions) {
+            let field_ty = &variant_def.data[position];
+            let value = self.compile_expression_with_type(expr, Some(field_ty))?;
+            let field_llvm = self.ast_type_to_llvm(field_ty);
+            slots[position] = Some(self.coerce_enum_field(value, field_llvm)?);
+        }
+        let field_values: Vec<BasicValueEnum<'ctx>> = slots.into_iter().flatten().collect();
+
+        let payload = match field_values.len() {
+            0 => None,
+            1 => Some(field_values[0]),
+            _ => {
+                // Multi-value tuple: pack into the variant's payload struct
+                let payload_type = self
+                    .enum_variant_payload_type(variant_def)
+                    .ok_or("Data variant without payload type")?
+                    .into_struct_type();
+                let mut tuple_val = payload_type.get_undef();
+                for (i, val) in field_values.iter().enumerate() {
+                    tuple_val = self
+                        .builder
+                        .build_insert_value(
+                            tuple_val,
+                            *val,
+                            i as u32,
+                            &format!("tuple_field_{}", i),
+                        )
+                        .map_err(|e| format!("Failed to insert tuple field: {}", e))?
+                        .into_struct_value();
+                }
+                Some(tuple_val.into())
+            }
+        };
 
-                // Allocate struct on stack
-                let struct_ptr = self
-                    .builder
-                    .build_alloca(struct_type, "enum_data_carrier")
-                    .map_err(|e| format!("Failed to allocate enum struct: {}", e))?;
+        self.build_enum_value(enum_type, variant_index as u64, payload)
+    }
 
-                // Store tag at index 0 (i32 type - consistent with struct definition)
-                let tag = self
-                    .context
-                    .i32_type()
-                    .const_int(variant_index as u64, false);
-                let tag_ptr = self
-                    .builder
-                    .build_struct_gep(struct_type, struct_ptr, 0, "enum_tag_ptr")
-                    .map_err(|e| format!("Failed to get tag pointer: {}", e))?;
+    /// Convert a compiled payload field to the variant's declared field type
+    fn coerce_enum_field(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        field_llvm: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        match (value, field_llvm) {
+            // Struct literals and struct variables evaluate to their storage address
+            (BasicValueEnum::PointerValue(ptr), BasicTypeEnum::StructType(_))
+            | (BasicValueEnum::PointerValue(ptr), BasicTypeEnum::ArrayType(_)) => self
+                .builder
+                .build_load(field_llvm, ptr, "enum_field_loaded")
+                .map_err(|e| format!("Failed to load enum field: {}", e)),
+            (BasicValueEnum::IntValue(iv), BasicTypeEnum::IntType(target))
+                if iv.get_type() != target =>
+            {
                 self.builder
-                    .build_store(tag_ptr, tag)
-                    .map_err(|e| format!("Failed to store tag: {}", e))?;
-
-                // Store data at index 1
-                let data_ptr = self
-                    .builder
-                    .build_struct_gep(struct_type, struct_ptr, 1, "enum_data_ptr")
-                    .map_err(|e| format!("Failed to get data pointer: {}", e))?;
+                    .build_int_cast(iv, target, "enum_field_cast")
+                    .map(Into::into)
+                    .map_err(|e| format!("Failed to cast enum field: {}", e))
+            }
+            (BasicValueEnum::FloatValue(fv), BasicTypeEnum::FloatType(target))
+                if fv.get_type() != target =>
+            {
                 self.builder
-                    .build_store(data_ptr, data_value)
-                    .map_err(|e| format!("Failed to store data: {}", e))?;
+                    .build_float_cast(fv, target, "enum_field_cast")
+                    .map(Into::into)
+                    .map_err(|e| format!("Failed to cast enum field: {}", e))
+            }
+            _ => Ok(value),
+        }
+    }
 
-                // Load and return the struct value
-                let struct_value = self
-                    .builder
-                    .build_load(struct_type, struct_ptr, "enum_with_data")
-                    .map_err(|e| format!("Failed to load enum struct: {}", e))?;
+    /// Generic enum literal: `{ i32, payload }` with the payload as compiled
+    fn compile_generic_enum_literal(
+        &mut self,
+        enum_name: &str,
+        variant_index: usize,
+        data: &[vex_ast::Expression],
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let mut field_values = Vec::with_capacity(data.len());
+        for expr in data {
+            field_values.push(self.compile_expression(expr)?);
+        }
 
-                Ok(struct_value)
+        let payload: Option<BasicValueEnum<'ctx>> = match field_values.len() {
+            0 => None,
+            1 => Some(field_values[0]),
+            _ => {
+                let field_types: Vec<_> = field_values.iter().map(|v| v.get_type()).collect();
+                let mut tuple_val = self.context.struct_type(&field_types, false).get_undef();
+                for (i, val) in field_values.iter().enumerate() {
+                    tuple_val = self
+                        .builder
+                        .build_insert_value(
+                            tuple_val,
+                            *val,
+                            i as u32,
+                            &format!("tuple_field_{}", i),
+                        )
+                        .map_err(|e| format!("Failed to insert tuple field: {}", e))?
+                        .into_struct_value();
+                }
+                Some(tuple_val.into())
             }
-        } else {
-            Err(format!("Enum {} not found in definitions", enum_name))
-        }
+        };
+
+        let enum_type = match payload {
+            Some(payload) => self
+                .context
+                .struct_type(&[self.context.i32_type().into(), payload.get_type()], false)
+                .into(),
+            None => self.ast_type_to_llvm(&vex_ast::Type::Named(enum_name.to_string())),
+        };
+        self.build_enum_value(enum_type, variant_index as u64, payload)
     }
 }
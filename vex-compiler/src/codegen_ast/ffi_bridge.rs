@@ -129,7 +129,8 @@ impl<'ctx> FFIBridge<'ctx> {
             Type::Bool => Ok(self.context.bool_type().into()),
             Type::Byte => Ok(self.context.i8_type().into()),
             Type::Nil => Ok(self.context.i8_type().into()),
-            Type::Error => Ok(self.context.i32_type().into()),
+            // error: VexError* (vex_error_new)
+            Type::Error => Ok(self.context.ptr_type(AddressSpace::default()).into()),
 
             // Raw pointer: *T or *const T (for FFI)
             Type::RawPtr {
@@ -1,38 +1,103 @@
 // src/codegen/functions/asynchronous.rs
 use super::super::*;
-use super::await_scanner::count_await_points;
-use inkwell::values::FunctionValue;
+use super::await_scanner::{count_await_points, locals_live_across_await};
+use super::move_scanner::moved_locals;
+use inkwell::basic_block::BasicBlock;
+use inkwell::types::BasicType;
+use inkwell::values::{FunctionValue, InstructionOpcode, InstructionValue};
+use std::collections::BTreeSet;
+
+/// `<fn>_AsyncState` prefix: { i32 state, Future<T>* future, params... }
+/// Generators use the same prefix with their handle in place of the future.
+const ASYNC_STATE_FUTURE_FIELD: u32 = 1;
+const ASYNC_STATE_PARAM_BASE: usize = 2;
+
+/// `Generator<T>` handle: { ptr resume_fn, ptr state, T item }
+pub(crate) const GENERATOR_HANDLE_RESUME_FIELD: u32 = 0;
+pub(crate) const GENERATOR_HANDLE_STATE_FIELD: u32 = 1;
+pub(crate) const GENERATOR_HANDLE_ITEM_FIELD: u32 = 2;
 
 /// Async context for tracking await compilation state
 #[derive(Debug)]
-pub(crate) struct AsyncContext {
-    // Empty for now - used as marker for async function compilation
+pub(crate) struct AsyncContext<'ctx> {
+    /// Future handle this coroutine completes (loaded in the resume entry block);
+    /// the generator handle when compiling a generator
+    pub(crate) future_ptr: PointerValue<'ctx>,
+    /// `T` of the `Future<T>` returned by the async function (None for no value)
+    pub(crate) result_type: Option<Type>,
+    /// `T` of `Generator<T>`: yields store into the handle and finishing completes nothing
+    pub(crate) generator_item: Option<Type>,
+    /// Locals read after an await that follows their declaration (spill candidates)
+    pub(crate) live_across_await: BTreeSet<String>,
+    /// Yield points seen so far; spill/reload code is emitted once the body is
+    /// compiled and every spilled local's type is known
+    pub(crate) yield_points: Vec<AsyncYieldPoint<'ctx>>,
+}
+
+/// One `return CORO_STATUS_YIELDED` and the block execution continues in
+#[derive(Debug)]
+pub(crate) struct AsyncYieldPoint<'ctx> {
+    yield_ret: InstructionValue<'ctx>,
+    resume_block: BasicBlock<'ctx>,
+    /// (entry-block alloca, value type) of every live local in scope at the yield
+    locals: Vec<(PointerValue<'ctx>, BasicTypeEnum<'ctx>)>,
 }
 
 impl<'ctx> ASTCodeGen<'ctx> {
     pub(crate) fn compile_async_function(&mut self, func: &Function) -> Result<(), String> {
+        self.compile_coroutine(func, None)
+    }
+
+    /// Generator functions (`fn f(): Generator<T>`) reuse the async state machine.
+    /// Each `yield` stores the item in the generator handle and returns
+    /// CORO_STATUS_YIELDED to the caller that resumed it; the wrapper returns the
+    /// handle instead of spawning.
+    pub(crate) fn compile_generator_function(
+        &mut self,
+        func: &Function,
+        item_type: &Type,
+    ) -> Result<(), String> {
+        self.compile_coroutine(func, Some(item_type))
+    }
+
+    fn compile_coroutine(
+        &mut self,
+        func: &Function,
+        generator_item: Option<&Type>,
+    ) -> Result<(), String> {
         // Async functions are transformed into state machines:
         // 1. Create a state struct with all locals + state field
         // 2. Generate a resume function: CoroStatus resume_fn(WorkerContext*, void* state)
-        // 3. At await points, save state and return CORO_STATUS_YIELDED
-        // 4. Original function becomes a spawn wrapper
+        // 3. At await (or yield) points, save state and return CORO_STATUS_YIELDED
+        // 4. Original function becomes a spawn wrapper (or returns the generator handle)
 
         let fn_name = &func.name;
 
         // Step 1: Generate state struct type
-        let state_struct_name = format!("{}_AsyncState", fn_name);
+        let state_struct_name = if generator_item.is_some() {
+            format!("{}_GeneratorState", fn_name)
+        } else {
+            format!("{}_AsyncState", fn_name)
+        };
         let mut state_fields = vec![];
 
         // Add state field (i32 for state machine)
         state_fields.push(self.context.i32_type().into());
 
+        // Add future handle the final state writes the result into
+        state_fields.push(
+            self.context
+                .ptr_type(inkwell::AddressSpace::default())
+                .into(),
+        );
+
         // Add fields for all parameters
         for param in &func.params {
             state_fields.push(self.ast_type_to_llvm(&param.ty));
         }
 
-        // Add fields for all local variables (collect from body)
-        // For now, we'll allocate them dynamically as needed
+        // Locals live across an await are appended after the body is compiled
+        // (see emit_async_spills); body code only addresses this prefix.
 
         let state_struct_type = self.context.struct_type(&state_fields, false);
 
@@ -98,7 +163,38 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .build_load(self.context.i32_type(), state_field_ptr, "current_state")
             .map_err(|e| format!("Failed to load state: {}", e))?;
 
-        // ⭐ PRE-SCAN: Count total await points in function body
+        let future_field_ptr = self
+            .builder
+            .build_struct_gep(
+                state_struct_type,
+                state_ptr,
+                ASYNC_STATE_FUTURE_FIELD,
+                "future_field_ptr",
+            )
+            .map_err(|e| format!("Failed to get future field: {}", e))?;
+        let future_ptr = self
+            .builder
+            .build_load(void_ptr, future_field_ptr, "future")
+            .map_err(|e| format!("Failed to load future: {}", e))?
+            .into_pointer_value();
+
+        // Parameter slots are addressed from the entry block so they dominate
+        // every resume block, not just state 0
+        let mut param_ptrs = Vec::with_capacity(func.params.len());
+        for (i, param) in func.params.iter().enumerate() {
+            let param_ptr = self
+                .builder
+                .build_struct_gep(
+                    state_struct_type,
+                    state_ptr,
+                    (i + ASYNC_STATE_PARAM_BASE) as u32, // after state and future fields
+                    &format!("param_{}_ptr", param.name),
+                )
+                .map_err(|e| format!("Failed to get param ptr: {}", e))?;
+            param_ptrs.push(param_ptr);
+        }
+
+        // ⭐ PRE-SCAN: Count total await (and yield) points in function body
         let await_count = count_await_points(&func.body);
 
         // Create switch on state - pre-allocate ALL blocks before building switch
@@ -133,28 +229,29 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.current_async_resume_fn = Some(resume_fn);
         self.variables.clear();
         self.variable_types.clear();
+        self.moved_locals = moved_locals(&func.body);
 
         // Push state machine context for await expressions
         // Store resume blocks in codegen state for await compilation
         self.async_resume_blocks = resume_blocks.clone();
         self.async_state_stack.push((state_ptr, state_field_ptr, 0));
-        self.async_context = Some(AsyncContext {}); // Set async context for implicit terminator check
+        let result_type = func
+            .return_type
+            .clone()
+            .filter(|ty| !matches!(ty, Type::Nil) && generator_item.is_none());
+        self.async_context = Some(AsyncContext {
+            future_ptr,
+            result_type: result_type.clone(),
+            generator_item: generator_item.cloned(),
+            live_across_await: locals_live_across_await(&func.body),
+            yield_points: Vec::new(),
+        });
         eprintln!(
             "🔧 Pushed state context: state_id=0, resume_blocks_count={}",
             resume_blocks.len()
         );
 
-        for (i, param) in func.params.iter().enumerate() {
-            let param_ptr = self
-                .builder
-                .build_struct_gep(
-                    state_struct_type,
-                    state_ptr,
-                    (i + 1) as u32, // +1 because state field is at 0
-                    &format!("param_{}_ptr", param.name),
-                )
-                .map_err(|e| format!("Failed to get param ptr: {}", e))?;
-
+        for (param, param_ptr) in func.params.iter().zip(param_ptrs) {
             let param_type = self.ast_type_to_llvm(&param.ty);
             self.variables.insert(param.name.clone(), param_ptr);
             self.variable_types.insert(param.name.clone(), param_type);
@@ -170,7 +267,6 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Pop state machine context and clear resume blocks
         self.async_state_stack.pop();
         self.current_async_resume_fn = None;
-        self.async_context = None; // Clear async context
 
         // ⭐ Resume blocks already have terminators (added during await compilation)
         // Just verify and add fallback if needed
@@ -179,11 +275,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // ⚠️ CRITICAL: Position builder back to state0 block's end before checking terminators
         let current_insert_block = self.builder.get_insert_block();
 
-        for resume_block in &self.async_resume_blocks {
+        let resume_blocks = self.async_resume_blocks.clone();
+        for resume_block in resume_blocks {
             // Each resume block should already have a branch to continuation
-            // If it doesn't, add DONE return as fallback
+            // If it doesn't, the body fell off its end: complete with no value
             if resume_block.get_terminator().is_none() {
-                self.builder.position_at_end(*resume_block);
+                self.builder.position_at_end(resume_block);
+                self.complete_async_future(None)?;
                 self.builder.build_return(Some(&done_status)).map_err(|e| {
                     format!("Failed to build resume block fallback terminator: {}", e)
                 })?;
@@ -206,6 +304,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .ok_or_else(|| format!("No current block in async function {}", fn_name))?;
         if current_block.get_terminator().is_none() {
             // Return CORO_STATUS_DONE (2)
+            self.complete_async_future(None)?;
             self.builder
                 .build_return(Some(&done_status))
                 .map_err(|e| format!("Failed to build return: {}", e))?;
@@ -217,6 +316,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .build_return(Some(&done_status))
             .map_err(|e| format!("Failed to build return: {}", e))?;
 
+        let async_context = self.async_context.take(); // Clear async context
+
+        // Every block is terminated now: store live locals before each yield and
+        // reload them at the top of its resume block
+        let yield_points = async_context
+            .map(|ctx| ctx.yield_points)
+            .unwrap_or_default();
+        let state_struct_type =
+            self.emit_async_spills(&state_struct_name, &state_fields, state_ptr, &yield_points)?;
+
         // Step 3: Generate wrapper function (original name)
         // This allocates state and spawns the coroutine (for regular async functions)
         // For async main, just returns the state pointer
@@ -266,6 +375,43 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .build_store(state_init_ptr, self.context.i32_type().const_int(0, false))
             .map_err(|e| format!("Failed to store state: {}", e))?;
 
+        let future_handle = match generator_item {
+            // Generators hand back { resume_fn, state, item } to step them directly
+            Some(item_type) => {
+                self.build_generator_handle(resume_fn, state_alloc_ptr, item_type)?
+            }
+            // Allocate the Future<T> the coroutine completes; sized for T's LLVM layout
+            None => {
+                let i64_type = self.context.i64_type();
+                let result_size = match &result_type {
+                    Some(ty) => self
+                        .ast_type_to_llvm(ty)
+                        .size_of()
+                        .ok_or_else(|| format!("Failed to get size of {} result type", fn_name))?,
+                    None => i64_type.const_zero(),
+                };
+                let future_new = self.get_or_declare_future_fn("vex_future_new")?;
+                self.builder
+                    .build_call(future_new, &[result_size.into()], "future")
+                    .map_err(|e| format!("Failed to call vex_future_new: {}", e))?
+                    .try_as_basic_value()
+                    .unwrap_basic()
+                    .into_pointer_value()
+            }
+        };
+        let future_init_ptr = self
+            .builder
+            .build_struct_gep(
+                state_struct_type,
+                state_alloc_typed,
+                ASYNC_STATE_FUTURE_FIELD,
+                "future_init_ptr",
+            )
+            .map_err(|e| format!("Failed to get future ptr: {}", e))?;
+        self.builder
+            .build_store(future_init_ptr, future_handle)
+            .map_err(|e| format!("Failed to store future: {}", e))?;
+
         // Copy parameters into state struct
         for i in 0..func.params.len() {
             let param_idx = crate::safe_field_index(i)
@@ -281,7 +427,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 .build_struct_gep(
                     state_struct_type,
                     state_alloc_typed,
-                    (i + 1) as u32,
+                    (i + ASYNC_STATE_PARAM_BASE) as u32,
                     &format!("param_{}_dest", i),
                 )
                 .map_err(|e| format!("Failed to get param dest: {}", e))?;
@@ -290,7 +436,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 .build_store(param_dest, param_val)
                 .map_err(|e| format!("Failed to store param: {}", e))?;
         }
-        if fn_name == "main" {
+        if generator_item.is_some() {
+            // Nothing runs until the consumer resumes the generator
+            self.builder
+                .build_return(Some(&future_handle))
+                .map_err(|e| format!("Failed to build generator return: {}", e))?;
+        } else if fn_name == "main" {
             // For async main, just return the state pointer as the future
             self.builder
                 .build_return(Some(&state_alloc_ptr))
@@ -336,26 +487,343 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 )
                 .map_err(|e| format!("Failed to call runtime_spawn_global: {}", e))?;
 
-            // Return appropriate value based on function return type
-            // Async functions should return Future<T> (opaque pointer to runtime future handle)
-            // For now, return null pointer as placeholder - runtime will manage the actual future
-            if let Some(_ret_type) = &func.return_type {
-                // TODO: Allocate and return actual Future<T> handle from runtime
-                // For now, return null pointer (void*)
-                let null_ptr = self
-                    .context
-                    .ptr_type(inkwell::AddressSpace::default())
-                    .const_null();
+            // Async functions return Future<T>: the handle the coroutine completes
+            self.builder
+                .build_return(Some(&future_handle))
+                .map_err(|e| format!("Failed to build wrapper return: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Write the async function's result into its Future<T> and mark it ready.
+    /// Called right before every `ret CORO_STATUS_DONE` of a resume function.
+    pub(crate) fn complete_async_future(
+        &mut self,
+        value: Option<BasicValueEnum<'ctx>>,
+    ) -> Result<(), String> {
+        // Generators have no future: finishing just reports CORO_STATUS_DONE
+        let Some((future_ptr, result_type)) = self
+            .async_context
+            .as_ref()
+            .filter(|ctx| ctx.generator_item.is_none())
+            .map(|ctx| (ctx.future_ptr, ctx.result_type.clone()))
+        else {
+            return Ok(());
+        };
+
+        if let (Some(value), Some(result_type)) = (value, result_type) {
+            let result_llvm_type = self.ast_type_to_llvm(&result_type);
+            let value = self.coerce_future_result(value, result_llvm_type)?;
+
+            let result_fn = self.get_or_declare_future_fn("vex_future_result")?;
+            let result_slot = self
+                .builder
+                .build_call(result_fn, &[future_ptr.into()], "future_slot")
+                .map_err(|e| format!("Failed to call vex_future_result: {}", e))?
+                .try_as_basic_value()
+                .unwrap_basic()
+                .into_pointer_value();
+            self.builder
+                .build_store(result_slot, value)
+                .map_err(|e| format!("Failed to store future result: {}", e))?;
+        }
+
+        let complete_fn = self.get_or_declare_future_fn("vex_future_complete")?;
+        self.builder
+            .build_call(complete_fn, &[future_ptr.into()], "")
+            .map_err(|e| format!("Failed to call vex_future_complete: {}", e))?;
+        Ok(())
+    }
+
+    /// LLVM layout of the `Generator<T>` handle
+    pub(crate) fn generator_handle_type(&self, item_type: &Type) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        self.context.struct_type(
+            &[
+                ptr_type.into(),
+                ptr_type.into(),
+                self.ast_type_to_llvm(item_type),
+            ],
+            false,
+        )
+    }
+
+    /// Heap handle returned by a generator function; the item slot is written by
+    /// every `yield`
+    fn build_generator_handle(
+        &mut self,
+        resume_fn: FunctionValue<'ctx>,
+        state: PointerValue<'ctx>,
+        item_type: &Type,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let handle_type = self.generator_handle_type(item_type);
+        let handle_size = handle_type
+            .size_of()
+            .ok_or("Failed to get size of generator handle")?;
+        let malloc_fn = self.get_or_declare_malloc();
+        let handle = self
+            .builder
+            .build_call(malloc_fn, &[handle_size.into()], "generator")
+            .map_err(|e| format!("Failed to call malloc: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value();
+
+        let fields = [
+            (
+                GENERATOR_HANDLE_RESUME_FIELD,
+                resume_fn.as_global_value().as_pointer_value(),
+            ),
+            (GENERATOR_HANDLE_STATE_FIELD, state),
+        ];
+        for (index, value) in fields {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(handle_type, handle, index, "generator_field")
+                .map_err(|e| format!("Failed to get generator handle field: {}", e))?;
+            self.builder
+                .build_store(field_ptr, value)
+                .map_err(|e| format!("Failed to initialize generator handle: {}", e))?;
+        }
+        Ok(handle)
+    }
+
+    /// Store a yielded value in the generator handle for the consumer to read
+    pub(crate) fn store_generator_item(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<(), String> {
+        let Some((handle, item_type)) = self.async_context.as_ref().and_then(|ctx| {
+            ctx.generator_item
+                .clone()
+                .map(|item_type| (ctx.future_ptr, item_type))
+        }) else {
+            return Err("`yield` is only allowed in functions returning Generator<T>".to_string());
+        };
+
+        let item_llvm_type = self.ast_type_to_llvm(&item_type);
+        let value = self.coerce_future_result(value, item_llvm_type)?;
+        let item_ptr = self
+            .builder
+            .build_struct_gep(
+                self.generator_handle_type(&item_type),
+                handle,
+                GENERATOR_HANDLE_ITEM_FIELD,
+                "generator_item_ptr",
+            )
+            .map_err(|e| format!("Failed to get generator item slot: {}", e))?;
+        self.builder
+            .build_store(item_ptr, value)
+            .map_err(|e| format!("Failed to store generator item: {}", e))?;
+        Ok(())
+    }
+
+    /// Read `T` out of a completed future
+    pub(crate) fn load_future_result(
+        &mut self,
+        future_ptr: PointerValue<'ctx>,
+        result_type: &Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let result_fn = self.get_or_declare_future_fn("vex_future_result")?;
+        let result_slot = self
+            .builder
+            .build_call(result_fn, &[future_ptr.into()], "future_slot")
+            .map_err(|e| format!("Failed to call vex_future_result: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value();
+        let result_llvm_type = self.ast_type_to_llvm(result_type);
+        self.builder
+            .build_load(result_llvm_type, result_slot, "await_result")
+            .map_err(|e| format!("Failed to load future result: {}", e))
+    }
+
+    /// Bring a returned value to the exact LLVM type stored in the future:
+    /// struct literals come back as pointers, integer literals as i32
+    fn coerce_future_result(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        target: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        match (value, target) {
+            (BasicValueEnum::PointerValue(ptr), BasicTypeEnum::StructType(struct_ty)) => self
+                .builder
+                .build_load(struct_ty, ptr, "future_result_load")
+                .map_err(|e| format!("Failed to load struct result: {}", e)),
+            (BasicValueEnum::IntValue(int_val), BasicTypeEnum::IntType(int_ty))
+                if int_val.get_type().get_bit_width() != int_ty.get_bit_width() =>
+            {
                 self.builder
-                    .build_return(Some(&null_ptr))
-                    .map_err(|e| format!("Failed to build wrapper return: {}", e))?;
-            } else {
+                    .build_int_cast(int_val, int_ty, "future_result_cast")
+                    .map(Into::into)
+                    .map_err(|e| format!("Failed to cast result: {}", e))
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Record a yield emitted by `await` / `async_sleep`: snapshot which live-across-await
+    /// locals are in scope so emit_async_spills can save and restore them around it.
+    /// `extra_locals` are compiler temporaries (e.g. the awaited future) that must
+    /// survive the yield as well.
+    pub(crate) fn record_async_yield(
+        &mut self,
+        yield_ret: InstructionValue<'ctx>,
+        resume_block: BasicBlock<'ctx>,
+        extra_locals: &[(PointerValue<'ctx>, BasicTypeEnum<'ctx>)],
+    ) {
+        let entry = self
+            .current_async_resume_fn
+            .and_then(|resume_fn| resume_fn.get_first_basic_block());
+        let Some(async_context) = self.async_context.as_mut() else {
+            return;
+        };
+
+        let mut locals = Vec::new();
+        for name in &async_context.live_across_await {
+            let (Some(ptr), Some(ty)) = (self.variables.get(name), self.variable_types.get(name))
+            else {
+                continue;
+            };
+            // Only entry-block allocas can be reloaded: they dominate every resume block.
+            // Parameters already live in the state struct.
+            let is_entry_alloca = ptr.as_instruction().is_some_and(|inst| {
+                inst.get_opcode() == InstructionOpcode::Alloca && inst.get_parent() == entry
+            });
+            if is_entry_alloca && !locals.iter().any(|(p, _)| p == ptr) {
+                locals.push((*ptr, *ty));
+            }
+        }
+        for (ptr, ty) in extra_locals {
+            if !locals.iter().any(|(p, _)| p == ptr) {
+                locals.push((*ptr, *ty));
+            }
+        }
+
+        async_context.yield_points.push(AsyncYieldPoint {
+            yield_ret,
+            resume_block,
+            locals,
+        });
+    }
+
+    /// Lay out the final `<fn>_AsyncState` (state, params, one slot per spilled local)
+    /// and emit the spill stores before each yield and reloads at each resume block.
+    /// The prefix fields are unchanged, so GEPs emitted against the prefix stay valid.
+    fn emit_async_spills(
+        &mut self,
+        state_struct_name: &str,
+        prefix_fields: &[BasicTypeEnum<'ctx>],
+        state_ptr: PointerValue<'ctx>,
+        yield_points: &[AsyncYieldPoint<'ctx>],
+    ) -> Result<StructType<'ctx>, String> {
+        // Assign one state slot per distinct alloca, in first-yield order
+        let mut spill_slots: Vec<PointerValue<'ctx>> = Vec::new();
+        let mut fields = prefix_fields.to_vec();
+        for point in yield_points {
+            for (ptr, ty) in &point.locals {
+                if !spill_slots.contains(ptr) {
+                    spill_slots.push(*ptr);
+                    fields.push(*ty);
+                }
+            }
+        }
+
+        let state_struct_type = self.context.opaque_struct_type(state_struct_name);
+        state_struct_type.set_body(&fields, false);
+
+        if spill_slots.is_empty() {
+            return Ok(state_struct_type);
+        }
+        eprintln!(
+            "💾 {}: spilling {} local(s) across {} await point(s)",
+            state_struct_name,
+            spill_slots.len(),
+            yield_points.len()
+        );
+
+        let slot_index = |ptr: &PointerValue<'ctx>| -> Result<u32, String> {
+            spill_slots
+                .iter()
+                .position(|slot| slot == ptr)
+                .map(|i| (prefix_fields.len() + i) as u32)
+                .ok_or_else(|| "Spilled local has no state slot".to_string())
+        };
+
+        let saved_block = self.builder.get_insert_block();
+        for point in yield_points {
+            // Spill: alloca -> state, right before `ret CORO_STATUS_YIELDED`
+            self.builder.position_before(&point.yield_ret);
+            for (ptr, ty) in &point.locals {
+                let value = self
+                    .builder
+                    .build_load(*ty, *ptr, "spill_load")
+                    .map_err(|e| format!("Failed to load local for spill: {}", e))?;
+                let slot = self
+                    .builder
+                    .build_struct_gep(state_struct_type, state_ptr, slot_index(ptr)?, "spill_slot")
+                    .map_err(|e| format!("Failed to get spill slot: {}", e))?;
                 self.builder
-                    .build_return(None)
-                    .map_err(|e| format!("Failed to build wrapper return: {}", e))?;
+                    .build_store(slot, value)
+                    .map_err(|e| format!("Failed to spill local: {}", e))?;
+            }
+
+            // Reload: state -> alloca, at the top of the resume block
+            match point.resume_block.get_first_instruction() {
+                Some(first) => self.builder.position_before(&first),
+                None => self.builder.position_at_end(point.resume_block),
+            }
+            for (ptr, ty) in &point.locals {
+                let slot = self
+                    .builder
+                    .build_struct_gep(
+                        state_struct_type,
+                        state_ptr,
+                        slot_index(ptr)?,
+                        "reload_slot",
+                    )
+                    .map_err(|e| format!("Failed to get reload slot: {}", e))?;
+                let value = self
+                    .builder
+                    .build_load(*ty, slot, "reload")
+                    .map_err(|e| format!("Failed to reload spilled local: {}", e))?;
+                self.builder
+                    .build_store(*ptr, value)
+                    .map_err(|e| format!("Failed to restore local: {}", e))?;
             }
         }
-        Ok(())
+        if let Some(block) = saved_block {
+            self.builder.position_at_end(block);
+        }
+
+        Ok(state_struct_type)
+    }
+
+    /// Declare one of the `vex_future_*` functions from vex_async.c
+    pub(crate) fn get_or_declare_future_fn(
+        &mut self,
+        name: &str,
+    ) -> Result<FunctionValue<'ctx>, String> {
+        if let Some(func) = self.module.get_function(name) {
+            return Ok(func);
+        }
+
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let void_type = self.context.void_type();
+        let fn_type = match name {
+            // VexFuture* vex_future_new(uint64_t size);
+            "vex_future_new" => ptr_type.fn_type(&[self.context.i64_type().into()], false),
+            // void* vex_future_result(VexFuture* future);
+            "vex_future_result" => ptr_type.fn_type(&[ptr_type.into()], false),
+            // bool vex_future_is_ready(VexFuture* future);
+            "vex_future_is_ready" => self.context.bool_type().fn_type(&[ptr_type.into()], false),
+            // void vex_future_complete / vex_future_wait / vex_future_free(VexFuture* future);
+            "vex_future_complete" | "vex_future_wait" | "vex_future_free" => {
+                void_type.fn_type(&[ptr_type.into()], false)
+            }
+            _ => return Err(format!("Unknown future runtime function: {}", name)),
+        };
+        Ok(self.module.add_function(name, fn_type, None))
     }
 
     pub(crate) fn get_or_declare_malloc(&mut self) -> FunctionValue<'ctx> {
@@ -369,6 +837,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.module.add_function("malloc", malloc_type, None)
     }
 
+    pub(crate) fn get_or_declare_free(&mut self) -> FunctionValue<'ctx> {
+        if let Some(free) = self.module.get_function("free") {
+            return free;
+        }
+
+        let i8_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let free_type = self.context.void_type().fn_type(&[i8_ptr.into()], false);
+        self.module.add_function("free", free_type, None)
+    }
+
     pub(crate) fn get_or_declare_worker_await(&mut self) -> FunctionValue<'ctx> {
         if let Some(worker_await) = self.module.get_function("worker_await_after") {
             return worker_await;
@@ -441,4 +919,115 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.module
             .add_function("runtime_destroy", destroy_type, None)
     }
+
+    pub(crate) fn get_or_declare_runtime_auto_shutdown(&mut self) -> FunctionValue<'ctx> {
+        if let Some(auto_shutdown) = self.module.get_function("runtime_enable_auto_shutdown") {
+            return auto_shutdown;
+        }
+
+        // void runtime_enable_auto_shutdown(Runtime* rt, bool enabled);
+        let runtime_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let bool_type = self.context.bool_type();
+        let void_type = self.context.void_type();
+
+        let auto_shutdown_type = void_type.fn_type(&[runtime_ptr.into(), bool_type.into()], false);
+        self.module
+            .add_function("runtime_enable_auto_shutdown", auto_shutdown_type, None)
+    }
+
+    pub(crate) fn get_or_declare_runtime_run_background(&mut self) -> FunctionValue<'ctx> {
+        if let Some(run) = self.module.get_function("runtime_run_background") {
+            return run;
+        }
+
+        // void runtime_run_background(Runtime* runtime);
+        let runtime_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let void_type = self.context.void_type();
+
+        let run_type = void_type.fn_type(&[runtime_ptr.into()], false);
+        self.module
+            .add_function("runtime_run_background", run_type, None)
+    }
+
+    pub(crate) fn get_or_declare_runtime_join(&mut self) -> FunctionValue<'ctx> {
+        if let Some(join) = self.module.get_function("runtime_join") {
+            return join;
+        }
+
+        // void runtime_join(Runtime* runtime);
+        let runtime_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let void_type = self.context.void_type();
+
+        let join_type = void_type.fn_type(&[runtime_ptr.into()], false);
+        self.module.add_function("runtime_join", join_type, None)
+    }
+
+    pub(crate) fn get_or_declare_worker_spawn_local(&mut self) -> FunctionValue<'ctx> {
+        if let Some(spawn) = self.module.get_function("worker_spawn_local") {
+            return spawn;
+        }
+
+        // void worker_spawn_local(WorkerContext* context, coro_resume_func resume_fn, void* coro_data);
+        let worker_ctx_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let fn_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let void_ptr = self.context.ptr_type(inkwell::AddressSpace::default());
+        let void_type = self.context.void_type();
+
+        let spawn_type = void_type.fn_type(
+            &[worker_ctx_ptr.into(), fn_ptr.into(), void_ptr.into()],
+            false,
+        );
+        self.module
+            .add_function("worker_spawn_local", spawn_type, None)
+    }
+
+    /// Wait for all spawned tasks before main() returns, then destroy the runtime.
+    /// Emitted ahead of main's own scope cleanup so tasks never see dropped locals.
+    pub(crate) fn emit_main_runtime_drain(&mut self) -> Result<(), String> {
+        let global_runtime_var = self
+            .global_runtime
+            .ok_or("global_runtime not initialized")?;
+
+        // Runtime* rt = __vex_global_runtime;
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let runtime_ptr = self
+            .builder
+            .build_load(ptr_type, global_runtime_var, "runtime_load")
+            .map_err(|e| format!("Failed to load runtime: {}", e))?
+            .into_pointer_value();
+
+        if self.runtime_in_background {
+            // Workers are already running: let them exit once the queues are empty
+            let auto_shutdown = self.get_or_declare_runtime_auto_shutdown();
+            self.builder
+                .build_call(
+                    auto_shutdown,
+                    &[
+                        runtime_ptr.into(),
+                        self.context.bool_type().const_int(1, false).into(),
+                    ],
+                    "",
+                )
+                .map_err(|e| format!("Failed to call runtime_enable_auto_shutdown: {}", e))?;
+
+            let runtime_join = self.get_or_declare_runtime_join();
+            self.builder
+                .build_call(runtime_join, &[runtime_ptr.into()], "join_runtime")
+                .map_err(|e| format!("Failed to call runtime_join: {}", e))?;
+        } else {
+            // void runtime_run(Runtime* runtime);
+            let runtime_run = self.get_or_declare_runtime_run();
+            self.builder
+                .build_call(runtime_run, &[runtime_ptr.into()], "run_runtime")
+                .map_err(|e| format!("Failed to call runtime_run: {}", e))?;
+        }
+
+        // void runtime_destroy(Runtime* runtime);
+        let runtime_destroy = self.get_or_declare_runtime_destroy();
+        self.builder
+            .build_call(runtime_destroy, &[runtime_ptr.into()], "destroy_runtime")
+            .map_err(|e| format!("Failed to call runtime_destroy: {}", e))?;
+
+        Ok(())
+    }
 }
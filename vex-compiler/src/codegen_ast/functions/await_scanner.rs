@@ -1,7 +1,10 @@
 // AST walker for counting await expressions before code generation
 // Required for pre-allocating state machine switch cases
+// Also computes which locals must survive a yield (spilled into the async state)
+// Generator `yield` suspends the same way, so it counts as an await point here
 
-use vex_ast::{Block, Expression, Statement};
+use std::collections::{BTreeSet, HashMap};
+use vex_ast::{Block, Expression, Pattern, Statement};
 
 /// Count total number of await expressions in a block (recursive)
 pub(crate) fn count_await_points(block: &Block) -> usize {
@@ -14,6 +17,172 @@ pub(crate) fn count_await_points(block: &Block) -> usize {
     count
 }
 
+/// True if the block contains a `go` statement at any nesting depth,
+/// including inside closures, async blocks and block expressions.
+/// Decides whether main() drives the runtime in the background.
+pub(crate) fn contains_go_statement(block: &Block) -> bool {
+    statements_contain_go(&block.statements)
+}
+
+fn statements_contain_go(statements: &[Statement]) -> bool {
+    statements.iter().any(statement_contains_go)
+}
+
+fn statement_contains_go(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Go { .. } => true,
+        Statement::Let { value, .. } | Statement::LetPattern { value, .. } => {
+            expression_contains_go(value)
+        }
+        Statement::Assign { target, value, .. }
+        | Statement::CompoundAssign { target, value, .. } => {
+            expression_contains_go(target) || expression_contains_go(value)
+        }
+        Statement::Return { value, .. } => value.as_ref().is_some_and(expression_contains_go),
+        Statement::Expression(expr) => expression_contains_go(expr),
+        Statement::If {
+            condition,
+            then_block,
+            elif_branches,
+            else_block,
+            ..
+        } => {
+            expression_contains_go(condition)
+                || contains_go_statement(then_block)
+                || elif_branches.iter().any(|(cond, block)| {
+                    expression_contains_go(cond) || contains_go_statement(block)
+                })
+                || else_block.as_ref().is_some_and(contains_go_statement)
+        }
+        Statement::For {
+            init,
+            condition,
+            post,
+            body,
+            ..
+        } => {
+            init.as_deref().is_some_and(statement_contains_go)
+                || condition.as_ref().is_some_and(expression_contains_go)
+                || post.as_deref().is_some_and(statement_contains_go)
+                || contains_go_statement(body)
+        }
+        Statement::While {
+            condition, body, ..
+        } => expression_contains_go(condition) || contains_go_statement(body),
+        Statement::ForIn { iterable, body, .. } => {
+            expression_contains_go(iterable) || contains_go_statement(body)
+        }
+        Statement::Loop { body, .. } | Statement::Unsafe { block: body, .. } => {
+            contains_go_statement(body)
+        }
+        Statement::Switch {
+            value,
+            cases,
+            default_case,
+            ..
+        } => {
+            value.as_ref().is_some_and(expression_contains_go)
+                || cases.iter().any(|case| {
+                    case.patterns.iter().any(expression_contains_go)
+                        || contains_go_statement(&case.body)
+                })
+                || default_case.as_ref().is_some_and(contains_go_statement)
+        }
+        Statement::Select {
+            cases,
+            default_case,
+            ..
+        } => {
+            cases
+                .iter()
+                .any(|case| expression_contains_go(&case.expr) || contains_go_statement(&case.body))
+                || default_case.as_ref().is_some_and(contains_go_statement)
+        }
+        Statement::Defer(inner) | Statement::Attributed { stmt: inner, .. } => {
+            statement_contains_go(inner)
+        }
+        Statement::Break { .. } | Statement::Continue { .. } => false,
+    }
+}
+
+fn expression_contains_go(expr: &Expression) -> bool {
+    let any = |exprs: &[Expression]| exprs.iter().any(expression_contains_go);
+    match expr {
+        Expression::IntLiteral(_)
+        | Expression::TypedIntLiteral { .. }
+        | Expression::BigIntLiteral(_)
+        | Expression::TypedBigIntLiteral { .. }
+        | Expression::FloatLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::FStringLiteral(_)
+        | Expression::BoolLiteral(_)
+        | Expression::Nil
+        | Expression::Ident(_) => false,
+        Expression::Binary { left, right, .. } => {
+            expression_contains_go(left) || expression_contains_go(right)
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Deref(expr)
+        | Expression::Reference { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Is { expr, .. }
+        | Expression::TryOp { expr }
+        | Expression::Typeof(expr)
+        | Expression::PostfixOp { expr, .. }
+        | Expression::ChannelReceive(expr)
+        | Expression::Await(expr)
+        | Expression::Yield(expr)
+        | Expression::ErrorNew(expr)
+        | Expression::New(expr) => expression_contains_go(expr),
+        Expression::Make { size, .. } => expression_contains_go(size),
+        Expression::Call { func, args, .. } => expression_contains_go(func) || any(args),
+        Expression::MethodCall { receiver, args, .. } => {
+            expression_contains_go(receiver) || any(args)
+        }
+        Expression::FieldAccess { object, .. } => expression_contains_go(object),
+        Expression::Index { object, index } => {
+            expression_contains_go(object) || expression_contains_go(index)
+        }
+        Expression::Array(elements)
+        | Expression::TupleLiteral(elements)
+        | Expression::EnumLiteral { data: elements, .. }
+        | Expression::TypeConstructor { args: elements, .. } => any(elements),
+        Expression::ArrayRepeat(value, count) => {
+            expression_contains_go(value) || expression_contains_go(count)
+        }
+        Expression::MapLiteral(pairs) => pairs
+            .iter()
+            .any(|(key, value)| expression_contains_go(key) || expression_contains_go(value)),
+        Expression::StructLiteral { fields, .. } => fields
+            .iter()
+            .any(|(_, value)| expression_contains_go(value)),
+        Expression::Range { start, end } | Expression::RangeInclusive { start, end } => {
+            start.as_deref().is_some_and(expression_contains_go)
+                || end.as_deref().is_some_and(expression_contains_go)
+        }
+        Expression::Match { value, arms, .. } => {
+            expression_contains_go(value)
+                || arms.iter().any(|arm| {
+                    arm.guard.as_ref().is_some_and(expression_contains_go)
+                        || expression_contains_go(&arm.body)
+                })
+        }
+        Expression::Block {
+            statements,
+            return_expr,
+        }
+        | Expression::AsyncBlock {
+            statements,
+            return_expr,
+        } => {
+            statements_contain_go(statements)
+                || return_expr.as_deref().is_some_and(expression_contains_go)
+        }
+        Expression::Launch { grid, args, .. } => any(grid) || any(args),
+        Expression::Closure { body, .. } => expression_contains_go(body),
+    }
+}
+
 /// Count await expressions in a single statement
 fn count_await_in_statement(stmt: &Statement) -> usize {
     match stmt {
@@ -90,7 +259,9 @@ fn count_await_in_statement(stmt: &Statement) -> usize {
             span_id: _,
             variable,
         } => count_await_in_expression(iterable) + count_await_points(body),
-        Statement::Defer(stmt) => count_await_in_statement(stmt),
+        Statement::Defer(stmt) | Statement::Attributed { stmt, .. } => {
+            count_await_in_statement(stmt)
+        }
         Statement::Loop { span_id: _, body } => count_await_points(body),
         Statement::Switch {
             span_id: _,
@@ -113,8 +284,21 @@ fn count_await_in_statement(stmt: &Statement) -> usize {
             }
             count
         }
-        Statement::Select { span_id: _, cases } => {
-            cases.iter().map(|c| count_await_points(&c.body)).sum()
+        Statement::Select {
+            span_id: _,
+            cases,
+            default_case,
+        } => {
+            let mut count: usize = cases
+                .iter()
+                .map(|c| count_await_in_expression(&c.expr) + count_await_points(&c.body))
+                .sum();
+            match default_case {
+                Some(default) => count += count_await_points(default),
+                // A blocking select yields while no case is ready
+                None => count += 1,
+            }
+            count
         }
         Statement::Go {
             span_id: _,
@@ -131,7 +315,9 @@ fn count_await_in_statement(stmt: &Statement) -> usize {
 fn count_await_in_expression(expr: &Expression) -> usize {
     match expr {
         // ⭐ KEY: Await expression found!
-        Expression::Await(inner) => 1 + count_await_in_expression(inner),
+        Expression::Await(inner) | Expression::Yield(inner) => {
+            1 + count_await_in_expression(inner)
+        }
 
         // Literals - no await
         Expression::IntLiteral(_)
@@ -219,7 +405,7 @@ fn count_await_in_expression(expr: &Expression) -> usize {
         }
 
         // Control flow
-        Expression::Match { value, arms } => {
+        Expression::Match { value, arms, .. } => {
             let mut count = count_await_in_expression(value);
             for arm in arms {
                 count += count_await_in_expression(&arm.body);
@@ -259,6 +445,7 @@ fn count_await_in_expression(expr: &Expression) -> usize {
 
         // Other expressions
         Expression::Cast { expr, .. } => count_await_in_expression(expr),
+        Expression::Is { expr, .. } => count_await_in_expression(expr),
         Expression::TryOp { expr } => count_await_in_expression(expr),
         Expression::Typeof(expr) => count_await_in_expression(expr),
         Expression::PostfixOp { expr, .. } => count_await_in_expression(expr),
@@ -291,6 +478,355 @@ fn count_await_in_expression(expr: &Expression) -> usize {
     }
 }
 
+/// Names of locals that are read after an await point which follows their
+/// declaration. The coroutine returns to the scheduler at every await, so these
+/// values are spilled into `<fn>_AsyncState` and reloaded when it resumes.
+///
+/// Conservative: branches are walked in source order and loop bodies containing
+/// an await are walked twice, so reads reached through the back edge are seen.
+pub(crate) fn locals_live_across_await(block: &Block) -> BTreeSet<String> {
+    let mut liveness = AwaitLiveness::default();
+    liveness.visit_block(block);
+
+    // Deferred statements run at function exit, after every await
+    let deferred = std::mem::take(&mut liveness.deferred);
+    for stmt in &deferred {
+        liveness.visit_statement(stmt);
+    }
+
+    liveness.live
+}
+
+#[derive(Default)]
+struct AwaitLiveness {
+    /// Await points passed so far, in evaluation order
+    awaits: usize,
+    /// Local name -> value of `awaits` at its (latest) declaration
+    declared: HashMap<String, usize>,
+    /// Locals read after an await that follows their declaration
+    live: BTreeSet<String>,
+    deferred: Vec<Statement>,
+}
+
+impl AwaitLiveness {
+    fn declare(&mut self, name: &str) {
+        self.declared.insert(name.to_string(), self.awaits);
+    }
+
+    fn declare_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Ident(name)
+            | Pattern::Type {
+                binding: Some(name),
+                ..
+            } => self.declare(name),
+            Pattern::Tuple(items) | Pattern::Or(items) => {
+                for item in items {
+                    self.declare_pattern(item);
+                }
+            }
+            Pattern::Struct { fields, .. } => {
+                for (_, field) in fields {
+                    self.declare_pattern(field);
+                }
+            }
+            Pattern::Enum { data, .. } => {
+                for item in data {
+                    self.declare_pattern(item);
+                }
+            }
+            Pattern::Array { elements, rest } => {
+                for item in elements {
+                    self.declare_pattern(item);
+                }
+                if let Some(rest) = rest {
+                    self.declare(rest);
+                }
+            }
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Type { binding: None, .. } => {}
+        }
+    }
+
+    fn use_local(&mut self, name: &str) {
+        if let Some(&declared_at) = self.declared.get(name) {
+            if self.awaits > declared_at {
+                self.live.insert(name.to_string());
+            }
+        }
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        for stmt in &block.statements {
+            self.visit_statement(stmt);
+        }
+    }
+
+    /// Visit a loop body once, or twice if an await inside it can reach the
+    /// top of the next iteration.
+    fn visit_loop(&mut self, has_await: bool, mut visit: impl FnMut(&mut Self)) {
+        visit(self);
+        if has_await {
+            visit(self);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Let { name, value, .. } => {
+                self.visit_expression(value);
+                self.declare(name);
+            }
+            Statement::LetPattern { pattern, value, .. } => {
+                self.visit_expression(value);
+                self.declare_pattern(pattern);
+            }
+            Statement::Assign { target, value, .. }
+            | Statement::CompoundAssign { target, value, .. } => {
+                self.visit_expression(value);
+                self.visit_expression(target);
+            }
+            Statement::Return {
+                value: Some(expr), ..
+            }
+            | Statement::Expression(expr)
+            | Statement::Go { expr, .. } => self.visit_expression(expr),
+            Statement::Return { value: None, .. }
+            | Statement::Break { .. }
+            | Statement::Continue { .. } => {}
+            Statement::Defer(inner) => self.deferred.push((**inner).clone()),
+            Statement::Attributed { stmt, .. } => self.visit_statement(stmt),
+            Statement::If {
+                condition,
+                then_block,
+                elif_branches,
+                else_block,
+                ..
+            } => {
+                self.visit_expression(condition);
+                self.visit_block(then_block);
+                for (elif_cond, elif_block) in elif_branches {
+                    self.visit_expression(elif_cond);
+                    self.visit_block(elif_block);
+                }
+                if let Some(else_b) = else_block {
+                    self.visit_block(else_b);
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                let has_await = count_await_in_expression(condition) + count_await_points(body) > 0;
+                self.visit_loop(has_await, |this| {
+                    this.visit_expression(condition);
+                    this.visit_block(body);
+                });
+            }
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                if let Some(init_stmt) = init {
+                    self.visit_statement(init_stmt);
+                }
+                let has_await = condition.as_ref().map_or(0, count_await_in_expression)
+                    + post.as_deref().map_or(0, count_await_in_statement)
+                    + count_await_points(body)
+                    > 0;
+                self.visit_loop(has_await, |this| {
+                    if let Some(cond) = condition {
+                        this.visit_expression(cond);
+                    }
+                    this.visit_block(body);
+                    if let Some(post_stmt) = post {
+                        this.visit_statement(post_stmt);
+                    }
+                });
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            } => {
+                self.visit_expression(iterable);
+                self.visit_loop(count_await_points(body) > 0, |this| {
+                    this.declare(variable);
+                    this.visit_block(body);
+                });
+            }
+            Statement::Loop { body, .. } => {
+                self.visit_loop(count_await_points(body) > 0, |this| this.visit_block(body));
+            }
+            Statement::Switch {
+                value,
+                cases,
+                default_case,
+                ..
+            } => {
+                if let Some(v) = value {
+                    self.visit_expression(v);
+                }
+                for case in cases {
+                    for pattern in &case.patterns {
+                        self.visit_expression(pattern);
+                    }
+                    self.visit_block(&case.body);
+                }
+                if let Some(default) = default_case {
+                    self.visit_block(default);
+                }
+            }
+            Statement::Select {
+                cases,
+                default_case,
+                ..
+            } => {
+                for case in cases {
+                    self.visit_expression(&case.expr);
+                }
+                if default_case.is_none() {
+                    self.awaits += 1;
+                }
+                for case in cases {
+                    if let Some(var) = &case.var {
+                        self.declare(var);
+                    }
+                    self.visit_block(&case.body);
+                }
+                if let Some(default) = default_case {
+                    self.visit_block(default);
+                }
+            }
+            Statement::Unsafe { block, .. } => self.visit_block(block),
+        }
+    }
+
+    fn visit_expressions(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.visit_expression(expr);
+        }
+    }
+
+    /// Walk in evaluation order; must agree with count_await_in_expression on
+    /// what counts as an await point.
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Await(inner) | Expression::Yield(inner) => {
+                self.visit_expression(inner);
+                self.awaits += 1;
+            }
+            Expression::Ident(name) => self.use_local(name),
+            Expression::FStringLiteral(text) => {
+                // Interpolated names are only visible in the raw text
+                for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+                    self.use_local(word);
+                }
+            }
+            Expression::IntLiteral(_)
+            | Expression::TypedIntLiteral { .. }
+            | Expression::BigIntLiteral(_)
+            | Expression::TypedBigIntLiteral { .. }
+            | Expression::FloatLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::BoolLiteral(_)
+            | Expression::Nil => {}
+            Expression::Binary { left, right, .. } => {
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            Expression::Unary { expr, .. }
+            | Expression::Deref(expr)
+            | Expression::Reference { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::Is { expr, .. }
+            | Expression::TryOp { expr }
+            | Expression::Typeof(expr)
+            | Expression::PostfixOp { expr, .. }
+            | Expression::ErrorNew(expr)
+            | Expression::New(expr)
+            | Expression::ChannelReceive(expr) => self.visit_expression(expr),
+            Expression::Make { size, .. } => self.visit_expression(size),
+            Expression::Call { func, args, .. } => {
+                self.visit_expression(func);
+                self.visit_expressions(args);
+                if matches!(func.as_ref(), Expression::Ident(name) if name == "async_sleep") {
+                    self.awaits += 1;
+                }
+            }
+            Expression::MethodCall { receiver, args, .. } => {
+                self.visit_expression(receiver);
+                self.visit_expressions(args);
+            }
+            Expression::FieldAccess { object, .. } => self.visit_expression(object),
+            Expression::Index { object, index } => {
+                self.visit_expression(object);
+                self.visit_expression(index);
+            }
+            Expression::Array(elements)
+            | Expression::TupleLiteral(elements)
+            | Expression::EnumLiteral { data: elements, .. }
+            | Expression::TypeConstructor { args: elements, .. } => {
+                self.visit_expressions(elements)
+            }
+            Expression::ArrayRepeat(value, count) => {
+                self.visit_expression(value);
+                self.visit_expression(count);
+            }
+            Expression::MapLiteral(pairs) => {
+                for (key, value) in pairs {
+                    self.visit_expression(key);
+                    self.visit_expression(value);
+                }
+            }
+            Expression::StructLiteral { fields, .. } => {
+                for (_, value) in fields {
+                    self.visit_expression(value);
+                }
+            }
+            Expression::Range { start, end } | Expression::RangeInclusive { start, end } => {
+                if let Some(s) = start {
+                    self.visit_expression(s);
+                }
+                if let Some(e) = end {
+                    self.visit_expression(e);
+                }
+            }
+            Expression::Match { value, arms, .. } => {
+                self.visit_expression(value);
+                for arm in arms {
+                    if let Some(guard) = &arm.guard {
+                        self.visit_expression(guard);
+                    }
+                    self.visit_expression(&arm.body);
+                }
+            }
+            Expression::Block {
+                statements,
+                return_expr,
+            }
+            | Expression::AsyncBlock {
+                statements,
+                return_expr,
+            } => {
+                for stmt in statements {
+                    self.visit_statement(stmt);
+                }
+                if let Some(ret) = return_expr {
+                    self.visit_expression(ret);
+                }
+            }
+            Expression::Launch { grid, args, .. } => {
+                self.visit_expressions(grid);
+                self.visit_expressions(args);
+            }
+            Expression::Closure { body, .. } => self.visit_expression(body),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,6 +844,150 @@ mod tests {
         assert_eq!(count_await_in_expression(&outer), 2);
     }
 
+    #[test]
+    fn test_contains_nested_go() {
+        let go_stmt = Statement::Go {
+            span_id: None,
+            expr: Expression::Call {
+                span_id: None,
+                func: Box::new(Expression::Ident("worker".to_string())),
+                type_args: vec![],
+                args: vec![],
+            },
+        };
+        let body = Block {
+            span_id: None,
+            statements: vec![Statement::Loop {
+                span_id: None,
+                body: Block {
+                    span_id: None,
+                    statements: vec![go_stmt],
+                },
+            }],
+        };
+        assert!(contains_go_statement(&body));
+        assert!(!contains_go_statement(&Block {
+            span_id: None,
+            statements: vec![],
+        }));
+    }
+
+    #[test]
+    fn test_contains_go_inside_closure() {
+        // let spawn = || { go worker(); };
+        let closure = Expression::Closure {
+            params: vec![],
+            return_type: None,
+            body: Box::new(Expression::Block {
+                statements: vec![Statement::Go {
+                    span_id: None,
+                    expr: Expression::Call {
+                        span_id: None,
+                        func: Box::new(ident("worker")),
+                        type_args: vec![],
+                        args: vec![],
+                    },
+                }],
+                return_expr: None,
+            }),
+            capture_mode: vex_ast::CaptureMode::Infer,
+        };
+        assert!(contains_go_statement(&block(vec![let_stmt("spawn", closure)])));
+        assert!(!contains_go_statement(&block(vec![let_stmt("x", ident("y"))])));
+    }
+
+    fn ident(name: &str) -> Expression {
+        Expression::Ident(name.to_string())
+    }
+
+    fn let_stmt(name: &str, value: Expression) -> Statement {
+        Statement::Let {
+            is_mutable: false,
+            name: name.to_string(),
+            ty: None,
+            value,
+        }
+    }
+
+    fn await_stmt(future: &str) -> Statement {
+        Statement::Expression(Expression::Await(Box::new(ident(future))))
+    }
+
+    fn block(statements: Vec<Statement>) -> Block {
+        Block {
+            span_id: None,
+            statements,
+        }
+    }
+
+    #[test]
+    fn test_live_across_await() {
+        // let a = 1; let b = 2; b; await f; let c = a; c;
+        let body = block(vec![
+            let_stmt("a", Expression::IntLiteral(1)),
+            let_stmt("b", Expression::IntLiteral(2)),
+            Statement::Expression(ident("b")),
+            await_stmt("f"),
+            let_stmt("c", ident("a")),
+            Statement::Expression(ident("c")),
+        ]);
+        let live = locals_live_across_await(&body);
+        assert_eq!(live.into_iter().collect::<Vec<_>>(), vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_live_across_await_loop_back_edge() {
+        // let total = 0; loop { total; await f; }
+        let body = block(vec![
+            let_stmt("total", Expression::IntLiteral(0)),
+            Statement::Loop {
+                span_id: None,
+                body: block(vec![Statement::Expression(ident("total")), await_stmt("f")]),
+            },
+        ]);
+        assert!(locals_live_across_await(&body).contains("total"));
+    }
+
+    #[test]
+    fn test_yield_is_a_suspension_point() {
+        // let! n = 0; loop { yield n; }
+        let body = block(vec![
+            let_stmt("n", Expression::IntLiteral(0)),
+            Statement::Loop {
+                span_id: None,
+                body: block(vec![Statement::Expression(Expression::Yield(Box::new(
+                    ident("n"),
+                )))]),
+            },
+        ]);
+        assert_eq!(count_await_points(&body), 1);
+        assert!(locals_live_across_await(&body).contains("n"));
+    }
+
+    #[test]
+    fn test_blocking_select_is_a_suspension_point() {
+        // let n = 0; select { case v = <-ch: { n; } }   and the same with a default arm
+        let select = |default_case: Option<Block>| Statement::Select {
+            span_id: None,
+            cases: vec![vex_ast::SelectCase {
+                var: Some("v".to_string()),
+                expr: Expression::ChannelReceive(Box::new(ident("ch"))),
+                body: block(vec![Statement::Expression(ident("n"))]),
+            }],
+            default_case,
+        };
+        let blocking = block(vec![let_stmt("n", Expression::IntLiteral(0)), select(None)]);
+        assert_eq!(count_await_points(&blocking), 1);
+        assert!(locals_live_across_await(&blocking).contains("n"));
+
+        let polling = block(vec![
+            let_stmt("n", Expression::IntLiteral(0)),
+            select(Some(block(vec![]))),
+        ]);
+        assert_eq!(count_await_points(&polling), 0);
+        assert!(locals_live_across_await(&polling).is_empty());
+    }
+
     #[test]
     fn test_count_binary_with_await() {
         let left = Expression::Await(Box::new(Expression::Ident("f1".to_string())));
@@ -1,5 +1,6 @@
 // src/codegen/functions/compile.rs
 use super::super::*;
+use super::move_scanner::moved_locals;
 use crate::{debug_log, debug_println};
 use inkwell::values::BasicValueEnum;
 
@@ -47,6 +48,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return result;
         }
 
+        // Generators compile to the same state machine as async functions
+        if let Some(item_type) = func.return_type.as_ref().and_then(Type::generator_item) {
+            let previous_return_type = self.current_function_return_type.clone();
+            self.current_function_return_type = func.return_type.clone();
+            let result = self.compile_generator_function(func, item_type);
+            self.current_function_return_type = previous_return_type;
+            return result;
+        }
+
         let previous_return_type = self.current_function_return_type.clone();
         self.current_function_return_type = func.return_type.clone();
 
@@ -178,31 +188,34 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 .build_store(global_runtime, runtime_ptr)
                 .map_err(|e| format!("Failed to store runtime: {}", e))?;
 
-            // Enable auto-shutdown so runtime exits when all tasks are done
+            // Enable auto-shutdown so runtime exits when all tasks are done.
+            // With go statements the workers start now and must stay alive until
+            // main() finishes spawning; emit_main_runtime_drain re-enables it.
             // void runtime_enable_auto_shutdown(Runtime* rt, bool enabled)
-            let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
-            let auto_shutdown_fn_type = self.context.void_type().fn_type(
-                &[
-                    ptr_type.into(),
-                    self.context.bool_type().into(),
-                ],
-                false,
-            );
-            let auto_shutdown_fn = self
-                .module
-                .add_function("runtime_enable_auto_shutdown", auto_shutdown_fn_type, None);
-            
+            let auto_shutdown_fn = self.get_or_declare_runtime_auto_shutdown();
+            let auto_shutdown = !self.runtime_in_background;
             self.builder
                 .build_call(
                     auto_shutdown_fn,
                     &[
                         runtime_ptr.into(),
-                        self.context.bool_type().const_int(1, false).into(),
+                        self.context
+                            .bool_type()
+                            .const_int(auto_shutdown as u64, false)
+                            .into(),
                     ],
                     "",
                 )
                 .map_err(|e| format!("Failed to call runtime_enable_auto_shutdown: {}", e))?;
 
+            if self.runtime_in_background {
+                // void runtime_run_background(Runtime* runtime);
+                let run_background = self.get_or_declare_runtime_run_background();
+                self.builder
+                    .build_call(run_background, &[runtime_ptr.into()], "")
+                    .map_err(|e| format!("Failed to call runtime_run_background: {}", e))?;
+            }
+
             eprintln!("✅ Runtime initialized and stored in global");
         }
 
@@ -213,6 +226,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.variable_concrete_types.clear(); // ⭐ CRITICAL: Clear concrete types too
         self.function_params.clear();
         self.function_param_types.clear();
+        self.moved_locals = moved_locals(&func.body);
         
         eprintln!("🔧 [COMPILE_FUNCTION] Starting: {} (params: {})", func.name, func.params.len());
 
@@ -374,7 +388,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     }
                 };
 
-                if let Some(struct_name) = extract_struct_name(&param.ty) {
+                // Record intersections are tracked under their registered struct name
+                let struct_param_ty = match &param.ty {
+                    Type::Intersection(_) => self.resolve_type(&param.ty),
+                    other => other.clone(),
+                };
+                if let Some(struct_name) = extract_struct_name(&struct_param_ty) {
                     if self.struct_defs.contains_key(&struct_name)
                         || self.struct_ast_defs.contains_key(&struct_name)
                     {
@@ -383,6 +402,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     }
                 }
 
+                // Builtin Range<T> values dispatch to the runtime range methods
+                if let Some(inclusive) = self.range_kind(&param.ty) {
+                    let range_name = if inclusive { "RangeInclusive" } else { "Range" };
+                    self.variable_struct_names
+                        .insert(param.name.clone(), range_name.to_string());
+                }
+
                 match &param.ty {
                     Type::Generic { name, type_args } => {
                         if let Ok(mangled_name) = self.instantiate_generic_struct(name, type_args) {
@@ -390,6 +416,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                                 .insert(param.name.clone(), mangled_name);
                         }
                     }
+                    // &[T] / &[T]! parameters dispatch len/get/is_empty as Slice methods
+                    Type::Slice(_, _) => {
+                        self.variable_struct_names
+                            .insert(param.name.clone(), "Slice".to_string());
+                    }
                     _ => {}
                 }
             }
@@ -405,6 +436,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         if let Some(current_block) = self.builder.get_insert_block() {
             if current_block.get_terminator().is_none() {
+                // ⭐ ASYNC: main() waits for the runtime to drain before its own cleanup
+                if func.name == "main" && self.global_runtime.is_some() {
+                    eprintln!("🔄 Draining async runtime at end of main()");
+                    self.emit_main_runtime_drain()?;
+                }
                 self.pop_scope()?;
                 self.execute_deferred_statements()?;
             }
@@ -421,27 +457,6 @@ impl<'ctx> ASTCodeGen<'ctx> {
                         func.name
                     );
                 } else {
-                    // ⭐ ASYNC: If this is main() with runtime, add runtime_run() and runtime_destroy()
-                    if func.name == "main" && self.global_runtime.is_some() {
-                        eprintln!("🔄 Adding runtime_run() and runtime_destroy() to main()");
-
-                        let runtime_ptr = self
-                            .global_runtime
-                            .ok_or("global_runtime not initialized")?;
-
-                        // void runtime_run(Runtime* runtime);
-                        let runtime_run = self.get_or_declare_runtime_run();
-                        self.builder
-                            .build_call(runtime_run, &[runtime_ptr.into()], "run_runtime")
-                            .map_err(|e| format!("Failed to call runtime_run: {}", e))?;
-
-                        // void runtime_destroy(Runtime* runtime);
-                        let runtime_destroy = self.get_or_declare_runtime_destroy();
-                        self.builder
-                            .build_call(runtime_destroy, &[runtime_ptr.into()], "destroy_runtime")
-                            .map_err(|e| format!("Failed to call runtime_destroy: {}", e))?;
-                    }
-
                     // ✅ FIX: Void functions should always get implicit return, not unreachable
                     let is_void_function = func.return_type.is_none()
                         || matches!(func.return_type.as_ref(), Some(Type::Nil));
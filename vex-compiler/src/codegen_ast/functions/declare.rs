@@ -35,6 +35,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
+        // Generic signatures are checked once instantiated with concrete arguments
+        if func.type_params.is_empty() {
+            let signature = func_for_decl.params.iter().map(|p| &p.ty);
+            for ty in signature.chain(func_for_decl.return_type.as_ref()) {
+                self.check_array_lengths(ty)
+                    .map_err(|e| format!("Function '{}': {}", func.name, e))?;
+            }
+        }
+
         let fn_name = if func_for_decl.is_static {
             let type_name = func_for_decl
                 .static_type
@@ -174,7 +183,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
             debug_log!("🔍 Function {} return type check: {:?}", fn_name, ty);
 
             // ⭐ SPECIAL: Type::Nil should be treated as void (no return value)
-            if matches!(ty, Type::Nil) {
+            if matches!(ty, Type::Nil) && !effective_is_async {
                 debug_log!("🟢 Function {} return type: nil (void)", mangled_llvm_name);
                 let fn_type = self.context.void_type().fn_type(&param_types, is_variadic);
                 let fn_val = self
@@ -188,6 +197,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                 // Store in functions map using the mangled name
                 self.functions.insert(mangled_llvm_name.clone(), fn_val);
+                self.apply_function_attributes(func, fn_val);
                 return Ok(fn_val);
             }
 
@@ -278,6 +288,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     fn_type,
                     Some(inkwell::module::Linkage::External),
                 )
+            } else if effective_is_async {
+                // Async wrapper still hands back a Future<()> to await on
+                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+                let fn_type = ptr_type.fn_type(&param_types, is_variadic);
+                self.module
+                    .add_function(&mangled_llvm_name, fn_type, linkage)
             } else {
                 eprintln!("🟢 Function {} return type: void", fn_name);
                 let fn_type = self.context.void_type().fn_type(&param_types, is_variadic);
@@ -289,7 +305,28 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Register in functions map with BOTH mangled name AND base name
         // This enables both direct calls (double_i32_1) and function pointers (double)
         self.register_function(&func_for_decl.name, &mangled_llvm_name, fn_val);
+        self.apply_function_attributes(func, fn_val);
 
         Ok(fn_val)
     }
+
+    /// Map #[inline] and #[cold] to LLVM function attributes.
+    /// Malformed or misplaced attributes were already reported by the type checker.
+    pub(crate) fn apply_function_attributes(&self, func: &Function, fn_val: FunctionValue<'ctx>) {
+        for attr in &func.attributes {
+            let kind = match (attr.name.as_str(), attr.args.as_slice()) {
+                ("inline", []) => "inlinehint",
+                ("inline", [AttributeArg::Ident(mode)]) if mode == "always" => "alwaysinline",
+                ("inline", [AttributeArg::Ident(mode)]) if mode == "never" => "noinline",
+                ("cold", []) => "cold",
+                _ => continue,
+            };
+            let attribute = self.context.create_enum_attribute(
+                inkwell::attributes::Attribute::get_named_enum_kind_id(kind),
+                0,
+            );
+            fn_val.add_attribute(inkwell::attributes::AttributeLoc::Function, attribute);
+            eprintln!("🏷️  {}: #[{}] → {}", func.name, attr.name, kind);
+        }
+    }
 }
@@ -3,3 +3,4 @@ pub(crate) mod asynchronous;
 pub(crate) mod await_scanner;
 pub(crate) mod compile;
 pub(crate) mod declare;
+pub(crate) mod move_scanner;
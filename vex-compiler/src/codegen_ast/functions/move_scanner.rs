@@ -0,0 +1,866 @@
+// AST walker for finding locals whose value is moved out of their binding
+// A make/new/error.new binding that is moved must not be released once the
+// destination (another binding, a callee, a struct, a task) owns it
+
+use std::collections::{HashMap, HashSet};
+use vex_ast::{Block, Expression, Pattern, Statement, Type};
+
+/// What scope exit does with a make/new/error.new binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MoveState {
+    /// Never moved: always released
+    Kept,
+    /// Moved on some path, or returned by name: released while its drop flag is set
+    MaybeMoved,
+    /// Captured by a closure, async block or `go`, or moved where no drop flag
+    /// follows it: never released here
+    Escaped,
+}
+
+/// Moves out of the make/new/error.new locals of one function body
+///
+/// Bindings are scoped, so a shadowing `let` is tracked on its own. A binding
+/// declared in a statement block is keyed by its `let` initializer; the
+/// statements that move it clear its drop flag when they run, so a value moved
+/// on one branch is still released on the others.
+#[derive(Debug, Default)]
+pub(crate) struct MovedLocals {
+    bindings: HashMap<*const Expression, MoveState>,
+    /// Bindings each statement moves out of, including inside its expressions
+    /// but not inside the statement blocks it runs (those are statements too)
+    statements: HashMap<*const Statement, Vec<String>>,
+    /// Moved bindings declared inside expressions (match arms, block expressions,
+    /// closures): codegen may compile those from a copy, so no flag follows them
+    nested: HashSet<String>,
+}
+
+impl MovedLocals {
+    /// State of the binding `name` initialized by `value`, the expression of its `let`
+    pub(crate) fn state(&self, name: &str, value: &Expression) -> MoveState {
+        match self.bindings.get(&(value as *const Expression)) {
+            Some(state) => *state,
+            None if self.nested.contains(name) => MoveState::Escaped,
+            None => MoveState::Kept,
+        }
+    }
+
+    /// Bindings whose value `stmt` moves out; `return name;` is not among them
+    /// (control_flow hands a directly returned binding to the caller itself)
+    pub(crate) fn moved_by(&self, stmt: &Statement) -> &[String] {
+        self.statements
+            .get(&(stmt as *const Statement))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn moved_locals(block: &Block) -> MovedLocals {
+    let mut scanner = MoveScanner::default();
+    scanner.visit_block(block);
+    scanner.result
+}
+
+#[derive(Clone, Copy)]
+struct Owner {
+    value: *const Expression,
+    /// Declared inside an expression rather than a statement block
+    nested: bool,
+    /// new(x) of a non-struct is a `&T`: passing it to a call copies the reference
+    copy_ref: bool,
+}
+
+#[derive(Default)]
+struct MoveScanner {
+    result: MovedLocals,
+    /// Visible bindings, innermost scope last; None for bindings that own nothing
+    scopes: Vec<Vec<(String, Option<Owner>)>>,
+    /// Statement from a statement block whose expressions are being scanned
+    statement: Option<*const Statement>,
+    /// Depth of expressions holding statements (match arms, blocks, closures)
+    nested: usize,
+    /// Scope depth where the innermost closure, async block or `go` begins:
+    /// every binding from below it that is mentioned is captured by move
+    capture_floor: Option<usize>,
+}
+
+impl MoveScanner {
+    fn visit_block(&mut self, block: &Block) {
+        self.scopes.push(Vec::new());
+        for stmt in &block.statements {
+            self.visit_statement(stmt);
+        }
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &str, ty: Option<&Type>, value: Option<&Expression>) {
+        let owner = value.filter(|v| is_owning(v)).map(|value| Owner {
+            value,
+            nested: self.nested > 0,
+            copy_ref: matches!(ty, Some(Type::Reference(..)))
+                || matches!(value, Expression::New(inner)
+                    if !matches!(inner.as_ref(), Expression::StructLiteral { .. })),
+        });
+        if let Some(owner) = owner.filter(|owner| !owner.nested) {
+            self.result.bindings.insert(owner.value, MoveState::Kept);
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name.to_string(), owner));
+        }
+    }
+
+    /// Innermost binding of `name` with the depth of its scope
+    fn resolve(&self, name: &str) -> Option<(usize, Option<Owner>)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| {
+                scope
+                    .iter()
+                    .rev()
+                    .find(|(bound, _)| bound == name)
+                    .map(|(_, owner)| (depth, *owner))
+            })
+    }
+
+    /// Owner of `name` unless a closure, async block or `go` captures it here
+    fn uncaptured_owner(&mut self, name: &str) -> Option<Owner> {
+        let (depth, owner) = self.resolve(name)?;
+        let owner = owner?;
+        if self.capture_floor.is_some_and(|floor| depth < floor) {
+            if owner.nested {
+                self.result.nested.insert(name.to_string());
+            } else {
+                self.result.bindings.insert(owner.value, MoveState::Escaped);
+            }
+            return None;
+        }
+        Some(owner)
+    }
+
+    /// `name` is stored or handed over; call arguments copy a `&T`
+    fn move_out(&mut self, name: &str, as_argument: bool) {
+        let Some(owner) = self.uncaptured_owner(name) else {
+            return;
+        };
+        if as_argument && owner.copy_ref {
+            return;
+        }
+        if owner.nested {
+            self.result.nested.insert(name.to_string());
+            return;
+        }
+        self.mark_maybe_moved(owner);
+        if let Some(stmt) = self.statement {
+            let moved = self.result.statements.entry(stmt).or_default();
+            if !moved.iter().any(|m| m == name) {
+                moved.push(name.to_string());
+            }
+        }
+    }
+
+    fn mark_maybe_moved(&mut self, owner: Owner) {
+        let state = self
+            .result
+            .bindings
+            .entry(owner.value)
+            .or_insert(MoveState::Kept);
+        if *state == MoveState::Kept {
+            *state = MoveState::MaybeMoved;
+        }
+    }
+
+    /// `name` is only read, which still captures it inside a closure or task
+    fn mention(&mut self, name: &str) {
+        self.uncaptured_owner(name);
+    }
+
+    /// Scan statements that live inside an expression
+    fn nest(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.nested += 1;
+        self.scopes.push(Vec::new());
+        visit(self);
+        self.scopes.pop();
+        self.nested -= 1;
+    }
+
+    /// Every outer local mentioned in a closure, async block or `go` expression
+    /// may outlive the spawning scope
+    fn capture_all(&mut self, visit: impl FnOnce(&mut Self)) {
+        let outer = self.capture_floor.replace(self.scopes.len());
+        self.nest(visit);
+        self.capture_floor = outer;
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        // Statements of statement blocks are compiled in place and clear the
+        // drop flags of what they move; nested ones count for their enclosing statement
+        let outer_statement = self.statement;
+        if self.nested == 0 {
+            self.statement = Some(stmt as *const Statement);
+        }
+
+        match stmt {
+            Statement::Let {
+                name, ty, value, ..
+            } => {
+                self.visit_value(value);
+                self.declare(name, ty.as_ref(), Some(value));
+            }
+            Statement::LetPattern { pattern, value, .. } => {
+                self.visit_value(value);
+                let mut names = Vec::new();
+                pattern_bindings(pattern, &mut names);
+                for name in names {
+                    self.declare(&name, None, None);
+                }
+            }
+            Statement::Assign { target, value, .. } => {
+                self.visit_expression(target);
+                self.visit_value(value);
+            }
+            Statement::CompoundAssign { target, value, .. } => {
+                self.visit_expression(target);
+                self.visit_expression(value);
+            }
+            // `return p;` keeps a drop flag for control_flow to clear; `return Some(p);` moves
+            Statement::Return {
+                value: Some(Expression::Ident(name)),
+                ..
+            } => {
+                if let Some(owner) = self.uncaptured_owner(name) {
+                    if !owner.nested {
+                        self.mark_maybe_moved(owner);
+                    }
+                }
+            }
+            Statement::Return { value: None, .. }
+            | Statement::Break { .. }
+            | Statement::Continue { .. } => {}
+            Statement::Return {
+                value: Some(expr), ..
+            } => self.visit_value(expr),
+            Statement::Expression(expr) => self.visit_expression(expr),
+            Statement::Go { expr, .. } => self.capture_all(|this| this.visit_expression(expr)),
+            // A deferred statement runs at scope exit: it owns what it moves from here on
+            Statement::Defer(inner) => self.nest(|this| this.visit_statement(inner)),
+            Statement::Attributed { stmt: inner, .. } => self.visit_statement(inner),
+            Statement::If {
+                condition,
+                then_block,
+                elif_branches,
+                else_block,
+                ..
+            } => {
+                self.visit_expression(condition);
+                self.visit_block(then_block);
+                for (elif_cond, elif_block) in elif_branches {
+                    self.visit_expression(elif_cond);
+                    self.visit_block(elif_block);
+                }
+                if let Some(else_b) = else_block {
+                    self.visit_block(else_b);
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.visit_expression(condition);
+                self.visit_block(body);
+            }
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                self.scopes.push(Vec::new());
+                self.nested += 1;
+                if let Some(init_stmt) = init {
+                    self.visit_statement(init_stmt);
+                }
+                if let Some(cond) = condition {
+                    self.visit_expression(cond);
+                }
+                if let Some(post_stmt) = post {
+                    self.visit_statement(post_stmt);
+                }
+                self.nested -= 1;
+                self.visit_block(body);
+                self.scopes.pop();
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            } => {
+                self.visit_expression(iterable);
+                self.scopes.push(Vec::new());
+                self.declare(variable, None, None);
+                self.visit_block(body);
+                self.scopes.pop();
+            }
+            Statement::Loop { body, .. } | Statement::Unsafe { block: body, .. } => {
+                self.visit_block(body)
+            }
+            Statement::Switch {
+                value,
+                cases,
+                default_case,
+                ..
+            } => {
+                if let Some(v) = value {
+                    self.visit_expression(v);
+                }
+                for case in cases {
+                    self.visit_expressions(&case.patterns);
+                    self.visit_block(&case.body);
+                }
+                if let Some(default) = default_case {
+                    self.visit_block(default);
+                }
+            }
+            Statement::Select {
+                cases,
+                default_case,
+                ..
+            } => {
+                for case in cases {
+                    self.visit_expression(&case.expr);
+                    self.scopes.push(Vec::new());
+                    if let Some(var) = &case.var {
+                        self.declare(var, None, None);
+                    }
+                    self.visit_block(&case.body);
+                    self.scopes.pop();
+                }
+                if let Some(default) = default_case {
+                    self.visit_block(default);
+                }
+            }
+        }
+
+        self.statement = outer_statement;
+    }
+
+    /// An expression whose result is stored or handed over: a bare local moves
+    fn visit_value(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Ident(name) => self.move_out(name, false),
+            _ => self.visit_expression(expr),
+        }
+    }
+
+    fn visit_values(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.visit_value(expr);
+        }
+    }
+
+    /// Call arguments move a local, except a `&T` that is copied
+    fn visit_arguments(&mut self, args: &[Expression]) {
+        for arg in args {
+            match arg {
+                Expression::Ident(name) => self.move_out(name, true),
+                _ => self.visit_expression(arg),
+            }
+        }
+    }
+
+    fn visit_expressions(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.visit_expression(expr);
+        }
+    }
+
+    /// An expression that is only read: a bare local stays where it is
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Ident(name) => self.mention(name),
+            Expression::FStringLiteral(text) => {
+                if self.capture_floor.is_some() {
+                    // Interpolated names are only visible in the raw text
+                    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+                        if !word.is_empty() {
+                            self.mention(word);
+                        }
+                    }
+                }
+            }
+            Expression::IntLiteral(_)
+            | Expression::TypedIntLiteral { .. }
+            | Expression::BigIntLiteral(_)
+            | Expression::TypedBigIntLiteral { .. }
+            | Expression::FloatLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::BoolLiteral(_)
+            | Expression::Nil => {}
+            Expression::Binary { left, right, .. } => {
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            Expression::Unary { expr, .. }
+            | Expression::Deref(expr)
+            | Expression::Reference { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::Is { expr, .. }
+            | Expression::TryOp { expr }
+            | Expression::Typeof(expr)
+            | Expression::PostfixOp { expr, .. }
+            | Expression::ChannelReceive(expr) => self.visit_expression(expr),
+            Expression::Await(inner)
+            | Expression::Yield(inner)
+            | Expression::ErrorNew(inner)
+            | Expression::New(inner) => self.visit_value(inner),
+            Expression::Make { size, .. } => self.visit_expression(size),
+            Expression::Call { func, args, .. } => {
+                self.visit_expression(func);
+                self.visit_arguments(args);
+            }
+            Expression::MethodCall { receiver, args, .. } => {
+                self.visit_expression(receiver);
+                self.visit_arguments(args);
+            }
+            Expression::FieldAccess { object, .. } => self.visit_expression(object),
+            Expression::Index { object, index } => {
+                self.visit_expression(object);
+                self.visit_expression(index);
+            }
+            Expression::Array(elements)
+            | Expression::TupleLiteral(elements)
+            | Expression::EnumLiteral { data: elements, .. }
+            | Expression::TypeConstructor { args: elements, .. } => self.visit_values(elements),
+            Expression::ArrayRepeat(value, count) => {
+                self.visit_value(value);
+                self.visit_expression(count);
+            }
+            Expression::MapLiteral(pairs) => {
+                for (key, value) in pairs {
+                    self.visit_value(key);
+                    self.visit_value(value);
+                }
+            }
+            Expression::StructLiteral { fields, .. } => {
+                for (_, value) in fields {
+                    self.visit_value(value);
+                }
+            }
+            Expression::Range { start, end } | Expression::RangeInclusive { start, end } => {
+                if let Some(s) = start {
+                    self.visit_expression(s);
+                }
+                if let Some(e) = end {
+                    self.visit_expression(e);
+                }
+            }
+            Expression::Match { value, arms, .. } => {
+                self.visit_expression(value);
+                for arm in arms {
+                    self.nest(|this| {
+                        let mut names = Vec::new();
+                        pattern_bindings(&arm.pattern, &mut names);
+                        for name in names {
+                            this.declare(&name, None, None);
+                        }
+                        if let Some(guard) = &arm.guard {
+                            this.visit_expression(guard);
+                        }
+                        // An arm's result is the match's value
+                        this.visit_value(&arm.body);
+                    });
+                }
+            }
+            Expression::Block {
+                statements,
+                return_expr,
+            } => self.nest(|this| {
+                for stmt in statements {
+                    this.visit_statement(stmt);
+                }
+                if let Some(ret) = return_expr {
+                    this.visit_value(ret);
+                }
+            }),
+            Expression::Launch { grid, args, .. } => {
+                self.visit_expressions(grid);
+                self.visit_arguments(args);
+            }
+            Expression::AsyncBlock {
+                statements,
+                return_expr,
+            } => self.capture_all(|this| {
+                for stmt in statements {
+                    this.visit_statement(stmt);
+                }
+                if let Some(ret) = return_expr {
+                    this.visit_value(ret);
+                }
+            }),
+            Expression::Closure { params, body, .. } => self.capture_all(|this| {
+                for param in params {
+                    this.declare(&param.name, Some(&param.ty), None);
+                }
+                this.visit_value(body);
+            }),
+        }
+    }
+}
+
+/// make/new/error.new, enum literals and calls (an async call hands back its
+/// future): the initializers register_builtin_drop may release
+fn is_owning(value: &Expression) -> bool {
+    matches!(
+        value,
+        Expression::New(_)
+            | Expression::Make { .. }
+            | Expression::ErrorNew(_)
+            | Expression::EnumLiteral { .. }
+            | Expression::Call { .. }
+            | Expression::MethodCall { .. }
+    )
+}
+
+/// Names a pattern binds
+fn pattern_bindings(pattern: &Pattern, out: &mut Vec<String>) {
+    match pattern {
+        Pattern::Ident(name) => out.push(name.clone()),
+        Pattern::Type {
+            binding: Some(name),
+            ..
+        } => out.push(name.clone()),
+        Pattern::Tuple(elements) | Pattern::Enum { data: elements, .. } => {
+            for element in elements {
+                pattern_bindings(element, out);
+            }
+        }
+        Pattern::Struct { fields, .. } => {
+            for (_, field) in fields {
+                pattern_bindings(field, out);
+            }
+        }
+        Pattern::Array { elements, rest } => {
+            for element in elements {
+                pattern_bindings(element, out);
+            }
+            out.extend(rest.iter().cloned());
+        }
+        // Every alternative binds the same names
+        Pattern::Or(alternatives) => {
+            if let Some(first) = alternatives.first() {
+                pattern_bindings(first, out);
+            }
+        }
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::Type { binding: None, .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(name: &str) -> Expression {
+        Expression::Ident(name.to_string())
+    }
+
+    fn let_stmt(name: &str, value: Expression) -> Statement {
+        Statement::Let {
+            is_mutable: false,
+            name: name.to_string(),
+            ty: None,
+            value,
+        }
+    }
+
+    /// let name = new(Point {}); - owns a heap struct
+    fn owned(name: &str) -> Statement {
+        let_stmt(
+            name,
+            Expression::New(Box::new(Expression::StructLiteral {
+                name: "Point".to_string(),
+                type_args: vec![],
+                fields: vec![],
+            })),
+        )
+    }
+
+    fn call(func: &str, args: Vec<Expression>) -> Expression {
+        Expression::Call {
+            span_id: None,
+            func: Box::new(ident(func)),
+            type_args: vec![],
+            args,
+        }
+    }
+
+    fn ret(value: Expression) -> Statement {
+        Statement::Return {
+            span_id: None,
+            value: Some(value),
+        }
+    }
+
+    fn block(statements: Vec<Statement>) -> Block {
+        Block {
+            span_id: None,
+            statements,
+        }
+    }
+
+    fn if_stmt(then_block: Vec<Statement>) -> Statement {
+        Statement::If {
+            span_id: None,
+            condition: ident("cond"),
+            then_block: block(then_block),
+            elif_branches: vec![],
+            else_block: None,
+        }
+    }
+
+    /// State of the first top-level `let name`
+    fn state(body: &Block, moves: &MovedLocals, name: &str) -> MoveState {
+        body.statements
+            .iter()
+            .find_map(|stmt| match stmt {
+                Statement::Let {
+                    name: bound, value, ..
+                } if bound == name => Some(moves.state(name, value)),
+                _ => None,
+            })
+            .expect("binding not declared")
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_let_from_binding_moves() {
+        let body = block(vec![owned("p"), let_stmt("q", ident("p"))]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "p"), MoveState::MaybeMoved);
+        assert_eq!(moves.moved_by(&body.statements[1]), names(&["p"]));
+    }
+
+    #[test]
+    fn test_call_argument_moves() {
+        let body = block(vec![
+            owned("p"),
+            owned("s"),
+            let_stmt(
+                "ch",
+                Expression::Make {
+                    element_type: Type::Channel(Box::new(Type::I32)),
+                    size: Box::new(Expression::IntLiteral(1)),
+                },
+            ),
+            Statement::Expression(call("consume", vec![ident("p")])),
+            Statement::Expression(Expression::MethodCall {
+                receiver: Box::new(ident("ch")),
+                method: "send".to_string(),
+                type_args: vec![],
+                args: vec![ident("s")],
+                is_mutable_call: false,
+            }),
+        ]);
+        let moves = moved_locals(&body);
+        // The receiver stays put; the argument moves
+        assert_eq!(state(&body, &moves, "ch"), MoveState::Kept);
+        assert_eq!(moves.moved_by(&body.statements[3]), names(&["p"]));
+        assert_eq!(moves.moved_by(&body.statements[4]), names(&["s"]));
+    }
+
+    #[test]
+    fn test_struct_field_moves() {
+        let body = block(vec![
+            owned("p"),
+            let_stmt(
+                "holder",
+                Expression::StructLiteral {
+                    name: "Holder".to_string(),
+                    type_args: vec![],
+                    fields: vec![("inner".to_string(), ident("p"))],
+                },
+            ),
+        ]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "p"), MoveState::MaybeMoved);
+    }
+
+    #[test]
+    fn test_return_wrapped_moves_but_return_binding_does_not() {
+        // control_flow clears the flag of a directly returned binding itself
+        let body = block(vec![owned("p"), ret(ident("p"))]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "p"), MoveState::MaybeMoved);
+        assert!(moves.moved_by(&body.statements[1]).is_empty());
+
+        let wrapped = Expression::EnumLiteral {
+            enum_name: "Option".to_string(),
+            variant: "Some".to_string(),
+            data: vec![ident("p")],
+            field_names: vec![],
+        };
+        let body = block(vec![owned("p"), ret(wrapped)]);
+        let moves = moved_locals(&body);
+        assert_eq!(moves.moved_by(&body.statements[1]), names(&["p"]));
+    }
+
+    #[test]
+    fn test_reads_do_not_move() {
+        let body = block(vec![
+            owned("p"),
+            let_stmt(
+                "n",
+                Expression::FieldAccess {
+                    object: Box::new(ident("p")),
+                    field: "x".to_string(),
+                },
+            ),
+            Statement::Expression(call(
+                "show",
+                vec![Expression::Reference {
+                    is_mutable: false,
+                    expr: Box::new(ident("p")),
+                }],
+            )),
+        ]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "p"), MoveState::Kept);
+    }
+
+    #[test]
+    fn test_move_on_one_branch_is_still_released_on_the_other() {
+        let body = block(vec![
+            owned("p"),
+            if_stmt(vec![Statement::Expression(call(
+                "consume",
+                vec![ident("p")],
+            ))]),
+        ]);
+        let moves = moved_locals(&body);
+        // Released at scope exit unless the branch ran and cleared the drop flag
+        assert_eq!(state(&body, &moves, "p"), MoveState::MaybeMoved);
+        assert!(moves.moved_by(&body.statements[1]).is_empty());
+        let Statement::If { then_block, .. } = &body.statements[1] else {
+            unreachable!()
+        };
+        assert_eq!(moves.moved_by(&then_block.statements[0]), names(&["p"]));
+    }
+
+    #[test]
+    fn test_shadowed_bindings_are_tracked_apart() {
+        let body = block(vec![
+            owned("p"),
+            if_stmt(vec![
+                let_stmt("p", Expression::IntLiteral(5)),
+                Statement::Expression(call("consume", vec![ident("p")])),
+            ]),
+            if_stmt(vec![
+                owned("p"),
+                Statement::Expression(call("consume", vec![ident("p")])),
+            ]),
+        ]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "p"), MoveState::Kept);
+        let Statement::If { then_block, .. } = &body.statements[2] else {
+            unreachable!()
+        };
+        assert_eq!(state(then_block, &moves, "p"), MoveState::MaybeMoved);
+    }
+
+    #[test]
+    fn test_reference_arguments_are_copies() {
+        // new(1) is a &i32: passing it to a call does not hand over the cell
+        let body = block(vec![
+            let_stmt("r", Expression::New(Box::new(Expression::IntLiteral(1)))),
+            Statement::Expression(call("show", vec![ident("r")])),
+        ]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "r"), MoveState::Kept);
+
+        let body = block(vec![
+            let_stmt("r", Expression::New(Box::new(Expression::IntLiteral(1)))),
+            let_stmt("q", ident("r")),
+        ]);
+        let moves = moved_locals(&body);
+        assert_eq!(state(&body, &moves, "r"), MoveState::MaybeMoved);
+    }
+
+    #[test]
+    fn test_go_and_closure_captures_escape() {
+        let body = block(vec![
+            owned("a"),
+            owned("b"),
+            owned("c"),
+            owned("d"),
+            Statement::Go {
+                span_id: None,
+                expr: call(
+                    "worker",
+                    vec![Expression::Binary {
+                        span_id: None,
+                        left: Box::new(ident("a")),
+                        op: vex_ast::BinaryOp::Add,
+                        right: Box::new(ident("b")),
+                    }],
+                ),
+            },
+            let_stmt(
+                "f",
+                Expression::Closure {
+                    params: vec![],
+                    return_type: None,
+                    body: Box::new(ident("c")),
+                    capture_mode: vex_ast::CaptureMode::Infer,
+                },
+            ),
+        ]);
+        let moves = moved_locals(&body);
+        for name in ["a", "b", "c"] {
+            assert_eq!(state(&body, &moves, name), MoveState::Escaped);
+        }
+        assert_eq!(state(&body, &moves, "d"), MoveState::Kept);
+    }
+
+    #[test]
+    fn test_bindings_inside_expressions_escape_when_moved() {
+        let inner = Expression::Block {
+            statements: vec![
+                owned("p"),
+                Statement::Expression(call("consume", vec![ident("p")])),
+            ],
+            return_expr: None,
+        };
+        let body = block(vec![owned("q"), Statement::Expression(inner)]);
+        let moves = moved_locals(&body);
+        let Statement::Expression(Expression::Block { statements, .. }) = &body.statements[1]
+        else {
+            unreachable!()
+        };
+        let Statement::Let { value, .. } = &statements[0] else {
+            unreachable!()
+        };
+        assert_eq!(moves.state("p", value), MoveState::Escaped);
+        assert_eq!(state(&body, &moves, "q"), MoveState::Kept);
+    }
+
+    #[test]
+    fn test_awaiting_a_future_moves_it() {
+        // let f = fetch(); let g = fetch(); if cond { let n = await f; }
+        let body = block(vec![
+            let_stmt("f", call("fetch", vec![])),
+            let_stmt("g", call("fetch", vec![])),
+            if_stmt(vec![let_stmt("n", Expression::Await(Box::new(ident("f"))))]),
+        ]);
+        let moves = moved_locals(&body);
+        // An unawaited future is released at scope exit, an awaited one by its awaiter
+        assert_eq!(state(&body, &moves, "f"), MoveState::MaybeMoved);
+        assert_eq!(state(&body, &moves, "g"), MoveState::Kept);
+        let Statement::If { then_block, .. } = &body.statements[2] else {
+            unreachable!()
+        };
+        assert_eq!(moves.moved_by(&then_block.statements[0]), names(&["f"]));
+    }
+}
@@ -141,6 +141,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let saved_variables = std::mem::take(&mut self.variables);
         let saved_variable_types = std::mem::take(&mut self.variable_types);
         let saved_variable_struct_names = std::mem::take(&mut self.variable_struct_names);
+        let saved_moved_locals = std::mem::take(&mut self.moved_locals);
 
         // ⭐ CRITICAL: Store type substitution map for use during compilation
         // This allows infer_expression_type() to resolve generic parameters like T → I32
@@ -164,6 +165,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.variables = saved_variables;
         self.variable_types = saved_variable_types;
         self.variable_struct_names = saved_variable_struct_names;
+        self.moved_locals = saved_moved_locals;
 
         if let Some(block) = saved_insert_block {
             self.builder.position_at_end(block);
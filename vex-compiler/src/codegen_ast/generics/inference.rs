@@ -6,9 +6,10 @@ use std::collections::HashMap;
 
 impl<'ctx> ASTCodeGen<'ctx> {
     pub(crate) fn infer_type_args_from_call(
-        &mut self,
+        &self,
         func_def: &Function,
         args: &[Expression],
+        expected_type: Option<&Type>,
     ) -> Result<Vec<Type>, String> {
         // For functions with multiple type parameters of the same type,
         // we need to infer unique type parameters, not all argument types
@@ -18,28 +19,104 @@ impl<'ctx> ASTCodeGen<'ctx> {
             return Ok(Vec::new());
         }
 
+        self.infer_type_params(
+            &func_def.name,
+            func_def,
+            HashMap::new(),
+            args,
+            expected_type,
+        )
+    }
+
+    /// Infer a generic method's own type parameters at a call site
+    ///
+    /// The receiver already fixes the struct's parameters: for `list.map(|x| x > 0)`
+    /// on `Vec<i32>`, `T = i32` types the closure and its result gives `U = bool`.
+    pub(crate) fn infer_method_type_args(
+        &self,
+        struct_name: &str,
+        struct_type_args: &[Type],
+        method_def: &Function,
+        args: &[Expression],
+        expected_type: Option<&Type>,
+    ) -> Result<Vec<Type>, String> {
+        let mut known = HashMap::new();
+        if let Some(struct_def) = self.struct_ast_defs.get(struct_name) {
+            for (type_param, concrete_type) in
+                struct_def.type_params.iter().zip(struct_type_args.iter())
+            {
+                known.insert(type_param.name.clone(), concrete_type.clone());
+            }
+        }
+
+        self.infer_type_params(
+            &format!("{}.{}", struct_name, method_def.name),
+            method_def,
+            known,
+            args,
+            expected_type,
+        )
+    }
+
+    /// Infer `func`'s type parameters from call arguments, then from the type
+    /// the call's result is expected to have
+    ///
+    /// `known` holds parameters fixed elsewhere (a receiver's struct type args).
+    /// Closure arguments are typed last, once the parameter types they receive
+    /// are known, so `|x| x * 2` against `fn(T): U` with `T = i32` gives `U = i32`.
+    fn infer_type_params(
+        &self,
+        callee: &str,
+        func: &Function,
+        known: HashMap<String, Type>,
+        args: &[Expression],
+        expected_type: Option<&Type>,
+    ) -> Result<Vec<Type>, String> {
+        let names: Vec<&str> = func.type_params.iter().map(|p| p.name.as_str()).collect();
+
         // Build map: type param name -> inferred type
         let mut type_param_map: HashMap<String, Type> = HashMap::new();
 
         // Match arguments to parameters and infer type parameters
-        for (i, param) in func_def.params.iter().enumerate() {
-            if i >= args.len() {
-                break; // Not enough arguments
+        for (param, arg) in func.params.iter().zip(args.iter()) {
+            if matches!(arg, Expression::Closure { .. }) {
+                continue;
+            }
+            let arg_type = self.infer_expression_type(arg)?;
+            self.match_type_param(&param.ty, &arg_type, &names, &mut type_param_map);
+        }
+
+        for (param, arg) in func.params.iter().zip(args.iter()) {
+            if !matches!(arg, Expression::Closure { .. }) {
+                continue;
             }
+            let mut subst = known.clone();
+            subst.extend(type_param_map.clone());
+            let expected_fn = self.substitute_type(&param.ty, &subst);
+            if let Some(closure_type) = self.closure_argument_type(arg, &expected_fn, &names) {
+                self.match_type_param(&param.ty, &closure_type, &names, &mut type_param_map);
+            }
+        }
 
-            let arg_type = self.infer_expression_type(&args[i])?;
-            self.match_type_param(&param.ty, &arg_type, &mut type_param_map);
+        // Whatever the arguments left open may follow from the expected result:
+        // let names: Vec<string> = list.map(to_name);
+        if let (Some(return_type), Some(expected)) = (&func.return_type, expected_type) {
+            let mut from_return = HashMap::new();
+            self.match_type_param(return_type, expected, &names, &mut from_return);
+            for (name, ty) in from_return {
+                type_param_map.entry(name).or_insert(ty);
+            }
         }
 
-        // Build type_args vector in the order of func_def.type_params
+        // Build type_args vector in the order of type_params
         let mut type_args = Vec::new();
-        for type_param in &func_def.type_params {
+        for type_param in &func.type_params {
             if let Some(inferred_ty) = type_param_map.get(&type_param.name) {
                 type_args.push(inferred_ty.clone());
             } else {
                 return Err(format!(
                     "Cannot infer type parameter '{}' for function '{}'",
-                    type_param.name, func_def.name
+                    type_param.name, callee
                 ));
             }
         }
@@ -47,51 +124,161 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(type_args)
     }
 
+    /// Type of a closure argument passed where `expected` is wanted
+    ///
+    /// Unannotated closure parameters take the expected parameter types; None
+    /// while one of those still names an uninferred type parameter.
+    fn closure_argument_type(
+        &self,
+        arg: &Expression,
+        expected: &Type,
+        type_params: &[&str],
+    ) -> Option<Type> {
+        let Expression::Closure {
+            params,
+            return_type,
+            body,
+            ..
+        } = arg
+        else {
+            return None;
+        };
+        let expected_params = match expected {
+            Type::Function { params, .. } => params.as_slice(),
+            _ => &[],
+        };
+
+        let mut bindings = Vec::new();
+        for (i, param) in params.iter().enumerate() {
+            let ty = match &param.ty {
+                Type::Infer(_) => expected_params.get(i)?.clone(),
+                annotated => annotated.clone(),
+            };
+            if mentions_type_param(&ty, type_params) {
+                return None;
+            }
+            bindings.push((param.name.clone(), ty));
+        }
+
+        let return_type = match return_type {
+            Some(ty) => ty.clone(),
+            None => {
+                let result = closure_result_expression(body)?;
+                self.infer_expression_type(&annotate_bindings(result, &bindings))
+                    .ok()?
+            }
+        };
+        Some(Type::Function {
+            params: bindings.into_iter().map(|(_, ty)| ty).collect(),
+            return_type: Box::new(return_type),
+        })
+    }
+
+    /// `closure` with its unannotated parameters typed from `expected`, so the
+    /// closure compiles with the signature the callee was instantiated with
+    pub(crate) fn closure_with_expected_types(
+        &self,
+        closure: &Expression,
+        expected: &Type,
+    ) -> Expression {
+        let (
+            Expression::Closure {
+                params,
+                return_type,
+                body,
+                capture_mode,
+            },
+            Type::Function {
+                params: expected_params,
+                return_type: expected_return,
+            },
+        ) = (closure, expected)
+        else {
+            return closure.clone();
+        };
+
+        let params = params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| match (&param.ty, expected_params.get(i)) {
+                (Type::Infer(_), Some(expected_ty)) => Param {
+                    ty: expected_ty.clone(),
+                    ..param.clone()
+                },
+                _ => param.clone(),
+            })
+            .collect();
+        Expression::Closure {
+            params,
+            return_type: return_type
+                .clone()
+                .or_else(|| Some((**expected_return).clone())),
+            body: body.clone(),
+            capture_mode: capture_mode.clone(),
+        }
+    }
+
     /// Match a parameter type pattern against an argument type to infer type parameters
     /// Example: param_ty = T, arg_ty = i32 → map[T] = i32
     ///          param_ty = (T, U), arg_ty = (i32, string) → map[T]=i32, map[U]=string
+    ///          param_ty = fn(T): U, arg_ty = fn(i32): bool → map[T]=i32, map[U]=bool
     fn match_type_param(
         &self,
         param_ty: &Type,
         arg_ty: &Type,
+        type_params: &[&str],
         type_param_map: &mut HashMap<String, Type>,
     ) {
         match param_ty {
             // Single type parameter: T
-            Type::Named(name) if name.chars().next().map_or(false, |c| c.is_uppercase()) => {
-                // Check if this is a type parameter (starts with uppercase)
-                // TODO: Better check - compare with func_def.type_params
+            Type::Named(name) if type_params.contains(&name.as_str()) => {
                 type_param_map.insert(name.clone(), arg_ty.clone());
             }
             // Tuple: (T, U) matched with (i32, string)
             Type::Tuple(param_types) => {
                 if let Type::Tuple(arg_types) = arg_ty {
                     for (p_ty, a_ty) in param_types.iter().zip(arg_types.iter()) {
-                        self.match_type_param(p_ty, a_ty, type_param_map);
+                        self.match_type_param(p_ty, a_ty, type_params, type_param_map);
                     }
                 }
             }
+            // Function: fn(T): U matched with a closure or function type
+            Type::Function {
+                params: param_types,
+                return_type: param_return,
+            } => {
+                if let Type::Function {
+                    params: arg_types,
+                    return_type: arg_return,
+                } = arg_ty
+                {
+                    for (p_ty, a_ty) in param_types.iter().zip(arg_types.iter()) {
+                        self.match_type_param(p_ty, a_ty, type_params, type_param_map);
+                    }
+                    self.match_type_param(param_return, arg_return, type_params, type_param_map);
+                }
+            }
             // Reference: &T matched with &i32
             Type::Reference(inner_param, _) => {
                 if let Type::Reference(inner_arg, _) = arg_ty {
-                    self.match_type_param(inner_param, inner_arg, type_param_map);
+                    self.match_type_param(inner_param, inner_arg, type_params, type_param_map);
                 }
             }
             // Option<T>, Result<T, E>, Vec<T>
             Type::Option(inner_param) => {
                 if let Type::Option(inner_arg) = arg_ty {
-                    self.match_type_param(inner_param, inner_arg, type_param_map);
+                    self.match_type_param(inner_param, inner_arg, type_params, type_param_map);
                 }
             }
             Type::Result(ok_param, err_param) => {
                 if let Type::Result(ok_arg, err_arg) = arg_ty {
-                    self.match_type_param(ok_param, ok_arg, type_param_map);
-                    self.match_type_param(err_param, err_arg, type_param_map);
+                    self.match_type_param(ok_param, ok_arg, type_params, type_param_map);
+                    self.match_type_param(err_param, err_arg, type_params, type_param_map);
                 }
             }
             Type::Vec(inner_param) => {
                 if let Type::Vec(inner_arg) = arg_ty {
-                    self.match_type_param(inner_param, inner_arg, type_param_map);
+                    self.match_type_param(inner_param, inner_arg, type_params, type_param_map);
                 }
             }
             // Generic with type args: HashMap<K, V>
@@ -103,7 +290,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 {
                     if name == arg_name {
                         for (p_ty, a_ty) in type_args.iter().zip(arg_type_args.iter()) {
-                            self.match_type_param(p_ty, a_ty, type_param_map);
+                            self.match_type_param(p_ty, a_ty, type_params, type_param_map);
                         }
                     }
                 }
@@ -119,16 +306,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
     pub(crate) fn instantiate_struct_methods(
         &mut self,
         struct_name: &str,
-        struct_type_params: &[TypeParam],
-        type_args: &[Type],
+        type_subst: &HashMap<String, Type>,
         mangled_struct_name: &str,
     ) -> Result<(), String> {
-        // Build type substitution map: K -> str, V -> i32, etc.
-        let mut type_subst = HashMap::new();
-        for (param, arg) in struct_type_params.iter().zip(type_args.iter()) {
-            type_subst.insert(param.name.clone(), arg.clone());
-        }
-
         eprintln!(
             "🔧 Instantiating methods for struct {} -> {}",
             struct_name, mangled_struct_name
@@ -155,6 +335,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     && !name.contains("_str_") // Not already instantiated
                     && !name.contains("_i32_")
                     && !name.contains("_i64_")
+                    && !name.starts_with(&format!("{}_", mangled_struct_name))
+                    && !self
+                        .generic_instantiations
+                        .values()
+                        .any(|instance| name.starts_with(&format!("{}_", instance)))
             })
             .cloned()
             .collect();
@@ -179,7 +364,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // Instantiate the method
                 let specialized_func = self.substitute_types_in_method(
                     &func,
-                    &type_subst,
+                    type_subst,
                     struct_name,
                     mangled_struct_name,
                 )?;
@@ -200,6 +385,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 let saved_variables = std::mem::take(&mut self.variables);
                 let saved_variable_types = std::mem::take(&mut self.variable_types);
                 let saved_variable_struct_names = std::mem::take(&mut self.variable_struct_names);
+                let saved_moved_locals = std::mem::take(&mut self.moved_locals);
 
                 // Declare the method
                 match self.declare_function(&specialized_func) {
@@ -229,6 +415,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 self.variables = saved_variables;
                 self.variable_types = saved_variable_types;
                 self.variable_struct_names = saved_variable_struct_names;
+                self.moved_locals = saved_moved_locals;
 
                 if let Some(block) = saved_insert_block {
                     self.builder.position_at_end(block);
@@ -303,6 +490,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
             new_func.return_type = Some(substituted_ret);
         }
 
+        // Substitute types (and const param values) in the method body
+        new_func.body = self.substitute_types_in_block(&new_func.body, type_subst);
+
         // Build mangled method name: HashMap_insert -> HashMap_str_i32_insert
         // Extract method name by removing struct prefix
         let struct_prefix = format!("{}_", struct_name);
@@ -484,6 +674,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 ty: ty.as_ref().map(|t| self.substitute_type(t, type_subst)),
                 value: self.substitute_types_in_expression(value, type_subst),
             },
+            Statement::Attributed { attributes, stmt } => Statement::Attributed {
+                attributes: attributes.clone(),
+                stmt: Box::new(self.substitute_types_in_statement(stmt, type_subst)),
+            },
             _ => stmt.clone(),
         }
     }
@@ -580,6 +774,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 expr: Box::new(self.substitute_types_in_expression(expr, type_subst)),
                 target_type: self.substitute_type(target_type, type_subst),
             },
+            Expression::Is { expr, target_type } => Expression::Is {
+                expr: Box::new(self.substitute_types_in_expression(expr, type_subst)),
+                target_type: self.substitute_type(target_type, type_subst),
+            },
             Expression::TupleLiteral(elements) => Expression::TupleLiteral(
                 elements
                     .iter()
@@ -615,7 +813,143 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .as_ref()
                     .map(|e| Box::new(self.substitute_types_in_expression(e, type_subst))),
             },
+            Expression::ArrayRepeat(value, count) => Expression::ArrayRepeat(
+                Box::new(self.substitute_types_in_expression(value, type_subst)),
+                Box::new(self.substitute_types_in_expression(count, type_subst)),
+            ),
+            // Const generic parameters are values: `C` in Matrix<T, 2, 3> is 3
+            Expression::Ident(name) => match type_subst.get(name) {
+                Some(Type::ConstValue(value)) => Expression::IntLiteral(*value),
+                _ => expr.clone(),
+            },
             _ => expr.clone(),
         }
     }
 }
+
+/// Whether `ty` still mentions one of `type_params`
+fn mentions_type_param(ty: &Type, type_params: &[&str]) -> bool {
+    match ty {
+        Type::Named(name) => type_params.contains(&name.as_str()),
+        Type::Generic { type_args, .. } | Type::Tuple(type_args) => type_args
+            .iter()
+            .any(|arg| mentions_type_param(arg, type_params)),
+        Type::Function {
+            params,
+            return_type,
+        } => {
+            params.iter().any(|p| mentions_type_param(p, type_params))
+                || mentions_type_param(return_type, type_params)
+        }
+        Type::Reference(inner, _)
+        | Type::Array(inner, _)
+        | Type::Slice(inner, _)
+        | Type::Vec(inner)
+        | Type::Box(inner)
+        | Type::Option(inner) => mentions_type_param(inner, type_params),
+        Type::Result(ok, err) => {
+            mentions_type_param(ok, type_params) || mentions_type_param(err, type_params)
+        }
+        _ => false,
+    }
+}
+
+/// The expression whose value a closure body produces
+fn closure_result_expression(body: &Expression) -> Option<&Expression> {
+    match body {
+        Expression::Block {
+            return_expr: Some(result),
+            ..
+        } => closure_result_expression(result),
+        Expression::Block { statements, .. } => match statements.last() {
+            Some(Statement::Expression(result)) => closure_result_expression(result),
+            Some(Statement::Return {
+                value: Some(result),
+                ..
+            }) => closure_result_expression(result),
+            _ => None,
+        },
+        other => Some(other),
+    }
+}
+
+/// Copy of `expr` with closure parameters cast to their types, so expression
+/// inference sees `x as i32` where the body says `x`
+fn annotate_bindings(expr: &Expression, bindings: &[(String, Type)]) -> Expression {
+    let annotate = |e: &Expression| Box::new(annotate_bindings(e, bindings));
+    match expr {
+        Expression::Ident(name) => match bindings.iter().find(|(param, _)| param == name) {
+            Some((_, ty)) => Expression::Cast {
+                expr: Box::new(expr.clone()),
+                target_type: ty.clone(),
+            },
+            None => expr.clone(),
+        },
+        Expression::Binary {
+            span_id,
+            left,
+            op,
+            right,
+        } => Expression::Binary {
+            span_id: span_id.clone(),
+            left: annotate(left),
+            op: op.clone(),
+            right: annotate(right),
+        },
+        Expression::Unary { span_id, op, expr } => Expression::Unary {
+            span_id: span_id.clone(),
+            op: op.clone(),
+            expr: annotate(expr),
+        },
+        Expression::MethodCall {
+            receiver,
+            method,
+            type_args,
+            args,
+            is_mutable_call,
+        } => Expression::MethodCall {
+            receiver: annotate(receiver),
+            method: method.clone(),
+            type_args: type_args.clone(),
+            args: args
+                .iter()
+                .map(|a| annotate_bindings(a, bindings))
+                .collect(),
+            is_mutable_call: *is_mutable_call,
+        },
+        Expression::Call {
+            span_id,
+            func,
+            type_args,
+            args,
+        } => Expression::Call {
+            span_id: span_id.clone(),
+            func: func.clone(),
+            type_args: type_args.clone(),
+            args: args
+                .iter()
+                .map(|a| annotate_bindings(a, bindings))
+                .collect(),
+        },
+        Expression::FieldAccess { object, field } => Expression::FieldAccess {
+            object: annotate(object),
+            field: field.clone(),
+        },
+        Expression::Index { object, index } => Expression::Index {
+            object: annotate(object),
+            index: annotate(index),
+        },
+        Expression::Reference { is_mutable, expr } => Expression::Reference {
+            is_mutable: *is_mutable,
+            expr: annotate(expr),
+        },
+        Expression::Deref(inner) => Expression::Deref(annotate(inner)),
+        Expression::TupleLiteral(elements) => Expression::TupleLiteral(
+            elements
+                .iter()
+                .map(|e| annotate_bindings(e, bindings))
+                .collect(),
+        ),
+        _ => expr.clone(),
+    }
+}
@@ -2,7 +2,9 @@
 // Generic method instantiation for struct/enum methods
 // This enables monomorphization of generic methods like Vec<T>::push
 
+use super::super::functions::move_scanner::moved_locals;
 use super::super::*;
+use crate::type_system::const_generics;
 use inkwell::types::BasicTypeEnum;
 use inkwell::values::FunctionValue;
 use std::collections::HashMap;
@@ -17,7 +19,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
     /// * `struct_type_args` - Struct's type arguments (e.g., [I32])
     /// * `method_name` - Method name (e.g., "push")
     /// * `method_def` - Generic method AST definition
-    /// * `arg_types` - Argument types for additional type inference
+    /// * `method_type_args` - The method's own type arguments (e.g., [String] for map<U>)
     ///
     /// # Returns
     /// LLVM function value for the instantiated method
@@ -27,7 +29,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         struct_type_args: &[Type],
         method_name: &str,
         method_def: &Function,
-        _arg_types: &[Type],
+        method_type_args: &[Type],
     ) -> Result<FunctionValue<'ctx>, String> {
         eprintln!(
             "🔧 instantiate_generic_method: {}::<{}>::{}",
@@ -47,18 +49,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             .collect();
 
         // Build mangled name for this specific instantiation
-        // Format: StructName_TypeArgs_methodname
-        // Example: Vec_i32_push, HashMap_String_i32_insert
-        let mangled_name = if struct_type_args.is_empty() {
-            format!("{}_{}", struct_name, method_name)
-        } else {
-            format!(
-                "{}_{}_{}",
-                struct_name,
-                struct_type_names.join("_"),
-                method_name
-            )
-        };
+        // Example: Vec_i32_push, HashMap_String_i32_insert, Vec_i32_map_string
+        let mangled_name = self.generic_method_mangled_name(
+            struct_name,
+            struct_type_args,
+            method_name,
+            method_type_args,
+        );
 
         eprintln!("  → Mangled name: {}", mangled_name);
 
@@ -110,6 +107,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     }
                 }
             }
+
+            // Struct const params: Matrix<f32, 2, 3> binds R = 2, C = 3
+            if !struct_def.const_params.is_empty() {
+                let (_, const_args) =
+                    const_generics::split_generic_args(struct_name, struct_type_args)?;
+                type_subst.extend(const_generics::bind_const_params(
+                    struct_name,
+                    &struct_def.const_params,
+                    &const_args,
+                )?);
+            }
         } else {
             eprintln!(
                 "  ⚠️  Could not find struct definition for '{}'",
@@ -117,15 +125,25 @@ impl<'ctx> ASTCodeGen<'ctx> {
             );
         }
 
-        // Second: Substitute method-level type parameters if any
-        // For generic methods like map<U>(f: fn(T) -> U)
-        if !method_def.type_params.is_empty() {
-            // Try to infer method type params from argument types
-            // This is more complex and might need explicit type args in the future
+        // Second: Substitute method-level type parameters
+        // For generic methods like map<U>(f: fn(T): U), U comes from the call site
+        for (i, type_param) in method_def.type_params.iter().enumerate() {
+            let concrete_type = match (method_type_args.get(i), &type_param.default_type) {
+                (Some(provided_type), _) => provided_type.clone(),
+                (None, Some(default_type)) => default_type.clone(),
+                (None, None) => {
+                    return Err(format!(
+                        "Missing type argument for parameter '{}' in method '{}.{}'",
+                        type_param.name, struct_name, method_name
+                    ))
+                }
+            };
             eprintln!(
-                "  ⚠️  Method has {} type parameters - inference not fully implemented yet",
-                method_def.type_params.len()
+                "  📝 Method type param {} → {}",
+                type_param.name,
+                self.type_to_string(&concrete_type)
             );
+            type_subst.insert(type_param.name.clone(), concrete_type);
         }
 
         // Substitute all type parameters in the method definition
@@ -175,8 +193,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             // Validate method's own trait bounds
             if !method_def.type_params.is_empty() {
-                // For now, skip method-level bounds
-                // TODO: Implement method-level type param inference
+                checker.check_function_bounds(method_def, method_type_args)?;
+                if !method_def.where_clause.is_empty() {
+                    use crate::trait_bounds_checker::TraitBoundsChecker;
+                    let type_substitutions = TraitBoundsChecker::build_type_substitutions(
+                        &method_def.type_params,
+                        method_type_args,
+                    );
+                    checker.check_where_clause(&method_def.where_clause, &type_substitutions)?;
+                }
             }
         }
 
@@ -200,6 +225,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let saved_closure_variables = std::mem::take(&mut self.closure_variables);
         let saved_last_tuple_type = self.last_compiled_tuple_type.take();
         let saved_method_mutability = self.current_method_is_mutable;
+        let saved_moved_locals =
+            std::mem::replace(&mut self.moved_locals, moved_locals(&concrete_method.body));
 
         // Compile the method (declare_function already done above)
         // We need to compile the body separately
@@ -432,6 +459,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.closure_variables = saved_closure_variables;
         self.last_compiled_tuple_type = saved_last_tuple_type;
         self.current_method_is_mutable = saved_method_mutability;
+        self.moved_locals = saved_moved_locals;
 
         if let Some(block) = saved_insert_block {
             self.builder.position_at_end(block);
@@ -445,6 +473,31 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(fn_val)
     }
 
+    /// Mangled name of a generic method instantiation
+    ///
+    /// Struct type args come before the method name and the method's own after it:
+    /// Vec<i32>.map<string> is Vec_i32_map_string
+    pub(crate) fn generic_method_mangled_name(
+        &self,
+        struct_name: &str,
+        struct_type_args: &[Type],
+        method_name: &str,
+        method_type_args: &[Type],
+    ) -> String {
+        let mut mangled_name = struct_name.to_string();
+        for ty in struct_type_args {
+            mangled_name.push('_');
+            mangled_name.push_str(&self.type_to_string(ty));
+        }
+        mangled_name.push('_');
+        mangled_name.push_str(method_name);
+        for ty in method_type_args {
+            mangled_name.push('_');
+            mangled_name.push_str(&self.type_to_string(ty));
+        }
+        mangled_name
+    }
+
     /// Find a generic method definition from struct AST
     pub(crate) fn find_generic_method(
         &self,
@@ -607,7 +660,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         struct_name: &str,
         type_args: &[Type],
         method_name: &str,
-        _receiver: &Expression,
+        method_type_args: &[Type],
         receiver_val: BasicValueEnum<'ctx>,
         args: &[Expression],
     ) -> Result<Vec<BasicMetadataValueEnum<'ctx>>, String> {
@@ -616,13 +669,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let mut arg_vals: Vec<BasicMetadataValueEnum> = vec![];
 
         // Build mangled method name to look up function definition
-        let type_names: Vec<String> = type_args.iter().map(|t| self.type_to_string(t)).collect();
-
-        let mangled_name = if type_args.is_empty() {
-            format!("{}_{}", struct_name, method_name)
-        } else {
-            format!("{}_{}_{}", struct_name, type_names.join("_"), method_name)
-        };
+        let mangled_name =
+            self.generic_method_mangled_name(struct_name, type_args, method_name, method_type_args);
 
         // First argument is always the receiver (self)
         arg_vals.push(receiver_val.into());
@@ -683,6 +731,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             }
                             _ => self.compile_expression(arg)?,
                         }
+                    }
+                    // Closures take their parameter types from the instantiated signature
+                    else if matches!(arg, Expression::Closure { .. }) {
+                        let closure = self.closure_with_expected_types(arg, expected_ty);
+                        self.compile_expression(&closure)?
                     } else {
                         self.compile_expression(arg)?
                     }
@@ -856,6 +909,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let saved_closure_variables = std::mem::take(&mut self.closure_variables);
         let saved_last_tuple_type = self.last_compiled_tuple_type.take();
         let saved_method_mutability = self.current_method_is_mutable;
+        let saved_moved_locals = std::mem::take(&mut self.moved_locals);
 
         // Compile function body
         let compile_result = self.compile_function(&concrete_method);
@@ -877,6 +931,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.closure_variables = saved_closure_variables;
         self.last_compiled_tuple_type = saved_last_tuple_type;
         self.current_method_is_mutable = saved_method_mutability;
+        self.moved_locals = saved_moved_locals;
 
         if let Some(block) = saved_insert_block {
             self.builder.position_at_end(block);
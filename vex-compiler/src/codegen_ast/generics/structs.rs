@@ -2,14 +2,20 @@
 // Generic struct instantiation
 
 use super::super::*;
+use crate::type_system::const_generics;
 use std::collections::HashMap;
 
 impl<'ctx> ASTCodeGen<'ctx> {
     pub(crate) fn instantiate_generic_struct(
         &mut self,
         struct_name: &str,
-        type_args: &[Type],
+        generic_args: &[Type],
     ) -> Result<String, String> {
+        // Matrix<f32, 2, 3>: type args first, then values for the const params
+        let (type_args, const_args) =
+            const_generics::split_generic_args(struct_name, generic_args)?;
+        let type_args = type_args.as_slice();
+
         // uses MAX_GENERIC_DEPTH from super
         for type_arg in type_args {
             let depth = self.get_generic_depth(type_arg);
@@ -23,8 +29,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
-        let type_arg_strings: Vec<String> =
-            type_args.iter().map(|t| self.type_to_string(t)).collect();
+        let type_arg_strings: Vec<String> = generic_args
+            .iter()
+            .map(|t| self.type_to_string(t))
+            .collect();
         let cache_key = (struct_name.to_string(), type_arg_strings.clone());
 
         if let Some(mangled_name) = self.generic_instantiations.get(&cache_key) {
@@ -49,7 +57,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // ⭐ NEW: Check trait bounds before instantiation
         if let Some(ref mut checker) = self.trait_bounds_checker {
-            checker.check_struct_bounds(&struct_ast, type_args)?;
+            checker.check_struct_bounds(&struct_ast, generic_args)?;
             eprintln!(
                 "✅ Trait bounds validated for {}<{}>",
                 struct_name,
@@ -89,6 +97,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             type_subst.insert(param.name.clone(), concrete_type);
         }
 
+        // Bind const params: R = 2, C = 3 sizes [T; R * C] as [T; 6]
+        type_subst.extend(const_generics::bind_const_params(
+            struct_name,
+            &struct_ast.const_params,
+            &const_args,
+        )?);
+
         // Rebuild mangled name with all type args (including defaults)
         let all_type_args: Vec<Type> = struct_ast
             .type_params
@@ -105,16 +120,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let all_type_arg_strings: Vec<String> = all_type_args
             .iter()
             .map(|t| self.type_to_string(t))
+            .chain(const_args.iter().map(|value| value.to_string()))
             .collect();
 
-        // Include const params in mangled name if present
-        let mangled_name = if struct_ast.const_params.is_empty() {
-            format!("{}_{}", struct_name, all_type_arg_strings.join("_"))
-        } else {
-            // For now, const params are not specialized - they're just in signature
-            // Future: Add const values to mangled name when instantiated with concrete values
-            format!("{}_{}", struct_name, all_type_arg_strings.join("_"))
-        };
+        // Const values are part of the mangled name: Matrix_f32_2_3
+        let mangled_name = format!("{}_{}", struct_name, all_type_arg_strings.join("_"));
 
         let specialized_fields: Vec<(String, Type)> = struct_ast
             .fields
@@ -134,6 +144,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
         for (fname, fty) in &specialized_fields {
             eprintln!("   Field: {} : {:?}", fname, fty);
         }
+        for (fname, fty) in &specialized_fields {
+            self.check_array_lengths(fty)
+                .map_err(|e| format!("Field '{}.{}': {}", mangled_name, fname, e))?;
+        }
 
         // StructDef type lives in super (as in the original file's usage)
         use super::super::StructDef;
@@ -145,12 +159,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         );
 
         // ⭐ NEW: Instantiate all methods for this struct
-        self.instantiate_struct_methods(
-            struct_name,
-            &struct_ast.type_params,
-            type_args,
-            &mangled_name,
-        )?;
+        self.instantiate_struct_methods(struct_name, &type_subst, &mangled_name)?;
 
         self.generic_instantiations
             .insert(cache_key, mangled_name.clone());
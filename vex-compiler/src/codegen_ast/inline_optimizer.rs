@@ -41,6 +41,11 @@ impl<'ctx> InlineOptimizer<'ctx> {
     fn should_inline(&self, function: &FunctionValue<'ctx>) -> bool {
         let name = function.get_name().to_str().unwrap_or("");
 
+        // #[inline(never)] always wins
+        if self.has_enum_attribute(function, "noinline") {
+            return false;
+        }
+
         // 1. Stdlib wrappers (io::print → vex_print wrapper)
         if name.starts_with("__stdlib_") || name.starts_with("__wrapper_") {
             return true;
@@ -56,8 +61,10 @@ impl<'ctx> InlineOptimizer<'ctx> {
             return true;
         }
 
-        // 4. Check for inline metadata (future: #[inline(always)] attribute)
-        // TODO: Parse function attributes from AST
+        // 4. #[inline(always)] (declare_function sets alwaysinline from the AST)
+        if self.has_alwaysinline_attribute(function) {
+            return true;
+        }
 
         false
     }
@@ -170,10 +177,15 @@ impl<'ctx> InlineOptimizer<'ctx> {
 
     /// Check if function has alwaysinline attribute
     fn has_alwaysinline_attribute(&self, function: &FunctionValue<'ctx>) -> bool {
-        let alwaysinline_kind = Attribute::get_named_enum_kind_id("alwaysinline");
+        self.has_enum_attribute(function, "alwaysinline")
+    }
+
+    /// Check if function carries the named LLVM enum attribute
+    fn has_enum_attribute(&self, function: &FunctionValue<'ctx>, name: &str) -> bool {
+        let kind = Attribute::get_named_enum_kind_id(name);
 
         function
-            .get_enum_attribute(AttributeLoc::Function, alwaysinline_kind)
+            .get_enum_attribute(AttributeLoc::Function, kind)
             .is_some()
     }
 }
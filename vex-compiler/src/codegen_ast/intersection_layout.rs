@@ -0,0 +1,289 @@
+// Intersection type layout
+// `A & B` of structs is a record with the fields of both, registered as a struct
+// under a canonical name; contract-only intersections are a fat pointer
+
+use super::{ASTCodeGen, StructDef};
+use crate::type_system::conditional;
+use crate::type_system::intersection::{classify_intersection, flatten_members, IntersectionShape};
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::{BasicValueEnum, PointerValue};
+use vex_ast::{Expression, Item, Program, Type};
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Classify an intersection of resolved members against registered structs and contracts
+    pub(crate) fn intersection_shape(&self, members: &[Type]) -> Result<IntersectionShape, String> {
+        classify_intersection(
+            members,
+            |name| self.trait_defs.contains_key(name),
+            |name| self.struct_defs.get(name).map(|def| def.fields.clone()),
+        )
+    }
+
+    /// Struct name of a record intersection: Named & Timestamped -> Named_and_Timestamped
+    pub(crate) fn intersection_record_name(&self, members: &[Type]) -> String {
+        flatten_members(members)
+            .iter()
+            .map(|member| self.type_to_string(member))
+            .collect::<Vec<_>>()
+            .join("_and_")
+    }
+
+    /// `i32 & i32` is `i32` and a registered record is its struct; anything else
+    /// stays an intersection of resolved members
+    pub(crate) fn resolve_intersection(&self, members: &[Type]) -> Type {
+        let members: Vec<Type> = members.iter().map(|m| self.resolve_type(m)).collect();
+        match self.intersection_shape(&members) {
+            Ok(IntersectionShape::Value(value)) => value,
+            Ok(IntersectionShape::Record { .. }) => {
+                let name = self.intersection_record_name(&members);
+                if self.struct_defs.contains_key(&name) {
+                    Type::Named(name)
+                } else {
+                    Type::Intersection(flatten_members(&members))
+                }
+            }
+            _ => Type::Intersection(flatten_members(&members)),
+        }
+    }
+
+    /// LLVM layout of an intersection
+    ///
+    /// Records are plain structs of the merged fields. Contract-only intersections
+    /// are `{ ptr data, ptr vtable }`, the shape of a contract object.
+    pub(crate) fn intersection_llvm_type(&self, members: &[Type]) -> BasicTypeEnum<'ctx> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let members = match self.resolve_intersection(members) {
+            Type::Intersection(members) => members,
+            resolved => return self.ast_type_to_llvm(&resolved),
+        };
+
+        match self.intersection_shape(&members) {
+            Ok(IntersectionShape::Record { fields, .. }) => {
+                let field_types: Vec<BasicTypeEnum> = fields
+                    .iter()
+                    .map(|(_, field_ty)| self.ast_type_to_llvm(field_ty))
+                    .collect();
+                self.context.struct_type(&field_types, false).into()
+            }
+            Ok(_) => self
+                .context
+                .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+                .into(),
+            Err(e) => {
+                eprintln!("⚠️  Invalid intersection type: {}", e);
+                ptr_type.into()
+            }
+        }
+    }
+
+    /// Register the record structs of every intersection written in item signatures,
+    /// struct fields and aliases. Runs once all structs and contracts are registered.
+    pub(crate) fn register_intersection_layouts(
+        &mut self,
+        program: &Program,
+    ) -> Result<(), String> {
+        for item in &program.items {
+            match item {
+                Item::TypeAlias(alias) if alias.type_params.is_empty() => {
+                    self.register_intersection_type(&alias.ty)?;
+                    // `Stamped { .. }` literals and `v: Stamped` name the record directly
+                    if let Type::Named(record) = self.resolve_type(&alias.ty) {
+                        if let Some(members) = self.intersection_records.get(&record).cloned() {
+                            let def = self.struct_defs[&record].clone();
+                            self.struct_defs.insert(alias.name.clone(), def);
+                            self.intersection_records
+                                .insert(alias.name.clone(), members);
+                        }
+                    }
+                }
+                Item::Struct(struct_def) if struct_def.type_params.is_empty() => {
+                    for field in &struct_def.fields {
+                        self.register_intersection_type(&field.ty)?;
+                    }
+                    for method in &struct_def.methods {
+                        self.register_signature_intersections(method)?;
+                    }
+                }
+                Item::Function(func) => self.register_signature_intersections(func)?,
+                Item::TraitImpl(impl_block) => {
+                    for method in &impl_block.methods {
+                        self.register_signature_intersections(method)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn register_signature_intersections(&mut self, func: &vex_ast::Function) -> Result<(), String> {
+        for param in &func.params {
+            self.register_intersection_type(&param.ty)?;
+        }
+        if let Some(return_type) = &func.return_type {
+            self.register_intersection_type(return_type)?;
+        }
+        Ok(())
+    }
+
+    /// Register the record structs of the intersections inside `ty`, innermost first
+    pub(crate) fn register_intersection_type(&mut self, ty: &Type) -> Result<(), String> {
+        let mut found = Vec::new();
+        conditional::map_type(ty, &mut |t| {
+            if let Type::Intersection(members) = t {
+                found.push(members.clone());
+            }
+            None
+        });
+
+        for members in found.into_iter().rev() {
+            let members: Vec<Type> = members.iter().map(|m| self.resolve_type(m)).collect();
+            let shape = self.intersection_shape(&members).map_err(|e| {
+                format!(
+                    "Invalid intersection `{}`: {}",
+                    crate::type_checker::display_type(&Type::Intersection(members.clone())),
+                    e
+                )
+            })?;
+            if let IntersectionShape::Record { fields, .. } = shape {
+                let name = self.intersection_record_name(&members);
+                self.struct_defs
+                    .entry(name.clone())
+                    .or_insert(StructDef { fields });
+                self.intersection_records
+                    .insert(name, flatten_members(&members));
+            }
+        }
+        Ok(())
+    }
+
+    /// Source and target struct names when `expr` converts between a record and a
+    /// struct (or another record) by field name
+    pub(crate) fn record_conversion(
+        &self,
+        expr: &Expression,
+        expected: &Type,
+    ) -> Option<(String, String)> {
+        if self.intersection_records.is_empty() {
+            return None;
+        }
+        let Type::Named(to) = self.resolve_type(expected) else {
+            return None;
+        };
+        let Ok(Type::Named(from)) = self
+            .infer_expression_type(expr)
+            .map(|ty| self.resolve_type(&ty))
+        else {
+            return None;
+        };
+        let involves_record = self.intersection_records.contains_key(&from)
+            || self.intersection_records.contains_key(&to);
+        if from == to
+            || !involves_record
+            || !self.struct_defs.contains_key(&from)
+            || !self.struct_defs.contains_key(&to)
+        {
+            return None;
+        }
+        Some((from, to))
+    }
+
+    /// Build a `to` struct from the same-named fields of a `from` struct value
+    pub(crate) fn project_record(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        from: &str,
+        to: &str,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let from_fields = self
+            .struct_defs
+            .get(from)
+            .map(|def| def.fields.clone())
+            .ok_or_else(|| format!("Struct '{}' not found in registry", from))?;
+        let to_fields = self
+            .struct_defs
+            .get(to)
+            .map(|def| def.fields.clone())
+            .ok_or_else(|| format!("Struct '{}' not found in registry", to))?;
+        let from_type = self.ast_type_to_llvm(&Type::Named(from.to_string()));
+        let to_type = self
+            .ast_type_to_llvm(&Type::Named(to.to_string()))
+            .into_struct_type();
+
+        let source = match value {
+            BasicValueEnum::PointerValue(ptr) => self
+                .builder
+                .build_load(from_type, ptr, "record_source")
+                .map_err(|e| format!("Failed to load record: {}", e))?
+                .into_struct_value(),
+            BasicValueEnum::StructValue(source) => source,
+            other => {
+                return Err(format!(
+                    "Expected a struct value for `{}`, got {:?}",
+                    from, other
+                ))
+            }
+        };
+
+        let mut result = to_type.get_undef();
+        for (index, (field, _)) in to_fields.iter().enumerate() {
+            let from_index = from_fields
+                .iter()
+                .position(|(name, _)| name == field)
+                .ok_or_else(|| format!("Field '{}' of '{}' not found in '{}'", field, to, from))?;
+            let field_value = self
+                .builder
+                .build_extract_value(source, from_index as u32, field)
+                .map_err(|e| format!("Failed to read field '{}': {}", field, e))?;
+            result = self
+                .builder
+                .build_insert_value(result, field_value, index as u32, field)
+                .map_err(|e| format!("Failed to write field '{}': {}", field, e))?
+                .into_struct_value();
+        }
+        Ok(result.into())
+    }
+
+    /// Receiver for `record.method()`: methods the record does not have itself are
+    /// called on a copy of the member struct that declares them
+    pub(crate) fn intersection_method_receiver(
+        &mut self,
+        struct_name: String,
+        receiver: PointerValue<'ctx>,
+        method: &str,
+        args: &[Expression],
+    ) -> Result<(String, PointerValue<'ctx>), String> {
+        let Some(members) = self.intersection_records.get(&struct_name).cloned() else {
+            return Ok((struct_name, receiver));
+        };
+        if self.resolve_method_name(&struct_name, method, args).is_ok() {
+            return Ok((struct_name, receiver));
+        }
+
+        for member in members {
+            let Type::Named(member_name) = member else {
+                continue;
+            };
+            if !self.struct_defs.contains_key(&member_name)
+                || self
+                    .resolve_method_name(&member_name, method, args)
+                    .is_err()
+            {
+                continue;
+            }
+            let projected = self.project_record(receiver.into(), &struct_name, &member_name)?;
+            let copy = self
+                .builder
+                .build_alloca(
+                    projected.get_type(),
+                    &format!("{}_as_{}", struct_name, member_name),
+                )
+                .map_err(|e| format!("Failed to allocate member copy: {}", e))?;
+            self.builder
+                .build_store(copy, projected)
+                .map_err(|e| format!("Failed to store member copy: {}", e))?;
+            return Ok((member_name, copy));
+        }
+        Ok((struct_name, receiver))
+    }
+}
@@ -26,25 +26,8 @@ impl<'ctx> super::ASTCodeGen<'ctx> {
             None => builder.position_at_end(entry),
         }
 
-        // Special handling for Range types - allocate struct {i64, i64, i64}
-        let llvm_type = if let Type::Named(type_name) = ty {
-            if type_name == "Range" || type_name == "RangeInclusive" {
-                self.context
-                    .struct_type(
-                        &[
-                            self.context.i64_type().into(),
-                            self.context.i64_type().into(),
-                            self.context.i64_type().into(),
-                        ],
-                        false,
-                    )
-                    .into()
-            } else {
-                self.ast_type_to_llvm(ty)
-            }
-        } else {
-            self.ast_type_to_llvm(ty)
-        };
+        // Range types map to the VexRange layout in ast_type_to_llvm
+        let llvm_type = self.ast_type_to_llvm(ty);
 
         let alloca = builder
             .build_alloca(llvm_type, name)
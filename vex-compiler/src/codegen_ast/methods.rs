@@ -1,4 +1,5 @@
 // src/codegen/methods.rs
+use super::functions::move_scanner::moved_locals;
 use super::*;
 use inkwell::types::BasicTypeEnum;
 
@@ -259,6 +260,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         let fn_val = self.module.add_function(&mangled_name, fn_type, None);
         eprintln!("🔧 Registering inline method: {} (functions map)", mangled_name);
+        self.apply_function_attributes(method, fn_val);
         self.functions.insert(mangled_name.clone(), fn_val);
 
         let mut mangled_method = method.clone();
@@ -368,6 +370,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.variable_types.clear();
         self.variable_struct_names.clear();
         self.variable_ast_types.clear();
+        self.moved_locals = moved_locals(&method.body);
 
         let param_offset;
 
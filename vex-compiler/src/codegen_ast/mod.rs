@@ -4,6 +4,10 @@
 // Compiler limits
 pub(crate) const MAX_GENERIC_DEPTH: usize = 64; // Maximum nesting depth for generic types (Rust uses 128)
 
+// Option<T> tags, shared by literals, the Some/None builtins, match, `??` and the niche
+pub(crate) const OPTION_SOME_TAG: u64 = 0;
+pub(crate) const OPTION_NONE_TAG: u64 = 1;
+
 use crate::diagnostics::DiagnosticEngine;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
@@ -18,9 +22,11 @@ mod associated_types; // Associated types resolution
 pub mod builtins; // Now a directory module
 mod compilation; // Compilation and code generation utilities
 mod constants;
+mod contract_objects; // dyn Contract fat pointers and vtables
 mod destructors; // Automatic destructors (RAII/Drop trait)
 mod diagnostic_helpers; // Diagnostic helper methods for error reporting
 mod drop_trait; // Drop trait automatic cleanup (RAII)
+mod enum_layout; // Data-carrying enum layout, payload drop and Option niche
 mod expressions;
 mod ffi;
 mod ffi_bridge;
@@ -37,11 +43,13 @@ pub mod program;
 pub mod registry;
 mod scope_management; // Scope and cleanup management
 mod statements;
+mod intersection_layout; // Record intersections and contract-set layouts
 mod string_conversion; // String conversion and formatting
 mod struct_def;
 pub mod traits;
 mod type_analysis; // Type analysis and expression handling
 mod types; // functions/{declare,compile,asynchronous}.rs // ASTCodeGen struct definition
+mod union_layout; // Union tags, member wrapping and `is` narrowing
 
 pub use struct_def::*;
 
@@ -92,8 +100,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
             function_defs: HashMap::new(),
             struct_ast_defs: HashMap::new(),
             struct_defs: HashMap::new(),
+            intersection_records: HashMap::new(),
             enum_ast_defs: HashMap::new(),
             type_aliases: HashMap::new(),
+            type_alias_params: HashMap::new(),
             generic_instantiations: HashMap::new(),
             trait_defs: HashMap::new(),
             trait_impls: HashMap::new(),
@@ -105,6 +115,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
             namespace_imports: HashMap::new(), // ⭐ NEW: Namespace import aliases
             module_constants: HashMap::new(),  // ⭐ NEW: Module constant registry
             module_constant_types: HashMap::new(), // ⭐ NEW: Module constant type tracking
+            const_values: HashMap::new(),
+            const_sizes: HashMap::new(),
             builtins: BuiltinRegistry::new(),
             current_function: None,
             current_function_return_type: None,
@@ -114,7 +126,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
             closure_envs: HashMap::new(),
             closure_variables: HashMap::new(),
             closure_types: HashMap::new(),
+            closure_env_types: HashMap::new(),
+            closure_capture_modes: HashMap::new(),
             scope_stack: Vec::new(),
+            moved_locals: Default::default(),
+            drop_flags: HashMap::new(),
             last_compiled_tuple_type: None,
             last_compiled_array_ptr: None,
             current_method_is_mutable: false, // ⭐ NEW: Default to immutable
@@ -123,16 +139,26 @@ impl<'ctx> ASTCodeGen<'ctx> {
             span_map,                         // ⭐ NEW: Store span map from parser
             trait_bounds_checker: None,       // ⭐ NEW: Initialized in compile_program
             source_file: source_file.to_string(), // ⭐ NEW: Store source file path
+            type_table: None,                     // Set by the driver after type checking
+            overflow_checks: true,                // Cleared by the driver for optimized builds
+            match_union_members: None,
+            contract_vtables: HashMap::new(),
             type_interner: crate::types::interner::TypeInterner::new(), // ⭐ NEW: Type interning for performance
             global_runtime: None, // ⭐ ASYNC: Initialize runtime handle as None
             async_block_counter: 0, // ⭐ ASYNC BLOCKS: Counter for unique names
+            go_task_counter: 0, // ⭐ GO: Counter for unique task names
+            runtime_in_background: false, // ⭐ GO: Set when go statements exist
             async_state_stack: Vec::new(), // ⭐ ASYNC STATE MACHINE: State tracking
             async_state_counter: 0, // ⭐ ASYNC STATE MACHINE: State ID counter
             current_async_resume_fn: None, // ⭐ ASYNC STATE MACHINE: Resume function
             async_resume_blocks: Vec::new(), // ⭐ ASYNC STATE MACHINE: Pre-allocated resume blocks
             async_context: None,  // ⭐ ASYNC: Current async context
             suppress_diagnostics: false, // ⭐ NEW: Default to false
+            target_data: Self::native_target_data(),
         };
+        codegen
+            .module
+            .set_data_layout(&codegen.target_data.get_data_layout());
 
         // Register Phase 0 builtin types (Vec, Option, Result, Box)
         // Pre-declare external C runtime functions for zero-overhead linking
@@ -152,6 +178,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.module_namespaces.insert(module_name, functions);
     }
 
+    /// Provide the type checker's results so inference can use checked expression types
+    pub fn set_type_table(&mut self, table: crate::type_checker::TypeTable) {
+        self.type_table = Some(table);
+    }
+
+    /// Trap on integer overflow (debug builds) or let arithmetic wrap (release builds)
+    pub fn set_overflow_checks(&mut self, enabled: bool) {
+        self.overflow_checks = enabled;
+    }
+
     /// Get reference to diagnostic engine for printing/checking diagnostics
     pub fn diagnostics(&self) -> &DiagnosticEngine {
         &self.diagnostics
@@ -362,14 +398,26 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 format!("_arr{}{}", size, self.generate_type_suffix(elem))
             }
 
-            // Const array: [T; N] -> _arrN_T
-            Type::ConstArray {
-                elem_type,
-                size_param,
-            } => {
-                format!("_arr{}{}", size_param, self.generate_type_suffix(elem_type))
+            // Const array: [T; N] -> _arrN_T, [T; R * C] -> _arrRxC_T
+            Type::ConstArray { elem_type, size } => {
+                let size: String = crate::type_system::const_generics::const_expr_to_string(size)
+                    .chars()
+                    .filter_map(|c| match c {
+                        '+' => Some('p'),
+                        '-' => Some('m'),
+                        '*' => Some('x'),
+                        '/' => Some('d'),
+                        '%' => Some('r'),
+                        c if c.is_ascii_alphanumeric() || c == '_' => Some(c),
+                        _ => None,
+                    })
+                    .collect();
+                format!("_arr{}{}", size, self.generate_type_suffix(elem_type))
             }
 
+            // Const generic argument: Matrix<f32, 3> -> _3
+            Type::ConstValue(value) => format!("_{}", value),
+
             // Slice types: &[T] -> _slice_T, &![T] -> _slicemut_T
             Type::Slice(elem, is_mutable) => {
                 let mut suffix = if *is_mutable {
@@ -458,6 +506,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 format!("{}__{}", self.generate_type_suffix(self_type), name)
             }
 
+            // Contract objects: dyn Shape -> _dynShape
+            Type::Dyn(contract) => format!("_dyn{}", contract),
+
             // Complex types - fallback to empty (skip in mangling)
             Type::Conditional { .. } => String::new(),
             Type::Infer(_) => String::new(),
@@ -545,8 +545,35 @@ impl<'ctx> ASTCodeGen<'ctx> {
             eprintln!("🔄 Async functions detected - runtime will be initialized in main");
         }
 
+        // ⭐ GO: go statements spawn onto the runtime, which then runs alongside main()
+        use super::functions::await_scanner::{contains_go_statement, count_await_points};
+        let has_go = merged_program.items.iter().any(|item| match item {
+            Item::Function(f) => contains_go_statement(&f.body),
+            Item::Struct(s) => s.methods.iter().any(|m| contains_go_statement(&m.body)),
+            Item::TraitImpl(i) => i.methods.iter().any(|m| contains_go_statement(&m.body)),
+            _ => false,
+        });
+
+        // ⭐ ASYNC: `await` in synchronous code blocks the caller until a worker
+        // completes the future (a blocking select until a task uses a channel),
+        // so the workers must already be running.
+        // Methods and contract default bodies count as much as free functions.
+        let blocking_await = |f: &Function| !f.is_async && count_await_points(&f.body) > 0;
+        let has_blocking_await = merged_program.items.iter().any(|item| match item {
+            Item::Function(f) => blocking_await(f),
+            Item::Struct(s) => s.methods.iter().any(blocking_await),
+            Item::TraitImpl(i) => i.methods.iter().any(blocking_await),
+            Item::Contract(c) => c
+                .methods
+                .iter()
+                .filter_map(|m| m.body.as_ref())
+                .any(|body| count_await_points(body) > 0),
+            _ => false,
+        });
+        self.runtime_in_background = has_go || has_blocking_await;
+
         // ⭐ ASYNC: Track if we need runtime initialization
-        let needs_runtime_init = has_async;
+        let needs_runtime_init = has_async || has_go;
 
         // Initialize trait bounds checker
         use crate::trait_bounds_checker::TraitBoundsChecker;
@@ -565,6 +592,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     if let Err(e) = trait_checker.validate_const_params(&struct_def.const_params) {
                         return Err(format!("Struct '{}': {}", struct_def.name, e));
                     }
+                    for method in &struct_def.methods {
+                        if let Err(e) = trait_checker.validate_const_params(&method.const_params) {
+                            return Err(format!(
+                                "Method '{}.{}': {}",
+                                struct_def.name, method.name, e
+                            ));
+                        }
+                    }
                 }
                 _ => {}
             }
@@ -572,6 +607,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         self.trait_bounds_checker = Some(trait_checker);
 
+        // Fold constant initializers first so constants can size array types
+        self.evaluate_constants(&merged_program)?;
+
         // First pass: register types, constants, and function signatures
         let mut constants_to_compile = Vec::new();
         for item in &merged_program.items {
@@ -597,6 +635,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
+        // Record intersections need every struct and contract registered first
+        self.register_intersection_layouts(&merged_program)?;
+
         // Compile constants with dependency resolution (multi-pass)
         let mut compiled_constants = std::collections::HashSet::new();
         let mut progress = true;
@@ -835,7 +876,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     // Check if the struct is generic
                     struct_name
                         .and_then(|name| self.struct_ast_defs.get(name))
-                        .map(|s| !s.type_params.is_empty())
+                        .map(|s| !s.type_params.is_empty() || !s.const_params.is_empty())
                         .unwrap_or(false)
                 } else {
                     false
@@ -869,7 +910,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     struct_def.type_params.len(),
                     struct_def.methods.len()
                 );
-                if struct_def.type_params.is_empty() {
+                if struct_def.type_params.is_empty() && struct_def.const_params.is_empty() {
                     for method in &struct_def.methods {
                         eprintln!("  🔧 Declaring method: {}.{}", struct_def.name, method.name);
                         self.declare_struct_method(&struct_def.name, method)?;
@@ -908,7 +949,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // MUST come before function bodies, as generic instantiation may need these methods
         for item in &merged_program.items {
             if let Item::Struct(struct_def) = item {
-                if struct_def.type_params.is_empty() {
+                if struct_def.type_params.is_empty() && struct_def.const_params.is_empty() {
                     for method in &struct_def.methods {
                         self.compile_struct_method(&struct_def.name, method)?;
                     }
@@ -966,7 +1007,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     // Check if the struct is generic
                     struct_name
                         .and_then(|name| self.struct_ast_defs.get(name))
-                        .map(|s| !s.type_params.is_empty())
+                        .map(|s| !s.type_params.is_empty() || !s.const_params.is_empty())
                         .unwrap_or(false)
                 } else {
                     false
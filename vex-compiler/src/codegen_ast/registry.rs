@@ -5,12 +5,23 @@ use crate::diagnostics::{error_codes, Diagnostic, ErrorLevel, Span};
 impl<'ctx> ASTCodeGen<'ctx> {
     pub(crate) fn register_type_alias(&mut self, type_alias: &TypeAlias) -> Result<(), String> {
         // Register ALL type aliases including generic ones
-        // Generic aliases will be monomorphized during type resolution
-        let resolved_type = self.resolve_type(&type_alias.ty);
-        self.type_aliases
-            .insert(type_alias.name.clone(), resolved_type);
-
-        if !type_alias.type_params.is_empty() {
+        // Generic aliases keep their body unresolved; resolve_type substitutes the
+        // arguments at each use so conditional types see concrete check types
+        if type_alias.type_params.is_empty() {
+            let resolved_type = self.resolve_type(&type_alias.ty);
+            self.type_aliases
+                .insert(type_alias.name.clone(), resolved_type);
+        } else {
+            self.type_aliases
+                .insert(type_alias.name.clone(), type_alias.ty.clone());
+            self.type_alias_params.insert(
+                type_alias.name.clone(),
+                type_alias
+                    .type_params
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .collect(),
+            );
             eprintln!(
                 "📋 Registered generic type alias: {} with {} params",
                 type_alias.name,
@@ -69,10 +80,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
             );
         }
 
-        if !struct_def.type_params.is_empty() {
+        if !struct_def.type_params.is_empty() || !struct_def.const_params.is_empty() {
             return Ok(());
         }
 
+        for field in &struct_def.fields {
+            self.check_array_lengths(&field.ty)
+                .map_err(|e| format!("Field '{}.{}': {}", struct_def.name, field.name, e))?;
+        }
+
         let fields: Vec<(String, Type)> = struct_def
             .fields
             .iter()
@@ -136,6 +152,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         let key = (trait_impl.trait_name.clone(), type_name.clone());
         self.trait_impls.insert(key, trait_impl.methods.clone());
+        self.register_associated_type_bindings(&type_name, &trait_impl.associated_type_bindings);
 
         for method in &trait_impl.methods {
             self.declare_trait_impl_method(&trait_impl.trait_name, &trait_impl.for_type, method)?;
@@ -210,6 +227,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         let fn_val = self.module.add_function(&mangled_name, fn_type, None);
         self.functions.insert(mangled_name.clone(), fn_val);
+        self.apply_function_attributes(method, fn_val);
 
         let mut mangled_method = method.clone();
         mangled_method.name = mangled_name.clone();
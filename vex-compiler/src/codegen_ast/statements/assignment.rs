@@ -43,7 +43,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
         target: &Expression,
         value: &Expression,
     ) -> Result<(), String> {
-        let val = self.compile_expression(value)?;
+        // Assigning to a union variable wraps the value with its member tag
+        let union_target = match target {
+            Expression::Ident(name) => self
+                .variable_ast_types
+                .get(name)
+                .filter(|ty| self.union_members(ty).is_some())
+                .cloned(),
+            _ => None,
+        };
+        let val = self.compile_expression_with_type(value, union_target.as_ref())?;
 
         match target {
             // Simple variable assignment: x = value
@@ -265,8 +274,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     }
                 }
 
-                // Array indexing - not yet supported
-                return Err("Array index assignment not yet supported".to_string());
+                // Array and slice element assignment: arr[i] = value, view[i] = value
+                let elem_ptr = self.get_index_pointer(object, index)?;
+                self.builder
+                    .build_store(elem_ptr, val)
+                    .map_err(|e| format!("Failed to store element: {}", e))?;
             }
 
             // Pointer dereference assignment: *ptr = value
@@ -337,6 +349,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         target: &Expression,
         op: &CompoundOp,
         value: &Expression,
+        span_id: Option<&String>,
     ) -> Result<(), String> {
         // Compound assignment: x += expr
         // Strategy:
@@ -363,6 +376,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                         &[], // No generic type args
                         &vec![value.clone()],
                         false,
+                        None,
                     )?;
 
                     // Store result back to target
@@ -402,22 +416,25 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Perform the operation based on type
         let result: BasicValueEnum = if current_val.is_int_value() {
             let lhs = current_val.into_int_value();
+            let is_signed = !self.is_unsigned_integer_type(&target_type);
             let rhs = rhs_val.into_int_value();
+            let rhs = if rhs.get_type() != lhs.get_type() {
+                self.builder
+                    .build_int_cast_sign_flag(rhs, lhs.get_type(), is_signed, "rhs_cast")
+                    .map_err(|e| format!("Failed to cast compound operand: {}", e))?
+            } else {
+                rhs
+            };
+            let location = self.span_location(span_id);
             match op {
                 CompoundOp::Add => self
-                    .builder
-                    .build_int_add(lhs, rhs, "add_tmp")
-                    .map_err(|e| format!("Failed to build add: {}", e))?
+                    .build_int_arith(&BinaryOp::Add, lhs, rhs, is_signed, location.as_deref())?
                     .into(),
                 CompoundOp::Sub => self
-                    .builder
-                    .build_int_sub(lhs, rhs, "sub_tmp")
-                    .map_err(|e| format!("Failed to build sub: {}", e))?
+                    .build_int_arith(&BinaryOp::Sub, lhs, rhs, is_signed, location.as_deref())?
                     .into(),
                 CompoundOp::Mul => self
-                    .builder
-                    .build_int_mul(lhs, rhs, "mul_tmp")
-                    .map_err(|e| format!("Failed to build mul: {}", e))?
+                    .build_int_arith(&BinaryOp::Mul, lhs, rhs, is_signed, location.as_deref())?
                     .into(),
                 CompoundOp::Div => self
                     .builder
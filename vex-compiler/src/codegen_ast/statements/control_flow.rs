@@ -22,11 +22,19 @@ impl<'ctx> ASTCodeGen<'ctx> {
             if func_name.ends_with("_resume") {
                 eprintln!("🔄 Async resume function return - returning CORO_STATUS_DONE");
 
-                // TODO: Store the actual return value in the state struct for Future<T>
-                // For now, just ignore it and return DONE status
+                // The returned value becomes the result of the Future<T>
+                let result_type = self
+                    .async_context
+                    .as_ref()
+                    .and_then(|ctx| ctx.result_type.clone());
+                let return_val = match expr {
+                    Some(e) => Some(self.compile_expression_with_type(e, result_type.as_ref())?),
+                    None => None,
+                };
 
                 // Execute deferred statements before returning
                 self.execute_deferred_statements()?;
+                self.complete_async_future(return_val)?;
 
                 let done_status = self.context.i32_type().const_int(2, false);
                 self.builder
@@ -41,6 +49,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
         let expected_return_type = self.current_function_return_type.clone();
         let return_val = if let Some(e) = expr {
             let val = self.compile_expression_with_type(e, expected_return_type.as_ref())?;
+            // A returned closure must not point into this frame
+            let val = self.escape_returned_closure(e, val)?;
 
             eprintln!(
                 "🔄 Return statement compiled expression: is_pointer={}, is_struct={}",
@@ -85,44 +95,29 @@ impl<'ctx> ASTCodeGen<'ctx> {
             None
         };
 
-        // Pop scope and emit automatic cleanup AFTER computing value
-        self.pop_scope()?;
-
-        // Execute deferred statements in reverse order before returning
-        self.execute_deferred_statements()?;
-
-        // ⭐ ASYNC: If returning from main() with runtime, call runtime_run() and runtime_destroy()
-        // BUT only if we're in the actual main() function, not in an async resume function
+        // ⭐ ASYNC: Returning from main() with a runtime waits for it to drain first,
+        // BEFORE main's cleanup so spawned tasks never see dropped locals.
+        // Only in the actual main() function, not in an async resume function or go task.
         if let Some(func) = self.current_function {
             let func_name = func.get_name().to_str().unwrap_or("");
             if func_name == "main" && self.global_runtime.is_some() {
-                eprintln!(
-                    "🔄 Intercepting main() return - adding runtime_run() and runtime_destroy()"
-                );
-
-                // Load runtime from global: Runtime* rt = __vex_global_runtime;
-                let global_runtime_var = self.global_runtime.unwrap();
-                let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
-                let runtime_ptr = self
-                    .builder
-                    .build_load(ptr_type, global_runtime_var, "runtime_load")
-                    .map_err(|e| format!("Failed to load runtime: {}", e))?
-                    .into_pointer_value();
-
-                // void runtime_run(Runtime* runtime);
-                let runtime_run = self.get_or_declare_runtime_run();
-                self.builder
-                    .build_call(runtime_run, &[runtime_ptr.into()], "run_runtime")
-                    .map_err(|e| format!("Failed to call runtime_run: {}", e))?;
-
-                // void runtime_destroy(Runtime* runtime);
-                let runtime_destroy = self.get_or_declare_runtime_destroy();
-                self.builder
-                    .build_call(runtime_destroy, &[runtime_ptr.into()], "destroy_runtime")
-                    .map_err(|e| format!("Failed to call runtime_destroy: {}", e))?;
+                eprintln!("🔄 Intercepting main() return - draining async runtime");
+                self.emit_main_runtime_drain()?;
             }
         }
 
+        // A returned make/new/error.new binding (or enum payload) is owned by the caller now
+        if let Some(Expression::Ident(name)) = expr {
+            let copied_out = matches!(return_val, Some(BasicValueEnum::StructValue(_)));
+            self.forget_builtin_drop(name, copied_out)?;
+        }
+
+        // Pop scope and emit automatic cleanup AFTER computing value
+        self.pop_scope()?;
+
+        // Execute deferred statements in reverse order before returning
+        self.execute_deferred_statements()?;
+
         // Build return instruction
         if let Some(mut val) = return_val {
             if self.current_function_returns_any() {
@@ -289,15 +284,6 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(())
     }
 
-    /// Compile go statement: go { ... } or go func()
-    /// For now, just execute the expression/block directly (no actual async spawning yet)
-    pub(crate) fn compile_go_statement(&mut self, expr: &Expression) -> Result<(), String> {
-        // TODO: Implement actual async task spawning
-        // For now, just compile the expression directly
-        self.compile_expression(expr)?;
-        Ok(())
-    }
-
     fn current_function_returns_any(&self) -> bool {
         matches!(self.current_function_return_type.as_ref(), Some(Type::Any))
     }
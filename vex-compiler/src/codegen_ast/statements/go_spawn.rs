@@ -0,0 +1,232 @@
+// statements/go_spawn.rs
+// go statement: body lowered to a coroutine task handed to the M:N scheduler
+
+use super::ASTCodeGen;
+use inkwell::types::BasicTypeEnum;
+use inkwell::AddressSpace;
+use vex_ast::*;
+
+/// Mirrors CORO_STATUS_DONE in async_runtime/include/runtime.h
+const CORO_STATUS_DONE: u64 = 2;
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Compile go statement: go { ... } or go worker(ch)
+    ///
+    /// The expression becomes the body of `__go_task_N(WorkerContext*, void* env)`.
+    /// Captured variables are moved (bitwise) into a malloc'd environment struct that
+    /// the task unpacks and frees on entry, so the task never touches the spawning
+    /// frame. The spawning scope forgets their drops; the task drops them on exit.
+    /// Inside an async resume function the task goes to the current worker's
+    /// local queue, otherwise to the global queue of `__vex_global_runtime`.
+    pub(crate) fn compile_go_statement(&mut self, expr: &Expression) -> Result<(), String> {
+        let global_runtime = self
+            .global_runtime
+            .ok_or("go statement requires the async runtime (not initialized)")?;
+
+        let task_name = format!("__go_task_{}", self.go_task_counter);
+        self.go_task_counter += 1;
+
+        // Step 1: Collect captures and lay out the environment struct
+        let captures = self.find_free_variables(expr, &[]);
+        let mut capture_types: Vec<BasicTypeEnum<'ctx>> = Vec::with_capacity(captures.len());
+        for name in &captures {
+            let ty = self
+                .variable_types
+                .get(name)
+                .ok_or_else(|| format!("Cannot find type for variable captured by go: {}", name))?;
+            capture_types.push(*ty);
+        }
+        let env_type = self.context.struct_type(&capture_types, false);
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        // Step 2: Move captured values into a heap environment
+        let env_ptr = if captures.is_empty() {
+            ptr_type.const_null()
+        } else {
+            let env_size = env_type
+                .size_of()
+                .ok_or("Failed to compute size of go task environment")?;
+            let malloc_fn = self.get_or_declare_malloc();
+            let env_ptr = self
+                .builder
+                .build_call(malloc_fn, &[env_size.into()], "go_env")
+                .map_err(|e| format!("Failed to allocate go task environment: {}", e))?
+                .try_as_basic_value()
+                .unwrap_basic()
+                .into_pointer_value();
+
+            for (idx, name) in captures.iter().enumerate() {
+                let var_ptr = *self
+                    .variables
+                    .get(name)
+                    .ok_or_else(|| format!("Captured variable not found: {}", name))?;
+                let value = self
+                    .builder
+                    .build_load(capture_types[idx], var_ptr, name)
+                    .map_err(|e| format!("Failed to load {} for go task: {}", name, e))?;
+                let field_ptr = self
+                    .builder
+                    .build_struct_gep(env_type, env_ptr, idx as u32, &format!("go_env_{}", name))
+                    .map_err(|e| format!("Failed to GEP go environment: {}", e))?;
+                self.builder
+                    .build_store(field_ptr, value)
+                    .map_err(|e| format!("Failed to move {} into go task: {}", name, e))?;
+                // The task owns the value now; the spawning scope must not drop it
+                self.forget_moved_binding(name);
+            }
+            env_ptr
+        };
+
+        // Step 3: Declare the task and hand it to the scheduler
+        // CoroStatus (*coro_resume_func)(WorkerContext* context, void* coro_data);
+        let task_fn_type = self
+            .context
+            .i32_type()
+            .fn_type(&[ptr_type.into(), ptr_type.into()], false);
+        let task_fn = self.module.add_function(&task_name, task_fn_type, None);
+        let task_fn_ptr = task_fn.as_global_value().as_pointer_value();
+
+        if let Some(resume_fn) = self.current_async_resume_fn {
+            // void worker_spawn_local(WorkerContext* context, coro_resume_func fn, void* data);
+            let worker_ctx = resume_fn
+                .get_nth_param(0)
+                .ok_or("Async resume function missing worker context")?
+                .into_pointer_value();
+            let spawn_local = self.get_or_declare_worker_spawn_local();
+            self.builder
+                .build_call(
+                    spawn_local,
+                    &[worker_ctx.into(), task_fn_ptr.into(), env_ptr.into()],
+                    "",
+                )
+                .map_err(|e| format!("Failed to call worker_spawn_local: {}", e))?;
+        } else {
+            let runtime = self
+                .builder
+                .build_load(ptr_type, global_runtime, "runtime_load")
+                .map_err(|e| format!("Failed to load runtime: {}", e))?
+                .into_pointer_value();
+            let spawn_global = self.get_or_declare_runtime_spawn();
+            self.builder
+                .build_call(
+                    spawn_global,
+                    &[runtime.into(), task_fn_ptr.into(), env_ptr.into()],
+                    "",
+                )
+                .map_err(|e| format!("Failed to call runtime_spawn_global: {}", e))?;
+        }
+
+        // Step 4: Generate the task body
+        let resume_block = self
+            .builder
+            .get_insert_block()
+            .ok_or("No insertion block for go statement")?;
+        self.compile_go_task_body(task_fn, expr, &captures, &capture_types, env_type)?;
+        self.builder.position_at_end(resume_block);
+
+        Ok(())
+    }
+
+    /// Emit `__go_task_N`: unpack the environment into locals, run the expression,
+    /// report CORO_STATUS_DONE. Codegen state of the spawning function is saved and
+    /// restored around it, the same way closures are compiled.
+    fn compile_go_task_body(
+        &mut self,
+        task_fn: inkwell::values::FunctionValue<'ctx>,
+        expr: &Expression,
+        captures: &[String],
+        capture_types: &[BasicTypeEnum<'ctx>],
+        env_type: inkwell::types::StructType<'ctx>,
+    ) -> Result<(), String> {
+        let saved_fn = self.current_function.replace(task_fn);
+        let saved_resume_fn = self.current_async_resume_fn.take();
+        let saved_async_context = self.async_context.take();
+        let saved_state_stack = std::mem::take(&mut self.async_state_stack);
+        let saved_resume_blocks = std::mem::take(&mut self.async_resume_blocks);
+        let saved_deferred = std::mem::take(&mut self.deferred_statements);
+        let saved_loops = std::mem::take(&mut self.loop_context_stack);
+        let saved_return_type = self.current_function_return_type.take();
+        let saved_variables = self.variables.clone();
+        let saved_variable_types = self.variable_types.clone();
+        let saved_variable_ast_types = self.variable_ast_types.clone();
+
+        let entry = self.context.append_basic_block(task_fn, "entry");
+        self.builder.position_at_end(entry);
+
+        if !captures.is_empty() {
+            let env_ptr = task_fn
+                .get_nth_param(1)
+                .ok_or("go task missing environment parameter")?
+                .into_pointer_value();
+            env_ptr.set_name("env");
+
+            for (idx, name) in captures.iter().enumerate() {
+                let field_ptr = self
+                    .builder
+                    .build_struct_gep(env_type, env_ptr, idx as u32, &format!("{}_ptr", name))
+                    .map_err(|e| format!("Failed to GEP go environment: {}", e))?;
+                let value = self
+                    .builder
+                    .build_load(capture_types[idx], field_ptr, name)
+                    .map_err(|e| format!("Failed to load captured {}: {}", name, e))?;
+                let local = self
+                    .builder
+                    .build_alloca(capture_types[idx], &format!("{}_local", name))
+                    .map_err(|e| format!("Failed to allocate captured {}: {}", name, e))?;
+                self.builder
+                    .build_store(local, value)
+                    .map_err(|e| format!("Failed to store captured {}: {}", name, e))?;
+                self.variables.insert(name.clone(), local);
+                self.variable_types.insert(name.clone(), capture_types[idx]);
+            }
+
+            // Environment is owned by the task: release it once unpacked
+            let free_fn = self.get_or_declare_free();
+            self.builder
+                .build_call(free_fn, &[env_ptr.into()], "")
+                .map_err(|e| format!("Failed to free go task environment: {}", e))?;
+        }
+
+        let scope_depth = self.scope_stack.len();
+        self.push_scope();
+        // Captured Drop values are released when the task finishes
+        for name in captures {
+            if let Some(ast_type) = self.variable_ast_types.get(name).cloned() {
+                self.register_drop_variable(name, &ast_type);
+            }
+        }
+        let result = self.compile_expression(expr);
+
+        let finish = result.and_then(|_| {
+            let needs_terminator = self
+                .builder
+                .get_insert_block()
+                .is_some_and(|block| block.get_terminator().is_none());
+            if needs_terminator {
+                self.pop_scope()?;
+                self.execute_deferred_statements()?;
+                let done = self.context.i32_type().const_int(CORO_STATUS_DONE, false);
+                self.builder
+                    .build_return(Some(&done))
+                    .map_err(|e| format!("Failed to build go task return: {}", e))?;
+            } else {
+                self.scope_stack.truncate(scope_depth);
+            }
+            Ok(())
+        });
+
+        self.current_function = saved_fn;
+        self.current_async_resume_fn = saved_resume_fn;
+        self.async_context = saved_async_context;
+        self.async_state_stack = saved_state_stack;
+        self.async_resume_blocks = saved_resume_blocks;
+        self.deferred_statements = saved_deferred;
+        self.loop_context_stack = saved_loops;
+        self.current_function_return_type = saved_return_type;
+        self.variables = saved_variables;
+        self.variable_types = saved_variable_types;
+        self.variable_ast_types = saved_variable_ast_types;
+
+        finish
+    }
+}
@@ -57,6 +57,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
+        // Record intersections written only in this annotation: let v: A & B = ...
+        // From here on the annotation names the record's struct
+        if let Some(annotation) = ty {
+            self.check_array_lengths(annotation)?;
+            self.register_intersection_type(annotation)?;
+        }
+        let record_annotation = match ty {
+            Some(annotation @ Type::Intersection(_)) => Some(self.resolve_type(annotation)),
+            _ => None,
+        };
+        let ty = record_annotation.as_ref().or(ty);
+
         // Step 1: Infer struct name from expression if no type annotation
         let struct_name_from_expr = self.infer_struct_name_from_expression(ty, value)?;
         eprintln!("🔵 struct_name_from_expr: {:?}", struct_name_from_expr);
@@ -73,14 +85,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Step 4: Compile the value expression
         let val = self.compile_value_expression(ty, &adjusted_value, name, is_mutable)?;
+        let val = self.bind_closure_object(name, &adjusted_value, val)?;
 
         // Step 4.5: Check if this is a closure and register it with its environment
         if let Expression::Closure {
             params,
             return_type,
+            capture_mode,
             ..
         } = adjusted_value
         {
+            self.closure_capture_modes
+                .insert(name.clone(), capture_mode.clone());
             if let BasicValueEnum::PointerValue(fn_ptr) = val {
                 // Check if this closure has an environment in closure_envs
                 if let Some(env_ptr) = self.closure_envs.get(&fn_ptr).copied() {
@@ -138,6 +154,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // Explicit type annotation - use it directly
                 self.variable_concrete_types
                     .insert(name.clone(), type_annotation.clone());
+            } else if let Some(range_type) = self.infer_range_binding_type(value) {
+                // Range<T> / RangeInclusive<T> - keep T for for-in and method calls
+                self.variable_concrete_types.insert(name.clone(), range_type);
             } else if let Some(ref struct_name_str) = struct_name_from_expr {
                 // Inferred struct type - determine if generic
                 if let Some(struct_def) = self.struct_ast_defs.get(struct_name_str) {
@@ -199,6 +218,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 }
             }
 
+            // Step 5.5: Bindings of make/new/error.new own their allocation
+            self.register_builtin_drop(name, value, &final_var_type)?;
+
             // Step 6: Register the variable (now variable_concrete_types is populated)
             self.register_variable(name, val, &final_var_type, final_llvm_type, is_mutable)?;
         }
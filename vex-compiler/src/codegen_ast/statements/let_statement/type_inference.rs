@@ -65,6 +65,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             Expression::Unary { expr, op, .. } => self.infer_from_unary_op(expr, op)?,
 
+            Expression::New(inner) => match inner.as_ref() {
+                Expression::StructLiteral {
+                    name: s_name,
+                    type_args,
+                    ..
+                } => self.infer_from_struct_literal(s_name, type_args)?,
+                _ => None,
+            },
+
+            Expression::Make { element_type, .. } => match element_type {
+                Type::Channel(_) => Some("Channel".to_string()),
+                _ => Some("Slice".to_string()),
+            },
+
             _ => None,
         };
 
@@ -531,6 +545,25 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(None)
     }
 
+    /// Full Range<T> type of an untyped binding (`let r = a..b`, `let r = r.rev()`)
+    pub(crate) fn infer_range_binding_type(&mut self, value: &Expression) -> Option<Type> {
+        let may_be_range = matches!(
+            value,
+            Expression::Range { .. }
+                | Expression::RangeInclusive { .. }
+                | Expression::MethodCall { .. }
+                | Expression::Binary {
+                    op: BinaryOp::Range | BinaryOp::RangeInclusive,
+                    ..
+                }
+        );
+        if !may_be_range {
+            return None;
+        }
+        let ty = self.infer_expression_type(value).ok()?;
+        self.range_kind(&ty).map(|_| ty)
+    }
+
     /// Validate array size if type annotation is array
     pub(crate) fn validate_array_size(
         &self,
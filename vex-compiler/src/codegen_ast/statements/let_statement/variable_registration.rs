@@ -57,8 +57,24 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // DON'T unwrap/consume - we need it later in register_struct_or_tuple_variable
                 (Type::Named("Tuple".to_string()), tuple_struct_type.into())
             }
-            // ⭐ CRITICAL: For MethodCall expressions, infer return type from AST
-            else if matches!(value, Expression::MethodCall { .. }) {
+            // Niche-packed Option<&T>: the value is the bare pointer
+            else if let Some(option_type) = self.niche_option_type_of(val, value) {
+                let option_llvm_type = self.ast_type_to_llvm(&option_type);
+                (option_type, option_llvm_type)
+            }
+            // Subslices (arr[a..b]) and &arr are views with an AST element type
+            else if let Some(slice_type) = self.slice_view_type_of(val, value) {
+                let slice_llvm_type = self.ast_type_to_llvm(&slice_type);
+                (slice_type, slice_llvm_type)
+            }
+            // ⭐ CRITICAL: For MethodCall and heap allocation expressions, infer type from AST
+            else if matches!(
+                value,
+                Expression::MethodCall { .. }
+                    | Expression::New(_)
+                    | Expression::Make { .. }
+                    | Expression::ErrorNew(_)
+            ) {
                 // Use AST-level type inference instead of LLVM type inference
                 match self.infer_expression_type(value) {
                     Ok(inferred_type) => {
@@ -101,6 +117,35 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok((final_var_type, final_llvm_type))
     }
 
+    /// Option type of an unannotated `let` whose value is niche-packed
+    fn niche_option_type_of(&self, val: BasicValueEnum<'ctx>, value: &Expression) -> Option<Type> {
+        if !Self::is_niche_option_value(val)
+            || !matches!(value, Expression::EnumLiteral { .. } | Expression::Call { .. })
+        {
+            return None;
+        }
+        match self.infer_expression_type(value).ok()? {
+            Type::Option(inner) if Self::option_uses_niche(&inner) => Some(Type::Option(inner)),
+            _ => None,
+        }
+    }
+
+    /// Slice type of an unannotated `let` bound to `arr[a..b]` or `&arr`
+    fn slice_view_type_of(&self, val: BasicValueEnum<'ctx>, value: &Expression) -> Option<Type> {
+        let is_slice_value = matches!(
+            val,
+            BasicValueEnum::StructValue(sv) if self.is_slice_layout(sv.get_type().into())
+        );
+        if !is_slice_value || !matches!(value, Expression::Index { .. } | Expression::Reference { .. })
+        {
+            return None;
+        }
+        match self.infer_expression_type(value).ok()? {
+            Type::Slice(elem, is_mutable) => Some(Type::Slice(elem, is_mutable)),
+            _ => None,
+        }
+    }
+
     fn cast_integer_if_needed(
         &self,
         val: BasicValueEnum<'ctx>,
@@ -450,6 +495,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // References don't need finalization
                 Ok(var_type.clone())
             }
+            Type::Union(_) => {
+                // Union values are already wrapped with their member tag
+                Ok(var_type.clone())
+            }
             // Primitive types don't need finalization
             Type::I8
             | Type::I16
@@ -465,7 +514,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
             | Type::F32
             | Type::F64
             | Type::Bool
-            | Type::String => Ok(var_type.clone()),
+            | Type::String
+            | Type::Error => Ok(var_type.clone()),
             _ => {
                 if let Some(type_name) = struct_name_from_expr {
                     self.finalize_inferred_type(type_name)
@@ -496,6 +546,9 @@ impl<'ctx> ASTCodeGen<'ctx> {
         } else if self.enum_ast_defs.contains_key(struct_name) {
             self.variable_enum_names
                 .insert(format!("temp"), struct_name.to_string());
+        } else if struct_name == "error" {
+            // `let e: error = ...` is the builtin error type (parsed as a keyword name)
+            return Ok(Type::Error);
         }
 
         Ok(Type::Named(struct_name.to_string()))
@@ -735,7 +788,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
             name, is_struct_or_tuple, is_builtin_pointer, is_array, is_slice, final_var_type
         );
 
-        if is_struct_or_tuple || is_builtin_pointer || is_array || is_slice {
+        // Niche-packed Options hold a pointer (or box) value, not an Option struct slot
+        let is_niche_option =
+            matches!(final_var_type, Type::Option(inner) if Self::option_uses_niche(inner));
+
+        if is_niche_option {
+            self.register_regular_variable(name, val, final_var_type, final_llvm_type, is_mutable)?;
+        } else if is_struct_or_tuple || is_builtin_pointer || is_array || is_slice {
             eprintln!("🔷 Calling register_struct_or_tuple_variable for '{}'", name);
             self.register_struct_or_tuple_variable(
                 name,
@@ -946,6 +1005,15 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     let mangled = format!("Box_{}", self.type_to_string(inner_ty.as_ref()));
                     self.variable_struct_names.insert(name.to_string(), mangled);
                 }
+                Type::Generic {
+                    name: range_name, ..
+                } if self.range_kind(final_var_type).is_some() => {
+                    // Range<T> keeps its element type for for-in and method inference
+                    self.variable_struct_names
+                        .insert(name.to_string(), range_name.clone());
+                    self.variable_ast_types
+                        .insert(name.to_string(), final_var_type.clone());
+                }
                 Type::Generic {
                     name: struct_name,
                     type_args,
@@ -957,6 +1025,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             .insert(name.to_string(), mangled_name);
                     }
                 }
+                Type::Slice(_, _) => {
+                    // Slice values (make([T], n)) dispatch len/get/is_empty as Slice methods
+                    self.variable_struct_names
+                        .insert(name.to_string(), "Slice".to_string());
+                    self.variable_ast_types
+                        .insert(name.to_string(), final_var_type.clone());
+                }
                 _ => {}
             }
         } else if let BasicValueEnum::ArrayValue(_array_val) = val {
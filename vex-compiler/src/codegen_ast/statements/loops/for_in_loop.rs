@@ -2,6 +2,9 @@
 // for-in loop compilation
 
 use super::super::ASTCodeGen;
+use crate::codegen_ast::functions::asynchronous::{
+    GENERATOR_HANDLE_ITEM_FIELD, GENERATOR_HANDLE_RESUME_FIELD, GENERATOR_HANDLE_STATE_FIELD,
+};
 use inkwell::values::BasicValueEnum;
 use inkwell::IntPredicate;
 use vex_ast::*;
@@ -9,8 +12,10 @@ use vex_ast::*;
 impl<'ctx> ASTCodeGen<'ctx> {
     /// Compile for-in loop: for item in iterator { body }
     /// Works with:
-    /// 1. Range/RangeInclusive (0..10, 0..=10)
-    /// 2. Any type implementing Iterator trait
+    /// 1. Range/RangeInclusive literals and Range<T> values (0..10, r.rev())
+    /// 2. Generator<T> values returned by generator functions
+    /// 3. Arrays and slices ([T; N], &[T], arr[a..b])
+    /// 4. Any type with a `next(): Option<T>` method (Iterator contract)
     pub(crate) fn compile_for_in_loop_dispatch(
         &mut self,
         variable: &str,
@@ -22,41 +27,60 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
     /// Compile for-in loop: for item in iterator { body }
     /// Works with:
-    /// 1. Range/RangeInclusive (0..10, 0..=10)
-    /// 2. Any type implementing Iterator trait
+    /// 1. Range/RangeInclusive literals and Range<T> values (0..10, r.rev())
+    /// 2. Generator<T> values returned by generator functions
+    /// 3. Arrays and slices ([T; N], &[T], arr[a..b])
+    /// 4. Any type with a `next(): Option<T>` method (Iterator contract)
     fn compile_for_in_loop_impl(
         &mut self,
         variable: &str,
         iterable: &Expression,
         body: &Block,
     ) -> Result<(), String> {
-        // Check if iterable is a Range expression (special case)
-        let is_range = matches!(
-            iterable,
-            Expression::Range { .. } | Expression::RangeInclusive { .. }
-        );
+        let iterable_type = self.infer_expression_type(iterable).ok();
 
-        if is_range {
-            // Use old Range-based implementation
-            self.compile_for_in_range_impl(variable, iterable, body)
-        } else {
-            // Use Iterator trait-based implementation
-            self.compile_for_in_iterator_impl(variable, iterable, body)
+        // Range literals and any Range<T> / RangeInclusive<T> value
+        let range_kind = match iterable {
+            Expression::Range { .. } => Some(false),
+            Expression::RangeInclusive { .. } => Some(true),
+            _ => iterable_type.as_ref().and_then(|ty| self.range_kind(ty)),
+        };
+        if let Some(inclusive) = range_kind {
+            let item_type = iterable_type
+                .as_ref()
+                .map(Self::range_element_type)
+                .unwrap_or(Type::I64);
+            return self.compile_for_in_range_impl(variable, iterable, inclusive, &item_type, body);
+        }
+
+        if let Some(item_type) = iterable_type.as_ref().and_then(Type::generator_item) {
+            return self.compile_for_in_generator_impl(variable, iterable, item_type, body);
+        }
+
+        if let Some(item_type) = iterable_type
+            .as_ref()
+            .and_then(Self::slice_element_type)
+            .cloned()
+        {
+            return self.compile_for_in_slice_impl(variable, iterable, &item_type, body);
         }
+
+        // Use Iterator trait-based implementation
+        self.compile_for_in_iterator_impl(variable, iterable, iterable_type, body)
     }
 
-    /// Compile for-in loop with Range (legacy implementation)
+    /// Compile for-in loop over a Range value
+    /// The runtime writes each value as i64; the loop variable has the range's element type
     fn compile_for_in_range_impl(
         &mut self,
         variable: &str,
         iterable: &Expression,
+        is_inclusive: bool,
+        item_type: &Type,
         body: &Block,
     ) -> Result<(), String> {
-        // Compile iterable (Range expression)
+        // Compile iterable and iterate over a private copy of it
         let range_val = self.compile_expression(iterable)?;
-
-        // Determine if Range or RangeInclusive based on expression type
-        let is_inclusive = matches!(iterable, Expression::RangeInclusive { .. });
         let range_type_name = if is_inclusive {
             "RangeInclusive"
         } else {
@@ -65,32 +89,34 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Create temporary range variable
         let range_var_name = format!("__forin_range_{}", variable);
-        let range_alloca = self.create_entry_block_alloca(
-            &range_var_name,
-            &Type::Named(range_type_name.to_string()),
-            true, // mutable
-        )?;
-        self.build_store_aligned(range_alloca, range_val)?;
-
-        // Get LLVM type for Range
-        let range_llvm_type = self.ast_type_to_llvm(&Type::Named(range_type_name.to_string()));
+        let range_alloca = self.spill_range_value(range_val, &range_var_name)?;
 
         // Track range variable for method calls
         self.variables.insert(range_var_name.clone(), range_alloca);
         self.variable_struct_names
             .insert(range_var_name.clone(), range_type_name.to_string());
         self.variable_types
-            .insert(range_var_name.clone(), range_llvm_type);
+            .insert(range_var_name.clone(), self.range_struct_type().into());
 
-        // Create loop variable (i64)
+        // Runtime output slot (i64); narrower element types get their own variable
+        let (item_type, item_llvm_type) = match self.ast_type_to_llvm(item_type) {
+            inkwell::types::BasicTypeEnum::IntType(int_type) => (item_type.clone(), int_type),
+            _ => (Type::I64, self.context.i64_type()),
+        };
+        let needs_item_cast = item_llvm_type.get_bit_width() != 64;
         let loop_var_alloca = self.create_entry_block_alloca(
-            variable,
+            &format!("__forin_value_{}", variable),
             &Type::I64,
             true, // mutable
         )?;
-        self.variables.insert(variable.to_string(), loop_var_alloca);
+        let item_alloca = if needs_item_cast {
+            self.create_entry_block_alloca(variable, &item_type, true)?
+        } else {
+            loop_var_alloca
+        };
+        self.variables.insert(variable.to_string(), item_alloca);
         self.variable_types
-            .insert(variable.to_string(), self.context.i64_type().into());
+            .insert(variable.to_string(), item_llvm_type.into());
 
         // Create loop blocks
         let fn_val = self.current_function.ok_or("No current function")?;
@@ -141,6 +167,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
         // Body
         self.builder.position_at_end(loop_body);
+        if needs_item_cast {
+            let value = self
+                .builder
+                .build_load(self.context.i64_type(), loop_var_alloca, "range_value")
+                .map_err(|e| format!("Failed to load range value: {}", e))?
+                .into_int_value();
+            let item = self
+                .builder
+                .build_int_cast(value, item_llvm_type, variable)
+                .map_err(|e| format!("Failed to convert range value: {}", e))?;
+            self.builder
+                .build_store(item_alloca, item)
+                .map_err(|e| format!("Failed to store loop variable: {}", e))?;
+        }
         self.compile_block(body)?;
 
         // Branch back to condition (if not terminated)
@@ -165,29 +205,265 @@ impl<'ctx> ASTCodeGen<'ctx> {
         Ok(())
     }
 
+    /// Compile for-in loop over a generator handle
+    /// Each iteration resumes the generator; YIELDED (1) means the item slot holds
+    /// the next value, anything else means the generator body has finished.
+    fn compile_for_in_generator_impl(
+        &mut self,
+        variable: &str,
+        iterable: &Expression,
+        item_type: &Type,
+        body: &Block,
+    ) -> Result<(), String> {
+        let ptr_type = self.context.ptr_type(inkwell::AddressSpace::default());
+        let handle = match self.compile_expression(iterable)? {
+            BasicValueEnum::PointerValue(ptr) => ptr,
+            other => return Err(format!("Generator must be a handle, got {:?}", other)),
+        };
+
+        // The handle is reloaded from an entry-block slot on every iteration
+        let handle_slot =
+            self.create_entry_block_alloca_for_type(ptr_type.into(), "__forin_generator")?;
+        self.builder
+            .build_store(handle_slot, handle)
+            .map_err(|e| format!("Failed to store generator handle: {}", e))?;
+
+        let handle_type = self.generator_handle_type(item_type);
+        let item_llvm_type = self.ast_type_to_llvm(item_type);
+
+        let fn_val = self.current_function.ok_or("No current function")?;
+        let loop_cond = self.context.append_basic_block(fn_val, "for_gen.cond");
+        let loop_body = self.context.append_basic_block(fn_val, "for_gen.body");
+        let loop_end = self.context.append_basic_block(fn_val, "for_gen.end");
+
+        self.loop_context_stack.push((loop_cond, loop_end));
+
+        self.builder
+            .build_unconditional_branch(loop_cond)
+            .map_err(|e| format!("Failed to branch to loop: {}", e))?;
+
+        // Condition: resume(null, state) == YIELDED
+        self.builder.position_at_end(loop_cond);
+        let handle = self
+            .builder
+            .build_load(ptr_type, handle_slot, "generator")
+            .map_err(|e| format!("Failed to load generator handle: {}", e))?
+            .into_pointer_value();
+        let mut fields = Vec::new();
+        for (index, name) in [
+            (GENERATOR_HANDLE_RESUME_FIELD, "generator_resume"),
+            (GENERATOR_HANDLE_STATE_FIELD, "generator_state"),
+        ] {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(handle_type, handle, index, name)
+                .map_err(|e| format!("Failed to get generator handle field: {}", e))?;
+            let field = self
+                .builder
+                .build_load(ptr_type, field_ptr, name)
+                .map_err(|e| format!("Failed to load generator handle field: {}", e))?
+                .into_pointer_value();
+            fields.push(field);
+        }
+
+        let resume_type = self
+            .context
+            .i32_type()
+            .fn_type(&[ptr_type.into(), ptr_type.into()], false);
+        let status = self
+            .builder
+            .build_indirect_call(
+                resume_type,
+                fields[0],
+                &[ptr_type.const_null().into(), fields[1].into()],
+                "generator_status",
+            )
+            .map_err(|e| format!("Failed to resume generator: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_int_value();
+        let yielded = self.context.i32_type().const_int(1, false);
+        let has_item = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, status, yielded, "has_item")
+            .map_err(|e| format!("Failed to compare generator status: {}", e))?;
+        self.builder
+            .build_conditional_branch(has_item, loop_body, loop_end)
+            .map_err(|e| format!("Failed to build conditional branch: {}", e))?;
+
+        // Body: bind the yielded item
+        self.builder.position_at_end(loop_body);
+        let item_ptr = self
+            .builder
+            .build_struct_gep(handle_type, handle, GENERATOR_HANDLE_ITEM_FIELD, "item_ptr")
+            .map_err(|e| format!("Failed to get generator item ptr: {}", e))?;
+        let item_val = self
+            .builder
+            .build_load(item_llvm_type, item_ptr, variable)
+            .map_err(|e| format!("Failed to load generator item: {}", e))?;
+        let item_alloca = self.create_entry_block_alloca(variable, item_type, false)?;
+        self.build_store_aligned(item_alloca, item_val)?;
+        self.variables.insert(variable.to_string(), item_alloca);
+        self.variable_types
+            .insert(variable.to_string(), item_llvm_type);
+
+        self.compile_block(body)?;
+
+        if self
+            .builder
+            .get_insert_block()
+            .ok_or("No active basic block")?
+            .get_terminator()
+            .is_none()
+        {
+            self.builder
+                .build_unconditional_branch(loop_cond)
+                .map_err(|e| format!("Failed to branch back: {}", e))?;
+        }
+
+        self.builder.position_at_end(loop_end);
+        self.loop_context_stack.pop();
+
+        Ok(())
+    }
+
+    /// Compile for-in loop over an array or slice view
+    /// Walks indices 0..len and binds each element with the AST element type
+    fn compile_for_in_slice_impl(
+        &mut self,
+        variable: &str,
+        iterable: &Expression,
+        item_type: &Type,
+        body: &Block,
+    ) -> Result<(), String> {
+        let view = self.compile_slice_source(iterable)?;
+        let len = self
+            .builder
+            .build_extract_value(view, 1, "slice_len")
+            .map_err(|e| format!("Failed to read slice length: {}", e))?
+            .into_int_value();
+
+        let i64_type = self.context.i64_type();
+        let index_slot =
+            self.create_entry_block_alloca_for_type(i64_type.into(), "__forin_index")?;
+        self.builder
+            .build_store(index_slot, i64_type.const_zero())
+            .map_err(|e| format!("Failed to init loop index: {}", e))?;
+
+        let item_llvm_type = self.ast_type_to_llvm(item_type);
+        let item_alloca = self.create_entry_block_alloca(variable, item_type, false)?;
+        self.variables.insert(variable.to_string(), item_alloca);
+        self.variable_types
+            .insert(variable.to_string(), item_llvm_type);
+        self.variable_ast_types
+            .insert(variable.to_string(), item_type.clone());
+
+        let fn_val = self.current_function.ok_or("No current function")?;
+        let loop_cond = self.context.append_basic_block(fn_val, "for_slice.cond");
+        let loop_body = self.context.append_basic_block(fn_val, "for_slice.body");
+        let loop_step = self.context.append_basic_block(fn_val, "for_slice.step");
+        let loop_end = self.context.append_basic_block(fn_val, "for_slice.end");
+
+        // continue jumps to the index increment
+        self.loop_context_stack.push((loop_step, loop_end));
+
+        self.builder
+            .build_unconditional_branch(loop_cond)
+            .map_err(|e| format!("Failed to branch to loop: {}", e))?;
+
+        // Condition: index < len
+        self.builder.position_at_end(loop_cond);
+        let index = self
+            .builder
+            .build_load(i64_type, index_slot, "index")
+            .map_err(|e| format!("Failed to load loop index: {}", e))?
+            .into_int_value();
+        let in_range = self
+            .builder
+            .build_int_compare(IntPredicate::ULT, index, len, "in_range")
+            .map_err(|e| format!("Failed to compare loop index: {}", e))?;
+        self.builder
+            .build_conditional_branch(in_range, loop_body, loop_end)
+            .map_err(|e| format!("Failed to build conditional branch: {}", e))?;
+
+        // Body: bind view[index]
+        self.builder.position_at_end(loop_body);
+        let elem_ptr = self.slice_element_ptr(view, index, item_llvm_type)?;
+        let item_val = self
+            .builder
+            .build_load(item_llvm_type, elem_ptr, variable)
+            .map_err(|e| format!("Failed to load slice element: {}", e))?;
+        self.build_store_aligned(item_alloca, item_val)?;
+
+        self.compile_block(body)?;
+
+        if self
+            .builder
+            .get_insert_block()
+            .ok_or("No active basic block")?
+            .get_terminator()
+            .is_none()
+        {
+            self.builder
+                .build_unconditional_branch(loop_step)
+                .map_err(|e| format!("Failed to branch to step: {}", e))?;
+        }
+
+        // Step: index += 1
+        self.builder.position_at_end(loop_step);
+        let index = self
+            .builder
+            .build_load(i64_type, index_slot, "index")
+            .map_err(|e| format!("Failed to load loop index: {}", e))?
+            .into_int_value();
+        let next = self
+            .builder
+            .build_int_add(index, i64_type.const_int(1, false), "index_next")
+            .map_err(|e| format!("Failed to increment loop index: {}", e))?;
+        self.builder
+            .build_store(index_slot, next)
+            .map_err(|e| format!("Failed to store loop index: {}", e))?;
+        self.builder
+            .build_unconditional_branch(loop_cond)
+            .map_err(|e| format!("Failed to branch back: {}", e))?;
+
+        self.builder.position_at_end(loop_end);
+        self.loop_context_stack.pop();
+
+        Ok(())
+    }
+
     /// Compile for-in loop with Iterator trait
     /// Desugars to: while let Some(item) = iterator.next() { body }
     fn compile_for_in_iterator_impl(
         &mut self,
         variable: &str,
         iterable: &Expression,
+        iterable_type: Option<Type>,
         body: &Block,
     ) -> Result<(), String> {
         // Get iterator type from expression
-        let iter_type_name = match iterable {
-            Expression::Ident(name) => {
+        let iter_type_name = match (iterable, iterable_type) {
+            (Expression::Ident(name), _) => {
                 // Lookup variable type
                 self.variable_struct_names
                     .get(name)
                     .cloned()
                     .ok_or_else(|| format!("Iterator variable '{}' not found", name))?
             }
-            Expression::StructLiteral { name, .. } => name.clone(),
-            _ => return Err("Iterator expression must be a variable or struct literal that implements Iterator trait".to_string()),
+            (Expression::StructLiteral { name, .. }, _) => name.clone(),
+            // Any other expression producing a user type, e.g. Counter.new(3)
+            (_, Some(Type::Named(name))) => name,
+            _ => {
+                return Err(
+                    "Iterator expression must produce a type with a next(): Option<T> method"
+                        .to_string(),
+                )
+            }
         };
 
         // For identifiers, use mutable reference to existing variable
-        // For struct literals, create new temporary
+        // Other expressions are stored in a new temporary
         let (iter_var_name, needs_temp) = match iterable {
             Expression::Ident(name) => (name.clone(), false),
             _ => ("__forin_iter".to_string(), true),
@@ -288,12 +564,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // 4. Body block: extract value from Option and bind to variable
         self.builder.position_at_end(loop_body);
 
-        // Get Item type from Iterator trait's associated type binding
-        let item_type = self
-            .associated_type_bindings
-            .get(&(iter_type_name.clone(), "Item".to_string()))
-            .cloned()
-            .unwrap_or(Type::I32); // Default to i32 if not found
+        // Item type comes from next(): Option<T>, then the Iterator trait's
+        // associated type binding
+        let item_type = match self.infer_expression_type(&next_call_expr) {
+            Ok(Type::Option(inner)) => *inner,
+            Ok(Type::Generic {
+                name,
+                mut type_args,
+            }) if name == "Option" && type_args.len() == 1 => type_args.remove(0),
+            _ => self
+                .associated_type_bindings
+                .get(&(iter_type_name.clone(), "Item".to_string()))
+                .cloned()
+                .unwrap_or(Type::I32), // Default to i32 if not found
+        };
 
         // Get LLVM type for Item
         let item_llvm_type = self.ast_type_to_llvm(&item_type);
@@ -135,7 +135,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
             "📋 Compiling if then_block with {} statements",
             then_block.statements.len()
         );
+        let narrowed = match self.union_narrowing(condition) {
+            Some((name, member, members)) => self.narrow_union_variable(&name, &member, &members)?,
+            None => None,
+        };
         self.compile_block(then_block)?;
+        if let Some(saved) = narrowed {
+            self.restore_narrowed_variable(saved);
+        }
         let then_terminated = self
             .builder
             .get_insert_block()
@@ -195,7 +202,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
             // Compile elif body
             self.builder.position_at_end(then_bb);
+            let narrowed = match self.union_narrowing(elif_cond) {
+                Some((name, member, members)) => {
+                    self.narrow_union_variable(&name, &member, &members)?
+                }
+                None => None,
+            };
             self.compile_block(elif_body)?;
+            if let Some(saved) = narrowed {
+                self.restore_narrowed_variable(saved);
+            }
             let elif_terminated = self
                 .builder
                 .get_insert_block()
@@ -213,7 +229,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
         // Compile else block
         self.builder.position_at_end(else_bb);
         if let Some(eb) = else_block {
+            // Every other member was ruled out by the conditions above
+            let conditions: Vec<&Expression> = std::iter::once(condition)
+                .chain(elif_branches.iter().map(|(elif_cond, _)| elif_cond))
+                .collect();
+            let narrowed = match self.remaining_union_member(&conditions) {
+                Some((name, member, members)) => {
+                    self.narrow_union_variable(&name, &member, &members)?
+                }
+                None => None,
+            };
             self.compile_block(eb)?;
+            if let Some(saved) = narrowed {
+                self.restore_narrowed_variable(saved);
+            }
         }
         let else_terminated = self
             .builder
@@ -6,16 +6,19 @@
 //   - control_flow.rs    : return / break / continue / defer
 //   - assignment.rs      : assign / compound_assign
 //   - let_statement.rs   : let + inject_type_args_recursive
+//   - select.rs          : select over channels
+//   - go_spawn.rs        : go (task spawning)
 //
 // Public re-exports provide a flat surface for the parent module.
 
 mod assignment;
 mod control_flow;
+mod go_spawn;
 mod let_statement;
 mod loops;
+mod select;
 
 use super::ASTCodeGen;
-use crate::diagnostics::{error_codes, Diagnostic, ErrorLevel, Span};
 use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -50,34 +53,16 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
         }
 
-        eprintln!("📋 Block compilation complete, checking for implicit terminator");
-
-        // ⭐ CRITICAL FIX: If we're in an async resume block and block has no terminator,
-        // the block MUST end somewhere. Add a DONE return if needed.
-        if let Some(current_block) = self.builder.get_insert_block() {
-            eprintln!(
-                "   Builder at: {:?}, has_terminator={}, async_stack_depth={}",
-                current_block.get_name().to_str(),
-                current_block.get_terminator().is_some(),
-                self.async_state_stack.len()
-            );
-
-            if current_block.get_terminator().is_none() && !self.async_state_stack.is_empty() {
-                eprintln!("   ⭐ Adding implicit DONE return");
-                // We're in async context and block has no explicit return/terminator
-                // This happens when a resume block reaches end without another await
-                let done_status = self.context.i32_type().const_int(2, false); // CORO_STATUS_DONE
-                self.builder
-                    .build_return(Some(&done_status))
-                    .map_err(|e| format!("Failed to add implicit async done return: {}", e))?;
-            }
-        }
+        // No implicit terminator here: nested blocks (if/while bodies) fall through
+        // to their parent, and compile_async_function returns CORO_STATUS_DONE after
+        // the body of an async function.
 
         Ok(())
     }
 
     /// Statement dispatcher. The actual statement bodies live in submodules.
     pub(crate) fn compile_statement(&mut self, stmt: &Statement) -> Result<(), String> {
+        self.clear_moved_drop_flags(stmt)?;
         match stmt {
             // let statement
             Statement::Let {
@@ -105,8 +90,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
 
             // compound assignment (+=, -=, *=, /=)
-            Statement::CompoundAssign { span_id: _, target, op, value } => {
-                self.compile_compound_assign_statement(target, op, value)?;
+            Statement::CompoundAssign { span_id, target, op, value } => {
+                self.compile_compound_assign_statement(target, op, value, span_id.as_ref())?;
             }
 
             // control-flow
@@ -122,6 +107,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Statement::Defer(stmt) => {
                 self.compile_defer_statement(stmt.as_ref())?;
             }
+            // Statement attributes are checked by the type checker and do not change codegen
+            Statement::Attributed { stmt, .. } => {
+                self.compile_statement(stmt)?;
+            }
             Statement::Go { span_id: _, expr } => {
                 self.compile_go_statement(expr)?;
             }
@@ -186,27 +175,19 @@ impl<'ctx> ASTCodeGen<'ctx> {
             } => {
                 self.compile_switch_statement(value, cases, default_case)?;
             }
+            Statement::Select {
+                span_id: _,
+                cases,
+                default_case,
+            } => {
+                self.compile_select_statement(cases, default_case)?;
+            }
 
             // pure expression statement
             Statement::Expression(expr) => {
                 // keep side effects
-                let _ = self.compile_expression(expr)?;
-            }
-
-            _ => {
-                let stmt_str = format!("{:?}", stmt);
-                self.diagnostics.emit(Diagnostic {
-                    level: ErrorLevel::Error,
-                    code: error_codes::NOT_IMPLEMENTED.to_string(),
-                    message: "This statement type is not yet implemented".to_string(),
-                    span: Span::unknown(),
-                    primary_label: Some("feature not implemented".to_string()),
-                    notes: vec![format!("Statement: {}", stmt_str)],
-                    help: Some("This feature is planned for a future release".to_string()),
-                    suggestion: None,
-                    related: Vec::new(),
-                });
-                    return Err(format!("Statement not yet implemented: {:?}", stmt));
+                let value = self.compile_expression(expr)?;
+                self.release_discarded_future(expr, value)?;
             }
         }
         Ok(())
@@ -0,0 +1,515 @@
+// statements/select.rs
+// select statement: multi-channel wait lowered onto vex_channel_select()
+
+use super::ASTCodeGen;
+use inkwell::types::{BasicType, BasicTypeEnum};
+use inkwell::values::PointerValue;
+use inkwell::AddressSpace;
+use vex_ast::*;
+
+/// Mirrors VEX_SELECT_MAX_CASES in vex_channel.h
+const MAX_SELECT_CASES: usize = 64;
+
+/// Mirrors vex_select_op_t in vex_channel.h
+const SELECT_OP_RECV: u64 = 0;
+const SELECT_OP_SEND: u64 = 1;
+
+/// Mirrors VEX_SELECT_DEFAULT in vex_channel.h
+const SELECT_DEFAULT: u64 = -1i64 as u64;
+
+/// Status an async resume function returns to give its worker back
+const CORO_STATUS_YIELDED: u64 = 1;
+
+/// Field indices of vex_select_case_t
+const CASE_FIELD_CHAN: u32 = 0;
+const CASE_FIELD_OP: u32 = 1;
+const CASE_FIELD_DATA: u32 = 2;
+
+/// Previous (pointer, LLVM type, AST type) of a name shadowed by a receive binding
+type SavedBinding<'ctx> = (
+    Option<PointerValue<'ctx>>,
+    Option<BasicTypeEnum<'ctx>>,
+    Option<Type>,
+);
+
+/// Channel operation performed by one select arm
+enum SelectOp<'a> {
+    Recv {
+        channel: &'a Expression,
+    },
+    Send {
+        channel: &'a Expression,
+        value: &'a Expression,
+    },
+}
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Compile select statement:
+    ///   select { case v = <-a: ... case b <- x: ... default: ... }
+    ///
+    /// Every case is written into a stack array of vex_select_case_t and handed to
+    /// the runtime, which shuffles the polling order (fair choice between ready cases),
+    /// completes exactly one operation and returns its index. With a `default` arm the
+    /// call is non-blocking and returns -1 when nothing is ready.
+    ///
+    /// Inside an async function a blocking select must not hold its worker thread:
+    /// it polls without blocking and yields the coroutine while nothing is ready,
+    /// like a pending await. Plain functions running as `go` tasks have no state
+    /// machine to yield from, so their blocking selects block the worker thread.
+    pub(crate) fn compile_select_statement(
+        &mut self,
+        cases: &[SelectCase],
+        default_case: &Option<Block>,
+    ) -> Result<(), String> {
+        if cases.len() > MAX_SELECT_CASES {
+            return Err(format!(
+                "select statement has {} cases, at most {} are supported",
+                cases.len(),
+                MAX_SELECT_CASES
+            ));
+        }
+
+        let fn_val = self.current_function.ok_or("No current function")?;
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let case_type = self.get_select_case_type();
+        let cases_array_type = case_type.array_type(cases.len() as u32);
+
+        // Case array lives in the entry block so selects inside loops don't grow the stack
+        let cases_alloca = {
+            let entry = fn_val
+                .get_first_basic_block()
+                .ok_or("Function has no entry block")?;
+            let entry_builder = self.context.create_builder();
+            match entry.get_first_instruction() {
+                Some(first_instr) => entry_builder.position_before(&first_instr),
+                None => entry_builder.position_at_end(entry),
+            }
+            entry_builder
+                .build_alloca(cases_array_type, "select_cases")
+                .map_err(|e| format!("Failed to allocate select cases: {}", e))?
+        };
+
+        // Fill in one vex_select_case_t per arm
+        let mut element_types = Vec::with_capacity(cases.len());
+        let mut is_send = Vec::with_capacity(cases.len());
+        for (i, case) in cases.iter().enumerate() {
+            let op = Self::classify_select_case(case)?;
+            let channel_expr = match &op {
+                SelectOp::Recv { channel } | SelectOp::Send { channel, .. } => *channel,
+            };
+
+            let elem_ty = self.channel_element_type(channel_expr);
+            let elem_llvm = self.ast_type_to_llvm(&elem_ty);
+
+            let channel_val = self.compile_expression(channel_expr)?;
+            if !channel_val.is_pointer_value() {
+                return Err(format!(
+                    "select case {} does not operate on a channel value",
+                    i + 1
+                ));
+            }
+
+            let case_ptr = self.select_case_ptr(cases_array_type, cases_alloca, i)?;
+            let chan_field = self
+                .builder
+                .build_struct_gep(case_type, case_ptr, CASE_FIELD_CHAN, "select_chan_ptr")
+                .map_err(|e| format!("Failed to get select channel field: {}", e))?;
+            self.builder
+                .build_store(chan_field, channel_val)
+                .map_err(|e| format!("Failed to store select channel: {}", e))?;
+
+            let (op_code, data_ptr) = match op {
+                SelectOp::Recv { .. } => {
+                    is_send.push(false);
+                    (SELECT_OP_RECV, ptr_type.const_null())
+                }
+                SelectOp::Send { value, .. } => {
+                    let boxed = self.box_select_send_value(value, &elem_ty, elem_llvm)?;
+                    is_send.push(true);
+                    (SELECT_OP_SEND, boxed)
+                }
+            };
+
+            let op_field = self
+                .builder
+                .build_struct_gep(case_type, case_ptr, CASE_FIELD_OP, "select_op_ptr")
+                .map_err(|e| format!("Failed to get select op field: {}", e))?;
+            self.builder
+                .build_store(op_field, i32_type.const_int(op_code, false))
+                .map_err(|e| format!("Failed to store select op: {}", e))?;
+
+            let data_field = self
+                .builder
+                .build_struct_gep(case_type, case_ptr, CASE_FIELD_DATA, "select_data_ptr")
+                .map_err(|e| format!("Failed to get select data field: {}", e))?;
+            self.builder
+                .build_store(data_field, data_ptr)
+                .map_err(|e| format!("Failed to store select data: {}", e))?;
+
+            element_types.push((elem_ty, elem_llvm));
+        }
+
+        // A blocking select in an async function polls, then yields until a case is ready
+        let parks = default_case.is_none()
+            && !self.async_state_stack.is_empty()
+            && self
+                .async_context
+                .as_ref()
+                .is_some_and(|ctx| ctx.generator_item.is_none());
+        let poll_bb = self.context.append_basic_block(fn_val, "select.poll");
+        self.builder
+            .build_unconditional_branch(poll_bb)
+            .map_err(|e| format!("Failed to build branch: {}", e))?;
+        self.builder.position_at_end(poll_bb);
+
+        // int64_t vex_channel_select(cases, count, blocking)
+        let select_fn = self.get_or_declare_vex_channel_select();
+        let blocking = self
+            .context
+            .bool_type()
+            .const_int((default_case.is_none() && !parks) as u64, false);
+        let chosen = self
+            .builder
+            .build_call(
+                select_fn,
+                &[
+                    cases_alloca.into(),
+                    i64_type.const_int(cases.len() as u64, false).into(),
+                    blocking.into(),
+                ],
+                "select_chosen",
+            )
+            .map_err(|e| format!("Failed to call vex_channel_select: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_int_value();
+
+        if parks {
+            let park_bb = self.context.append_basic_block(fn_val, "select.park");
+            let ready_bb = self.context.append_basic_block(fn_val, "select.ready");
+            let pending = self
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    chosen,
+                    i64_type.const_int(SELECT_DEFAULT, true),
+                    "select_pending",
+                )
+                .map_err(|e| format!("Failed to check select result: {}", e))?;
+            self.builder
+                .build_conditional_branch(pending, park_bb, ready_bb)
+                .map_err(|e| format!("Failed to build branch: {}", e))?;
+
+            self.builder.position_at_end(park_bb);
+            self.park_select(cases_alloca, cases_array_type.into(), poll_bb)?;
+            self.builder.position_at_end(ready_bb);
+        }
+
+        // Dispatch on the chosen index; -1 (or anything else) falls to default
+        let case_blocks: Vec<_> = cases
+            .iter()
+            .map(|_| self.context.append_basic_block(fn_val, "select.case"))
+            .collect();
+        let default_bb = self.context.append_basic_block(fn_val, "select.default");
+        let end_bb = self.context.append_basic_block(fn_val, "select.end");
+
+        let switch_cases: Vec<_> = case_blocks
+            .iter()
+            .enumerate()
+            .map(|(i, bb)| (i64_type.const_int(i as u64, false), *bb))
+            .collect();
+        self.builder
+            .build_switch(chosen, default_bb, &switch_cases)
+            .map_err(|e| format!("Failed to build select switch: {}", e))?;
+
+        for (i, case) in cases.iter().enumerate() {
+            self.builder.position_at_end(case_blocks[i]);
+
+            self.free_unsent_select_boxes(cases_array_type, cases_alloca, &is_send, Some(i))?;
+
+            let (elem_ty, elem_llvm) = element_types[i].clone();
+            let is_recv = matches!(case.expr, Expression::ChannelReceive(_));
+
+            let mut shadowed = None;
+            if is_recv {
+                let case_ptr = self.select_case_ptr(cases_array_type, cases_alloca, i)?;
+                let data_field = self
+                    .builder
+                    .build_struct_gep(case_type, case_ptr, CASE_FIELD_DATA, "select_recv_ptr")
+                    .map_err(|e| format!("Failed to get select data field: {}", e))?;
+                let received = self
+                    .builder
+                    .build_load(ptr_type, data_field, "select_received")
+                    .map_err(|e| format!("Failed to load received value: {}", e))?
+                    .into_pointer_value();
+
+                if let Some(var) = &case.var {
+                    shadowed = Some(self.bind_select_receive(var, received, &elem_ty, elem_llvm)?);
+                } else {
+                    // Value discarded: release the sender's heap box (free(NULL) is a no-op)
+                    let free_fn = self.get_or_declare_free();
+                    self.builder
+                        .build_call(free_fn, &[received.into()], "select_discard")
+                        .map_err(|e| format!("Failed to free received value: {}", e))?;
+                }
+            }
+
+            self.compile_block(&case.body)?;
+
+            if let (Some(var), Some(previous)) = (&case.var, shadowed) {
+                self.restore_select_binding(var, previous);
+            }
+
+            if self
+                .builder
+                .get_insert_block()
+                .ok_or("No active basic block")?
+                .get_terminator()
+                .is_none()
+            {
+                self.builder
+                    .build_unconditional_branch(end_bb)
+                    .map_err(|e| format!("Failed to build branch: {}", e))?;
+            }
+        }
+
+        // Default arm (unreachable for blocking selects, LLVM removes it)
+        self.builder.position_at_end(default_bb);
+        self.free_unsent_select_boxes(cases_array_type, cases_alloca, &is_send, None)?;
+        if let Some(def_block) = default_case {
+            self.compile_block(def_block)?;
+        }
+        if self
+            .builder
+            .get_insert_block()
+            .ok_or("No active basic block")?
+            .get_terminator()
+            .is_none()
+        {
+            self.builder
+                .build_unconditional_branch(end_bb)
+                .map_err(|e| format!("Failed to build branch: {}", e))?;
+        }
+
+        self.builder.position_at_end(end_bb);
+        Ok(())
+    }
+
+    fn classify_select_case(case: &SelectCase) -> Result<SelectOp<'_>, String> {
+        match &case.expr {
+            Expression::ChannelReceive(channel) => Ok(SelectOp::Recv { channel }),
+            Expression::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } if method == "send" && args.len() == 1 => {
+                if case.var.is_some() {
+                    return Err("select send case cannot bind a variable".to_string());
+                }
+                Ok(SelectOp::Send {
+                    channel: receiver,
+                    value: &args[0],
+                })
+            }
+            other => Err(format!(
+                "select case must be a channel send or receive, found {:?}",
+                std::mem::discriminant(other)
+            )),
+        }
+    }
+
+    fn select_case_ptr(
+        &mut self,
+        cases_array_type: inkwell::types::ArrayType<'ctx>,
+        cases_alloca: PointerValue<'ctx>,
+        index: usize,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let i32_type = self.context.i32_type();
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(
+                    cases_array_type,
+                    cases_alloca,
+                    &[
+                        i32_type.const_zero(),
+                        i32_type.const_int(index as u64, false),
+                    ],
+                    "select_case",
+                )
+                .map_err(|e| format!("Failed to index select cases: {}", e))
+        }
+    }
+
+    /// Heap-box a value for a send case (same ownership model as Channel.send)
+    fn box_select_send_value(
+        &mut self,
+        value: &Expression,
+        elem_ty: &Type,
+        elem_llvm: BasicTypeEnum<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let val = self.compile_channel_send_value(value, elem_ty, elem_llvm)?;
+
+        let size = elem_llvm
+            .size_of()
+            .ok_or("select: cannot compute size of channel element")?;
+        let malloc_fn = self.get_or_declare_malloc();
+        let boxed = self
+            .builder
+            .build_call(malloc_fn, &[size.into()], "select_send_box")
+            .map_err(|e| format!("Failed to allocate send value: {}", e))?
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value();
+        self.build_store_aligned(boxed, val)?;
+        Ok(boxed)
+    }
+
+    /// Yield the coroutine while a blocking select has nothing ready; its worker
+    /// runs other tasks and resumes this one at `poll_bb` to try again.
+    /// The case array survives the yield in the async state like a spilled local.
+    fn park_select(
+        &mut self,
+        cases_alloca: PointerValue<'ctx>,
+        cases_array_type: BasicTypeEnum<'ctx>,
+        poll_bb: inkwell::basic_block::BasicBlock<'ctx>,
+    ) -> Result<(), String> {
+        let (state_ptr, state_field_ptr, current_state_id) = self
+            .async_state_stack
+            .last()
+            .copied()
+            .ok_or("select parked outside async context")?;
+        let next_state_id = current_state_id + 1;
+        let resume_block = self
+            .async_resume_blocks
+            .get((next_state_id - 1) as usize)
+            .copied()
+            .ok_or_else(|| format!("Resume block {} not pre-allocated", next_state_id))?;
+
+        self.builder
+            .build_store(
+                state_field_ptr,
+                self.context
+                    .i32_type()
+                    .const_int(next_state_id as u64, false),
+            )
+            .map_err(|e| format!("Failed to save state: {}", e))?;
+        let yielded_status = self
+            .context
+            .i32_type()
+            .const_int(CORO_STATUS_YIELDED, false);
+        let yield_ret = self
+            .builder
+            .build_return(Some(&yielded_status))
+            .map_err(|e| format!("Failed to build select yield: {}", e))?;
+        self.record_async_yield(yield_ret, resume_block, &[(cases_alloca, cases_array_type)]);
+
+        self.builder.position_at_end(resume_block);
+        self.builder
+            .build_unconditional_branch(poll_bb)
+            .map_err(|e| format!("Failed to build branch: {}", e))?;
+
+        self.async_state_stack.pop();
+        self.async_state_stack
+            .push((state_ptr, state_field_ptr, next_state_id));
+        Ok(())
+    }
+
+    /// Release the boxes of send cases that did not complete: only the chosen
+    /// send (if any) handed its box to the channel. The boxes are read back from
+    /// the case array, which is still valid after a parked select resumes.
+    fn free_unsent_select_boxes(
+        &mut self,
+        cases_array_type: inkwell::types::ArrayType<'ctx>,
+        cases_alloca: PointerValue<'ctx>,
+        is_send: &[bool],
+        chosen: Option<usize>,
+    ) -> Result<(), String> {
+        let case_type = self.get_select_case_type();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let unsent = is_send
+            .iter()
+            .enumerate()
+            .filter(|&(i, send)| *send && chosen != Some(i));
+        for (i, _) in unsent {
+            let case_ptr = self.select_case_ptr(cases_array_type, cases_alloca, i)?;
+            let data_field = self
+                .builder
+                .build_struct_gep(case_type, case_ptr, CASE_FIELD_DATA, "select_unsent_ptr")
+                .map_err(|e| format!("Failed to get select data field: {}", e))?;
+            let boxed = self
+                .builder
+                .build_load(ptr_type, data_field, "select_unsent")
+                .map_err(|e| format!("Failed to load unsent value: {}", e))?;
+            let free_fn = self.get_or_declare_free();
+            self.builder
+                .build_call(free_fn, &[boxed.into()], "select_unsent_free")
+                .map_err(|e| format!("Failed to free unsent value: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Bind `case v = <-ch` into a fresh local. A closed channel yields the zero value.
+    /// Returns the previous binding of the same name so it can be restored afterwards.
+    fn bind_select_receive(
+        &mut self,
+        var: &str,
+        received: PointerValue<'ctx>,
+        elem_ty: &Type,
+        elem_llvm: BasicTypeEnum<'ctx>,
+    ) -> Result<SavedBinding<'ctx>, String> {
+        let fn_val = self.current_function.ok_or("No current function")?;
+        let var_alloca = self.create_entry_block_alloca(var, elem_ty, false)?;
+        self.build_store_aligned(var_alloca, elem_llvm.const_zero())?;
+
+        let load_bb = self.context.append_basic_block(fn_val, "select.recv.value");
+        let bind_bb = self.context.append_basic_block(fn_val, "select.recv.bind");
+        let is_closed = self
+            .builder
+            .build_is_null(received, "select_recv_closed")
+            .map_err(|e| format!("Failed to check received value: {}", e))?;
+        self.builder
+            .build_conditional_branch(is_closed, bind_bb, load_bb)
+            .map_err(|e| format!("Failed to build branch: {}", e))?;
+
+        self.builder.position_at_end(load_bb);
+        let value = self.build_load_aligned(elem_llvm, received, "select_recv_value")?;
+        self.build_store_aligned(var_alloca, value)?;
+        let free_fn = self.get_or_declare_free();
+        self.builder
+            .build_call(free_fn, &[received.into()], "select_recv_free")
+            .map_err(|e| format!("Failed to free received value: {}", e))?;
+        self.builder
+            .build_unconditional_branch(bind_bb)
+            .map_err(|e| format!("Failed to build branch: {}", e))?;
+
+        self.builder.position_at_end(bind_bb);
+
+        let previous = (
+            self.variables.insert(var.to_string(), var_alloca),
+            self.variable_types.insert(var.to_string(), elem_llvm),
+            self.variable_ast_types
+                .insert(var.to_string(), elem_ty.clone()),
+        );
+        Ok(previous)
+    }
+
+    /// Restore bindings shadowed by a select receive variable
+    fn restore_select_binding(&mut self, var: &str, previous: SavedBinding<'ctx>) {
+        let (ptr, llvm_ty, ast_ty) = previous;
+        match ptr {
+            Some(p) => self.variables.insert(var.to_string(), p),
+            None => self.variables.remove(var),
+        };
+        match llvm_ty {
+            Some(t) => self.variable_types.insert(var.to_string(), t),
+            None => self.variable_types.remove(var),
+        };
+        match ast_ty {
+            Some(t) => self.variable_ast_types.insert(var.to_string(), t),
+            None => self.variable_ast_types.remove(var),
+        };
+    }
+}
@@ -77,8 +77,12 @@ pub struct ASTCodeGen<'ctx> {
     pub(crate) function_defs: HashMap<String, Function>,
     pub(crate) struct_ast_defs: HashMap<String, Struct>,
     pub(crate) struct_defs: HashMap<String, StructDef>,
+    // Structs registered for record intersections (A & B), with their members
+    pub(crate) intersection_records: HashMap<String, Vec<Type>>,
     pub(crate) enum_ast_defs: HashMap<String, Enum>,
     pub(crate) type_aliases: HashMap<String, Type>,
+    // Parameter names of generic type aliases, substituted on each use
+    pub(crate) type_alias_params: HashMap<String, Vec<String>>,
     pub(crate) generic_instantiations: HashMap<(String, Vec<String>), String>,
 
     // Trait definitions: trait_name -> Trait
@@ -120,6 +124,10 @@ pub struct ASTCodeGen<'ctx> {
     // Used by infer_expression_type() for namespace access (math.PI)
     pub(crate) module_constant_types: HashMap<String, Type>,
 
+    // Constants evaluated at compile time, and the integer ones as array lengths
+    pub(crate) const_values: HashMap<String, crate::type_system::const_eval::ConstValue>,
+    pub(crate) const_sizes: HashMap<String, Type>,
+
     // Builtin functions registry
     pub(crate) builtins: BuiltinRegistry<'ctx>,
 
@@ -150,11 +158,26 @@ pub struct ASTCodeGen<'ctx> {
     // Used when calling closures to know the correct signature
     pub(crate) closure_types: HashMap<String, (Vec<Type>, Type)>,
 
+    // Environment struct type of each capturing closure, keyed by closure function
+    // pointer; used to copy the environment to the heap when the closure escapes
+    pub(crate) closure_env_types: HashMap<PointerValue<'ctx>, StructType<'ctx>>,
+
+    // Capture mode of closures bound to variables, checked against Callable bounds
+    pub(crate) closure_capture_modes: HashMap<String, CaptureMode>,
+
     // Scope tracking for automatic cleanup (Drop trait)
     // Stack of scopes, each scope contains variable names that need cleanup
     // Inner Vec<(var_name, type_name)> tracks variables that need drop calls
     pub(crate) scope_stack: Vec<Vec<(String, String)>>,
 
+    // Moves out of the locals of the function being compiled (see move_scanner):
+    // decides which make/new/error.new bindings are released at scope exit
+    pub(crate) moved_locals: super::functions::move_scanner::MovedLocals,
+
+    // Drop flags of bindings moved on some path, with the function owning the flag:
+    // scope exit releases the binding only while its flag is still set
+    pub(crate) drop_flags: HashMap<String, (FunctionValue<'ctx>, PointerValue<'ctx>)>,
+
     // Tuple type tracking: when compile_tuple_literal is called, store struct type here
     // Let statement reads this to get tuple struct type without recompiling elements
     pub(crate) last_compiled_tuple_type: Option<inkwell::types::StructType<'ctx>>,
@@ -186,6 +209,18 @@ pub struct ASTCodeGen<'ctx> {
     // ⭐ NEW: Source file path for resolving relative imports
     pub(crate) source_file: String,
 
+    // Expression types from the semantic type checker, when it ran before codegen
+    pub(crate) type_table: Option<crate::type_checker::TypeTable>,
+
+    // Trap on integer overflow in `+ - *` (debug builds); wrap when false
+    pub(crate) overflow_checks: bool,
+
+    // Members of the union being matched, for `n: T =>` type patterns
+    pub(crate) match_union_members: Option<Vec<Type>>,
+
+    // Vtables emitted for contract objects, keyed by (type, contract)
+    pub(crate) contract_vtables: HashMap<(String, String), PointerValue<'ctx>>,
+
     // ⭐ NEW: Type interning for performance optimization
     // Reduces memory usage and clone overhead for common types
     pub(crate) type_interner: crate::types::interner::TypeInterner,
@@ -197,6 +232,13 @@ pub struct ASTCodeGen<'ctx> {
     // ⭐ ASYNC BLOCKS: Counter for generating unique async block function names
     pub(crate) async_block_counter: u32,
 
+    // ⭐ GO: Counter for generating unique go task function names
+    pub(crate) go_task_counter: u32,
+
+    // ⭐ GO: Runtime is driven on a background thread while main() runs
+    // (set when the program contains go statements)
+    pub(crate) runtime_in_background: bool,
+
     // ⭐ ASYNC STATE MACHINE: State tracking for await points
     // Stack of (state_struct_ptr, state_field_ptr, next_state_id)
     pub(crate) async_state_stack: Vec<(PointerValue<'ctx>, PointerValue<'ctx>, u32)>,
@@ -212,8 +254,11 @@ pub struct ASTCodeGen<'ctx> {
     pub(crate) async_resume_blocks: Vec<inkwell::basic_block::BasicBlock<'ctx>>,
 
     // ⭐ ASYNC: Current async context for await compilation
-    pub(crate) async_context: Option<AsyncContext>,
+    pub(crate) async_context: Option<AsyncContext<'ctx>>,
 
     // ⭐ NEW: Suppress diagnostics during speculative compilation (e.g. constants)
     pub(crate) suppress_diagnostics: bool,
+
+    // Data layout of the native target: sizes enum union storage like LLVM does
+    pub(crate) target_data: inkwell::targets::TargetData,
 }
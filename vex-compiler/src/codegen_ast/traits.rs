@@ -1,4 +1,5 @@
 // src/codegen/traits.rs
+use super::functions::move_scanner::moved_locals;
 use super::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -28,6 +29,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
         self.variables.clear();
         self.variable_types.clear();
         self.variable_struct_names.clear();
+        self.moved_locals = moved_locals(&method.body);
 
         let mut param_offset = 0;
 
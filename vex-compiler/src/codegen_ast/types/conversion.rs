@@ -39,8 +39,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 BasicTypeEnum::IntType(self.context.i8_type())
             }
             Type::Error => {
-                // Error type as i32 (error code)
-                BasicTypeEnum::IntType(self.context.i32_type())
+                // Error type as pointer to runtime VexError (error.new)
+                BasicTypeEnum::PointerType(self.context.ptr_type(inkwell::AddressSpace::default()))
+            }
+            // Contract objects: { data, vtable } (see contract_objects.rs)
+            Type::Dyn(_) => self.dyn_llvm_type(),
+            Type::Reference(inner, _) | Type::Box(inner) if matches!(**inner, Type::Dyn(_)) => {
+                self.dyn_llvm_type()
             }
             Type::Array(elem_ty, size) => {
                 let elem_llvm = self.ast_type_to_llvm(elem_ty);
@@ -63,6 +68,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     _ => BasicTypeEnum::IntType(self.context.i32_type()), // fallback
                 }
             }
+            // [T; SIZE] where SIZE is built from constants
+            Type::ConstArray { elem_type, size } => match self.const_array_len(size) {
+                Some(n) => self.ast_type_to_llvm(&Type::Array(elem_type.clone(), n)),
+                // Unreachable for checked types: check_array_lengths rejects them first
+                None => BasicTypeEnum::IntType(self.context.i32_type()),
+            },
             Type::Reference(inner_ty, _is_mutable) => {
                 // Reference type: &T or &mut T
                 // In LLVM, references are just pointers
@@ -95,6 +106,10 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     ),
                 }
             }
+            Type::Named(_) | Type::Generic { .. } if self.range_kind(ty).is_some() => {
+                // Range<T> / RangeInclusive<T>: { start, end, current, step } as i64
+                BasicTypeEnum::StructType(self.range_struct_type())
+            }
             Type::Named(name) => {
                 // ⚠️ Check type aliases FIRST before any other lookups
                 if let Some(resolved) = self.type_aliases.get(name) {
@@ -115,6 +130,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     );
                 }
 
+                // `error` annotation is the builtin error type unless a struct shadows it
+                if name == "error" && !self.struct_defs.contains_key(name) {
+                    return self.ast_type_to_llvm(&Type::Error);
+                }
+
                 // Handle custom struct types
 
                 // Check if this struct is registered
@@ -135,70 +155,13 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     BasicTypeEnum::StructType(struct_ty)
                 } else if let Some(enum_def) = self.enum_ast_defs.get(name) {
                     // Handle enum types
-                    // Enums with data are represented as structs: {i32 tag, T data}
-                    // Find the largest data type among all variants
-                    let has_data = enum_def.variants.iter().any(|v| !v.data.is_empty());
+                    // Unit-only enums are an i32 tag, data-carrying enums {i32 tag, union}
                     eprintln!(
-                        "🟠 ast_type_to_llvm for enum {}: has_data={}, variants={}",
+                        "🟠 ast_type_to_llvm for enum {}: variants={}",
                         name,
-                        has_data,
                         enum_def.variants.len()
                     );
-
-                    if has_data {
-                        // For data-carrying enums, we need to find the largest data type
-                        // For simplicity, use the first data type we find
-                        // For mixed enums (Some + None), calculate union size
-
-                        // Find the largest data type (for single-value variants, use first type)
-                        let largest_data_type = enum_def
-                            .variants
-                            .iter()
-                            .filter(|v| !v.data.is_empty())
-                            .map(|v| {
-                                // For multi-value tuple variants, create a tuple type
-                                if v.data.len() > 1 {
-                                    // Multi-value tuple: create struct type
-                                    let field_types: Vec<BasicTypeEnum> =
-                                        v.data.iter().map(|ty| self.ast_type_to_llvm(ty)).collect();
-                                    let tuple_ty = self.context.struct_type(&field_types, false);
-                                    (&v.data[0], BasicTypeEnum::StructType(tuple_ty))
-                                } else {
-                                    // Single-value tuple
-                                    let ty = &v.data[0];
-                                    let llvm_ty = self.ast_type_to_llvm(ty);
-                                    (ty, llvm_ty)
-                                }
-                            })
-                            .max_by_key(|(_, llvm_ty)| {
-                                // Get size of LLVM type
-                                match llvm_ty {
-                                    BasicTypeEnum::IntType(i) => i.get_bit_width() as usize,
-                                    BasicTypeEnum::FloatType(f) => match f {
-                                        _ if *f == self.context.f32_type() => 32,
-                                        _ if *f == self.context.f64_type() => 64,
-                                        _ => 32,
-                                    },
-                                    BasicTypeEnum::PointerType(_) => 64,
-                                    BasicTypeEnum::StructType(s) => {
-                                        // Approximate struct size
-                                        s.count_fields() as usize * 32
-                                    }
-                                    _ => 32,
-                                }
-                            })
-                            .map(|(_, llvm_ty)| llvm_ty)
-                            .unwrap_or(self.context.i8_type().into()); // Default: i8 for unit variants
-
-                        let enum_struct_type = self.context.struct_type(
-                            &[self.context.i32_type().into(), largest_data_type],
-                            false,
-                        );
-                        BasicTypeEnum::StructType(enum_struct_type)
-                    } else {
-                        // Unit-only enum: just an i32 tag
-                        BasicTypeEnum::IntType(self.context.i32_type())
-                    }
+                    self.enum_llvm_type(enum_def)
                 } else if name == "AnonymousStruct" {
                     // Placeholder for inferred structs
                     BasicTypeEnum::IntType(self.context.i32_type())
@@ -255,6 +218,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // Return as pointer to function
                 BasicTypeEnum::PointerType(fn_type.ptr_type(inkwell::AddressSpace::default()))
             }
+            Type::Generic { name, .. } if self.type_alias_params.contains_key(name) => {
+                self.ast_type_to_llvm(&self.resolve_type(ty))
+            }
+            Type::Conditional { .. } | Type::Typeof(_) => {
+                self.ast_type_to_llvm(&self.resolve_type(ty))
+            }
+            Type::Generic { name, .. } if name == "Generator" => {
+                // Generator<T> - pointer to the heap handle { resume_fn, state, item }
+                // built by the generator function's wrapper
+                BasicTypeEnum::PointerType(self.context.ptr_type(inkwell::AddressSpace::default()))
+            }
             Type::Generic { name, type_args } => {
                 // Generic struct type: Box<T>, Pair<T, U>
                 // Need to instantiate and look up monomorphized struct
@@ -294,45 +268,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
             Type::Union(types) => {
                 // Union type: T1 | T2 | T3
-                // Implementation: Tagged union with discriminator (like Rust enums)
-                // Layout: { i32 tag, <largest_type> data }
-
-                if types.is_empty() {
-                    // Empty union, fallback to i32
-                    return BasicTypeEnum::IntType(self.context.i32_type());
-                }
-
-                // Find the largest type among all union members
-                let largest_type = types
-                    .iter()
-                    .map(|ty| {
-                        let llvm_ty = self.ast_type_to_llvm(ty);
-                        let size = Self::approximate_type_size(&llvm_ty);
-                        (llvm_ty, size)
-                    })
-                    .max_by_key(|(_, size)| *size)
-                    .map(|(ty, _)| ty)
-                    .unwrap_or(self.context.i32_type().into());
-
-                // Create tagged union: { i32 tag, <largest_type> data }
-                let tag_ty = self.context.i32_type();
-                let union_struct = self
-                    .context
-                    .struct_type(&[tag_ty.into(), largest_type], false);
-
-                BasicTypeEnum::StructType(union_struct)
+                // Tagged like a data enum: { i32 tag, storage } (see union_layout.rs)
+                self.union_llvm_type(types)
             }
 
             Type::Intersection(types) => {
                 // Intersection type: T1 & T2 & T3
-                // Implementation: Merge all struct fields (for trait composition)
-                // For now, use first type as representation
-                // TODO: Implement proper intersection semantics
-                if let Some(first_type) = types.first() {
-                    self.ast_type_to_llvm(first_type)
-                } else {
-                    BasicTypeEnum::IntType(self.context.i32_type())
-                }
+                // Structs merge into one record, contracts are a fat pointer (see intersection_layout.rs)
+                self.intersection_llvm_type(types)
             }
 
             // ===== PHASE 0: BUILTIN TYPES =====
@@ -410,9 +353,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 BasicTypeEnum::StructType(box_struct)
             }
 
+            Type::Option(inner_ty) if Self::option_uses_niche(inner_ty) => {
+                // Option<&T>: a null pointer is None, no tag needed
+                self.ast_type_to_llvm(inner_ty)
+            }
+
             Type::Option(inner_ty) => {
                 // Option<T> layout: { i32, T }
-                // Fields: tag (0=None, 1=Some), value
+                // Fields: tag (0=Some, 1=None), value
                 // Runtime helpers handle unwrap/is_some checks
                 let tag_ty = self.context.i32_type();
                 let value_ty = self.ast_type_to_llvm(inner_ty);
@@ -493,22 +441,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
             }
 
             Type::Slice(_elem_ty, _is_mutable) => {
-                // Slice<T> layout: { i8*, i64, i64 }
-                // Fields: data_ptr, len, elem_size
-                // Match C runtime VexSlice struct
-                let ptr_ty = self.context.ptr_type(inkwell::AddressSpace::default());
-                let size_ty = self.context.i64_type();
-
-                let slice_struct = self.context.struct_type(
-                    &[
-                        ptr_ty.into(),  // void *data
-                        size_ty.into(), // size_t len
-                        size_ty.into(), // size_t elem_size
-                    ],
-                    false,
-                );
-
-                BasicTypeEnum::StructType(slice_struct)
+                // Slice<T> layout matches the C runtime VexSlice: { data, len, elem_size }
+                BasicTypeEnum::StructType(self.slice_struct_type())
             }
 
             Type::Never => {
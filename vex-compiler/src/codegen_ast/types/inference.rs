@@ -1,5 +1,6 @@
 use super::super::ASTCodeGen;
 use inkwell::types::BasicTypeEnum;
+use std::collections::HashMap;
 use vex_ast::*;
 
 impl<'ctx> ASTCodeGen<'ctx> {
@@ -82,6 +83,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
             } => {
                 // Infer receiver type first
                 let receiver_type = self.infer_expression_type_with_context(receiver, None)?;
+                if let Some(ty) = self.range_method_type(&receiver_type, method) {
+                    return Ok(ty);
+                }
+                if let Some(ty) = self.int_overflow_method_type(&receiver_type, method) {
+                    return Ok(ty);
+                }
 
                 // Try to resolve method return type from function_defs
                 // Extract type args from receiver type (e.g., Vec<i32> -> [i32])
@@ -164,6 +171,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
         }
     }
 
+    /// Fully known type the semantic checker recorded for an expression span
+    fn checked_expression_type(&self, span_id: &Option<String>) -> Option<Type> {
+        let table = self.type_table.as_ref()?;
+        let ty = table.expr_type(span_id.as_deref()?)?;
+        if self.contains_unknown(ty) {
+            None
+        } else {
+            Some(ty.clone())
+        }
+    }
+
     pub(crate) fn infer_expression_type(&self, expr: &Expression) -> Result<Type, String> {
         let result = match expr {
             Expression::IntLiteral(_) => Ok(Type::I32),
@@ -308,12 +326,42 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Expression::Reference { expr, is_mutable } => {
                 // For references (&x), return Reference type wrapping the inner type
                 let inner_type = self.infer_expression_type(expr)?;
-                Ok(Type::Reference(Box::new(inner_type), *is_mutable))
+                match inner_type {
+                    // &arr and &arr[a..b] compile to slice views
+                    Type::Array(elem, _) => Ok(Type::Slice(elem, *is_mutable)),
+                    Type::Slice(elem, _) if matches!(**expr, Expression::Index { .. }) => {
+                        Ok(Type::Slice(elem, *is_mutable))
+                    }
+                    inner_type => Ok(Type::Reference(Box::new(inner_type), *is_mutable)),
+                }
+            }
+            // arr[i] is the element type; arr[a..b] is a view with the source's mutability
+            Expression::Index { object, index } => {
+                let object_type = self.infer_expression_type(object)?;
+                match Self::slice_element_type(&object_type) {
+                    Some(elem) if self.range_index_kind(index).is_some() => Ok(Type::Slice(
+                        Box::new(elem.clone()),
+                        Self::is_mutable_view(&object_type),
+                    )),
+                    Some(elem) => Ok(elem.clone()),
+                    None => Ok(Type::I32),
+                }
+            }
+            Expression::EnumLiteral {
+                enum_name,
+                variant,
+                data,
+                ..
+            } if enum_name == "Option" && variant == "Some" && data.len() == 1 => {
+                // Some(x) is Option<typeof x> (decides niche packing for Some(&x))
+                let inner_type = self.infer_expression_type(&data[0])?;
+                Ok(Type::Option(Box::new(inner_type)))
             }
             Expression::Cast { target_type, .. } => {
                 // For cast expressions (x as T), return the target type
                 Ok(target_type.clone())
             }
+            Expression::Is { .. } => Ok(Type::Bool),
             Expression::FieldAccess { object, field } => {
                 // ⭐ PRIORITY 1: Check namespace constant access FIRST (math.PI)
                 // This must come before struct field access to handle module imports correctly
@@ -397,12 +445,28 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     | BinaryOp::GtEq => Ok(Type::Bool),
                     // Logical operators return bool
                     BinaryOp::And | BinaryOp::Or => Ok(Type::Bool),
-                    // Range operators need special handling (TODO: return Range<T>)
-                    BinaryOp::Range | BinaryOp::RangeInclusive => {
-                        Ok(left_type) // Placeholder
-                    }
-                    // Null coalesce returns left type
-                    BinaryOp::NullCoalesce => Ok(left_type),
+                    // a..b is a Range<T> over the operand type
+                    BinaryOp::Range | BinaryOp::RangeInclusive => Ok(Type::Generic {
+                        name: if *op == BinaryOp::Range {
+                            "Range".to_string()
+                        } else {
+                            "RangeInclusive".to_string()
+                        },
+                        type_args: vec![left_type],
+                    }),
+                    // Null coalesce unwraps Option<T> to T; pointers keep their type
+                    BinaryOp::NullCoalesce => match left_type {
+                        Type::Option(inner) => Ok(*inner),
+                        Type::Generic { name, type_args }
+                            if name == "Option" && type_args.len() == 1 =>
+                        {
+                            type_args
+                                .into_iter()
+                                .next()
+                                .ok_or_else(|| "Option without a type argument".to_string())
+                        }
+                        other => Ok(other),
+                    },
                     // Arithmetic/bitwise operators preserve operand type
                     _ => Ok(left_type),
                 }
@@ -413,7 +477,11 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 self.infer_expression_type(expr)
             }
             Expression::MethodCall {
-                receiver, method, ..
+                receiver,
+                method,
+                type_args: method_type_args,
+                args,
+                ..
             } => {
                 // Infer return type of method call
 
@@ -440,6 +508,18 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     self.infer_expression_type(receiver)?
                 };
 
+                // error.message() -> str
+                if matches!(receiver_type, Type::Error) && method == "message" {
+                    return Ok(Type::Named("str".to_string()));
+                }
+
+                if let Some(ty) = self.range_method_type(&receiver_type, method) {
+                    return Ok(ty);
+                }
+                if let Some(ty) = self.int_overflow_method_type(&receiver_type, method) {
+                    return Ok(ty);
+                }
+
                 // Get struct name and extract type arguments
                 let (struct_name, type_args) = match &receiver_type {
                     Type::Named(name) => (name.clone(), vec![]),
@@ -499,6 +579,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     struct_name, method, receiver_type, type_args
                 );
 
+                // Generic methods: Vec<i32>.map<string> is instantiated as Vec_i32_map_string
+                if let Some(ty) = self.generic_method_return_type(
+                    &struct_name,
+                    &type_args,
+                    method,
+                    method_type_args,
+                    args,
+                ) {
+                    return Ok(ty);
+                }
+
                 // ⭐ CRITICAL: For generic types like Vec<i32>, construct the instantiated method name
                 // E.g., Vec<i32>.get() -> look up Vec_i32_get, NOT Vec_get!
                 let instantiated_method_name = if !type_args.is_empty() {
@@ -593,7 +684,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 // typeof always returns string
                 Ok(Type::String)
             }
-            Expression::Call { func, args, .. } => {
+            Expression::Call {
+                span_id,
+                func,
+                args,
+                ..
+            } => {
                 // Infer return type of function call
                 match func.as_ref() {
                     Expression::Ident(func_name) => {
@@ -633,7 +729,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                             };
 
                             if let Some(func_def) = self.function_defs.get(&mangled_name) {
-                                return Ok(func_def.return_type.clone().unwrap_or(Type::I32));
+                                return Ok(Self::call_result_type(func_def));
                             } else {
                                 // ⭐ GENERIC INSTANTIATION FIX: Try with only first arg type
                                 // Generic functions are named min_i32 (type param), not min_i32_i32 (args)
@@ -641,10 +737,7 @@ impl<'ctx> ASTCodeGen<'ctx> {
                                     let first_arg_suffix = self.generate_type_suffix(&arg_types[0]);
                                     let generic_name = format!("{}{}", func_name, first_arg_suffix);
                                     if let Some(func_def) = self.function_defs.get(&generic_name) {
-                                        return Ok(func_def
-                                            .return_type
-                                            .clone()
-                                            .unwrap_or(Type::I32));
+                                        return Ok(Self::call_result_type(func_def));
                                     }
                                 }
                             }
@@ -652,19 +745,122 @@ impl<'ctx> ASTCodeGen<'ctx> {
 
                         // 4. Fallback to base name (last registered overload)
                         if let Some(func_def) = self.function_defs.get(func_name.as_str()) {
-                            return Ok(func_def.return_type.clone().unwrap_or(Type::I32));
+                            return Ok(Self::call_result_type(func_def));
+                        }
+
+                        // Use the type checker's answer before guessing
+                        if let Some(ty) = self.checked_expression_type(span_id) {
+                            return Ok(ty);
                         }
 
                         Ok(Type::I32) // Fallback
                     }
-                    _ => Ok(Type::I32),
+                    _ => Ok(self.checked_expression_type(span_id).unwrap_or(Type::I32)),
                 }
             }
+            // 0..n is a Range<T>; T comes from whichever bound is present
+            Expression::Range { start, end } | Expression::RangeInclusive { start, end } => {
+                let elem = match start.as_ref().or(end.as_ref()) {
+                    Some(bound) => self.infer_expression_type(bound)?,
+                    None => Type::I64,
+                };
+                let name = if matches!(expr, Expression::Range { .. }) {
+                    "Range"
+                } else {
+                    "RangeInclusive"
+                };
+                Ok(Type::Generic {
+                    name: name.to_string(),
+                    type_args: vec![elem],
+                })
+            }
+            // await Future<T> yields T
+            Expression::Await(inner) => match self.infer_expression_type(inner)? {
+                Type::Future(result_ty) => Ok(*result_ty),
+                other => Ok(other),
+            },
+            // new(Struct{..}) is used like the struct itself, new(x) is a &T to the heap cell
+            Expression::New(inner) => match self.infer_new_inner_type(inner)? {
+                Type::Named(name) if self.struct_defs.contains_key(&name) => Ok(Type::Named(name)),
+                inner_ty => Ok(Type::Reference(Box::new(inner_ty), true)),
+            },
+            Expression::Make { element_type, .. } => match element_type {
+                Type::Channel(_) => Ok(element_type.clone()),
+                _ => Ok(Type::Slice(Box::new(element_type.clone()), true)),
+            },
+            Expression::ErrorNew(_) => Ok(Type::Error),
             _ => Ok(Type::I32), // Default for complex expressions
         };
         result
     }
 
+    /// Result type of a call to a method with its own type parameters, None for
+    /// other methods
+    fn generic_method_return_type(
+        &self,
+        struct_name: &str,
+        struct_type_args: &[Type],
+        method: &str,
+        method_type_args: &[Type],
+        args: &[Expression],
+    ) -> Option<Type> {
+        let struct_def = self.struct_ast_defs.get(struct_name)?;
+        let method_def = struct_def
+            .methods
+            .iter()
+            .find(|m| m.name == method)
+            .or_else(|| {
+                self.function_defs
+                    .get(&format!("{}_{}", struct_name, method))
+            })
+            .filter(|m| !m.type_params.is_empty())?
+            .clone();
+        let method_type_args = if method_type_args.is_empty() {
+            self.infer_method_type_args(struct_name, struct_type_args, &method_def, args, None)
+                .ok()?
+        } else {
+            method_type_args.to_vec()
+        };
+
+        let mangled_name = self.generic_method_mangled_name(
+            struct_name,
+            struct_type_args,
+            method,
+            &method_type_args,
+        );
+        if let Some(func_def) = self.function_defs.get(&mangled_name) {
+            return Some(Self::call_result_type(func_def));
+        }
+
+        // Not instantiated yet: substitute both levels of parameters in the signature
+        let type_subst: HashMap<String, Type> = struct_def
+            .type_params
+            .iter()
+            .zip(struct_type_args)
+            .chain(method_def.type_params.iter().zip(&method_type_args))
+            .map(|(param, ty)| (param.name.clone(), ty.clone()))
+            .collect();
+        let return_type = method_def.return_type.as_ref()?;
+        Some(self.substitute_type(return_type, &type_subst))
+    }
+
+    /// Type produced by calling `func_def`: async functions hand back a Future<T>
+    fn call_result_type(func_def: &Function) -> Type {
+        if func_def.is_async {
+            let result_ty = func_def.return_type.clone().unwrap_or(Type::Nil);
+            return Type::Future(Box::new(result_ty));
+        }
+        func_def.return_type.clone().unwrap_or(Type::I32)
+    }
+
+    /// Type of the value moved onto the heap by new(expr)
+    pub(crate) fn infer_new_inner_type(&self, expr: &Expression) -> Result<Type, String> {
+        match expr {
+            Expression::StructLiteral { name, .. } => Ok(Type::Named(name.clone())),
+            _ => self.infer_expression_type(expr),
+        }
+    }
+
     /// Substitute generic type parameters (e.g., T) with concrete types (e.g., i32)
     /// For Vec<i32>.get() returning T → returns i32
     fn substitute_type_params(
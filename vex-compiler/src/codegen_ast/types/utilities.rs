@@ -1,4 +1,5 @@
 use super::super::ASTCodeGen;
+use crate::type_system::{conditional, const_generics};
 use inkwell::types::BasicTypeEnum;
 use std::collections::HashMap;
 use vex_ast::*;
@@ -26,6 +27,8 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Type::Nil => "nil".to_string(),
             Type::Error => "error".to_string(),
             Type::Named(name) => name.clone(),
+            Type::ConstValue(value) => value.to_string(),
+            Type::Dyn(contract) => format!("dyn_{}", contract),
             Type::Generic { name, type_args } => {
                 // Recursive mangling for nested generics: Box<Box<i32>> => Box_Box_i32
                 let arg_strs: Vec<String> = type_args
@@ -49,6 +52,33 @@ impl<'ctx> ASTCodeGen<'ctx> {
         }
     }
 
+    /// Length of `[T; SIZE]` written over module constants
+    pub(crate) fn const_array_len(&self, size: &Expression) -> Option<usize> {
+        const_generics::eval_const_expr(size, &self.const_sizes)
+            .and_then(|n| usize::try_from(n).ok())
+    }
+
+    /// Reject a `[T; N]` whose length does not evaluate before it is lowered:
+    /// there is no layout for an array of unknown length
+    pub(crate) fn check_array_lengths(&self, ty: &Type) -> Result<(), String> {
+        let mut unresolved = None;
+        conditional::map_type(ty, &mut |t| {
+            if let Type::ConstArray { size, .. } = t {
+                if unresolved.is_none() && self.const_array_len(size).is_none() {
+                    unresolved = Some(const_generics::const_expr_to_string(size));
+                }
+            }
+            None
+        });
+        match unresolved {
+            Some(len) => Err(format!(
+                "Array length `{}` is not a compile-time constant",
+                len
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Substitute type parameters in a type
     pub(crate) fn substitute_type(&self, ty: &Type, type_subst: &HashMap<String, Type>) -> Type {
         match ty {
@@ -66,6 +96,20 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Type::Array(elem_ty, size) => {
                 Type::Array(Box::new(self.substitute_type(elem_ty, type_subst)), *size)
             }
+            // [T; R * C] with R = 2, C = 3 is [T; 6]
+            Type::ConstArray { elem_type, size } => {
+                let elem_type = Box::new(self.substitute_type(elem_type, type_subst));
+                match const_generics::eval_const_expr(size, type_subst)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .or_else(|| self.const_array_len(size))
+                {
+                    Some(n) => Type::Array(elem_type, n),
+                    None => Type::ConstArray {
+                        elem_type,
+                        size: size.clone(),
+                    },
+                }
+            }
             Type::Generic { name, type_args } => {
                 // Recursively substitute type arguments (for nested generics)
                 // Example: Box<T> where T=Box<i32> becomes Box<Box<i32>>
@@ -74,6 +118,14 @@ impl<'ctx> ASTCodeGen<'ctx> {
                     .map(|arg| self.substitute_type(arg, type_subst))
                     .collect();
 
+                // Generic type aliases expand instead of naming an instantiated struct
+                if self.type_alias_params.contains_key(name) {
+                    return self.resolve_type(&Type::Generic {
+                        name: name.clone(),
+                        type_args: substituted_args,
+                    });
+                }
+
                 // ⭐ NEW: If all type arguments are fully concrete (no Named type params),
                 // convert to a mangled Named type for instantiated structs
                 // Example: Generic { name: "HashMap", type_args: [Named("str"), I32] }
@@ -98,6 +150,12 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 }
             }
 
+            // Conditional types reduce once their check type is concrete
+            Type::Conditional { .. } => {
+                let substituted = conditional::substitute_params(ty, type_subst);
+                self.reduce_conditional_type(&substituted)
+            }
+
             // Phase 0: Builtin types (substitute inner type parameters)
             Type::Vec(inner) => Type::Vec(Box::new(self.substitute_type(inner, type_subst))),
             Type::Box(inner) => Type::Box(Box::new(self.substitute_type(inner, type_subst))),
@@ -107,6 +165,34 @@ impl<'ctx> ASTCodeGen<'ctx> {
                 Box::new(self.substitute_type(err_ty, type_subst)),
             ),
 
+            // Closure parameters: fn(T): U
+            Type::Function {
+                params,
+                return_type,
+            } => Type::Function {
+                params: params
+                    .iter()
+                    .map(|p| self.substitute_type(p, type_subst))
+                    .collect(),
+                return_type: Box::new(self.substitute_type(return_type, type_subst)),
+            },
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|e| self.substitute_type(e, type_subst))
+                    .collect(),
+            ),
+
+            // Projections: T.Item with T = Counter is Counter's `type Item`
+            Type::AssociatedType { self_type, name } => {
+                let self_type = self.substitute_type(self_type, type_subst);
+                self.resolve_associated_type(&self_type, name)
+                    .unwrap_or_else(|_| Type::AssociatedType {
+                        self_type: Box::new(self_type),
+                        name: name.clone(),
+                    })
+            }
+
             _ => ty.clone(),
         }
     }
@@ -128,9 +214,42 @@ impl<'ctx> ASTCodeGen<'ctx> {
             Type::Reference(inner, is_mut) => {
                 Type::Reference(Box::new(self.resolve_type(inner)), *is_mut)
             }
-            Type::Generic { name, type_args } => Type::Generic {
-                name: name.clone(),
-                type_args: type_args.iter().map(|t| self.resolve_type(t)).collect(),
+            Type::Generic { name, type_args } => {
+                let type_args: Vec<Type> = type_args.iter().map(|t| self.resolve_type(t)).collect();
+                // Generic type alias: type Unwrap<T> = ...; Unwrap<Option<i32>>
+                if let (Some(params), Some(body)) = (
+                    self.type_alias_params.get(name),
+                    self.type_aliases.get(name),
+                ) {
+                    let subst: HashMap<String, Type> = params
+                        .iter()
+                        .cloned()
+                        .zip(
+                            type_args
+                                .into_iter()
+                                .chain(std::iter::repeat(Type::Unknown)),
+                        )
+                        .collect();
+                    return self.resolve_type(&conditional::substitute_params(body, &subst));
+                }
+                Type::Generic {
+                    name: name.clone(),
+                    type_args,
+                }
+            }
+            Type::Conditional { .. } => {
+                let resolved = conditional::map_type(ty, &mut |t| match t {
+                    Type::Conditional { .. } | Type::Infer(_) => None,
+                    _ => Some(self.resolve_type(t)),
+                });
+                self.reduce_conditional_type(&resolved)
+            }
+            // Record intersections resolve to their registered struct
+            Type::Intersection(members) => self.resolve_intersection(members),
+            // typeof(expr) takes the expression's checked type
+            Type::Typeof(expr) => match self.infer_expression_type(expr) {
+                Ok(expr_ty) => self.resolve_type(&expr_ty),
+                Err(_) => Type::Unknown,
             },
 
             // Phase 0: Builtin types (recursively resolve inner types)
@@ -147,6 +266,17 @@ impl<'ctx> ASTCodeGen<'ctx> {
         }
     }
 
+    /// Reduce conditional types; the type checker has already reported irreducible ones
+    fn reduce_conditional_type(&self, ty: &Type) -> Type {
+        match conditional::reduce_conditionals(ty) {
+            Ok(reduced) => reduced,
+            Err(e) => {
+                eprintln!("⚠️  Conditional type not reduced: {}", e);
+                Type::Unknown
+            }
+        }
+    }
+
     /// Approximate size of LLVM type in bits (for Result<T,E> union layout)
     pub(crate) fn approximate_type_size(llvm_ty: &BasicTypeEnum) -> u32 {
         match llvm_ty {
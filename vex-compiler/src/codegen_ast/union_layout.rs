@@ -0,0 +1,329 @@
+// Union type layout
+// `A | B` values are a tag (the member's position) plus storage shared with
+// data enums; wrapping members, `is` tests and narrowing a variable to one member
+
+use super::ASTCodeGen;
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use inkwell::IntPredicate;
+use vex_ast::{Expression, Type};
+
+/// Bindings of a union variable replaced while one of its members is known
+pub(crate) struct NarrowedVariable<'ctx> {
+    name: String,
+    ptr: PointerValue<'ctx>,
+    llvm_type: BasicTypeEnum<'ctx>,
+    ast_type: Option<Type>,
+    struct_name: Option<String>,
+}
+
+impl<'ctx> ASTCodeGen<'ctx> {
+    /// Members of a union type after alias resolution, None for any other type
+    pub(crate) fn union_members(&self, ty: &Type) -> Option<Vec<Type>> {
+        match self.resolve_type(ty) {
+            Type::Union(members) if !members.is_empty() => Some(members),
+            _ => None,
+        }
+    }
+
+    /// LLVM layout of a union: `{ i32 tag, storage }`, storage as for data enums
+    pub(crate) fn union_llvm_type(&self, members: &[Type]) -> BasicTypeEnum<'ctx> {
+        let tag_type = self.context.i32_type();
+        let payloads: Vec<BasicTypeEnum<'ctx>> = members
+            .iter()
+            .map(|member| self.ast_type_to_llvm(member))
+            .collect();
+
+        match self.shared_payload_storage(&payloads) {
+            Some(storage) => self
+                .context
+                .struct_type(&[tag_type.into(), storage], false)
+                .into(),
+            None => tag_type.into(),
+        }
+    }
+
+    /// Tag of `member` within `members`
+    pub(crate) fn union_member_index(&self, members: &[Type], member: &Type) -> Option<usize> {
+        let member = self.resolve_type(member);
+        members.iter().position(|m| self.resolve_type(m) == member)
+    }
+
+    /// Compile `expr` where a union is expected, wrapping a member value with its tag
+    pub(crate) fn compile_into_union(
+        &mut self,
+        expr: &Expression,
+        members: &[Type],
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let union_type = self.union_llvm_type(members);
+        let found = self
+            .infer_expression_type(expr)
+            .ok()
+            .map(|ty| self.resolve_type(&ty));
+
+        // Already a value of this union: variables, calls, nested matches
+        if let Some(Type::Union(found_members)) = &found {
+            if found_members.as_slice() == members {
+                return self.compile_expression(expr);
+            }
+        }
+
+        let index = found
+            .as_ref()
+            .and_then(|ty| self.union_member_index(members, ty))
+            .or_else(|| self.literal_union_member(expr, members));
+        let value = match index {
+            Some(index) => self.compile_expression_with_type(expr, Some(&members[index]))?,
+            None => self.compile_expression(expr)?,
+        };
+        if value.get_type() == union_type {
+            return Ok(value);
+        }
+
+        // Fall back to the LLVM type for values inference could not name (`str` literals)
+        let index = match index {
+            Some(index) => index,
+            None => members
+                .iter()
+                .position(|m| self.ast_type_to_llvm(m) == value.get_type())
+                .ok_or_else(|| {
+                    format!(
+                        "value of type `{}` is not a member of `{}`",
+                        found
+                            .as_ref()
+                            .map(|ty| self.type_to_string(ty))
+                            .unwrap_or_else(|| "_".to_string()),
+                        self.type_to_string(&Type::Union(members.to_vec()))
+                    )
+                })?,
+        };
+
+        // Struct values are held by pointer; the union stores the struct itself
+        let payload_type = self.ast_type_to_llvm(&members[index]);
+        let payload = match value {
+            BasicValueEnum::PointerValue(ptr) if payload_type.is_struct_type() => self
+                .builder
+                .build_load(payload_type, ptr, "union_member")
+                .map_err(|e| format!("Failed to load union member: {}", e))?,
+            _ => value,
+        };
+
+        self.build_enum_value(union_type, index as u64, Some(payload))
+    }
+
+    /// First integer (or float) member for an unsuffixed numeric literal
+    fn literal_union_member(&self, expr: &Expression, members: &[Type]) -> Option<usize> {
+        let is_integer = |ty: &Type| {
+            matches!(
+                ty,
+                Type::I8
+                    | Type::I16
+                    | Type::I32
+                    | Type::I64
+                    | Type::I128
+                    | Type::U8
+                    | Type::U16
+                    | Type::U32
+                    | Type::U64
+                    | Type::U128
+            )
+        };
+        let is_float = |ty: &Type| matches!(ty, Type::F16 | Type::F32 | Type::F64);
+
+        match expr {
+            Expression::IntLiteral(_) | Expression::BigIntLiteral(_) => members
+                .iter()
+                .position(|m| is_integer(&self.resolve_type(m))),
+            Expression::FloatLiteral(_) => {
+                members.iter().position(|m| is_float(&self.resolve_type(m)))
+            }
+            _ => None,
+        }
+    }
+
+    /// `expr is T`: true when the union's tag is T's position
+    pub(crate) fn compile_is_expression(
+        &mut self,
+        expr: &Expression,
+        target_type: &Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let ty = self.infer_expression_type(expr)?;
+        let members = self.union_members(&ty).ok_or_else(|| {
+            format!(
+                "`is` needs a union value, found `{}`",
+                self.type_to_string(&ty)
+            )
+        })?;
+        let index = self
+            .union_member_index(&members, target_type)
+            .ok_or_else(|| {
+                format!(
+                    "`{}` is not a member of `{}`",
+                    self.type_to_string(target_type),
+                    self.type_to_string(&ty)
+                )
+            })?;
+
+        let value = self.compile_expression(expr)?;
+        let value = match value {
+            BasicValueEnum::PointerValue(ptr) => self
+                .builder
+                .build_load(self.union_llvm_type(&members), ptr, "union_value")
+                .map_err(|e| format!("Failed to load union value: {}", e))?,
+            other => other,
+        };
+        Ok(self.build_union_tag_check(value, index)?.into())
+    }
+
+    /// Compare the tag of a union value with member `index`
+    pub(crate) fn build_union_tag_check(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        index: usize,
+    ) -> Result<IntValue<'ctx>, String> {
+        let tag = self.extract_enum_tag(value)?;
+        let expected = self.context.i32_type().const_int(index as u64, false);
+        self.builder
+            .build_int_compare(IntPredicate::EQ, tag, expected, "union_tag_check")
+            .map_err(|e| format!("Failed to compare union tag: {}", e))
+    }
+
+    /// Read the value of `member` out of a union value whose tag selects it
+    pub(crate) fn extract_union_member(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        member: &Type,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let union_val = match value {
+            BasicValueEnum::StructValue(union_val) => union_val,
+            other => return Ok(other),
+        };
+        let member_type = self.ast_type_to_llvm(member);
+        if union_val.get_type().get_field_type_at_index(1) == Some(member_type) {
+            return self
+                .builder
+                .build_extract_value(union_val, 1, "union_member")
+                .map_err(|e| format!("Failed to extract union member: {}", e));
+        }
+
+        let slot = self
+            .builder
+            .build_alloca(union_val.get_type(), "union_view")
+            .map_err(|e| format!("Failed to allocate union view: {}", e))?;
+        self.builder
+            .build_store(slot, union_val)
+            .map_err(|e| format!("Failed to store union view: {}", e))?;
+        let payload_ptr = self
+            .builder
+            .build_struct_gep(union_val.get_type(), slot, 1, "union_payload_ptr")
+            .map_err(|e| format!("Failed to get union payload pointer: {}", e))?;
+        self.builder
+            .build_load(member_type, payload_ptr, "union_member")
+            .map_err(|e| format!("Failed to load union member: {}", e))
+    }
+
+    /// `name is T` where `name` is a union variable: the variable, T and all members
+    pub(crate) fn union_narrowing(
+        &self,
+        condition: &Expression,
+    ) -> Option<(String, Type, Vec<Type>)> {
+        let (name, target_type) = match condition {
+            Expression::Is { expr, target_type } => match expr.as_ref() {
+                Expression::Ident(name) => (name, target_type),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let members = self.union_members(self.variable_ast_types.get(name)?)?;
+        self.union_member_index(&members, target_type)?;
+        Some((name.clone(), target_type.clone(), members))
+    }
+
+    /// The one member left for an else branch when every condition tests the
+    /// same union variable with `is`
+    pub(crate) fn remaining_union_member(
+        &self,
+        conditions: &[&Expression],
+    ) -> Option<(String, Type, Vec<Type>)> {
+        let mut tested = Vec::new();
+        let mut variable: Option<(String, Vec<Type>)> = None;
+        for condition in conditions {
+            let (name, member, members) = self.union_narrowing(condition)?;
+            match &variable {
+                Some((first, _)) if *first != name => return None,
+                Some(_) => {}
+                None => variable = Some((name, members)),
+            }
+            tested.push(self.resolve_type(&member));
+        }
+
+        let (name, members) = variable?;
+        let mut remaining = members
+            .iter()
+            .filter(|m| !tested.contains(&self.resolve_type(m)));
+        match (remaining.next(), remaining.next()) {
+            (Some(last), None) => Some((name, last.clone(), members.clone())),
+            _ => None,
+        }
+    }
+
+    /// Rebind a union variable to its payload as `member`, in place
+    ///
+    /// Reads and writes of the narrowed name go straight to the union's
+    /// storage, so assignments in the branch update the union itself.
+    pub(crate) fn narrow_union_variable(
+        &mut self,
+        name: &str,
+        member: &Type,
+        members: &[Type],
+    ) -> Result<Option<NarrowedVariable<'ctx>>, String> {
+        let (ptr, llvm_type) = match (self.variables.get(name), self.variable_types.get(name)) {
+            (Some(ptr), Some(llvm_type)) => (*ptr, *llvm_type),
+            _ => return Ok(None),
+        };
+        let union_type = match self.union_llvm_type(members) {
+            BasicTypeEnum::StructType(union_type) if llvm_type == union_type.into() => union_type,
+            _ => return Ok(None),
+        };
+
+        let payload_ptr = self
+            .builder
+            .build_struct_gep(union_type, ptr, 1, &format!("{}_as_member", name))
+            .map_err(|e| format!("Failed to get union payload pointer: {}", e))?;
+        let saved = NarrowedVariable {
+            name: name.to_string(),
+            ptr,
+            llvm_type,
+            ast_type: self.variable_ast_types.get(name).cloned(),
+            struct_name: self.variable_struct_names.remove(name),
+        };
+
+        let member = self.resolve_type(member);
+        self.variables.insert(name.to_string(), payload_ptr);
+        self.variable_types
+            .insert(name.to_string(), self.ast_type_to_llvm(&member));
+        if let Type::Named(struct_name) = &member {
+            if self.struct_defs.contains_key(struct_name) {
+                self.variable_struct_names
+                    .insert(name.to_string(), struct_name.clone());
+            }
+        }
+        self.variable_ast_types.insert(name.to_string(), member);
+        Ok(Some(saved))
+    }
+
+    /// Undo `narrow_union_variable` when the branch ends
+    pub(crate) fn restore_narrowed_variable(&mut self, saved: NarrowedVariable<'ctx>) {
+        self.variables.insert(saved.name.clone(), saved.ptr);
+        self.variable_types
+            .insert(saved.name.clone(), saved.llvm_type);
+        match saved.ast_type {
+            Some(ast_type) => self.variable_ast_types.insert(saved.name.clone(), ast_type),
+            None => self.variable_ast_types.remove(&saved.name),
+        };
+        match saved.struct_name {
+            Some(struct_name) => self.variable_struct_names.insert(saved.name, struct_name),
+            None => self.variable_struct_names.remove(&saved.name),
+        };
+    }
+}
@@ -7,6 +7,7 @@ pub mod prelude; // Embedded Layer 1 prelude (Vex code in compiler binary)
 pub mod prelude_loader; // Prelude parser and injection
 pub mod resolver; // Platform detection & stdlib resolution
 pub mod trait_bounds_checker; // Trait bounds verification
+pub mod type_checker; // Semantic type checking before codegen
 pub mod type_registry; // Builtin type name registry for O(1) lookup
 pub mod type_system; // Type coercion rules and safety checks
 pub mod types; // Type interning and utilities
@@ -41,6 +42,7 @@ pub use module_resolver::ModuleResolver;
 pub use prelude_loader::{inject_prelude_into_program, load_embedded_prelude, PreludeLoadError};
 pub use resolver::{Arch, Platform, ResolveError, StdlibResolver, Target};
 pub use trait_bounds_checker::TraitBoundsChecker;
+pub use type_checker::{TypeChecker, TypeTable};
 pub use utils::llvm_safety::{
     emit_bounds_check, emit_null_check, is_pointer_provably_nonnull,
     validate_stack_allocation_size, MAX_STACK_ALLOC_SIZE,
@@ -1,15 +1,18 @@
 // Linter module - static analysis and warnings for Vex code
 // Detects code quality issues and unused code
 
+use crate::type_checker::TypeTable;
 use vex_ast::Program;
 use vex_diagnostics::{Diagnostic, DiagnosticEngine};
 
+pub mod unused_result;
 pub mod unused_variables;
 // TODO: Fix AST structure compatibility
 // pub mod dead_code;
 // pub mod unreachable_code;
 // pub mod naming_convention;
 
+pub use unused_result::UnusedResultRule;
 pub use unused_variables::UnusedVariableRule;
 // pub use dead_code::DeadCodeRule;
 // pub use unreachable_code::UnreachableCodeRule;
@@ -20,6 +23,17 @@ pub trait LintRule {
     /// Check the AST and return diagnostics
     fn check(&self, program: &Program, span_map: &vex_diagnostics::SpanMap) -> Vec<Diagnostic>;
 
+    /// Check the AST with the type checker's results available.
+    /// Rules that do not need types keep the untyped behavior.
+    fn check_typed(
+        &self,
+        program: &Program,
+        span_map: &vex_diagnostics::SpanMap,
+        _types: &TypeTable,
+    ) -> Vec<Diagnostic> {
+        self.check(program, span_map)
+    }
+
     /// Name of the lint rule
     fn name(&self) -> &str;
 
@@ -45,6 +59,7 @@ impl Linter {
 
         // Add default rules
         linter.add_rule(Box::new(UnusedVariableRule::new()));
+        linter.add_rule(Box::new(UnusedResultRule::new()));
         // TODO: Add more rules when AST structure is fully compatible
         // linter.add_rule(Box::new(DeadCodeRule::new()));
         // linter.add_rule(Box::new(UnreachableCodeRule::new()));
@@ -82,6 +97,23 @@ impl Linter {
         all_diagnostics
     }
 
+    /// Run all lint rules on a program that passed type checking
+    pub fn lint_typed(
+        &mut self,
+        program: &Program,
+        span_map: &vex_diagnostics::SpanMap,
+        types: &TypeTable,
+    ) -> Vec<Diagnostic> {
+        let mut all_diagnostics = Vec::new();
+
+        for rule in &self.rules {
+            let diagnostics = rule.check_typed(program, span_map, types);
+            all_diagnostics.extend(diagnostics);
+        }
+
+        all_diagnostics
+    }
+
     /// Get the diagnostic engine
     pub fn diagnostics(&self) -> &DiagnosticEngine {
         &self.diagnostics
@@ -109,7 +141,7 @@ mod tests {
     #[test]
     fn test_linter_creation() {
         let linter = Linter::new();
-        assert_eq!(linter.rules.len(), 1); // Only unused_variables for now
+        assert_eq!(linter.rules.len(), 2); // unused_variables and unused_result
     }
 
     #[test]
@@ -0,0 +1,230 @@
+// Unused result detection - finds calls whose Result or Future is thrown away
+// A dropped Result hides an error; a dropped Future is never run
+
+use super::LintRule;
+use crate::type_checker::TypeTable;
+use vex_ast::{Block, Expression, Item, Program, Statement, Type};
+use vex_diagnostics::{error_codes, Diagnostic, Span, SpanMap};
+
+/// Lint rule for call statements whose value must be handled.
+/// Needs the type checker's results: without them every call is skipped.
+pub struct UnusedResultRule;
+
+impl UnusedResultRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn check_block(
+        &self,
+        block: &Block,
+        span_map: &SpanMap,
+        types: &TypeTable,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for stmt in &block.statements {
+            self.check_statement(stmt, span_map, types, diagnostics);
+        }
+    }
+
+    fn check_statement(
+        &self,
+        stmt: &Statement,
+        span_map: &SpanMap,
+        types: &TypeTable,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match stmt {
+            Statement::Expression(Expression::Call {
+                span_id: Some(id), ..
+            }) => {
+                if let Some(diagnostic) = types
+                    .expr_type(id)
+                    .and_then(|ty| Self::discarded_value_warning(ty, span_map.get(id)))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+            Statement::If {
+                then_block,
+                elif_branches,
+                else_block,
+                ..
+            } => {
+                self.check_block(then_block, span_map, types, diagnostics);
+                for (_cond, block) in elif_branches {
+                    self.check_block(block, span_map, types, diagnostics);
+                }
+                if let Some(block) = else_block {
+                    self.check_block(block, span_map, types, diagnostics);
+                }
+            }
+            Statement::While { body, .. }
+            | Statement::For { body, .. }
+            | Statement::ForIn { body, .. }
+            | Statement::Loop { body, .. }
+            | Statement::Unsafe { block: body, .. } => {
+                self.check_block(body, span_map, types, diagnostics)
+            }
+            Statement::Switch {
+                cases,
+                default_case,
+                ..
+            } => {
+                for case in cases {
+                    self.check_block(&case.body, span_map, types, diagnostics);
+                }
+                if let Some(block) = default_case {
+                    self.check_block(block, span_map, types, diagnostics);
+                }
+            }
+            Statement::Select {
+                cases,
+                default_case,
+                ..
+            } => {
+                for case in cases {
+                    self.check_block(&case.body, span_map, types, diagnostics);
+                }
+                if let Some(block) = default_case {
+                    self.check_block(block, span_map, types, diagnostics);
+                }
+            }
+            Statement::Attributed { stmt, .. } => {
+                self.check_statement(stmt, span_map, types, diagnostics)
+            }
+            _ => {}
+        }
+    }
+
+    /// Warning for a discarded value of this type, if it must be used
+    fn discarded_value_warning(ty: &Type, span: Option<&Span>) -> Option<Diagnostic> {
+        let is_result = matches!(ty, Type::Result(_, _))
+            || matches!(ty, Type::Generic { name, .. } if name == "Result");
+        let (message, help) = if is_result {
+            (
+                "unused `Result` that must be used",
+                "handle the error with `?` or `match`, or bind it to a `_`-prefixed name",
+            )
+        } else if matches!(ty, Type::Future(_)) {
+            (
+                "unused future that is never awaited",
+                "`await` the call, or the async function never runs",
+            )
+        } else {
+            return None;
+        };
+
+        Some(
+            Diagnostic::warning(
+                error_codes::UNUSED_RESULT,
+                message.to_string(),
+                span.cloned().unwrap_or_else(Span::unknown),
+            )
+            .with_primary_label("value discarded here".to_string())
+            .with_help(help.to_string()),
+        )
+    }
+}
+
+impl LintRule for UnusedResultRule {
+    fn check(&self, _program: &Program, _span_map: &SpanMap) -> Vec<Diagnostic> {
+        // Call types are only known after type checking
+        Vec::new()
+    }
+
+    fn check_typed(
+        &self,
+        program: &Program,
+        span_map: &SpanMap,
+        types: &TypeTable,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for item in &program.items {
+            match item {
+                Item::Function(func) => {
+                    self.check_block(&func.body, span_map, types, &mut diagnostics);
+                }
+                Item::Struct(s) => {
+                    for method in &s.methods {
+                        self.check_block(&method.body, span_map, types, &mut diagnostics);
+                    }
+                }
+                Item::TraitImpl(impl_) => {
+                    for method in &impl_.methods {
+                        self.check_block(&method.body, span_map, types, &mut diagnostics);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    fn name(&self) -> &str {
+        "unused_result"
+    }
+}
+
+impl Default for UnusedResultRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::type_checker::TypeChecker;
+    use vex_parser::Parser;
+
+    fn lint(source: &str) -> Vec<String> {
+        let mut parser = Parser::new(source).expect("lexer failed");
+        let program = parser.parse().expect("parse failed");
+        let span_map = parser.take_span_map();
+        let mut checker = TypeChecker::new(&span_map);
+        assert!(checker.check_program(&program).is_ok());
+        UnusedResultRule::new()
+            .check_typed(&program, &span_map, checker.type_table())
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_discarded_result_and_future_warn() {
+        let source = r#"
+            fn parse(n: i32): Result<i32, string> { return Ok(n); }
+            async fn fetch(): i32 { return 1; }
+            fn main(): i32 {
+                parse(1);
+                if true { fetch(); }
+                return 0;
+            }
+        "#;
+        assert_eq!(
+            lint(source),
+            vec![
+                "unused `Result` that must be used",
+                "unused future that is never awaited"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_handled_values_do_not_warn() {
+        let source = r#"
+            fn parse(n: i32): Result<i32, string> { return Ok(n); }
+            fn log(n: i32) { }
+            fn main(): i32 {
+                let _ignored = parse(1);
+                let r = parse(2);
+                log(3);
+                return 0;
+            }
+        "#;
+        assert!(lint(source).is_empty());
+    }
+}
@@ -123,6 +123,9 @@ impl UnusedVariableRule {
                         self.collect_declarations(&else_blk.statements, declared);
                     }
                 }
+                Statement::Attributed { stmt, .. } => {
+                    self.collect_declarations(&[*stmt.clone()], declared);
+                }
                 _ => {}
             }
         }
@@ -190,7 +193,7 @@ impl UnusedVariableRule {
                     }
                     self.collect_usages(&body.statements, used);
                 }
-                Statement::Defer(stmt) => {
+                Statement::Defer(stmt) | Statement::Attributed { stmt, .. } => {
                     self.collect_usages(&[*stmt.clone()], used);
                 }
                 _ => {}
@@ -256,7 +259,7 @@ impl UnusedVariableRule {
             Expression::AsyncBlock { statements, .. } => {
                 self.collect_usages(statements, used);
             }
-            Expression::Match { value, arms } => {
+            Expression::Match { value, arms, .. } => {
                 self.collect_usages_expr(value, used);
                 for arm in arms {
                     self.collect_usages_expr(&arm.body, used);
@@ -286,7 +289,7 @@ impl UnusedVariableRule {
             Expression::Deref(expr) => {
                 self.collect_usages_expr(expr, used);
             }
-            Expression::Await(expr) => {
+            Expression::Await(expr) | Expression::Yield(expr) => {
                 self.collect_usages_expr(expr, used);
             }
             Expression::Launch { args, grid, .. } => {
@@ -303,7 +306,7 @@ impl UnusedVariableRule {
             Expression::Make { size, .. } => {
                 self.collect_usages_expr(size, used);
             }
-            Expression::Cast { expr, .. } => {
+            Expression::Cast { expr, .. } | Expression::Is { expr, .. } => {
                 self.collect_usages_expr(expr, used);
             }
             _ => {}
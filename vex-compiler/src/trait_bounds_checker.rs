@@ -1,8 +1,11 @@
 // Trait bounds checker for generic functions
 // Verifies that type arguments satisfy trait bounds at compile time
 
+use crate::type_checker::display_type;
+use crate::type_system::intersection::{classify_intersection, IntersectionShape};
+use crate::type_system::{conditional, const_generics};
 use std::collections::HashMap;
-use vex_ast::{Function, Program, Struct, Trait, TraitBound, Type, TypeParam};
+use vex_ast::{CaptureMode, Function, Program, Struct, Trait, TraitBound, Type, TypeParam};
 use vex_diagnostics::DiagnosticEngine;
 
 pub struct TraitBoundsChecker {
@@ -13,6 +16,19 @@ pub struct TraitBoundsChecker {
     // Maps trait names to their definitions
     traits: HashMap<String, Trait>,
 
+    // Non-generic type aliases, so `type ReadWrite = Reader & Writer` works as a bound
+    aliases: HashMap<String, Type>,
+
+    // Fields of non-generic structs, for intersections that merge them
+    struct_fields: HashMap<String, Vec<(String, Type)>>,
+
+    // Associated type bindings, so `T.Item` can be checked once T is known
+    // Example: ("Counter", "Item") -> i32
+    associated_types: HashMap<(String, String), Type>,
+
+    // Type parameter names of generic structs, for bindings such as `type Item = T;`
+    struct_type_params: HashMap<String, Vec<String>>,
+
     // Diagnostic engine for error reporting
     diagnostics: DiagnosticEngine,
 }
@@ -22,6 +38,10 @@ impl TraitBoundsChecker {
         Self {
             type_impls: HashMap::new(),
             traits: HashMap::new(),
+            aliases: HashMap::new(),
+            struct_fields: HashMap::new(),
+            associated_types: HashMap::new(),
+            struct_type_params: HashMap::new(),
             diagnostics: DiagnosticEngine::new(),
         }
     }
@@ -44,6 +64,24 @@ impl TraitBoundsChecker {
             }
         }
 
+        // Collect aliases and struct fields for intersection types
+        for item in &program.items {
+            match item {
+                vex_ast::Item::TypeAlias(alias) if alias.type_params.is_empty() => {
+                    self.aliases.insert(alias.name.clone(), alias.ty.clone());
+                }
+                vex_ast::Item::Struct(struct_def) if struct_def.type_params.is_empty() => {
+                    let fields = struct_def
+                        .fields
+                        .iter()
+                        .map(|f| (f.name.clone(), f.ty.clone()))
+                        .collect();
+                    self.struct_fields.insert(struct_def.name.clone(), fields);
+                }
+                _ => {}
+            }
+        }
+
         // Collect trait implementations from inline struct declarations
         for item in &program.items {
             if let vex_ast::Item::Struct(struct_def) = item {
@@ -55,6 +93,19 @@ impl TraitBoundsChecker {
                         .collect();
                     self.type_impls.insert(struct_def.name.clone(), trait_names);
                 }
+                self.register_associated_types(
+                    &struct_def.name,
+                    &struct_def.associated_type_bindings,
+                );
+                if !struct_def.type_params.is_empty() {
+                    let params = struct_def
+                        .type_params
+                        .iter()
+                        .map(|p| p.name.clone())
+                        .collect();
+                    self.struct_type_params
+                        .insert(struct_def.name.clone(), params);
+                }
             }
         }
 
@@ -62,6 +113,7 @@ impl TraitBoundsChecker {
         for item in &program.items {
             if let vex_ast::Item::TraitImpl(impl_block) = item {
                 let type_name = self.extract_type_name(&impl_block.for_type);
+                self.register_associated_types(&type_name, &impl_block.associated_type_bindings);
                 self.type_impls
                     .entry(type_name)
                     .or_insert_with(Vec::new)
@@ -70,6 +122,45 @@ impl TraitBoundsChecker {
         }
     }
 
+    fn register_associated_types(&mut self, type_name: &str, bindings: &[(String, Type)]) {
+        for (assoc_name, bound_type) in bindings {
+            self.associated_types.insert(
+                (type_name.to_string(), assoc_name.clone()),
+                bound_type.clone(),
+            );
+        }
+    }
+
+    /// Concrete type of the projection `ty.assoc_name`: `Counter.Item` is the
+    /// `type Item = ...` binding of Counter, with a generic struct's parameters
+    /// replaced by the type's arguments
+    fn project_associated_type(&self, ty: &Type, assoc_name: &str) -> Option<Type> {
+        let (type_name, type_args) = match self.resolve_alias(ty) {
+            Type::Reference(inner, _) => return self.project_associated_type(&inner, assoc_name),
+            Type::Named(name) => (name, Vec::new()),
+            Type::Generic { name, type_args } => (name, type_args),
+            _ => return None,
+        };
+        let bound_type = self
+            .associated_types
+            .get(&(type_name.clone(), assoc_name.to_string()))?;
+
+        let substitutions: HashMap<String, Type> = self
+            .struct_type_params
+            .get(&type_name)
+            .map(|params| params.iter().cloned().zip(type_args).collect())
+            .unwrap_or_default();
+        let projected = conditional::substitute_params(bound_type, &substitutions);
+
+        // A binding may itself name a projection: type Item = Inner.Item;
+        match projected {
+            Type::AssociatedType { self_type, name } => {
+                self.project_associated_type(&self_type, &name)
+            }
+            projected => Some(projected),
+        }
+    }
+
     /// Check if a function call with generic type arguments satisfies trait bounds
     /// Example: print_value<Point>(...) where print_value<T: Display>
     /// Note: type_args may be shorter than type_params if defaults are used
@@ -112,8 +203,20 @@ impl TraitBoundsChecker {
     pub fn check_struct_bounds(
         &mut self,
         struct_def: &Struct,
-        type_args: &[Type],
+        generic_args: &[Type],
     ) -> Result<(), String> {
+        // Const arguments follow the type arguments and must fit their params: Matrix<f32, 2, 3>
+        let (type_args, const_args) =
+            const_generics::split_generic_args(&struct_def.name, generic_args)?;
+        if !const_args.is_empty() || !struct_def.const_params.is_empty() {
+            const_generics::bind_const_params(
+                &struct_def.name,
+                &struct_def.const_params,
+                &const_args,
+            )?;
+        }
+        let type_args = type_args.as_slice();
+
         // Allow type_args to be shorter if remaining params have defaults
         if type_args.len() > struct_def.type_params.len() {
             return Err(format!(
@@ -158,12 +261,14 @@ impl TraitBoundsChecker {
         // Check each required trait bound
         for required_trait in &type_param.bounds {
             match required_trait {
-                TraitBound::Simple(trait_name) => {
-                    if !self.type_implements_trait(&type_name, trait_name) {
-                        return Err(format!(
-                            "Trait bound not satisfied: type `{}` does not implement trait `{}` (required by type parameter `{}`)",
-                            type_name, trait_name, type_param.name
-                        ));
+                TraitBound::Simple(bound_name) => {
+                    for trait_name in self.expand_bound(bound_name) {
+                        if !self.satisfies_contract(concrete_type, &trait_name) {
+                            return Err(format!(
+                                "Trait bound not satisfied: type `{}` does not implement trait `{}` (required by type parameter `{}`)",
+                                type_name, trait_name, type_param.name
+                            ));
+                        }
                     }
                 }
                 TraitBound::Callable { trait_name, .. } => {
@@ -171,8 +276,8 @@ impl TraitBoundsChecker {
                     // For now, we accept function types as satisfying closure traits
                     match concrete_type {
                         Type::Function { .. } => {
-                            // Function types satisfy all closure traits
-                            // TODO: More precise checking based on capture mode
+                            // The capture mode is not part of the type; closure
+                            // arguments are checked by check_closure_arguments
                         }
                         _ => {
                             return Err(format!(
@@ -197,6 +302,62 @@ impl TraitBoundsChecker {
         }
     }
 
+    /// Contracts a bound requires: an alias such as `type ReadWrite = Reader & Writer`
+    /// stands for every contract in it
+    fn expand_bound(&self, bound_name: &str) -> Vec<String> {
+        match self.resolve_alias(&Type::Named(bound_name.to_string())) {
+            Type::Intersection(members) => match self.classify(&members) {
+                Ok(IntersectionShape::Contracts(contracts)) => contracts,
+                _ => vec![bound_name.to_string()],
+            },
+            _ => vec![bound_name.to_string()],
+        }
+    }
+
+    /// Whether a type argument implements a contract. An intersection does when
+    /// it lists the contract or one of its struct members implements it.
+    fn satisfies_contract(&self, ty: &Type, trait_name: &str) -> bool {
+        match self.resolve_alias(ty) {
+            Type::Intersection(members) => match self.classify(&members) {
+                Ok(IntersectionShape::Value(value)) => self.satisfies_contract(&value, trait_name),
+                Ok(IntersectionShape::Record { structs, .. })
+                    if structs
+                        .iter()
+                        .any(|name| self.type_implements_trait(name, trait_name)) =>
+                {
+                    true
+                }
+                Ok(shape) => shape.contracts().iter().any(|c| c == trait_name),
+                Err(_) => false,
+            },
+            resolved => self.type_implements_trait(&self.extract_type_name(&resolved), trait_name),
+        }
+    }
+
+    fn resolve_alias(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        // Bounded so alias cycles (type A = B; type B = A;) terminate
+        for _ in 0..32 {
+            match &ty {
+                Type::Named(name) => match self.aliases.get(name) {
+                    Some(aliased) => ty = aliased.clone(),
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        ty
+    }
+
+    fn classify(&self, members: &[Type]) -> Result<IntersectionShape, String> {
+        let members: Vec<Type> = members.iter().map(|m| self.resolve_alias(m)).collect();
+        classify_intersection(
+            &members,
+            |name| self.traits.contains_key(name),
+            |name| self.struct_fields.get(name).cloned(),
+        )
+    }
+
     /// Extract type name from Type enum for lookup
     fn extract_type_name(&self, ty: &Type) -> String {
         match ty {
@@ -222,6 +383,7 @@ impl TraitBoundsChecker {
             Type::Byte => "byte".to_string(),
             Type::Array(_, _) => "Array".to_string(),
             Type::ConstArray { .. } => "ConstArray".to_string(),
+            Type::ConstValue(value) => value.to_string(),
             Type::Slice(_, _) => "Slice".to_string(),
             Type::Tuple(_) => "Tuple".to_string(),
             Type::Function { .. } => "Function".to_string(),
@@ -232,7 +394,11 @@ impl TraitBoundsChecker {
             Type::Vec(_) => "Vec".to_string(),
             Type::Box(_) => "Box".to_string(),
             Type::Union(_) => "Union".to_string(),
-            Type::Intersection(_) => "Intersection".to_string(),
+            Type::Intersection(members) => members
+                .iter()
+                .map(|m| self.extract_type_name(m))
+                .collect::<Vec<_>>()
+                .join(" & "),
             Type::Conditional { .. } => "Conditional".to_string(),
             Type::Infer(_) => "infer".to_string(),
             Type::Error => "error".to_string(),
@@ -249,6 +415,7 @@ impl TraitBoundsChecker {
             Type::Channel(inner) => format!("Channel<{}>", self.extract_type_name(inner)),
             Type::Future(inner) => format!("Future<{}>", self.extract_type_name(inner)),
             Type::Typeof(_) => "typeof".to_string(), // Compile-time evaluated
+            Type::Dyn(contract) => format!("dyn {}", contract),
             Type::SelfType => "Self".to_string(),
             Type::AssociatedType { name, .. } => name.clone(), // Return associated type name
         }
@@ -287,6 +454,62 @@ impl TraitBoundsChecker {
         }
     }
 
+    /// Check closure arguments against the closure trait their parameter requires.
+    /// `capture_modes` has one entry per argument, None for arguments that are not closures.
+    /// A Callable closure also serves where CallableMut or CallableOnce is required, and
+    /// a CallableMut closure where CallableOnce is; never the other way round.
+    pub fn check_closure_arguments(
+        &self,
+        func: &Function,
+        capture_modes: &[Option<CaptureMode>],
+    ) -> Result<(), String> {
+        for (param, mode) in func.params.iter().zip(capture_modes) {
+            let (Some(mode), Type::Named(type_param)) = (mode, &param.ty) else {
+                continue;
+            };
+            for required in Self::closure_bounds(func, type_param) {
+                let reason = match (required, mode) {
+                    ("Callable", CaptureMode::Mutable) => "it assigns to a captured variable",
+                    ("Callable" | "CallableMut", CaptureMode::Once) => {
+                        "it moves a captured value out of its environment"
+                    }
+                    _ => continue,
+                };
+                return Err(format!(
+                    "Closure passed as `{}` to `{}` does not implement `{}` (required by type parameter `{}`): {}",
+                    param.name, func.name, required, type_param, reason
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Closure traits required of a type parameter, from its bounds and the where clause
+    fn closure_bounds<'f>(func: &'f Function, type_param: &str) -> Vec<&'f str> {
+        let inline = func
+            .type_params
+            .iter()
+            .filter(|tp| tp.name == type_param)
+            .flat_map(|tp| tp.bounds.iter());
+        let where_bounds = func
+            .where_clause
+            .iter()
+            .flat_map(|predicate| match predicate {
+                vex_ast::WhereClausePredicate::TypeBound {
+                    type_param: name,
+                    bounds,
+                } if name == type_param => bounds.as_slice(),
+                _ => &[],
+            });
+        inline
+            .chain(where_bounds)
+            .filter_map(|bound| match bound {
+                TraitBound::Callable { trait_name, .. } => Some(trait_name.as_str()),
+                TraitBound::Simple(_) => None,
+            })
+            .collect()
+    }
+
     /// Check where clause predicates for a function
     /// Validates that concrete type arguments satisfy where clause constraints
     /// Example: where T: Display, T.Item: Clone
@@ -310,18 +533,20 @@ impl TraitBoundsChecker {
                     // Check each trait bound
                     for bound in bounds {
                         match bound {
-                            TraitBound::Simple(trait_name) => {
-                                if !self.type_implements_trait(&type_name, trait_name) {
-                                    return Err(format!(
-                                        "Where clause not satisfied: type `{}` does not implement trait `{}` (required for type parameter `{}`)",
-                                        type_name, trait_name, type_param
-                                    ));
+                            TraitBound::Simple(bound_name) => {
+                                for trait_name in self.expand_bound(bound_name) {
+                                    if !self.satisfies_contract(concrete_type, &trait_name) {
+                                        return Err(format!(
+                                            "Where clause not satisfied: type `{}` does not implement trait `{}` (required for type parameter `{}`)",
+                                            type_name, trait_name, type_param
+                                        ));
+                                    }
                                 }
                             }
                             TraitBound::Callable { trait_name, .. } => {
                                 match concrete_type {
                                     Type::Function { .. } => {
-                                        // Function types satisfy closure traits
+                                        // Capture modes are checked by check_closure_arguments
                                     }
                                     _ => {
                                         return Err(format!(
@@ -339,34 +564,39 @@ impl TraitBoundsChecker {
                     assoc_type,
                     bounds,
                 } => {
-                    // For associated type bounds like T.Item: Display
-                    // We need to:
-                    // 1. Get the concrete type for T
-                    // 2. Find what T.Item resolves to
-                    // 3. Check if that type implements the required traits
-
+                    // T.Item: Display with T = Counter checks Counter's `type Item`
                     let concrete_type = type_substitutions.get(type_param).ok_or_else(|| {
                         format!("Type parameter '{}' not found in substitutions", type_param)
                     })?;
+                    let type_name = display_type(concrete_type);
 
-                    // For now, we'll do basic validation
-                    // Full implementation would require resolving associated types
-                    let type_name = self.extract_type_name(concrete_type);
+                    let projected = self
+                        .project_associated_type(concrete_type, assoc_type)
+                        .ok_or_else(|| {
+                            format!(
+                                "Where clause not satisfied: type `{}` has no associated type `{}` (required by `{}.{}`)",
+                                type_name, assoc_type, type_param, assoc_type
+                            )
+                        })?;
 
-                    // TODO: Resolve associated type T.Item to concrete type
-                    // For now, we'll just validate that T implements the trait that defines Item
-                    // This is incomplete but better than nothing
-                    eprintln!(
-                        "⚠️  Associated type bound validation incomplete: {}.{}: {:?}",
-                        type_name, assoc_type, bounds
-                    );
-
-                    // At minimum, ensure the type is known
-                    if type_name == "Unknown" || type_name == "error" {
-                        return Err(format!(
-                            "Cannot validate associated type bound {}.{} for unknown type",
-                            type_param, assoc_type
-                        ));
+                    for bound in bounds {
+                        let TraitBound::Simple(bound_name) = bound else {
+                            continue;
+                        };
+                        for trait_name in self.expand_bound(bound_name) {
+                            if !self.satisfies_contract(&projected, &trait_name) {
+                                return Err(format!(
+                                    "Where clause not satisfied: `{}.{}` is `{}`, which does not implement trait `{}` (required by `{}.{}: {}`)",
+                                    type_name,
+                                    assoc_type,
+                                    display_type(&projected),
+                                    trait_name,
+                                    type_param,
+                                    assoc_type,
+                                    bound_name
+                                ));
+                            }
+                        }
                     }
                 }
             }
@@ -399,3 +629,182 @@ impl TraitBoundsChecker {
         substitutions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vex_ast::Item;
+    use vex_parser::Parser;
+
+    fn checker_and_function(source: &str, name: &str) -> (TraitBoundsChecker, Function) {
+        let program = Parser::new(source)
+            .expect("lexer failed")
+            .parse()
+            .expect("parse failed");
+        let mut checker = TraitBoundsChecker::new();
+        checker.initialize(&program);
+        let func = program
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Function(f) if f.name == name => Some(f.clone()),
+                _ => None,
+            })
+            .expect("function not found");
+        (checker, func)
+    }
+
+    #[test]
+    fn test_intersection_bounds_require_every_contract() {
+        let source = r#"
+            contract Reader { fn read(): i32; }
+            contract Writer { fn write(v: i32); }
+            struct File impl Reader, Writer { fd: i32 }
+            struct Input impl Reader { fd: i32 }
+            type ReadWrite = Reader & Writer;
+            fn copy<T: Reader & Writer>(io: T): i32 { return 0; }
+            fn sync<T: ReadWrite>(io: T): i32 { return 0; }
+        "#;
+        for name in ["copy", "sync"] {
+            let (mut checker, func) = checker_and_function(source, name);
+            let file = Type::Named("File".to_string());
+            let input = Type::Named("Input".to_string());
+            assert!(checker.check_function_bounds(&func, &[file]).is_ok());
+            let err = checker.check_function_bounds(&func, &[input]).unwrap_err();
+            assert!(err.contains("`Writer`"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_records_satisfy_contracts_of_their_members() {
+        let source = r#"
+            contract Display { fn show(): string; }
+            struct Named impl Display { name: string }
+            struct Timestamped { created: i64 }
+            type Stamped = Named & Timestamped;
+            fn print<T: Display>(v: T): i32 { return 0; }
+        "#;
+        let (mut checker, func) = checker_and_function(source, "print");
+        let stamped = Type::Named("Stamped".to_string());
+        let plain = Type::Named("Timestamped".to_string());
+        assert!(checker.check_function_bounds(&func, &[stamped]).is_ok());
+        assert!(checker.check_function_bounds(&func, &[plain]).is_err());
+    }
+
+    fn check_where(
+        checker: &mut TraitBoundsChecker,
+        func: &Function,
+        ty: Type,
+    ) -> Result<(), String> {
+        let substitutions = TraitBoundsChecker::build_type_substitutions(&func.type_params, &[ty]);
+        checker.check_where_clause(&func.where_clause, &substitutions)
+    }
+
+    #[test]
+    fn test_associated_type_bounds_check_the_binding() {
+        let source = r#"
+            contract Iterator { type Item; next(): Option<Self.Item>; }
+            contract Display { show(): string; }
+            struct Label impl Display { text: string }
+            struct Labels impl Iterator { type Item = Label; index: i32 }
+            struct Counter impl Iterator { type Item = i32; count: i32 }
+            struct Empty { size: i32 }
+            struct Wrap<T> impl Iterator { type Item = T; inner: T }
+            fn show_all<T>(it: T): i32 where T: Iterator, T.Item: Display { return 0; }
+        "#;
+        let (mut checker, func) = checker_and_function(source, "show_all");
+        let named = |name: &str| Type::Named(name.to_string());
+
+        assert!(check_where(&mut checker, &func, named("Labels")).is_ok());
+
+        let err = check_where(&mut checker, &func, named("Counter")).unwrap_err();
+        assert!(err.contains("`Counter.Item` is `i32`"), "{}", err);
+        assert!(err.contains("`Display`"), "{}", err);
+
+        let err = check_where(&mut checker, &func, named("Empty")).unwrap_err();
+        assert!(err.contains("`Empty`"), "{}", err);
+
+        let wrapped_label = Type::Generic {
+            name: "Wrap".to_string(),
+            type_args: vec![named("Label")],
+        };
+        assert!(check_where(&mut checker, &func, wrapped_label).is_ok());
+        let wrapped_int = Type::Generic {
+            name: "Wrap".to_string(),
+            type_args: vec![Type::I32],
+        };
+        let err = check_where(&mut checker, &func, wrapped_int).unwrap_err();
+        assert!(err.contains("`Wrap<i32>.Item` is `i32`"), "{}", err);
+    }
+    #[test]
+    fn test_struct_const_arguments_are_validated() {
+        let source = r#"
+            struct Matrix<T, const R: usize, const C: usize> { data: [T; R * C] }
+        "#;
+        let program = Parser::new(source).unwrap().parse().unwrap();
+        let Some(Item::Struct(matrix)) = program.items.first() else {
+            panic!("expected struct");
+        };
+        let mut checker = TraitBoundsChecker::new();
+        checker.initialize(&program);
+        assert!(checker.validate_const_params(&matrix.const_params).is_ok());
+
+        let args = |consts: &[i64]| {
+            std::iter::once(Type::F32)
+                .chain(consts.iter().map(|&n| Type::ConstValue(n)))
+                .collect::<Vec<_>>()
+        };
+        assert!(checker.check_struct_bounds(matrix, &args(&[2, 3])).is_ok());
+
+        let err = checker
+            .check_struct_bounds(matrix, &args(&[2]))
+            .unwrap_err();
+        assert!(
+            err.contains("expects 2 const argument(s), got 1"),
+            "{}",
+            err
+        );
+        let err = checker
+            .check_struct_bounds(matrix, &args(&[2, -3]))
+            .unwrap_err();
+        assert!(err.contains("out of range"), "{}", err);
+    }
+
+    #[test]
+    fn test_closure_arguments_respect_capture_mode() {
+        let source = r#"
+            fn apply<F: Callable(i32): i32>(f: F, x: i32): i32 { return x; }
+            fn apply_mut<F>(f: F, x: i32): i32 where F: CallableMut(i32): i32 { return x; }
+            fn apply_once<F: CallableOnce(i32): i32>(f: F, x: i32): i32 { return x; }
+        "#;
+        let modes = |mode: CaptureMode| vec![Some(mode), None];
+
+        let (checker, apply) = checker_and_function(source, "apply");
+        assert!(checker
+            .check_closure_arguments(&apply, &modes(CaptureMode::Immutable))
+            .is_ok());
+        let err = checker
+            .check_closure_arguments(&apply, &modes(CaptureMode::Mutable))
+            .unwrap_err();
+        assert!(err.contains("assigns to a captured variable"), "{}", err);
+
+        let (checker, apply_mut) = checker_and_function(source, "apply_mut");
+        assert!(checker
+            .check_closure_arguments(&apply_mut, &modes(CaptureMode::Mutable))
+            .is_ok());
+        assert!(checker
+            .check_closure_arguments(&apply_mut, &modes(CaptureMode::Once))
+            .is_err());
+
+        let (checker, apply_once) = checker_and_function(source, "apply_once");
+        for mode in [
+            CaptureMode::Immutable,
+            CaptureMode::Mutable,
+            CaptureMode::Once,
+        ] {
+            assert!(checker
+                .check_closure_arguments(&apply_once, &modes(mode))
+                .is_ok());
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// Attribute checking for the checker
+// Validates #[name(args)] on items, fields and statements, and reports uses of
+// #[deprecated] items and discarded #[must_use] values
+
+use super::compatibility::strip_references;
+use super::Checker;
+use vex_ast::*;
+use vex_diagnostics::{error_codes, Diagnostic};
+
+/// What an attribute is written on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum AttributeTarget {
+    Function,
+    Struct,
+    Enum,
+    Field,
+    Statement,
+}
+
+impl AttributeTarget {
+    fn describe(self) -> &'static str {
+        match self {
+            AttributeTarget::Function => "a function",
+            AttributeTarget::Struct => "a struct",
+            AttributeTarget::Enum => "an enum",
+            AttributeTarget::Field => "a field",
+            AttributeTarget::Statement => "a statement",
+        }
+    }
+}
+
+/// Built-in attributes, where they apply and the argument forms they accept
+const BUILTIN_ATTRIBUTES: &[(&str, &[AttributeTarget], &str)] = &[
+    (
+        "inline",
+        &[AttributeTarget::Function],
+        "`#[inline]`, `#[inline(always)]` or `#[inline(never)]`",
+    ),
+    ("cold", &[AttributeTarget::Function], "`#[cold]`"),
+    (
+        "must_use",
+        &[
+            AttributeTarget::Function,
+            AttributeTarget::Struct,
+            AttributeTarget::Enum,
+        ],
+        "`#[must_use]` or `#[must_use(\"reason\")]`",
+    ),
+    (
+        "deprecated",
+        &[
+            AttributeTarget::Function,
+            AttributeTarget::Struct,
+            AttributeTarget::Enum,
+            AttributeTarget::Field,
+        ],
+        "`#[deprecated]`, `#[deprecated(\"note\")]` or `#[deprecated(since = \"1.0\", note = \"...\")]`",
+    ),
+];
+
+fn well_formed(name: &str, args: &[AttributeArg]) -> bool {
+    match name {
+        "inline" => match args {
+            [] => true,
+            [AttributeArg::Ident(mode)] => mode == "always" || mode == "never",
+            _ => false,
+        },
+        "cold" => args.is_empty(),
+        "must_use" => matches!(args, [] | [AttributeArg::Str(_)]),
+        "deprecated" => match args {
+            [AttributeArg::Str(_)] => true,
+            _ => args.iter().all(|arg| {
+                matches!(arg, AttributeArg::KeyValue(key, _) if key == "note" || key == "since")
+            }),
+        },
+        _ => true,
+    }
+}
+
+impl<'a> Checker<'a> {
+    /// Report unknown attributes, built-ins on the wrong target and malformed arguments
+    pub(super) fn check_attributes(&mut self, attributes: &[Attribute], target: AttributeTarget) {
+        for attr in attributes {
+            let span = attr
+                .span_id
+                .as_ref()
+                .and_then(|id| self.span_map.get(id))
+                .cloned()
+                .unwrap_or_else(|| self.span.clone());
+
+            let Some((_, targets, forms)) = BUILTIN_ATTRIBUTES
+                .iter()
+                .find(|(name, _, _)| *name == attr.name)
+            else {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        error_codes::UNKNOWN_ATTRIBUTE,
+                        format!("unknown attribute `{}`", attr.name),
+                        span,
+                    )
+                    .with_primary_label("not a built-in attribute".to_string())
+                    .with_help(
+                        "built-in attributes are `inline`, `cold`, `must_use` and `deprecated`"
+                            .to_string(),
+                    ),
+                );
+                continue;
+            };
+
+            let diagnostic = if !targets.contains(&target) {
+                Diagnostic::warning(
+                    error_codes::UNKNOWN_ATTRIBUTE,
+                    format!(
+                        "attribute `{}` cannot be applied to {}",
+                        attr.name,
+                        target.describe()
+                    ),
+                    span,
+                )
+                .with_primary_label("ignored".to_string())
+                .with_note(format!(
+                    "`{}` applies to {}",
+                    attr.name,
+                    targets
+                        .iter()
+                        .map(|t| t.describe())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            } else if !well_formed(&attr.name, &attr.args) {
+                Diagnostic::warning(
+                    error_codes::UNKNOWN_ATTRIBUTE,
+                    format!("malformed `{}` attribute", attr.name),
+                    span,
+                )
+                .with_primary_label("ignored".to_string())
+                .with_help(format!("use {}", forms))
+            } else {
+                continue;
+            };
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Report a use of an item marked #[deprecated], unless the user is deprecated too
+    pub(super) fn check_deprecated(&mut self, attributes: &[Attribute], kind: &str, name: &str) {
+        if self.in_deprecated {
+            return;
+        }
+        let Some(attr) = find_attribute(attributes, "deprecated") else {
+            return;
+        };
+        let message = match attr.message() {
+            Some(note) => format!("use of deprecated {} `{}`: {}", kind, name, note),
+            None => format!("use of deprecated {} `{}`", kind, name),
+        };
+        self.diagnostics.push(
+            Diagnostic::warning(error_codes::DEPRECATED, message, self.span.clone())
+                .with_primary_label("deprecated".to_string()),
+        );
+    }
+
+    /// Report an expression statement that discards a #[must_use] value
+    pub(super) fn check_unused_result(&mut self, expr: &Expression, ty: &Type) {
+        let by_callee = self.called_function(expr).and_then(|(callee, name)| {
+            find_attribute(&callee.attributes, "must_use").map(|attr| {
+                let message = format!("unused return value of `{}` that must be used", name);
+                (attr, message)
+            })
+        });
+        let (attr, message) = match by_callee {
+            Some(found) => found,
+            None => {
+                let type_name = match strip_references(ty) {
+                    Type::Named(name) | Type::Generic { name, .. } => name.clone(),
+                    _ => return,
+                };
+                let attributes = match (
+                    self.env.user_struct(&type_name),
+                    self.env.user_enum(&type_name),
+                ) {
+                    (Some(s), _) => &s.attributes,
+                    (None, Some(e)) => &e.attributes,
+                    (None, None) => return,
+                };
+                let Some(attr) = find_attribute(attributes, "must_use") else {
+                    return;
+                };
+                (attr, format!("unused `{}` that must be used", type_name))
+            }
+        };
+
+        let previous = match expr {
+            Expression::Call { span_id, .. } => self.enter_span(span_id),
+            _ => self.span.clone(),
+        };
+        let mut diagnostic =
+            Diagnostic::warning(error_codes::UNUSED_MUST_USE, message, self.span.clone())
+                .with_primary_label("value discarded here".to_string())
+                .with_help("bind it to a `_`-prefixed name to ignore it on purpose".to_string());
+        if let Some(reason) = attr.message() {
+            diagnostic = diagnostic.with_note(reason.to_string());
+        }
+        self.diagnostics.push(diagnostic);
+        self.span = previous;
+    }
+
+    /// Function a call expression resolves to, found without re-checking its operands
+    fn called_function(&self, expr: &Expression) -> Option<(&'a Function, String)> {
+        match expr {
+            Expression::Call { func, .. } => match func.as_ref() {
+                Expression::Ident(name) if self.lookup_local(name).is_none() => {
+                    match self.env.functions.get(name)?.as_slice() {
+                        [callee] => Some((*callee, name.clone())),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            Expression::MethodCall {
+                receiver, method, ..
+            } => {
+                let Expression::Ident(name) = receiver.as_ref() else {
+                    return None;
+                };
+                let candidates = match self.lookup_local(name) {
+                    // Instance call on a local: p.area()
+                    Some(ty) => {
+                        let type_name = match strip_references(ty) {
+                            Type::Named(type_name)
+                            | Type::Generic {
+                                name: type_name, ..
+                            } => type_name.clone(),
+                            _ => return None,
+                        };
+                        let candidates = self.env.instance_methods(&type_name, method);
+                        (candidates, type_name)
+                    }
+                    // Static call: Point.new()
+                    None => {
+                        let candidates = self
+                            .env
+                            .static_methods
+                            .get(name)
+                            .and_then(|methods| methods.get(method))
+                            .map(Vec::as_slice)
+                            .unwrap_or(&[]);
+                        (candidates, name.clone())
+                    }
+                };
+                match candidates {
+                    ([callee], type_name) => Some((*callee, format!("{}.{}", type_name, method))),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
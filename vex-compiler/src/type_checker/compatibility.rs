@@ -0,0 +1,355 @@
+// Type compatibility for the checker
+// Only reports a mismatch when both sides are fully known and belong to different kinds;
+// numeric width and sign changes are left to codegen's coercion rules.
+
+use super::expressions::range_element;
+use super::Checker;
+use crate::type_system::const_generics;
+use crate::type_system::intersection::{classify_intersection, IntersectionShape};
+use vex_ast::Type;
+
+/// Coarse kind of a known type; values of different kinds never convert implicitly
+#[derive(Debug, PartialEq)]
+enum TypeKind<'t> {
+    Numeric,
+    Bool,
+    Text,
+    Sequence,
+    Tuple,
+    Nominal(&'t str),
+}
+
+impl<'a> Checker<'a> {
+    /// Whether a value of type `found` may be used where `expected` is required
+    pub(super) fn is_compatible(&self, expected: &Type, found: &Type) -> bool {
+        // A read-only view never becomes a writable `&[T]!`
+        if matches!(expected, Type::Slice(_, true))
+            && matches!(found, Type::Slice(_, false) | Type::Reference(_, false))
+        {
+            return false;
+        }
+        let expected = strip_references(expected);
+        let found = strip_references(found);
+        if expected == found {
+            return true;
+        }
+
+        match (expected, found) {
+            (Type::Union(members), Type::Union(found_members)) => found_members
+                .iter()
+                .all(|f| members.iter().any(|m| self.is_compatible(m, f))),
+            (Type::Union(members), _) => members.iter().any(|m| self.is_compatible(m, found)),
+            // A union value only fits where every member does; narrow it with `is` or `match`
+            (_, Type::Union(members)) => members.iter().all(|m| self.is_compatible(expected, m)),
+            // Records convert by field name in either direction
+            (Type::Intersection(_), _) | (_, Type::Intersection(_)) => {
+                self.record_compatible(expected, found)
+            }
+            // Any implementing value converts to a contract object; a contract object
+            // only fits where the expected type is not known to be concrete
+            (Type::Dyn(contract), Type::Dyn(found_contract)) => contract == found_contract,
+            (Type::Dyn(contract), _) => self.implements(strip_boxes(found), contract),
+            (_, Type::Dyn(_)) => self.kind_of(expected).is_none(),
+            (Type::Option(inner), Type::Option(found_inner)) => {
+                self.is_compatible(inner, found_inner)
+            }
+            (Type::Result(ok, err), Type::Result(found_ok, found_err)) => {
+                self.is_compatible(ok, found_ok) && self.is_compatible(err, found_err)
+            }
+            (Type::Box(inner), Type::Box(found_inner))
+            | (Type::Channel(inner), Type::Channel(found_inner))
+            | (Type::Future(inner), Type::Future(found_inner)) => {
+                self.is_compatible(inner, found_inner)
+            }
+            // Implicit wrapping and unwrapping is resolved by codegen
+            (Type::Option(inner), _) | (Type::Box(inner), _) | (Type::Future(inner), _) => {
+                self.is_compatible(inner, found)
+            }
+            (_, Type::Box(inner)) => self.is_compatible(expected, inner),
+            (Type::Result(ok, err), _) => {
+                self.is_compatible(ok, found) || self.is_compatible(err, found)
+            }
+            // Range and RangeInclusive share a layout but not their end semantics
+            (
+                Type::Generic { name, type_args },
+                Type::Generic {
+                    name: found_name,
+                    type_args: found_args,
+                },
+            ) if range_element(expected).is_some() && range_element(found).is_some() => {
+                name == found_name && self.is_compatible(&type_args[0], &found_args[0])
+            }
+            (Type::Tuple(items), Type::Tuple(found_items)) => {
+                items.len() == found_items.len()
+                    && items
+                        .iter()
+                        .zip(found_items)
+                        .all(|(e, f)| self.is_compatible(e, f))
+            }
+            _ => match (sequence_element(expected), sequence_element(found)) {
+                (Some(elem), Some(found_elem)) => self.is_compatible(elem, found_elem),
+                _ => match (self.nominal_args(expected), self.nominal_args(found)) {
+                    (Some((name, args)), Some((found_name, found_args))) => {
+                        name == found_name
+                            && (args.len() != found_args.len()
+                                || args
+                                    .iter()
+                                    .zip(found_args)
+                                    .all(|(e, f)| self.is_compatible(e, f)))
+                    }
+                    _ => match (self.kind_of(expected), self.kind_of(found)) {
+                        (Some(expected_kind), Some(found_kind)) => expected_kind == found_kind,
+                        _ => true,
+                    },
+                },
+            },
+        }
+    }
+
+    /// Classify an intersection of resolved members against the program's declarations
+    pub(super) fn intersection_shape(&self, members: &[Type]) -> Result<IntersectionShape, String> {
+        classify_intersection(
+            members,
+            |name| self.env.traits.contains_key(name),
+            |name| {
+                let s = self.env.user_struct(name)?;
+                if !s.type_params.is_empty() {
+                    return None;
+                }
+                Some(
+                    s.fields
+                        .iter()
+                        .map(|f| (f.name.clone(), self.resolve_type(&f.ty)))
+                        .collect(),
+                )
+            },
+        )
+    }
+
+    /// Fields of a non-generic struct or a record intersection
+    pub(super) fn record_fields(&self, ty: &Type) -> Option<Vec<(String, Type)>> {
+        match ty {
+            Type::Intersection(members) => match self.intersection_shape(members) {
+                Ok(IntersectionShape::Record { fields, .. }) => Some(fields),
+                _ => None,
+            },
+            Type::Named(name) => match self.env.user_struct(name) {
+                Some(s) if s.type_params.is_empty() => Some(
+                    s.fields
+                        .iter()
+                        .map(|f| (f.name.clone(), self.resolve_type(&f.ty)))
+                        .collect(),
+                ),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// A record fits where every expected field is present with a compatible type and
+    /// every contract the expected intersection lists is implemented
+    fn record_compatible(&self, expected: &Type, found: &Type) -> bool {
+        let (Some(expected_fields), Some(found_fields)) =
+            (self.record_fields(expected), self.record_fields(found))
+        else {
+            // Contract-only intersections are reported where they are written
+            return self.kind_of(expected).is_none() || self.kind_of(found).is_none();
+        };
+
+        let fields_fit = expected_fields.iter().all(|(name, ty)| {
+            found_fields.iter().any(|(found_name, found_ty)| {
+                found_name == name && self.is_compatible(ty, found_ty)
+            })
+        });
+        let contracts = match expected {
+            Type::Intersection(members) => self
+                .intersection_shape(members)
+                .map(|shape| shape.contracts().to_vec())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        fields_fit && contracts.iter().all(|c| self.implements(found, c))
+    }
+
+    /// Whether a struct or record implements a contract; unknown implementations count
+    fn implements(&self, ty: &Type, contract: &str) -> bool {
+        match ty {
+            Type::Named(name) => match self.env.user_struct(name) {
+                Some(s) => match self.env.implemented_traits(s) {
+                    Some(traits) => traits.iter().any(|t| t.name == contract),
+                    None => true,
+                },
+                None => true,
+            },
+            Type::Intersection(members) => match self.intersection_shape(members) {
+                Ok(IntersectionShape::Record {
+                    structs, contracts, ..
+                }) => {
+                    contracts.iter().any(|c| c == contract)
+                        || structs
+                            .iter()
+                            .any(|s| self.implements(&Type::Named(s.clone()), contract))
+                }
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+
+    /// Kind of a type when it is fully known, None for anything the checker cannot judge
+    fn kind_of<'t>(&self, ty: &'t Type) -> Option<TypeKind<'t>> {
+        match ty {
+            Type::I8
+            | Type::I16
+            | Type::I32
+            | Type::I64
+            | Type::I128
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::F16
+            | Type::F32
+            | Type::F64
+            | Type::Byte => Some(TypeKind::Numeric),
+            Type::Bool => Some(TypeKind::Bool),
+            Type::String | Type::Error => Some(TypeKind::Text),
+            Type::Array(..) | Type::Slice(..) | Type::Vec(_) => Some(TypeKind::Sequence),
+            Type::Tuple(_) => Some(TypeKind::Tuple),
+            _ => self.nominal_args(ty).map(|(name, _)| {
+                // C-like enums are plain integer tags and convert like numbers
+                let unit_only = self
+                    .env
+                    .user_enum(name)
+                    .is_some_and(|e| e.variants.iter().all(|v| v.data.is_empty()));
+                if unit_only {
+                    TypeKind::Numeric
+                } else {
+                    TypeKind::Nominal(name)
+                }
+            }),
+        }
+    }
+
+    /// Name and type arguments of a struct or enum declared in the program
+    fn nominal_args<'t>(&self, ty: &'t Type) -> Option<(&'t str, &'t [Type])> {
+        let (name, args): (&str, &[Type]) = match ty {
+            Type::Named(name) => (name, &[]),
+            Type::Generic { name, type_args } => (name, type_args),
+            _ => return None,
+        };
+        if self.env.user_struct(name).is_some() || self.env.user_enum(name).is_some() {
+            Some((name, args))
+        } else {
+            None
+        }
+    }
+}
+
+/// Auto-deref: &T, &&T and &mut T compare as T
+pub(super) fn strip_references(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(inner, _) => strip_references(inner),
+        _ => ty,
+    }
+}
+
+/// The boxed value of `Box<T>`, which is what a contract object points at
+fn strip_boxes(ty: &Type) -> &Type {
+    match ty {
+        Type::Box(inner) => strip_boxes(inner),
+        _ => ty,
+    }
+}
+
+fn sequence_element(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Array(elem, _) | Type::Slice(elem, _) | Type::Vec(elem) => Some(elem),
+        _ => None,
+    }
+}
+
+/// Render a type the way it is written in Vex source
+pub fn display_type(ty: &Type) -> String {
+    let list = |types: &[Type]| {
+        types
+            .iter()
+            .map(display_type)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match ty {
+        Type::I8 => "i8".to_string(),
+        Type::I16 => "i16".to_string(),
+        Type::I32 => "i32".to_string(),
+        Type::I64 => "i64".to_string(),
+        Type::I128 => "i128".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::F16 => "f16".to_string(),
+        Type::F32 => "f32".to_string(),
+        Type::F64 => "f64".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::String => "string".to_string(),
+        Type::Byte => "byte".to_string(),
+        Type::Error => "error".to_string(),
+        Type::Nil => "nil".to_string(),
+        Type::Unit => "()".to_string(),
+        Type::Never => "!".to_string(),
+        Type::Any => "any".to_string(),
+        Type::SelfType => "Self".to_string(),
+        Type::Unknown | Type::Infer(_) => "_".to_string(),
+        Type::Named(name) => name.clone(),
+        Type::Generic { name, type_args } => format!("{}<{}>", name, list(type_args)),
+        Type::Array(elem, size) => format!("[{}; {}]", display_type(elem), size),
+        Type::ConstArray { elem_type, size } => format!(
+            "[{}; {}]",
+            display_type(elem_type),
+            const_generics::const_expr_to_string(size)
+        ),
+        Type::ConstValue(value) => value.to_string(),
+        Type::Slice(elem, true) => format!("&[{}]!", display_type(elem)),
+        Type::Slice(elem, false) => format!("&[{}]", display_type(elem)),
+        Type::Reference(inner, true) => format!("&{}!", display_type(inner)),
+        Type::Reference(inner, false) => format!("&{}", display_type(inner)),
+        Type::Union(members) => members
+            .iter()
+            .map(display_type)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        Type::Intersection(members) => members
+            .iter()
+            .map(display_type)
+            .collect::<Vec<_>>()
+            .join(" & "),
+        Type::Tuple(members) => format!("({})", list(members)),
+        Type::Function {
+            params,
+            return_type,
+        } => format!("fn({}): {}", list(params), display_type(return_type)),
+        Type::RawPtr { inner, is_const } => {
+            if *is_const {
+                format!("*const {}", display_type(inner))
+            } else {
+                format!("*{}", display_type(inner))
+            }
+        }
+        Type::AssociatedType { self_type, name } => {
+            format!("{}.{}", display_type(self_type), name)
+        }
+        Type::Option(inner) => format!("Option<{}>", display_type(inner)),
+        Type::Result(ok, err) => format!("Result<{}, {}>", display_type(ok), display_type(err)),
+        Type::Vec(elem) => format!("Vec<{}>", display_type(elem)),
+        Type::Box(inner) => format!("Box<{}>", display_type(inner)),
+        Type::Channel(elem) => format!("Channel<{}>", display_type(elem)),
+        Type::Future(inner) => format!("Future<{}>", display_type(inner)),
+        Type::Conditional { .. } => "<conditional>".to_string(),
+        Type::Typeof(_) => "typeof(..)".to_string(),
+        Type::Dyn(contract) => format!("dyn {}", contract),
+    }
+}
@@ -0,0 +1,166 @@
+// Item environment for the type checker
+// Collects every declaration once so expressions can resolve names without codegen state
+
+use std::collections::HashMap;
+use vex_ast::*;
+
+/// Top-level declarations visible to the checker, borrowed from the program
+#[derive(Default)]
+pub(crate) struct ItemEnv<'a> {
+    pub structs: HashMap<String, &'a Struct>,
+    pub enums: HashMap<String, &'a Enum>,
+    pub traits: HashMap<String, &'a Trait>,
+    pub aliases: HashMap<String, &'a Type>,
+    /// Generic aliases are expanded at each use: type Unwrap<T> = ...
+    pub generic_aliases: HashMap<String, &'a TypeAlias>,
+    pub functions: HashMap<String, Vec<&'a Function>>,
+    pub externs: HashMap<String, &'a ExternFunction>,
+    pub consts: HashMap<String, &'a Const>,
+    /// Instance methods by type name, from inline bodies, receiver functions and external impls
+    pub methods: HashMap<String, HashMap<String, Vec<&'a Function>>>,
+    /// Static methods by type name: fn Point.new()
+    pub static_methods: HashMap<String, HashMap<String, Vec<&'a Function>>>,
+    /// Contracts implemented through `impl Trait for Type`
+    pub external_traits: HashMap<String, Vec<String>>,
+}
+
+impl<'a> ItemEnv<'a> {
+    pub fn collect(program: &'a Program) -> Self {
+        let mut env = Self::default();
+
+        for item in &program.items {
+            match item {
+                Item::Struct(s) => {
+                    env.structs.insert(s.name.clone(), s);
+                    for method in &s.methods {
+                        env.add_method(&s.name, method);
+                    }
+                }
+                Item::Enum(e) => {
+                    env.enums.insert(e.name.clone(), e);
+                }
+                Item::Contract(t) => {
+                    env.traits.insert(t.name.clone(), t);
+                }
+                Item::TypeAlias(alias) if alias.type_params.is_empty() => {
+                    env.aliases.insert(alias.name.clone(), &alias.ty);
+                }
+                Item::TypeAlias(alias) => {
+                    env.generic_aliases.insert(alias.name.clone(), alias);
+                }
+                Item::Const(c) => {
+                    env.consts.insert(c.name.clone(), c);
+                }
+                Item::ExternBlock(block) => {
+                    for func in &block.functions {
+                        env.externs.insert(func.name.clone(), func);
+                    }
+                }
+                Item::TraitImpl(impl_) => {
+                    if let Some(type_name) = Self::nominal_name(&impl_.for_type) {
+                        env.external_traits
+                            .entry(type_name.clone())
+                            .or_default()
+                            .push(impl_.trait_name.clone());
+                        for method in &impl_.methods {
+                            env.add_method(&type_name, method);
+                        }
+                    }
+                }
+                Item::Function(func) => {
+                    if func.is_static {
+                        if let Some(type_name) = &func.static_type {
+                            env.static_methods
+                                .entry(type_name.clone())
+                                .or_default()
+                                .entry(func.name.clone())
+                                .or_default()
+                                .push(func);
+                        }
+                    } else if let Some(receiver) = &func.receiver {
+                        if let Some(type_name) = Self::nominal_name(&receiver.ty) {
+                            env.add_method(&type_name, func);
+                        }
+                    } else {
+                        env.functions
+                            .entry(func.name.clone())
+                            .or_default()
+                            .push(func);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        env
+    }
+
+    /// Type name behind a receiver or impl target: &Point, Point, Vec<T>
+    pub fn nominal_name(ty: &Type) -> Option<String> {
+        match ty {
+            Type::Named(name) | Type::Generic { name, .. } => Some(name.clone()),
+            Type::Reference(inner, _) => Self::nominal_name(inner),
+            _ => None,
+        }
+    }
+
+    fn add_method(&mut self, type_name: &str, method: &'a Function) {
+        // Methods merged from imported modules may already carry the Type_ prefix
+        let prefix = format!("{}_", type_name);
+        let name = method
+            .name
+            .strip_prefix(&prefix)
+            .unwrap_or(&method.name)
+            .to_string();
+        self.methods
+            .entry(type_name.to_string())
+            .or_default()
+            .entry(name)
+            .or_default()
+            .push(method);
+    }
+
+    /// User-declared struct, excluding builtin names the prelude redefines
+    pub fn user_struct(&self, name: &str) -> Option<&'a Struct> {
+        if crate::type_registry::is_builtin_type(name) {
+            return None;
+        }
+        self.structs.get(name).copied()
+    }
+
+    /// User-declared enum, excluding Option and Result
+    pub fn user_enum(&self, name: &str) -> Option<&'a Enum> {
+        if crate::type_registry::is_builtin_type(name) {
+            return None;
+        }
+        self.enums.get(name).copied()
+    }
+
+    pub fn instance_methods(&self, type_name: &str, method: &str) -> &[&'a Function] {
+        self.methods
+            .get(type_name)
+            .and_then(|methods| methods.get(method))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Every contract a struct implements, including supercontracts.
+    /// Returns None when one of them is not declared in this program.
+    pub fn implemented_traits(&self, s: &Struct) -> Option<Vec<&'a Trait>> {
+        let mut pending: Vec<String> = s.impl_traits.iter().map(|t| t.name.clone()).collect();
+        if let Some(external) = self.external_traits.get(&s.name) {
+            pending.extend(external.iter().cloned());
+        }
+
+        let mut traits: Vec<&'a Trait> = Vec::new();
+        while let Some(name) = pending.pop() {
+            if traits.iter().any(|t| t.name == name) {
+                continue;
+            }
+            let trait_def = self.traits.get(&name).copied()?;
+            pending.extend(trait_def.super_traits.iter().cloned());
+            traits.push(trait_def);
+        }
+        Some(traits)
+    }
+}
@@ -0,0 +1,1358 @@
+// Expression typing for the checker
+// Infers a type for every expression, records span-tagged ones in the table and checks
+// call arguments, struct and enum literals, field access and method lookup on the way
+
+use super::compatibility::{display_type, strip_references};
+use super::Checker;
+use crate::type_system::const_generics;
+use crate::type_system::intersection::IntersectionShape;
+use crate::type_system::object_safety;
+use std::collections::HashMap;
+use vex_ast::*;
+use vex_diagnostics::{error_codes, Diagnostic};
+
+impl<'a> Checker<'a> {
+    pub(super) fn infer_expr(&mut self, expr: &Expression) -> Type {
+        match expr {
+            Expression::IntLiteral(_) => Type::I32,
+            Expression::BigIntLiteral(_) => Type::I128,
+            Expression::TypedIntLiteral { type_suffix, .. }
+            | Expression::TypedBigIntLiteral { type_suffix, .. } => suffix_type(type_suffix),
+            Expression::FloatLiteral(_) => Type::F64,
+            Expression::StringLiteral(_) | Expression::FStringLiteral(_) => Type::String,
+            Expression::BoolLiteral(_) => Type::Bool,
+            Expression::Nil => Type::Nil,
+            Expression::Ident(name) => self.infer_ident(name),
+
+            Expression::Binary {
+                span_id,
+                left,
+                op,
+                right,
+            } => {
+                let previous = self.enter_span(span_id);
+                let left_ty = self.infer_expr(left);
+                let right_ty = self.infer_expr(right);
+                let ty = self.binary_result(op, &left_ty, &right_ty);
+                self.record(span_id, &ty);
+                self.span = previous;
+                ty
+            }
+            Expression::Unary { span_id, op, expr } => {
+                let previous = self.enter_span(span_id);
+                let operand = self.infer_expr(expr);
+                let ty = match op {
+                    UnaryOp::Not if operand == Type::Bool => Type::Bool,
+                    UnaryOp::Ref => Type::Reference(Box::new(operand), false),
+                    UnaryOp::Deref => deref_type(&operand),
+                    _ => operand,
+                };
+                self.record(span_id, &ty);
+                self.span = previous;
+                ty
+            }
+            Expression::Call {
+                span_id,
+                func,
+                type_args,
+                args,
+            } => {
+                let previous = self.enter_span(span_id);
+                let ty = self.infer_call(func, type_args, args);
+                self.record(span_id, &ty);
+                self.span = previous;
+                ty
+            }
+            Expression::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } => self.infer_method_call(receiver, method, args),
+            Expression::FieldAccess { object, field } => self.infer_field_access(object, field),
+            Expression::Index { object, index } => {
+                let object_ty = self.infer_expr(object);
+                let index_ty = self.infer_expr(index);
+                let is_range = range_element(&index_ty).is_some();
+                match strip_references(&object_ty) {
+                    // A subslice of a view keeps its mutability; owned storage gives a `&[T]`
+                    Type::Array(elem, _) | Type::Slice(elem, _) | Type::Vec(elem) if is_range => {
+                        let is_mutable =
+                            matches!(object_ty, Type::Slice(_, true) | Type::Reference(_, true));
+                        Type::Slice(elem.clone(), is_mutable)
+                    }
+                    Type::Array(elem, _) | Type::Slice(elem, _) | Type::Vec(elem) => {
+                        (**elem).clone()
+                    }
+                    _ => Type::Unknown,
+                }
+            }
+
+            Expression::Array(items) => {
+                let types: Vec<Type> = items.iter().map(|item| self.infer_expr(item)).collect();
+                let elem = types
+                    .into_iter()
+                    .find(|ty| *ty != Type::Unknown)
+                    .unwrap_or(Type::Unknown);
+                Type::Array(Box::new(elem), items.len())
+            }
+            Expression::ArrayRepeat(value, count) => {
+                let elem = self.infer_expr(value);
+                self.infer_expr(count);
+                match self.const_len(count) {
+                    Some(n) => Type::Array(Box::new(elem), n),
+                    None => Type::Slice(Box::new(elem), false),
+                }
+            }
+            Expression::MapLiteral(entries) => {
+                for (key, value) in entries {
+                    self.infer_expr(key);
+                    self.infer_expr(value);
+                }
+                Type::Unknown
+            }
+            Expression::TupleLiteral(items) => {
+                Type::Tuple(items.iter().map(|item| self.infer_expr(item)).collect())
+            }
+            Expression::StructLiteral {
+                name,
+                type_args,
+                fields,
+            } => self.infer_struct_literal(name, type_args, fields),
+            Expression::EnumLiteral {
+                enum_name,
+                variant,
+                data,
+                field_names,
+            } => self.infer_enum_literal(enum_name, variant, data, field_names),
+            Expression::Range { start, end } => {
+                let start_ty = start.as_ref().map(|e| self.infer_expr(e));
+                let end_ty = end.as_ref().map(|e| self.infer_expr(e));
+                range_type(start_ty, end_ty, false)
+            }
+            Expression::RangeInclusive { start, end } => {
+                let start_ty = start.as_ref().map(|e| self.infer_expr(e));
+                let end_ty = end.as_ref().map(|e| self.infer_expr(e));
+                range_type(start_ty, end_ty, true)
+            }
+
+            Expression::Reference { is_mutable, expr } => {
+                Type::Reference(Box::new(self.infer_expr(expr)), *is_mutable)
+            }
+            Expression::Deref(inner) => {
+                let inner_ty = self.infer_expr(inner);
+                deref_type(&inner_ty)
+            }
+            Expression::Await(inner) => match self.infer_expr(inner) {
+                Type::Future(result) => *result,
+                _ => Type::Unknown,
+            },
+            Expression::Yield(inner) => {
+                let found = self.infer_expr(inner);
+                self.check_yield(&found);
+                Type::Unit
+            }
+            Expression::Match { value, arms, .. } => self.infer_match(value, arms),
+            Expression::Block {
+                statements,
+                return_expr,
+            } => {
+                self.scopes.push(HashMap::new());
+                self.check_statements(statements);
+                let ty = match return_expr {
+                    Some(expr) => self.infer_expr(expr),
+                    None => Type::Unit,
+                };
+                self.scopes.pop();
+                ty
+            }
+            Expression::AsyncBlock {
+                statements,
+                return_expr,
+            } => {
+                // `return` inside the block completes the future, not the enclosing function
+                let saved_return = self.return_type.take();
+                self.scopes.push(HashMap::new());
+                self.check_statements(statements);
+                let ty = match return_expr {
+                    Some(expr) => self.infer_expr(expr),
+                    None => Type::Unit,
+                };
+                self.scopes.pop();
+                self.return_type = saved_return;
+                Type::Future(Box::new(ty))
+            }
+            Expression::Launch { grid, args, .. } => {
+                for expr in grid.iter().chain(args) {
+                    self.infer_expr(expr);
+                }
+                Type::Unknown
+            }
+            Expression::New(inner) => match self.infer_expr(inner) {
+                Type::Named(name) if self.env.user_struct(&name).is_some() => Type::Named(name),
+                inner_ty => Type::Reference(Box::new(inner_ty), true),
+            },
+            Expression::Make { element_type, size } => {
+                self.infer_expr(size);
+                match self.resolve_type(element_type) {
+                    channel @ Type::Channel(_) => channel,
+                    elem => Type::Slice(Box::new(elem), true),
+                }
+            }
+            Expression::Cast { expr, target_type } => {
+                self.infer_expr(expr);
+                self.resolve_annotation(target_type)
+            }
+            Expression::Is { expr, target_type } => {
+                let ty = self.infer_expr(expr);
+                let target = self.resolve_annotation(target_type);
+                self.check_union_member(&ty, &target, "`is`");
+                Type::Bool
+            }
+            Expression::TryOp { expr } => match self.infer_expr(expr) {
+                Type::Result(ok, _) => *ok,
+                Type::Option(inner) => *inner,
+                _ => Type::Unknown,
+            },
+            Expression::Typeof(inner) => {
+                self.infer_expr(inner);
+                Type::String
+            }
+            Expression::PostfixOp { expr, .. } => self.infer_expr(expr),
+            Expression::ErrorNew(message) => {
+                self.infer_expr(message);
+                Type::Error
+            }
+            Expression::ChannelReceive(channel) => match self.infer_expr(channel) {
+                Type::Channel(elem) => *elem,
+                _ => Type::Unknown,
+            },
+            Expression::Closure {
+                params,
+                return_type,
+                body,
+                ..
+            } => {
+                self.scopes.push(HashMap::new());
+                let mut param_types = Vec::new();
+                for param in params {
+                    // Untyped closure parameters are parsed as Infer("_")
+                    let ty = match &param.ty {
+                        Type::Infer(_) => Type::Unknown,
+                        written => self.resolve_annotation(written),
+                    };
+                    self.bind(&param.name, ty.clone());
+                    param_types.push(ty);
+                }
+                let declared = return_type.as_ref().map(|ty| self.resolve_annotation(ty));
+                let saved_return = std::mem::replace(&mut self.return_type, declared.clone());
+                let body_ty = self.infer_expr(body);
+                self.return_type = saved_return;
+                self.scopes.pop();
+                Type::Function {
+                    params: param_types,
+                    return_type: Box::new(declared.unwrap_or(body_ty)),
+                }
+            }
+            Expression::TypeConstructor {
+                type_name,
+                type_args,
+                args,
+            } => {
+                for arg in args {
+                    self.infer_expr(arg);
+                }
+                if self.env.user_struct(type_name).is_none() {
+                    return Type::Unknown;
+                }
+                if type_args.is_empty() {
+                    self.owner_type(type_name)
+                } else {
+                    Type::Generic {
+                        name: type_name.clone(),
+                        type_args: type_args.iter().map(|t| self.resolve_type(t)).collect(),
+                    }
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, span_id: &Option<String>, ty: &Type) {
+        if let Some(id) = span_id {
+            if *ty != Type::Unknown {
+                self.table.record_expr(id, ty.clone());
+            }
+        }
+    }
+
+    fn infer_ident(&mut self, name: &str) -> Type {
+        if let Some(ty) = self.lookup_local(name) {
+            return ty.clone();
+        }
+        if let Some(c) = self.env.consts.get(name).copied() {
+            return match &c.ty {
+                Some(ty) => self.resolve_in(ty, None, Vec::new()),
+                None => literal_type(&c.value),
+            };
+        }
+        if let Some([func]) = self.env.functions.get(name).map(Vec::as_slice) {
+            let func: &'a Function = func;
+            if func.type_params.is_empty() {
+                let params = func
+                    .params
+                    .iter()
+                    .map(|p| self.resolve_in(&p.ty, None, Vec::new()))
+                    .collect();
+                return Type::Function {
+                    params,
+                    return_type: Box::new(self.call_return_type(func, None)),
+                };
+            }
+        }
+        Type::Unknown
+    }
+
+    fn binary_result(&self, op: &BinaryOp, left: &Type, right: &Type) -> Type {
+        match op {
+            BinaryOp::Eq
+            | BinaryOp::NotEq
+            | BinaryOp::Lt
+            | BinaryOp::LtEq
+            | BinaryOp::Gt
+            | BinaryOp::GtEq
+            | BinaryOp::And
+            | BinaryOp::Or => Type::Bool,
+            BinaryOp::NullCoalesce => match strip_references(left) {
+                Type::Option(inner) => (**inner).clone(),
+                _ => right.clone(),
+            },
+            BinaryOp::Range | BinaryOp::RangeInclusive => range_type(
+                Some(left.clone()),
+                Some(right.clone()),
+                *op == BinaryOp::RangeInclusive,
+            ),
+            _ => {
+                let (left, right) = (strip_references(left), strip_references(right));
+                match (left, right) {
+                    (Type::String, Type::String) if *op == BinaryOp::Add => Type::String,
+                    _ if is_float(left) => left.clone(),
+                    _ if is_float(right) && is_numeric(left) => right.clone(),
+                    _ if is_numeric(left) && is_numeric(right) => {
+                        crate::type_system::coercion_rules::wider_type(left, right)
+                            .unwrap_or(left)
+                            .clone()
+                    }
+                    _ => Type::Unknown,
+                }
+            }
+        }
+    }
+
+    fn infer_call(&mut self, func: &Expression, type_args: &[Type], args: &[Expression]) -> Type {
+        if let Expression::Ident(name) = func {
+            if self.lookup_local(name).is_none() {
+                let arg_types: Vec<Type> = args.iter().map(|arg| self.infer_expr(arg)).collect();
+                let candidates: Vec<&'a Function> =
+                    self.env.functions.get(name).cloned().unwrap_or_default();
+
+                if let [callee] = candidates.as_slice() {
+                    self.check_deprecated(&callee.attributes, "function", name);
+                    if callee.type_params.is_empty()
+                        && callee.const_params.is_empty()
+                        && type_args.is_empty()
+                        && !callee.is_variadic
+                    {
+                        self.check_arguments(name, &callee.params, &arg_types, None);
+                    }
+                    return self.call_return_type(callee, None);
+                }
+                if !candidates.is_empty() {
+                    // Overloads are resolved by codegen; agree on a result only when they all do
+                    let returns: Vec<Type> = candidates
+                        .iter()
+                        .map(|callee| self.call_return_type(callee, None))
+                        .collect();
+                    return common_type(returns);
+                }
+                if let Some(ext) = self.env.externs.get(name).copied() {
+                    return match &ext.return_type {
+                        Some(ty) => self.resolve_in(ty, None, Vec::new()),
+                        None => Type::Unit,
+                    };
+                }
+                return Type::Unknown;
+            }
+        }
+
+        let callee_ty = self.infer_expr(func);
+        for arg in args {
+            self.infer_expr(arg);
+        }
+        match strip_references(&callee_ty) {
+            Type::Function { return_type, .. } => (**return_type).clone(),
+            _ => Type::Unknown,
+        }
+    }
+
+    /// Check call arguments against declared parameters; missing trailing ones may have defaults
+    fn check_arguments(
+        &mut self,
+        callee: &str,
+        params: &[Param],
+        arg_types: &[Type],
+        self_type: Option<&Type>,
+    ) {
+        for (index, (param, found)) in params.iter().zip(arg_types).enumerate() {
+            let expected = self.resolve_in(&param.ty, self_type.cloned(), Vec::new());
+            self.check_assignable(
+                &expected,
+                found,
+                &format!(
+                    "argument {} (`{}`) of `{}` has this type",
+                    index + 1,
+                    param.name,
+                    callee
+                ),
+            );
+        }
+    }
+
+    fn call_return_type(&mut self, callee: &Function, self_type: Option<&Type>) -> Type {
+        let generics: Vec<String> = callee.type_params.iter().map(|p| p.name.clone()).collect();
+        let ty = match &callee.return_type {
+            Some(ty) => self.resolve_in(ty, self_type.cloned(), generics),
+            None => Type::Unit,
+        };
+        if callee.is_async {
+            Type::Future(Box::new(ty))
+        } else {
+            ty
+        }
+    }
+
+    /// Resolve a type written in another item's signature
+    pub(super) fn resolve_in(
+        &mut self,
+        ty: &Type,
+        self_type: Option<Type>,
+        type_params: Vec<String>,
+    ) -> Type {
+        let saved_self = std::mem::replace(&mut self.self_type, self_type);
+        let saved_params = std::mem::replace(&mut self.type_params, type_params);
+        let resolved = self.resolve_type(ty);
+        self.self_type = saved_self;
+        self.type_params = saved_params;
+        resolved
+    }
+
+    fn infer_method_call(
+        &mut self,
+        receiver: &Expression,
+        method: &str,
+        args: &[Expression],
+    ) -> Type {
+        // Static calls: Point.new(1, 2)
+        if let Expression::Ident(type_name) = receiver {
+            if self.lookup_local(type_name).is_none() && !self.env.consts.contains_key(type_name) {
+                let arg_types: Vec<Type> = args.iter().map(|arg| self.infer_expr(arg)).collect();
+                let candidates: Vec<&'a Function> = self
+                    .env
+                    .static_methods
+                    .get(type_name)
+                    .and_then(|methods| methods.get(method))
+                    .cloned()
+                    .unwrap_or_default();
+                if let [callee] = candidates.as_slice() {
+                    let owner = self.owner_type(type_name);
+                    let callee_name = format!("{}.{}", type_name, method);
+                    self.check_deprecated(&callee.attributes, "associated function", &callee_name);
+                    if callee.type_params.is_empty() {
+                        self.check_arguments(
+                            &callee_name,
+                            &callee.params,
+                            &arg_types,
+                            Some(&owner),
+                        );
+                    }
+                    return self.call_return_type(callee, Some(&owner));
+                }
+                return Type::Unknown;
+            }
+        }
+
+        let receiver_ty = self.infer_expr(receiver);
+        let arg_types: Vec<Type> = args.iter().map(|arg| self.infer_expr(arg)).collect();
+        let base = auto_deref(&receiver_ty).clone();
+        let type_name = match &base {
+            Type::Named(name) | Type::Generic { name, .. } => name.clone(),
+            Type::Union(_) => {
+                self.member_of_union(error_codes::NO_SUCH_METHOD, "method", method, &base);
+                return Type::Unknown;
+            }
+            Type::Intersection(members) => {
+                return self.infer_intersection_method(members, method, &arg_types);
+            }
+            Type::Dyn(contract) => {
+                return self.infer_dyn_method(contract, method, &arg_types);
+            }
+            ty if is_numeric(ty) && !is_float(ty) && *ty != Type::Byte => {
+                return self
+                    .infer_int_overflow_method(ty, method, &arg_types)
+                    .unwrap_or(Type::Unknown);
+            }
+            _ => return Type::Unknown,
+        };
+
+        let candidates: Vec<&'a Function> = self.env.instance_methods(&type_name, method).to_vec();
+        match candidates.as_slice() {
+            [callee] => {
+                let callee_name = format!("{}.{}", type_name, method);
+                self.check_deprecated(&callee.attributes, "method", &callee_name);
+                if callee.type_params.is_empty() {
+                    self.check_arguments(&callee_name, &callee.params, &arg_types, Some(&base));
+                }
+                return self.call_return_type(callee, Some(&base));
+            }
+            [_, ..] => {
+                let returns: Vec<Type> = candidates
+                    .iter()
+                    .map(|callee| self.call_return_type(callee, Some(&base)))
+                    .collect();
+                return common_type(returns);
+            }
+            [] => {}
+        }
+
+        // Only user structs have a closed method set; enums and builtins fall through to codegen
+        let Some(s) = self.env.user_struct(&type_name) else {
+            return self
+                .infer_range_method(&base, method, &arg_types)
+                .unwrap_or(Type::Unknown);
+        };
+
+        // A field holding a closure: self.callback(x)
+        if let Some(field) = s.fields.iter().find(|f| f.name == method) {
+            return match self.struct_field_type(s, field, &base) {
+                Type::Function { return_type, .. } => *return_type,
+                _ => Type::Unknown,
+            };
+        }
+
+        let Some(traits) = self.env.implemented_traits(s) else {
+            return Type::Unknown;
+        };
+        let provided = traits
+            .iter()
+            .find_map(|t| t.methods.iter().find(|m| m.name == method).map(|m| (*t, m)));
+        if let Some((trait_def, trait_method)) = provided {
+            let mut generics: Vec<String> = trait_def
+                .type_params
+                .iter()
+                .map(|p| p.name.clone())
+                .collect();
+            generics.extend(trait_def.associated_types.iter().cloned());
+            return match &trait_method.return_type {
+                Some(ty) => self.resolve_in(ty, Some(base), generics),
+                None => Type::Unit,
+            };
+        }
+
+        // Go-style free functions stand in for methods: point_new for Point.new
+        let free_function = format!("{}_{}", type_name.to_lowercase(), method);
+        if !self.env.functions.contains_key(&free_function) {
+            self.no_such_method(&type_name, method);
+        }
+        Type::Unknown
+    }
+
+    /// Builtin methods of Range<T> and RangeInclusive<T> values
+    fn infer_range_method(
+        &mut self,
+        range: &Type,
+        method: &str,
+        arg_types: &[Type],
+    ) -> Option<Type> {
+        let elem = range_element(range)?;
+        match method {
+            "contains" => {
+                if let Some(found) = arg_types.first() {
+                    self.check_assignable(
+                        &elem,
+                        found,
+                        "argument 1 of `Range.contains` has this type",
+                    );
+                }
+                Some(Type::Bool)
+            }
+            "is_empty" | "next" => Some(Type::Bool),
+            "len" => Some(Type::I64),
+            "rev" | "step_by" => Some(range.clone()),
+            _ => None,
+        }
+    }
+
+    /// `wrapping_*`, `checked_*` and `saturating_*` arithmetic on integers
+    fn infer_int_overflow_method(
+        &mut self,
+        int_type: &Type,
+        method: &str,
+        arg_types: &[Type],
+    ) -> Option<Type> {
+        let (mode, op) = method.split_once('_')?;
+        if !matches!(op, "add" | "sub" | "mul") {
+            return None;
+        }
+        let result = match mode {
+            "wrapping" | "saturating" => int_type.clone(),
+            "checked" => Type::Option(Box::new(int_type.clone())),
+            _ => return None,
+        };
+        if let Some(found) = arg_types.first() {
+            let context = format!(
+                "argument 1 of `{}.{}` has this type",
+                display_type(int_type),
+                method
+            );
+            self.check_assignable(int_type, found, &context);
+        }
+        Some(result)
+    }
+
+    /// Methods of an intersection come from its struct members, then from the contracts
+    /// it lists. A member method runs on a copy of the member's fields, so methods that
+    /// mutate their receiver are rejected.
+    fn infer_intersection_method(
+        &mut self,
+        members: &[Type],
+        method: &str,
+        arg_types: &[Type],
+    ) -> Type {
+        let intersection = Type::Intersection(members.to_vec());
+        let (structs, contracts) = match self.intersection_shape(members) {
+            Ok(IntersectionShape::Record {
+                structs, contracts, ..
+            }) => (structs, contracts),
+            Ok(IntersectionShape::Contracts(contracts)) => (Vec::new(), contracts),
+            _ => return Type::Unknown,
+        };
+
+        for struct_name in &structs {
+            let candidates: Vec<&'a Function> =
+                self.env.instance_methods(struct_name, method).to_vec();
+            let Some(callee) = candidates.first().copied() else {
+                continue;
+            };
+            let owner = Type::Named(struct_name.clone());
+            if mutates_receiver(callee) {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        error_codes::MUTABLE_BORROW,
+                        format!(
+                            "cannot call mutating method `{}.{}` through `{}`",
+                            struct_name,
+                            method,
+                            display_type(&intersection)
+                        ),
+                        self.span.clone(),
+                    )
+                    .with_primary_label("method needs a mutable receiver".to_string())
+                    .with_help(format!(
+                        "copy the value into a `{}` and call the method on it",
+                        struct_name
+                    )),
+                );
+                return Type::Unknown;
+            }
+            if candidates.len() == 1 && callee.type_params.is_empty() {
+                let callee_name = format!("{}.{}", struct_name, method);
+                self.check_arguments(&callee_name, &callee.params, arg_types, Some(&owner));
+            }
+            return self.call_return_type(callee, Some(&owner));
+        }
+
+        let mut pending = contracts;
+        for struct_name in &structs {
+            if let Some(traits) = self
+                .env
+                .user_struct(struct_name)
+                .and_then(|s| self.env.implemented_traits(s))
+            {
+                pending.extend(traits.iter().map(|t| t.name.clone()));
+            }
+        }
+        for contract in pending {
+            let Some(trait_def) = self.env.traits.get(&contract).copied() else {
+                return Type::Unknown;
+            };
+            if let Some(trait_method) = trait_def.methods.iter().find(|m| m.name == method) {
+                let mut generics: Vec<String> = trait_def
+                    .type_params
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .collect();
+                generics.extend(trait_def.associated_types.iter().cloned());
+                return match &trait_method.return_type {
+                    Some(ty) => self.resolve_in(ty, Some(intersection), generics),
+                    None => Type::Unit,
+                };
+            }
+        }
+
+        self.diagnostics.push(
+            Diagnostic::error(
+                error_codes::NO_SUCH_METHOD,
+                format!(
+                    "no method named `{}` found for `{}`",
+                    method,
+                    display_type(&intersection)
+                ),
+                self.span.clone(),
+            )
+            .with_primary_label("method not found".to_string()),
+        );
+        Type::Unknown
+    }
+
+    /// Methods of a contract object are the slots of its vtable
+    fn infer_dyn_method(&mut self, contract: &str, method: &str, arg_types: &[Type]) -> Type {
+        let Some(trait_def) = self.env.traits.get(contract).copied() else {
+            return Type::Unknown;
+        };
+        let lookup = |name: &str| self.env.traits.get(name).copied();
+        let Ok(methods) = object_safety::vtable_methods(trait_def, &lookup) else {
+            return Type::Unknown;
+        };
+
+        let Some(trait_method) = methods.iter().copied().find(|m| m.name == method) else {
+            let mut available: Vec<String> = methods.iter().map(|m| m.name.clone()).collect();
+            available.sort();
+            let mut diagnostic = Diagnostic::error(
+                error_codes::NO_SUCH_METHOD,
+                format!("no method named `{}` found for `dyn {}`", method, contract),
+                self.span.clone(),
+            )
+            .with_primary_label("method not found".to_string());
+            if !available.is_empty() {
+                diagnostic = diagnostic
+                    .with_help(format!("available methods: `{}`", available.join("`, `")));
+            }
+            self.diagnostics.push(diagnostic);
+            return Type::Unknown;
+        };
+
+        let callee_name = format!("{}.{}", contract, method);
+        self.check_arguments(&callee_name, &trait_method.params, arg_types, None);
+        match &trait_method.return_type {
+            Some(ty) => self.resolve_type(ty),
+            None => Type::Unit,
+        }
+    }
+
+    fn no_such_method(&mut self, type_name: &str, method: &str) {
+        let mut available: Vec<String> = self
+            .env
+            .methods
+            .get(type_name)
+            .map(|methods| methods.keys().cloned().collect())
+            .unwrap_or_default();
+        available.sort();
+
+        let mut diagnostic = Diagnostic::error(
+            error_codes::NO_SUCH_METHOD,
+            format!(
+                "no method named `{}` found for struct `{}`",
+                method, type_name
+            ),
+            self.span.clone(),
+        )
+        .with_primary_label("method not found".to_string());
+        if !available.is_empty() {
+            diagnostic =
+                diagnostic.with_help(format!("available methods: `{}`", available.join("`, `")));
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn infer_field_access(&mut self, object: &Expression, field: &str) -> Type {
+        let object_ty = self.infer_expr(object);
+        let base = auto_deref(&object_ty).clone();
+
+        match &base {
+            Type::Tuple(items) => field
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index).cloned())
+                .unwrap_or(Type::Unknown),
+            Type::Union(_) => {
+                self.member_of_union(error_codes::NO_SUCH_FIELD, "field", field, &base);
+                Type::Unknown
+            }
+            Type::Intersection(_) => {
+                let Some(fields) = self.record_fields(&base) else {
+                    return Type::Unknown;
+                };
+                if let Some((_, ty)) = fields.iter().find(|(name, _)| name == field) {
+                    return ty.clone();
+                }
+                let available: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        error_codes::NO_SUCH_FIELD,
+                        format!("no field `{}` on type `{}`", field, display_type(&base)),
+                        self.span.clone(),
+                    )
+                    .with_primary_label("unknown field".to_string())
+                    .with_help(format!("available fields: `{}`", available.join("`, `"))),
+                );
+                Type::Unknown
+            }
+            Type::Named(name) | Type::Generic { name, .. } => {
+                let Some(s) = self.env.user_struct(name) else {
+                    return Type::Unknown;
+                };
+                if let Some(f) = s.fields.iter().find(|f| f.name == field) {
+                    let field_name = format!("{}.{}", s.name, field);
+                    self.check_deprecated(&f.attributes, "field", &field_name);
+                    return self.struct_field_type(s, f, &base);
+                }
+                // Method values: let f = p.area;
+                if self.env.instance_methods(name, field).is_empty() {
+                    self.no_such_field(s, field);
+                }
+                Type::Unknown
+            }
+            _ => Type::Unknown,
+        }
+    }
+
+    /// Methods and fields belong to one member; a union has to be narrowed first
+    fn member_of_union(&mut self, code: &str, what: &str, name: &str, union: &Type) {
+        self.diagnostics.push(
+            Diagnostic::error(
+                code,
+                format!(
+                    "no {} `{}` on union type `{}`",
+                    what,
+                    name,
+                    display_type(union)
+                ),
+                self.span.clone(),
+            )
+            .with_primary_label(format!("{} access on an un-narrowed union", what))
+            .with_help("narrow with `is` or `match` first".to_string()),
+        );
+    }
+
+    /// `target` must be one of the members of the union `ty`
+    pub(super) fn check_union_member(&mut self, ty: &Type, target: &Type, what: &str) {
+        let message = match strip_references(ty) {
+            Type::Union(members) => {
+                if *target == Type::Unknown || members.contains(target) {
+                    return;
+                }
+                format!(
+                    "`{}` is not a member of `{}`",
+                    display_type(target),
+                    display_type(ty)
+                )
+            }
+            Type::Unknown => return,
+            other => format!(
+                "{} needs a union value, found `{}`",
+                what,
+                display_type(other)
+            ),
+        };
+        self.diagnostics.push(
+            Diagnostic::error(error_codes::TYPE_MISMATCH, message, self.span.clone())
+                .with_primary_label("not a union member".to_string()),
+        );
+    }
+
+    /// `yield` needs an enclosing function returning `Generator<T>`, and the value must fit `T`
+    fn check_yield(&mut self, found: &Type) {
+        match self
+            .return_type
+            .as_ref()
+            .and_then(Type::generator_item)
+            .cloned()
+        {
+            Some(item) => {
+                let context = format!("`{}` yields `{}`", self.function, display_type(&item));
+                self.check_assignable(&item, found, &context);
+            }
+            None => self.diagnostics.push(
+                Diagnostic::error(
+                    error_codes::TYPE_MISMATCH,
+                    "`yield` outside a generator function".to_string(),
+                    self.span.clone(),
+                )
+                .with_primary_label("not inside a generator".to_string())
+                .with_help("declare the return type as `Generator<T>`".to_string()),
+            ),
+        }
+    }
+
+    fn no_such_field(&mut self, s: &Struct, field: &str) {
+        let available: Vec<&str> = s.fields.iter().map(|f| f.name.as_str()).collect();
+        let mut diagnostic = Diagnostic::error(
+            error_codes::NO_SUCH_FIELD,
+            format!("no field `{}` on type `{}`", field, s.name),
+            self.span.clone(),
+        )
+        .with_primary_label("unknown field".to_string());
+        if !available.is_empty() {
+            diagnostic =
+                diagnostic.with_help(format!("available fields: `{}`", available.join("`, `")));
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Field type with the struct's type arguments substituted from `owner`
+    pub(super) fn struct_field_type(&mut self, s: &Struct, field: &Field, owner: &Type) -> Type {
+        let params: Vec<String> = s
+            .type_params
+            .iter()
+            .map(|p| p.name.clone())
+            .chain(s.const_params.iter().map(|(name, _)| name.clone()))
+            .collect();
+        match owner {
+            Type::Generic { type_args, .. } if type_args.len() == params.len() => {
+                let ty = substitute(&field.ty, &params, type_args);
+                self.resolve_in(&ty, Some(owner.clone()), Vec::new())
+            }
+            _ => self.resolve_in(&field.ty, Some(owner.clone()), params),
+        }
+    }
+
+    fn infer_struct_literal(
+        &mut self,
+        name: &str,
+        type_args: &[Type],
+        fields: &[(String, Expression)],
+    ) -> Type {
+        let field_types: Vec<Type> = fields
+            .iter()
+            .map(|(_, value)| self.infer_expr(value))
+            .collect();
+
+        let Some(s) = self.env.user_struct(name) else {
+            // `type Stamped = Named & Timestamped;` builds records with `Stamped { .. }`
+            let record = self.resolve_type(&Type::Named(name.to_string()));
+            if let (Some(declared), true) = (self.record_fields(&record), type_args.is_empty()) {
+                for ((field_name, _), found) in fields.iter().zip(&field_types) {
+                    match declared
+                        .iter()
+                        .find(|(declared_name, _)| declared_name == field_name)
+                    {
+                        Some((_, expected)) => self.check_assignable(
+                            expected,
+                            found,
+                            &format!("field `{}` of `{}` has this type", field_name, name),
+                        ),
+                        None => self.diagnostics.push(
+                            Diagnostic::error(
+                                error_codes::NO_SUCH_FIELD,
+                                format!("no field `{}` on type `{}`", field_name, name),
+                                self.span.clone(),
+                            )
+                            .with_primary_label("unknown field".to_string()),
+                        ),
+                    }
+                }
+                return record;
+            }
+            return Type::Unknown;
+        };
+        self.check_deprecated(&s.attributes, "struct", name);
+        let owner = if type_args.is_empty() {
+            self.owner_type(name)
+        } else {
+            Type::Generic {
+                name: name.to_string(),
+                type_args: type_args.iter().map(|t| self.resolve_type(t)).collect(),
+            }
+        };
+
+        for ((field_name, _), found) in fields.iter().zip(&field_types) {
+            match s.fields.iter().find(|f| &f.name == field_name) {
+                Some(field) => {
+                    let deprecated_name = format!("{}.{}", name, field_name);
+                    self.check_deprecated(&field.attributes, "field", &deprecated_name);
+                    let expected = self.struct_field_type(s, field, &owner);
+                    self.check_assignable(
+                        &expected,
+                        found,
+                        &format!("field `{}` of `{}` has this type", field_name, name),
+                    );
+                }
+                None => self.no_such_field(s, field_name),
+            }
+        }
+        owner
+    }
+
+    fn infer_enum_literal(
+        &mut self,
+        enum_name: &str,
+        variant: &str,
+        data: &[Expression],
+        field_names: &[String],
+    ) -> Type {
+        let data_types: Vec<Type> = data.iter().map(|value| self.infer_expr(value)).collect();
+        let first = || Box::new(data_types.first().cloned().unwrap_or(Type::Unknown));
+
+        match (enum_name, variant) {
+            ("Option", "Some") => return Type::Option(first()),
+            ("Option", "None") => return Type::Option(Box::new(Type::Unknown)),
+            ("Result", "Ok") => return Type::Result(first(), Box::new(Type::Unknown)),
+            ("Result", "Err") => return Type::Result(Box::new(Type::Unknown), first()),
+            _ => {}
+        }
+
+        let Some(e) = self.env.user_enum(enum_name) else {
+            return Type::Unknown;
+        };
+        self.check_deprecated(&e.attributes, "enum", enum_name);
+        let owner = self.owner_type(enum_name);
+
+        // Payload shape errors (missing or positional fields) are reported by codegen
+        let variant_def = e.variants.iter().find(|v| v.name == variant);
+        if let (Some(v), true) = (variant_def, e.type_params.is_empty()) {
+            if v.is_struct_like() != field_names.is_empty() {
+                for (index, found) in data_types.iter().enumerate() {
+                    let position = match field_names.get(index) {
+                        Some(field) => v.field_index(field),
+                        None => Some(index),
+                    };
+                    if let Some(declared) = position.and_then(|p| v.data.get(p)) {
+                        let expected = self.resolve_in(declared, Some(owner.clone()), Vec::new());
+                        self.check_assignable(
+                            &expected,
+                            found,
+                            &format!("payload of `{}.{}` has this type", enum_name, variant),
+                        );
+                    }
+                }
+            }
+        }
+        owner
+    }
+
+    fn infer_match(&mut self, value: &Expression, arms: &[MatchArm]) -> Type {
+        let scrutinee = self.infer_expr(value);
+        let mut result = Type::Unknown;
+
+        for arm in arms {
+            self.scopes.push(HashMap::new());
+            self.bind_pattern(&arm.pattern, &scrutinee);
+            if let Some(guard) = &arm.guard {
+                self.infer_expr(guard);
+            }
+            let arm_ty = self.infer_expr(&arm.body);
+            self.scopes.pop();
+
+            if result == Type::Unknown
+                && !matches!(arm_ty, Type::Unknown | Type::Never | Type::Unit)
+            {
+                result = arm_ty;
+            }
+        }
+        result
+    }
+
+    /// Bind the variables a pattern introduces, typed from the matched value where known
+    pub(super) fn bind_pattern(&mut self, pattern: &Pattern, ty: &Type) {
+        let ty = strip_references(ty).clone();
+        match pattern {
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+            Pattern::Ident(name) => self.bind(name, ty),
+            Pattern::Tuple(items) => {
+                let types = match &ty {
+                    Type::Tuple(types) if types.len() == items.len() => types.clone(),
+                    _ => vec![Type::Unknown; items.len()],
+                };
+                for (item, item_ty) in items.iter().zip(&types) {
+                    self.bind_pattern(item, item_ty);
+                }
+            }
+            Pattern::Struct { name, fields } => {
+                let s = self.env.user_struct(name);
+                let owner = match &ty {
+                    Type::Generic { name: ty_name, .. } if ty_name == name => ty.clone(),
+                    _ => self.owner_type(name),
+                };
+                for (field_name, field_pattern) in fields {
+                    let field_ty = match s.and_then(|s| {
+                        s.fields
+                            .iter()
+                            .find(|f| &f.name == field_name)
+                            .map(|f| (s, f))
+                    }) {
+                        Some((s, field)) => self.struct_field_type(s, field, &owner),
+                        None => Type::Unknown,
+                    };
+                    self.bind_pattern(field_pattern, &field_ty);
+                }
+            }
+            Pattern::Enum {
+                name,
+                variant,
+                data,
+                field_names,
+            } => {
+                let payload = self.variant_payload(&ty, name, variant, data.len(), field_names);
+                for (item, item_ty) in data.iter().zip(&payload) {
+                    self.bind_pattern(item, item_ty);
+                }
+            }
+            Pattern::Array { elements, rest } => {
+                let elem = match &ty {
+                    Type::Array(elem, _) | Type::Slice(elem, _) | Type::Vec(elem) => {
+                        (**elem).clone()
+                    }
+                    _ => Type::Unknown,
+                };
+                for element in elements {
+                    self.bind_pattern(element, &elem);
+                }
+                if let Some(rest) = rest {
+                    self.bind(rest, Type::Slice(Box::new(elem), false));
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    self.bind_pattern(alternative, &ty);
+                }
+            }
+            Pattern::Type {
+                binding,
+                ty: member,
+            } => {
+                let member = self.resolve_annotation(member);
+                self.check_union_member(&ty, &member, "a type pattern");
+                if let Some(name) = binding {
+                    self.bind(name, member);
+                }
+            }
+        }
+    }
+
+    /// Payload types of an enum pattern, in pattern order
+    fn variant_payload(
+        &mut self,
+        scrutinee: &Type,
+        name: &str,
+        variant: &str,
+        count: usize,
+        field_names: &[String],
+    ) -> Vec<Type> {
+        let unknown = vec![Type::Unknown; count];
+        match (scrutinee, variant) {
+            (Type::Option(inner), "Some") => return vec![(**inner).clone()],
+            (Type::Result(ok, _), "Ok") => return vec![(**ok).clone()],
+            (Type::Result(_, err), "Err") => return vec![(**err).clone()],
+            _ => {}
+        }
+
+        // Bare `Circle(r)` patterns leave the enum name to the scrutinee
+        let enum_name = match (name, scrutinee) {
+            ("", Type::Named(n)) | ("", Type::Generic { name: n, .. }) => n.as_str(),
+            _ => name,
+        };
+        let Some(e) = self.env.user_enum(enum_name) else {
+            return unknown;
+        };
+        let Some(v) = e.variants.iter().find(|v| v.name == variant) else {
+            return unknown;
+        };
+
+        let params: Vec<String> = e.type_params.iter().map(|p| p.name.clone()).collect();
+        let owner = match scrutinee {
+            Type::Generic { name: n, .. } if n == enum_name => scrutinee.clone(),
+            _ => self.owner_type(enum_name),
+        };
+        (0..count)
+            .map(|index| {
+                let position = match field_names.get(index) {
+                    Some(field) => v.field_index(field),
+                    None => Some(index),
+                };
+                match position.and_then(|p| v.data.get(p)) {
+                    Some(declared) => match &owner {
+                        Type::Generic { type_args, .. } if type_args.len() == params.len() => {
+                            let ty = substitute(declared, &params, type_args);
+                            self.resolve_in(&ty, Some(owner.clone()), Vec::new())
+                        }
+                        _ => self.resolve_in(declared, Some(owner.clone()), params.clone()),
+                    },
+                    None => Type::Unknown,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Numeric type named by a literal suffix: 42i64, 7u8
+/// `fn (self: &T!) m()` and inline `fn m()!` change the value they are called on
+fn mutates_receiver(func: &Function) -> bool {
+    func.is_mutable
+        || func
+            .receiver
+            .as_ref()
+            .is_some_and(|r| r.is_mutable || matches!(r.ty, Type::Reference(_, true)))
+}
+
+fn suffix_type(suffix: &str) -> Type {
+    match suffix {
+        "i8" => Type::I8,
+        "i16" => Type::I16,
+        "i32" => Type::I32,
+        "i64" => Type::I64,
+        "i128" => Type::I128,
+        "u8" => Type::U8,
+        "u16" => Type::U16,
+        "u32" => Type::U32,
+        "u64" => Type::U64,
+        "u128" => Type::U128,
+        "f16" => Type::F16,
+        "f32" => Type::F32,
+        "f64" => Type::F64,
+        _ => Type::Unknown,
+    }
+}
+
+/// Type of an unannotated constant, when its value is a plain literal
+fn literal_type(value: &Expression) -> Type {
+    match value {
+        Expression::IntLiteral(_) => Type::I32,
+        Expression::TypedIntLiteral { type_suffix, .. } => suffix_type(type_suffix),
+        Expression::FloatLiteral(_) => Type::F64,
+        Expression::StringLiteral(_) => Type::String,
+        Expression::BoolLiteral(_) => Type::Bool,
+        _ => Type::Unknown,
+    }
+}
+
+fn range_type(start: Option<Type>, end: Option<Type>, inclusive: bool) -> Type {
+    let elem = start
+        .into_iter()
+        .chain(end)
+        .find(|ty| *ty != Type::Unknown)
+        .unwrap_or(Type::Unknown);
+    let name = if inclusive { "RangeInclusive" } else { "Range" };
+    Type::Generic {
+        name: name.to_string(),
+        type_args: vec![elem],
+    }
+}
+
+/// Element type T of Range<T> / RangeInclusive<T>
+pub(super) fn range_element(ty: &Type) -> Option<Type> {
+    match strip_references(ty) {
+        Type::Generic { name, type_args }
+            if (name == "Range" || name == "RangeInclusive") && type_args.len() == 1 =>
+        {
+            Some(type_args[0].clone())
+        }
+        _ => None,
+    }
+}
+
+fn deref_type(ty: &Type) -> Type {
+    match ty {
+        Type::Reference(inner, _) | Type::Box(inner) => (**inner).clone(),
+        Type::RawPtr { inner, .. } => (**inner).clone(),
+        _ => Type::Unknown,
+    }
+}
+
+/// Field and method access look through references and boxes
+fn auto_deref(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(inner, _) | Type::Box(inner) => auto_deref(inner),
+        _ => ty,
+    }
+}
+
+fn common_type(types: Vec<Type>) -> Type {
+    match types.split_first() {
+        Some((first, rest)) if rest.iter().all(|ty| ty == first) => first.clone(),
+        _ => Type::Unknown,
+    }
+}
+
+pub(super) fn is_float(ty: &Type) -> bool {
+    matches!(ty, Type::F16 | Type::F32 | Type::F64)
+}
+
+pub(super) fn is_numeric(ty: &Type) -> bool {
+    is_float(ty)
+        || matches!(
+            ty,
+            Type::I8
+                | Type::I16
+                | Type::I32
+                | Type::I64
+                | Type::I128
+                | Type::U8
+                | Type::U16
+                | Type::U32
+                | Type::U64
+                | Type::U128
+                | Type::Byte
+        )
+}
+
+/// Replace generic parameters with concrete arguments: Pair<T>.first with T = i32
+fn substitute(ty: &Type, params: &[String], args: &[Type]) -> Type {
+    let sub = |t: &Type| Box::new(substitute(t, params, args));
+    let sub_all = |ts: &[Type]| ts.iter().map(|t| substitute(t, params, args)).collect();
+
+    match ty {
+        Type::Named(name) => match params.iter().position(|p| p == name) {
+            Some(index) => args[index].clone(),
+            None => ty.clone(),
+        },
+        Type::Generic { name, type_args } => Type::Generic {
+            name: name.clone(),
+            type_args: sub_all(type_args),
+        },
+        Type::Array(elem, size) => Type::Array(sub(elem), *size),
+        Type::ConstArray { elem_type, size } => {
+            let bindings: HashMap<String, Type> =
+                params.iter().cloned().zip(args.iter().cloned()).collect();
+            match const_generics::eval_const_expr(size, &bindings)
+                .and_then(|n| usize::try_from(n).ok())
+            {
+                Some(n) => Type::Array(sub(elem_type), n),
+                None => Type::ConstArray {
+                    elem_type: sub(elem_type),
+                    size: size.clone(),
+                },
+            }
+        }
+        Type::Slice(elem, is_mut) => Type::Slice(sub(elem), *is_mut),
+        Type::Reference(inner, is_mut) => Type::Reference(sub(inner), *is_mut),
+        Type::Union(members) => Type::Union(sub_all(members)),
+        Type::Tuple(members) => Type::Tuple(sub_all(members)),
+        Type::Function {
+            params: fn_params,
+            return_type,
+        } => Type::Function {
+            params: sub_all(fn_params),
+            return_type: sub(return_type),
+        },
+        Type::RawPtr { inner, is_const } => Type::RawPtr {
+            inner: sub(inner),
+            is_const: *is_const,
+        },
+        Type::Option(inner) => Type::Option(sub(inner)),
+        Type::Result(ok, err) => Type::Result(sub(ok), sub(err)),
+        Type::Vec(elem) => Type::Vec(sub(elem)),
+        Type::Box(inner) => Type::Box(sub(inner)),
+        Type::Channel(elem) => Type::Channel(sub(elem)),
+        Type::Future(inner) => Type::Future(sub(inner)),
+        _ => ty.clone(),
+    }
+}
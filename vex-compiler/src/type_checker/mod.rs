@@ -0,0 +1,1204 @@
+// Semantic type checking pass
+// Runs after import merging and before the borrow checker: resolves names, infers the
+// type of every expression and reports E0308/E0599/E0609 before codegen starts.
+//
+// The checker is deliberately conservative. Anything it cannot resolve (imported
+// symbols the LSP never sees, generic parameters, builtins handled by codegen) becomes
+// Type::Unknown, which is compatible with everything and never produces a diagnostic.
+
+mod attributes;
+mod compatibility;
+mod environment;
+mod expressions;
+mod statements;
+mod table;
+
+pub use compatibility::display_type;
+pub use table::{FunctionSignature, TypeTable};
+
+use crate::type_system::const_eval::{ConstEvalError, ConstEvaluator, ConstValue};
+use crate::type_system::intersection::{self, IntersectionShape};
+use crate::type_system::{conditional, const_generics, object_safety};
+use attributes::AttributeTarget;
+use environment::ItemEnv;
+use std::collections::HashMap;
+use vex_ast::*;
+use vex_diagnostics::{error_codes, Diagnostic, DiagnosticEngine, Span, SpanMap};
+
+/// Standalone type checker: produces diagnostics and a [`TypeTable`] for later phases
+pub struct TypeChecker {
+    span_map: SpanMap,
+    diagnostics: DiagnosticEngine,
+    table: TypeTable,
+}
+
+impl TypeChecker {
+    pub fn new(span_map: &SpanMap) -> Self {
+        Self {
+            span_map: span_map.clone(),
+            diagnostics: DiagnosticEngine::new(),
+            table: TypeTable::new(),
+        }
+    }
+
+    /// Check every function, method and constant in the program
+    pub fn check_program(&mut self, program: &Program) -> Result<(), Vec<Diagnostic>> {
+        let mut checker = Checker::new(program, &self.span_map);
+        checker.check_items(program);
+        let (diagnostics, table) = (checker.diagnostics, checker.table);
+
+        self.table = table;
+        for diagnostic in &diagnostics {
+            self.diagnostics.emit(diagnostic.clone());
+        }
+
+        if self.diagnostics.has_errors() {
+            Err(diagnostics)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Diagnostics reported by the last check
+    pub fn diagnostics(&self) -> &DiagnosticEngine {
+        &self.diagnostics
+    }
+
+    /// Types resolved by the last check
+    pub fn type_table(&self) -> &TypeTable {
+        &self.table
+    }
+
+    /// Hand the resolved types to the next phase
+    pub fn into_type_table(self) -> TypeTable {
+        self.table
+    }
+}
+
+/// Per-run checking state, borrowing the program being checked
+pub(crate) struct Checker<'a> {
+    env: ItemEnv<'a>,
+    span_map: &'a SpanMap,
+    scopes: Vec<HashMap<String, Type>>,
+    /// Generic parameters in scope; they resolve to Unknown
+    type_params: Vec<String>,
+    self_type: Option<Type>,
+    /// Declared return type of the current function, None when unannotated
+    return_type: Option<Type>,
+    /// Table key of the current function: `main`, `Point.area`
+    function: String,
+    /// Nearest source location with a span id
+    span: Span,
+    diagnostics: Vec<Diagnostic>,
+    table: TypeTable,
+    /// Integer constants as `Type::ConstValue`, so they can size arrays
+    const_sizes: HashMap<String, Type>,
+    /// Constants whose initializer failed to evaluate at compile time
+    const_errors: HashMap<String, ConstEvalError>,
+    /// Inside a #[deprecated] item, where uses of other deprecated items are not reported
+    in_deprecated: bool,
+}
+
+impl<'a> Checker<'a> {
+    fn new(program: &'a Program, span_map: &'a SpanMap) -> Self {
+        let mut const_sizes = HashMap::new();
+        let mut const_errors = HashMap::new();
+        for (decl, result) in ConstEvaluator::new(program).eval_all() {
+            match result {
+                Ok(ConstValue::Int(value, _)) => {
+                    if let Ok(value) = i64::try_from(value) {
+                        const_sizes.insert(decl.name.clone(), Type::ConstValue(value));
+                    }
+                }
+                // Left to codegen, which compiles the initializer at runtime
+                Ok(_) | Err(ConstEvalError::Unsupported) => {}
+                Err(e) => {
+                    const_errors.insert(decl.name.clone(), e);
+                }
+            }
+        }
+
+        Self {
+            env: ItemEnv::collect(program),
+            span_map,
+            scopes: Vec::new(),
+            type_params: Vec::new(),
+            self_type: None,
+            return_type: None,
+            function: String::new(),
+            span: Span::unknown(),
+            diagnostics: Vec::new(),
+            table: TypeTable::new(),
+            const_sizes,
+            const_errors,
+            in_deprecated: false,
+        }
+    }
+
+    fn check_items(&mut self, program: &'a Program) {
+        for item in &program.items {
+            match item {
+                Item::Function(func) => {
+                    let owner = if func.is_static {
+                        func.static_type.clone()
+                    } else {
+                        func.receiver
+                            .as_ref()
+                            .and_then(|r| ItemEnv::nominal_name(&r.ty))
+                    };
+                    self.check_function(func, owner.as_deref(), &[]);
+                }
+                Item::Struct(s) => {
+                    self.span = Span::unknown();
+                    self.enter_span(&s.span_id);
+                    self.check_attributes(&s.attributes, AttributeTarget::Struct);
+                    for field in &s.fields {
+                        self.check_attributes(&field.attributes, AttributeTarget::Field);
+                        self.check_array_lengths(&field.ty);
+                    }
+                    for method in &s.methods {
+                        self.check_function(method, Some(&s.name), &s.type_params);
+                    }
+                }
+                Item::TraitImpl(impl_) => {
+                    let owner = ItemEnv::nominal_name(&impl_.for_type);
+                    for method in &impl_.methods {
+                        self.check_function(method, owner.as_deref(), &impl_.type_params);
+                    }
+                }
+                Item::Contract(trait_def) => {
+                    for method in &trait_def.methods {
+                        if let Some(body) = &method.body {
+                            self.check_default_method(trait_def, method, body);
+                        }
+                    }
+                }
+                Item::Const(c) => {
+                    self.function.clear();
+                    self.in_deprecated = false;
+                    self.enter_span(&c.span_id);
+                    let found = self.infer_expr(&c.value);
+                    if let Some(ty) = &c.ty {
+                        let expected = self.resolve_annotation(ty);
+                        self.check_assignable(
+                            &expected,
+                            &found,
+                            &format!("constant `{}` is declared here", c.name),
+                        );
+                    }
+                    self.report_const_error(c);
+                }
+                Item::Enum(e) => {
+                    self.span = Span::unknown();
+                    self.enter_span(&e.span_id);
+                    self.check_attributes(&e.attributes, AttributeTarget::Enum);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_function(&mut self, func: &'a Function, owner: Option<&str>, outer: &[TypeParam]) {
+        self.type_params = outer
+            .iter()
+            .chain(func.type_params.iter())
+            .map(|p| p.name.clone())
+            .chain(func.const_params.iter().map(|(name, _)| name.clone()))
+            .collect();
+        self.self_type = owner.map(|name| self.owner_type(name));
+        self.function = match owner {
+            Some(owner) => format!("{}.{}", owner, func.name),
+            None => func.name.clone(),
+        };
+        self.span = Span::unknown();
+        self.enter_span(&func.span_id);
+        self.check_attributes(&func.attributes, AttributeTarget::Function);
+        self.in_deprecated = find_attribute(&func.attributes, "deprecated").is_some()
+            || owner
+                .and_then(|name| self.env.user_struct(name))
+                .is_some_and(|s| find_attribute(&s.attributes, "deprecated").is_some());
+
+        self.scopes.push(HashMap::new());
+        if let Some(receiver) = &func.receiver {
+            let ty = self.resolve_type(&receiver.ty);
+            self.bind(&receiver.name, ty);
+        } else if let (Some(self_ty), false) = (self.self_type.clone(), func.is_static) {
+            // Inline methods take an implicit `self`
+            self.bind("self", Type::Reference(Box::new(self_ty), func.is_mutable));
+        }
+
+        let mut params = Vec::new();
+        for param in &func.params {
+            let ty = self.resolve_annotation(&param.ty);
+            self.bind(&param.name, ty.clone());
+            params.push((param.name.clone(), ty));
+        }
+
+        self.return_type = func
+            .return_type
+            .as_ref()
+            .map(|ty| self.resolve_annotation(ty));
+        let signature = FunctionSignature {
+            params,
+            return_type: self.return_type.clone().unwrap_or(Type::Unit),
+        };
+        let key = self.function.clone();
+        self.table.record_signature(&key, signature);
+        self.record_scope(&func.body);
+
+        self.check_statements(&func.body.statements);
+        self.scopes.pop();
+    }
+
+    fn check_default_method(
+        &mut self,
+        trait_def: &'a Trait,
+        method: &'a TraitMethod,
+        body: &'a Block,
+    ) {
+        // Self is whatever implements the contract, so it stays unresolved
+        self.type_params = trait_def
+            .type_params
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+        self.self_type = None;
+        self.function = format!("{}.{}", trait_def.name, method.name);
+        self.in_deprecated = false;
+        self.span = Span::unknown();
+        self.enter_span(&method.span_id);
+
+        self.scopes.push(HashMap::new());
+        match &method.receiver {
+            Some(receiver) => {
+                let ty = self.resolve_type(&receiver.ty);
+                self.bind(&receiver.name, ty);
+            }
+            None => self.bind("self", Type::Unknown),
+        }
+        for param in &method.params {
+            let ty = self.resolve_annotation(&param.ty);
+            self.bind(&param.name, ty);
+        }
+        self.return_type = method
+            .return_type
+            .as_ref()
+            .map(|ty| self.resolve_annotation(ty));
+        self.record_scope(body);
+        self.check_statements(&body.statements);
+        self.scopes.pop();
+    }
+
+    /// Remember where the current function's body is, for position lookups
+    fn record_scope(&mut self, body: &Block) {
+        if let Some(span) = body.span_id.as_ref().and_then(|id| self.span_map.get(id)) {
+            let key = self.function.clone();
+            self.table.record_scope(&key, span.clone());
+        }
+    }
+
+    /// Type of `Self` inside a method of `name`
+    fn owner_type(&self, name: &str) -> Type {
+        let param_count = self
+            .env
+            .structs
+            .get(name)
+            .map(|s| s.type_params.len() + s.const_params.len())
+            .or_else(|| self.env.enums.get(name).map(|e| e.type_params.len()))
+            .unwrap_or(0);
+        if param_count == 0 {
+            Type::Named(name.to_string())
+        } else {
+            Type::Generic {
+                name: name.to_string(),
+                type_args: vec![Type::Unknown; param_count],
+            }
+        }
+    }
+
+    /// Move the reported location to the node with this span id, if it has one
+    fn enter_span(&mut self, span_id: &Option<String>) -> Span {
+        let previous = self.span.clone();
+        if let Some(span) = span_id.as_ref().and_then(|id| self.span_map.get(id)) {
+            self.span = span.clone();
+        }
+        previous
+    }
+
+    fn bind(&mut self, name: &str, ty: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), ty.clone());
+        }
+        if !self.function.is_empty() {
+            self.table.record_local(&self.function, name, ty);
+        }
+    }
+
+    fn lookup_local(&self, name: &str) -> Option<&Type> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Array length written over integer constants: `3`, `SIZE`, `ROWS * COLS`
+    fn const_len(&self, size: &Expression) -> Option<usize> {
+        let value = if self
+            .const_sizes
+            .keys()
+            .any(|name| self.lookup_local(name).is_some())
+        {
+            // Locals shadow constants of the same name
+            let visible: HashMap<String, Type> = self
+                .const_sizes
+                .iter()
+                .filter(|(name, _)| self.lookup_local(name).is_none())
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            const_generics::eval_const_expr(size, &visible)
+        } else {
+            const_generics::eval_const_expr(size, &self.const_sizes)
+        };
+        value.and_then(|n| usize::try_from(n).ok())
+    }
+
+    /// Resolve annotations: aliases, Self, builtin generics; generic parameters become Unknown
+    fn resolve_type(&self, ty: &Type) -> Type {
+        self.resolve_type_depth(ty, 0)
+    }
+
+    fn resolve_type_depth(&self, ty: &Type, depth: usize) -> Type {
+        // Guards against alias cycles: type A = B; type B = A;
+        if depth > 32 {
+            return Type::Unknown;
+        }
+        let resolve = |t: &Type| Box::new(self.resolve_type_depth(t, depth + 1));
+
+        match ty {
+            Type::Named(name) if name == "Self" => self.self_type.clone().unwrap_or(Type::Unknown),
+            Type::Named(name) if self.type_params.contains(name) => Type::Unknown,
+            Type::Named(name) => match self.env.aliases.get(name) {
+                Some(aliased) => self.resolve_type_depth(aliased, depth + 1),
+                None => ty.clone(),
+            },
+            Type::SelfType => self.self_type.clone().unwrap_or(Type::Unknown),
+            Type::Generic { name, type_args } => {
+                let args: Vec<Type> = type_args
+                    .iter()
+                    .map(|t| self.resolve_type_depth(t, depth + 1))
+                    .collect();
+                if let Some(alias) = self.env.generic_aliases.get(name) {
+                    let subst: HashMap<String, Type> = alias
+                        .type_params
+                        .iter()
+                        .map(|p| p.name.clone())
+                        .zip(args.into_iter().chain(std::iter::repeat(Type::Unknown)))
+                        .collect();
+                    let expanded = conditional::substitute_params(&alias.ty, &subst);
+                    return self.resolve_type_depth(&expanded, depth + 1);
+                }
+                match (name.as_str(), args.as_slice()) {
+                    ("Option", [inner]) => Type::Option(Box::new(inner.clone())),
+                    ("Result", [ok, err]) => {
+                        Type::Result(Box::new(ok.clone()), Box::new(err.clone()))
+                    }
+                    ("Vec", [elem]) => Type::Vec(Box::new(elem.clone())),
+                    ("Box", [inner]) => Type::Box(Box::new(inner.clone())),
+                    ("Channel", [elem]) => Type::Channel(Box::new(elem.clone())),
+                    ("Future", [inner]) => Type::Future(Box::new(inner.clone())),
+                    _ => Type::Generic {
+                        name: name.clone(),
+                        type_args: args,
+                    },
+                }
+            }
+            Type::Array(elem, size) => Type::Array(resolve(elem), *size),
+            Type::ConstArray { elem_type, size } => match self.const_len(size) {
+                Some(n) => Type::Array(resolve(elem_type), n),
+                None => Type::Slice(resolve(elem_type), false),
+            },
+            Type::Slice(elem, is_mut) => Type::Slice(resolve(elem), *is_mut),
+            Type::Reference(inner, is_mut) => Type::Reference(resolve(inner), *is_mut),
+            Type::Union(members) => Type::Union(
+                members
+                    .iter()
+                    .map(|t| self.resolve_type_depth(t, depth + 1))
+                    .collect(),
+            ),
+            Type::Tuple(members) => Type::Tuple(
+                members
+                    .iter()
+                    .map(|t| self.resolve_type_depth(t, depth + 1))
+                    .collect(),
+            ),
+            Type::Function {
+                params,
+                return_type,
+            } => Type::Function {
+                params: params
+                    .iter()
+                    .map(|t| self.resolve_type_depth(t, depth + 1))
+                    .collect(),
+                return_type: resolve(return_type),
+            },
+            Type::RawPtr { inner, is_const } => Type::RawPtr {
+                inner: resolve(inner),
+                is_const: *is_const,
+            },
+            Type::Option(inner) => Type::Option(resolve(inner)),
+            Type::Result(ok, err) => Type::Result(resolve(ok), resolve(err)),
+            Type::Vec(elem) => Type::Vec(resolve(elem)),
+            Type::Box(inner) => Type::Box(resolve(inner)),
+            Type::Channel(elem) => Type::Channel(resolve(elem)),
+            Type::Future(inner) => Type::Future(resolve(inner)),
+            // Reduced here when possible; resolve_annotation reports the ones that never reduce
+            Type::Conditional { .. } => {
+                let resolved = conditional::map_type(ty, &mut |t| match t {
+                    Type::Conditional { .. } | Type::Infer(_) => None,
+                    _ => Some(self.resolve_type_depth(t, depth + 1)),
+                });
+                conditional::reduce_conditionals(&resolved).unwrap_or(resolved)
+            }
+            Type::Infer(_) => ty.clone(),
+            // `i32 & i32` is just `i32`; records and contract sets stay intersections
+            Type::Intersection(members) => {
+                let members: Vec<Type> = members
+                    .iter()
+                    .map(|t| self.resolve_type_depth(t, depth + 1))
+                    .collect();
+                if members.contains(&Type::Unknown) {
+                    return Type::Unknown;
+                }
+                match self.intersection_shape(&members) {
+                    Ok(IntersectionShape::Value(value)) => value,
+                    _ => Type::Intersection(intersection::flatten_members(&members)),
+                }
+            }
+            // typeof(expr) is replaced by resolve_annotation before resolution
+            Type::Typeof(_) | Type::AssociatedType { .. } => Type::Unknown,
+            _ => ty.clone(),
+        }
+    }
+
+    /// Resolve a type written in the source: `typeof(expr)` takes the checked type of
+    /// `expr`, and conditional types that cannot be reduced are reported
+    fn resolve_annotation(&mut self, ty: &Type) -> Type {
+        let ty = conditional::map_type(ty, &mut |t| match t {
+            Type::Typeof(expr) => Some(self.infer_expr(expr)),
+            _ => None,
+        });
+        self.check_array_lengths(&ty);
+        let resolved = self.resolve_type(&ty);
+
+        let message = match conditional::reduce_conditionals(&resolved) {
+            Err(message) => message,
+            Ok(reduced) => match stray_infer(&reduced) {
+                Some(name) => format!(
+                    "`infer {}` can only appear in the `extends` clause of a conditional type",
+                    name
+                ),
+                None => {
+                    self.check_intersections(&reduced);
+                    self.check_dyn_contracts(&reduced);
+                    return reduced;
+                }
+            },
+        };
+        self.diagnostics.push(
+            Diagnostic::error(
+                error_codes::IRREDUCIBLE_TYPE,
+                format!("cannot reduce type `{}`: {}", display_type(&ty), message),
+                self.span.clone(),
+            )
+            .with_primary_label("type cannot be reduced".to_string()),
+        );
+        Type::Unknown
+    }
+
+    /// Report `[T; N]` lengths written over this file's constants that do not
+    /// evaluate to a size; other names (const parameters, imports) stay silent
+    fn check_array_lengths(&mut self, ty: &Type) {
+        let mut failed = Vec::new();
+        conditional::map_type(ty, &mut |t| {
+            if let Type::ConstArray { size, .. } = t {
+                if names_only_constants(size, &self.const_sizes) && self.const_len(size).is_none() {
+                    failed.push(const_generics::const_expr_to_string(size));
+                }
+            }
+            None
+        });
+
+        for len in failed {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    error_codes::CONST_EVAL,
+                    format!("array length `{}` does not evaluate to a size", len),
+                    self.span.clone(),
+                )
+                .with_primary_label("length must be a non-negative integer".to_string()),
+            );
+        }
+    }
+
+    /// Report intersections in a written type that have no values, and contract-only
+    /// ones, which constrain generic parameters but are not types of their own
+    fn check_intersections(&mut self, ty: &Type) {
+        let mut found = Vec::new();
+        conditional::map_type(ty, &mut |t| {
+            if let Type::Intersection(members) = t {
+                found.push(members.clone());
+            }
+            None
+        });
+
+        for members in found {
+            let written = display_type(&Type::Intersection(members.clone()));
+            let diagnostic = match self.intersection_shape(&members) {
+                Ok(IntersectionShape::Contracts(contracts)) => Diagnostic::error(
+                    error_codes::CONTRACT_AS_TYPE,
+                    format!("contract intersection `{}` used as a type", written),
+                    self.span.clone(),
+                )
+                .with_primary_label("contracts are not types".to_string())
+                .with_help(format!(
+                    "take a generic parameter instead: `<T: {}>`",
+                    contracts.join(" & ")
+                )),
+                Ok(_) => continue,
+                Err(message) => Diagnostic::error(
+                    error_codes::TYPE_MISMATCH,
+                    format!("invalid intersection `{}`: {}", written, message),
+                    self.span.clone(),
+                )
+                .with_primary_label("members cannot be combined".to_string()),
+            };
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Report `dyn C` in a written type when `C` is a struct or a contract that is not
+    /// object safe
+    fn check_dyn_contracts(&mut self, ty: &Type) {
+        let mut found = Vec::new();
+        conditional::map_type(ty, &mut |t| {
+            if let Type::Dyn(contract) = t {
+                found.push(contract.clone());
+            }
+            None
+        });
+
+        for contract in found {
+            let Some(trait_def) = self.env.traits.get(&contract).copied() else {
+                // Contracts the checker cannot see (imports) are left to codegen
+                if self.env.user_struct(&contract).is_some() {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            error_codes::TYPE_MISMATCH,
+                            format!("`dyn {}` names a struct, not a contract", contract),
+                            self.span.clone(),
+                        )
+                        .with_primary_label("expected a contract".to_string())
+                        .with_help(format!("use `{}` directly", contract)),
+                    );
+                }
+                continue;
+            };
+
+            let lookup = |name: &str| self.env.traits.get(name).copied();
+            // Imported supercontracts are not visible here either
+            if object_safety::vtable_methods(trait_def, &lookup).is_err() {
+                continue;
+            }
+            let violations = object_safety::object_safety_violations(trait_def, &lookup);
+            if violations.is_empty() {
+                continue;
+            }
+            let mut diagnostic = Diagnostic::error(
+                error_codes::OBJECT_UNSAFE,
+                format!("contract `{}` cannot be made into an object", contract),
+                self.span.clone(),
+            )
+            .with_primary_label(format!("`dyn {}` is not allowed", contract));
+            for violation in violations {
+                diagnostic = diagnostic.with_note(violation);
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Report a value of type `found` used where `expected` is required
+    fn check_assignable(&mut self, expected: &Type, found: &Type, context: &str) {
+        if self.is_compatible(expected, found) {
+            return;
+        }
+        let expected_str = display_type(expected);
+        let found_str = display_type(found);
+        self.diagnostics.push(
+            Diagnostic::error(
+                error_codes::TYPE_MISMATCH,
+                format!(
+                    "mismatched types: expected `{}`, found `{}`",
+                    expected_str, found_str
+                ),
+                self.span.clone(),
+            )
+            .with_primary_label(format!("expected `{}`", expected_str))
+            .with_note(context.to_string()),
+        );
+    }
+
+    /// Report a constant whose initializer failed to evaluate at compile time
+    fn report_const_error(&mut self, c: &Const) {
+        let diagnostic = match self.const_errors.get(&c.name) {
+            Some(ConstEvalError::Failed(message)) => Diagnostic::error(
+                error_codes::CONST_EVAL,
+                format!("evaluation of constant `{}` failed", c.name),
+                self.span.clone(),
+            )
+            .with_primary_label(message.clone()),
+            Some(ConstEvalError::NotConst(message)) => Diagnostic::error(
+                error_codes::NON_CONST_CALL,
+                message.clone(),
+                self.span.clone(),
+            )
+            .with_primary_label(format!("in the initializer of `{}`", c.name))
+            .with_note("only functions declared `const fn` can be called in constants".to_string()),
+            _ => return,
+        };
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+/// Whether a const size expression is built from literals and constants only
+fn names_only_constants(size: &Expression, constants: &HashMap<String, Type>) -> bool {
+    match size {
+        Expression::IntLiteral(_) => true,
+        Expression::Ident(name) => constants.contains_key(name),
+        Expression::Binary { left, right, .. } => {
+            names_only_constants(left, constants) && names_only_constants(right, constants)
+        }
+        _ => false,
+    }
+}
+
+/// Name of an `infer X` left outside any conditional type
+fn stray_infer(ty: &Type) -> Option<String> {
+    let mut name = None;
+    conditional::map_type(ty, &mut |t| match t {
+        Type::Infer(infer) => {
+            name.get_or_insert_with(|| infer.clone());
+            None
+        }
+        // infer inside an unreduced conditional is legitimate
+        Type::Conditional { .. } => Some(Type::Unknown),
+        _ => None,
+    });
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vex_diagnostics::ErrorLevel;
+    use vex_parser::Parser;
+
+    fn check(source: &str) -> (Result<(), Vec<Diagnostic>>, TypeTable) {
+        let mut parser = Parser::new(source).expect("lexer failed");
+        let program = parser.parse().expect("parse failed");
+        let span_map = parser.take_span_map();
+        let mut checker = TypeChecker::new(&span_map);
+        let result = checker.check_program(&program);
+        (result, checker.into_type_table())
+    }
+
+    fn error_codes_of(source: &str) -> Vec<String> {
+        match check(source).0 {
+            Ok(()) => Vec::new(),
+            Err(diagnostics) => diagnostics.into_iter().map(|d| d.code).collect(),
+        }
+    }
+
+    /// Sorted warning codes of a program that must type check
+    fn warning_codes_of(source: &str) -> Vec<String> {
+        let mut parser = Parser::new(source).expect("lexer failed");
+        let program = parser.parse().expect("parse failed");
+        let span_map = parser.take_span_map();
+        let mut checker = TypeChecker::new(&span_map);
+        assert!(checker.check_program(&program).is_ok());
+        let mut codes: Vec<String> = checker
+            .diagnostics()
+            .diagnostics()
+            .iter()
+            .filter(|d| d.level == ErrorLevel::Warning)
+            .map(|d| d.code.clone())
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    #[test]
+    fn test_well_typed_program_passes() {
+        let source = r#"
+            struct Point { x: i32, y: i32 }
+            fn (self: &Point) sum(): i32 { return self.x + self.y; }
+            fn build(x: i32): Point { return Point { x: x, y: 2 }; }
+            fn main(): i32 {
+                let p = build(1);
+                let total: i64 = p.sum();
+                let f: f64 = 2;
+                if p.x > 0 { return p.sum(); }
+                return 0;
+            }
+        "#;
+        assert!(error_codes_of(source).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_let_and_return() {
+        let source = r#"
+            fn name(): string { return 42; }
+            fn main(): i32 {
+                let flag: bool = "yes";
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_mismatched_argument_and_field() {
+        let source = r#"
+            struct User { name: string, age: i32 }
+            fn greet(user: User): i32 { return user.age; }
+            fn main(): i32 {
+                let u = User { name: 7, age: 30 };
+                return greet("bob");
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_unknown_field_and_method() {
+        let source = r#"
+            struct Point { x: i32, y: i32 }
+            fn (self: &Point) len(): i32 { return self.x; }
+            fn main(): i32 {
+                let p = Point { x: 1, y: 2 };
+                let a = p.z;
+                let b = p.area();
+                return p.len();
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0609", "E0599"]);
+    }
+
+    #[test]
+    fn test_conditional_and_typeof_annotations() {
+        let source = r#"
+            type Unwrap<T> = T extends Option<infer U> ? U : T;
+            type ExtractErr<T> = T extends Result<infer V, infer E> ? E : never;
+            fn main(): i32 {
+                let a: Unwrap<Option<i32>> = 5;
+                let b: Unwrap<string> = "text";
+                let c: ExtractErr<Result<i32, string>> = "boom";
+                let d: typeof(a) = 7;
+                let e: Unwrap<Option<bool>> = "wrong";
+                let f: typeof(b) = 3;
+                return a;
+            }
+        "#;
+        let (result, table) = check(source);
+        let codes: Vec<String> = result.unwrap_err().into_iter().map(|d| d.code).collect();
+        assert_eq!(codes, vec!["E0308", "E0308"]);
+        assert_eq!(table.local_type("main", "a"), Some(&Type::I32));
+        assert_eq!(table.local_type("main", "c"), Some(&Type::String));
+        assert_eq!(table.local_type("main", "d"), Some(&Type::I32));
+    }
+
+    #[test]
+    fn test_irreducible_conditional_is_reported() {
+        let source = r#"
+            type Leak<T> = T extends Option<infer U> ? i32 : U;
+            fn keep<T>(value: Leak<T>): i32 { return 0; }
+            fn main(): i32 {
+                let x: Leak<bool> = 1;
+                return 0;
+            }
+        "#;
+        // The generic use is deferred; the concrete one cannot be reduced
+        assert_eq!(error_codes_of(source), vec!["E0399"]);
+    }
+
+    #[test]
+    fn test_union_narrowing_with_is_and_match() {
+        let source = r#"
+            struct Point { x: f64, y: f64 }
+            fn (self: &Point) norm(): f64 { return self.x; }
+            fn size(v: Point | i32): f64 {
+                if v is Point {
+                    return v.norm();
+                } else {
+                    let n: i32 = v;
+                }
+                return 0.0;
+            }
+            fn label(v: i32 | string | bool): i32 {
+                let w: i32 | string | bool = "text";
+                return match v {
+                    n: i32 => n,
+                    s: string => 1,
+                    _: bool => 2,
+                };
+            }
+        "#;
+        let (result, table) = check(source);
+        assert!(result.is_ok(), "{:?}", result);
+        // Narrowing shadows the parameter inside the branch only
+        assert_eq!(table.local_type("label", "n"), Some(&Type::I32));
+        assert_eq!(
+            table.local_type("size", "v"),
+            Some(&Type::Union(vec![
+                Type::Named("Point".to_string()),
+                Type::I32
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_union_member_access_needs_narrowing() {
+        let source = r#"
+            struct Point { x: f64, y: f64 }
+            fn (self: &Point) norm(): f64 { return self.x; }
+            fn size(v: Point | i32): f64 {
+                let a = v.norm();
+                let b = v.x;
+                let n: i32 = v;
+                if v is string { return 1.0; }
+                return 0.0;
+            }
+        "#;
+        assert_eq!(
+            error_codes_of(source),
+            vec!["E0599", "E0609", "E0308", "E0308"]
+        );
+    }
+
+    #[test]
+    fn test_record_intersections_merge_fields() {
+        let source = r#"
+            struct Named { id: i64, name: string }
+            struct Timestamped { id: i64, created: i64 }
+            type Stamped = Named & Timestamped;
+            fn (self: &Named) label(): string { return self.name; }
+            fn age(v: Named & Timestamped, now: i64): i64 { return now - v.created; }
+            fn main(): i64 {
+                let s = Stamped { id: 1, name: "a", created: 10 };
+                let label: string = s.label();
+                let named: Named = s;
+                return age(s, 20) + s.id;
+            }
+        "#;
+        let (result, table) = check(source);
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(
+            table.local_type("main", "s"),
+            Some(&Type::Intersection(vec![
+                Type::Named("Named".to_string()),
+                Type::Named("Timestamped".to_string())
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_invalid_intersections_are_reported() {
+        let source = r#"
+            contract Reader { fn read(): i32; }
+            contract Writer { fn write(v: i32); }
+            struct Named { id: i64, name: string }
+            struct Tagged { id: string }
+            fn (self: &Named!) rename(name: string) { self.name = name; }
+            fn pipe(io: Reader & Writer): i32 { return 0; }
+            fn tag(v: Named & Tagged): i32 { return 0; }
+            fn odd(v: Named & bool): i32 { return 0; }
+            fn touch(v: Named & Named): i32 {
+                let b = v.missing;
+                return 0;
+            }
+            fn rename_all(v: Named & Reader) { v.rename("x"); }
+        "#;
+        assert_eq!(
+            error_codes_of(source),
+            vec!["E0782", "E0308", "E0308", "E0609", "E0502"]
+        );
+    }
+
+    #[test]
+    fn test_dyn_contract_objects() {
+        let source = r#"
+            contract Shape { fn area(): f64; }
+            contract Same { fn same(other: Self): bool; }
+            struct Circle impl Shape {
+                r: f64,
+                fn area(): f64 { return self.r * self.r; }
+            }
+            struct Plain { x: i32 }
+            fn total(shape: &dyn Shape): f64 {
+                let a: f64 = shape.area();
+                let name: string = shape.area();
+                let b = shape.perimeter();
+                return a;
+            }
+            fn compare(v: &dyn Same): bool { return true; }
+            fn main(): i32 {
+                let c = Circle { r: 1.0 };
+                let s: &dyn Shape = &c;
+                let p: &dyn Shape = &Plain { x: 1 };
+                let back: Circle = s;
+                return 0;
+            }
+        "#;
+        assert_eq!(
+            error_codes_of(source),
+            vec!["E0308", "E0599", "E0038", "E0308", "E0308"]
+        );
+    }
+
+    #[test]
+    fn test_generators_and_iterators() {
+        let source = r#"
+            struct Countdown {
+                remaining: i32,
+                fn next()!: Option<i32> {
+                    if self.remaining == 0 { return None; }
+                    self.remaining = self.remaining - 1;
+                    return Some(self.remaining);
+                }
+            }
+            fn evens(limit: i32): Generator<i32> {
+                let! n = 0;
+                while n < limit { yield n; n = n + 2; }
+            }
+            fn words(): Generator<string> { yield 1; }
+            fn plain(): i32 { yield 1; return 0; }
+            fn main(): i32 {
+                for n in evens(10) { let a: i32 = n; }
+                for n in Countdown { remaining: 3 } { let b: string = n; }
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_range_values() {
+        let source = r#"
+            fn sum(r: Range<i64>): i64 {
+                let! total: i64 = 0;
+                for i in r { total = total + i; }
+                return total;
+            }
+            fn main(): i32 {
+                let n: i64 = 10;
+                let r = 0..n;
+                let total: i64 = sum(r.rev().step_by(2));
+                let hit: bool = r.contains(3);
+                let size: i64 = r.len();
+                let closed = 1..=n;
+                let missed: bool = closed.contains("three");
+                let wrong: Range<i64> = closed;
+                for c in closed { let s: string = c; }
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_integer_overflow_methods() {
+        let source = r#"
+            fn main(): i32 {
+                let a: u8 = 250;
+                let wrapped: u8 = a.wrapping_add(10);
+                let clamped: u8 = a.saturating_mul(2);
+                let big: i128 = 170141183460469231731687303715884105727;
+                let next: Option<i128> = big.checked_add(1);
+                let label: Option<string> = big.checked_mul(2);
+                let count: i64 = 5;
+                let bad: i64 = count.saturating_sub("one");
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_slice_views() {
+        let source = r#"
+            fn fill(xs: &[f64]!, value: f64) {
+                for i in 0..xs.len() { xs[i] = value; }
+            }
+            fn total(xs: &[f64]): f64 {
+                let! sum: f64 = 0.0;
+                for x in xs { sum = sum + x; }
+                return sum;
+            }
+            fn main(): i32 {
+                let! data = [1.0, 2.0, 3.0, 4.0];
+                let view: &[f64]! = &data!;
+                fill(view[1..3], 0.5);
+                let head: &[f64] = data[..2];
+                let sum: f64 = total(head);
+                fill(head, 1.0);
+                fill(&data, 1.0);
+                let first: string = view[0];
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0308", "E0308", "E0308"]);
+    }
+
+    #[test]
+    fn test_unresolved_names_stay_silent() {
+        // Imported functions and types are not visible to a single-file check
+        let source = r#"
+            import { HashMap } from "collections";
+            fn main(): i32 {
+                let m = HashMap.new();
+                let v = m.get("key");
+                let n: i32 = external_call(v);
+                println(n.missing);
+                return n;
+            }
+        "#;
+        assert!(error_codes_of(source).is_empty());
+    }
+
+    #[test]
+    fn test_methods_from_undeclared_contracts_are_not_reported() {
+        // Greeter lives in another module, so its default methods are unknown here
+        let source = r#"
+            struct Bot impl Greeter {
+                fn name(): string { return "bot"; }
+            }
+            fn main(): i32 {
+                let b = Bot {};
+                let s: string = b.greet();
+                let n: string = b.name();
+                return 0;
+            }
+        "#;
+        assert!(error_codes_of(source).is_empty());
+    }
+
+    #[test]
+    fn test_table_records_locals_and_signatures() {
+        let source = r#"
+            struct Point { x: f64, y: f64 }
+            fn (self: &Point) norm(): f64 { return self.x; }
+            fn main(): i32 {
+                let p = Point { x: 1.0, y: 2.0 };
+                let n = p.norm();
+                let items = [1, 2, 3];
+                for item in items { let doubled = item * 2; }
+                return 0;
+            }
+        "#;
+        let (result, table) = check(source);
+        assert!(result.is_ok());
+        assert_eq!(
+            table.local_type("main", "p"),
+            Some(&Type::Named("Point".to_string()))
+        );
+        assert_eq!(table.local_type("main", "n"), Some(&Type::F64));
+        assert_eq!(table.local_type("main", "doubled"), Some(&Type::I32));
+        assert_eq!(
+            table.signature("Point.norm").map(|s| &s.return_type),
+            Some(&Type::F64)
+        );
+    }
+
+    #[test]
+    fn test_table_finds_function_at_position() {
+        let source = "struct Point { x: f64 }\nfn (self: &Point) norm(): f64 {\n    return self.x;\n}\nfn main(): i32 {\n    let p = Point { x: 1.0 };\n    return 0;\n}\n";
+        let (result, table) = check(source);
+        assert!(result.is_ok());
+        assert_eq!(table.function_at(source, 3, 12), Some("Point.norm"));
+        assert_eq!(table.function_at(source, 6, 9), Some("main"));
+        // Between functions and past the end there is no enclosing body
+        assert_eq!(table.function_at(source, 1, 5), None);
+        assert_eq!(table.function_at(source, 40, 1), None);
+    }
+
+    #[test]
+    fn test_constant_evaluation() {
+        let source = r#"
+            const fn cube(x: i64): i64 { return x * x * x; }
+            fn runtime(): i32 { return 1; }
+            const WIDTH: i32 = 4;
+            const CELLS: [u8; WIDTH * 2] = [0; WIDTH * 2];
+            const VOLUME: i64 = cube(3);
+            const TOO_BIG: u8 = 200 + 100;
+            const NO_RATIO: i32 = WIDTH / (WIDTH - 4);
+            const CALLED: i32 = runtime();
+            fn main(): i32 {
+                let grid: [u8; 8] = CELLS;
+                let row: [i32; WIDTH] = [1; WIDTH];
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0080", "E0080", "E0015"]);
+    }
+
+    #[test]
+    fn test_array_lengths_must_evaluate() {
+        let source = r#"
+            const WIDTH: i32 = 4;
+            struct Grid { cells: [u8; WIDTH - 5] }
+            struct Matrix<const R: usize> { rows: [i32; R] }
+            fn main(): i32 {
+                let row: [i32; WIDTH / (WIDTH - 4)] = [0; 1];
+                let ok: [i32; WIDTH * 2] = [0; 8];
+                let imported: [i32; LIMIT] = [0; 3];
+                return 0;
+            }
+        "#;
+        assert_eq!(error_codes_of(source), vec!["E0080", "E0080"]);
+    }
+
+    #[test]
+    fn test_attributes() {
+        let source = r#"
+            #[inline(always)]
+            #[must_use("the checksum detects corruption")]
+            fn checksum(x: i32): i32 { return x * 31; }
+            #[deprecated("use checksum")]
+            fn old_checksum(x: i32): i32 { return checksum(x); }
+            #[deprecated]
+            fn older(): i32 { return old_checksum(1); }
+            #[must_use]
+            struct Token {
+                id: i32,
+                #[deprecated(note = "use id")]
+                raw: i32,
+            }
+            fn new_token(): Token { return Token { id: 1, raw: 0 }; }
+            #[cold]
+            enum Mode { Fast }
+            #[inline(sometimes)]
+            fn weird() {}
+            #[frobnicate]
+            fn unknown() {}
+            fn main(): i32 {
+                checksum(1);
+                let _kept = checksum(2);
+                new_token();
+                old_checksum(3);
+                #[inline]
+                let x = 1;
+                return x;
+            }
+        "#;
+        assert_eq!(
+            warning_codes_of(source),
+            vec!["W0006", "W0006", "W0009", "W0009", "W0010", "W0010", "W0010", "W0010"]
+        );
+    }
+}
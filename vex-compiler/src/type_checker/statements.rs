@@ -0,0 +1,344 @@
+// Statement checking for the checker
+// Binds locals, checks annotations, assignments, returns and conditions
+
+use super::compatibility::{display_type, strip_references};
+use super::expressions::{is_numeric, range_element};
+use super::{AttributeTarget, Checker, ItemEnv};
+use std::collections::HashMap;
+use vex_ast::*;
+
+impl<'a> Checker<'a> {
+    pub(super) fn check_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_block(&mut self, block: &Block) {
+        let previous = self.enter_span(&block.span_id);
+        self.scopes.push(HashMap::new());
+        self.check_statements(&block.statements);
+        self.scopes.pop();
+        self.span = previous;
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Let {
+                name, ty, value, ..
+            } => {
+                let found = self.infer_expr(value);
+                let bound = match ty {
+                    Some(annotation) => {
+                        let expected = self.resolve_annotation(annotation);
+                        self.check_assignable(
+                            &expected,
+                            &found,
+                            &format!("`{}` is declared as `{}`", name, display_type(&expected)),
+                        );
+                        expected
+                    }
+                    None => found,
+                };
+                self.bind(name, bound);
+            }
+            Statement::LetPattern {
+                pattern, ty, value, ..
+            } => {
+                let found = self.infer_expr(value);
+                let bound = match ty {
+                    Some(annotation) => {
+                        let expected = self.resolve_annotation(annotation);
+                        self.check_assignable(
+                            &expected,
+                            &found,
+                            "the pattern is declared with this type",
+                        );
+                        expected
+                    }
+                    None => found,
+                };
+                self.bind_pattern(pattern, &bound);
+            }
+            Statement::Assign {
+                span_id,
+                target,
+                value,
+            } => {
+                let previous = self.enter_span(span_id);
+                let target_ty = self.infer_expr(target);
+                let value_ty = self.infer_expr(value);
+                self.check_assignable(
+                    &target_ty,
+                    &value_ty,
+                    &format!("the assigned place has type `{}`", display_type(&target_ty)),
+                );
+                self.span = previous;
+            }
+            Statement::CompoundAssign {
+                span_id,
+                target,
+                value,
+                ..
+            } => {
+                let previous = self.enter_span(span_id);
+                self.infer_expr(target);
+                self.infer_expr(value);
+                self.span = previous;
+            }
+            Statement::Return { span_id, value } => {
+                let previous = self.enter_span(span_id);
+                if let Some(value) = value {
+                    let found = self.infer_expr(value);
+                    if let Some(expected) = self.return_type.clone() {
+                        let context =
+                            format!("`{}` returns `{}`", self.function, display_type(&expected));
+                        self.check_assignable(&expected, &found, &context);
+                    }
+                }
+                self.span = previous;
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => {}
+            Statement::Defer(inner) => self.check_statement(inner),
+            Statement::If {
+                span_id,
+                condition,
+                then_block,
+                elif_branches,
+                else_block,
+            } => {
+                let previous = self.enter_span(span_id);
+                self.check_condition(condition, "if");
+                let narrowed = self.union_narrowing(condition);
+                self.check_narrowed_block(then_block, narrowed);
+                for (elif_condition, block) in elif_branches {
+                    self.check_condition(elif_condition, "elif");
+                    let narrowed = self.union_narrowing(elif_condition);
+                    self.check_narrowed_block(block, narrowed);
+                }
+                if let Some(block) = else_block {
+                    let conditions: Vec<&Expression> = std::iter::once(condition)
+                        .chain(
+                            elif_branches
+                                .iter()
+                                .map(|(elif_condition, _)| elif_condition),
+                        )
+                        .collect();
+                    let narrowed = self.remaining_union_member(&conditions);
+                    self.check_narrowed_block(block, narrowed);
+                }
+                self.span = previous;
+            }
+            Statement::For {
+                span_id,
+                init,
+                condition,
+                post,
+                body,
+            } => {
+                let previous = self.enter_span(span_id);
+                self.scopes.push(HashMap::new());
+                if let Some(init) = init {
+                    self.check_statement(init);
+                }
+                if let Some(condition) = condition {
+                    self.check_condition(condition, "for");
+                }
+                if let Some(post) = post {
+                    self.check_statement(post);
+                }
+                self.check_block(body);
+                self.scopes.pop();
+                self.span = previous;
+            }
+            Statement::While {
+                span_id,
+                condition,
+                body,
+            } => {
+                let previous = self.enter_span(span_id);
+                self.check_condition(condition, "while");
+                self.check_block(body);
+                self.span = previous;
+            }
+            Statement::Loop { span_id, body } => {
+                let previous = self.enter_span(span_id);
+                self.check_block(body);
+                self.span = previous;
+            }
+            Statement::ForIn {
+                span_id,
+                variable,
+                iterable,
+                body,
+            } => {
+                let previous = self.enter_span(span_id);
+                let iterable_ty = self.infer_expr(iterable);
+                self.scopes.push(HashMap::new());
+                let element = self.iteration_element(&iterable_ty);
+                self.bind(variable, element);
+                self.check_block(body);
+                self.scopes.pop();
+                self.span = previous;
+            }
+            Statement::Switch {
+                span_id,
+                value,
+                cases,
+                default_case,
+            } => {
+                let previous = self.enter_span(span_id);
+                if let Some(value) = value {
+                    self.infer_expr(value);
+                }
+                for case in cases {
+                    for pattern in &case.patterns {
+                        self.infer_expr(pattern);
+                    }
+                    self.check_block(&case.body);
+                }
+                if let Some(block) = default_case {
+                    self.check_block(block);
+                }
+                self.span = previous;
+            }
+            Statement::Select {
+                span_id,
+                cases,
+                default_case,
+            } => {
+                let previous = self.enter_span(span_id);
+                for case in cases {
+                    let received = self.infer_expr(&case.expr);
+                    self.scopes.push(HashMap::new());
+                    if let Some(var) = &case.var {
+                        self.bind(var, received);
+                    }
+                    self.check_block(&case.body);
+                    self.scopes.pop();
+                }
+                if let Some(block) = default_case {
+                    self.check_block(block);
+                }
+                self.span = previous;
+            }
+            Statement::Go { span_id, expr } => {
+                let previous = self.enter_span(span_id);
+                self.infer_expr(expr);
+                self.span = previous;
+            }
+            Statement::Unsafe { span_id, block } => {
+                let previous = self.enter_span(span_id);
+                self.check_block(block);
+                self.span = previous;
+            }
+            Statement::Attributed { attributes, stmt } => {
+                self.check_attributes(attributes, AttributeTarget::Statement);
+                self.check_statement(stmt);
+            }
+            Statement::Expression(expr) => {
+                let ty = self.infer_expr(expr);
+                self.check_unused_result(expr, &ty);
+            }
+        }
+    }
+
+    /// Check a branch where a union variable is known to hold one member
+    fn check_narrowed_block(&mut self, block: &Block, narrowed: Option<(String, Type)>) {
+        match narrowed {
+            Some((name, member)) => {
+                // Shadows the union for this branch only; the table keeps the declared type
+                self.scopes.push(HashMap::from([(name, member)]));
+                self.check_block(block);
+                self.scopes.pop();
+            }
+            None => self.check_block(block),
+        }
+    }
+
+    /// `name is T` on a local union variable: the variable and the member T
+    fn union_narrowing(&self, condition: &Expression) -> Option<(String, Type)> {
+        let (name, target_type) = match condition {
+            Expression::Is { expr, target_type } => match expr.as_ref() {
+                Expression::Ident(name) => (name, target_type),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let Some(Type::Union(members)) = self.lookup_local(name) else {
+            return None;
+        };
+        let member = self.resolve_type(target_type);
+        members.contains(&member).then(|| (name.clone(), member))
+    }
+
+    /// The one member an else branch is left with when every condition tests
+    /// the same union variable with `is`
+    fn remaining_union_member(&self, conditions: &[&Expression]) -> Option<(String, Type)> {
+        let mut tested = Vec::new();
+        let mut variable: Option<String> = None;
+        for condition in conditions {
+            let (name, member) = self.union_narrowing(condition)?;
+            if variable.get_or_insert_with(|| name.clone()) != &name {
+                return None;
+            }
+            tested.push(member);
+        }
+
+        let name = variable?;
+        let Some(Type::Union(members)) = self.lookup_local(&name) else {
+            return None;
+        };
+        let mut remaining = members.iter().filter(|m| !tested.contains(m));
+        match (remaining.next(), remaining.next()) {
+            (Some(last), None) => Some((name.clone(), last.clone())),
+            _ => None,
+        }
+    }
+
+    /// Conditions must be `bool`; numeric truthiness is left to codegen
+    fn check_condition(&mut self, condition: &Expression, keyword: &str) {
+        let ty = self.infer_expr(condition);
+        if !is_numeric(strip_references(&ty)) {
+            self.check_assignable(
+                &Type::Bool,
+                &ty,
+                &format!("`{}` conditions must be `bool`", keyword),
+            );
+        }
+    }
+
+    /// Loop variable type for `for x in iterable`: builtin sequences, `Generator<T>`,
+    /// or a user type whose `next()` returns `Option<T>`
+    fn iteration_element(&self, iterable: &Type) -> Type {
+        let ty = strip_references(iterable);
+        if let Some(item) = ty.generator_item() {
+            return item.clone();
+        }
+        let next_item = ItemEnv::nominal_name(ty)
+            .and_then(|name| self.env.instance_methods(&name, "next").first().copied())
+            .and_then(|next| match &next.return_type {
+                Some(Type::Option(item)) => Some((**item).clone()),
+                _ => None,
+            });
+        match next_item {
+            // `Self.Item` is bound by the impl; leave it to codegen
+            Some(Type::AssociatedType { .. }) => Type::Unknown,
+            Some(item) => item,
+            None => builtin_iteration_element(ty),
+        }
+    }
+}
+
+/// Loop variable type for builtin iterables
+fn builtin_iteration_element(iterable: &Type) -> Type {
+    match iterable {
+        Type::Array(elem, _) | Type::Slice(elem, _) | Type::Vec(elem) => (**elem).clone(),
+        Type::Generic { .. } => match range_element(iterable) {
+            Some(Type::Unknown) => Type::I32,
+            Some(elem) => elem,
+            None => Type::Unknown,
+        },
+        _ => Type::Unknown,
+    }
+}
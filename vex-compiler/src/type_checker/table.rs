@@ -0,0 +1,113 @@
+// Typed side table produced by the type checker
+// Codegen, the linter and the LSP read resolved types from here instead of re-inferring them
+
+use std::collections::HashMap;
+use vex_ast::Type;
+use vex_diagnostics::Span;
+
+/// Resolved signature of a function or method
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSignature {
+    pub params: Vec<(String, Type)>,
+    pub return_type: Type,
+}
+
+/// Types resolved by the checker
+///
+/// Functions are keyed by name, methods by `Type.method`.
+/// Expressions are keyed by span id, so only nodes the parser tags (calls,
+/// binary and unary operations) get an entry.
+/// Scopes map each function key to the span of its body.
+#[derive(Debug, Clone, Default)]
+pub struct TypeTable {
+    expr_types: HashMap<String, Type>,
+    locals: HashMap<String, Vec<(String, Type)>>,
+    signatures: HashMap<String, FunctionSignature>,
+    scopes: Vec<(String, Span)>,
+}
+
+impl TypeTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Type of the expression with this span id
+    pub fn expr_type(&self, span_id: &str) -> Option<&Type> {
+        self.expr_types.get(span_id)
+    }
+
+    /// Type of a local variable or parameter; the latest binding wins when a name is shadowed
+    pub fn local_type(&self, function: &str, name: &str) -> Option<&Type> {
+        self.locals
+            .get(function)?
+            .iter()
+            .rev()
+            .find(|(local, _)| local == name)
+            .map(|(_, ty)| ty)
+    }
+
+    /// All locals of a function in declaration order
+    pub fn locals(&self, function: &str) -> &[(String, Type)] {
+        self.locals.get(function).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Signature of a function (`add`) or method (`Point.area`)
+    pub fn signature(&self, name: &str) -> Option<&FunctionSignature> {
+        self.signatures.get(name)
+    }
+
+    /// Names of every checked function and method
+    pub fn functions(&self) -> impl Iterator<Item = &String> {
+        self.signatures.keys()
+    }
+
+    /// Key of the function whose body contains the 1-based `line` and `column`
+    /// of `source`. Spans only record where a body starts and its byte length,
+    /// so the source is needed to place the position.
+    pub fn function_at(&self, source: &str, line: usize, column: usize) -> Option<&str> {
+        let offset = byte_offset(source, line, column)?;
+        self.scopes
+            .iter()
+            .filter(|(_, body)| {
+                byte_offset(source, body.line, body.column)
+                    .is_some_and(|start| start <= offset && offset < start + body.length)
+            })
+            .min_by_key(|(_, body)| body.length)
+            .map(|(function, _)| function.as_str())
+    }
+
+    pub(crate) fn record_expr(&mut self, span_id: &str, ty: Type) {
+        self.expr_types.insert(span_id.to_string(), ty);
+    }
+
+    pub(crate) fn record_local(&mut self, function: &str, name: &str, ty: Type) {
+        self.locals
+            .entry(function.to_string())
+            .or_default()
+            .push((name.to_string(), ty));
+    }
+
+    pub(crate) fn record_signature(&mut self, name: &str, signature: FunctionSignature) {
+        self.signatures.insert(name.to_string(), signature);
+    }
+
+    pub(crate) fn record_scope(&mut self, function: &str, body: Span) {
+        self.scopes.push((function.to_string(), body));
+    }
+}
+
+/// Byte offset of a 1-based line and column, as the parser's spans count them
+fn byte_offset(source: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = match line {
+        0 => return None,
+        1 => 0,
+        _ => {
+            source
+                .match_indices('\n')
+                .nth(line - 2)
+                .map(|(newline, _)| newline)?
+                + 1
+        }
+    };
+    Some(line_start + column.checked_sub(1)?)
+}
@@ -0,0 +1,507 @@
+//! Type-level evaluation of conditional types
+//!
+//! Reduces `T extends U ? X : Y` once the generic parameters in `T` have been
+//! substituted, binding `infer` variables from the matched part of `U`.
+//! Union check types distribute over their members (TypeScript's distributive
+//! conditionals) and `never` results drop out of the union.
+
+use super::const_generics;
+use std::collections::HashMap;
+use vex_ast::Type;
+
+/// Rebuild a type bottom-up; `f` may replace any node before its children are visited
+pub fn map_type(ty: &Type, f: &mut impl FnMut(&Type) -> Option<Type>) -> Type {
+    if let Some(replaced) = f(ty) {
+        return replaced;
+    }
+
+    let mut map = |t: &Type| Box::new(map_type(t, f));
+    match ty {
+        Type::Generic { name, type_args } => Type::Generic {
+            name: name.clone(),
+            type_args: type_args.iter().map(|t| *map(t)).collect(),
+        },
+        Type::Array(elem, size) => Type::Array(map(elem), *size),
+        Type::ConstArray { elem_type, size } => Type::ConstArray {
+            elem_type: map(elem_type),
+            size: size.clone(),
+        },
+        Type::Slice(elem, is_mut) => Type::Slice(map(elem), *is_mut),
+        Type::Reference(inner, is_mut) => Type::Reference(map(inner), *is_mut),
+        Type::Union(members) => Type::Union(members.iter().map(|t| *map(t)).collect()),
+        Type::Intersection(members) => {
+            Type::Intersection(members.iter().map(|t| *map(t)).collect())
+        }
+        Type::Tuple(members) => Type::Tuple(members.iter().map(|t| *map(t)).collect()),
+        Type::Function {
+            params,
+            return_type,
+        } => Type::Function {
+            params: params.iter().map(|t| *map(t)).collect(),
+            return_type: map(return_type),
+        },
+        Type::RawPtr { inner, is_const } => Type::RawPtr {
+            inner: map(inner),
+            is_const: *is_const,
+        },
+        Type::Conditional {
+            check_type,
+            extends_type,
+            true_type,
+            false_type,
+        } => Type::Conditional {
+            check_type: map(check_type),
+            extends_type: map(extends_type),
+            true_type: map(true_type),
+            false_type: map(false_type),
+        },
+        Type::AssociatedType { self_type, name } => Type::AssociatedType {
+            self_type: map(self_type),
+            name: name.clone(),
+        },
+        Type::Option(inner) => Type::Option(map(inner)),
+        Type::Result(ok, err) => Type::Result(map(ok), map(err)),
+        Type::Vec(elem) => Type::Vec(map(elem)),
+        Type::Box(inner) => Type::Box(map(inner)),
+        Type::Channel(elem) => Type::Channel(map(elem)),
+        Type::Future(inner) => Type::Future(map(inner)),
+        _ => ty.clone(),
+    }
+}
+
+/// Replace generic parameters by name: `Unwrap<Option<i32>>` → body with T = Option<i32>.
+/// A conditional that tests a bare parameter bound to a union is distributed over the
+/// members, so `T` in its branches refers to one member at a time.
+pub fn substitute_params(ty: &Type, subst: &HashMap<String, Type>) -> Type {
+    map_type(ty, &mut |t| match t {
+        Type::Named(name) => subst.get(name).cloned(),
+        // [T; R * C] becomes a fixed array once R and C are bound
+        Type::ConstArray { elem_type, size } => const_generics::eval_const_expr(size, subst)
+            .and_then(|n| usize::try_from(n).ok())
+            .map(|n| Type::Array(Box::new(substitute_params(elem_type, subst)), n)),
+        Type::Conditional { check_type, .. } => match check_type.as_ref() {
+            Type::Named(param) => match subst.get(param) {
+                Some(Type::Union(members)) => Some(Type::Union(
+                    members
+                        .iter()
+                        .map(|member| {
+                            let mut single = subst.clone();
+                            single.insert(param.clone(), member.clone());
+                            substitute_params(t, &single)
+                        })
+                        .collect(),
+                )),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Whether a type still contains a conditional that has to be reduced
+pub fn contains_conditional(ty: &Type) -> bool {
+    let mut found = false;
+    map_type(ty, &mut |t| {
+        if matches!(t, Type::Conditional { .. }) {
+            found = true;
+        }
+        None
+    });
+    found
+}
+
+/// Reduce every conditional type inside `ty`.
+///
+/// Conditionals whose check type is not known yet (a generic parameter the
+/// caller resolved to `Unknown`) become `Unknown`; they are reduced again after
+/// monomorphization. Conditionals that can never be reduced are an error.
+pub fn reduce_conditionals(ty: &Type) -> Result<Type, String> {
+    reduce_depth(ty, 0)
+}
+
+/// Nested conditionals deeper than this are treated as non-terminating
+const MAX_REDUCTION_DEPTH: usize = 64;
+
+fn reduce_depth(ty: &Type, depth: usize) -> Result<Type, String> {
+    let mut error = None;
+    let reduced = map_type(ty, &mut |t| match t {
+        Type::Conditional {
+            check_type,
+            extends_type,
+            true_type,
+            false_type,
+        } => {
+            let result = evaluate(check_type, extends_type, true_type, false_type, depth);
+            Some(result.unwrap_or_else(|e| {
+                error.get_or_insert(e);
+                Type::Unknown
+            }))
+        }
+        Type::Union(members) if members.iter().any(contains_conditional) => {
+            let mut reduced = Vec::new();
+            for member in members {
+                match reduce_depth(member, depth + 1) {
+                    Ok(ty) => reduced.push(ty),
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        reduced.push(Type::Unknown);
+                    }
+                }
+            }
+            Some(union_of(reduced))
+        }
+        _ => None,
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(reduced),
+    }
+}
+
+fn evaluate(
+    check_type: &Type,
+    extends_type: &Type,
+    true_type: &Type,
+    false_type: &Type,
+    depth: usize,
+) -> Result<Type, String> {
+    if depth > MAX_REDUCTION_DEPTH {
+        return Err(format!(
+            "conditional type `{}` does not terminate",
+            describe(check_type, extends_type)
+        ));
+    }
+
+    let check = normalize(&reduce_depth(check_type, depth + 1)?);
+    let extends = normalize(&reduce_depth(extends_type, depth + 1)?);
+
+    match &check {
+        Type::Never => return Ok(Type::Never),
+        Type::Union(members) => {
+            let results = members
+                .iter()
+                .map(|member| evaluate(member, &extends, true_type, false_type, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(union_of(results));
+        }
+        _ => {}
+    }
+
+    if !is_known(&check) || !is_known(&extends) {
+        return Ok(Type::Unknown);
+    }
+    if check == Type::Any && extends != Type::Any {
+        return Err(format!(
+            "cannot decide whether `any` extends `{}`",
+            crate::type_checker::display_type(&extends)
+        ));
+    }
+
+    let inferred = infer_names(&extends);
+    let mut bindings = HashMap::new();
+    if extends_match(&check, &extends, &mut bindings) {
+        let mut unbound = None;
+        let branch = map_type(true_type, &mut |t| match t {
+            Type::Named(name) if inferred.contains(name) => {
+                Some(bindings.get(name).cloned().unwrap_or_else(|| {
+                    unbound.get_or_insert_with(|| name.clone());
+                    Type::Unknown
+                }))
+            }
+            _ => None,
+        });
+        if let Some(name) = unbound {
+            return Err(format!(
+                "`infer {}` is not bound when `{}` holds",
+                name,
+                describe(&check, &extends)
+            ));
+        }
+        Ok(normalize(&reduce_depth(&branch, depth + 1)?))
+    } else {
+        let mut leaked = None;
+        map_type(false_type, &mut |t| {
+            if let Type::Named(name) = t {
+                if inferred.contains(name) {
+                    leaked.get_or_insert_with(|| name.clone());
+                }
+            }
+            None
+        });
+        if let Some(name) = leaked {
+            return Err(format!(
+                "`{}` is only bound by `infer` when `{}` holds, but it is used in the false branch",
+                name,
+                describe(&check, &extends)
+            ));
+        }
+        Ok(normalize(&reduce_depth(false_type, depth + 1)?))
+    }
+}
+
+/// Structural `ty extends pattern`, binding `infer` variables in `pattern`
+fn extends_match(ty: &Type, pattern: &Type, bindings: &mut HashMap<String, Type>) -> bool {
+    match (ty, pattern) {
+        (_, Type::Infer(name)) => match bindings.get(name) {
+            Some(bound) => bound == ty,
+            None => {
+                bindings.insert(name.clone(), ty.clone());
+                true
+            }
+        },
+        (_, Type::Any) | (Type::Never, _) => true,
+        (_, Type::Union(members)) => members.iter().any(|member| {
+            let mut trial = bindings.clone();
+            let matched = extends_match(ty, member, &mut trial);
+            if matched {
+                *bindings = trial;
+            }
+            matched
+        }),
+        (Type::Union(members), _) => members.iter().all(|m| extends_match(m, pattern, bindings)),
+        (Type::Option(inner), Type::Option(p))
+        | (Type::Vec(inner), Type::Vec(p))
+        | (Type::Box(inner), Type::Box(p))
+        | (Type::Channel(inner), Type::Channel(p))
+        | (Type::Future(inner), Type::Future(p)) => extends_match(inner, p, bindings),
+        (Type::Result(ok, err), Type::Result(p_ok, p_err)) => {
+            extends_match(ok, p_ok, bindings) && extends_match(err, p_err, bindings)
+        }
+        (Type::Array(elem, size), Type::Array(p, p_size)) => {
+            size == p_size && extends_match(elem, p, bindings)
+        }
+        // Arrays coerce to slices, and a mutable slice can be used as a shared one
+        (Type::Array(elem, _), Type::Slice(p, false)) => extends_match(elem, p, bindings),
+        (Type::Slice(elem, is_mut), Type::Slice(p, p_mut))
+        | (Type::Reference(elem, is_mut), Type::Reference(p, p_mut)) => {
+            (*is_mut || !*p_mut) && extends_match(elem, p, bindings)
+        }
+        (
+            Type::RawPtr { inner, is_const },
+            Type::RawPtr {
+                inner: p,
+                is_const: p_const,
+            },
+        ) => (*p_const || !*is_const) && extends_match(inner, p, bindings),
+        (Type::Tuple(items), Type::Tuple(p_items)) => {
+            items.len() == p_items.len()
+                && items
+                    .iter()
+                    .zip(p_items)
+                    .all(|(t, p)| extends_match(t, p, bindings))
+        }
+        (
+            Type::Generic { name, type_args },
+            Type::Generic {
+                name: p_name,
+                type_args: p_args,
+            },
+        ) => {
+            name == p_name
+                && type_args.len() == p_args.len()
+                && type_args
+                    .iter()
+                    .zip(p_args)
+                    .all(|(t, p)| extends_match(t, p, bindings))
+        }
+        (
+            Type::Function {
+                params,
+                return_type,
+            },
+            Type::Function {
+                params: p_params,
+                return_type: p_return,
+            },
+        ) => {
+            params.len() == p_params.len()
+                && params
+                    .iter()
+                    .zip(p_params)
+                    .all(|(t, p)| extends_match(t, p, bindings))
+                && extends_match(return_type, p_return, bindings)
+        }
+        _ => ty == pattern,
+    }
+}
+
+/// Names introduced by `infer X` inside an extends clause
+fn infer_names(extends: &Type) -> Vec<String> {
+    let mut names = Vec::new();
+    map_type(extends, &mut |t| {
+        if let Type::Infer(name) = t {
+            names.push(name.clone());
+        }
+        None
+    });
+    names
+}
+
+/// Fully known types can be tested; `infer` placeholders count as known
+fn is_known(ty: &Type) -> bool {
+    let mut known = true;
+    map_type(ty, &mut |t| {
+        if matches!(
+            t,
+            Type::Unknown
+                | Type::Typeof(_)
+                | Type::SelfType
+                | Type::AssociatedType { .. }
+                | Type::Conditional { .. }
+        ) {
+            known = false;
+        }
+        None
+    });
+    known
+}
+
+/// Flatten nested unions, drop `never` members and duplicates
+fn union_of(types: Vec<Type>) -> Type {
+    let mut members: Vec<Type> = Vec::new();
+    for ty in types {
+        let flattened = match normalize(&ty) {
+            Type::Union(inner) => inner,
+            Type::Never => Vec::new(),
+            other => vec![other],
+        };
+        for member in flattened {
+            if member != Type::Never && !members.contains(&member) {
+                members.push(member);
+            }
+        }
+    }
+    match members.len() {
+        0 => Type::Never,
+        1 => members.remove(0),
+        _ => Type::Union(members),
+    }
+}
+
+/// `never` is written as a plain name in type aliases
+fn normalize(ty: &Type) -> Type {
+    map_type(ty, &mut |t| match t {
+        Type::Named(name) if name == "never" => Some(Type::Never),
+        _ => None,
+    })
+}
+
+fn describe(check: &Type, extends: &Type) -> String {
+    format!(
+        "{} extends {}",
+        crate::type_checker::display_type(check),
+        crate::type_checker::display_type(extends)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditional(check: Type, extends: Type, true_type: Type, false_type: Type) -> Type {
+        Type::Conditional {
+            check_type: Box::new(check),
+            extends_type: Box::new(extends),
+            true_type: Box::new(true_type),
+            false_type: Box::new(false_type),
+        }
+    }
+
+    fn named(name: &str) -> Type {
+        Type::Named(name.to_string())
+    }
+
+    /// type Unwrap<T> = T extends Option<infer U> ? U : T;
+    fn unwrap_of(arg: Type) -> Type {
+        let body = conditional(
+            named("T"),
+            Type::Option(Box::new(Type::Infer("U".to_string()))),
+            named("U"),
+            named("T"),
+        );
+        substitute_params(&body, &HashMap::from([("T".to_string(), arg)]))
+    }
+
+    #[test]
+    fn test_infer_binds_from_matched_pattern() {
+        let unwrapped = reduce_conditionals(&unwrap_of(Type::Option(Box::new(Type::I32))));
+        assert_eq!(unwrapped, Ok(Type::I32));
+        assert_eq!(
+            reduce_conditionals(&unwrap_of(Type::String)),
+            Ok(Type::String)
+        );
+    }
+
+    #[test]
+    fn test_result_pattern_and_never() {
+        // type ExtractErr<T> = T extends Result<infer V, infer E> ? E : never;
+        let extract_err = |arg: Type| {
+            conditional(
+                arg,
+                Type::Result(
+                    Box::new(Type::Infer("V".to_string())),
+                    Box::new(Type::Infer("E".to_string())),
+                ),
+                named("E"),
+                named("never"),
+            )
+        };
+        let ok = Type::Result(Box::new(Type::I32), Box::new(Type::String));
+        assert_eq!(reduce_conditionals(&extract_err(ok)), Ok(Type::String));
+        assert_eq!(
+            reduce_conditionals(&extract_err(Type::I32)),
+            Ok(Type::Never)
+        );
+    }
+
+    #[test]
+    fn test_union_check_type_distributes() {
+        let arg = Type::Union(vec![
+            Type::Option(Box::new(Type::I32)),
+            Type::Bool,
+            Type::Option(Box::new(Type::I32)),
+        ]);
+        assert_eq!(
+            reduce_conditionals(&unwrap_of(arg)),
+            Ok(Type::Union(vec![Type::I32, Type::Bool]))
+        );
+
+        // Members that reduce to never drop out
+        let only_numbers = conditional(
+            Type::Union(vec![Type::I32, Type::String]),
+            Type::I32,
+            Type::I32,
+            named("never"),
+        );
+        assert_eq!(reduce_conditionals(&only_numbers), Ok(Type::I32));
+    }
+
+    #[test]
+    fn test_unknown_check_type_is_deferred() {
+        assert_eq!(
+            reduce_conditionals(&unwrap_of(Type::Unknown)),
+            Ok(Type::Unknown)
+        );
+        let nested = Type::Vec(Box::new(unwrap_of(Type::Box(Box::new(Type::F64)))));
+        assert_eq!(
+            reduce_conditionals(&nested),
+            Ok(Type::Vec(Box::new(Type::Box(Box::new(Type::F64)))))
+        );
+    }
+
+    #[test]
+    fn test_irreducible_conditionals_are_errors() {
+        let leaked = conditional(
+            Type::I32,
+            Type::Option(Box::new(Type::Infer("U".to_string()))),
+            named("U"),
+            named("U"),
+        );
+        let err = reduce_conditionals(&leaked).unwrap_err();
+        assert!(err.contains("false branch"), "{}", err);
+
+        let any = conditional(Type::Any, Type::I32, Type::I32, Type::Bool);
+        assert!(reduce_conditionals(&any).is_err());
+    }
+}
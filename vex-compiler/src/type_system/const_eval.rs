@@ -0,0 +1,1302 @@
+//! Compile-time evaluation of constants and `const fn`
+//!
+//! Interprets `const` initializers over the AST: integer and float arithmetic,
+//! string concatenation, array literals and calls to functions declared
+//! `const fn`. Integer overflow, division by zero and out-of-bounds indexing
+//! are evaluation errors rather than runtime traps. Expressions the evaluator
+//! does not model are reported as `Unsupported` so callers can fall back to
+//! compiling them at runtime.
+
+use std::collections::{HashMap, HashSet};
+use vex_ast::{
+    BinaryOp, Block, CompoundOp, Const, Expression, Function, Item, PostfixOp, Program, Statement,
+    Type, UnaryOp,
+};
+
+/// Maximum nesting of `const fn` calls
+const MAX_CALL_DEPTH: usize = 128;
+/// Maximum number of evaluation steps for a single constant
+const MAX_STEPS: usize = 1_000_000;
+
+/// A value computed at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    /// Integer of the given integer type
+    Int(i128, Type),
+    /// Float of the given float type, already rounded to its precision
+    Float(f64, Type),
+    Bool(bool),
+    Str(String),
+    /// Fixed-size array: element type and elements
+    Array(Type, Vec<ConstValue>),
+}
+
+impl ConstValue {
+    /// Vex type of the value
+    pub fn ty(&self) -> Type {
+        match self {
+            ConstValue::Int(_, ty) | ConstValue::Float(_, ty) => ty.clone(),
+            ConstValue::Bool(_) => Type::Bool,
+            ConstValue::Str(_) => Type::String,
+            ConstValue::Array(elem, items) => Type::Array(Box::new(elem.clone()), items.len()),
+        }
+    }
+
+    /// Integer value, if this is an integer
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            ConstValue::Int(value, _) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Why a constant could not be evaluated
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstEvalError {
+    /// Evaluation failed: overflow, division by zero, index out of bounds (E0080)
+    Failed(String),
+    /// A function that is not `const fn` was called (E0015)
+    NotConst(String),
+    /// The value cannot be computed at compile time; it is left to runtime
+    Unsupported,
+}
+
+type EvalResult<T> = Result<T, ConstEvalError>;
+
+/// Control flow out of a statement inside a `const fn`
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Option<ConstValue>),
+}
+
+/// Evaluates the constants of one program, caching each result
+pub struct ConstEvaluator<'a> {
+    /// Constants in declaration order
+    decls: Vec<&'a Const>,
+    consts: HashMap<&'a str, &'a Const>,
+    functions: HashMap<&'a str, &'a Function>,
+    results: HashMap<String, EvalResult<ConstValue>>,
+    in_progress: HashSet<String>,
+    /// One entry per active `const fn` call, each a stack of block scopes
+    frames: Vec<Vec<HashMap<String, ConstValue>>>,
+    /// Declared return type of each active `const fn` call
+    return_types: Vec<Option<Type>>,
+    steps: usize,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(program: &'a Program) -> Self {
+        let mut decls = Vec::new();
+        let mut consts = HashMap::new();
+        let mut functions = HashMap::new();
+        for item in &program.items {
+            match item {
+                Item::Const(c) => {
+                    decls.push(c);
+                    consts.insert(c.name.as_str(), c);
+                }
+                Item::Function(f) if f.receiver.is_none() && !f.is_static => {
+                    functions.insert(f.name.as_str(), f);
+                }
+                _ => {}
+            }
+        }
+        Self {
+            decls,
+            consts,
+            functions,
+            results: HashMap::new(),
+            in_progress: HashSet::new(),
+            frames: Vec::new(),
+            return_types: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Value of the constant `name`, evaluating it on first use
+    pub fn eval_const(&mut self, name: &str) -> EvalResult<ConstValue> {
+        if let Some(result) = self.results.get(name) {
+            return result.clone();
+        }
+        let Some(decl) = self.consts.get(name).copied() else {
+            return Err(ConstEvalError::Unsupported);
+        };
+        if !self.in_progress.insert(name.to_string()) {
+            return Err(ConstEvalError::Failed(format!(
+                "cycle detected when evaluating constant `{}`",
+                name
+            )));
+        }
+
+        // Each constant is evaluated on its own, outside any caller's frames
+        let saved_frames = std::mem::take(&mut self.frames);
+        let saved_return_types = std::mem::take(&mut self.return_types);
+        let saved_steps = std::mem::replace(&mut self.steps, 0);
+        let declared = decl.ty.as_ref().map(|ty| self.resolve_type(ty));
+        let result =
+            self.eval_expr(&decl.value, declared.as_ref())
+                .and_then(|value| match &declared {
+                    Some(ty) => coerce(value, ty),
+                    None => Ok(value),
+                });
+        self.frames = saved_frames;
+        self.return_types = saved_return_types;
+        self.steps = saved_steps;
+
+        self.in_progress.remove(name);
+        self.results.insert(name.to_string(), result.clone());
+        result
+    }
+
+    /// Evaluate every constant of the program, in declaration order
+    pub fn eval_all(&mut self) -> Vec<(&'a Const, EvalResult<ConstValue>)> {
+        self.decls
+            .clone()
+            .into_iter()
+            .map(|decl| (decl, self.eval_const(&decl.name)))
+            .collect()
+    }
+
+    /// Length of an array type whose size is written as a constant expression
+    pub fn array_len(&mut self, size: &Expression) -> EvalResult<usize> {
+        match self.eval_expr(size, None)? {
+            ConstValue::Int(value, _) => usize::try_from(value)
+                .map_err(|_| ConstEvalError::Failed(format!("array length {} is negative", value))),
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    /// Replace `[T; N]` sizes that are constant expressions with their value
+    pub fn resolve_type(&mut self, ty: &Type) -> Type {
+        match ty {
+            Type::ConstArray { elem_type, size } => {
+                let elem = self.resolve_type(elem_type);
+                match self.array_len(size) {
+                    Ok(len) => Type::Array(Box::new(elem), len),
+                    Err(_) => Type::ConstArray {
+                        elem_type: Box::new(elem),
+                        size: size.clone(),
+                    },
+                }
+            }
+            Type::Array(elem, len) => Type::Array(Box::new(self.resolve_type(elem)), *len),
+            other => other.clone(),
+        }
+    }
+
+    fn tick(&mut self) -> EvalResult<()> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Err(ConstEvalError::Failed(
+                "constant evaluation exceeded the step limit".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Evaluate an expression; `expected` types untyped literals
+    pub fn eval_expr(
+        &mut self,
+        expr: &Expression,
+        expected: Option<&Type>,
+    ) -> EvalResult<ConstValue> {
+        self.tick()?;
+        match expr {
+            Expression::IntLiteral(value) => int_literal(*value as i128, expected),
+            Expression::BigIntLiteral(text) => {
+                let value = text
+                    .parse::<i128>()
+                    .map_err(|_| ConstEvalError::Unsupported)?;
+                match expected.filter(|ty| int_bounds(ty).is_some()) {
+                    Some(ty) => int_value(value, ty, "literal out of range"),
+                    None => Ok(ConstValue::Int(value, Type::I128)),
+                }
+            }
+            Expression::TypedIntLiteral { value, type_suffix } => {
+                literal_with_suffix(*value as i128, type_suffix)
+            }
+            Expression::TypedBigIntLiteral { value, type_suffix } => {
+                let value = value
+                    .parse::<i128>()
+                    .map_err(|_| ConstEvalError::Unsupported)?;
+                literal_with_suffix(value, type_suffix)
+            }
+            Expression::FloatLiteral(value) => {
+                let ty = expected
+                    .filter(|ty| is_float(ty))
+                    .cloned()
+                    .unwrap_or(Type::F64);
+                Ok(float_value(*value, ty))
+            }
+            Expression::BoolLiteral(value) => Ok(ConstValue::Bool(*value)),
+            Expression::StringLiteral(value) => Ok(ConstValue::Str(value.clone())),
+            Expression::Ident(name) => self.eval_ident(name),
+            Expression::Unary { op, expr, .. } => self.eval_unary(op, expr, expected),
+            Expression::Binary {
+                left, op, right, ..
+            } => self.eval_binary(op, left, right, expected),
+            Expression::Cast { expr, target_type } => {
+                let value = self.eval_expr(expr, None)?;
+                cast(value, &self.resolve_type(target_type))
+            }
+            Expression::Array(elements) => {
+                let elem_expected = expected.and_then(element_type);
+                let mut items = Vec::with_capacity(elements.len());
+                for element in elements {
+                    // Later elements follow the first one when there is no declared type
+                    let hint = elem_expected
+                        .clone()
+                        .or_else(|| items.first().map(ConstValue::ty));
+                    items.push(self.eval_expr(element, hint.as_ref())?);
+                }
+                let elem = match (elem_expected, items.first()) {
+                    (Some(ty), _) => ty,
+                    (None, Some(first)) => first.ty(),
+                    (None, None) => return Err(ConstEvalError::Unsupported),
+                };
+                let items = items
+                    .into_iter()
+                    .map(|item| coerce(item, &elem))
+                    .collect::<EvalResult<Vec<_>>>()?;
+                Ok(ConstValue::Array(elem, items))
+            }
+            Expression::ArrayRepeat(value, count) => {
+                let elem_expected = expected.and_then(element_type);
+                let value = self.eval_expr(value, elem_expected.as_ref())?;
+                let len = self.array_len(count)?;
+                if len > MAX_STEPS {
+                    // Too large to materialize here; compiled from the initializer instead
+                    return Err(ConstEvalError::Unsupported);
+                }
+                let elem = elem_expected.unwrap_or_else(|| value.ty());
+                let value = coerce(value, &elem)?;
+                Ok(ConstValue::Array(elem, vec![value; len]))
+            }
+            Expression::Index { object, index } => {
+                let object = self.eval_expr(object, None)?;
+                let index = self.eval_expr(index, None)?;
+                index_array(&object, &index).cloned()
+            }
+            Expression::Call {
+                func,
+                type_args,
+                args,
+                ..
+            } => match func.as_ref() {
+                Expression::Ident(name) if type_args.is_empty() => self.eval_call(name, args),
+                _ => Err(ConstEvalError::Unsupported),
+            },
+            // Block scopes live in the frame of the enclosing `const fn`
+            Expression::Block {
+                statements,
+                return_expr,
+            } if !self.frames.is_empty() => {
+                self.push_scope();
+                let result = self.eval_block_expr(statements, return_expr.as_deref(), expected);
+                self.pop_scope();
+                result
+            }
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    fn eval_block_expr(
+        &mut self,
+        statements: &[Statement],
+        return_expr: Option<&Expression>,
+        expected: Option<&Type>,
+    ) -> EvalResult<ConstValue> {
+        for stmt in statements {
+            if !matches!(self.exec_statement(stmt)?, Flow::Normal) {
+                return Err(ConstEvalError::Unsupported);
+            }
+        }
+        match return_expr {
+            Some(expr) => self.eval_expr(expr, expected),
+            None => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    fn eval_ident(&mut self, name: &str) -> EvalResult<ConstValue> {
+        if let Some(scopes) = self.frames.last() {
+            if let Some(value) = scopes.iter().rev().find_map(|scope| scope.get(name)) {
+                return Ok(value.clone());
+            }
+        }
+        match self.eval_const(name) {
+            Ok(value) => Ok(value),
+            // The failing constant reports its own error
+            Err(ConstEvalError::Failed(_)) | Err(ConstEvalError::NotConst(_))
+                if !self.in_progress.contains(name) =>
+            {
+                Err(ConstEvalError::Unsupported)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn eval_unary(
+        &mut self,
+        op: &UnaryOp,
+        expr: &Expression,
+        expected: Option<&Type>,
+    ) -> EvalResult<ConstValue> {
+        // `-128i8` and `-9223372036854775808` are in range even though their magnitude is not
+        if *op == UnaryOp::Neg {
+            match expr {
+                Expression::IntLiteral(value) => return int_literal(-(*value as i128), expected),
+                Expression::BigIntLiteral(text) => {
+                    let value = text
+                        .parse::<i128>()
+                        .map_err(|_| ConstEvalError::Unsupported)?;
+                    let ty = expected
+                        .filter(|ty| int_bounds(ty).is_some())
+                        .cloned()
+                        .unwrap_or(Type::I128);
+                    return int_value(-value, &ty, "literal out of range");
+                }
+                _ => {}
+            }
+        }
+
+        match (op, self.eval_expr(expr, expected)?) {
+            (UnaryOp::Neg, ConstValue::Int(value, ty)) => {
+                let negated = value.checked_neg().ok_or_else(|| overflow("negate"))?;
+                int_value(negated, &ty, "attempt to negate with overflow")
+            }
+            (UnaryOp::Neg, ConstValue::Float(value, ty)) => Ok(float_value(-value, ty)),
+            (UnaryOp::Not, ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+            (UnaryOp::Not | UnaryOp::BitNot, ConstValue::Int(value, ty)) => {
+                let (min, max) = int_bounds(&ty).ok_or(ConstEvalError::Unsupported)?;
+                if ty == Type::U128 {
+                    return Err(ConstEvalError::Unsupported);
+                }
+                // Signed: !v == -v - 1; unsigned: !v == MAX - v
+                Ok(ConstValue::Int(
+                    if min < 0 { !value } else { max - value },
+                    ty,
+                ))
+            }
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    fn eval_binary(
+        &mut self,
+        op: &BinaryOp,
+        left: &Expression,
+        right: &Expression,
+        expected: Option<&Type>,
+    ) -> EvalResult<ConstValue> {
+        match op {
+            BinaryOp::And | BinaryOp::Or => {
+                let ConstValue::Bool(l) = self.eval_expr(left, None)? else {
+                    return Err(ConstEvalError::Unsupported);
+                };
+                // Short-circuit like the runtime does
+                if l == (*op == BinaryOp::Or) {
+                    return Ok(ConstValue::Bool(l));
+                }
+                return match self.eval_expr(right, None)? {
+                    ConstValue::Bool(r) => Ok(ConstValue::Bool(r)),
+                    _ => Err(ConstEvalError::Unsupported),
+                };
+            }
+            BinaryOp::Range | BinaryOp::RangeInclusive | BinaryOp::NullCoalesce => {
+                return Err(ConstEvalError::Unsupported)
+            }
+            _ => {}
+        }
+
+        // Comparisons don't pass their result type down to the operands
+        let operand_expected = if is_comparison(op) { None } else { expected };
+        // Shift amounts keep their own type; other operands share one
+        let (l, r) = if matches!(op, BinaryOp::Shl | BinaryOp::Shr) {
+            let l = self.eval_expr(left, operand_expected)?;
+            (l, self.eval_expr(right, None)?)
+        } else if is_untyped_literal(left) && !is_untyped_literal(right) {
+            // `3 < N`: the literal takes the type of the other side
+            let r = self.eval_expr(right, operand_expected)?;
+            let l = self.eval_expr(left, Some(&r.ty()))?;
+            (l, r)
+        } else {
+            let l = self.eval_expr(left, operand_expected)?;
+            let r = self.eval_expr(right, Some(&l.ty()))?;
+            (l, r)
+        };
+        binary(op, l, r)
+    }
+
+    fn eval_call(&mut self, name: &str, args: &[Expression]) -> EvalResult<ConstValue> {
+        // Local bindings shadow functions
+        if let Some(scopes) = self.frames.last() {
+            if scopes.iter().any(|scope| scope.contains_key(name)) {
+                return Err(ConstEvalError::Unsupported);
+            }
+        }
+        let Some(func) = self.functions.get(name).copied() else {
+            return Err(ConstEvalError::Unsupported);
+        };
+        if !func.is_const {
+            return Err(ConstEvalError::NotConst(format!(
+                "cannot call non-const fn `{}` in a constant",
+                name
+            )));
+        }
+        if !func.type_params.is_empty() || func.is_async || func.is_variadic {
+            return Err(ConstEvalError::Unsupported);
+        }
+        if args.len() != func.params.len() {
+            return Err(ConstEvalError::Unsupported);
+        }
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(ConstEvalError::Failed(format!(
+                "reached the recursion limit while calling `{}`",
+                name
+            )));
+        }
+
+        let mut scope = HashMap::new();
+        for (param, arg) in func.params.iter().zip(args) {
+            let ty = self.resolve_type(&param.ty);
+            let value = self.eval_expr(arg, Some(&ty))?;
+            scope.insert(param.name.clone(), coerce(value, &ty)?);
+        }
+
+        let return_type = func.return_type.as_ref().map(|ty| self.resolve_type(ty));
+        self.frames.push(vec![scope]);
+        self.return_types.push(return_type.clone());
+        let flow = self.exec_block(&func.body);
+        self.return_types.pop();
+        self.frames.pop();
+
+        match (flow?, return_type) {
+            (Flow::Return(Some(value)), Some(ty)) => coerce(value, &ty),
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        if let Some(scopes) = self.frames.last_mut() {
+            scopes.push(HashMap::new());
+        }
+    }
+
+    fn pop_scope(&mut self) {
+        if let Some(scopes) = self.frames.last_mut() {
+            scopes.pop();
+        }
+    }
+
+    fn declare(&mut self, name: &str, value: ConstValue) {
+        if let Some(scope) = self.frames.last_mut().and_then(|scopes| scopes.last_mut()) {
+            scope.insert(name.to_string(), value);
+        }
+    }
+
+    fn local_mut(&mut self, name: &str) -> EvalResult<&mut ConstValue> {
+        self.frames
+            .last_mut()
+            .and_then(|scopes| scopes.iter_mut().rev().find_map(|s| s.get_mut(name)))
+            .ok_or(ConstEvalError::Unsupported)
+    }
+
+    fn exec_block(&mut self, block: &Block) -> EvalResult<Flow> {
+        self.push_scope();
+        let mut flow = Ok(Flow::Normal);
+        for stmt in &block.statements {
+            flow = self.exec_statement(stmt);
+            if !matches!(flow, Ok(Flow::Normal)) {
+                break;
+            }
+        }
+        self.pop_scope();
+        flow
+    }
+
+    fn exec_statement(&mut self, stmt: &Statement) -> EvalResult<Flow> {
+        self.tick()?;
+        match stmt {
+            Statement::Let {
+                name, ty, value, ..
+            } => {
+                let ty = ty.as_ref().map(|ty| self.resolve_type(ty));
+                let value = self.eval_expr(value, ty.as_ref())?;
+                let value = match &ty {
+                    Some(ty) => coerce(value, ty)?,
+                    None => value,
+                };
+                self.declare(name, value);
+                Ok(Flow::Normal)
+            }
+            Statement::Assign { target, value, .. } => {
+                let current = self.eval_expr(target, None)?;
+                let value = self.eval_expr(value, Some(&current.ty()))?;
+                let value = coerce(value, &current.ty())?;
+                self.assign(target, value)?;
+                Ok(Flow::Normal)
+            }
+            Statement::CompoundAssign {
+                target, op, value, ..
+            } => {
+                let current = self.eval_expr(target, None)?;
+                let ty = current.ty();
+                let rhs = if matches!(op, CompoundOp::Shl | CompoundOp::Shr) {
+                    self.eval_expr(value, None)?
+                } else {
+                    self.eval_expr(value, Some(&ty))?
+                };
+                let value = coerce(binary(&compound_to_binary(op), current, rhs)?, &ty)?;
+                self.assign(target, value)?;
+                Ok(Flow::Normal)
+            }
+            Statement::Expression(Expression::PostfixOp { expr, op }) => {
+                let ConstValue::Int(value, ty) = self.eval_expr(expr, None)? else {
+                    return Err(ConstEvalError::Unsupported);
+                };
+                let one = ConstValue::Int(1, ty.clone());
+                let current = ConstValue::Int(value, ty);
+                let next = match op {
+                    PostfixOp::PostInc => binary(&BinaryOp::Add, current, one)?,
+                    PostfixOp::PostDec => binary(&BinaryOp::Sub, current, one)?,
+                };
+                self.assign(expr, next)?;
+                Ok(Flow::Normal)
+            }
+            Statement::Expression(expr) => {
+                self.eval_expr(expr, None)?;
+                Ok(Flow::Normal)
+            }
+            Statement::Return { value, .. } => {
+                let return_type = self.return_types.last().cloned().flatten();
+                let value = match value {
+                    Some(expr) => Some(self.eval_expr(expr, return_type.as_ref())?),
+                    None => None,
+                };
+                Ok(Flow::Return(value))
+            }
+            Statement::Break { .. } => Ok(Flow::Break),
+            Statement::Continue { .. } => Ok(Flow::Continue),
+            Statement::If {
+                condition,
+                then_block,
+                elif_branches,
+                else_block,
+                ..
+            } => {
+                if self.eval_condition(condition)? {
+                    return self.exec_block(then_block);
+                }
+                for (condition, block) in elif_branches {
+                    if self.eval_condition(condition)? {
+                        return self.exec_block(block);
+                    }
+                }
+                match else_block {
+                    Some(block) => self.exec_block(block),
+                    None => Ok(Flow::Normal),
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                while self.eval_condition(condition)? {
+                    match self.exec_block(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Statement::Loop { body, .. } => loop {
+                match self.exec_block(body)? {
+                    Flow::Break => return Ok(Flow::Normal),
+                    Flow::Return(value) => return Ok(Flow::Return(value)),
+                    Flow::Normal | Flow::Continue => self.tick()?,
+                }
+            },
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                self.push_scope();
+                let flow =
+                    self.exec_for(init.as_deref(), condition.as_ref(), post.as_deref(), body);
+                self.pop_scope();
+                flow
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+                ..
+            } => {
+                for item in self.iteration_values(iterable)? {
+                    self.push_scope();
+                    self.declare(variable, item);
+                    let flow = self.exec_block(body);
+                    self.pop_scope();
+                    match flow? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Statement::Attributed { stmt, .. } => self.exec_statement(stmt),
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    fn exec_for(
+        &mut self,
+        init: Option<&Statement>,
+        condition: Option<&Expression>,
+        post: Option<&Statement>,
+        body: &Block,
+    ) -> EvalResult<Flow> {
+        if let Some(init) = init {
+            self.exec_statement(init)?;
+        }
+        loop {
+            if let Some(condition) = condition {
+                if !self.eval_condition(condition)? {
+                    return Ok(Flow::Normal);
+                }
+            }
+            match self.exec_block(body)? {
+                Flow::Break => return Ok(Flow::Normal),
+                Flow::Return(value) => return Ok(Flow::Return(value)),
+                Flow::Normal | Flow::Continue => {}
+            }
+            if let Some(post) = post {
+                self.exec_statement(post)?;
+            }
+        }
+    }
+
+    /// Elements of a `for x in ...` iterable: an integer range or an array
+    fn iteration_values(&mut self, iterable: &Expression) -> EvalResult<Vec<ConstValue>> {
+        let (start, end, inclusive) = match iterable {
+            Expression::Range {
+                start: Some(start),
+                end: Some(end),
+            } => (start.as_ref(), end.as_ref(), false),
+            Expression::RangeInclusive {
+                start: Some(start),
+                end: Some(end),
+            } => (start.as_ref(), end.as_ref(), true),
+            Expression::Binary {
+                left,
+                op: op @ (BinaryOp::Range | BinaryOp::RangeInclusive),
+                right,
+                ..
+            } => (
+                left.as_ref(),
+                right.as_ref(),
+                *op == BinaryOp::RangeInclusive,
+            ),
+            other => {
+                return match self.eval_expr(other, None)? {
+                    ConstValue::Array(_, items) => Ok(items),
+                    _ => Err(ConstEvalError::Unsupported),
+                }
+            }
+        };
+
+        let (start, end) = if is_untyped_literal(start) && !is_untyped_literal(end) {
+            let end = self.eval_expr(end, None)?;
+            (self.eval_expr(start, Some(&end.ty()))?, end)
+        } else {
+            let start = self.eval_expr(start, None)?;
+            let end = self.eval_expr(end, Some(&start.ty()))?;
+            (start, end)
+        };
+        let (ConstValue::Int(start, ty), Some(end)) = (start, end.as_int()) else {
+            return Err(ConstEvalError::Unsupported);
+        };
+        let end = if inclusive {
+            end.saturating_add(1)
+        } else {
+            end
+        };
+        if end.saturating_sub(start) > MAX_STEPS as i128 {
+            return Err(ConstEvalError::Failed(
+                "constant evaluation exceeded the step limit".to_string(),
+            ));
+        }
+        Ok((start..end)
+            .map(|value| ConstValue::Int(value, ty.clone()))
+            .collect())
+    }
+
+    fn eval_condition(&mut self, condition: &Expression) -> EvalResult<bool> {
+        match self.eval_expr(condition, Some(&Type::Bool))? {
+            ConstValue::Bool(value) => Ok(value),
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+
+    /// Store into a local variable or an element of a local array
+    fn assign(&mut self, target: &Expression, value: ConstValue) -> EvalResult<()> {
+        match target {
+            Expression::Ident(name) => {
+                *self.local_mut(name)? = value;
+                Ok(())
+            }
+            Expression::Index { object, index } => {
+                let Expression::Ident(name) = object.as_ref() else {
+                    return Err(ConstEvalError::Unsupported);
+                };
+                let index = self.eval_expr(index, None)?;
+                let array = self.local_mut(name)?;
+                let len = match array {
+                    ConstValue::Array(_, items) => items.len(),
+                    _ => return Err(ConstEvalError::Unsupported),
+                };
+                let slot = checked_index(len, &index)?;
+                if let ConstValue::Array(_, items) = array {
+                    items[slot] = value;
+                }
+                Ok(())
+            }
+            _ => Err(ConstEvalError::Unsupported),
+        }
+    }
+}
+
+fn overflow(verb: &str) -> ConstEvalError {
+    ConstEvalError::Failed(format!("attempt to {} with overflow", verb))
+}
+
+fn is_float(ty: &Type) -> bool {
+    matches!(ty, Type::F16 | Type::F32 | Type::F64)
+}
+
+fn is_comparison(op: &BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::Eq
+            | BinaryOp::NotEq
+            | BinaryOp::Lt
+            | BinaryOp::LtEq
+            | BinaryOp::Gt
+            | BinaryOp::GtEq
+    )
+}
+
+/// An integer literal without a suffix, whose type comes from context
+fn is_untyped_literal(expr: &Expression) -> bool {
+    match expr {
+        Expression::IntLiteral(_) | Expression::BigIntLiteral(_) => true,
+        Expression::Unary {
+            op: UnaryOp::Neg,
+            expr,
+            ..
+        } => is_untyped_literal(expr),
+        _ => false,
+    }
+}
+
+/// Inclusive range of an integer type. `u128` is limited to what `i128` holds.
+fn int_bounds(ty: &Type) -> Option<(i128, i128)> {
+    Some(match ty {
+        Type::I8 => (i8::MIN as i128, i8::MAX as i128),
+        Type::I16 => (i16::MIN as i128, i16::MAX as i128),
+        Type::I32 => (i32::MIN as i128, i32::MAX as i128),
+        Type::I64 => (i64::MIN as i128, i64::MAX as i128),
+        Type::I128 => (i128::MIN, i128::MAX),
+        Type::U8 | Type::Byte => (0, u8::MAX as i128),
+        Type::U16 => (0, u16::MAX as i128),
+        Type::U32 => (0, u32::MAX as i128),
+        Type::U64 => (0, u64::MAX as i128),
+        Type::U128 => (0, i128::MAX),
+        _ => return None,
+    })
+}
+
+fn int_bit_width(ty: &Type) -> Option<u32> {
+    Some(match ty {
+        Type::I8 | Type::U8 | Type::Byte => 8,
+        Type::I16 | Type::U16 => 16,
+        Type::I32 | Type::U32 => 32,
+        Type::I64 | Type::U64 => 64,
+        Type::I128 | Type::U128 => 128,
+        _ => return None,
+    })
+}
+
+/// `value` as `ty`, or `message` when it is out of range
+fn int_value(value: i128, ty: &Type, message: &str) -> EvalResult<ConstValue> {
+    let (min, max) = int_bounds(ty).ok_or(ConstEvalError::Unsupported)?;
+    if value > max && *ty == Type::U128 {
+        // Above i128::MAX: valid, but not representable here
+        return Err(ConstEvalError::Unsupported);
+    }
+    if value < min || value > max {
+        return Err(ConstEvalError::Failed(format!(
+            "{}: {} does not fit in `{}`",
+            message,
+            value,
+            type_name(ty)
+        )));
+    }
+    Ok(ConstValue::Int(value, ty.clone()))
+}
+
+fn int_literal(value: i128, expected: Option<&Type>) -> EvalResult<ConstValue> {
+    match expected {
+        Some(ty) if int_bounds(ty).is_some() => int_value(value, ty, "literal out of range"),
+        Some(ty) if is_float(ty) => Ok(float_value(value as f64, ty.clone())),
+        // Unsuffixed literals default to i32, widening when they don't fit
+        _ if i32::try_from(value).is_ok() => Ok(ConstValue::Int(value, Type::I32)),
+        _ => int_value(value, &Type::I64, "literal out of range"),
+    }
+}
+
+fn literal_with_suffix(value: i128, suffix: &str) -> EvalResult<ConstValue> {
+    let ty = suffix_type(suffix).ok_or(ConstEvalError::Unsupported)?;
+    if is_float(&ty) {
+        return Ok(float_value(value as f64, ty));
+    }
+    int_value(value, &ty, "literal out of range")
+}
+
+fn suffix_type(suffix: &str) -> Option<Type> {
+    Some(match suffix {
+        "i8" => Type::I8,
+        "i16" => Type::I16,
+        "i32" => Type::I32,
+        "i64" => Type::I64,
+        "i128" => Type::I128,
+        "u8" => Type::U8,
+        "u16" => Type::U16,
+        "u32" => Type::U32,
+        "u64" => Type::U64,
+        "u128" => Type::U128,
+        "f16" => Type::F16,
+        "f32" => Type::F32,
+        "f64" => Type::F64,
+        _ => return None,
+    })
+}
+
+/// Round a float to the precision of its type
+fn float_value(value: f64, ty: Type) -> ConstValue {
+    match ty {
+        Type::F64 => ConstValue::Float(value, ty),
+        _ => ConstValue::Float(value as f32 as f64, ty),
+    }
+}
+
+fn type_name(ty: &Type) -> String {
+    crate::type_checker::display_type(ty)
+}
+
+fn element_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Array(elem, _) | Type::Slice(elem, _) => Some(elem.as_ref().clone()),
+        Type::ConstArray { elem_type, .. } => Some(elem_type.as_ref().clone()),
+        _ => None,
+    }
+}
+
+/// Convert `value` to the declared type `ty`, the way an initializer does
+fn coerce(value: ConstValue, ty: &Type) -> EvalResult<ConstValue> {
+    match (value, ty) {
+        (ConstValue::Int(value, from), to) if int_bounds(to).is_some() => {
+            if from == *to {
+                return Ok(ConstValue::Int(value, from));
+            }
+            int_value(value, to, "value out of range")
+        }
+        (ConstValue::Float(value, _), to) if is_float(to) => Ok(float_value(value, to.clone())),
+        (ConstValue::Array(_, items), Type::Array(elem, len)) => {
+            if items.len() != *len {
+                return Err(ConstEvalError::Failed(format!(
+                    "expected an array of {} elements, found {}",
+                    len,
+                    items.len()
+                )));
+            }
+            let items = items
+                .into_iter()
+                .map(|item| coerce(item, elem))
+                .collect::<EvalResult<Vec<_>>>()?;
+            Ok(ConstValue::Array(elem.as_ref().clone(), items))
+        }
+        (value @ ConstValue::Bool(_), Type::Bool) => Ok(value),
+        // `str` is spelled as a named type
+        (value @ ConstValue::Str(_), Type::String) => Ok(value),
+        (value @ ConstValue::Str(_), Type::Named(name)) if name == "str" => Ok(value),
+        _ => Err(ConstEvalError::Unsupported),
+    }
+}
+
+/// `value as ty`, with the same semantics as the runtime cast
+fn cast(value: ConstValue, ty: &Type) -> EvalResult<ConstValue> {
+    match value {
+        ConstValue::Int(value, _) if int_bounds(ty).is_some() => wrap_int(value, ty),
+        ConstValue::Int(value, _) if is_float(ty) => Ok(float_value(value as f64, ty.clone())),
+        ConstValue::Bool(value) if int_bounds(ty).is_some() => {
+            Ok(ConstValue::Int(value as i128, ty.clone()))
+        }
+        ConstValue::Float(value, _) if is_float(ty) => Ok(float_value(value, ty.clone())),
+        ConstValue::Float(value, _) if int_bounds(ty).is_some() => {
+            // Saturate at the target's bounds; NaN becomes 0
+            let (min, max) = int_bounds(ty).unwrap_or((0, 0));
+            if *ty == Type::U128 && value >= i128::MAX as f64 {
+                return Err(ConstEvalError::Unsupported);
+            }
+            let truncated = if value.is_nan() { 0 } else { value as i128 };
+            Ok(ConstValue::Int(truncated.clamp(min, max), ty.clone()))
+        }
+        ConstValue::Bool(value) if *ty == Type::Bool => Ok(ConstValue::Bool(value)),
+        _ => Err(ConstEvalError::Unsupported),
+    }
+}
+
+/// Two's complement truncation or extension of `value` to `ty`
+fn wrap_int(value: i128, ty: &Type) -> EvalResult<ConstValue> {
+    let bits = int_bit_width(ty).ok_or(ConstEvalError::Unsupported)?;
+    let (min, _) = int_bounds(ty).ok_or(ConstEvalError::Unsupported)?;
+    let wrapped = if bits == 128 {
+        if *ty == Type::U128 && value < 0 {
+            return Err(ConstEvalError::Unsupported);
+        }
+        value
+    } else {
+        let modulus = 1i128 << bits;
+        let unsigned = value.rem_euclid(modulus);
+        if min < 0 && unsigned >= modulus / 2 {
+            unsigned - modulus
+        } else {
+            unsigned
+        }
+    };
+    Ok(ConstValue::Int(wrapped, ty.clone()))
+}
+
+fn binary(op: &BinaryOp, l: ConstValue, r: ConstValue) -> EvalResult<ConstValue> {
+    match (l, r) {
+        (ConstValue::Int(l, ty), ConstValue::Int(r, _)) => int_binary(op, l, r, ty),
+        (ConstValue::Float(l, ty), ConstValue::Float(r, _)) => {
+            let value = match op {
+                BinaryOp::Add => l + r,
+                BinaryOp::Sub => l - r,
+                BinaryOp::Mul => l * r,
+                BinaryOp::Div => l / r,
+                BinaryOp::Mod => l % r,
+                BinaryOp::Pow => l.powf(r),
+                _ => return compare(op, l.partial_cmp(&r)),
+            };
+            Ok(float_value(value, ty))
+        }
+        (ConstValue::Str(l), ConstValue::Str(r)) => match op {
+            BinaryOp::Add => Ok(ConstValue::Str(l + &r)),
+            _ => compare(op, Some(l.cmp(&r))),
+        },
+        (ConstValue::Bool(l), ConstValue::Bool(r)) => match op {
+            BinaryOp::BitAnd => Ok(ConstValue::Bool(l & r)),
+            BinaryOp::BitOr => Ok(ConstValue::Bool(l | r)),
+            BinaryOp::BitXor => Ok(ConstValue::Bool(l ^ r)),
+            BinaryOp::Eq | BinaryOp::NotEq => compare(op, Some(l.cmp(&r))),
+            _ => Err(ConstEvalError::Unsupported),
+        },
+        (l @ ConstValue::Array(..), r @ ConstValue::Array(..)) => match op {
+            BinaryOp::Eq => Ok(ConstValue::Bool(l == r)),
+            BinaryOp::NotEq => Ok(ConstValue::Bool(l != r)),
+            _ => Err(ConstEvalError::Unsupported),
+        },
+        _ => Err(ConstEvalError::Unsupported),
+    }
+}
+
+fn int_binary(op: &BinaryOp, l: i128, r: i128, ty: Type) -> EvalResult<ConstValue> {
+    // u128 results above i128::MAX can't be represented, so they aren't overflow errors
+    let unrepresentable = |verb: &str| {
+        if ty == Type::U128 {
+            ConstEvalError::Unsupported
+        } else {
+            overflow(verb)
+        }
+    };
+    let checked = |value: Option<i128>, verb: &str| -> EvalResult<ConstValue> {
+        let value = value.ok_or_else(|| unrepresentable(verb))?;
+        int_value(value, &ty, &format!("attempt to {} with overflow", verb))
+    };
+
+    match op {
+        BinaryOp::Add => checked(l.checked_add(r), "add"),
+        BinaryOp::Sub => checked(l.checked_sub(r), "subtract"),
+        BinaryOp::Mul => checked(l.checked_mul(r), "multiply"),
+        BinaryOp::Div => {
+            if r == 0 {
+                return Err(ConstEvalError::Failed(
+                    "attempt to divide by zero".to_string(),
+                ));
+            }
+            checked(l.checked_div(r), "divide")
+        }
+        BinaryOp::Mod => {
+            if r == 0 {
+                return Err(ConstEvalError::Failed(
+                    "attempt to calculate the remainder with a divisor of zero".to_string(),
+                ));
+            }
+            checked(l.checked_rem(r), "calculate the remainder")
+        }
+        BinaryOp::Pow => {
+            let exponent = u32::try_from(r).map_err(|_| ConstEvalError::Unsupported)?;
+            checked(l.checked_pow(exponent), "raise to a power")
+        }
+        BinaryOp::BitAnd => Ok(ConstValue::Int(l & r, ty)),
+        BinaryOp::BitOr => Ok(ConstValue::Int(l | r, ty)),
+        BinaryOp::BitXor => Ok(ConstValue::Int(l ^ r, ty)),
+        BinaryOp::Shl | BinaryOp::Shr => {
+            let bits = int_bit_width(&ty).ok_or(ConstEvalError::Unsupported)?;
+            if r < 0 || r >= bits as i128 {
+                let direction = if *op == BinaryOp::Shl {
+                    "left"
+                } else {
+                    "right"
+                };
+                return Err(overflow(&format!("shift {}", direction)));
+            }
+            if *op == BinaryOp::Shr {
+                return Ok(ConstValue::Int(l >> r, ty));
+            }
+            // Bits shifted out are discarded, as at runtime
+            wrap_int(l.wrapping_shl(r as u32), &ty)
+        }
+        _ => compare(op, Some(l.cmp(&r))),
+    }
+}
+
+fn compare(op: &BinaryOp, ordering: Option<std::cmp::Ordering>) -> EvalResult<ConstValue> {
+    use std::cmp::Ordering::*;
+    let result = match (op, ordering) {
+        (BinaryOp::Eq, ordering) => ordering == Some(Equal),
+        (BinaryOp::NotEq, ordering) => ordering != Some(Equal),
+        (BinaryOp::Lt, ordering) => ordering == Some(Less),
+        (BinaryOp::LtEq, ordering) => matches!(ordering, Some(Less | Equal)),
+        (BinaryOp::Gt, ordering) => ordering == Some(Greater),
+        (BinaryOp::GtEq, ordering) => matches!(ordering, Some(Greater | Equal)),
+        _ => return Err(ConstEvalError::Unsupported),
+    };
+    Ok(ConstValue::Bool(result))
+}
+
+fn compound_to_binary(op: &CompoundOp) -> BinaryOp {
+    match op {
+        CompoundOp::Add => BinaryOp::Add,
+        CompoundOp::Sub => BinaryOp::Sub,
+        CompoundOp::Mul => BinaryOp::Mul,
+        CompoundOp::Div => BinaryOp::Div,
+        CompoundOp::Mod => BinaryOp::Mod,
+        CompoundOp::BitAnd => BinaryOp::BitAnd,
+        CompoundOp::BitOr => BinaryOp::BitOr,
+        CompoundOp::BitXor => BinaryOp::BitXor,
+        CompoundOp::Shl => BinaryOp::Shl,
+        CompoundOp::Shr => BinaryOp::Shr,
+    }
+}
+
+fn checked_index(len: usize, index: &ConstValue) -> EvalResult<usize> {
+    let index = index.as_int().ok_or(ConstEvalError::Unsupported)?;
+    usize::try_from(index)
+        .ok()
+        .filter(|i| *i < len)
+        .ok_or_else(|| {
+            ConstEvalError::Failed(format!(
+                "index out of bounds: the length is {} but the index is {}",
+                len, index
+            ))
+        })
+}
+
+fn index_array<'v>(array: &'v ConstValue, index: &ConstValue) -> EvalResult<&'v ConstValue> {
+    match array {
+        ConstValue::Array(_, items) => Ok(&items[checked_index(items.len(), index)?]),
+        _ => Err(ConstEvalError::Unsupported),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vex_parser::Parser;
+
+    fn program(source: &str) -> Program {
+        Parser::new(source)
+            .expect("lexer failed")
+            .parse()
+            .expect("parse failed")
+    }
+
+    fn eval(source: &str, name: &str) -> EvalResult<ConstValue> {
+        let program = program(source);
+        ConstEvaluator::new(&program).eval_const(name)
+    }
+
+    fn failure(source: &str, name: &str) -> String {
+        match eval(source, name) {
+            Err(ConstEvalError::Failed(message)) => message,
+            other => panic!("expected evaluation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_folds_arithmetic_with_declared_type() {
+        let source = r#"
+            const SECOND: i64 = 1000000;
+            const MINUTE: i64 = 60 * SECOND;
+            const DAY: i64 = 24 * 60 * MINUTE;
+            const HALF: f32 = 1.0 / 2.0;
+            const MASK: u8 = (1 << 7) | 3;
+        "#;
+        assert_eq!(
+            eval(source, "DAY"),
+            Ok(ConstValue::Int(86_400_000_000, Type::I64))
+        );
+        assert_eq!(eval(source, "HALF"), Ok(ConstValue::Float(0.5, Type::F32)));
+        assert_eq!(eval(source, "MASK"), Ok(ConstValue::Int(131, Type::U8)));
+    }
+
+    #[test]
+    fn test_strings_and_arrays() {
+        let source = r#"
+            const NAME: str = "vex";
+            const GREETING: str = "hello " + NAME;
+            const PRIMES: [i32; 4] = [2, 3, 5, 7];
+            const THIRD: i32 = PRIMES[2];
+            const ZEROS: [u8; 3] = [0; 3];
+        "#;
+        assert_eq!(
+            eval(source, "GREETING"),
+            Ok(ConstValue::Str("hello vex".to_string()))
+        );
+        assert_eq!(eval(source, "THIRD"), Ok(ConstValue::Int(5, Type::I32)));
+        assert_eq!(
+            eval(source, "ZEROS").map(|v| v.ty()),
+            Ok(Type::Array(Box::new(Type::U8), 3))
+        );
+    }
+
+    #[test]
+    fn test_const_fn_calls() {
+        let source = r#"
+            const fn square(x: i64): i64 {
+                return x * x;
+            }
+            const fn factorial(n: u64): u64 {
+                let! result: u64 = 1;
+                for i in 1..=n {
+                    result *= i;
+                }
+                return result;
+            }
+            const fn fib(n: i32): i32 {
+                if n < 2 {
+                    return n;
+                }
+                return fib(n - 1) + fib(n - 2);
+            }
+            const AREA: i64 = square(12);
+            const F10: u64 = factorial(10);
+            const FIB: i32 = fib(15);
+        "#;
+        assert_eq!(eval(source, "AREA"), Ok(ConstValue::Int(144, Type::I64)));
+        assert_eq!(
+            eval(source, "F10"),
+            Ok(ConstValue::Int(3_628_800, Type::U64))
+        );
+        assert_eq!(eval(source, "FIB"), Ok(ConstValue::Int(610, Type::I32)));
+    }
+
+    #[test]
+    fn test_overflow_and_division_by_zero_are_errors() {
+        let source = r#"
+            const BIG: i32 = 2147483647;
+            const NEXT: i32 = BIG + 1;
+            const SMALL: u8 = 3 - 4;
+            const ZERO: i64 = 0;
+            const RATIO: i64 = 10 / ZERO;
+            const REM: i64 = 10 % ZERO;
+            const ITEMS: [i32; 2] = [1, 2];
+            const OUT: i32 = ITEMS[2];
+        "#;
+        assert!(failure(source, "NEXT").contains("attempt to add with overflow"));
+        assert!(failure(source, "SMALL").contains("attempt to subtract with overflow"));
+        assert_eq!(failure(source, "RATIO"), "attempt to divide by zero");
+        assert!(failure(source, "REM").contains("divisor of zero"));
+        assert!(failure(source, "OUT").contains("index out of bounds"));
+
+        // Float division by zero is well defined
+        let inf = eval("const INF: f64 = 1.0 / 0.0;", "INF");
+        assert_eq!(inf, Ok(ConstValue::Float(f64::INFINITY, Type::F64)));
+    }
+
+    #[test]
+    fn test_non_const_calls_and_cycles() {
+        let source = r#"
+            fn runtime(): i32 {
+                return 1;
+            }
+            const A: i32 = runtime();
+            const B: i32 = C + 1;
+            const C: i32 = B + 1;
+        "#;
+        assert!(matches!(
+            eval(source, "A"),
+            Err(ConstEvalError::NotConst(_))
+        ));
+
+        // A cycle is reported once, on the constant where it was detected
+        let program = program(source);
+        let results = ConstEvaluator::new(&program).eval_all();
+        let cycles: Vec<_> = results
+            .iter()
+            .filter(|(_, result)| {
+                matches!(result, Err(ConstEvalError::Failed(m)) if m.contains("cycle detected"))
+            })
+            .collect();
+        assert_eq!(cycles.len(), 1);
+    }
+
+    #[test]
+    fn test_array_length_from_constant() {
+        let source = r#"
+            const WIDTH: u32 = 4;
+            const CELLS: [i32; WIDTH * 2] = [0; WIDTH * 2];
+        "#;
+        let program = program(source);
+        let mut evaluator = ConstEvaluator::new(&program);
+        assert_eq!(
+            eval(source, "CELLS").map(|v| v.ty()),
+            Ok(Type::Array(Box::new(Type::I32), 8))
+        );
+
+        let size = Expression::Ident("WIDTH".to_string());
+        assert_eq!(evaluator.array_len(&size), Ok(4));
+    }
+
+    #[test]
+    fn test_unmodeled_expressions_fall_back() {
+        let source = r#"
+            extern "C" {
+                fn getpid(): i32;
+            }
+            const PID: i32 = getpid();
+        "#;
+        assert_eq!(eval(source, "PID"), Err(ConstEvalError::Unsupported));
+    }
+}
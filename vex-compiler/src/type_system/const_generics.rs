@@ -0,0 +1,185 @@
+//! Const generic parameters
+//!
+//! Binds the integer arguments of an instantiation (`Matrix<f32, 2, 3>`) to the
+//! owner's `const` parameters and evaluates array sizes written over them
+//! (`[T; R * C]`), so each instantiation gets its own fixed-size layout.
+
+use crate::type_checker::display_type;
+use std::collections::HashMap;
+use vex_ast::{BinaryOp, Expression, Type};
+
+/// Split generic arguments into type arguments and const arguments.
+/// Const arguments follow the type arguments: `Matrix<f32, 2, 3>`.
+pub fn split_generic_args(owner: &str, args: &[Type]) -> Result<(Vec<Type>, Vec<i64>), String> {
+    let type_count = args
+        .iter()
+        .take_while(|arg| !matches!(arg, Type::ConstValue(_)))
+        .count();
+    let (type_args, const_args) = args.split_at(type_count);
+
+    let mut values = Vec::with_capacity(const_args.len());
+    for arg in const_args {
+        match arg {
+            Type::ConstValue(value) => values.push(*value),
+            other => {
+                return Err(format!(
+                    "Type argument `{}` of `{}` must come before its const arguments",
+                    display_type(other),
+                    owner
+                ))
+            }
+        }
+    }
+    Ok((type_args.to_vec(), values))
+}
+
+/// Bind const arguments to `owner`'s const parameters, checking count and range
+pub fn bind_const_params(
+    owner: &str,
+    const_params: &[(String, Type)],
+    values: &[i64],
+) -> Result<HashMap<String, Type>, String> {
+    if const_params.len() != values.len() {
+        return Err(format!(
+            "`{}` expects {} const argument(s), got {}",
+            owner,
+            const_params.len(),
+            values.len()
+        ));
+    }
+
+    let mut bindings = HashMap::new();
+    for ((name, ty), value) in const_params.iter().zip(values) {
+        if !fits_const_type(*value, ty) {
+            return Err(format!(
+                "Const argument {} for `{}` in `{}` is out of range for `{}`",
+                value,
+                name,
+                owner,
+                display_type(ty)
+            ));
+        }
+        bindings.insert(name.clone(), Type::ConstValue(*value));
+    }
+    Ok(bindings)
+}
+
+/// Evaluate a const size expression; identifiers must be bound to `Type::ConstValue`
+pub fn eval_const_expr(expr: &Expression, bindings: &HashMap<String, Type>) -> Option<i64> {
+    match expr {
+        Expression::IntLiteral(value) => Some(*value),
+        Expression::Ident(name) => match bindings.get(name) {
+            Some(Type::ConstValue(value)) => Some(*value),
+            _ => None,
+        },
+        Expression::Binary {
+            left, op, right, ..
+        } => {
+            let l = eval_const_expr(left, bindings)?;
+            let r = eval_const_expr(right, bindings)?;
+            match op {
+                BinaryOp::Add => l.checked_add(r),
+                BinaryOp::Sub => l.checked_sub(r),
+                BinaryOp::Mul => l.checked_mul(r),
+                BinaryOp::Div => l.checked_div(r),
+                BinaryOp::Mod => l.checked_rem(r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Source form of a const size expression, for diagnostics and hover
+pub fn const_expr_to_string(expr: &Expression) -> String {
+    match expr {
+        Expression::IntLiteral(value) => value.to_string(),
+        Expression::Ident(name) => name.clone(),
+        Expression::Binary {
+            left, op, right, ..
+        } => {
+            let op = match op {
+                BinaryOp::Add => "+",
+                BinaryOp::Sub => "-",
+                BinaryOp::Mul => "*",
+                BinaryOp::Div => "/",
+                BinaryOp::Mod => "%",
+                _ => "?",
+            };
+            format!(
+                "{} {} {}",
+                const_expr_to_string(left),
+                op,
+                const_expr_to_string(right)
+            )
+        }
+        _ => "?".to_string(),
+    }
+}
+
+fn fits_const_type(value: i64, ty: &Type) -> bool {
+    match ty {
+        Type::I8 => i8::try_from(value).is_ok(),
+        Type::I16 => i16::try_from(value).is_ok(),
+        Type::I32 => i32::try_from(value).is_ok(),
+        Type::U8 => u8::try_from(value).is_ok(),
+        Type::U16 => u16::try_from(value).is_ok(),
+        Type::U32 => u32::try_from(value).is_ok(),
+        Type::U64 | Type::U128 => value >= 0,
+        Type::Named(name) if name == "usize" => value >= 0,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(name: &str) -> Box<Expression> {
+        Box::new(Expression::Ident(name.to_string()))
+    }
+
+    fn matrix_params() -> Vec<(String, Type)> {
+        let usize_ty = Type::Named("usize".to_string());
+        vec![
+            ("R".to_string(), usize_ty.clone()),
+            ("C".to_string(), usize_ty),
+        ]
+    }
+
+    #[test]
+    fn test_split_type_and_const_args() {
+        let args = vec![Type::F32, Type::ConstValue(2), Type::ConstValue(3)];
+        let (types, consts) = split_generic_args("Matrix", &args).unwrap();
+        assert_eq!(types, vec![Type::F32]);
+        assert_eq!(consts, vec![2, 3]);
+
+        let misordered = vec![Type::ConstValue(2), Type::F32];
+        assert!(split_generic_args("Matrix", &misordered).is_err());
+    }
+
+    #[test]
+    fn test_bind_checks_count_and_range() {
+        let bindings = bind_const_params("Matrix", &matrix_params(), &[2, 3]).unwrap();
+        assert_eq!(bindings.get("C"), Some(&Type::ConstValue(3)));
+
+        assert!(bind_const_params("Matrix", &matrix_params(), &[2]).is_err());
+        assert!(bind_const_params("Matrix", &matrix_params(), &[2, -1]).is_err());
+    }
+
+    #[test]
+    fn test_eval_size_expression() {
+        let bindings = bind_const_params("Matrix", &matrix_params(), &[2, 3]).unwrap();
+        let size = Expression::Binary {
+            span_id: None,
+            left: ident("R"),
+            op: BinaryOp::Mul,
+            right: ident("C"),
+        };
+        assert_eq!(eval_const_expr(&size, &bindings), Some(6));
+        assert_eq!(const_expr_to_string(&size), "R * C");
+
+        // Unbound parameters stay symbolic
+        assert_eq!(eval_const_expr(&size, &HashMap::new()), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Type-level meaning of intersection types
+//!
+//! `A & B` is read by what its members are: contracts form a conjunction that
+//! a type must satisfy in full, structs merge their fields into one record, and
+//! any other type has to agree with every other member. The checker, the trait
+//! bounds checker and codegen all classify intersections here so they agree.
+
+use crate::type_checker::display_type;
+use vex_ast::Type;
+
+/// What an intersection stands for once its member names are looked up
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntersectionShape {
+    /// Only contracts: `Reader & Writer`
+    Contracts(Vec<String>),
+    /// At least one struct: `Named & Timestamped` has the fields of both,
+    /// in member order; any contracts listed alongside must be implemented
+    Record {
+        structs: Vec<String>,
+        fields: Vec<(String, Type)>,
+        contracts: Vec<String>,
+    },
+    /// Every member is the same type: `i32 & i32` is `i32`
+    Value(Type),
+}
+
+impl IntersectionShape {
+    /// Contracts a value of this type is known to implement by construction
+    pub fn contracts(&self) -> &[String] {
+        match self {
+            IntersectionShape::Contracts(contracts)
+            | IntersectionShape::Record { contracts, .. } => contracts,
+            IntersectionShape::Value(_) => &[],
+        }
+    }
+}
+
+/// Members with nested intersections flattened and repeats removed
+pub fn flatten_members(members: &[Type]) -> Vec<Type> {
+    let mut flat: Vec<Type> = Vec::new();
+    for member in members {
+        let nested = match member {
+            Type::Intersection(inner) => flatten_members(inner),
+            other => vec![other.clone()],
+        };
+        for ty in nested {
+            if !flat.contains(&ty) {
+                flat.push(ty);
+            }
+        }
+    }
+    flat
+}
+
+/// Classify an intersection whose members are already resolved.
+///
+/// `is_contract` and `struct_fields` look member names up in the program;
+/// `struct_fields` answers only for non-generic structs. Fields declared by
+/// several structs must agree on their type.
+pub fn classify_intersection(
+    members: &[Type],
+    is_contract: impl Fn(&str) -> bool,
+    struct_fields: impl Fn(&str) -> Option<Vec<(String, Type)>>,
+) -> Result<IntersectionShape, String> {
+    let members = flatten_members(members);
+    let mut contracts = Vec::new();
+    let mut structs: Vec<String> = Vec::new();
+    // Field name, type and the struct that declared it first
+    let mut fields: Vec<(String, Type, String)> = Vec::new();
+    let mut value: Option<Type> = None;
+
+    for member in &members {
+        if let Type::Named(name) = member {
+            if is_contract(name) {
+                contracts.push(name.clone());
+                continue;
+            }
+            if let Some(declared) = struct_fields(name) {
+                for (field, ty) in declared {
+                    match fields.iter().find(|(existing, _, _)| *existing == field) {
+                        Some((_, existing_ty, owner)) if *existing_ty != ty => {
+                            return Err(format!(
+                                "field `{}` is `{}` in `{}` but `{}` in `{}`",
+                                field,
+                                display_type(existing_ty),
+                                owner,
+                                display_type(&ty),
+                                name
+                            ));
+                        }
+                        Some(_) => {}
+                        None => fields.push((field, ty, name.clone())),
+                    }
+                }
+                structs.push(name.clone());
+                continue;
+            }
+        }
+
+        match &value {
+            Some(existing) if existing != member => {
+                return Err(format!(
+                    "`{}` has no values: `{}` and `{}` never overlap",
+                    display_type(&Type::Intersection(members.clone())),
+                    display_type(existing),
+                    display_type(member)
+                ));
+            }
+            _ => value = Some(member.clone()),
+        }
+    }
+
+    match value {
+        Some(value) if structs.is_empty() && contracts.is_empty() => {
+            Ok(IntersectionShape::Value(value))
+        }
+        Some(value) => Err(format!(
+            "`{}` cannot be combined with {}; use a bound such as `T: {}` instead",
+            display_type(&value),
+            if structs.is_empty() {
+                "contracts"
+            } else {
+                "struct members"
+            },
+            contracts
+                .first()
+                .cloned()
+                .unwrap_or_else(|| structs.join(" & "))
+        )),
+        None if structs.is_empty() => Ok(IntersectionShape::Contracts(contracts)),
+        None => Ok(IntersectionShape::Record {
+            structs,
+            fields: fields.into_iter().map(|(name, ty, _)| (name, ty)).collect(),
+            contracts,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> Type {
+        Type::Named(name.to_string())
+    }
+
+    fn is_contract(name: &str) -> bool {
+        matches!(name, "Reader" | "Writer" | "Display")
+    }
+
+    fn struct_fields(name: &str) -> Option<Vec<(String, Type)>> {
+        let fields = match name {
+            "Named" => vec![("id", Type::I64), ("name", Type::String)],
+            "Timestamped" => vec![("id", Type::I64), ("created", Type::I64)],
+            "Tagged" => vec![("id", Type::String)],
+            _ => return None,
+        };
+        Some(
+            fields
+                .into_iter()
+                .map(|(field, ty)| (field.to_string(), ty))
+                .collect(),
+        )
+    }
+
+    fn classify(members: Vec<Type>) -> Result<IntersectionShape, String> {
+        classify_intersection(&members, is_contract, struct_fields)
+    }
+
+    #[test]
+    fn test_contracts_form_a_conjunction() {
+        assert_eq!(
+            classify(vec![
+                named("Reader"),
+                Type::Intersection(vec![named("Writer"), named("Reader")]),
+            ]),
+            Ok(IntersectionShape::Contracts(vec![
+                "Reader".to_string(),
+                "Writer".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_structs_merge_fields() {
+        let shape = classify(vec![named("Named"), named("Timestamped"), named("Display")]);
+        assert_eq!(
+            shape,
+            Ok(IntersectionShape::Record {
+                structs: vec!["Named".to_string(), "Timestamped".to_string()],
+                fields: vec![
+                    ("id".to_string(), Type::I64),
+                    ("name".to_string(), Type::String),
+                    ("created".to_string(), Type::I64),
+                ],
+                contracts: vec!["Display".to_string()],
+            })
+        );
+
+        let err = classify(vec![named("Named"), named("Tagged")]).unwrap_err();
+        assert!(err.contains("field `id` is `i64` in `Named`"), "{}", err);
+    }
+
+    #[test]
+    fn test_plain_types_must_agree() {
+        assert_eq!(
+            classify(vec![Type::I32, Type::I32]),
+            Ok(IntersectionShape::Value(Type::I32))
+        );
+        assert!(classify(vec![Type::I32, Type::String]).is_err());
+        assert!(classify(vec![Type::I32, named("Display")]).is_err());
+        assert!(classify(vec![named("Named"), Type::Bool]).is_err());
+    }
+}
@@ -1 +1,6 @@
 pub mod coercion_rules;
+pub mod conditional;
+pub mod const_eval;
+pub mod const_generics;
+pub mod intersection;
+pub mod object_safety;
@@ -0,0 +1,198 @@
+//! Contract objects: `dyn Contract`
+//!
+//! A contract object pairs a pointer to the value with a vtable holding one
+//! function pointer per contract method. The slot order is fixed here so the
+//! checker and codegen agree on it: inherited contract methods come first, in
+//! declaration order, followed by the contract's own methods.
+
+use crate::type_system::conditional::map_type;
+use vex_ast::{Trait, TraitMethod, Type};
+
+/// Methods in vtable slot order; a method redeclared by a subcontract keeps its first slot
+pub fn vtable_methods<'t>(
+    contract: &'t Trait,
+    lookup: &impl Fn(&str) -> Option<&'t Trait>,
+) -> Result<Vec<&'t TraitMethod>, String> {
+    let mut methods: Vec<&'t TraitMethod> = Vec::new();
+    collect_methods(contract, lookup, &mut Vec::new(), &mut methods)?;
+    Ok(methods)
+}
+
+fn collect_methods<'t>(
+    contract: &'t Trait,
+    lookup: &impl Fn(&str) -> Option<&'t Trait>,
+    visited: &mut Vec<String>,
+    methods: &mut Vec<&'t TraitMethod>,
+) -> Result<(), String> {
+    if visited.contains(&contract.name) {
+        return Ok(());
+    }
+    visited.push(contract.name.clone());
+
+    for super_name in &contract.super_traits {
+        let super_contract = lookup(super_name).ok_or_else(|| {
+            format!(
+                "Contract `{}` extends unknown contract `{}`",
+                contract.name, super_name
+            )
+        })?;
+        collect_methods(super_contract, lookup, visited, methods)?;
+    }
+    for method in &contract.methods {
+        if !methods.iter().any(|m| m.name == method.name) {
+            methods.push(method);
+        }
+    }
+    Ok(())
+}
+
+/// Reasons `contract` cannot be used as `dyn contract`; empty when it is object safe
+pub fn object_safety_violations<'t>(
+    contract: &'t Trait,
+    lookup: &impl Fn(&str) -> Option<&'t Trait>,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+    if !contract.type_params.is_empty() {
+        violations.push(format!(
+            "`{}` has type parameters; they cannot be chosen through a vtable",
+            contract.name
+        ));
+    }
+
+    let methods = match vtable_methods(contract, lookup) {
+        Ok(methods) => methods,
+        Err(message) => {
+            violations.push(message);
+            return violations;
+        }
+    };
+    for method in methods {
+        if method.params.iter().any(|p| mentions_self(&p.ty)) {
+            violations.push(format!(
+                "method `{}` takes `Self` as a parameter; the concrete type is erased",
+                method.name
+            ));
+        }
+        if method.return_type.as_ref().is_some_and(mentions_self) {
+            violations.push(format!(
+                "method `{}` returns `Self`; the concrete type is erased",
+                method.name
+            ));
+        }
+    }
+    violations
+}
+
+/// Whether a type names the implementing type: `Self`, `&Self`, `Self.Item`
+fn mentions_self(ty: &Type) -> bool {
+    let mut found = false;
+    map_type(ty, &mut |t| {
+        match t {
+            Type::SelfType => found = true,
+            Type::Named(name) if name == "Self" => found = true,
+            _ => {}
+        }
+        None
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vex_ast::Param;
+
+    fn method(name: &str, params: Vec<Type>, return_type: Option<Type>) -> TraitMethod {
+        TraitMethod {
+            name: name.to_string(),
+            span_id: None,
+            is_mutable: false,
+            is_operator: false,
+            receiver: None,
+            params: params
+                .into_iter()
+                .enumerate()
+                .map(|(i, ty)| Param {
+                    name: format!("p{}", i),
+                    ty,
+                    default_value: None,
+                })
+                .collect(),
+            return_type,
+            body: None,
+        }
+    }
+
+    fn contract(name: &str, super_traits: &[&str], methods: Vec<TraitMethod>) -> Trait {
+        Trait {
+            is_exported: false,
+            span_id: None,
+            name: name.to_string(),
+            type_params: Vec::new(),
+            super_traits: super_traits.iter().map(|s| s.to_string()).collect(),
+            associated_types: Vec::new(),
+            type_aliases: Vec::new(),
+            methods,
+        }
+    }
+
+    #[test]
+    fn test_vtable_order_puts_supercontract_methods_first() {
+        let named = contract(
+            "Named",
+            &[],
+            vec![method("name", vec![], Some(Type::String))],
+        );
+        let shape = contract(
+            "Shape",
+            &["Named"],
+            vec![
+                method("area", vec![], Some(Type::F64)),
+                method("name", vec![], Some(Type::String)),
+            ],
+        );
+        let lookup = |name: &str| (name == "Named").then_some(&named);
+
+        let slots: Vec<&str> = vtable_methods(&shape, &lookup)
+            .unwrap()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(slots, vec!["name", "area"]);
+        assert!(object_safety_violations(&shape, &lookup).is_empty());
+    }
+
+    #[test]
+    fn test_self_in_signature_is_not_object_safe() {
+        let eq = contract(
+            "Eq",
+            &[],
+            vec![method(
+                "eq",
+                vec![Type::Reference(Box::new(Type::SelfType), false)],
+                Some(Type::Bool),
+            )],
+        );
+        let clone = contract(
+            "Clone",
+            &[],
+            vec![method(
+                "clone",
+                vec![],
+                Some(Type::Named("Self".to_string())),
+            )],
+        );
+        let lookup = |_: &str| None;
+
+        assert_eq!(object_safety_violations(&eq, &lookup).len(), 1);
+        assert_eq!(object_safety_violations(&clone, &lookup).len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_supercontract_is_reported() {
+        let shape = contract("Shape", &["Missing"], vec![]);
+        let lookup = |_: &str| None;
+        assert!(vtable_methods(&shape, &lookup).is_err());
+        assert_eq!(object_safety_violations(&shape, &lookup).len(), 1);
+    }
+}
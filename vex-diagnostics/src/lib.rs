@@ -653,6 +653,9 @@ pub mod error_codes {
     pub const GENERIC_MISMATCH: &str = "E0308";
     pub const TRAIT_NOT_IMPL: &str = "E0277";
     pub const INVALID_CAST: &str = "E0606";
+    pub const IRREDUCIBLE_TYPE: &str = "E0399"; // Conditional type that cannot be reduced
+    pub const CONST_EVAL: &str = "E0080"; // Constant initializer failed to evaluate
+    pub const NON_CONST_CALL: &str = "E0015"; // Non-const fn called from a constant
 
     // Name resolution errors (E0400-E0499)
     pub const UNDEFINED_VARIABLE: &str = "E0425";
@@ -689,6 +692,8 @@ pub mod error_codes {
     pub const TRAIT_BOUNDS_NOT_MET: &str = "E0277";
     pub const CONFLICTING_IMPL: &str = "E0119";
     pub const ORPHAN_IMPL: &str = "E0117";
+    pub const CONTRACT_AS_TYPE: &str = "E0782"; // Contract intersection used as a value type
+    pub const OBJECT_UNSAFE: &str = "E0038"; // Contract cannot be used as `dyn Contract`
 
     // Warnings (W0001-W9999)
     pub const UNUSED_VARIABLE: &str = "W0001";
@@ -699,6 +704,9 @@ pub mod error_codes {
     pub const DEPRECATED: &str = "W0006";
     pub const UNREACHABLE_CODE: &str = "W0007";
     pub const INEFFICIENT_PATTERN: &str = "W0008";
+    pub const UNUSED_MUST_USE: &str = "W0009"; // #[must_use] value discarded
+    pub const UNKNOWN_ATTRIBUTE: &str = "W0010"; // Unknown, misplaced or malformed attribute
+    pub const UNUSED_RESULT: &str = "W0011"; // Result or Future call value discarded
 
     // Info messages (I0001-I9999)
     pub const TYPE_INFERENCE: &str = "I0001";
@@ -72,6 +72,7 @@ impl<'a> FormattingVisitor<'a> {
 
     /// Visit function declaration
     fn visit_function(&mut self, func: &Function) {
+        self.visit_attributes(&func.attributes);
         self.write_indent();
 
         // Async modifier
@@ -79,6 +80,10 @@ impl<'a> FormattingVisitor<'a> {
             self.write("async ");
         }
 
+        if func.is_const {
+            self.write("const ");
+        }
+
         self.write("fn ");
         self.write(&func.name);
 
@@ -128,6 +133,7 @@ impl<'a> FormattingVisitor<'a> {
 
     /// Visit struct definition
     fn visit_struct(&mut self, struct_def: &Struct) {
+        self.visit_attributes(&struct_def.attributes);
         self.write_indent();
         self.write("struct ");
         self.write(&struct_def.name);
@@ -160,6 +166,7 @@ impl<'a> FormattingVisitor<'a> {
 
         self.indent_level += 1;
         for field in &struct_def.fields {
+            self.visit_attributes(&field.attributes);
             self.write_indent();
             self.write(&field.name);
             self.write(": ");
@@ -181,6 +188,7 @@ impl<'a> FormattingVisitor<'a> {
     }
     /// Visit enum definition
     fn visit_enum(&mut self, enum_def: &Enum) {
+        self.visit_attributes(&enum_def.attributes);
         self.write_indent();
         self.write("enum ");
         self.write(&enum_def.name);
@@ -313,6 +321,39 @@ impl<'a> FormattingVisitor<'a> {
         self.write_line("}");
     }
 
+    /// Visit attributes, one per line: #[inline(always)]
+    fn visit_attributes(&mut self, attributes: &[Attribute]) {
+        for attr in attributes {
+            self.write_indent();
+            self.visit_attribute(attr);
+            self.write_line("");
+        }
+    }
+
+    /// Visit a single attribute
+    fn visit_attribute(&mut self, attr: &Attribute) {
+        self.write("#[");
+        self.write(&attr.name);
+        if !attr.args.is_empty() {
+            self.write("(");
+            for (i, arg) in attr.args.iter().enumerate() {
+                match arg {
+                    AttributeArg::Ident(name) => self.write(name),
+                    AttributeArg::Str(value) => self.write(&format!("\"{}\"", value)),
+                    AttributeArg::Int(value) => self.write(value),
+                    AttributeArg::KeyValue(key, value) => {
+                        self.write(&format!("{} = \"{}\"", key, value))
+                    }
+                }
+                if i < attr.args.len() - 1 {
+                    self.write(", ");
+                }
+            }
+            self.write(")");
+        }
+        self.write("]");
+    }
+
     /// Visit block (statement list)
     fn visit_block(&mut self, block: &Block) {
         self.write_line("{");
@@ -405,6 +446,17 @@ impl<'a> FormattingVisitor<'a> {
                 self.write("defer ");
                 self.visit_statement(&*stmt);
             }
+            Statement::Attributed { attributes, stmt } => {
+                // Already indented: first attribute goes on this line
+                for (i, attr) in attributes.iter().enumerate() {
+                    if i > 0 {
+                        self.write_indent();
+                    }
+                    self.visit_attribute(attr);
+                    self.write_line("");
+                }
+                self.visit_statement(stmt);
+            }
             Statement::Go { span_id: _, expr } => {
                 self.write("go ");
                 self.visit_expression(expr);
@@ -681,11 +733,25 @@ impl<'a> FormattingVisitor<'a> {
                 }
                 self.visit_type(inner);
             }
-            Type::Array(inner, _size) => {
+            Type::Array(inner, size) => {
                 self.write("[");
                 self.visit_type(inner);
+                self.write(&format!("; {}]", size));
+            }
+            Type::ConstArray { elem_type, size } => {
+                self.write("[");
+                self.visit_type(elem_type);
+                self.write("; ");
+                self.visit_expression(size);
                 self.write("]");
             }
+            Type::ConstValue(value) => {
+                self.write(&value.to_string());
+            }
+            Type::Dyn(contract) => {
+                self.write("dyn ");
+                self.write(contract);
+            }
             Type::Generic { name, type_args } => {
                 self.write(name);
                 self.write("<");
@@ -744,4 +810,23 @@ mod tests {
         let visitor = FormattingVisitor::new(&config);
         assert_eq!(visitor.indent_level, 0);
     }
+
+    #[test]
+    fn test_attributes_are_kept() {
+        let source = r#"
+#[inline(always)] #[deprecated(since = "0.3", note = "old")]
+fn f(): i32 {
+    #[trace] return 1;
+}
+struct P {
+    #[deprecated("use y")] x: i32,
+}
+"#;
+        let formatted = crate::format_with_defaults(source).unwrap();
+        assert!(formatted.contains(
+            "#[inline(always)]\n#[deprecated(since = \"0.3\", note = \"old\")]\nfn f()"
+        ));
+        assert!(formatted.contains("    #[trace]\n    return 1;"));
+        assert!(formatted.contains("    #[deprecated(\"use y\")]\n    x: i32,"));
+    }
 }
@@ -66,6 +66,8 @@ pub enum Token {
     Async,
     #[token("await")]
     Await,
+    #[token("yield")]
+    Yield,
     #[token("go")]
     Go,
     #[token("gpu")]
@@ -104,6 +106,10 @@ pub enum Token {
     From,
     #[token("as")]
     As,
+    #[token("is")]
+    Is,
+    #[token("dyn")]
+    Dyn,
     #[token("true")]
     True,
     #[token("false")]
@@ -260,6 +266,8 @@ pub enum Token {
     FatArrow,
     #[token("...")]
     DotDotDot,
+    #[token("#")]
+    Hash, // Attribute marker: #[inline]
 
     // Advanced operators
     #[token("**")]
@@ -419,7 +427,7 @@ mod tests {
 
     #[test]
     fn test_keywords() {
-        let source = "fn let struct async await contract";
+        let source = "fn let struct async await yield contract";
         let mut lexer = Lexer::new(source);
 
         assert_eq!(lexer.next().unwrap().unwrap().token, Token::Fn);
@@ -427,6 +435,7 @@ mod tests {
         assert_eq!(lexer.next().unwrap().unwrap().token, Token::Struct);
         assert_eq!(lexer.next().unwrap().unwrap().token, Token::Async);
         assert_eq!(lexer.next().unwrap().unwrap().token, Token::Await);
+        assert_eq!(lexer.next().unwrap().unwrap().token, Token::Yield);
         assert_eq!(lexer.next().unwrap().unwrap().token, Token::Contract);
     }
 
@@ -130,6 +130,8 @@ pub struct VexBackend {
     pub documents: Arc<DashMap<String, Arc<String>>>,
     /// Parsed AST cache (Arc for cheap cloning)
     pub ast_cache: Arc<DashMap<String, Arc<vex_ast::Program>>>,
+    /// Type checker results per document, used for hover
+    pub type_table_cache: Arc<DashMap<String, Arc<vex_compiler::TypeTable>>>,
     /// Debounce tasks for did_change events (prevents UI freezing)
     pub debounce_tasks: Arc<RwLock<HashMap<String, tokio::task::JoinHandle<()>>>>,
     /// Module resolver for import path resolution
@@ -152,6 +154,7 @@ impl VexBackend {
             document_cache: Arc::new(DocumentCache::new()),
             documents: Arc::new(DashMap::new()),
             ast_cache: Arc::new(DashMap::new()),
+            type_table_cache: Arc::new(DashMap::new()),
             debounce_tasks: Arc::new(RwLock::new(HashMap::new())),
             module_resolver: Arc::new(resolver),
             workspace_root,
@@ -285,6 +288,7 @@ impl LanguageServer for VexBackend {
         // Clear all caches
         self.documents.clear();
         self.ast_cache.clear();
+        self.type_table_cache.clear();
         self.module_resolver.clear_cache();
 
         tracing::info!("LSP server shutdown complete");
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use tower_lsp::lsp_types::*;
 use vex_compiler::borrow_checker::BorrowChecker;
 use vex_compiler::linter::Linter;
+use vex_compiler::type_checker::TypeChecker;
 
 use super::VexBackend;
 
@@ -30,9 +31,23 @@ impl VexBackend {
             self.validate_imports(&program, text, uri, &mut diagnostics)
                 .await;
 
+            // Type check: E0308/E0599/E0609 and the type table used by hover.
+            // Attribute warnings (#[deprecated], #[must_use]) are reported even when it passes.
+            let mut type_checker = TypeChecker::new(&cached_doc.span_map);
+            let _ = type_checker.check_program(&program);
+            for vex_diag in type_checker.diagnostics().diagnostics() {
+                let mut lsp_diag = vex_to_lsp_diagnostic(vex_diag);
+                if vex_diag.level == vex_diagnostics::ErrorLevel::Warning {
+                    lsp_diag.severity = Some(DiagnosticSeverity::WARNING);
+                }
+                lsp_diag.source = Some("vex-typeck".to_string());
+                diagnostics.push(lsp_diag);
+            }
+
             // Run linter for warnings (unused variables, etc.) - this is fast
             let mut linter = Linter::new();
-            let lint_warnings = linter.lint(&program, &cached_doc.span_map);
+            let lint_warnings =
+                linter.lint_typed(&program, &cached_doc.span_map, type_checker.type_table());
             for vex_diag in &lint_warnings {
                 let mut lsp_diag = vex_to_lsp_diagnostic(vex_diag);
                 lsp_diag.severity = Some(DiagnosticSeverity::WARNING);
@@ -55,6 +70,8 @@ impl VexBackend {
 
             // Store in legacy cache for compatibility (wrap in Arc)
             self.ast_cache.insert(uri.to_string(), Arc::new(program));
+            self.type_table_cache
+                .insert(uri.to_string(), Arc::new(type_checker.into_type_table()));
         }
 
         diagnostics
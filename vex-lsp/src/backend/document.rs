@@ -13,6 +13,7 @@ impl VexBackend {
             document_cache: Arc::clone(&self.document_cache),
             documents: Arc::clone(&self.documents),
             ast_cache: Arc::clone(&self.ast_cache),
+            type_table_cache: Arc::clone(&self.type_table_cache),
             debounce_tasks: Arc::clone(&self.debounce_tasks),
             module_resolver: Arc::clone(&self.module_resolver),
             workspace_root: self.workspace_root.clone(),
@@ -119,6 +120,7 @@ impl VexBackend {
         // Remove from all caches
         self.documents.remove(&uri);
         self.ast_cache.remove(&uri);
+        self.type_table_cache.remove(&uri);
         self.document_cache.remove(&uri);
     }
 }
@@ -71,6 +71,7 @@ impl VexBackend {
             ("select", CompletionItemKind::KEYWORD, "select statement"),
             ("async", CompletionItemKind::KEYWORD, "async function"),
             ("await", CompletionItemKind::KEYWORD, "await expression"),
+            ("yield", CompletionItemKind::KEYWORD, "yield expression"),
             ("go", CompletionItemKind::KEYWORD, "goroutine"),
             ("gpu", CompletionItemKind::KEYWORD, "GPU launch"),
             ("launch", CompletionItemKind::KEYWORD, "launch keyword"),
@@ -78,6 +79,8 @@ impl VexBackend {
             ("import", CompletionItemKind::KEYWORD, "import module"),
             ("from", CompletionItemKind::KEYWORD, "from import"),
             ("as", CompletionItemKind::KEYWORD, "as keyword"),
+            ("is", CompletionItemKind::KEYWORD, "union member test"),
+            ("dyn", CompletionItemKind::KEYWORD, "contract object type"),
             ("with", CompletionItemKind::KEYWORD, "with clause"),
         ];
 
@@ -188,10 +191,29 @@ impl VexBackend {
 
                         // Suggest enum variants
                         for variant in &e.variants {
+                            // Struct-like variants expand to their named fields
+                            let insert_text = variant.is_struct_like().then(|| {
+                                let fields = variant
+                                    .field_names
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, name)| format!("{}: ${}", name, i + 1))
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+                                format!("{} {{ {} }}", variant.name, fields)
+                            });
                             items.push(CompletionItem {
                                 label: variant.name.clone(),
                                 kind: Some(CompletionItemKind::ENUM_MEMBER),
-                                detail: Some(format!("{}::{}", e.name, variant.name)),
+                                detail: Some(format!(
+                                    "{}::{}",
+                                    e.name,
+                                    enum_variant_signature(variant)
+                                )),
+                                insert_text_format: insert_text
+                                    .as_ref()
+                                    .map(|_| InsertTextFormat::SNIPPET),
+                                insert_text,
                                 ..Default::default()
                             });
                         }
@@ -110,6 +110,21 @@ impl VexBackend {
                                 // Format multi-value tuple variant types
                                 let detail = if variant.data.is_empty() {
                                     None
+                                } else if variant.is_struct_like() {
+                                    Some(format!(
+                                        "{{ {} }}",
+                                        variant
+                                            .field_names
+                                            .iter()
+                                            .zip(&variant.data)
+                                            .map(|(name, t)| format!(
+                                                "{}: {}",
+                                                name,
+                                                type_to_string(t)
+                                            ))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    ))
                                 } else if variant.data.len() == 1 {
                                     Some(type_to_string(&variant.data[0]))
                                 } else {
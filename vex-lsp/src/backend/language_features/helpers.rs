@@ -369,3 +369,28 @@ pub fn type_to_string(ty: &vex_ast::Type) -> String {
         _ => format!("{:?}", ty), // Fallback for unhandled variants
     }
 }
+
+/// Enum variant as written in its declaration:
+/// `Empty`, `Circle(f64)`, `Label(string, i32)` or `Rect { w: f64, h: f64 }`
+pub fn enum_variant_signature(variant: &vex_ast::EnumVariant) -> String {
+    if variant.data.is_empty() {
+        variant.name.clone()
+    } else if variant.is_struct_like() {
+        let fields = variant
+            .field_names
+            .iter()
+            .zip(&variant.data)
+            .map(|(name, ty)| format!("{}: {}", name, type_to_string(ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{} {{ {} }}", variant.name, fields)
+    } else {
+        let types = variant
+            .data
+            .iter()
+            .map(type_to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({})", variant.name, types)
+    }
+}
@@ -125,6 +125,16 @@ impl VexBackend {
                     }));
                 }
             }
+            // Local variables and parameters: show the type the checker resolved
+            if let Some(hover_info) = self.find_local_hover_info(&uri, &text, position, &word) {
+                return Ok(Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: hover_info,
+                    }),
+                    range: None,
+                }));
+            }
             // Fallback: show the word that was found
             Ok(Some(Hover {
                 contents: HoverContents::Markup(MarkupContent {
@@ -188,6 +198,29 @@ impl VexBackend {
         None
     }
 
+    fn find_local_hover_info(
+        &self,
+        uri: &str,
+        text: &str,
+        position: Position,
+        name: &str,
+    ) -> Option<String> {
+        let table = Arc::clone(self.type_table_cache.get(uri)?.value());
+        // LSP positions are 0-based, checker spans 1-based
+        let function = table.function_at(
+            text,
+            position.line as usize + 1,
+            position.character as usize + 1,
+        )?;
+        let ty = table.local_type(function, name)?;
+
+        Some(format!(
+            "```vex\nlet {}: {}\n```\n\n*Vex local*",
+            name,
+            vex_compiler::type_checker::display_type(ty)
+        ))
+    }
+
     fn find_method_hover_info(
         &self,
         ast: &vex_ast::Program,
@@ -221,7 +254,11 @@ impl VexBackend {
     }
 
     fn format_function_hover(&self, func: &vex_ast::Function) -> String {
-        let async_str = if func.is_async { "async " } else { "" };
+        let qualifier_str = match (func.is_async, func.is_const) {
+            (true, _) => "async ",
+            (false, true) => "const ",
+            (false, false) => "",
+        };
         let params_str = func
             .params
             .iter()
@@ -235,8 +272,12 @@ impl VexBackend {
             .unwrap_or_else(|| "".to_string());
 
         format!(
-            "```vex\n{}fn {}({}){}\n```\n\n*Vex function*",
-            async_str, func.name, params_str, return_str
+            "```vex\n{}fn {}({}){}\n```\n\n*Vex function*{}",
+            qualifier_str,
+            func.name,
+            params_str,
+            return_str,
+            deprecation_note(&func.attributes)
         )
     }
 
@@ -249,8 +290,10 @@ impl VexBackend {
             .join("\n");
 
         format!(
-            "```vex\nstruct {} {{\n{}\n}}\n```\n\n*Vex struct*",
-            s.name, fields_str
+            "```vex\nstruct {} {{\n{}\n}}\n```\n\n*Vex struct*{}",
+            s.name,
+            fields_str,
+            deprecation_note(&s.attributes)
         )
     }
 
@@ -261,6 +304,15 @@ impl VexBackend {
             .map(|v| {
                 if v.data.is_empty() {
                     format!("  {}", v.name)
+                } else if v.is_struct_like() {
+                    let fields_str = v
+                        .field_names
+                        .iter()
+                        .zip(&v.data)
+                        .map(|(name, ty)| format!("{}: {}", name, self.type_to_string(ty)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("  {} {{ {} }}", v.name, fields_str)
                 } else {
                     let data_str = v
                         .data
@@ -275,8 +327,10 @@ impl VexBackend {
             .join("\n");
 
         format!(
-            "```vex\nenum {} {{\n{}\n}}\n```\n\n*Vex enum*",
-            e.name, variants_str
+            "```vex\nenum {} {{\n{}\n}}\n```\n\n*Vex enum*{}",
+            e.name,
+            variants_str,
+            deprecation_note(&e.attributes)
         )
     }
 
@@ -351,12 +405,14 @@ impl VexBackend {
             vex_ast::Type::Array(elem, size) => {
                 format!("[{}; {}]", self.type_to_string(elem), size)
             }
-            vex_ast::Type::ConstArray {
-                elem_type,
-                size_param,
-            } => {
-                format!("[{}; {}]", self.type_to_string(elem_type), size_param)
+            vex_ast::Type::ConstArray { elem_type, size } => {
+                format!(
+                    "[{}; {}]",
+                    self.type_to_string(elem_type),
+                    vex_compiler::type_system::const_generics::const_expr_to_string(size)
+                )
             }
+            vex_ast::Type::ConstValue(value) => value.to_string(),
             vex_ast::Type::Slice(elem, is_mutable) => {
                 let mut_str = if *is_mutable { "mut " } else { "" };
                 format!("&{}{}[]", mut_str, self.type_to_string(elem))
@@ -409,6 +465,7 @@ impl VexBackend {
             }
             vex_ast::Type::Infer(name) => format!("infer {}", name),
             vex_ast::Type::Typeof(_) => "typeof(...)".to_string(),
+            vex_ast::Type::Dyn(contract) => format!("dyn {}", contract),
             vex_ast::Type::Unit => "()".to_string(),
             vex_ast::Type::Never => "!".to_string(),
             vex_ast::Type::Any => "any".to_string(),
@@ -442,3 +499,14 @@ impl VexBackend {
         }
     }
 }
+
+/// Hover footer for items marked #[deprecated]
+fn deprecation_note(attributes: &[vex_ast::Attribute]) -> String {
+    match vex_ast::find_attribute(attributes, "deprecated") {
+        Some(attr) => match attr.message() {
+            Some(note) => format!("\n\n**Deprecated**: {}", note),
+            None => "\n\n**Deprecated**".to_string(),
+        },
+        None => String::new(),
+    }
+}
@@ -76,18 +76,28 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    /// Parse type cast: expr as TargetType
+    /// Parse type cast: expr as TargetType, and union member test: expr is Type
     /// Priority: Higher than unary, lower than multiplicative
     pub(crate) fn parse_cast(&mut self) -> Result<Expression, ParseError> {
         let mut expr = self.parse_unary()?;
 
-        while self.check(&Token::As) {
-            self.advance(); // Consume 'as'
-            let target_type = self.parse_type()?;
-            expr = Expression::Cast {
-                expr: Box::new(expr),
-                target_type,
-            };
+        loop {
+            if self.match_token(&Token::As) {
+                let target_type = self.parse_type()?;
+                expr = Expression::Cast {
+                    expr: Box::new(expr),
+                    target_type,
+                };
+            } else if self.match_token(&Token::Is) {
+                // parse_type_primary keeps `x is A | B` from swallowing the `|` operator
+                let target_type = self.parse_type_primary()?;
+                expr = Expression::Is {
+                    expr: Box::new(expr),
+                    target_type,
+                };
+            } else {
+                break;
+            }
         }
 
         Ok(expr)
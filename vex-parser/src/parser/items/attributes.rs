@@ -0,0 +1,118 @@
+use crate::parser::Parser;
+use crate::ParseError;
+use vex_ast::*;
+use vex_lexer::Token;
+
+impl<'a> Parser<'a> {
+    /// Parse consecutive attributes: #[inline(always)] #[deprecated("use parse")]
+    pub(crate) fn parse_attributes(&mut self) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes = Vec::new();
+        while self.check(&Token::Hash) {
+            let start = self.current;
+            self.advance(); // consume '#'
+            self.consume(&Token::LBracket, "Expected '[' after '#'")?;
+            let name = self.consume_identifier()?;
+
+            let mut args = Vec::new();
+            if self.match_token(&Token::LParen) {
+                while !self.check(&Token::RParen) && !self.is_at_end() {
+                    args.push(self.parse_attribute_arg()?);
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.consume(&Token::RParen, "Expected ')' after attribute arguments")?;
+            }
+            self.consume(&Token::RBracket, "Expected ']' to close attribute")?;
+
+            let span = crate::Span::from_file_and_span(
+                &self.file_name,
+                self.source,
+                self.tokens[start].span.start..self.tokens[self.current - 1].span.end,
+            );
+            let span_id = self.span_map.generate_id();
+            self.span_map.record(span_id.clone(), span);
+
+            attributes.push(Attribute {
+                span_id: Some(span_id),
+                name,
+                args,
+            });
+        }
+        Ok(attributes)
+    }
+
+    fn parse_attribute_arg(&mut self) -> Result<AttributeArg, ParseError> {
+        match self.peek().clone() {
+            Token::Ident(key) => {
+                self.advance();
+                if !self.match_token(&Token::Eq) {
+                    return Ok(AttributeArg::Ident(key));
+                }
+                match self.peek().clone() {
+                    Token::StringLiteral(value) => {
+                        self.advance();
+                        Ok(AttributeArg::KeyValue(key, value))
+                    }
+                    _ => Err(self.make_syntax_error(
+                        "Expected string literal after '=' in attribute",
+                        Some("expected string literal"),
+                        None,
+                        Some(("try a string", "note = \"reason\"")),
+                    )),
+                }
+            }
+            Token::StringLiteral(value) => {
+                self.advance();
+                Ok(AttributeArg::Str(value))
+            }
+            Token::IntLiteral(value) => {
+                self.advance();
+                Ok(AttributeArg::Int(value))
+            }
+            _ => Err(self.make_syntax_error(
+                "Expected attribute argument",
+                Some("expected identifier, literal or 'key = \"value\"'"),
+                Some("Attribute arguments look like: #[inline(always)], #[deprecated(\"reason\")]"),
+                None,
+            )),
+        }
+    }
+
+    /// Attributes may precede a function, struct or enum, optionally exported
+    pub(crate) fn check_attribute_target(&self) -> Result<(), ParseError> {
+        let mut index = self.current;
+        if matches!(
+            self.tokens.get(index).map(|t| &t.token),
+            Some(Token::Export)
+        ) {
+            index += 1;
+        }
+        if matches!(
+            self.tokens.get(index).map(|t| &t.token),
+            Some(Token::Async | Token::Const)
+        ) {
+            index += 1;
+        }
+        match self.tokens.get(index).map(|t| &t.token) {
+            Some(Token::Fn | Token::Struct | Token::Enum) => Ok(()),
+            _ => Err(self.make_syntax_error(
+                "Attributes can only be applied to functions, structs, enums, fields and statements",
+                Some("expected 'fn', 'struct' or 'enum' after attributes"),
+                None,
+                None,
+            )),
+        }
+    }
+
+    /// Store parsed attributes on the item that follows them
+    pub(crate) fn attach_attributes(item: &mut Item, attributes: Vec<Attribute>) {
+        match item {
+            Item::Function(func) => func.attributes = attributes,
+            Item::Struct(s) => s.attributes = attributes,
+            Item::Enum(e) => e.attributes = attributes,
+            // Rejected by check_attribute_target before the item is parsed
+            _ => {}
+        }
+    }
+}
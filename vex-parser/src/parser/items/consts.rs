@@ -7,6 +7,13 @@ impl<'a> Parser<'a> {
     pub(crate) fn parse_const(&mut self) -> Result<Item, ParseError> {
         self.consume(&Token::Const, "Expected 'const'")?;
 
+        // const fn: a function that constant initializers may call
+        if self.match_token(&Token::Fn) {
+            let mut func = self.parse_function()?;
+            func.is_const = true;
+            return Ok(Item::Function(func));
+        }
+
         // Capture span for the const name
         let span = self.token_to_diag_span(&self.peek_span().span);
         let span_id = self.span_map.generate_id();
@@ -26,9 +26,33 @@ impl<'a> Parser<'a> {
                 break;
             }
             let variant_name = self.consume_identifier()?;
+            let mut field_names = Vec::new();
 
             // Check for tuple data: None, Some(T), V4(u8, u8, u8, u8)
-            let data = if self.match_token(&Token::LParen) {
+            // or named fields: Rect { x: f64, y: f64 }
+            let data = if self.match_token(&Token::LBrace) {
+                let mut types = Vec::new();
+
+                while !self.check(&Token::RBrace) && !self.is_at_end() {
+                    let field_name = self.consume_identifier()?;
+                    if field_names.contains(&field_name) {
+                        return Err(self.error(&format!(
+                            "Duplicate field '{}' in variant '{}'",
+                            field_name, variant_name
+                        )));
+                    }
+                    self.consume(&Token::Colon, "Expected ':' after field name")?;
+                    types.push(self.parse_type()?);
+                    field_names.push(field_name);
+
+                    if !self.match_token(&Token::Comma) {
+                        break;
+                    }
+                }
+
+                self.consume(&Token::RBrace, "Expected '}' after variant fields")?;
+                types
+            } else if self.match_token(&Token::LParen) {
                 let mut types = Vec::new();
 
                 // Parse comma-separated types
@@ -50,6 +74,7 @@ impl<'a> Parser<'a> {
             variants.push(EnumVariant {
                 name: variant_name,
                 data,
+                field_names,
             });
 
             if !self.match_token(&Token::Comma) {
@@ -62,6 +87,7 @@ impl<'a> Parser<'a> {
         Ok(Item::Enum(Enum {
             is_exported: false, // Default to false
             span_id: Some(span_id),
+            attributes: Vec::new(), // Set by the caller that parsed them
             name,
             type_params,
             variants,
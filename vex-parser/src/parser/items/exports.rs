@@ -199,10 +199,12 @@ impl<'a> Parser<'a> {
                 Ok(Item::Function(func))
             }
         } else if self.check(&Token::Const) {
-            // Pattern 2: export const X = 5;
+            // Pattern 2: export const X = 5; / export const fn f() {}
             let mut item = self.parse_const()?;
-            if let Item::Const(ref mut c) = item {
-                c.is_exported = true;
+            match item {
+                Item::Const(ref mut c) => c.is_exported = true,
+                Item::Function(ref mut f) => f.is_exported = true,
+                _ => {}
             }
             Ok(item)
         } else if self.check(&Token::Struct) {
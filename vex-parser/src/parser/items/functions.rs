@@ -120,10 +120,12 @@ impl<'a> Parser<'a> {
         self.in_method_body = was_in_method;
 
         Ok(Function {
-            is_exported: false, // Default to false, set to true by parse_export
-            span_id,            // ⭐ Captured span ID
+            is_exported: false,     // Default to false, set to true by parse_export
+            span_id,                // ⭐ Captured span ID
+            attributes: Vec::new(), // Set by the caller that parsed them
             is_async: false,
             is_gpu: false,
+            is_const: false,
             is_mutable,        // ⭐ NEW: Store mutability flag
             is_operator,       // ⭐ NEW: Store operator flag
             is_static: false,  // ⭐ NEW: Set by caller for static methods
@@ -165,7 +167,7 @@ impl<'a> Parser<'a> {
                     let bound_name = self.consume_identifier()?;
                     bounds.push(TraitBound::Simple(bound_name));
 
-                    if !self.match_token(&Token::Plus) {
+                    if !self.match_bound_separator() {
                         break;
                     }
                 }
@@ -214,7 +216,7 @@ impl<'a> Parser<'a> {
                         bounds.push(TraitBound::Simple(bound_name));
                     }
 
-                    if !self.match_token(&Token::Plus) {
+                    if !self.match_bound_separator() {
                         break;
                     }
                 }
@@ -1,6 +1,7 @@
 // Item parsing (struct, enum, function, trait, const, import, export)
 
 mod aliases;
+mod attributes;
 mod consts;
 mod enums;
 mod exports;
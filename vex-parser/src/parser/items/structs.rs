@@ -86,6 +86,9 @@ impl<'a> Parser<'a> {
             if self.guard_tick(&mut steps, "struct body parse timeout", Self::PARSE_LOOP_DEFAULT_MAX_STEPS) {
                 break;
             }
+            // Field and method attributes: #[deprecated] x: i32
+            let attributes = self.parse_attributes()?;
+
             // Check if this is a method (fn keyword - DEPRECATED), associated type (type keyword), or field
             if self.check(&Token::Fn) {
                 // ⚠️ DEPRECATED: Inline struct methods are deprecated!
@@ -98,8 +101,16 @@ impl<'a> Parser<'a> {
                 eprintln!("   → fn (self: &{}) method_name() {{ }}", name);
                 eprintln!("   → See VEX_IDENTITY.md for migration guide");
 
-                methods.push(self.parse_struct_method()?);
+                methods.push(self.parse_struct_method(attributes)?);
             } else if self.check(&Token::Type) {
+                if !attributes.is_empty() {
+                    return Err(self.make_syntax_error(
+                        "Attributes cannot be applied to associated types",
+                        Some("expected field or method after attributes"),
+                        None,
+                        None,
+                    ));
+                }
                 // ⭐ NEW: Parse associated type binding: type Item = i32;
                 self.advance(); // consume 'type'
                 let type_name = self.consume_identifier()?;
@@ -129,7 +140,7 @@ impl<'a> Parser<'a> {
                 .with_note("See docs/REFERENCE.md (Method Definitions & Calls) for migration guide".to_string());
 
                 self.diagnostics.push(warning);
-                methods.push(self.parse_struct_method()?);
+                methods.push(self.parse_struct_method(attributes)?);
             } else if matches!(self.peek(), Token::OperatorMethod(_)) {
                 // ⭐ Emit deprecation warning for operator methods inside struct body
                 let span = self.token_to_diag_span(&self.peek_span().span);
@@ -146,7 +157,7 @@ impl<'a> Parser<'a> {
                 .with_note("See docs/REFERENCE.md (Method Definitions & Calls) for migration guide".to_string());
 
                 self.diagnostics.push(warning);
-                methods.push(self.parse_struct_method()?);
+                methods.push(self.parse_struct_method(attributes)?);
             } else if matches!(self.peek(), Token::Ident(s) if s == "op") {
                 // ⭐ NEW: Bare "op" identifier for constructor operator method
                 let span = self.token_to_diag_span(&self.peek_span().span);
@@ -166,7 +177,7 @@ impl<'a> Parser<'a> {
                 );
 
                 self.diagnostics.push(warning);
-                methods.push(self.parse_struct_method()?);
+                methods.push(self.parse_struct_method(attributes)?);
             } else if matches!(self.peek(), Token::Ident(_)) {
                 // Could be a method without 'fn' or a field - need to disambiguate
                 // Look ahead: if next token is '(' it's a method, if ':' it's a field
@@ -193,7 +204,7 @@ impl<'a> Parser<'a> {
                     );
 
                     self.diagnostics.push(warning);
-                    methods.push(self.parse_struct_method()?);
+                    methods.push(self.parse_struct_method(attributes)?);
                 } else if self.check(&Token::Colon) {
                     // It's a field - continue with field parsing
                     self.advance(); // consume ':'
@@ -215,6 +226,7 @@ impl<'a> Parser<'a> {
                         ty: field_type,
                         tag: None,
                         metadata, // Inline backtick metadata
+                        attributes,
                     });
 
                     if !self.match_token(&Token::Comma) {
@@ -243,6 +255,7 @@ impl<'a> Parser<'a> {
         Ok(Item::Struct(Struct {
             is_exported: false,     // Default to false
             span_id: Some(span_id), // ⭐ NEW: Source location ID
+            attributes: Vec::new(), // Set by the caller that parsed them
             name,
             type_params,
             const_params,
@@ -260,7 +273,10 @@ impl<'a> Parser<'a> {
     /// Supports two syntaxes:
     /// 1. Golang-style: fn (self: &Type) method_name(...)
     /// 2. Simplified: fn method_name(...) - receiver auto-detected from body
-    fn parse_struct_method(&mut self) -> Result<Function, ParseError> {
+    fn parse_struct_method(
+        &mut self,
+        attributes: Vec<Attribute>,
+    ) -> Result<Function, ParseError> {
         // ⭐ NEW: 'fn' keyword is now OPTIONAL in struct methods
         let _has_fn_keyword = self.match_token(&Token::Fn);
 
@@ -351,6 +367,9 @@ impl<'a> Parser<'a> {
             }
         };
 
+        // Method type and const parameters: fn get<const I: usize>()
+        let (type_params, const_params) = self.parse_type_params()?;
+
         // Parameters: (param1: T1, param2: T2)
         self.consume(&Token::LParen, "Expected '('")?;
         let params = self.parse_parameters()?;
@@ -377,16 +396,18 @@ impl<'a> Parser<'a> {
         Ok(Function {
             is_exported: false, // Struct methods are not exported individually
             span_id,            // ⭐ NEW: Source location ID
+            attributes,
             is_async: false,
             is_gpu: false,
+            is_const: false,
             is_mutable,       // ⭐ NEW: Store mutability flag
             is_operator,      // ⭐ NEW: Store operator flag
             is_static: false, // ⭐ Struct methods are never static (use external static methods)
             static_type: None,
             receiver,
             name,
-            type_params: Vec::new(),
-            const_params: vec![],     // ⭐ TODO: Parse const params
+            type_params,
+            const_params,
             where_clause: Vec::new(), // Struct inline methods don't support where clauses yet
             params,
             return_type,
@@ -118,6 +118,13 @@ impl<'a> Parser<'a> {
             ) {
                 break;
             }
+            // Attributes apply to the function, struct or enum that follows
+            let attributes = self.parse_attributes()?;
+            if !attributes.is_empty() {
+                self.check_attribute_target()?;
+            }
+            let item_count = items.len();
+
             // Parse top-level items
             if self.check(&Token::Import) {
                 imports.push(self.parse_import()?);
@@ -218,6 +225,12 @@ impl<'a> Parser<'a> {
                     Some(("try 'fn'", "fn main() { }")),
                 ));
             }
+
+            if !attributes.is_empty() {
+                if let Some(item) = items.get_mut(item_count) {
+                    Self::attach_attributes(item, attributes);
+                }
+            }
         }
         Ok(Program { imports, items })
     }
@@ -521,9 +534,30 @@ impl<'a> Parser<'a> {
                 && !self.check(&Token::Continue)
                 && !self.check(&Token::Switch)
                 && !self.check(&Token::Defer)
+                && !self.check(&Token::Hash)
             {
                 // Try to parse expression
                 if let Ok(expr) = self.parse_expression() {
+                    // Assignment target: `count = count + 1;` is a statement
+                    let is_assignment = matches!(
+                        self.peek(),
+                        Token::Eq
+                            | Token::PlusEq
+                            | Token::MinusEq
+                            | Token::StarEq
+                            | Token::SlashEq
+                            | Token::PercentEq
+                            | Token::AmpersandEq
+                            | Token::PipeEq
+                            | Token::CaretEq
+                            | Token::LShiftEq
+                            | Token::RShiftEq
+                    );
+                    if is_assignment {
+                        self.current = checkpoint;
+                        statements.push(self.parse_statement()?);
+                        continue;
+                    }
                     // If no semicolon follows and we're at closing brace, this is the return expr
                     if !self.match_token(&Token::Semicolon) && self.check(&Token::RBrace) {
                         return_expr = Some(Box::new(expr));
@@ -584,7 +618,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse generic type parameters with optional trait bounds and const params
-    /// Examples: <T>, <T: Display>, <const N: usize>, <T, const N: usize, const M: i32>
+    /// Examples: <T>, <T: Display>, <T: Reader & Writer>, <const N: usize>, <T, const N: usize>
     /// Closure traits: <F: Callable(i32): i32>, <F: CallableMut(T, U): bool>
     pub(crate) fn parse_type_params(
         &mut self,
@@ -607,7 +641,7 @@ impl<'a> Parser<'a> {
                 // Regular type parameter
                 let name = self.consume_identifier()?;
 
-                // Optional trait bounds: T: Display + Clone or F: Callable(i32): i32
+                // Optional trait bounds: T: Display + Clone, T: Reader & Writer or F: Callable(i32): i32
                 let mut bounds = Vec::new();
                 if self.match_token(&Token::Colon) {
                     loop {
@@ -647,7 +681,7 @@ impl<'a> Parser<'a> {
                             bounds.push(TraitBound::Simple(bound_name));
                         }
 
-                        if !self.match_token(&Token::Plus) {
+                        if !self.match_bound_separator() {
                             break;
                         }
                     }
@@ -675,4 +709,9 @@ impl<'a> Parser<'a> {
         self.consume_generic_close("Expected '>' after type parameters")?;
         Ok((type_params, const_params))
     }
+
+    /// `+` or `&` between bounds; both require every contract: `T: Reader & Writer`
+    pub(crate) fn match_bound_separator(&mut self) -> bool {
+        self.match_token(&Token::Plus) || self.match_token(&Token::Ampersand)
+    }
 }
@@ -35,9 +35,11 @@ impl<'a> Parser<'a> {
     pub(crate) fn parse_null_coalesce(&mut self) -> Result<Expression, ParseError> {
         let mut expr = self.parse_logical_and()?;
 
-        while self.match_token(&Token::QuestionQuestion) {
+        // Right-associative: `a ?? b ?? c` is `a ?? (b ?? c)`, so each fallback
+        // may itself be an Option that is tried before the final default
+        if self.match_token(&Token::QuestionQuestion) {
             let op_start = self.current - 1;
-            let right = self.parse_logical_and()?;
+            let right = self.parse_null_coalesce()?;
             let op_end = self.current - 1;
 
             let span = crate::Span::from_file_and_span(
@@ -391,6 +393,12 @@ impl<'a> Parser<'a> {
             return Ok(Expression::Await(Box::new(expr)));
         }
 
+        // Yield expression: yield expr (generator functions)
+        if self.match_token(&Token::Yield) {
+            let expr = self.parse_expression()?;
+            return Ok(Expression::Yield(Box::new(expr)));
+        }
+
         // Reference expression: &expr or &expr! (mutable)
         if self.match_token(&Token::Ampersand) {
             let expr = self.parse_unary()?;
@@ -523,6 +531,7 @@ impl<'a> Parser<'a> {
                         | Token::Bool
                         | Token::LBracket
                         | Token::Ampersand
+                        | Token::Dyn
                 );
 
                 // For identifier, check what comes after it (type parameter vs variable)
@@ -545,7 +554,7 @@ impl<'a> Parser<'a> {
                     self.advance(); // consume <
                     let mut type_args = Vec::new();
                     loop {
-                        type_args.push(self.parse_type()?);
+                        type_args.push(self.parse_type_arg()?);
                         if !self.match_token(&Token::Comma) {
                             break;
                         }
@@ -642,8 +651,10 @@ impl<'a> Parser<'a> {
                         let temp_checkpoint = self.current;
                         self.advance();
                         let has_colon = self.check(&Token::Colon) || self.check(&Token::RBrace);
+                        // `match v { n: i32 => ... }` opens match arms with a type pattern
+                        let is_type_pattern = has_colon && self.field_reaches_fat_arrow();
                         self.current = temp_checkpoint;
-                        has_colon
+                        has_colon && !is_type_pattern
                     });
 
                 if !is_struct_literal {
@@ -657,29 +668,7 @@ impl<'a> Parser<'a> {
                     _ => unreachable!(),
                 };
 
-                let mut fields = Vec::new();
-
-                let mut steps = 0usize;
-                while !self.check(&Token::RBrace) && !self.is_at_end() {
-                    if self.guard_tick(
-                        &mut steps,
-                        "struct literal parse timeout",
-                        Self::PARSE_LOOP_DEFAULT_MAX_STEPS,
-                    ) {
-                        break;
-                    }
-                    let field_name = self.consume_identifier()?;
-                    self.consume(&Token::Colon, "Expected ':' after field name")?;
-                    let field_value = self.parse_expression()?;
-
-                    fields.push((field_name, field_value));
-
-                    if !self.match_token(&Token::Comma) {
-                        break;
-                    }
-                }
-
-                self.consume(&Token::RBrace, "Expected '}'")?;
+                let fields = self.parse_field_inits()?;
 
                 expr = Expression::StructLiteral {
                     name: struct_name,
@@ -731,6 +720,7 @@ impl<'a> Parser<'a> {
                             | Token::Bool
                             | Token::LBracket
                             | Token::Ampersand
+                            | Token::Dyn
                     );
 
                     let looks_like_generic = if matches!(first_token, Token::Ident(_)) {
@@ -755,7 +745,7 @@ impl<'a> Parser<'a> {
                         self.advance(); // consume <
                         let mut type_args = Vec::new();
                         loop {
-                            type_args.push(self.parse_type()?);
+                            type_args.push(self.parse_type_arg()?);
                             if !self.match_token(&Token::Comma) {
                                 break;
                             }
@@ -821,6 +811,7 @@ impl<'a> Parser<'a> {
                             enum_name,
                             variant: field_or_method,
                             data: args,
+                            field_names: vec![],
                         };
                         continue; // Skip to next iteration
                     } else {
@@ -870,11 +861,26 @@ impl<'a> Parser<'a> {
                                 .map(|c| c.is_uppercase())
                                 .unwrap_or(false)
                         {
+                            if self.check(&Token::LBrace) && self.next_is_field_init() {
+                                // Struct-like variant: Shape.Rect { w: 2.0, h: 3.0 }
+                                self.advance(); // consume '{'
+                                let (field_names, data) =
+                                    self.parse_field_inits()?.into_iter().unzip();
+                                expr = Expression::EnumLiteral {
+                                    enum_name,
+                                    variant: field_or_method,
+                                    data,
+                                    field_names,
+                                };
+                                continue;
+                            }
+
                             // Both PascalCase = likely Enum.Variant (unit variant, no data)
                             expr = Expression::EnumLiteral {
                                 enum_name,
                                 variant: field_or_method,
                                 data: vec![],
+                                field_names: vec![],
                             };
                         } else {
                             // Field access: obj.field
@@ -922,4 +928,60 @@ impl<'a> Parser<'a> {
 
         Ok(expr)
     }
+
+    /// Lookahead past '{': is this `ident: value`, i.e. fields rather than a block?
+    fn next_is_field_init(&self) -> bool {
+        matches!(
+            self.tokens.get(self.current + 1).map(|t| &t.token),
+            Some(Token::Ident(_))
+        ) && matches!(
+            self.tokens.get(self.current + 2).map(|t| &t.token),
+            Some(Token::Colon)
+        )
+    }
+
+    /// Lookahead from a field's ':': does `=>` follow before the field ends?
+    /// Field values only contain `=>` inside nested brackets, match arms have it at the top
+    fn field_reaches_fat_arrow(&self) -> bool {
+        let mut depth = 0usize;
+        for spanned in &self.tokens[self.current..] {
+            match spanned.token {
+                Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+                Token::RParen | Token::RBracket | Token::RBrace if depth == 0 => return false,
+                Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+                Token::Comma | Token::Semicolon if depth == 0 => return false,
+                Token::FatArrow if depth == 0 => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Parse `field: value, ...}` after the opening '{' of a struct or enum variant literal
+    fn parse_field_inits(&mut self) -> Result<Vec<(String, Expression)>, ParseError> {
+        let mut fields = Vec::new();
+
+        let mut steps = 0usize;
+        while !self.check(&Token::RBrace) && !self.is_at_end() {
+            if self.guard_tick(
+                &mut steps,
+                "struct literal parse timeout",
+                Self::PARSE_LOOP_DEFAULT_MAX_STEPS,
+            ) {
+                break;
+            }
+            let field_name = self.consume_identifier()?;
+            self.consume(&Token::Colon, "Expected ':' after field name")?;
+            let field_value = self.parse_expression()?;
+
+            fields.push((field_name, field_value));
+
+            if !self.match_token(&Token::Comma) {
+                break;
+            }
+        }
+
+        self.consume(&Token::RBrace, "Expected '}'")?;
+        Ok(fields)
+    }
 }
@@ -7,9 +7,19 @@ use vex_lexer::Token;
 impl<'a> Parser<'a> {
     /// Parse match expression: match value { pattern => expr, ... }
     pub(crate) fn parse_match_expression(&mut self) -> Result<Expression, ParseError> {
+        let match_start = self.current - 1; // 'match' was already consumed
+
         // Parse the value to match on
         let value = Box::new(self.parse_expression()?);
 
+        let span = crate::Span::from_file_and_span(
+            &self.file_name,
+            self.source,
+            self.tokens[match_start].span.start..self.tokens[self.current - 1].span.end,
+        );
+        let span_id = self.span_map.generate_id();
+        self.span_map.record(span_id.clone(), span);
+
         self.consume(&Token::LBrace, "Expected '{' after match value")?;
 
         let mut arms = Vec::new();
@@ -20,7 +30,15 @@ impl<'a> Parser<'a> {
                 break;
             }
             // Parse pattern
+            let pattern_start = self.current;
             let pattern = self.parse_pattern()?;
+            let pattern_span = crate::Span::from_file_and_span(
+                &self.file_name,
+                self.source,
+                self.tokens[pattern_start].span.start..self.tokens[self.current - 1].span.end,
+            );
+            let pattern_span_id = self.span_map.generate_id();
+            self.span_map.record(pattern_span_id.clone(), pattern_span);
 
             // Optional guard: if condition
             let guard = if self.match_token(&Token::If) {
@@ -55,6 +73,7 @@ impl<'a> Parser<'a> {
             };
 
             arms.push(MatchArm {
+                span_id: Some(pattern_span_id),
                 pattern,
                 guard,
                 body,
@@ -66,7 +85,11 @@ impl<'a> Parser<'a> {
 
         self.consume(&Token::RBrace, "Expected '}' after match arms")?;
 
-        Ok(Expression::Match { value, arms })
+        Ok(Expression::Match {
+            span_id: Some(span_id),
+            value,
+            arms,
+        })
     }
 
     /// Parse pattern for match expressions (with Or support)
@@ -89,8 +112,12 @@ impl<'a> Parser<'a> {
 
     /// Parse a single pattern (without Or)
     fn parse_single_pattern(&mut self) -> Result<Pattern, ParseError> {
-        // Wildcard: _
+        // Wildcard: _ or union member test without binding: _: string
         if self.match_token(&Token::Underscore) {
+            if self.match_token(&Token::Colon) {
+                let ty = self.parse_type_primary()?;
+                return Ok(Pattern::Type { binding: None, ty });
+            }
             return Ok(Pattern::Wildcard);
         }
 
@@ -180,6 +207,18 @@ impl<'a> Parser<'a> {
             if self.match_token(&Token::Dot) {
                 let variant = self.consume_identifier()?;
 
+                // Named fields: Shape.Rect { w, h: height }
+                if self.check(&Token::LBrace) {
+                    let (field_names, data) =
+                        self.parse_field_patterns("enum pattern")?.into_iter().unzip();
+                    return Ok(Pattern::Enum {
+                        name,
+                        variant,
+                        data,
+                        field_names,
+                    });
+                }
+
                 // Check for data: Variant() or Variant(pattern) or Variant(p1, p2, ...)
                 let data = if self.match_token(&Token::LParen) {
                     let mut patterns = Vec::new();
@@ -202,38 +241,13 @@ impl<'a> Parser<'a> {
                     name,
                     variant,
                     data,
+                    field_names: Vec::new(),
                 });
             }
 
             // Check for struct pattern: Point { x, y }
             if self.check(&Token::LBrace) {
-                self.advance();
-                let mut fields = Vec::new();
-
-                let mut steps = 0usize;
-                while !self.check(&Token::RBrace) && !self.is_at_end() {
-                    if self.guard_tick(&mut steps, "struct pattern parse timeout", Self::PARSE_LOOP_DEFAULT_MAX_STEPS) {
-                        break;
-                    }
-                    let field_name = self.consume_identifier()?;
-
-                    // Check for field: pattern or just field (shorthand)
-                    let field_pattern = if self.match_token(&Token::Colon) {
-                        self.parse_single_pattern()?
-                    } else {
-                        // Shorthand: { x, y } means { x: x, y: y }
-                        Pattern::Ident(field_name.clone())
-                    };
-
-                    fields.push((field_name, field_pattern));
-
-                    if !self.match_token(&Token::Comma) {
-                        break;
-                    }
-                }
-
-                self.consume(&Token::RBrace, "Expected '}' after struct pattern")?;
-
+                let fields = self.parse_field_patterns("struct pattern")?;
                 return Ok(Pattern::Struct { name, fields });
             }
 
@@ -258,6 +272,16 @@ impl<'a> Parser<'a> {
                     name: String::new(), // Will be resolved later
                     variant: name,
                     data: patterns,
+                    field_names: Vec::new(),
+                });
+            }
+
+            // Union member binding: n: i32
+            if self.match_token(&Token::Colon) {
+                let ty = self.parse_type_primary()?;
+                return Ok(Pattern::Type {
+                    binding: Some(name),
+                    ty,
                 });
             }
 
@@ -269,4 +293,35 @@ impl<'a> Parser<'a> {
         let expr = self.parse_primary()?;
         Ok(Pattern::Literal(expr))
     }
+
+    /// Parse `{ x, y: pattern }` field patterns of a struct or struct-like enum variant
+    fn parse_field_patterns(&mut self, what: &str) -> Result<Vec<(String, Pattern)>, ParseError> {
+        self.consume(&Token::LBrace, &format!("Expected '{{' to start {}", what))?;
+        let mut fields = Vec::new();
+
+        let mut steps = 0usize;
+        while !self.check(&Token::RBrace) && !self.is_at_end() {
+            if self.guard_tick(&mut steps, "struct pattern parse timeout", Self::PARSE_LOOP_DEFAULT_MAX_STEPS) {
+                break;
+            }
+            let field_name = self.consume_identifier()?;
+
+            // Check for field: pattern or just field (shorthand)
+            let field_pattern = if self.match_token(&Token::Colon) {
+                self.parse_single_pattern()?
+            } else {
+                // Shorthand: { x, y } means { x: x, y: y }
+                Pattern::Ident(field_name.clone())
+            };
+
+            fields.push((field_name, field_pattern));
+
+            if !self.match_token(&Token::Comma) {
+                break;
+            }
+        }
+
+        self.consume(&Token::RBrace, &format!("Expected '}}' after {}", what))?;
+        Ok(fields)
+    }
 }
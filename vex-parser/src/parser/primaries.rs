@@ -39,6 +39,39 @@ impl<'a> Parser<'a> {
             return Ok(Expression::Typeof(Box::new(expr)));
         }
 
+        // Heap allocation: new(expr)
+        if self.check(&Token::New) {
+            let checkpoint = self.current;
+            self.advance();
+            if self.match_token(&Token::LParen) {
+                let expr = self.parse_expression()?;
+                self.consume(&Token::RParen, "Expected ')' after new expression")?;
+                return Ok(Expression::New(Box::new(expr)));
+            }
+            self.current = checkpoint;
+        }
+
+        // Make: make([T], len) or make(Channel<T>, capacity)
+        if self.check(&Token::Make) {
+            self.advance();
+            return self.parse_make_expression();
+        }
+
+        // Error value: error.new("message")
+        if self.check(&Token::Error) {
+            let checkpoint = self.current;
+            self.advance();
+            if self.match_token(&Token::Dot)
+                && self.match_token(&Token::New)
+                && self.match_token(&Token::LParen)
+            {
+                let message = self.parse_expression()?;
+                self.consume(&Token::RParen, "Expected ')' after error.new message")?;
+                return Ok(Expression::ErrorNew(Box::new(message)));
+            }
+            self.current = checkpoint;
+        }
+
         // Closure/Lambda: |x, y| expr or |x: i32, y: i32| { body } or || expr
         if self.check(&Token::Pipe) || self.check(&Token::Or) {
             return self.parse_closure();
@@ -378,6 +411,7 @@ impl<'a> Parser<'a> {
                         enum_name: "Option".to_string(),
                         variant: "None".to_string(),
                         data: vec![],
+                        field_names: vec![],
                     });
                 }
                 "Some" => {
@@ -390,6 +424,7 @@ impl<'a> Parser<'a> {
                             enum_name: "Option".to_string(),
                             variant: "Some".to_string(),
                             data: vec![value],
+                            field_names: vec![],
                         });
                     } else {
                         // No parens - might be used as identifier (error case)
@@ -406,6 +441,7 @@ impl<'a> Parser<'a> {
                             enum_name: "Result".to_string(),
                             variant: "Ok".to_string(),
                             data: vec![value],
+                            field_names: vec![],
                         });
                     } else {
                         return Ok(Expression::Ident(name));
@@ -421,6 +457,7 @@ impl<'a> Parser<'a> {
                             enum_name: "Result".to_string(),
                             variant: "Err".to_string(),
                             data: vec![error],
+                            field_names: vec![],
                         });
                     } else {
                         return Ok(Expression::Ident(name));
@@ -463,7 +500,7 @@ impl<'a> Parser<'a> {
                             self.advance(); // consume <
                             let mut args = Vec::new();
                             loop {
-                                args.push(self.parse_type()?);
+                                args.push(self.parse_type_arg()?);
                                 if !self.match_token(&Token::Comma) {
                                     break;
                                 }
@@ -580,6 +617,38 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parse make arguments: ([T], len) or (Channel<T>, capacity)
+    /// For slices `element_type` is T; channel types are kept whole.
+    fn parse_make_expression(&mut self) -> Result<Expression, ParseError> {
+        self.consume(&Token::LParen, "Expected '(' after 'make'")?;
+
+        let element_type = if self.match_token(&Token::LBracket) {
+            let elem_ty = self.parse_type()?;
+            self.consume(&Token::RBracket, "Expected ']' after slice element type")?;
+            elem_ty
+        } else {
+            let ty = self.parse_type()?;
+            if !matches!(ty, Type::Channel(_)) {
+                return Err(self.make_syntax_error(
+                    "make expects a slice or channel type",
+                    Some("unsupported make type"),
+                    Some("make creates slices ([T]) and channels (Channel<T>)"),
+                    Some(("make a slice", "make([i32], 16)")),
+                ));
+            }
+            ty
+        };
+
+        self.consume(&Token::Comma, "Expected ',' and a size after make type")?;
+        let size = self.parse_expression()?;
+        self.consume(&Token::RParen, "Expected ')' after make arguments")?;
+
+        Ok(Expression::Make {
+            element_type,
+            size: Box::new(size),
+        })
+    }
+
     /// Parse async block: async { stmts; expr }
     /// Returns AsyncBlock expression
     pub(crate) fn parse_async_block(&mut self) -> Result<Expression, ParseError> {
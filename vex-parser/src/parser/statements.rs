@@ -7,6 +7,16 @@ use vex_lexer::Token;
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        // Attributed statement: #[name(args)] stmt
+        if self.check(&Token::Hash) {
+            let attributes = self.parse_attributes()?;
+            let stmt = self.parse_statement()?;
+            return Ok(Statement::Attributed {
+                attributes,
+                stmt: Box::new(stmt),
+            });
+        }
+
         // Let statement: let x = expr; or let! x = expr; or let (a, b) = expr;
         if self.match_token(&Token::Let) || self.match_token(&Token::LetMut) {
             let is_mutable = *self.previous() == Token::LetMut;
@@ -354,6 +364,76 @@ impl<'a> Parser<'a> {
             });
         }
 
+        // Select statement (Go-style)
+        // select { case v = <-ch: ... case out <- v: ... default: ... }
+        if self.match_token(&Token::Select) {
+            let select_start = self.current - 1;
+
+            self.consume(&Token::LBrace, "Expected '{' after select")?;
+
+            let mut cases = Vec::new();
+            let mut default_case = None;
+
+            let mut steps = 0usize;
+            while !self.check(&Token::RBrace) && !self.is_at_end() {
+                if self.guard_tick(&mut steps, "select body parse timeout", Self::PARSE_LOOP_DEFAULT_MAX_STEPS) {
+                    break;
+                }
+                if self.match_token(&Token::Default) {
+                    if default_case.is_some() {
+                        return Err(self.make_syntax_error(
+                            "Multiple 'default' cases in select",
+                            Some("duplicate default"),
+                            Some("A select statement may have at most one 'default' case"),
+                            None,
+                        ));
+                    }
+                    self.consume(&Token::Colon, "Expected ':' after default")?;
+                    default_case = Some(if self.check(&Token::LBrace) {
+                        self.parse_block()?
+                    } else {
+                        self.parse_block_until_case_or_brace()?
+                    });
+                } else if self.match_token(&Token::Case) {
+                    let (var, expr) = self.parse_select_case_header()?;
+                    self.consume(&Token::Colon, "Expected ':' after select case")?;
+
+                    // Body is either a braced block or statements up to the next case
+                    let body = if self.check(&Token::LBrace) {
+                        self.parse_block()?
+                    } else {
+                        self.parse_block_until_case_or_brace()?
+                    };
+
+                    cases.push(SelectCase { var, expr, body });
+                } else {
+                    return Err(self.make_syntax_error(
+                        "Expected 'case' or 'default' in select",
+                        Some("expected 'case' or 'default'"),
+                        Some("Each select arm is a channel operation: 'case v = <-ch:' or 'case ch <- v:'"),
+                        Some(("try 'case'", "case v = <-ch: { ... }")),
+                    ));
+                }
+            }
+
+            self.consume(&Token::RBrace, "Expected '}' after select")?;
+            let select_end = self.current - 1;
+
+            let span = crate::Span::from_file_and_span(
+                &self.file_name,
+                self.source,
+                self.tokens[select_start].span.start..self.tokens[select_end].span.end,
+            );
+            let span_id = self.span_map.generate_id();
+            self.span_map.record(span_id.clone(), span);
+
+            return Ok(Statement::Select {
+                span_id: Some(span_id),
+                cases,
+                default_case,
+            });
+        }
+
         // For statement
         // For loop
         if self.match_token(&Token::For) {
@@ -551,6 +631,68 @@ impl<'a> Parser<'a> {
         self.parse_expression_statement()
     }
 
+    /// Parse the channel operation of a select case (after `case`, before `:`)
+    /// Forms: `v = <-ch`, `let v = <-ch`, `<-ch`, `ch <- value`, `ch.send(value)`
+    fn parse_select_case_header(&mut self) -> Result<(Option<String>, Expression), ParseError> {
+        // Receive with binding: v = <-ch or let v = <-ch
+        let has_let = self.match_token(&Token::Let);
+        if matches!(self.peek(), Token::Ident(_)) {
+            let checkpoint = self.current;
+            let name = self.consume_identifier()?;
+            if self.match_token(&Token::Eq) {
+                let expr = self.parse_expression()?;
+                if !matches!(expr, Expression::ChannelReceive(_)) {
+                    return Err(self.make_syntax_error(
+                        "Select case binding must receive from a channel",
+                        Some("expected '<-channel'"),
+                        Some("Only receive operations can bind a value in a select case"),
+                        Some(("receive from the channel", "case v = <-ch:")),
+                    ));
+                }
+                return Ok((Some(name), expr));
+            }
+            self.current = checkpoint;
+        }
+        if has_let {
+            return Err(self.make_syntax_error(
+                "Expected binding name after 'let' in select case",
+                Some("expected identifier"),
+                None,
+                Some(("bind the received value", "case let v = <-ch:")),
+            ));
+        }
+
+        let expr = self.parse_expression()?;
+
+        // Send: ch <- value (desugars to ch.send(value), like <-ch desugars to ch.recv())
+        if self.match_token(&Token::LeftArrow) {
+            let value = self.parse_expression()?;
+            return Ok((
+                None,
+                Expression::MethodCall {
+                    receiver: Box::new(expr),
+                    method: "send".to_string(),
+                    type_args: vec![],
+                    args: vec![value],
+                    is_mutable_call: true,
+                },
+            ));
+        }
+
+        match &expr {
+            Expression::ChannelReceive(_) => Ok((None, expr)),
+            Expression::MethodCall { method, args, .. } if method == "send" && args.len() == 1 => {
+                Ok((None, expr))
+            }
+            _ => Err(self.make_syntax_error(
+                "Select case must be a channel send or receive",
+                Some("expected channel operation"),
+                Some("Use '<-ch' to receive or 'ch <- value' to send"),
+                Some(("receive from a channel", "case v = <-ch:")),
+            )),
+        }
+    }
+
     pub(crate) fn parse_expression_statement(&mut self) -> Result<Statement, ParseError> {
         let expr_start = self.current;
         let expr = self.parse_expression()?;
@@ -82,34 +82,50 @@ impl<'a> Parser<'a> {
         Ok(Type::Tuple(types))
     }
 
-    /// Parse array type: [T; N]
+    /// Parse array type: [T; 4], or [T; N] / [T; R * C] over const params
     fn parse_array_type(&mut self) -> Result<Type, ParseError> {
         self.advance(); // consume [
         let elem_ty = self.parse_type()?;
         self.consume(&Token::Semicolon, "Expected ';' in array type")?;
 
-        let size = if let Token::IntLiteral(s) = self.peek() {
-            let s_val = s.clone();
+        let literal_size = match (
+            self.peek(),
+            self.tokens.get(self.current + 1).map(|t| &t.token),
+        ) {
+            (Token::IntLiteral(s), Some(Token::RBracket)) => Some(s.clone()),
+            _ => None,
+        };
+
+        if let Some(s_val) = literal_size {
             self.advance();
-            s_val
-                .parse::<usize>()
-                    .map_err(|_| self.make_syntax_error(
-                        &format!("Array size out of range: {}", s_val),
-                        Some("array size out of range"),
-                        Some("Array size must be a positive integer within range"),
-                        Some(("try a small integer", "10")),
-                    ))?
-        } else {
+            let size = s_val.parse::<usize>().map_err(|_| {
+                self.make_syntax_error(
+                    &format!("Array size out of range: {}", s_val),
+                    Some("array size out of range"),
+                    Some("Array size must be a positive integer within range"),
+                    Some(("try a small integer", "10")),
+                )
+            })?;
+            self.consume(&Token::RBracket, "Expected ']'")?;
+            return Ok(Type::Array(Box::new(elem_ty), size));
+        }
+
+        if self.check(&Token::RBracket) {
             return Err(self.make_syntax_error(
                 "Expected array size",
                 Some("expected array size"),
                 Some("Specify the size for the array: [T; N]"),
                 Some(("add a size", "[i32; 10]")),
             ));
-        };
+        }
 
+        // Size computed from const generic parameters, evaluated per instantiation
+        let size = self.parse_expression()?;
         self.consume(&Token::RBracket, "Expected ']'")?;
-        Ok(Type::Array(Box::new(elem_ty), size))
+        Ok(Type::ConstArray {
+            elem_type: Box::new(elem_ty),
+            size: Box::new(size),
+        })
     }
 
     /// Parse function type: fn(T1, T2): R
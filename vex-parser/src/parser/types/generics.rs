@@ -59,7 +59,7 @@ impl<'a> Parser<'a> {
             "Result" => self.parse_result_type(),
             "Vec" => self.parse_vec_type(),
             "Box" => self.parse_box_type(),
-            "Channel" => self.parse_channel_type(),
+            "Channel" | "chan" => self.parse_channel_type(),
             "Future" => self.parse_future_type(),
             "Map" => self.parse_map_type(),
             _ => self.parse_generic_or_named(name),
@@ -116,9 +116,9 @@ impl<'a> Parser<'a> {
         Ok(Type::Box(inner_type))
     }
 
-    /// Parse Channel<T>
+    /// Parse Channel<T> (also spelled chan<T>)
     fn parse_channel_type(&mut self) -> Result<Type, ParseError> {
-        self.consume(&Token::Lt, "Expected '<' after 'Channel'")?;
+        self.consume(&Token::Lt, "Expected '<' after channel type")?;
         let inner_type = Box::new(self.parse_type()?);
         self.consume_generic_close("Expected '>' after Channel type argument")?;
         Ok(Type::Channel(inner_type))
@@ -159,15 +159,55 @@ impl<'a> Parser<'a> {
         if self.match_token(&Token::Lt) {
             let mut type_args = Vec::new();
             loop {
-                type_args.push(self.parse_type()?);
+                type_args.push(self.parse_type_arg()?);
                 if !self.match_token(&Token::Comma) {
                     break;
                 }
             }
             self.consume_generic_close("Expected '>' after type arguments")?;
             Ok(Type::Generic { name, type_args })
+        } else if self.next_is_type_projection() {
+            // Associated type projection: T.Item
+            self.advance(); // consume .
+            let assoc_name = self.consume_identifier()?;
+            Ok(Type::AssociatedType {
+                self_type: Box::new(Type::Named(name)),
+                name: assoc_name,
+            })
         } else {
             Ok(Type::Named(name))
         }
     }
+
+    /// Parse one generic argument: a type, or an integer for a const parameter
+    /// (the 3 in `Matrix<f32, 2, 3>`)
+    pub(crate) fn parse_type_arg(&mut self) -> Result<Type, ParseError> {
+        if let Token::IntLiteral(s) = self.peek() {
+            let s_val = s.clone();
+            self.advance();
+            let value = s_val.parse::<i64>().map_err(|_| {
+                self.make_syntax_error(
+                    &format!("Const generic argument out of range: {}", s_val),
+                    Some("const argument out of range"),
+                    Some("Const generic arguments must fit in a 64-bit integer"),
+                    None,
+                )
+            })?;
+            return Ok(Type::ConstValue(value));
+        }
+        self.parse_type()
+    }
+
+    /// Lookahead at '.': `.Item` names an associated type, `.method(` does not
+    fn next_is_type_projection(&self) -> bool {
+        self.check(&Token::Dot)
+            && matches!(
+                self.tokens.get(self.current + 1).map(|t| &t.token),
+                Some(Token::Ident(_))
+            )
+            && !matches!(
+                self.tokens.get(self.current + 2).map(|t| &t.token),
+                Some(Token::LParen) | Some(Token::Lt)
+            )
+    }
 }
@@ -28,9 +28,21 @@ impl<'a> Parser<'a> {
             return Ok(Some(self.parse_raw_pointer_type()?));
         }
 
+        // Contract object type: dyn Shape
+        if self.check(&Token::Dyn) {
+            return Ok(Some(self.parse_dyn_type()?));
+        }
+
         Ok(None)
     }
 
+    /// Parse contract object type: dyn Shape
+    fn parse_dyn_type(&mut self) -> Result<Type, ParseError> {
+        self.advance(); // consume 'dyn'
+        let name = self.consume_identifier()?;
+        Ok(Type::Dyn(name))
+    }
+
     /// Parse infer type: infer E
     fn parse_infer_type(&mut self) -> Result<Type, ParseError> {
         self.advance(); // consume 'infer'
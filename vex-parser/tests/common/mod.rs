@@ -0,0 +1,14 @@
+// Parsing helpers shared by the parser integration tests
+
+use vex_ast::{Function, Item};
+use vex_parser::Parser;
+
+/// Parse `code` and return its first item, which must be a function
+pub fn parse_function(code: &str) -> Function {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    let program = parser.parse().expect("Parse failed");
+    match program.items.into_iter().next() {
+        Some(Item::Function(func)) => func,
+        other => panic!("Expected function, got {:?}", other),
+    }
+}
@@ -0,0 +1,111 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse_let_values(code: &str) -> Vec<Expression> {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    let program = parser.parse().expect("Parse failed");
+
+    let func = match program.items.into_iter().next() {
+        Some(Item::Function(func)) => func,
+        other => panic!("Expected function, got {:?}", other),
+    };
+
+    func.body
+        .statements
+        .into_iter()
+        .filter_map(|stmt| match stmt {
+            Statement::Let { value, .. } => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_make_slice_and_channel() {
+    let code = r#"
+        fn main(): i32 {
+            let buf = make([i32], n);
+            let ch = make(chan<i64>, 16);
+            let named = make(Channel<i32>, 4);
+            return 0;
+        }
+    "#;
+
+    let values = parse_let_values(code);
+    assert_eq!(values.len(), 3);
+
+    match &values[0] {
+        Expression::Make { element_type, size } => {
+            assert_eq!(*element_type, Type::I32);
+            assert!(matches!(size.as_ref(), Expression::Ident(name) if name == "n"));
+        }
+        other => panic!("Expected make expression, got {:?}", other),
+    }
+
+    match &values[1] {
+        Expression::Make { element_type, size } => {
+            assert_eq!(*element_type, Type::Channel(Box::new(Type::I64)));
+            assert!(matches!(size.as_ref(), Expression::IntLiteral(16)));
+        }
+        other => panic!("Expected make expression, got {:?}", other),
+    }
+
+    assert!(matches!(
+        &values[2],
+        Expression::Make {
+            element_type: Type::Channel(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_make_rejects_non_container_type() {
+    let code = r#"
+        fn main(): i32 {
+            let x = make(i32, 4);
+            return 0;
+        }
+    "#;
+
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_new_and_error_new() {
+    let code = r#"
+        fn main(): i32 {
+            let p = new(Point { x: 1, y: 2 });
+            let n = new(42);
+            let e = error.new("disk full");
+            let plain = error;
+            return 0;
+        }
+    "#;
+
+    let values = parse_let_values(code);
+    assert_eq!(values.len(), 4);
+
+    match &values[0] {
+        Expression::New(inner) => {
+            assert!(
+                matches!(inner.as_ref(), Expression::StructLiteral { name, .. } if name == "Point")
+            )
+        }
+        other => panic!("Expected new expression, got {:?}", other),
+    }
+    assert!(
+        matches!(&values[1], Expression::New(inner) if matches!(inner.as_ref(), Expression::IntLiteral(42)))
+    );
+
+    match &values[2] {
+        Expression::ErrorNew(message) => {
+            assert!(matches!(message.as_ref(), Expression::StringLiteral(s) if s == "disk full"))
+        }
+        other => panic!("Expected error.new expression, got {:?}", other),
+    }
+
+    // Bare `error` is still an identifier
+    assert!(matches!(&values[3], Expression::Ident(name) if name == "error"));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::parse_function;
+use vex_ast::*;
+
+fn projection(type_param: &str, name: &str) -> Type {
+    Type::AssociatedType {
+        self_type: Box::new(Type::Named(type_param.to_string())),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn test_projection_in_signature() {
+    let func = parse_function(
+        r#"
+        fn first<T: Iterator>(it: T, fallback: T.Item): Option<T.Item> {
+            return it.next();
+        }
+    "#,
+    );
+
+    assert_eq!(func.params[1].ty, projection("T", "Item"));
+    assert_eq!(
+        func.return_type,
+        Some(Type::Option(Box::new(projection("T", "Item"))))
+    );
+}
+
+#[test]
+fn test_projection_bound_in_where_clause() {
+    let func = parse_function(
+        r#"
+        fn show_all<T>(it: T): i32 where T: Iterator, T.Item: Display {
+            return 0;
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.where_clause[1],
+        WhereClausePredicate::AssociatedTypeBound {
+            type_param: "T".to_string(),
+            assoc_type: "Item".to_string(),
+            bounds: vec![TraitBound::Simple("Display".to_string())],
+        }
+    );
+}
+
+#[test]
+fn test_projection_annotation_in_body() {
+    let func = parse_function(
+        r#"
+        fn take<T: Iterator>(it: T): i32 {
+            let item: T.Item = it.next();
+            return 0;
+        }
+    "#,
+    );
+
+    match &func.body.statements[0] {
+        Statement::Let { ty, .. } => assert_eq!(ty, &Some(projection("T", "Item"))),
+        other => panic!("Expected let, got {:?}", other),
+    }
+}
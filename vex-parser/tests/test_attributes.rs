@@ -0,0 +1,148 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse(code: &str) -> Program {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    parser.parse().expect("Parse failed")
+}
+
+fn names(attributes: &[Attribute]) -> Vec<&str> {
+    attributes.iter().map(|attr| attr.name.as_str()).collect()
+}
+
+#[test]
+fn test_attributes_on_items_and_fields() {
+    let program = parse(
+        r#"
+        #[inline(always)]
+        #[must_use("the sum is the point")]
+        export fn add(a: i32, b: i32): i32 {
+            return a + b;
+        }
+
+        #[deprecated(since = "0.3", note = "use Point")]
+        struct OldPoint {
+            #[deprecated("use y")]
+            legacy: i32,
+            y: i32,
+
+            #[cold]
+            fn reset()! {
+                self.y = 0;
+            }
+        }
+
+        #[must_use]
+        enum Status {
+            Ok,
+            Failed,
+        }
+
+        fn plain(): i32 {
+            return 0;
+        }
+    "#,
+    );
+
+    match &program.items[0] {
+        Item::Function(func) => {
+            assert!(func.is_exported);
+            assert_eq!(names(&func.attributes), vec!["inline", "must_use"]);
+            assert_eq!(
+                func.attributes[0].args,
+                vec![AttributeArg::Ident("always".to_string())]
+            );
+            assert_eq!(func.attributes[1].message(), Some("the sum is the point"));
+            assert!(func.attributes[0].span_id.is_some());
+        }
+        other => panic!("Expected function, got {:?}", other),
+    }
+
+    match &program.items[1] {
+        Item::Struct(s) => {
+            let deprecated = find_attribute(&s.attributes, "deprecated").unwrap();
+            assert_eq!(
+                deprecated.args,
+                vec![
+                    AttributeArg::KeyValue("since".to_string(), "0.3".to_string()),
+                    AttributeArg::KeyValue("note".to_string(), "use Point".to_string()),
+                ]
+            );
+            assert_eq!(deprecated.message(), Some("use Point"));
+            assert_eq!(names(&s.fields[0].attributes), vec!["deprecated"]);
+            assert!(s.fields[1].attributes.is_empty());
+            assert_eq!(names(&s.methods[0].attributes), vec!["cold"]);
+        }
+        other => panic!("Expected struct, got {:?}", other),
+    }
+
+    match &program.items[2] {
+        Item::Enum(e) => assert_eq!(names(&e.attributes), vec!["must_use"]),
+        other => panic!("Expected enum, got {:?}", other),
+    }
+
+    match &program.items[3] {
+        Item::Function(func) => assert!(func.attributes.is_empty()),
+        other => panic!("Expected function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_attributes_on_statements() {
+    let program = parse(
+        r#"
+        fn main(): i32 {
+            #[allow(unused)] #[trace]
+            let x = 1;
+            #[hot]
+            while x < 0 {
+                #[note(7)]
+                return 1;
+            }
+            return x;
+        }
+    "#,
+    );
+
+    let Item::Function(func) = &program.items[0] else {
+        panic!("Expected function");
+    };
+    match &func.body.statements[0] {
+        Statement::Attributed { attributes, stmt } => {
+            assert_eq!(names(attributes), vec!["allow", "trace"]);
+            assert_eq!(
+                attributes[0].args,
+                vec![AttributeArg::Ident("unused".to_string())]
+            );
+            assert!(matches!(stmt.as_ref(), Statement::Let { name, .. } if name == "x"));
+        }
+        other => panic!("Expected attributed let, got {:?}", other),
+    }
+    let body = match &func.body.statements[1] {
+        Statement::Attributed { stmt, .. } => match stmt.as_ref() {
+            Statement::While { body, .. } => body,
+            other => panic!("Expected while loop, got {:?}", other),
+        },
+        other => panic!("Expected attributed while, got {:?}", other),
+    };
+    match &body.statements[0] {
+        Statement::Attributed { attributes, stmt } => {
+            assert_eq!(attributes[0].args, vec![AttributeArg::Int("7".to_string())]);
+            assert!(matches!(stmt.as_ref(), Statement::Return { .. }));
+        }
+        other => panic!("Expected attributed return, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_attribute_on_unsupported_item_is_error() {
+    for code in [
+        "#[inline] const LIMIT: i32 = 10;",
+        "#[inline] import { println } from \"io\";",
+        "#[inline]",
+        "#[inline(always fn f() {}",
+    ] {
+        let mut parser = Parser::new(code).expect("Parser::new failed");
+        assert!(parser.parse().is_err(), "expected error for {}", code);
+    }
+}
@@ -0,0 +1,51 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn closure_body(code: &str) -> Expression {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    let program = parser.parse().expect("Parse failed");
+    let func = match program.items.into_iter().next() {
+        Some(Item::Function(func)) => func,
+        other => panic!("Expected function, got {:?}", other),
+    };
+    match func.body.statements.into_iter().nth(1) {
+        Some(Statement::Let {
+            value: Expression::Closure { body, .. },
+            ..
+        }) => *body,
+        other => panic!("Expected closure let, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_assignment_in_closure_block() {
+    let body = closure_body(
+        r#"
+        fn main(): i32 {
+            let! count = 0;
+            let tick = || {
+                count = count + 1;
+                count += 2;
+                count
+            };
+            return 0;
+        }
+    "#,
+    );
+
+    match body {
+        Expression::Block {
+            statements,
+            return_expr,
+        } => {
+            assert!(matches!(statements[0], Statement::Assign { .. }));
+            assert!(matches!(statements[1], Statement::CompoundAssign { .. }));
+            assert_eq!(statements.len(), 2);
+            assert_eq!(
+                return_expr,
+                Some(Box::new(Expression::Ident("count".to_string())))
+            );
+        }
+        other => panic!("Expected block body, got {:?}", other),
+    }
+}
@@ -0,0 +1,66 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse(code: &str) -> Program {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    parser.parse().expect("Parse failed")
+}
+
+#[test]
+fn test_const_fn_and_const_items() {
+    let program = parse(
+        r#"
+        const fn square(x: i64): i64 {
+            return x * x;
+        }
+        export const fn cube(x: i64): i64 {
+            return x * square(x);
+        }
+        const AREA: i64 = square(12);
+        fn runtime(): i64 {
+            return 0;
+        }
+    "#,
+    );
+
+    let functions: Vec<&Function> = program
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(func) => Some(func),
+            _ => None,
+        })
+        .collect();
+    let flags: Vec<(&str, bool, bool)> = functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.is_const, f.is_exported))
+        .collect();
+    assert_eq!(
+        flags,
+        vec![
+            ("square", true, false),
+            ("cube", true, true),
+            ("runtime", false, false)
+        ]
+    );
+
+    match &program.items[2] {
+        Item::Const(c) => {
+            assert_eq!(c.name, "AREA");
+            assert!(matches!(c.value, Expression::Call { .. }));
+        }
+        other => panic!("Expected const item, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_const_array_length_over_constant() {
+    let program = parse("const ROW: [u8; WIDTH * 2] = [0; WIDTH * 2];");
+    match &program.items[0] {
+        Item::Const(Const {
+            ty: Some(Type::ConstArray { elem_type, .. }),
+            ..
+        }) => assert_eq!(**elem_type, Type::U8),
+        other => panic!("Expected const array, got {:?}", other),
+    }
+}
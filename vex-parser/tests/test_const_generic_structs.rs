@@ -0,0 +1,145 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse_items(code: &str) -> Vec<Item> {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    parser.parse().expect("Parse failed").items
+}
+
+fn ident(name: &str) -> Box<Expression> {
+    Box::new(Expression::Ident(name.to_string()))
+}
+
+#[test]
+fn test_struct_const_params_and_sized_field() {
+    let items = parse_items(
+        r#"
+        struct Matrix<T, const R: u64, const C: u64> {
+            data: [T; R * C],
+        }
+    "#,
+    );
+
+    let Some(Item::Struct(s)) = items.first() else {
+        panic!("Expected struct, got {:?}", items.first());
+    };
+    assert_eq!(s.type_params.len(), 1);
+    assert_eq!(
+        s.const_params,
+        vec![("R".to_string(), Type::U64), ("C".to_string(), Type::U64)]
+    );
+
+    match &s.fields[0].ty {
+        Type::ConstArray { elem_type, size } => {
+            assert_eq!(elem_type.as_ref(), &Type::Named("T".to_string()));
+            match size.as_ref() {
+                Expression::Binary {
+                    left, op, right, ..
+                } => {
+                    assert_eq!(left, &ident("R"));
+                    assert_eq!(op, &BinaryOp::Mul);
+                    assert_eq!(right, &ident("C"));
+                }
+                other => panic!("Expected R * C, got {:?}", other),
+            }
+        }
+        other => panic!("Expected const array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_literal_array_size_stays_fixed() {
+    let items = parse_items(
+        r#"
+        struct Buffer {
+            data: [u8; 16],
+        }
+    "#,
+    );
+
+    let Some(Item::Struct(s)) = items.first() else {
+        panic!("Expected struct, got {:?}", items.first());
+    };
+    assert_eq!(s.fields[0].ty, Type::Array(Box::new(Type::U8), 16));
+}
+
+#[test]
+fn test_const_arguments_in_types_and_literals() {
+    let items = parse_items(
+        r#"
+        fn main(): i32 {
+            let m: Matrix<i32, 2, 3> = Matrix<i32, 2, 3> { data: [0; 6] };
+            return 0;
+        }
+    "#,
+    );
+
+    let Some(Item::Function(func)) = items.first() else {
+        panic!("Expected function, got {:?}", items.first());
+    };
+    let expected_args = vec![Type::I32, Type::ConstValue(2), Type::ConstValue(3)];
+    match &func.body.statements[0] {
+        Statement::Let { ty, value, .. } => {
+            assert_eq!(
+                ty,
+                &Some(Type::Generic {
+                    name: "Matrix".to_string(),
+                    type_args: expected_args.clone(),
+                })
+            );
+            match value {
+                Expression::StructLiteral { type_args, .. } => {
+                    assert_eq!(type_args, &expected_args)
+                }
+                other => panic!("Expected struct literal, got {:?}", other),
+            }
+        }
+        other => panic!("Expected let, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_comparison_with_integer_is_not_a_type_argument() {
+    let items = parse_items(
+        r#"
+        fn small(x: i32): bool {
+            return x < 2;
+        }
+    "#,
+    );
+
+    let Some(Item::Function(func)) = items.first() else {
+        panic!("Expected function, got {:?}", items.first());
+    };
+    match &func.body.statements[0] {
+        Statement::Return {
+            value: Some(Expression::Binary { op, .. }),
+            ..
+        } => {
+            assert_eq!(op, &BinaryOp::Lt)
+        }
+        other => panic!("Expected comparison, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_inline_method_const_params() {
+    let items = parse_items(
+        r#"
+        struct Grid {
+            width: i32,
+            fn cell<const I: i32>(): i32 {
+                return I;
+            }
+        }
+    "#,
+    );
+
+    let Some(Item::Struct(s)) = items.first() else {
+        panic!("Expected struct, got {:?}", items.first());
+    };
+    assert_eq!(
+        s.methods[0].const_params,
+        vec![("I".to_string(), Type::I32)]
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::parse_function;
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn dyn_shape() -> Type {
+    Type::Dyn("Shape".to_string())
+}
+
+#[test]
+fn test_dyn_in_parameters_and_return() {
+    let func = parse_function(
+        r#"
+        fn pick(a: &dyn Shape, b: Box<dyn Shape>): Box<dyn Shape> {
+            return b;
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.params[0].ty,
+        Type::Reference(Box::new(dyn_shape()), false)
+    );
+    assert_eq!(func.params[1].ty, Type::Box(Box::new(dyn_shape())));
+    assert_eq!(func.return_type, Some(Type::Box(Box::new(dyn_shape()))));
+}
+
+#[test]
+fn test_dyn_in_let_annotations() {
+    let func = parse_function(
+        r#"
+        fn main(): i32 {
+            let shapes: Vec<Box<dyn Shape>> = Vec.new();
+            let first: &dyn Shape = &circle;
+            return 0;
+        }
+    "#,
+    );
+
+    match &func.body.statements[0] {
+        Statement::Let { ty, .. } => assert_eq!(
+            ty,
+            &Some(Type::Vec(Box::new(Type::Box(Box::new(dyn_shape())))))
+        ),
+        other => panic!("Expected let, got {:?}", other),
+    }
+    match &func.body.statements[1] {
+        Statement::Let { ty, .. } => {
+            assert_eq!(ty, &Some(Type::Reference(Box::new(dyn_shape()), false)))
+        }
+        other => panic!("Expected let, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_dyn_requires_contract_name() {
+    let mut parser = Parser::new("fn f(s: &dyn): i32 { return 0; }").expect("Parser::new failed");
+    assert!(parser.parse().is_err());
+}
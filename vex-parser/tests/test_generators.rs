@@ -0,0 +1,41 @@
+mod common;
+
+use common::parse_function;
+use vex_ast::*;
+
+#[test]
+fn test_generator_function_with_yield() {
+    let func = parse_function(
+        r#"
+        fn evens(limit: i32): Generator<i32> {
+            let! n = 0;
+            while n < limit {
+                yield n * 2;
+                n = n + 1;
+            }
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.return_type.as_ref().and_then(Type::generator_item),
+        Some(&Type::I32)
+    );
+
+    let loop_body = match &func.body.statements[1] {
+        Statement::While { body, .. } => body,
+        other => panic!("Expected while loop, got {:?}", other),
+    };
+    match &loop_body.statements[0] {
+        Statement::Expression(Expression::Yield(value)) => {
+            assert!(matches!(
+                value.as_ref(),
+                Expression::Binary {
+                    op: BinaryOp::Mul,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected yield statement, got {:?}", other),
+    }
+}
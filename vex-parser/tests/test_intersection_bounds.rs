@@ -0,0 +1,62 @@
+mod common;
+
+use common::parse_function;
+use vex_ast::*;
+
+fn simple(name: &str) -> TraitBound {
+    TraitBound::Simple(name.to_string())
+}
+
+#[test]
+fn test_ampersand_separates_type_param_bounds() {
+    let func = parse_function(
+        r#"
+        fn copy<T: Reader & Writer + Display>(v: T): i32 {
+            return 0;
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.type_params[0].bounds,
+        vec![simple("Reader"), simple("Writer"), simple("Display")]
+    );
+}
+
+#[test]
+fn test_ampersand_separates_where_clause_bounds() {
+    let func = parse_function(
+        r#"
+        fn copy<T>(v: T): i32 where T: Reader & Writer {
+            return 0;
+        }
+    "#,
+    );
+
+    match &func.where_clause[0] {
+        WhereClausePredicate::TypeBound { type_param, bounds } => {
+            assert_eq!(type_param, "T");
+            assert_eq!(bounds, &vec![simple("Reader"), simple("Writer")]);
+        }
+        other => panic!("Expected type bound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_intersection_parameter_type() {
+    let func = parse_function(
+        r#"
+        fn show(v: Named & Timestamped): i64 {
+            return v.created;
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.params[0].ty,
+        Type::Intersection(vec![
+            Type::Named("Named".to_string()),
+            Type::Named("Timestamped".to_string()),
+        ])
+    );
+}
@@ -0,0 +1,120 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse_first_let_value(code: &str) -> Expression {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    let program = parser.parse().expect("Parse failed");
+
+    let func = match program.items.into_iter().next() {
+        Some(Item::Function(func)) => func,
+        other => panic!("Expected function, got {:?}", other),
+    };
+
+    func.body
+        .statements
+        .into_iter()
+        .find_map(|stmt| match stmt {
+            Statement::Let { value, .. } => Some(value),
+            _ => None,
+        })
+        .expect("Expected a let statement")
+}
+
+#[test]
+fn test_null_coalesce_is_right_associative() {
+    let code = r#"
+        fn main(): i32 {
+            let x = a ?? b ?? 0;
+            return 0;
+        }
+    "#;
+
+    match parse_first_let_value(code) {
+        Expression::Binary {
+            left,
+            op: BinaryOp::NullCoalesce,
+            right,
+            ..
+        } => {
+            assert!(matches!(left.as_ref(), Expression::Ident(name) if name == "a"));
+            match right.as_ref() {
+                Expression::Binary {
+                    left,
+                    op: BinaryOp::NullCoalesce,
+                    right,
+                    ..
+                } => {
+                    assert!(matches!(left.as_ref(), Expression::Ident(name) if name == "b"));
+                    assert!(matches!(right.as_ref(), Expression::IntLiteral(0)));
+                }
+                other => panic!("Expected nested ?? on the right, got {:?}", other),
+            }
+        }
+        other => panic!("Expected ?? expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_null_coalesce_binds_looser_than_arithmetic() {
+    let code = r#"
+        fn main(): i32 {
+            let x = a ?? b + 1;
+            return 0;
+        }
+    "#;
+
+    match parse_first_let_value(code) {
+        Expression::Binary {
+            left,
+            op: BinaryOp::NullCoalesce,
+            right,
+            ..
+        } => {
+            assert!(matches!(left.as_ref(), Expression::Ident(name) if name == "a"));
+            assert!(matches!(
+                right.as_ref(),
+                Expression::Binary {
+                    op: BinaryOp::Add,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected ?? expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_null_coalesce_on_builtin_option_constructor() {
+    let code = r#"
+        fn main(): i32 {
+            let x = Some<i32>(4) ?? 0;
+            return 0;
+        }
+    "#;
+
+    // `Some<T>(v)` stays a call to the Some builtin rather than an enum literal
+    match parse_first_let_value(code) {
+        Expression::Binary {
+            left,
+            op: BinaryOp::NullCoalesce,
+            right,
+            ..
+        } => {
+            match left.as_ref() {
+                Expression::Call {
+                    func,
+                    type_args,
+                    args,
+                    ..
+                } => {
+                    assert!(matches!(func.as_ref(), Expression::Ident(name) if name == "Some"));
+                    assert_eq!(type_args, &vec![Type::I32]);
+                    assert_eq!(args.len(), 1);
+                }
+                other => panic!("Expected a Some<i32>(..) call, got {:?}", other),
+            }
+            assert!(matches!(right.as_ref(), Expression::IntLiteral(0)));
+        }
+        other => panic!("Expected ?? expression, got {:?}", other),
+    }
+}
@@ -0,0 +1,113 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse_main_body(code: &str) -> Vec<Statement> {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    let program = parser.parse().expect("Parse failed");
+
+    match program.items.into_iter().next() {
+        Some(Item::Function(func)) => func.body.statements,
+        other => panic!("Expected function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_select_receive_send_and_default() {
+    let code = r#"
+        fn main(): i32 {
+            select {
+                case v = <-input:
+                    total = total + v;
+                case <-done:
+                    return 1;
+                case out <- 42:
+                    sent = true;
+                default:
+                    idle = true;
+            }
+            return 0;
+        }
+    "#;
+
+    let statements = parse_main_body(code);
+
+    if let Statement::Select {
+        cases,
+        default_case,
+        ..
+    } = &statements[0]
+    {
+        assert_eq!(cases.len(), 3);
+
+        assert_eq!(cases[0].var.as_deref(), Some("v"));
+        assert!(matches!(cases[0].expr, Expression::ChannelReceive(_)));
+
+        assert_eq!(cases[1].var, None);
+        assert!(matches!(cases[1].expr, Expression::ChannelReceive(_)));
+
+        assert_eq!(cases[2].var, None);
+        match &cases[2].expr {
+            Expression::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } => {
+                assert_eq!(**receiver, Expression::Ident("out".to_string()));
+                assert_eq!(method, "send");
+                assert_eq!(args, &vec![Expression::IntLiteral(42)]);
+            }
+            other => panic!("Expected send desugared to method call, got {:?}", other),
+        }
+
+        let default_case = default_case.as_ref().expect("default case missing");
+        assert_eq!(default_case.statements.len(), 1);
+    } else {
+        panic!("Expected Select, got {:?}", statements[0]);
+    }
+}
+
+#[test]
+fn test_select_let_binding_without_default() {
+    let code = r#"
+        fn main(): i32 {
+            select {
+                case let msg = <-inbox: {
+                    handle(msg);
+                }
+            }
+            return 0;
+        }
+    "#;
+
+    let statements = parse_main_body(code);
+
+    if let Statement::Select {
+        cases,
+        default_case,
+        ..
+    } = &statements[0]
+    {
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].var.as_deref(), Some("msg"));
+        assert!(default_case.is_none());
+    } else {
+        panic!("Expected Select, got {:?}", statements[0]);
+    }
+}
+
+#[test]
+fn test_select_rejects_non_channel_case() {
+    let code = r#"
+        fn main(): i32 {
+            select {
+                case x + 1:
+                    return 1;
+            }
+            return 0;
+        }
+    "#;
+
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    assert!(parser.parse().is_err());
+}
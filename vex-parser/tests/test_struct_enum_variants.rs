@@ -0,0 +1,114 @@
+use vex_ast::*;
+use vex_parser::Parser;
+
+fn parse_items(code: &str) -> Vec<Item> {
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    parser.parse().expect("Parse failed").items
+}
+
+#[test]
+fn test_struct_like_variant_declaration() {
+    let code = r#"
+        enum Shape {
+            Circle(f64),
+            Rect { x: f64, y: f64, w: f64, h: f64 },
+            Empty,
+        }
+    "#;
+
+    let shape = match parse_items(code).into_iter().next() {
+        Some(Item::Enum(e)) => e,
+        other => panic!("Expected enum, got {:?}", other),
+    };
+
+    assert_eq!(shape.variants.len(), 3);
+    assert!(!shape.variants[0].is_struct_like());
+
+    let rect = &shape.variants[1];
+    assert!(rect.is_struct_like());
+    assert_eq!(rect.field_names, vec!["x", "y", "w", "h"]);
+    assert_eq!(rect.data, vec![Type::F64; 4]);
+    assert_eq!(rect.field_index("w"), Some(2));
+
+    assert!(shape.variants[2].data.is_empty());
+}
+
+#[test]
+fn test_struct_like_variant_rejects_duplicate_field() {
+    let code = r#"
+        enum Shape {
+            Rect { w: f64, w: f64 },
+        }
+    "#;
+
+    let mut parser = Parser::new(code).expect("Parser::new failed");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_struct_like_variant_literal_and_pattern() {
+    let code = r#"
+        fn main(): i32 {
+            let s = Shape.Rect { w: 2.0, h: 3.0 };
+            match s {
+                Shape.Rect { h, w: width } => { return 1; },
+                _ => { return 0; },
+            }
+        }
+    "#;
+
+    let func = match parse_items(code).into_iter().next() {
+        Some(Item::Function(func)) => func,
+        other => panic!("Expected function, got {:?}", other),
+    };
+
+    match &func.body.statements[0] {
+        Statement::Let {
+            value:
+                Expression::EnumLiteral {
+                    enum_name,
+                    variant,
+                    data,
+                    field_names,
+                },
+            ..
+        } => {
+            assert_eq!(enum_name, "Shape");
+            assert_eq!(variant, "Rect");
+            assert_eq!(field_names, &vec!["w".to_string(), "h".to_string()]);
+            assert!(matches!(data[0], Expression::FloatLiteral(v) if v == 2.0));
+        }
+        other => panic!("Expected enum literal, got {:?}", other),
+    }
+
+    let arms = func
+        .body
+        .statements
+        .iter()
+        .find_map(|stmt| match stmt {
+            Statement::Expression(Expression::Match { arms, .. }) => Some(arms),
+            _ => None,
+        })
+        .expect("Expected a match statement");
+
+    match &arms[0].pattern {
+        Pattern::Enum {
+            name,
+            variant,
+            data,
+            field_names,
+        } => {
+            assert_eq!(name, "Shape");
+            assert_eq!(variant, "Rect");
+            assert_eq!(field_names, &vec!["h".to_string(), "w".to_string()]);
+            assert_eq!(
+                data,
+                &vec![
+                    Pattern::Ident("h".to_string()),
+                    Pattern::Ident("width".to_string())
+                ]
+            );
+        }
+        other => panic!("Expected enum pattern, got {:?}", other),
+    }
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::parse_function;
+use vex_ast::*;
+
+#[test]
+fn test_is_expression() {
+    let func = parse_function(
+        r#"
+        fn describe(v: i32 | string): bool {
+            return v is i32 || v is string;
+        }
+    "#,
+    );
+
+    assert_eq!(
+        func.params[0].ty,
+        Type::Union(vec![Type::I32, Type::String])
+    );
+
+    let value = match &func.body.statements[0] {
+        Statement::Return {
+            value: Some(value), ..
+        } => value,
+        other => panic!("Expected return, got {:?}", other),
+    };
+
+    match value {
+        Expression::Binary {
+            left, op, right, ..
+        } => {
+            assert_eq!(*op, BinaryOp::Or);
+            assert!(matches!(
+                left.as_ref(),
+                Expression::Is { expr, target_type: Type::I32 }
+                    if matches!(expr.as_ref(), Expression::Ident(name) if name == "v")
+            ));
+            assert!(matches!(
+                right.as_ref(),
+                Expression::Is {
+                    target_type: Type::String,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected binary expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_is_binds_tighter_than_comparison() {
+    let func = parse_function(
+        r#"
+        fn check(v: i32 | bool): bool {
+            return v is bool == true;
+        }
+    "#,
+    );
+
+    match &func.body.statements[0] {
+        Statement::Return {
+            value: Some(Expression::Binary { left, op, .. }),
+            ..
+        } => {
+            assert_eq!(*op, BinaryOp::Eq);
+            assert!(matches!(left.as_ref(), Expression::Is { .. }));
+        }
+        other => panic!("Expected comparison, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_type_patterns() {
+    let func = parse_function(
+        r#"
+        fn size(v: i32 | string | bool): i32 {
+            return match v {
+                n: i32 => n,
+                s: string => 1,
+                _: bool => 0,
+            };
+        }
+    "#,
+    );
+
+    let arms = match &func.body.statements[0] {
+        Statement::Return {
+            value: Some(Expression::Match { arms, .. }),
+            ..
+        } => arms,
+        other => panic!("Expected match, got {:?}", other),
+    };
+
+    assert_eq!(
+        arms[0].pattern,
+        Pattern::Type {
+            binding: Some("n".to_string()),
+            ty: Type::I32,
+        }
+    );
+    assert_eq!(
+        arms[1].pattern,
+        Pattern::Type {
+            binding: Some("s".to_string()),
+            ty: Type::String,
+        }
+    );
+    assert_eq!(
+        arms[2].pattern,
+        Pattern::Type {
+            binding: None,
+            ty: Type::Bool,
+        }
+    );
+}
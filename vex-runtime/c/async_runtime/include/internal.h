@@ -51,6 +51,7 @@ struct Runtime
 #else
     void *poller_thread;
 #endif
+    void *driver_thread; // Set by runtime_run_background, joined by runtime_join
     _Atomic(bool) tracing;
     _Atomic(int) pending_io_count;
 };
@@ -31,6 +31,11 @@ extern "C"
     void runtime_run(Runtime *runtime);
     void runtime_shutdown(Runtime *runtime);
 
+    // Run the scheduler on a driver thread so the caller keeps executing,
+    // then block until it drains (used by `go` from a synchronous main)
+    void runtime_run_background(Runtime *runtime);
+    void runtime_join(Runtime *runtime);
+
     // Timing (monotonic clock)
     uint64_t rt_now_ns(void);
 
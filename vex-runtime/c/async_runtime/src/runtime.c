@@ -19,6 +19,12 @@ static void *worker_main(void *arg);
 static void *poller_main(void *arg);
 #endif
 
+#ifdef _WIN32
+static unsigned __stdcall driver_main(void *arg);
+#else
+static void *driver_main(void *arg);
+#endif
+
 // === Pro extension: Runtime counters ===
 typedef struct
 {
@@ -84,6 +90,7 @@ Runtime *runtime_create(int num_workers)
     rt->timer_heap = timer_heap_create(64); // Initial capacity for timers
     rt->poller = poller_create();
     rt->poller_thread = NULL;
+    rt->driver_thread = NULL;
     atomic_store(&rt->tracing, false);
     atomic_store(&rt->pending_io_count, 0);
 
@@ -405,6 +412,47 @@ void runtime_run(Runtime *rt)
 #endif
 }
 
+#ifndef _WIN32
+static void *driver_main(void *arg)
+{
+    runtime_run((Runtime *)arg);
+    return NULL;
+}
+#else
+static unsigned __stdcall driver_main(void *arg)
+{
+    runtime_run((Runtime *)arg);
+    return 0;
+}
+#endif
+
+void runtime_run_background(Runtime *rt)
+{
+    if (!rt || rt->driver_thread)
+        return;
+#ifdef _WIN32
+    unsigned tid;
+    rt->driver_thread = (void *)_beginthreadex(NULL, 0, driver_main, rt, 0, &tid);
+#else
+    pthread_t th;
+    pthread_create(&th, NULL, driver_main, rt);
+    rt->driver_thread = (void *)th;
+#endif
+}
+
+void runtime_join(Runtime *rt)
+{
+    if (!rt || !rt->driver_thread)
+        return;
+#ifdef _WIN32
+    WaitForSingleObject((HANDLE)rt->driver_thread, INFINITE);
+    CloseHandle((HANDLE)rt->driver_thread);
+#else
+    pthread_join((pthread_t)rt->driver_thread, NULL);
+#endif
+    rt->driver_thread = NULL;
+}
+
 #ifndef _WIN32
 static void *worker_main(void *arg)
 {
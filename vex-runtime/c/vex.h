@@ -48,6 +48,7 @@ extern "C"
     int64_t start;
     int64_t end;
     int64_t current; // Iterator state
+    int64_t step;    // Stride; negative after rev()
   } VexRange;
 
   /**
@@ -58,6 +59,7 @@ extern "C"
     int64_t start;
     int64_t end;
     int64_t current; // Iterator state
+    int64_t step;    // Stride; negative after rev()
   } VexRangeInclusive;
 
   /**
@@ -711,6 +713,34 @@ extern "C"
    */
   void vex_assert(bool cond, const char *msg);
 
+  /**
+   * Error value created by `error.new(msg)`
+   */
+  typedef struct VexError
+  {
+    char *message;
+  } VexError;
+
+  /**
+   * Create an error value (copies the message)
+   * @param message Error message
+   * @return Heap-allocated error, released with vex_error_free
+   */
+  VexError *vex_error_new(const char *message);
+
+  /**
+   * Get the message of an error value
+   * @param err Error value (may be NULL)
+   * @return Error message ("" for NULL)
+   */
+  const char *vex_error_message(const VexError *err);
+
+  /**
+   * Free an error value and its message
+   * @param err Error value (may be NULL)
+   */
+  void vex_error_free(VexError *err);
+
   // ============================================================================
   // FILE I/O
   // ============================================================================
@@ -1724,6 +1754,48 @@ extern "C"
    */
   int64_t vex_range_inclusive_len(const VexRangeInclusive *range);
 
+  /**
+   * Check whether a value lies within the bounds of a Range
+   * @param range Range to test
+   * @param value Value to look for
+   * @return true if start <= value < end
+   */
+  bool vex_range_contains(const VexRange *range, int64_t value);
+
+  /**
+   * Check whether a value lies within the bounds of a RangeInclusive
+   * @param range RangeInclusive to test
+   * @param value Value to look for
+   * @return true if start <= value <= end
+   */
+  bool vex_range_inclusive_contains(const VexRangeInclusive *range, int64_t value);
+
+  /**
+   * Reverse the iteration order of a Range in place
+   * @param range Range to reverse; iteration restarts from its last remaining value
+   */
+  void vex_range_rev(VexRange *range);
+
+  /**
+   * Reverse the iteration order of a RangeInclusive in place
+   * @param range RangeInclusive to reverse
+   */
+  void vex_range_inclusive_rev(VexRangeInclusive *range);
+
+  /**
+   * Set the stride of a Range, keeping its direction
+   * @param range Range to modify
+   * @param step Distance between yielded values (panics unless > 0)
+   */
+  void vex_range_step_by(VexRange *range, int64_t step);
+
+  /**
+   * Set the stride of a RangeInclusive, keeping its direction
+   * @param range RangeInclusive to modify
+   * @param step Distance between yielded values (panics unless > 0)
+   */
+  void vex_range_inclusive_step_by(VexRangeInclusive *range, int64_t step);
+
   // ============================================================================
   // SLICE OPERATIONS
   // ============================================================================
@@ -2,7 +2,18 @@
 // Async runtime integration functions for Vex language
 
 #include "async_runtime/include/runtime.h"
+#include <stdatomic.h>
+#include <stdbool.h>
+#include <stddef.h>
 #include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#ifdef _WIN32
+#include <windows.h>
+#else
+#include <sched.h>
+#endif
 
 // async_sleep(millis: i64) - Sleep for specified milliseconds
 // Must be called from within an async context (WorkerContext)
@@ -14,3 +25,86 @@ void vex_async_sleep(WorkerContext *ctx, uint64_t millis)
     
     worker_await_after(ctx, millis);
 }
+
+// ============================================================================
+// Future<T>: result slot shared between an async function and its awaiter
+// ============================================================================
+//
+// The async wrapper allocates the future before spawning the coroutine; the
+// coroutine writes its T into vex_future_result() and then publishes it with
+// vex_future_complete(). Awaiters poll vex_future_is_ready() (inside async code)
+// or block in vex_future_wait() (from synchronous code) before reading the slot.
+//
+// The future has two owners: the coroutine, which lets go of it in
+// vex_future_complete(), and the holder, which calls vex_future_free() after
+// awaiting it or when dropping it unawaited. Whichever comes last frees it.
+
+typedef struct VexFuture
+{
+    _Atomic(int) ready;
+    _Atomic(int) refs; // coroutine + holder
+    uint64_t size;
+    max_align_t result[];
+} VexFuture;
+
+// Allocate a pending future with room for a `size`-byte result (0 for no value)
+VexFuture *vex_future_new(uint64_t size)
+{
+    VexFuture *future = (VexFuture *)calloc(1, sizeof(VexFuture) + (size_t)size);
+    if (!future) {
+        fprintf(stderr, "vex_future_new: out of memory\n");
+        abort();
+    }
+    atomic_init(&future->ready, 0);
+    atomic_init(&future->refs, 2);
+    future->size = size;
+    return future;
+}
+
+// Storage for the result value; written before completion, read after readiness
+void *vex_future_result(VexFuture *future)
+{
+    return future ? (void *)future->result : NULL;
+}
+
+static void vex_future_release(VexFuture *future)
+{
+    if (future && atomic_fetch_sub_explicit(&future->refs, 1, memory_order_acq_rel) == 1) {
+        free(future);
+    }
+}
+
+// Publish the result: everything stored into the slot happens-before any reader.
+// The coroutine must not touch the future afterwards.
+void vex_future_complete(VexFuture *future)
+{
+    if (future) {
+        atomic_store_explicit(&future->ready, 1, memory_order_release);
+        vex_future_release(future);
+    }
+}
+
+bool vex_future_is_ready(VexFuture *future)
+{
+    // A missing future has nothing to wait for
+    return !future || atomic_load_explicit(&future->ready, memory_order_acquire) != 0;
+}
+
+// Block the calling (non-worker) thread until the future completes
+void vex_future_wait(VexFuture *future)
+{
+    while (!vex_future_is_ready(future)) {
+#ifdef _WIN32
+        SwitchToThread();
+#else
+        sched_yield();
+#endif
+    }
+}
+
+// Drop the holder's reference, awaited or not; a pending future lives on until
+// its coroutine completes
+void vex_future_free(VexFuture *future)
+{
+    vex_future_release(future);
+}
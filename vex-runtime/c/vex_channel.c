@@ -1,5 +1,6 @@
 #include "vex_channel.h"
 #include "vex.h"
+#include <pthread.h>
 #include <stdint.h>
 #include <string.h>
 
@@ -9,6 +10,40 @@ static bool is_power_of_two(size_t n)
   return (n > 0) && ((n & (n - 1)) == 0);
 }
 
+// Event count shared by all channels: every send, receive and close bumps the
+// epoch, and a blocking select that found nothing ready parks until it moves.
+static _Atomic(uint64_t) channel_epoch = 0;
+static atomic_size_t channel_waiters = 0;
+static pthread_mutex_t channel_wait_lock = PTHREAD_MUTEX_INITIALIZER;
+static pthread_cond_t channel_wait_cond = PTHREAD_COND_INITIALIZER;
+
+// Wake parked selects after a channel changed state. Cheap when nobody waits.
+static void channel_notify(void)
+{
+  atomic_fetch_add(&channel_epoch, 1);
+  if (atomic_load(&channel_waiters) > 0)
+  {
+    pthread_mutex_lock(&channel_wait_lock);
+    pthread_cond_broadcast(&channel_wait_cond);
+    pthread_mutex_unlock(&channel_wait_lock);
+  }
+}
+
+// Park until some channel changed state after `seen` was read.
+// The waiter count is published before the epoch is re-checked under the lock,
+// so a notify either sees the waiter and broadcasts or bumped the epoch first.
+static void channel_wait(uint64_t seen)
+{
+  atomic_fetch_add(&channel_waiters, 1);
+  pthread_mutex_lock(&channel_wait_lock);
+  while (atomic_load(&channel_epoch) == seen)
+  {
+    pthread_cond_wait(&channel_wait_cond, &channel_wait_lock);
+  }
+  pthread_mutex_unlock(&channel_wait_lock);
+  atomic_fetch_sub(&channel_waiters, 1);
+}
+
 vex_channel_t *vex_channel_create(size_t capacity)
 {
   if (!is_power_of_two(capacity))
@@ -74,6 +109,7 @@ vex_channel_status_t vex_channel_send(vex_channel_t *chan, void *data)
       atomic_store_explicit(&slot->data, data, memory_order_relaxed);
       atomic_store_explicit(&slot->turn, tail + 1, memory_order_release);
       atomic_fetch_add_explicit(&chan->tail, 1, memory_order_relaxed); // Use fetch_add for producers
+      channel_notify();
       return VEX_CHANNEL_OK;
     }
 
@@ -108,6 +144,7 @@ vex_channel_status_t vex_channel_recv(vex_channel_t *chan, void **data_out)
       *data_out = atomic_load_explicit(&slot->data, memory_order_relaxed);
       atomic_store_explicit(&slot->turn, head + chan->capacity, memory_order_release);
       atomic_fetch_add_explicit(&chan->head, 1, memory_order_relaxed); // Use fetch_add for the single consumer
+      channel_notify();
       return VEX_CHANNEL_OK;
     }
 
@@ -132,6 +169,7 @@ void vex_channel_close(vex_channel_t *chan)
   if (chan)
   {
     atomic_store_explicit(&chan->closed, true, memory_order_release);
+    channel_notify();
   }
 }
 
@@ -157,6 +195,7 @@ vex_channel_status_t vex_channel_try_recv(vex_channel_t *chan, void **data_out)
     *data_out = atomic_load_explicit(&slot->data, memory_order_relaxed);
     atomic_store_explicit(&slot->turn, head + chan->capacity, memory_order_release);
     atomic_store_explicit(&chan->head, head + 1, memory_order_relaxed);
+    channel_notify();
     return VEX_CHANNEL_OK;
   }
 
@@ -172,3 +211,142 @@ vex_channel_status_t vex_channel_try_recv(vex_channel_t *chan, void **data_out)
 
   return VEX_CHANNEL_EMPTY;
 }
+
+vex_channel_status_t vex_channel_try_send(vex_channel_t *chan, void *data)
+{
+  if (!chan)
+    return VEX_CHANNEL_INVALID;
+
+  size_t tail = atomic_load_explicit(&chan->tail, memory_order_relaxed);
+
+  while (true)
+  {
+    if (atomic_load_explicit(&chan->closed, memory_order_acquire))
+    {
+      return VEX_CHANNEL_CLOSED;
+    }
+
+    vex_channel_slot_t *slot = &chan->buffer[tail & chan->mask];
+    size_t turn = atomic_load_explicit(&slot->turn, memory_order_acquire);
+
+    if (turn == tail)
+    {
+      // Claim the slot; another producer may race us for the same tail.
+      if (atomic_compare_exchange_weak_explicit(&chan->tail, &tail, tail + 1,
+                                                memory_order_relaxed, memory_order_relaxed))
+      {
+        atomic_store_explicit(&slot->data, data, memory_order_relaxed);
+        atomic_store_explicit(&slot->turn, tail + 1, memory_order_release);
+        channel_notify();
+        return VEX_CHANNEL_OK;
+      }
+      // CAS failure reloaded `tail`; retry with the new value.
+      continue;
+    }
+
+    if (turn < tail + 1)
+    {
+      // Slot still holds an unread value from the previous lap: channel is full.
+      return VEX_CHANNEL_FULL;
+    }
+
+    tail = atomic_load_explicit(&chan->tail, memory_order_relaxed);
+  }
+}
+
+// Small per-thread xorshift generator used to shuffle select polling order.
+static uint64_t select_next_random(void)
+{
+  static _Thread_local uint64_t state = 0;
+  if (state == 0)
+  {
+    state = (uint64_t)(uintptr_t)&state ^ 0x9E3779B97F4A7C15ULL;
+  }
+  state ^= state << 13;
+  state ^= state >> 7;
+  state ^= state << 17;
+  return state;
+}
+
+// Try to complete a single case without blocking. Returns true if it completed.
+static bool select_try_case(vex_select_case_t *c)
+{
+  if (!c->chan)
+  {
+    return false; // nil channel: never ready (Go semantics)
+  }
+
+  if (c->op == VEX_SELECT_SEND)
+  {
+    vex_channel_status_t status = vex_channel_try_send(c->chan, c->data);
+    if (status == VEX_CHANNEL_CLOSED)
+    {
+      vex_panic("send on closed channel in select");
+    }
+    return status == VEX_CHANNEL_OK;
+  }
+
+  void *received = NULL;
+  vex_channel_status_t status = vex_channel_try_recv(c->chan, &received);
+  if (status == VEX_CHANNEL_OK)
+  {
+    c->data = received;
+    return true;
+  }
+  if (status == VEX_CHANNEL_CLOSED)
+  {
+    c->data = NULL;
+    return true;
+  }
+  return false;
+}
+
+int64_t vex_channel_select(vex_select_case_t *cases, size_t count, bool blocking)
+{
+  if (!cases || count > VEX_SELECT_MAX_CASES)
+  {
+    vex_panic("invalid select: too many cases");
+  }
+
+  size_t order[VEX_SELECT_MAX_CASES];
+
+  while (true)
+  {
+    // Read before polling: a change that lands after this is never missed
+    uint64_t seen = atomic_load(&channel_epoch);
+
+    // Fisher-Yates shuffle so simultaneously-ready cases are chosen fairly.
+    for (size_t i = 0; i < count; ++i)
+    {
+      order[i] = i;
+    }
+    for (size_t i = count; i > 1; --i)
+    {
+      size_t j = (size_t)(select_next_random() % i);
+      size_t tmp = order[i - 1];
+      order[i - 1] = order[j];
+      order[j] = tmp;
+    }
+
+    for (size_t i = 0; i < count; ++i)
+    {
+      if (select_try_case(&cases[order[i]]))
+      {
+        return (int64_t)order[i];
+      }
+    }
+
+    if (!blocking)
+    {
+      return VEX_SELECT_DEFAULT;
+    }
+
+    if (count == 0)
+    {
+      vex_panic("select with no cases blocks forever");
+    }
+
+    // Nothing ready: sleep until a send, receive or close on any channel.
+    channel_wait(seen);
+  }
+}
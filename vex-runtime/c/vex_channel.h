@@ -5,6 +5,7 @@
 #include <stdalign.h>
 #include <stdbool.h>
 #include <stddef.h>
+#include <stdint.h>
 
 // Represents the status of a channel operation
 typedef enum
@@ -41,6 +42,27 @@ typedef struct
 
 } vex_channel_t;
 
+// Operation performed by a single `select` case.
+typedef enum
+{
+  VEX_SELECT_RECV = 0, // case v = <-ch
+  VEX_SELECT_SEND = 1, // case ch <- v
+} vex_select_op_t;
+
+// One arm of a `select` statement, laid out as { ptr, i32, ptr } for codegen.
+typedef struct
+{
+  vex_channel_t *chan;
+  int32_t op;  // vex_select_op_t
+  void *data;  // SEND: value pointer to send. RECV: receives the value pointer (NULL if closed).
+} vex_select_case_t;
+
+// Upper bound on the number of cases in a single select statement.
+#define VEX_SELECT_MAX_CASES 64
+
+// Returned by vex_channel_select when no case was ready and the call was non-blocking.
+#define VEX_SELECT_DEFAULT (-1)
+
 /**
  * @brief Creates a new channel with a given capacity.
  *
@@ -90,6 +112,37 @@ vex_channel_status_t vex_channel_recv(vex_channel_t *chan, void **data_out);
  */
 vex_channel_status_t vex_channel_try_recv(vex_channel_t *chan, void **data_out);
 
+/**
+ * @brief Attempts to send a value into the channel without blocking.
+ *
+ * @param chan The channel.
+ * @param data The data pointer to send.
+ * @return VEX_CHANNEL_OK if the value was enqueued.
+ *         VEX_CHANNEL_FULL if there is no free slot right now.
+ *         VEX_CHANNEL_CLOSED if the channel was closed.
+ */
+vex_channel_status_t vex_channel_try_send(vex_channel_t *chan, void *data);
+
+/**
+ * @brief Waits on several channel operations and completes exactly one of them.
+ *
+ * Ready cases are polled in a freshly shuffled order on every attempt, so when
+ * several cases are ready at once each one is equally likely to be chosen.
+ * A receive on a closed, drained channel is ready and stores NULL into `data`.
+ * A send on a closed channel panics, matching Go semantics.
+ * A blocking select with nothing ready parks the calling thread until some
+ * channel is sent to, received from or closed, then polls again. Async
+ * functions never block here: they poll with blocking = false and yield to
+ * their worker between polls. A blocking call from a plain function running as
+ * a `go` task holds its worker thread until a case is ready.
+ *
+ * @param cases Array of select cases (at most VEX_SELECT_MAX_CASES).
+ * @param count Number of cases.
+ * @param blocking If false, return VEX_SELECT_DEFAULT when no case is ready.
+ * @return Index of the completed case, or VEX_SELECT_DEFAULT.
+ */
+int64_t vex_channel_select(vex_select_case_t *cases, size_t count, bool blocking);
+
 /**
  * @brief Closes the channel, preventing further sends.
  *
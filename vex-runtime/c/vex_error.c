@@ -24,3 +24,29 @@ void vex_assert(bool cond, const char *msg)
         vex_panic(msg);
     }
 }
+
+// ============================================================================
+// ERROR VALUES
+// ============================================================================
+
+VexError *vex_error_new(const char *message)
+{
+    VexError *err = (VexError *)vex_malloc(sizeof(VexError));
+    err->message = vex_strdup(message ? message : "");
+    return err;
+}
+
+const char *vex_error_message(const VexError *err)
+{
+    return err ? err->message : "";
+}
+
+void vex_error_free(VexError *err)
+{
+    if (!err)
+    {
+        return;
+    }
+    vex_free(err->message);
+    vex_free(err);
+}
@@ -4,6 +4,57 @@
 #include <stdio.h>
 #include <stdlib.h>
 
+/*
+ * Range and RangeInclusive share one layout. Iteration starts at `current`
+ * and moves by `step`: towards `end` while step > 0, and back down to `start`
+ * once the range has been reversed (step < 0).
+ */
+
+static bool range_has_next(int64_t start, int64_t end, int64_t current, int64_t step,
+                           bool inclusive)
+{
+  if (step < 0)
+    return current >= start;
+  return inclusive ? current <= end : current < end;
+}
+
+static int64_t range_remaining(int64_t start, int64_t end, int64_t current, int64_t step,
+                               bool inclusive)
+{
+  if (!range_has_next(start, end, current, step, inclusive))
+    return 0;
+  if (step < 0)
+    return (current - start) / -step + 1;
+  int64_t last = inclusive ? end : end - 1;
+  return (last - current) / step + 1;
+}
+
+static void range_rev(int64_t start, int64_t end, int64_t *current, int64_t *step,
+                      bool inclusive)
+{
+  int64_t remaining = range_remaining(start, end, *current, *step, inclusive);
+  if (remaining == 0)
+  {
+    // Exhausted: park the cursor outside the bounds of the new direction
+    *current = *step > 0 ? start - 1 : (inclusive ? end + 1 : end);
+  }
+  else
+  {
+    // Restart from the last value the old direction would have produced
+    *current += (remaining - 1) * *step;
+  }
+  *step = -*step;
+}
+
+static void range_step_by(int64_t *step, int64_t new_step)
+{
+  if (new_step <= 0)
+  {
+    vex_panic("Range.step_by() requires a positive step");
+  }
+  *step = *step < 0 ? -new_step : new_step;
+}
+
 /**
  * Create a Range (exclusive end): 0..10
  * @param start Start value (inclusive)
@@ -16,6 +67,7 @@ VexRange vex_range_new(int64_t start, int64_t end)
   range.start = start;
   range.end = end;
   range.current = start;
+  range.step = 1;
   return range;
 }
 
@@ -31,6 +83,7 @@ VexRangeInclusive vex_range_inclusive_new(int64_t start, int64_t end)
   range.start = start;
   range.end = end;
   range.current = start;
+  range.step = 1;
   return range;
 }
 
@@ -42,10 +95,10 @@ VexRangeInclusive vex_range_inclusive_new(int64_t start, int64_t end)
  */
 bool vex_range_next(VexRange *range, int64_t *out_value)
 {
-  if (range->current < range->end)
+  if (range_has_next(range->start, range->end, range->current, range->step, false))
   {
     *out_value = range->current;
-    range->current++;
+    range->current += range->step;
     return true;
   }
   return false;
@@ -59,10 +112,10 @@ bool vex_range_next(VexRange *range, int64_t *out_value)
  */
 bool vex_range_inclusive_next(VexRangeInclusive *range, int64_t *out_value)
 {
-  if (range->current <= range->end)
+  if (range_has_next(range->start, range->end, range->current, range->step, true))
   {
     *out_value = range->current;
-    range->current++;
+    range->current += range->step;
     return true;
   }
   return false;
@@ -71,21 +124,80 @@ bool vex_range_inclusive_next(VexRangeInclusive *range, int64_t *out_value)
 /**
  * Get length of Range
  * @param range Range to measure
- * @return Number of elements (end - start, clamped to 0)
+ * @return Number of values left to yield (end - start for a fresh range)
  */
 int64_t vex_range_len(const VexRange *range)
 {
-  int64_t len = range->end - range->start;
-  return len > 0 ? len : 0;
+  return range_remaining(range->start, range->end, range->current, range->step, false);
 }
 
 /**
  * Get length of RangeInclusive
  * @param range RangeInclusive to measure
- * @return Number of elements (end - start + 1, clamped to 0)
+ * @return Number of values left to yield (end - start + 1 for a fresh range)
  */
 int64_t vex_range_inclusive_len(const VexRangeInclusive *range)
 {
-  int64_t len = range->end - range->start + 1;
-  return len > 0 ? len : 0;
+  return range_remaining(range->start, range->end, range->current, range->step, true);
+}
+
+/**
+ * Check whether a value lies within the bounds of a Range
+ * @param range Range to test
+ * @param value Value to look for
+ * @return true if start <= value < end
+ */
+bool vex_range_contains(const VexRange *range, int64_t value)
+{
+  return range->start <= value && value < range->end;
+}
+
+/**
+ * Check whether a value lies within the bounds of a RangeInclusive
+ * @param range RangeInclusive to test
+ * @param value Value to look for
+ * @return true if start <= value <= end
+ */
+bool vex_range_inclusive_contains(const VexRangeInclusive *range, int64_t value)
+{
+  return range->start <= value && value <= range->end;
+}
+
+/**
+ * Reverse the iteration order of a Range in place: (0..10).step_by(3).rev()
+ * yields 9, 6, 3, 0
+ * @param range Range to reverse
+ */
+void vex_range_rev(VexRange *range)
+{
+  range_rev(range->start, range->end, &range->current, &range->step, false);
+}
+
+/**
+ * Reverse the iteration order of a RangeInclusive in place
+ * @param range RangeInclusive to reverse
+ */
+void vex_range_inclusive_rev(VexRangeInclusive *range)
+{
+  range_rev(range->start, range->end, &range->current, &range->step, true);
+}
+
+/**
+ * Set the stride of a Range, keeping its direction
+ * @param range Range to modify
+ * @param step Distance between yielded values (panics unless > 0)
+ */
+void vex_range_step_by(VexRange *range, int64_t step)
+{
+  range_step_by(&range->step, step);
+}
+
+/**
+ * Set the stride of a RangeInclusive, keeping its direction
+ * @param range RangeInclusive to modify
+ * @param step Distance between yielded values (panics unless > 0)
+ */
+void vex_range_inclusive_step_by(VexRangeInclusive *range, int64_t step)
+{
+  range_step_by(&range->step, step);
 }
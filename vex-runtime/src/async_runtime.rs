@@ -42,6 +42,12 @@ pub enum EventType {
     Writable = 2,
 }
 
+/// Opaque Future<T> handle (result slot written by an async function)
+#[repr(C)]
+pub struct VexFuture {
+    _private: [u8; 0],
+}
+
 /// Runtime statistics
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
@@ -76,6 +82,12 @@ extern "C" {
     /// Run runtime (blocks until shutdown)
     pub fn runtime_run(runtime: *mut Runtime);
 
+    /// Run runtime on a background driver thread (returns immediately)
+    pub fn runtime_run_background(runtime: *mut Runtime);
+
+    /// Block until a background runtime has shut down
+    pub fn runtime_join(runtime: *mut Runtime);
+
     /// Request shutdown
     pub fn runtime_shutdown(runtime: *mut Runtime);
 
@@ -112,6 +124,24 @@ extern "C" {
 
     /// Request cancellation
     pub fn cancel_request(token: *mut CancelToken);
+
+    /// Allocate a pending future with room for a `size`-byte result
+    pub fn vex_future_new(size: u64) -> *mut VexFuture;
+
+    /// Result storage of a future
+    pub fn vex_future_result(future: *mut VexFuture) -> *mut c_void;
+
+    /// Publish the result written into the future
+    pub fn vex_future_complete(future: *mut VexFuture);
+
+    /// Check whether the future has completed
+    pub fn vex_future_is_ready(future: *mut VexFuture) -> bool;
+
+    /// Block the calling thread until the future completes
+    pub fn vex_future_wait(future: *mut VexFuture);
+
+    /// Release the holder's reference to a future, awaited or not
+    pub fn vex_future_free(future: *mut VexFuture);
 }
 
 /// Safe Rust wrapper for Runtime
@@ -225,4 +255,23 @@ mod tests {
         let stats = rt.stats();
         assert_eq!(stats.tasks_spawned, 0);
     }
+
+    #[test]
+    fn test_future_outlives_whichever_owner_lets_go_first() {
+        unsafe {
+            // Awaited: the coroutine completes before the holder frees it
+            let awaited = vex_future_new(8);
+            *(vex_future_result(awaited) as *mut i64) = 42;
+            vex_future_complete(awaited);
+            assert!(vex_future_is_ready(awaited));
+            assert_eq!(*(vex_future_result(awaited) as *const i64), 42);
+            vex_future_free(awaited);
+
+            // Dropped unawaited: the pending coroutine still writes its result
+            let dropped = vex_future_new(8);
+            vex_future_free(dropped);
+            *(vex_future_result(dropped) as *mut i64) = 7;
+            vex_future_complete(dropped);
+        }
+    }
 }